@@ -0,0 +1,298 @@
+use super::config::*;
+use super::{prompts, AIEnhancementRequest, AIEnhancementResponse, AIError, AIProvider};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
+pub const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+// Anthropic requires max_tokens on every request
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+const SYSTEM_PROMPT: &str =
+    "You are a careful text formatter that only returns the cleaned text per the provided rules.";
+
+pub struct AnthropicProvider {
+    api_key: String,
+    model: String,
+    client: Client,
+    base_url: String,
+    options: HashMap<String, serde_json::Value>,
+}
+
+impl AnthropicProvider {
+    pub fn new(
+        api_key: String,
+        model: String,
+        options: HashMap<String, serde_json::Value>,
+    ) -> Result<Self, AIError> {
+        // Model ids change often; only require the Claude family prefix
+        if !model.starts_with("claude-") {
+            return Err(AIError::ValidationError(format!(
+                "Unsupported model: {}",
+                model
+            )));
+        }
+
+        // Validate API key format (basic check)
+        if api_key.trim().is_empty() || api_key.len() < MIN_API_KEY_LENGTH {
+            return Err(AIError::ValidationError(
+                "Invalid API key format".to_string(),
+            ));
+        }
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| AIError::NetworkError(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            api_key,
+            model,
+            client,
+            base_url: ANTHROPIC_API_URL.to_string(),
+            options,
+        })
+    }
+
+    fn build_request(&self, prompt: String) -> AnthropicRequest {
+        let temperature = self
+            .options
+            .get("temperature")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .unwrap_or(DEFAULT_TEMPERATURE);
+
+        let max_tokens = self
+            .options
+            .get("max_tokens")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+            .unwrap_or(DEFAULT_MAX_TOKENS);
+
+        AnthropicRequest {
+            model: self.model.clone(),
+            max_tokens,
+            system: SYSTEM_PROMPT.to_string(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: prompt,
+            }],
+            // Anthropic accepts temperature in [0, 1]
+            temperature: Some(temperature.clamp(0.0, 1.0)),
+        }
+    }
+
+    async fn make_request_with_retry(
+        &self,
+        request: &AnthropicRequest,
+    ) -> Result<AnthropicResponse, AIError> {
+        let mut last_error = None;
+
+        for attempt in 1..=MAX_RETRIES {
+            match self.make_single_request(request).await {
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    log::warn!("API request attempt {} failed: {}", attempt, e);
+                    last_error = Some(e);
+
+                    if attempt < MAX_RETRIES {
+                        tokio::time::sleep(Duration::from_millis(
+                            RETRY_BASE_DELAY_MS * attempt as u64,
+                        ))
+                        .await;
+                    }
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| AIError::NetworkError("Unknown error".to_string())))
+    }
+
+    async fn make_single_request(
+        &self,
+        request: &AnthropicRequest,
+    ) -> Result<AnthropicResponse, AIError> {
+        let response = self
+            .client
+            .post(&self.base_url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .header("Content-Type", "application/json")
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        let status = response.status();
+
+        // Handle rate limiting
+        if status.as_u16() == 429 {
+            return Err(AIError::RateLimitExceeded);
+        }
+
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AIError::ApiError(format!(
+                "API returned {}: {}",
+                status, error_text
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AIError::InvalidResponse(e.to_string()))
+    }
+}
+
+#[derive(Serialize)]
+struct AnthropicRequest {
+    model: String,
+    max_tokens: u32,
+    system: String,
+    messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct AnthropicResponse {
+    content: Vec<ContentBlock>,
+}
+
+#[derive(Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: String,
+}
+
+#[async_trait]
+impl AIProvider for AnthropicProvider {
+    async fn enhance_text(
+        &self,
+        request: AIEnhancementRequest,
+    ) -> Result<AIEnhancementResponse, AIError> {
+        request.validate()?;
+
+        let prompt = prompts::build_enhancement_prompt(
+            &request.text,
+            request.context.as_deref(),
+            &request.options.unwrap_or_default(),
+        );
+
+        let anthropic_request = self.build_request(prompt);
+        let anthropic_response = self.make_request_with_retry(&anthropic_request).await?;
+
+        let enhanced_text = anthropic_response
+            .content
+            .iter()
+            .find(|block| block.kind == "text")
+            .ok_or_else(|| AIError::InvalidResponse("No text content in response".to_string()))?
+            .text
+            .trim()
+            .to_string();
+
+        if enhanced_text.is_empty() {
+            return Err(AIError::InvalidResponse(
+                "Empty response from API".to_string(),
+            ));
+        }
+
+        Ok(AIEnhancementResponse {
+            enhanced_text,
+            original_text: request.text,
+            provider: self.name().to_string(),
+            model: self.model.clone(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "anthropic"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_creation() {
+        let result = AnthropicProvider::new(
+            "".to_string(),
+            "claude-3-5-haiku-latest".to_string(),
+            HashMap::new(),
+        );
+        assert!(result.is_err());
+
+        let result = AnthropicProvider::new(
+            "test_key_12345".to_string(),
+            "gpt-5-nano".to_string(),
+            HashMap::new(),
+        );
+        assert!(result.is_err());
+
+        let result = AnthropicProvider::new(
+            "test_key_12345".to_string(),
+            "claude-3-5-haiku-latest".to_string(),
+            HashMap::new(),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_request_body_construction() {
+        let mut options = HashMap::new();
+        options.insert("temperature".to_string(), serde_json::json!(1.7));
+
+        let provider = AnthropicProvider::new(
+            "test_key_12345".to_string(),
+            "claude-3-5-haiku-latest".to_string(),
+            options,
+        )
+        .unwrap();
+
+        let body = serde_json::to_value(provider.build_request("hello world".to_string())).unwrap();
+
+        assert_eq!(body["model"], "claude-3-5-haiku-latest");
+        assert_eq!(body["max_tokens"], DEFAULT_MAX_TOKENS);
+        assert_eq!(body["system"], SYSTEM_PROMPT);
+        assert_eq!(body["messages"][0]["role"], "user");
+        assert_eq!(body["messages"][0]["content"], "hello world");
+        assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+        // Clamped to Anthropic's [0, 1] range
+        assert_eq!(body["temperature"], 1.0);
+    }
+
+    #[test]
+    fn test_response_parsing_skips_non_text_blocks() {
+        let response: AnthropicResponse = serde_json::from_value(serde_json::json!({
+            "content": [
+                {"type": "thinking", "thinking": "..."},
+                {"type": "text", "text": " Cleaned text. "}
+            ]
+        }))
+        .unwrap();
+
+        let text = response
+            .content
+            .iter()
+            .find(|block| block.kind == "text")
+            .map(|block| block.text.trim());
+        assert_eq!(text, Some("Cleaned text."));
+    }
+}
@@ -53,6 +53,33 @@ impl GeminiProvider {
         })
     }
 
+    fn build_request(&self, prompt: String) -> GeminiRequest {
+        let temperature = self
+            .options
+            .get("temperature")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .unwrap_or(DEFAULT_TEMPERATURE);
+
+        let max_tokens = self
+            .options
+            .get("max_tokens")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32);
+
+        let generation_config = GenerationConfig {
+            temperature: Some(temperature.clamp(0.0, 2.0)),
+            max_output_tokens: max_tokens,
+        };
+
+        GeminiRequest {
+            contents: vec![Content {
+                parts: vec![Part { text: prompt }],
+            }],
+            generation_config: Some(generation_config),
+        }
+    }
+
     async fn make_request_with_retry(
         &self,
         request: &GeminiRequest,
@@ -171,30 +198,7 @@ impl AIProvider for GeminiProvider {
             &request.options.unwrap_or_default(),
         );
 
-        let temperature = self
-            .options
-            .get("temperature")
-            .and_then(|v| v.as_f64())
-            .map(|v| v as f32)
-            .unwrap_or(DEFAULT_TEMPERATURE);
-
-        let max_tokens = self
-            .options
-            .get("max_tokens")
-            .and_then(|v| v.as_u64())
-            .map(|v| v as u32);
-
-        let generation_config = GenerationConfig {
-            temperature: Some(temperature.clamp(0.0, 2.0)),
-            max_output_tokens: max_tokens,
-        };
-
-        let gemini_request = GeminiRequest {
-            contents: vec![Content {
-                parts: vec![Part { text: prompt }],
-            }],
-            generation_config: Some(generation_config),
-        };
+        let gemini_request = self.build_request(prompt);
 
         let gemini_response = self.make_request_with_retry(&gemini_request).await?;
 
@@ -257,4 +261,26 @@ mod tests {
         );
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_request_body_construction() {
+        let mut options = HashMap::new();
+        options.insert("max_tokens".to_string(), serde_json::json!(256));
+
+        let provider = GeminiProvider::new(
+            "test_key_12345".to_string(),
+            "gemini-2.5-flash-lite".to_string(),
+            options,
+        )
+        .unwrap();
+
+        let body = serde_json::to_value(provider.build_request("hello world".to_string())).unwrap();
+
+        assert_eq!(body["contents"][0]["parts"][0]["text"], "hello world");
+        assert_eq!(body["generation_config"]["maxOutputTokens"], 256);
+        assert_eq!(
+            body["generation_config"]["temperature"],
+            DEFAULT_TEMPERATURE as f64
+        );
+    }
 }
@@ -1,13 +1,17 @@
 use super::config::*;
-use super::{prompts, AIEnhancementRequest, AIEnhancementResponse, AIError, AIProvider};
+use super::{
+    prompts, streaming, AIEnhancementRequest, AIEnhancementResponse, AIError, AIProvider,
+    ChunkCallback,
+};
 use crate::utils::network_diagnostics::{
     log_api_request, log_api_response, log_network_error, log_network_error_with_duration,
     log_retry_attempt, NetworkError,
 };
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
 // Supported models with validation
@@ -57,6 +61,40 @@ impl GroqProvider {
         })
     }
 
+    fn build_request(&self, prompt: String, stream: bool) -> GroqRequest {
+        let temperature = self
+            .options
+            .get("temperature")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .unwrap_or(DEFAULT_TEMPERATURE);
+
+        let max_tokens = self
+            .options
+            .get("max_tokens")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32);
+
+        GroqRequest {
+            model: self.model.clone(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: prompt,
+            }],
+            temperature: Some(temperature.clamp(0.0, 2.0)), // Clamp to valid range
+            max_tokens,
+            stream,
+        }
+    }
+
+    fn post(&self, request: &GroqRequest) -> RequestBuilder {
+        self.client
+            .post(&self.base_url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(request)
+    }
+
     async fn make_request_with_retry(
         &self,
         request: &GroqRequest,
@@ -89,22 +127,14 @@ impl GroqProvider {
 
     async fn make_single_request(&self, request: &GroqRequest) -> Result<GroqResponse, AIError> {
         let request_start = Instant::now();
-        let response = self
-            .client
-            .post(&self.base_url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(request)
-            .send()
-            .await
-            .map_err(|e| {
-                let elapsed = request_start.elapsed().as_millis() as u64;
-                let error = NetworkError::Unknown {
-                    message: e.to_string(),
-                };
-                log_network_error_with_duration(error, Some(elapsed));
-                AIError::NetworkError(e.to_string())
-            })?;
+        let response = self.post(request).send().await.map_err(|e| {
+            let elapsed = request_start.elapsed().as_millis() as u64;
+            let error = NetworkError::Unknown {
+                message: e.to_string(),
+            };
+            log_network_error_with_duration(error, Some(elapsed));
+            AIError::NetworkError(e.to_string())
+        })?;
 
         let status = response.status();
         let duration_ms = request_start.elapsed().as_millis() as u64;
@@ -171,6 +201,8 @@ struct GroqRequest {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -209,28 +241,7 @@ impl AIProvider for GroqProvider {
             log_api_request("groq", &self.model, prompt.len());
         }
 
-        let temperature = self
-            .options
-            .get("temperature")
-            .and_then(|v| v.as_f64())
-            .map(|v| v as f32)
-            .unwrap_or(DEFAULT_TEMPERATURE);
-
-        let max_tokens = self
-            .options
-            .get("max_tokens")
-            .and_then(|v| v.as_u64())
-            .map(|v| v as u32);
-
-        let groq_request = GroqRequest {
-            model: self.model.clone(),
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: prompt,
-            }],
-            temperature: Some(temperature.clamp(0.0, 2.0)), // Clamp to valid range
-            max_tokens,
-        };
+        let groq_request = self.build_request(prompt, false);
 
         let groq_response = self.make_request_with_retry(&groq_request).await?;
 
@@ -258,6 +269,61 @@ impl AIProvider for GroqProvider {
         })
     }
 
+    async fn enhance_text_streaming(
+        &self,
+        request: AIEnhancementRequest,
+        on_chunk: ChunkCallback<'_>,
+    ) -> Result<AIEnhancementResponse, AIError> {
+        request.validate()?;
+
+        let prompt = prompts::build_enhancement_prompt(
+            &request.text,
+            request.context.as_deref(),
+            &request.options.clone().unwrap_or_default(),
+        );
+
+        if log::log_enabled!(log::Level::Info) {
+            log_api_request("groq", &self.model, prompt.len());
+        }
+
+        let emitted = AtomicBool::new(false);
+        let forward = |delta: &str| {
+            emitted.store(true, Ordering::Relaxed);
+            on_chunk(delta)
+        };
+
+        let groq_request = self.build_request(prompt, true);
+        let enhanced_text =
+            match streaming::stream_chat_completions(self.post(&groq_request), &forward).await {
+                Ok(text) => text.trim().to_string(),
+                Err(e) if matches!(e, AIError::Cancelled) || emitted.load(Ordering::Relaxed) => {
+                    return Err(e)
+                }
+                Err(e) => {
+                    // Nothing was streamed yet; fall back to the regular request with retries
+                    log::warn!("Streaming request failed, falling back: {}", e);
+                    let response = self.enhance_text(request).await?;
+                    if !on_chunk(&response.enhanced_text) {
+                        return Err(AIError::Cancelled);
+                    }
+                    return Ok(response);
+                }
+            };
+
+        if enhanced_text.is_empty() {
+            return Err(AIError::InvalidResponse(
+                "Empty response from API".to_string(),
+            ));
+        }
+
+        Ok(AIEnhancementResponse {
+            enhanced_text,
+            original_text: request.text,
+            provider: self.name().to_string(),
+            model: self.model.clone(),
+        })
+    }
+
     fn name(&self) -> &str {
         "groq"
     }
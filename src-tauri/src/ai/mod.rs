@@ -2,14 +2,18 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub mod anthropic;
 pub mod config;
 pub mod gemini;
 pub mod groq;
+pub mod ollama;
 pub mod openai;
 pub mod prompts;
+pub mod streaming;
 
 pub use config::MAX_TEXT_LENGTH;
 pub use prompts::EnhancementOptions;
+pub use streaming::ChunkCallback;
 
 #[cfg(test)]
 mod tests;
@@ -77,6 +81,9 @@ pub enum AIError {
 
     #[error("Rate limit exceeded")]
     RateLimitExceeded,
+
+    #[error("Request cancelled")]
+    Cancelled,
 }
 
 #[async_trait]
@@ -86,6 +93,20 @@ pub trait AIProvider: Send + Sync {
         request: AIEnhancementRequest,
     ) -> Result<AIEnhancementResponse, AIError>;
 
+    /// Enhance text, forwarding output to `on_chunk` as it arrives.
+    /// Providers without streaming support deliver the full result as a single chunk.
+    async fn enhance_text_streaming(
+        &self,
+        request: AIEnhancementRequest,
+        on_chunk: ChunkCallback<'_>,
+    ) -> Result<AIEnhancementResponse, AIError> {
+        let response = self.enhance_text(request).await?;
+        if !on_chunk(&response.enhanced_text) {
+            return Err(AIError::Cancelled);
+        }
+        Ok(response)
+    }
+
     fn name(&self) -> &str;
 }
 
@@ -121,11 +142,23 @@ impl AIProviderFactory {
                 config.model.clone(),
                 config.options.clone(),
             )?)),
+            "anthropic" => Ok(Box::new(anthropic::AnthropicProvider::new(
+                config.api_key.clone(),
+                config.model.clone(),
+                config.options.clone(),
+            )?)),
+            "ollama" => Ok(Box::new(ollama::OllamaProvider::new(
+                config.model.clone(),
+                config.options.clone(),
+            )?)),
             provider => Err(AIError::ProviderNotFound(provider.to_string())),
         }
     }
 
     fn is_valid_provider(provider: &str) -> bool {
-        matches!(provider, "groq" | "gemini" | "openai")
+        matches!(
+            provider,
+            "groq" | "gemini" | "openai" | "anthropic" | "ollama"
+        )
     }
 }
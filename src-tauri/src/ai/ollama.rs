@@ -0,0 +1,291 @@
+use super::config::*;
+use super::{prompts, AIEnhancementRequest, AIEnhancementResponse, AIError, AIModel, AIProvider};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub const DEFAULT_OLLAMA_BASE_URL: &str = "http://localhost:11434";
+
+// Local models can take a while to load on first use
+const OLLAMA_TIMEOUT_SECS: u64 = 120;
+
+pub struct OllamaProvider {
+    model: String,
+    client: Client,
+    base_url: String,
+    options: HashMap<String, serde_json::Value>,
+}
+
+impl OllamaProvider {
+    pub fn new(
+        model: String,
+        options: HashMap<String, serde_json::Value>,
+    ) -> Result<Self, AIError> {
+        if model.trim().is_empty() {
+            return Err(AIError::ValidationError(
+                "No Ollama model selected".to_string(),
+            ));
+        }
+
+        let base_url = options
+            .get("base_url")
+            .and_then(|v| v.as_str())
+            .unwrap_or(DEFAULT_OLLAMA_BASE_URL)
+            .trim_end_matches('/')
+            .to_string();
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(OLLAMA_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| AIError::NetworkError(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            model,
+            client,
+            base_url,
+            options,
+        })
+    }
+
+    fn build_request(&self, prompt: String) -> OllamaChatRequest {
+        let temperature = self
+            .options
+            .get("temperature")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .unwrap_or(DEFAULT_TEMPERATURE);
+
+        OllamaChatRequest {
+            model: self.model.clone(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: "You are a careful text formatter that only returns the cleaned text per the provided rules.".to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: prompt,
+                },
+            ],
+            stream: true,
+            options: OllamaOptions {
+                temperature: temperature.clamp(0.0, 2.0),
+            },
+        }
+    }
+}
+
+/// List models installed in the local Ollama instance via `/api/tags`.
+pub async fn list_local_models(base_url: &str) -> Result<Vec<AIModel>, AIError> {
+    let url = format!("{}/api/tags", base_url.trim_end_matches('/'));
+    let response = Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|e| AIError::NetworkError(format!("Failed to create HTTP client: {}", e)))?
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(AIError::ApiError(format!(
+            "API returned {}",
+            response.status()
+        )));
+    }
+
+    let tags: OllamaTagsResponse = response
+        .json()
+        .await
+        .map_err(|e| AIError::InvalidResponse(e.to_string()))?;
+
+    Ok(tags
+        .models
+        .into_iter()
+        .map(|m| AIModel {
+            id: m.name.clone(),
+            name: m.name,
+            description: m.details.and_then(|d| d.parameter_size),
+        })
+        .collect())
+}
+
+/// Concatenate the message content of a streamed (NDJSON) `/api/chat` response.
+fn parse_chat_stream(body: &str) -> Result<String, AIError> {
+    let mut content = String::new();
+
+    for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let chunk: OllamaChatChunk = serde_json::from_str(line)
+            .map_err(|e| AIError::InvalidResponse(format!("Invalid stream chunk: {}", e)))?;
+
+        if let Some(error) = chunk.error {
+            return Err(AIError::ApiError(error));
+        }
+        if let Some(message) = chunk.message {
+            content.push_str(&message.content);
+        }
+        if chunk.done {
+            break;
+        }
+    }
+
+    Ok(content)
+}
+
+#[derive(Serialize)]
+struct OllamaChatRequest {
+    model: String,
+    messages: Vec<Message>,
+    stream: bool,
+    options: OllamaOptions,
+}
+
+#[derive(Serialize)]
+struct OllamaOptions {
+    temperature: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct OllamaChatChunk {
+    message: Option<Message>,
+    #[serde(default)]
+    done: bool,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OllamaTagsResponse {
+    #[serde(default)]
+    models: Vec<OllamaModelTag>,
+}
+
+#[derive(Deserialize)]
+struct OllamaModelTag {
+    name: String,
+    details: Option<OllamaModelDetails>,
+}
+
+#[derive(Deserialize)]
+struct OllamaModelDetails {
+    parameter_size: Option<String>,
+}
+
+#[async_trait]
+impl AIProvider for OllamaProvider {
+    async fn enhance_text(
+        &self,
+        request: AIEnhancementRequest,
+    ) -> Result<AIEnhancementResponse, AIError> {
+        request.validate()?;
+
+        let prompt = prompts::build_enhancement_prompt(
+            &request.text,
+            request.context.as_deref(),
+            &request.options.unwrap_or_default(),
+        );
+
+        // Local server: no retries, a refused connection means Ollama isn't running
+        let response = self
+            .client
+            .post(format!("{}/api/chat", self.base_url))
+            .header("Content-Type", "application/json")
+            .json(&self.build_request(prompt))
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        if !status.is_success() {
+            return Err(AIError::ApiError(format!(
+                "API returned {}: {}",
+                status, body
+            )));
+        }
+
+        let enhanced_text = parse_chat_stream(&body)?.trim().to_string();
+
+        if enhanced_text.is_empty() {
+            return Err(AIError::InvalidResponse(
+                "Empty response from API".to_string(),
+            ));
+        }
+
+        Ok(AIEnhancementResponse {
+            enhanced_text,
+            original_text: request.text,
+            provider: self.name().to_string(),
+            model: self.model.clone(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "ollama"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_creation() {
+        assert!(OllamaProvider::new("".to_string(), HashMap::new()).is_err());
+
+        let provider = OllamaProvider::new("llama3.2".to_string(), HashMap::new()).unwrap();
+        assert_eq!(provider.base_url, DEFAULT_OLLAMA_BASE_URL);
+
+        let mut options = HashMap::new();
+        options.insert(
+            "base_url".to_string(),
+            serde_json::json!("http://127.0.0.1:8080/"),
+        );
+        let provider = OllamaProvider::new("llama3.2".to_string(), options).unwrap();
+        assert_eq!(provider.base_url, "http://127.0.0.1:8080");
+    }
+
+    #[test]
+    fn test_request_body_construction() {
+        let provider = OllamaProvider::new("llama3.2".to_string(), HashMap::new()).unwrap();
+        let body = serde_json::to_value(provider.build_request("hello".to_string())).unwrap();
+
+        assert_eq!(body["model"], "llama3.2");
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["messages"][1]["role"], "user");
+        assert_eq!(body["messages"][1]["content"], "hello");
+    }
+
+    #[test]
+    fn test_parse_chat_stream_concatenates_chunks() {
+        let body = concat!(
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"Hello\"},\"done\":false}\n",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\", world.\"},\"done\":false}\n",
+            "\n",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true,\"total_duration\":1}\n",
+        );
+        assert_eq!(parse_chat_stream(body).unwrap(), "Hello, world.");
+    }
+
+    #[test]
+    fn test_parse_chat_stream_errors() {
+        let body = "{\"error\":\"model 'missing' not found\"}\n";
+        assert!(matches!(parse_chat_stream(body), Err(AIError::ApiError(_))));
+
+        assert!(matches!(
+            parse_chat_stream("not json"),
+            Err(AIError::InvalidResponse(_))
+        ));
+    }
+}
@@ -1,9 +1,13 @@
 use super::config::*;
-use super::{prompts, AIEnhancementRequest, AIEnhancementResponse, AIError, AIProvider};
+use super::{
+    prompts, streaming, AIEnhancementRequest, AIEnhancementResponse, AIError, AIProvider,
+    ChunkCallback,
+};
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 pub struct OpenAIProvider {
@@ -65,6 +69,59 @@ impl OpenAIProvider {
         })
     }
 
+    fn build_request(&self, prompt: String, stream: bool) -> OpenAIRequest {
+        let temperature = self
+            .options
+            .get("temperature")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .unwrap_or(DEFAULT_TEMPERATURE);
+
+        let max_tokens = self
+            .options
+            .get("max_tokens")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32);
+
+        OpenAIRequest {
+            model: self.model.clone(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: "You are a careful text formatter that only returns the cleaned text per the provided rules.".to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: prompt,
+                },
+            ],
+            temperature: Some(temperature.clamp(0.0, 2.0)),
+            max_tokens,
+            stream,
+        }
+    }
+
+    fn post(&self, request: &OpenAIRequest) -> RequestBuilder {
+        // Determine if auth header should be sent
+        let no_auth = self
+            .options
+            .get("no_auth")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let req = self
+            .client
+            .post(&self.base_url)
+            .header("Content-Type", "application/json")
+            .json(request);
+
+        if no_auth {
+            req
+        } else {
+            req.header("Authorization", format!("Bearer {}", self.api_key))
+        }
+    }
+
     async fn make_request_with_retry(
         &self,
         request: &OpenAIRequest,
@@ -95,24 +152,8 @@ impl OpenAIProvider {
         &self,
         request: &OpenAIRequest,
     ) -> Result<OpenAIResponse, AIError> {
-        // Determine if auth header should be sent
-        let no_auth = self
-            .options
-            .get("no_auth")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
-
-        let mut req = self
-            .client
-            .post(&self.base_url)
-            .header("Content-Type", "application/json")
-            .json(request);
-
-        if !no_auth {
-            req = req.header("Authorization", format!("Bearer {}", self.api_key));
-        }
-
-        let response = req
+        let response = self
+            .post(request)
             .send()
             .await
             .map_err(|e| AIError::NetworkError(e.to_string()))?;
@@ -149,6 +190,8 @@ struct OpenAIRequest {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -181,34 +224,7 @@ impl AIProvider for OpenAIProvider {
             &request.options.unwrap_or_default(),
         );
 
-        let temperature = self
-            .options
-            .get("temperature")
-            .and_then(|v| v.as_f64())
-            .map(|v| v as f32)
-            .unwrap_or(DEFAULT_TEMPERATURE);
-
-        let max_tokens = self
-            .options
-            .get("max_tokens")
-            .and_then(|v| v.as_u64())
-            .map(|v| v as u32);
-
-        let request_body = OpenAIRequest {
-            model: self.model.clone(),
-            messages: vec![
-                Message {
-                    role: "system".to_string(),
-                    content: "You are a careful text formatter that only returns the cleaned text per the provided rules.".to_string(),
-                },
-                Message {
-                    role: "user".to_string(),
-                    content: prompt,
-                },
-            ],
-            temperature: Some(temperature.clamp(0.0, 2.0)),
-            max_tokens,
-        };
+        let request_body = self.build_request(prompt, false);
 
         let api_response = self.make_request_with_retry(&request_body).await?;
 
@@ -235,6 +251,57 @@ impl AIProvider for OpenAIProvider {
         })
     }
 
+    async fn enhance_text_streaming(
+        &self,
+        request: AIEnhancementRequest,
+        on_chunk: ChunkCallback<'_>,
+    ) -> Result<AIEnhancementResponse, AIError> {
+        request.validate()?;
+
+        let prompt = prompts::build_enhancement_prompt(
+            &request.text,
+            request.context.as_deref(),
+            &request.options.clone().unwrap_or_default(),
+        );
+
+        let emitted = AtomicBool::new(false);
+        let forward = |delta: &str| {
+            emitted.store(true, Ordering::Relaxed);
+            on_chunk(delta)
+        };
+
+        let request_body = self.build_request(prompt, true);
+        let enhanced_text =
+            match streaming::stream_chat_completions(self.post(&request_body), &forward).await {
+                Ok(text) => text.trim().to_string(),
+                Err(e) if matches!(e, AIError::Cancelled) || emitted.load(Ordering::Relaxed) => {
+                    return Err(e)
+                }
+                Err(e) => {
+                    // Not every OpenAI-compatible server streams; retry the regular way
+                    log::warn!("Streaming request failed, falling back: {}", e);
+                    let response = self.enhance_text(request).await?;
+                    if !on_chunk(&response.enhanced_text) {
+                        return Err(AIError::Cancelled);
+                    }
+                    return Ok(response);
+                }
+            };
+
+        if enhanced_text.is_empty() {
+            return Err(AIError::InvalidResponse(
+                "Empty response from API".to_string(),
+            ));
+        }
+
+        Ok(AIEnhancementResponse {
+            enhanced_text,
+            original_text: request.text,
+            provider: self.name().to_string(),
+            model: self.model.clone(),
+        })
+    }
+
     fn name(&self) -> &str {
         "openai"
     }
@@ -264,4 +331,21 @@ mod tests {
         );
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_stream_flag_serialization() {
+        let provider = OpenAIProvider::new(
+            "test_key_12345".to_string(),
+            "gpt-5-nano".to_string(),
+            HashMap::new(),
+        )
+        .unwrap();
+
+        let body = serde_json::to_value(provider.build_request("hi".to_string(), true)).unwrap();
+        assert_eq!(body["stream"], true);
+
+        // Non-streaming requests keep the original body shape
+        let body = serde_json::to_value(provider.build_request("hi".to_string(), false)).unwrap();
+        assert!(body.get("stream").is_none());
+    }
 }
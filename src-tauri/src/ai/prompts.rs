@@ -27,16 +27,86 @@ pub enum EnhancementPreset {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnhancementOptions {
     pub preset: EnhancementPreset,
+    // Instructions from a user-defined preset; replaces the built-in preset transform
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_prompt: Option<String>,
 }
 
 impl Default for EnhancementOptions {
     fn default() -> Self {
         Self {
             preset: EnhancementPreset::Default,
+            custom_prompt: None,
         }
     }
 }
 
+/// Values for the placeholders available in custom preset prompts:
+/// `{date}`, `{time}`, `{active_app}` and `{clipboard}`.
+/// Unavailable values render as empty text; use `{{` and `}}` for literal braces.
+#[derive(Debug, Clone, Default)]
+pub struct PromptVariables {
+    pub date: Option<String>,
+    pub time: Option<String>,
+    pub active_app: Option<String>,
+    pub clipboard: Option<String>,
+}
+
+pub const PROMPT_VARIABLES: &[&str] = &["date", "time", "active_app", "clipboard"];
+
+impl PromptVariables {
+    fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "date" => self.date.as_deref(),
+            "time" => self.time.as_deref(),
+            "active_app" => self.active_app.as_deref(),
+            "clipboard" => self.clipboard.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `template` references the placeholder `{name}`
+pub fn template_uses_variable(template: &str, name: &str) -> bool {
+    template.contains(&format!("{{{}}}", name))
+}
+
+/// Substitute known placeholders in a single pass, so substituted values
+/// (e.g. clipboard text containing `{date}`) are never expanded again.
+/// Unknown `{...}` sequences are kept verbatim.
+pub fn render_prompt_template(template: &str, vars: &PromptVariables) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+
+        if tail.starts_with('{') {
+            if let Some(end) = tail.find('}') {
+                let name = &tail[1..end];
+                if PROMPT_VARIABLES.contains(&name) {
+                    out.push_str(vars.get(name).unwrap_or(""));
+                    rest = &tail[end + 1..];
+                    continue;
+                }
+            }
+        }
+
+        out.push_str(&tail[..1]);
+        rest = &tail[1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
 pub fn build_enhancement_prompt(
     text: &str,
     context: Option<&str>,
@@ -46,11 +116,14 @@ pub fn build_enhancement_prompt(
     let base_prompt = BASE_PROMPT;
 
     // Add mode-specific transformation if not Default
-    let mode_transform = match options.preset {
-        EnhancementPreset::Default => "",
-        EnhancementPreset::Prompts => PROMPTS_TRANSFORM,
-        EnhancementPreset::Email => EMAIL_TRANSFORM,
-        EnhancementPreset::Commit => COMMIT_TRANSFORM,
+    let mode_transform = match options.custom_prompt.as_deref().map(str::trim) {
+        Some(custom) if !custom.is_empty() => custom,
+        _ => match options.preset {
+            EnhancementPreset::Default => "",
+            EnhancementPreset::Prompts => PROMPTS_TRANSFORM,
+            EnhancementPreset::Email => EMAIL_TRANSFORM,
+            EnhancementPreset::Commit => COMMIT_TRANSFORM,
+        },
     };
 
     // Build the complete prompt
@@ -0,0 +1,165 @@
+use super::AIError;
+use reqwest::RequestBuilder;
+use serde::Deserialize;
+
+/// Receives each streamed text delta; returning `false` stops the stream (cancellation)
+pub type ChunkCallback<'a> = &'a (dyn Fn(&str) -> bool + Send + Sync);
+
+/// Incremental parser for `text/event-stream` bodies.
+/// Bytes are buffered until a full line is available so multi-byte characters split
+/// across network chunks are never decoded half-way.
+#[derive(Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    /// Feed raw bytes and return the `data:` payloads of all complete lines
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut payloads = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            if let Some(data) = line.strip_prefix("data:") {
+                payloads.push(data.trim_start().to_string());
+            }
+        }
+        payloads
+    }
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChunkChoice {
+    #[serde(default)]
+    delta: ChunkDelta,
+}
+
+#[derive(Default, Deserialize)]
+struct ChunkDelta {
+    content: Option<String>,
+}
+
+/// Extract the text delta from an OpenAI-compatible `chat.completion.chunk` payload.
+/// Returns `Ok(None)` for the `[DONE]` sentinel and for chunks without content.
+pub fn parse_chat_delta(data: &str) -> Result<Option<String>, AIError> {
+    if data == "[DONE]" {
+        return Ok(None);
+    }
+
+    let chunk: ChatCompletionChunk = serde_json::from_str(data)
+        .map_err(|e| AIError::InvalidResponse(format!("Invalid stream chunk: {}", e)))?;
+
+    Ok(chunk
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.delta.content)
+        .filter(|content| !content.is_empty()))
+}
+
+/// Send an OpenAI-compatible chat request with `stream: true` and collect the deltas.
+/// Each delta is forwarded to `on_chunk`; the concatenated text is returned untrimmed.
+pub async fn stream_chat_completions(
+    request: RequestBuilder,
+    on_chunk: ChunkCallback<'_>,
+) -> Result<String, AIError> {
+    let mut response = request
+        .header("Accept", "text/event-stream")
+        .send()
+        .await
+        .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+    let status = response.status();
+
+    if status.as_u16() == 429 {
+        return Err(AIError::RateLimitExceeded);
+    }
+
+    if !status.is_success() {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(AIError::ApiError(format!(
+            "API returned {}: {}",
+            status, error_text
+        )));
+    }
+
+    let mut parser = SseParser::default();
+    let mut content = String::new();
+
+    while let Some(bytes) = response
+        .chunk()
+        .await
+        .map_err(|e| AIError::NetworkError(e.to_string()))?
+    {
+        for data in parser.push(&bytes) {
+            if data == "[DONE]" {
+                return Ok(content);
+            }
+            if let Some(delta) = parse_chat_delta(&data)? {
+                content.push_str(&delta);
+                if !on_chunk(&delta) {
+                    return Err(AIError::Cancelled);
+                }
+            }
+        }
+    }
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_parser_buffers_partial_lines() {
+        let mut parser = SseParser::default();
+
+        assert!(parser.push(b"data: {\"a\":").is_empty());
+        assert_eq!(parser.push(b"1}\r\n\r\n"), vec!["{\"a\":1}".to_string()]);
+
+        // Comments and other fields are ignored
+        assert_eq!(
+            parser.push(b": keep-alive\nevent: message\ndata: [DONE]\n"),
+            vec!["[DONE]".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_sse_parser_keeps_split_utf8_intact() {
+        let mut parser = SseParser::default();
+        let line = "data: caf\u{e9}\n".as_bytes();
+        let split = line.len() - 2; // inside the two-byte 'é'
+
+        assert!(parser.push(&line[..split]).is_empty());
+        assert_eq!(parser.push(&line[split..]), vec!["caf\u{e9}".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_chat_delta() {
+        let data = r#"{"choices":[{"index":0,"delta":{"content":"Hello"}}]}"#;
+        assert_eq!(parse_chat_delta(data).unwrap().as_deref(), Some("Hello"));
+
+        // Role-only first chunk and final chunk carry no content
+        let data = r#"{"choices":[{"index":0,"delta":{"role":"assistant"}}]}"#;
+        assert_eq!(parse_chat_delta(data).unwrap(), None);
+        let data = r#"{"choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#;
+        assert_eq!(parse_chat_delta(data).unwrap(), None);
+
+        assert_eq!(parse_chat_delta("[DONE]").unwrap(), None);
+        assert!(parse_chat_delta("not json").is_err());
+    }
+}
@@ -146,6 +146,80 @@ mod tests {
         assert!(commit_prompt.contains("convert the cleaned text to a Conventional Commit"));
     }
 
+    #[test]
+    fn test_prompt_template_substitution() {
+        use crate::ai::prompts::{render_prompt_template, PromptVariables};
+
+        let vars = PromptVariables {
+            date: Some("2025-03-01".to_string()),
+            time: Some("09:30".to_string()),
+            active_app: Some("Slack".to_string()),
+            clipboard: None,
+        };
+
+        assert_eq!(
+            render_prompt_template("Reply for {active_app} on {date} at {time}.", &vars),
+            "Reply for Slack on 2025-03-01 at 09:30."
+        );
+
+        // Missing values render blank, unknown placeholders stay verbatim
+        assert_eq!(
+            render_prompt_template("Context: [{clipboard}] {unknown}", &vars),
+            "Context: [] {unknown}"
+        );
+        assert_eq!(
+            render_prompt_template("{date}", &PromptVariables::default()),
+            ""
+        );
+
+        // Escaped braces and unbalanced braces are preserved
+        assert_eq!(
+            render_prompt_template("Use {{date}} literally, {json: 1} }", &vars),
+            "Use {date} literally, {json: 1} }"
+        );
+    }
+
+    #[test]
+    fn test_prompt_template_values_are_not_reexpanded() {
+        use crate::ai::prompts::{render_prompt_template, template_uses_variable, PromptVariables};
+
+        let vars = PromptVariables {
+            date: Some("2025-03-01".to_string()),
+            clipboard: Some("{date} {{time}}".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            render_prompt_template("Quote: {clipboard}", &vars),
+            "Quote: {date} {{time}}"
+        );
+        assert!(template_uses_variable("Quote: {clipboard}", "clipboard"));
+        assert!(!template_uses_variable("Quote: {clipboard}", "date"));
+    }
+
+    #[test]
+    fn test_custom_prompt_replaces_preset_transform() {
+        use crate::ai::prompts::{build_enhancement_prompt, EnhancementOptions, EnhancementPreset};
+
+        let options = EnhancementOptions {
+            preset: EnhancementPreset::Email,
+            custom_prompt: Some("Format as a code comment.".to_string()),
+        };
+        let prompt = build_enhancement_prompt("hello world", None, &options);
+
+        assert!(prompt.contains("post-processor for voice transcripts"));
+        assert!(prompt.contains("Format as a code comment."));
+        assert!(!prompt.contains("format the cleaned text as an email"));
+
+        // Blank custom prompts fall back to the built-in preset
+        let options = EnhancementOptions {
+            preset: EnhancementPreset::Email,
+            custom_prompt: Some("  ".to_string()),
+        };
+        let prompt = build_enhancement_prompt("hello world", None, &options);
+        assert!(prompt.contains("format the cleaned text as an email"));
+    }
+
     #[test]
     fn test_self_correction_rules_in_all_presets() {
         use crate::ai::prompts::{build_enhancement_prompt, EnhancementOptions, EnhancementPreset};
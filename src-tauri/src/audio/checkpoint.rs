@@ -0,0 +1,180 @@
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+const CHECKPOINT_FILE: &str = "recording_checkpoint.json";
+
+/// Orphaned recordings shorter than this aren't worth offering
+pub const MIN_RECOVERABLE_SECS: f32 = 1.0;
+
+/// Written when a recording starts and removed when it stops or is cancelled,
+/// so one still on disk at launch means the app went down mid-recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordingCheckpoint {
+    pub path: PathBuf,
+    /// Unix seconds
+    pub started_at: u64,
+    pub microphone: Option<String>,
+}
+
+/// What startup found for a checkpoint left behind
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrphanedRecording {
+    /// No checkpoint: the last session ended cleanly
+    None,
+    /// The audio file is gone, nothing to recover
+    Missing,
+    /// Too little audio to be worth transcribing; the file can go
+    TooShort(PathBuf),
+    /// Audio to offer for transcription
+    Recoverable {
+        checkpoint: RecordingCheckpoint,
+        duration_secs: f32,
+    },
+}
+
+/// Payload of the `recovered-recording` event
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoveredRecording {
+    pub path: String,
+    pub started_at: u64,
+    pub duration_secs: f32,
+}
+
+pub fn checkpoint_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(CHECKPOINT_FILE)
+}
+
+pub fn write_checkpoint(data_dir: &Path, checkpoint: &RecordingCheckpoint) -> Result<(), String> {
+    let json = serde_json::to_vec(checkpoint)
+        .map_err(|e| format!("Failed to serialize recording checkpoint: {}", e))?;
+    std::fs::write(checkpoint_path(data_dir), json)
+        .map_err(|e| format!("Failed to write recording checkpoint: {}", e))
+}
+
+pub fn read_checkpoint(data_dir: &Path) -> Option<RecordingCheckpoint> {
+    let json = std::fs::read(checkpoint_path(data_dir)).ok()?;
+    match serde_json::from_slice(&json) {
+        Ok(checkpoint) => Some(checkpoint),
+        Err(e) => {
+            log::warn!("Ignoring unreadable recording checkpoint: {}", e);
+            None
+        }
+    }
+}
+
+pub fn clear_checkpoint(data_dir: &Path) {
+    let path = checkpoint_path(data_dir);
+    if let Err(e) = std::fs::remove_file(&path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            log::warn!("Failed to remove recording checkpoint: {}", e);
+        }
+    }
+}
+
+/// Whether an orphaned recording of `duration_secs` (`None` when the file is
+/// missing or unreadable) should be offered for transcription
+pub fn classify_orphan(
+    checkpoint: RecordingCheckpoint,
+    duration_secs: Option<f32>,
+) -> OrphanedRecording {
+    match duration_secs {
+        None => OrphanedRecording::Missing,
+        Some(secs) if secs < MIN_RECOVERABLE_SECS => OrphanedRecording::TooShort(checkpoint.path),
+        Some(duration_secs) => OrphanedRecording::Recoverable {
+            checkpoint,
+            duration_secs,
+        },
+    }
+}
+
+/// Look for a recording the last session never finished. The checkpoint is
+/// consumed; a recoverable file has its WAV header repaired so it can be read.
+pub fn take_orphaned_recording(data_dir: &Path) -> OrphanedRecording {
+    let Some(checkpoint) = read_checkpoint(data_dir) else {
+        clear_checkpoint(data_dir);
+        return OrphanedRecording::None;
+    };
+    clear_checkpoint(data_dir);
+
+    let duration_secs = if checkpoint.path.is_file() {
+        match repair_wav_header(&checkpoint.path) {
+            Ok(secs) => Some(secs),
+            Err(e) => {
+                log::warn!(
+                    "Orphaned recording {:?} is unusable: {}",
+                    checkpoint.path,
+                    e
+                );
+                Some(0.0)
+            }
+        }
+    } else {
+        None
+    };
+    classify_orphan(checkpoint, duration_secs)
+}
+
+/// Fix the RIFF and data chunk sizes of a WAV file whose writer never got to
+/// finalize it (they stay 0 until then), returning its duration in seconds
+pub fn repair_wav_header(path: &Path) -> Result<f32, String> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open recording: {}", e))?;
+    let file_len = file
+        .metadata()
+        .map_err(|e| format!("Failed to read recording size: {}", e))?
+        .len();
+
+    let mut riff = [0u8; 12];
+    file.read_exact(&mut riff)
+        .map_err(|_| "Recording has no WAV header".to_string())?;
+    if &riff[0..4] != b"RIFF" || &riff[8..12] != b"WAVE" {
+        return Err("Recording is not a WAV file".to_string());
+    }
+
+    // Walk the chunks up to "data", picking up the frame layout from "fmt "
+    let mut byte_rate = 0u32;
+    let mut block_align = 1u64;
+    let mut offset = 12u64;
+    loop {
+        let mut header = [0u8; 8];
+        file.seek(SeekFrom::Start(offset))
+            .and_then(|_| file.read_exact(&mut header))
+            .map_err(|_| "Recording has no audio data".to_string())?;
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as u64;
+
+        if &header[0..4] == b"data" {
+            let data_start = offset + 8;
+            let available = file_len.saturating_sub(data_start);
+            // Drop a partly written last frame
+            let data_len = (available - available % block_align).min(u32::MAX as u64);
+
+            file.seek(SeekFrom::Start(4))
+                .and_then(|_| file.write_all(&((data_start + data_len - 8) as u32).to_le_bytes()))
+                .and_then(|_| file.seek(SeekFrom::Start(offset + 4)))
+                .and_then(|_| file.write_all(&(data_len as u32).to_le_bytes()))
+                .and_then(|_| file.set_len(data_start + data_len))
+                .map_err(|e| format!("Failed to repair recording header: {}", e))?;
+
+            if byte_rate == 0 {
+                return Err("Recording has no format chunk".to_string());
+            }
+            return Ok(data_len as f32 / byte_rate as f32);
+        }
+
+        if &header[0..4] == b"fmt " {
+            let mut fmt = [0u8; 14];
+            file.read_exact(&mut fmt)
+                .map_err(|_| "Recording format chunk is truncated".to_string())?;
+            byte_rate = u32::from_le_bytes([fmt[8], fmt[9], fmt[10], fmt[11]]);
+            block_align = u16::from_le_bytes([fmt[12], fmt[13]]).max(1) as u64;
+        }
+        // Chunks are padded to an even size
+        offset += 8 + size + size % 2;
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use super::super::checkpoint::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn checkpoint(path: &Path) -> RecordingCheckpoint {
+        RecordingCheckpoint {
+            path: path.to_path_buf(),
+            started_at: 1_700_000_000,
+            microphone: Some("USB Mic".to_string()),
+        }
+    }
+
+    /// A 16 kHz mono recording cut off the way a crash leaves it: samples on
+    /// disk, but the header sizes never updated from 0
+    fn write_unfinalized_wav(path: &Path, secs: f32) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for i in 0..(secs * 16_000.0) as usize {
+            writer.write_sample((i % 100) as i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let mut bytes = std::fs::read(path).unwrap();
+        bytes[4..8].copy_from_slice(&0u32.to_le_bytes());
+        let data = bytes.windows(4).position(|w| w == b"data").unwrap();
+        bytes[data + 4..data + 8].copy_from_slice(&0u32.to_le_bytes());
+        // Half a sample written when the process died
+        bytes.push(0x7f);
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let cp = checkpoint(&dir.path().join("recording_1.wav"));
+
+        assert_eq!(read_checkpoint(dir.path()), None);
+        write_checkpoint(dir.path(), &cp).unwrap();
+        assert_eq!(read_checkpoint(dir.path()), Some(cp));
+
+        clear_checkpoint(dir.path());
+        assert_eq!(read_checkpoint(dir.path()), None);
+        // Clearing twice is fine
+        clear_checkpoint(dir.path());
+    }
+
+    #[test]
+    fn test_corrupt_checkpoint_is_ignored() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(checkpoint_path(dir.path()), "{not json").unwrap();
+        assert_eq!(take_orphaned_recording(dir.path()), OrphanedRecording::None);
+        assert!(!checkpoint_path(dir.path()).exists());
+    }
+
+    #[test]
+    fn test_orphan_classification() {
+        let cp = checkpoint(Path::new("/tmp/recording_1.wav"));
+        assert_eq!(
+            classify_orphan(cp.clone(), None),
+            OrphanedRecording::Missing
+        );
+        assert_eq!(
+            classify_orphan(cp.clone(), Some(0.4)),
+            OrphanedRecording::TooShort(cp.path.clone())
+        );
+        assert_eq!(
+            classify_orphan(cp.clone(), Some(12.0)),
+            OrphanedRecording::Recoverable {
+                checkpoint: cp,
+                duration_secs: 12.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_crashed_recording_is_recovered_and_readable() {
+        let dir = TempDir::new().unwrap();
+        let wav = dir.path().join("recording_1.wav");
+        write_unfinalized_wav(&wav, 3.0);
+        write_checkpoint(dir.path(), &checkpoint(&wav)).unwrap();
+
+        match take_orphaned_recording(dir.path()) {
+            OrphanedRecording::Recoverable { duration_secs, .. } => {
+                assert!((duration_secs - 3.0).abs() < 0.01)
+            }
+            other => panic!("expected a recoverable recording, got {:?}", other),
+        }
+        // The checkpoint is consumed and the repaired file reads normally
+        assert_eq!(read_checkpoint(dir.path()), None);
+        let reader = hound::WavReader::open(&wav).unwrap();
+        assert_eq!(reader.duration(), 48_000);
+    }
+
+    #[test]
+    fn test_missing_or_short_orphans_are_not_offered() {
+        let dir = TempDir::new().unwrap();
+        let missing = dir.path().join("gone.wav");
+        write_checkpoint(dir.path(), &checkpoint(&missing)).unwrap();
+        assert_eq!(
+            take_orphaned_recording(dir.path()),
+            OrphanedRecording::Missing
+        );
+
+        let short = dir.path().join("short.wav");
+        write_unfinalized_wav(&short, 0.2);
+        write_checkpoint(dir.path(), &checkpoint(&short)).unwrap();
+        assert_eq!(
+            take_orphaned_recording(dir.path()),
+            OrphanedRecording::TooShort(short)
+        );
+    }
+}
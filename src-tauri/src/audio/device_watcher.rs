@@ -1,27 +1,128 @@
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use serde::Serialize;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_store::StoreExt;
 
 use crate::audio::recorder::AudioRecorder;
-use crate::commands::settings::{get_settings, set_audio_device, update_tray_menu};
+use crate::commands::settings::{
+    apply_device_recording_mode, get_settings, set_audio_device, update_tray_menu,
+};
 use crate::{get_recording_state, RecordingState};
 
+/// How often the watcher polls the OS for input devices
+const POLL_INTERVAL: Duration = Duration::from_millis(1500);
+
+/// Poll interval while a change is waiting to settle
+const SETTLE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A change must hold this long before it is reported, so docking or
+/// undocking (several devices appearing one after another) is reported once
+pub const DEVICE_CHANGE_DEBOUNCE: Duration = Duration::from_millis(1000);
+
+/// Payload of the `audio-devices-changed` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AudioDevicesChanged {
+    pub devices: Vec<String>,
+    /// The OS default input device, `None` when there is none
+    pub default_device: Option<String>,
+}
+
+/// Holds back device changes until they stop changing for `settle`
+pub struct DeviceChangeDebouncer {
+    settle: Duration,
+    reported: Option<AudioDevicesChanged>,
+    pending: Option<(AudioDevicesChanged, Instant)>,
+}
+
+impl DeviceChangeDebouncer {
+    pub fn new(settle: Duration) -> Self {
+        Self {
+            settle,
+            reported: None,
+            pending: None,
+        }
+    }
+
+    /// Whether a change has been seen but not reported yet
+    pub fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Feed the latest poll. Returns the snapshot to report once it has held for
+    /// the settle time; a change that reverts before then is never reported.
+    pub fn observe(
+        &mut self,
+        snapshot: AudioDevicesChanged,
+        now: Instant,
+    ) -> Option<AudioDevicesChanged> {
+        if self.reported.as_ref() == Some(&snapshot) {
+            self.pending = None;
+            return None;
+        }
+
+        match &self.pending {
+            Some((pending, since)) if *pending == snapshot => {
+                if now.duration_since(*since) < self.settle {
+                    return None;
+                }
+                self.pending = None;
+                self.reported = Some(snapshot.clone());
+                Some(snapshot)
+            }
+            _ => {
+                self.pending = Some((snapshot, now));
+                None
+            }
+        }
+    }
+}
+
+/// The microphone recordings will use: the selected one while it's connected,
+/// otherwise the OS default
+pub fn active_input_device(
+    selected: Option<&str>,
+    devices: &[String],
+    default_device: Option<&str>,
+) -> Option<String> {
+    selected
+        .filter(|selected| devices.iter().any(|device| device == selected))
+        .or(default_device)
+        .map(str::to_string)
+}
+
+/// The recording mode remembered for `device`, if per-device modes are on and
+/// one was stored. Unknown mode strings are ignored.
+pub fn remembered_recording_mode<'a>(
+    enabled: bool,
+    modes: &'a HashMap<String, String>,
+    device: Option<&str>,
+) -> Option<&'a str> {
+    if !enabled {
+        return None;
+    }
+    modes
+        .get(device?)
+        .map(String::as_str)
+        .filter(|mode| matches!(*mode, "toggle" | "push_to_talk"))
+}
+
+fn onboarding_completed(app: &AppHandle) -> bool {
+    app.store("settings")
+        .ok()
+        .and_then(|store| store.get("onboarding_completed").and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
 /// Check if device watcher should start and start it if conditions are met.
 /// Conditions: onboarding_completed = true AND microphone permission granted.
 /// This is called from backend when either condition becomes true.
 pub async fn try_start_device_watcher_if_ready(app: &AppHandle) {
-    // Check onboarding status
-    let onboarding_done = app
-        .store("settings")
-        .ok()
-        .and_then(|store| store.get("onboarding_completed").and_then(|v| v.as_bool()))
-        .unwrap_or(false);
-
-    if !onboarding_done {
+    if !onboarding_completed(app) {
         log::debug!("Device watcher: onboarding not complete, skipping start");
         return;
     }
@@ -89,20 +190,36 @@ impl DeviceWatcher {
         let app = self.app.clone();
 
         let handle = thread::spawn(move || {
-            let mut last_devices: Vec<String> = Vec::new();
+            let mut debouncer = DeviceChangeDebouncer::new(DEVICE_CHANGE_DEBOUNCE);
 
             while !stop_flag.load(Ordering::Relaxed) {
-                let devices = AudioRecorder::get_devices();
+                let snapshot = AudioDevicesChanged {
+                    devices: AudioRecorder::get_devices(),
+                    default_device: AudioRecorder::get_default_device(),
+                };
+
+                // Same gate as the device commands: nothing reaches the UI before onboarding
+                let change = debouncer
+                    .observe(snapshot, Instant::now())
+                    .filter(|_| onboarding_completed(&app));
 
-                if devices != last_devices {
-                    log::info!("Audio devices changed: {:?}", devices);
+                if let Some(change) = change {
+                    log::info!(
+                        "Audio devices changed: {:?} (default: {:?})",
+                        change.devices,
+                        change.default_device
+                    );
 
-                    if let Err(err) = app.emit("audio-devices-updated", &devices) {
+                    if let Err(err) = app.emit("audio-devices-changed", &change) {
+                        log::warn!("Failed to emit audio-devices-changed: {}", err);
+                    }
+                    if let Err(err) = app.emit("audio-devices-updated", &change.devices) {
                         log::warn!("Failed to emit audio-devices-updated: {}", err);
                     }
 
                     let app_for_tasks = app.clone();
-                    let devices_for_tasks = devices.clone();
+                    let devices_for_tasks = change.devices;
+                    let default_for_tasks = change.default_device;
 
                     tauri::async_runtime::spawn(async move {
                         // Refresh tray regardless of selection outcome.
@@ -112,7 +229,7 @@ impl DeviceWatcher {
 
                         match get_settings(app_for_tasks.clone()).await {
                             Ok(settings) => {
-                                if let Some(current) = settings.selected_microphone {
+                                if let Some(current) = settings.selected_microphone.clone() {
                                     if !devices_for_tasks.contains(&current) {
                                         let state = get_recording_state(&app_for_tasks);
                                         let is_recording = matches!(
@@ -145,17 +262,35 @@ impl DeviceWatcher {
                                         }
                                     }
                                 }
+
+                                let active = active_input_device(
+                                    settings.selected_microphone.as_deref(),
+                                    &devices_for_tasks,
+                                    default_for_tasks.as_deref(),
+                                );
+                                if let Err(err) =
+                                    apply_device_recording_mode(&app_for_tasks, active.as_deref())
+                                        .await
+                                {
+                                    log::warn!(
+                                        "Failed to apply recording mode for {:?}: {}",
+                                        active,
+                                        err
+                                    );
+                                }
                             }
                             Err(err) => {
                                 log::warn!("Failed to read settings after device change: {}", err);
                             }
                         }
                     });
-
-                    last_devices = devices;
                 }
 
-                thread::sleep(Duration::from_millis(1500));
+                thread::sleep(if debouncer.has_pending() {
+                    SETTLE_POLL_INTERVAL
+                } else {
+                    POLL_INTERVAL
+                });
             }
         });
 
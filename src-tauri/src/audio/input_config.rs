@@ -0,0 +1,122 @@
+use cpal::SampleFormat;
+
+/// Sample rates to try, best first. 16 kHz needs no resampling; the next ones
+/// divide down to it evenly, 44.1 kHz and the rest need fractional resampling.
+const PREFERRED_SAMPLE_RATES: &[u32] = &[16_000, 48_000, 32_000, 96_000, 44_100, 22_050];
+
+/// One range of configs an input device reports as supported
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputConfigRange {
+    pub channels: u16,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+    pub sample_format: SampleFormat,
+}
+
+impl From<&cpal::SupportedStreamConfigRange> for InputConfigRange {
+    fn from(range: &cpal::SupportedStreamConfigRange) -> Self {
+        Self {
+            channels: range.channels(),
+            min_sample_rate: range.min_sample_rate().0,
+            max_sample_rate: range.max_sample_rate().0,
+            sample_format: range.sample_format(),
+        }
+    }
+}
+
+/// A concrete config to open an input stream with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputConfig {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub sample_format: SampleFormat,
+}
+
+impl From<&cpal::SupportedStreamConfig> for InputConfig {
+    fn from(config: &cpal::SupportedStreamConfig) -> Self {
+        Self {
+            channels: config.channels(),
+            sample_rate: config.sample_rate().0,
+            sample_format: config.sample_format(),
+        }
+    }
+}
+
+impl InputConfig {
+    pub fn stream_config(&self) -> cpal::StreamConfig {
+        cpal::StreamConfig {
+            channels: self.channels,
+            sample_rate: cpal::SampleRate(self.sample_rate),
+            buffer_size: cpal::BufferSize::Default,
+        }
+    }
+}
+
+/// Sample formats the recorder can convert, best first
+fn format_rank(format: SampleFormat) -> Option<usize> {
+    match format {
+        SampleFormat::F32 => Some(0),
+        SampleFormat::I16 => Some(1),
+        SampleFormat::U16 => Some(2),
+        _ => None,
+    }
+}
+
+fn rate_rank(rate: u32) -> usize {
+    PREFERRED_SAMPLE_RATES
+        .iter()
+        .position(|&preferred| preferred == rate)
+        .unwrap_or(PREFERRED_SAMPLE_RATES.len())
+}
+
+/// Every config worth trying on a device, in the order to try them.
+///
+/// Configs that normalize most cleanly to 16 kHz mono come first: sample rate
+/// matters most, then fewer channels, then sample format. The device's own
+/// default config goes last as a final fallback. Ranges in formats the recorder
+/// can't convert are skipped.
+pub fn input_config_priority(
+    ranges: &[InputConfigRange],
+    default_config: Option<InputConfig>,
+) -> Vec<InputConfig> {
+    let mut candidates: Vec<InputConfig> = Vec::new();
+    for range in ranges {
+        if format_rank(range.sample_format).is_none() || range.channels == 0 {
+            continue;
+        }
+        let mut rates: Vec<u32> = PREFERRED_SAMPLE_RATES
+            .iter()
+            .copied()
+            .filter(|rate| (range.min_sample_rate..=range.max_sample_rate).contains(rate))
+            .collect();
+        if rates.is_empty() {
+            // Nothing familiar in range; the highest rate keeps the most detail
+            rates.push(range.max_sample_rate);
+        }
+        for sample_rate in rates {
+            candidates.push(InputConfig {
+                channels: range.channels,
+                sample_rate,
+                sample_format: range.sample_format,
+            });
+        }
+    }
+
+    candidates.sort_by_key(|config| {
+        (
+            rate_rank(config.sample_rate),
+            config.channels,
+            format_rank(config.sample_format),
+        )
+    });
+    candidates.dedup();
+
+    if let Some(default_config) = default_config {
+        if format_rank(default_config.sample_format).is_some()
+            && !candidates.contains(&default_config)
+        {
+            candidates.push(default_config);
+        }
+    }
+    candidates
+}
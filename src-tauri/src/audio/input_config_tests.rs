@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use super::super::input_config::*;
+    use cpal::SampleFormat;
+
+    fn range(channels: u16, min: u32, max: u32, format: SampleFormat) -> InputConfigRange {
+        InputConfigRange {
+            channels,
+            min_sample_rate: min,
+            max_sample_rate: max,
+            sample_format: format,
+        }
+    }
+
+    fn config(channels: u16, sample_rate: u32, format: SampleFormat) -> InputConfig {
+        InputConfig {
+            channels,
+            sample_rate,
+            sample_format: format,
+        }
+    }
+
+    #[test]
+    fn test_prefers_16k_mono_when_offered() {
+        let ranges = [
+            range(2, 8_000, 96_000, SampleFormat::F32),
+            range(1, 8_000, 96_000, SampleFormat::I16),
+        ];
+        let priority = input_config_priority(&ranges, None);
+        assert_eq!(priority[0], config(1, 16_000, SampleFormat::I16));
+        assert_eq!(priority[1], config(2, 16_000, SampleFormat::F32));
+        assert_eq!(priority[2], config(1, 48_000, SampleFormat::I16));
+    }
+
+    #[test]
+    fn test_pro_interface_with_only_48k_stereo() {
+        // What some audio interfaces report: nothing but 48 kHz, 2 channels
+        let ranges = [
+            range(2, 48_000, 48_000, SampleFormat::I16),
+            range(2, 48_000, 48_000, SampleFormat::F32),
+        ];
+        let default_config = config(2, 48_000, SampleFormat::F32);
+        let priority = input_config_priority(&ranges, Some(default_config));
+        assert_eq!(
+            priority,
+            vec![
+                config(2, 48_000, SampleFormat::F32),
+                config(2, 48_000, SampleFormat::I16),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rate_outranks_channels_and_format() {
+        let ranges = [
+            range(1, 44_100, 44_100, SampleFormat::F32),
+            range(4, 48_000, 48_000, SampleFormat::U16),
+        ];
+        let priority = input_config_priority(&ranges, None);
+        assert_eq!(priority[0], config(4, 48_000, SampleFormat::U16));
+        assert_eq!(priority[1], config(1, 44_100, SampleFormat::F32));
+    }
+
+    #[test]
+    fn test_unfamiliar_rate_uses_range_maximum() {
+        let ranges = [range(1, 11_025, 11_025, SampleFormat::I16)];
+        assert_eq!(
+            input_config_priority(&ranges, None),
+            vec![config(1, 11_025, SampleFormat::I16)]
+        );
+    }
+
+    #[test]
+    fn test_default_config_is_last_resort() {
+        let ranges = [range(1, 16_000, 48_000, SampleFormat::F32)];
+        let default_config = config(2, 44_100, SampleFormat::I16);
+        let priority = input_config_priority(&ranges, Some(default_config));
+        assert_eq!(
+            priority.first(),
+            Some(&config(1, 16_000, SampleFormat::F32))
+        );
+        assert_eq!(priority.last(), Some(&default_config));
+    }
+
+    #[test]
+    fn test_skips_unconvertible_formats() {
+        let ranges = [
+            range(1, 16_000, 16_000, SampleFormat::I32),
+            range(1, 16_000, 16_000, SampleFormat::F64),
+        ];
+        let default_config = config(1, 16_000, SampleFormat::I32);
+        assert!(input_config_priority(&ranges, Some(default_config)).is_empty());
+    }
+}
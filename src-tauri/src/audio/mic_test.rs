@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+use std::sync::{mpsc, Mutex};
+
+use crate::audio::recorder::AudioRecorder;
+use crate::RecordingState;
+
+/// The settings mic test stops itself after this long if the page never calls `stop_mic_test`
+pub const MIC_TEST_MAX_DURATION: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// A short capture for the settings mic test, separate from the recording flow.
+/// It uses its own [`AudioRecorder`] so the levels can go to the main window
+/// without touching recording state.
+#[derive(Default)]
+pub struct MicTest {
+    recorder: Option<AudioRecorder>,
+    capture_path: Option<PathBuf>,
+    /// Bumped on every start so a stale auto-stop timer leaves a newer test alone
+    session: u64,
+}
+
+pub struct MicTestState(pub Mutex<MicTest>);
+
+impl MicTest {
+    pub fn is_active(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    pub fn session(&self) -> u64 {
+        self.session
+    }
+
+    /// A test can't start while one is running or a real recording owns the microphone
+    pub fn check_can_start(&self, recording_state: RecordingState) -> Result<(), String> {
+        if self.is_active() {
+            return Err("Mic test already running".to_string());
+        }
+        if recording_state != RecordingState::Idle {
+            return Err("Can't test the microphone while recording".to_string());
+        }
+        Ok(())
+    }
+
+    /// Open a capture on `device` (the default input when `None`) and return its level receiver
+    pub fn start(
+        &mut self,
+        capture_path: PathBuf,
+        device: Option<String>,
+    ) -> Result<mpsc::Receiver<f64>, String> {
+        let path_str = capture_path
+            .to_str()
+            .ok_or_else(|| "Invalid path encoding".to_string())?;
+
+        let mut recorder = AudioRecorder::new();
+        if let Err(e) = recorder.start_recording(path_str, device) {
+            let _ = std::fs::remove_file(&capture_path);
+            return Err(e);
+        }
+        let Some(levels) = recorder.take_audio_level_receiver() else {
+            let _ = recorder.stop_recording();
+            let _ = std::fs::remove_file(&capture_path);
+            return Err("Microphone produced no level stream".to_string());
+        };
+
+        self.recorder = Some(recorder);
+        self.capture_path = Some(capture_path);
+        self.session += 1;
+        Ok(levels)
+    }
+
+    /// Close the capture and delete what it wrote. Returns false if no test was running.
+    pub fn stop(&mut self) -> bool {
+        let Some(mut recorder) = self.recorder.take() else {
+            return false;
+        };
+        if let Err(e) = recorder.stop_recording() {
+            log::warn!("Mic test capture did not stop cleanly: {}", e);
+        }
+        if let Some(path) = self.capture_path.take() {
+            let _ = std::fs::remove_file(path);
+        }
+        true
+    }
+}
@@ -1,12 +1,21 @@
+pub mod checkpoint;
 pub mod converter;
 pub mod device_watcher;
+pub mod input_config;
 pub mod level_meter;
+pub mod mic_test;
 pub mod normalizer;
+pub mod player;
 pub mod recorder;
 pub mod resampler;
 pub mod silence_detector;
+pub mod sounds;
 
+#[cfg(test)]
+mod checkpoint_tests;
 #[cfg(test)]
 mod converter_tests;
 #[cfg(test)]
+mod input_config_tests;
+#[cfg(test)]
 mod normalizer_tests;
@@ -0,0 +1,195 @@
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The path of `filename` inside `recordings_dir`. Only a bare file name is
+/// accepted, so the UI can't reach files outside the recordings folder.
+pub fn resolve_recording_path(recordings_dir: &Path, filename: &str) -> Result<PathBuf, String> {
+    let mut components = Path::new(filename).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(name)), None) => {
+            let path = recordings_dir.join(name);
+            if path.is_file() {
+                Ok(path)
+            } else {
+                Err(format!("Recording not found: {}", filename))
+            }
+        }
+        _ => Err(format!("Invalid recording name: {}", filename)),
+    }
+}
+
+/// Read a WAV file as mono f32 samples plus its sample rate
+fn read_wav_mono(path: &Path) -> Result<(Vec<f32>, u32), String> {
+    let mut reader =
+        hound::WavReader::open(path).map_err(|e| format!("Failed to open recording: {}", e))?;
+    let spec = reader.spec();
+    let samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read recording: {}", e))?,
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample.max(1) - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|s| s as f32 / scale))
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("Failed to read recording: {}", e))?
+        }
+    };
+
+    let channels = spec.channels.max(1) as usize;
+    let mono = samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+    Ok((mono, spec.sample_rate.max(1)))
+}
+
+/// One clip playing on the default output device
+struct Playback {
+    stop: Arc<AtomicBool>,
+}
+
+/// The clip currently playing, if any. Starting another stops it first.
+#[derive(Default)]
+pub struct PlaybackState(Mutex<Option<Playback>>);
+
+impl PlaybackState {
+    /// Start playing `path` and return once the output stream is running.
+    /// `on_finished` runs on the playback thread with `true` when the clip
+    /// played to the end and `false` when it was stopped.
+    pub fn play(
+        &self,
+        path: &Path,
+        on_finished: impl FnOnce(bool) + Send + 'static,
+    ) -> Result<(), String> {
+        let (samples, source_rate) = read_wav_mono(path)?;
+        self.stop();
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_for_thread = stop.clone();
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<(), String>>();
+
+        // cpal streams aren't Send on every platform, so the stream lives on its own thread
+        thread::spawn(move || {
+            let done = Arc::new(AtomicBool::new(false));
+            let stream = match open_output_stream(samples, source_rate, done.clone()) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+            let _ = ready_tx.send(Ok(()));
+
+            while !done.load(Ordering::Relaxed) && !stop_for_thread.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(50));
+            }
+            // Let the device drain its last buffer before closing
+            if done.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(100));
+            }
+            drop(stream);
+            on_finished(!stop_for_thread.load(Ordering::Relaxed));
+        });
+
+        ready_rx
+            .recv()
+            .map_err(|_| "Playback thread exited unexpectedly".to_string())??;
+        if let Ok(mut current) = self.0.lock() {
+            *current = Some(Playback { stop });
+        }
+        Ok(())
+    }
+
+    /// Stop the current clip. Returns false if nothing was playing.
+    pub fn stop(&self) -> bool {
+        let Some(playback) = self.0.lock().ok().and_then(|mut current| current.take()) else {
+            return false;
+        };
+        playback.stop.store(true, Ordering::Relaxed);
+        true
+    }
+}
+
+/// Open and start the default output device, feeding it `samples` resampled
+/// (linearly; fine for speech) to the device rate and copied to every channel
+fn open_output_stream(
+    samples: Vec<f32>,
+    source_rate: u32,
+    done: Arc<AtomicBool>,
+) -> Result<cpal::Stream, String> {
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| "No output device available".to_string())?;
+    let config = device
+        .default_output_config()
+        .map_err(|e| format!("Failed to get output config: {}", e))?;
+    let channels = config.channels().max(1) as usize;
+    let step = source_rate as f64 / config.sample_rate().0 as f64;
+
+    let mut frame = 0usize;
+    let next_sample = move || -> Option<f32> {
+        let source = frame as f64 * step;
+        frame += 1;
+        let index = source as usize;
+        let current = *samples.get(index)?;
+        let next = samples.get(index + 1).copied().unwrap_or(current);
+        let fraction = (source - index as f64) as f32;
+        Some(current + (next - current) * fraction)
+    };
+
+    let err_fn = |err| log::error!("Playback stream error: {}", err);
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => {
+            build_stream::<f32>(&device, &config, channels, next_sample, done, err_fn)
+        }
+        cpal::SampleFormat::I16 => {
+            build_stream::<i16>(&device, &config, channels, next_sample, done, err_fn)
+        }
+        cpal::SampleFormat::U16 => {
+            build_stream::<u16>(&device, &config, channels, next_sample, done, err_fn)
+        }
+        other => return Err(format!("Unsupported output sample format: {:?}", other)),
+    }?;
+    stream
+        .play()
+        .map_err(|e| format!("Failed to start playback: {}", e))?;
+    Ok(stream)
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    channels: usize,
+    mut next_sample: impl FnMut() -> Option<f32> + Send + 'static,
+    done: Arc<AtomicBool>,
+    err_fn: impl FnMut(cpal::StreamError) + Send + 'static,
+) -> Result<cpal::Stream, String>
+where
+    T: cpal::SizedSample + cpal::FromSample<f32>,
+{
+    device
+        .build_output_stream(
+            &config.config(),
+            move |data: &mut [T], _: &_| {
+                for frame in data.chunks_mut(channels) {
+                    let value = next_sample().unwrap_or_else(|| {
+                        done.store(true, Ordering::Relaxed);
+                        0.0
+                    });
+                    for sample in frame {
+                        *sample = T::from_sample(value);
+                    }
+                }
+            },
+            err_fn,
+            None,
+        )
+        .map_err(|e| format!("Failed to open output stream: {}", e))
+}
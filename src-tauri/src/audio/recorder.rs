@@ -1,10 +1,14 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+use super::input_config::{input_config_priority, InputConfig, InputConfigRange};
 use super::level_meter::AudioLevelMeter;
 use super::silence_detector::SilenceDetector;
 
@@ -26,9 +30,20 @@ impl RecordingSize {
     }
 }
 
+/// Audio handed to a live consumer (realtime transcription) while recording
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamedAudio {
+    /// Sent once, before any samples
+    Format { sample_rate: u32, channels: u16 },
+    /// Interleaved 16-bit samples, as written to the WAV file
+    Samples(Vec<i16>),
+}
+
 pub struct AudioRecorder {
     recording_handle: Arc<Mutex<Option<RecordingHandle>>>,
     audio_level_receiver: Arc<Mutex<Option<mpsc::Receiver<f64>>>>,
+    /// Consumer for the next recording's audio; the sender closes when it stops
+    audio_tap: Option<tokio::sync::mpsc::UnboundedSender<StreamedAudio>>,
 }
 
 impl Drop for AudioRecorder {
@@ -71,9 +86,15 @@ impl AudioRecorder {
         Self {
             recording_handle: Arc::new(Mutex::new(None)),
             audio_level_receiver: Arc::new(Mutex::new(None)),
+            audio_tap: None,
         }
     }
 
+    /// Also send the next recording's audio to `tap` as it is captured
+    pub fn set_audio_tap(&mut self, tap: tokio::sync::mpsc::UnboundedSender<StreamedAudio>) {
+        self.audio_tap = Some(tap);
+    }
+
     pub fn start_recording(
         &mut self,
         output_path: &str,
@@ -110,6 +131,8 @@ impl AudioRecorder {
         // Silence detection config for VAD
         let silence_duration = Duration::from_secs(10); // 10 seconds of continuous silence
 
+        let audio_tap = self.audio_tap.take();
+
         // Spawn recording thread
         let thread_handle = thread::spawn(move || -> Result<String, String> {
             let host = cpal::default_host();
@@ -141,14 +164,25 @@ impl AudioRecorder {
             log::info!("🎤 AUDIO DEVICE SELECTED: {}", device_name);
             log::info!("======================================");
 
-            let config = device.default_input_config().map_err(|e| e.to_string())?;
-
-            log::info!(
-                "Audio config: sample_rate={} Hz, channels={}, format={:?}",
-                config.sample_rate().0,
-                config.channels(),
-                config.sample_format()
-            );
+            let ranges: Vec<InputConfigRange> = device
+                .supported_input_configs()
+                .map(|configs| {
+                    configs
+                        .map(|range| InputConfigRange::from(&range))
+                        .collect()
+                })
+                .unwrap_or_else(|e| {
+                    log::warn!("Failed to query supported input configs: {}", e);
+                    Vec::new()
+                });
+            let default_config = device
+                .default_input_config()
+                .ok()
+                .map(|config| InputConfig::from(&config));
+            let candidates = input_config_priority(&ranges, default_config);
+            if candidates.is_empty() {
+                return Err("No supported input configuration for this device".to_string());
+            }
 
             // List all available input devices for debugging
             log::info!("Available input devices:");
@@ -160,169 +194,40 @@ impl AudioRecorder {
                 }
             }
 
-            // Initialize silence detector and level meter
-            let silence_detector = Arc::new(Mutex::new(SilenceDetector::new(silence_duration)));
-
-            let level_meter = Arc::new(Mutex::new(
-                AudioLevelMeter::new(
-                    config.sample_rate().0,
-                    config.channels() as u32,
+            // Try each config until one opens, instead of failing on the first
+            let mut opened = None;
+            let mut last_error = String::new();
+            for config in candidates {
+                match open_recording_stream(
+                    &device,
+                    config,
+                    &output_path,
+                    silence_duration,
                     audio_level_tx.clone(),
-                )
-                .map_err(|e| format!("Failed to create level meter: {}", e))?,
-            ));
-
-            // Record with native settings, Whisper will handle resampling
-            let spec = hound::WavSpec {
-                channels: config.channels(),
-                sample_rate: config.sample_rate().0,
-                bits_per_sample: 16,
-                sample_format: hound::SampleFormat::Int,
-            };
-
-            let writer = Arc::new(Mutex::new(Some(
-                hound::WavWriter::create(&output_path, spec).map_err(|e| e.to_string())?,
-            )));
-            let err_fn = |err| log::error!("Stream error: {}", err);
-            let error_occurred = Arc::new(Mutex::new(None::<String>));
-
-            // Shared state for size tracking
-            let bytes_written = Arc::new(Mutex::new(0u64));
-
-            // Common audio processing closure
-            let process_audio = {
-                let writer_clone = writer.clone();
-                let error_clone = error_occurred.clone();
-                let bytes_clone = bytes_written.clone();
-                let stop_tx_for_size = stop_tx_clone.clone();
-                let stop_tx_for_silence = stop_tx_clone.clone();
-                let silence_detector_clone = silence_detector.clone();
-                let level_meter_clone = level_meter.clone();
-
-                move |f32_samples: &[f32], i16_samples: &[i16]| {
-                    // Calculate RMS for both level meter and silence detection
-                    let sum: f32 = f32_samples.iter().map(|x| x * x).sum();
-                    let rms = (sum / f32_samples.len() as f32).sqrt();
-
-                    // Process with level meter
-                    if let Ok(mut meter) = level_meter_clone.try_lock() {
-                        let _ = meter.process_samples(f32_samples);
-                    }
-
-                    // Check for silence
-                    if let Ok(mut detector) = silence_detector_clone.try_lock() {
-                        if detector.update(rms) {
-                            // Silence duration exceeded, stop recording
-                            let _ = stop_tx_for_silence.send(RecorderCommand::StopSilence);
-                        }
-                    }
-
-                    // Check size before writing
-                    let sample_bytes = i16_samples.len() * 2; // 2 bytes per i16 sample
-                    if let Ok(mut bytes_guard) = bytes_clone.lock() {
-                        let new_total = *bytes_guard + sample_bytes as u64;
-                        if RecordingSize::check(new_total).is_err() {
-                            let _ = stop_tx_for_size.send(RecorderCommand::Stop);
-                            return;
-                        }
-                        *bytes_guard = new_total;
+                    stop_tx_clone.clone(),
+                    audio_tap.clone(),
+                ) {
+                    Ok(stream) => {
+                        log::info!(
+                            "Audio config: sample_rate={} Hz, channels={}, format={:?}",
+                            config.sample_rate,
+                            config.channels,
+                            config.sample_format
+                        );
+                        opened = Some(stream);
+                        break;
                     }
-
-                    // Write audio data (i16 format)
-                    if let Ok(mut guard) = writer_clone.try_lock() {
-                        if let Some(writer) = guard.as_mut() {
-                            for &sample in i16_samples {
-                                if let Err(e) = writer.write_sample(sample) {
-                                    if let Ok(mut error_guard) = error_clone.lock() {
-                                        *error_guard =
-                                            Some(format!("Failed to write audio sample: {}", e));
-                                    }
-                                    break;
-                                }
-                            }
-                        }
+                    Err(e) => {
+                        log::warn!("Input config {:?} failed to open: {}", config, e);
+                        last_error = e;
                     }
                 }
-            };
-
-            let stream = match config.sample_format() {
-                cpal::SampleFormat::F32 => {
-                    let process_clone = process_audio.clone();
-                    device
-                        .build_input_stream(
-                            &config.config(),
-                            move |data: &[f32], _: &_| {
-                                // Convert F32 to I16 with proper clamping to avoid distortion
-                                let i16_samples: Vec<i16> = data
-                                    .iter()
-                                    .map(|&sample| {
-                                        // Clamp to avoid overflow and use 32767.0 for symmetric conversion
-                                        let clamped = sample.clamp(-1.0, 1.0);
-                                        (clamped * 32767.0) as i16
-                                    })
-                                    .collect();
-
-                                // Process audio
-                                process_clone(data, &i16_samples);
-                            },
-                            err_fn,
-                            None,
-                        )
-                        .map_err(|e| e.to_string())?
-                }
-                cpal::SampleFormat::I16 => {
-                    let process_clone = process_audio.clone();
-                    device
-                        .build_input_stream(
-                            &config.config(),
-                            move |data: &[i16], _: &_| {
-                                // Convert I16 to F32 for processing
-                                let f32_samples: Vec<f32> =
-                                    data.iter().map(|&x| x as f32 / i16::MAX as f32).collect();
-
-                                // Process audio
-                                process_clone(&f32_samples, data);
-                            },
-                            err_fn,
-                            None,
-                        )
-                        .map_err(|e| e.to_string())?
-                }
-                cpal::SampleFormat::U16 => {
-                    device
-                        .build_input_stream(
-                            &config.config(),
-                            move |data: &[u16], _: &_| {
-                                // Convert U16 to F32 for processing
-                                let f32_samples: Vec<f32> = data
-                                    .iter()
-                                    .map(|&x| (x as f32 - 32768.0) / 32768.0)
-                                    .collect();
-
-                                // Convert U16 to I16 for writing
-                                let i16_samples: Vec<i16> =
-                                    data.iter().map(|&x| (x as i32 - 32768) as i16).collect();
-
-                                // Process audio
-                                process_audio(&f32_samples, &i16_samples);
-                            },
-                            err_fn,
-                            None,
-                        )
-                        .map_err(|e| e.to_string())?
-                }
-                _ => {
-                    return Err(format!(
-                        "Unsupported sample format: {:?}",
-                        config.sample_format()
-                    ))
-                }
-            };
-
-            stream.play().map_err(|e| {
-                log::error!("Failed to start audio stream: {}", e);
-                e.to_string()
-            })?;
+            }
+            let OpenedStream {
+                stream,
+                writer,
+                error_occurred,
+            } = opened.ok_or_else(|| format!("Failed to open audio input: {}", last_error))?;
 
             log::info!("Audio stream started successfully");
 
@@ -431,4 +336,209 @@ impl AudioRecorder {
             .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
             .unwrap_or_else(|_| Vec::new())
     }
+
+    /// Name of the OS default input device, if there is one
+    pub fn get_default_device() -> Option<String> {
+        cpal::default_host()
+            .default_input_device()
+            .and_then(|device| device.name().ok())
+    }
+}
+
+/// A started input stream and the WAV writer it feeds
+struct OpenedStream {
+    stream: cpal::Stream,
+    writer: Arc<Mutex<Option<hound::WavWriter<BufWriter<File>>>>>,
+    error_occurred: Arc<Mutex<Option<String>>>,
+}
+
+/// Open and start an input stream on `device` with `config`, writing to `output_path`
+fn open_recording_stream(
+    device: &cpal::Device,
+    config: InputConfig,
+    output_path: &Path,
+    silence_duration: Duration,
+    audio_level_tx: mpsc::Sender<f64>,
+    stop_tx: mpsc::Sender<RecorderCommand>,
+    audio_tap: Option<tokio::sync::mpsc::UnboundedSender<StreamedAudio>>,
+) -> Result<OpenedStream, String> {
+    let stream_config = config.stream_config();
+
+    // Initialize silence detector and level meter
+    let silence_detector = Arc::new(Mutex::new(SilenceDetector::new(silence_duration)));
+
+    let level_meter = Arc::new(Mutex::new(
+        AudioLevelMeter::new(config.sample_rate, config.channels as u32, audio_level_tx)
+            .map_err(|e| format!("Failed to create level meter: {}", e))?,
+    ));
+
+    // Record with native settings, Whisper will handle resampling
+    let spec = hound::WavSpec {
+        channels: config.channels,
+        sample_rate: config.sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let writer = Arc::new(Mutex::new(Some(
+        hound::WavWriter::create(output_path, spec).map_err(|e| e.to_string())?,
+    )));
+    let err_fn = |err| log::error!("Stream error: {}", err);
+    let error_occurred = Arc::new(Mutex::new(None::<String>));
+
+    // Shared state for size tracking
+    let bytes_written = Arc::new(Mutex::new(0u64));
+
+    // Common audio processing closure
+    let process_audio = {
+        let writer_clone = writer.clone();
+        let error_clone = error_occurred.clone();
+        let bytes_clone = bytes_written.clone();
+        let stop_tx_for_size = stop_tx.clone();
+        let stop_tx_for_silence = stop_tx.clone();
+        let silence_detector_clone = silence_detector.clone();
+        let level_meter_clone = level_meter.clone();
+        let format_sent = Arc::new(AtomicBool::new(false));
+
+        move |f32_samples: &[f32], i16_samples: &[i16]| {
+            // Calculate RMS for both level meter and silence detection
+            let sum: f32 = f32_samples.iter().map(|x| x * x).sum();
+            let rms = (sum / f32_samples.len() as f32).sqrt();
+
+            // Process with level meter
+            if let Ok(mut meter) = level_meter_clone.try_lock() {
+                let _ = meter.process_samples(f32_samples);
+            }
+
+            // Check for silence
+            if let Ok(mut detector) = silence_detector_clone.try_lock() {
+                if detector.update(rms) {
+                    // Silence duration exceeded, stop recording
+                    let _ = stop_tx_for_silence.send(RecorderCommand::StopSilence);
+                }
+            }
+
+            // Check size before writing
+            let sample_bytes = i16_samples.len() * 2; // 2 bytes per i16 sample
+            if let Ok(mut bytes_guard) = bytes_clone.lock() {
+                let new_total = *bytes_guard + sample_bytes as u64;
+                if RecordingSize::check(new_total).is_err() {
+                    let _ = stop_tx_for_size.send(RecorderCommand::Stop);
+                    return;
+                }
+                *bytes_guard = new_total;
+            }
+
+            if let Some(tap) = &audio_tap {
+                // Announce the format with the first buffer, so a config that
+                // failed to open never reaches the consumer
+                if !format_sent.swap(true, Ordering::SeqCst) {
+                    let _ = tap.send(StreamedAudio::Format {
+                        sample_rate: config.sample_rate,
+                        channels: config.channels,
+                    });
+                }
+                let _ = tap.send(StreamedAudio::Samples(i16_samples.to_vec()));
+            }
+
+            // Write audio data (i16 format)
+            if let Ok(mut guard) = writer_clone.try_lock() {
+                if let Some(writer) = guard.as_mut() {
+                    for &sample in i16_samples {
+                        if let Err(e) = writer.write_sample(sample) {
+                            if let Ok(mut error_guard) = error_clone.lock() {
+                                *error_guard = Some(format!("Failed to write audio sample: {}", e));
+                            }
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    let stream = match config.sample_format {
+        cpal::SampleFormat::F32 => {
+            let process_clone = process_audio.clone();
+            device
+                .build_input_stream(
+                    &stream_config,
+                    move |data: &[f32], _: &_| {
+                        // Convert F32 to I16 with proper clamping to avoid distortion
+                        let i16_samples: Vec<i16> = data
+                            .iter()
+                            .map(|&sample| {
+                                // Clamp to avoid overflow and use 32767.0 for symmetric conversion
+                                let clamped = sample.clamp(-1.0, 1.0);
+                                (clamped * 32767.0) as i16
+                            })
+                            .collect();
+
+                        // Process audio
+                        process_clone(data, &i16_samples);
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(|e| e.to_string())?
+        }
+        cpal::SampleFormat::I16 => {
+            let process_clone = process_audio.clone();
+            device
+                .build_input_stream(
+                    &stream_config,
+                    move |data: &[i16], _: &_| {
+                        // Convert I16 to F32 for processing
+                        let f32_samples: Vec<f32> =
+                            data.iter().map(|&x| x as f32 / i16::MAX as f32).collect();
+
+                        // Process audio
+                        process_clone(&f32_samples, data);
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(|e| e.to_string())?
+        }
+        cpal::SampleFormat::U16 => {
+            device
+                .build_input_stream(
+                    &stream_config,
+                    move |data: &[u16], _: &_| {
+                        // Convert U16 to F32 for processing
+                        let f32_samples: Vec<f32> = data
+                            .iter()
+                            .map(|&x| (x as f32 - 32768.0) / 32768.0)
+                            .collect();
+
+                        // Convert U16 to I16 for writing
+                        let i16_samples: Vec<i16> =
+                            data.iter().map(|&x| (x as i32 - 32768) as i16).collect();
+
+                        // Process audio
+                        process_audio(&f32_samples, &i16_samples);
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(|e| e.to_string())?
+        }
+        _ => {
+            return Err(format!(
+                "Unsupported sample format: {:?}",
+                config.sample_format
+            ))
+        }
+    };
+
+    stream.play().map_err(|e| {
+        log::error!("Failed to start audio stream: {}", e);
+        e.to_string()
+    })?;
+
+    Ok(OpenedStream {
+        stream,
+        writer,
+        error_occurred,
+    })
 }
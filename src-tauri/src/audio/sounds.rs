@@ -0,0 +1,131 @@
+use std::path::{Path, PathBuf};
+
+use crate::audio::player::PlaybackState;
+
+/// Apps whose being frontmost means the user is probably in a call.
+/// Matched case-insensitively against the start of the frontmost app's name.
+const MEETING_APPS: &[&str] = &[
+    "zoom",
+    "microsoft teams",
+    "teams",
+    "ms-teams",
+    "webex",
+    "cisco webex",
+    "facetime",
+    "skype",
+    "whereby",
+    "gotomeeting",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingSound {
+    Start,
+    End,
+}
+
+/// What to play for a recording sound
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SoundSource {
+    /// The user's own WAV file
+    Custom(PathBuf),
+    /// The platform sound (Tink/Pop on macOS, a beep on Windows)
+    Builtin,
+}
+
+/// The custom sound when one is set and still on disk, otherwise the built-in one
+pub fn resolve_sound_source(custom_path: Option<&str>) -> SoundSource {
+    let Some(path) = custom_path.map(str::trim).filter(|p| !p.is_empty()) else {
+        return SoundSource::Builtin;
+    };
+    let path = PathBuf::from(path);
+    if path.is_file() {
+        SoundSource::Custom(path)
+    } else {
+        log::warn!(
+            "Custom recording sound {:?} is missing, using the system sound",
+            path
+        );
+        SoundSource::Builtin
+    }
+}
+
+/// Check a custom sound before it is saved: it has to exist and be a WAV file
+/// the player can read
+pub fn validate_sound_file(path: &str) -> Result<(), String> {
+    let path = Path::new(path.trim());
+    if !path.is_file() {
+        return Err(format!("Sound file not found: {}", path.display()));
+    }
+    let reader = hound::WavReader::open(path)
+        .map_err(|e| format!("Sound file is not a playable WAV file: {}", e))?;
+    if reader.duration() == 0 {
+        return Err(format!("Sound file is empty: {}", path.display()));
+    }
+    Ok(())
+}
+
+pub fn is_meeting_app(app_name: &str) -> bool {
+    let name = app_name.trim().to_lowercase();
+    MEETING_APPS.iter().any(|app| name.starts_with(app))
+}
+
+/// Whether "mute in calls" silences a sound given the frontmost app
+pub fn should_mute_for_frontmost(mute_in_calls: bool, frontmost_app: Option<&str>) -> bool {
+    mute_in_calls && frontmost_app.is_some_and(is_meeting_app)
+}
+
+/// Play `sound` off the calling thread, skipping it when `mute_in_calls` is on
+/// and a meeting app is frontmost
+pub fn play_recording_sound(
+    sound: RecordingSound,
+    custom_path: Option<String>,
+    mute_in_calls: bool,
+) {
+    std::thread::spawn(move || {
+        // Looking up the frontmost app can take a moment, so only do it when it matters
+        if mute_in_calls {
+            let frontmost = crate::utils::active_app::frontmost_app_name();
+            if should_mute_for_frontmost(true, frontmost.as_deref()) {
+                log::debug!("Recording sound muted, {:?} is frontmost", frontmost);
+                return;
+            }
+        }
+
+        match resolve_sound_source(custom_path.as_deref()) {
+            SoundSource::Custom(path) => {
+                // A throwaway player so the sound never interrupts history playback
+                if let Err(e) = PlaybackState::default().play(&path, |_| {}) {
+                    log::warn!("Failed to play custom recording sound: {}", e);
+                    play_builtin_sound(sound);
+                }
+            }
+            SoundSource::Builtin => play_builtin_sound(sound),
+        }
+    });
+}
+
+#[cfg(target_os = "macos")]
+fn play_builtin_sound(sound: RecordingSound) {
+    let file = match sound {
+        RecordingSound::Start => "/System/Library/Sounds/Tink.aiff",
+        RecordingSound::End => "/System/Library/Sounds/Pop.aiff",
+    };
+    let _ = std::process::Command::new("afplay").arg(file).spawn();
+}
+
+#[cfg(target_os = "windows")]
+fn play_builtin_sound(sound: RecordingSound) {
+    let beep = match sound {
+        RecordingSound::Start => "[console]::beep(800, 100)",
+        RecordingSound::End => "[console]::beep(600, 100)",
+    };
+    // Use PowerShell to play a system sound on Windows
+    let _ = std::process::Command::new("powershell")
+        .args(["-c", beep])
+        .spawn();
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn play_builtin_sound(_sound: RecordingSound) {
+    // No-op on other platforms
+}
@@ -1,10 +1,16 @@
-use crate::ai::{AIEnhancementRequest, AIProviderConfig, AIProviderFactory, EnhancementOptions};
+use crate::ai::ollama::DEFAULT_OLLAMA_BASE_URL;
+use crate::ai::prompts::{render_prompt_template, template_uses_variable, PromptVariables};
+use crate::ai::{
+    AIEnhancementRequest, AIModel, AIProviderConfig, AIProviderFactory, ChunkCallback,
+    EnhancementOptions,
+};
 use crate::commands::audio::pill_toast;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Mutex;
+use tauri::Emitter;
 use tauri_plugin_store::StoreExt;
 
 // In-memory cache for API keys to avoid system password prompts
@@ -22,11 +28,21 @@ fn check_has_api_key<R: tauri::Runtime>(
     if provider == "openai" {
         let configured_base = store.get("ai_openai_base_url").is_some();
         configured_base || cache.contains_key(&format!("ai_api_key_{}", provider))
+    } else if provider == "ollama" {
+        // Local server, no key needed
+        true
     } else {
         cache.contains_key(&format!("ai_api_key_{}", provider))
     }
 }
 
+fn get_ollama_base_url<R: tauri::Runtime>(store: &tauri_plugin_store::Store<R>) -> String {
+    store
+        .get("ai_ollama_base_url")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| DEFAULT_OLLAMA_BASE_URL.to_string())
+}
+
 // Normalize base URL to a Chat Completions endpoint. Base should include version (e.g., .../v1).
 fn normalize_chat_completions_url(base: &str) -> String {
     let b = base.trim_end_matches('/');
@@ -35,6 +51,69 @@ fn normalize_chat_completions_url(base: &str) -> String {
 
 // removed unused validate_api_key helper
 
+// Build a minimal request that checks a key against the provider's own API.
+// Gemini lists the model (free); Anthropic sends a 1-token message.
+fn build_key_check_request(
+    client: &reqwest::Client,
+    provider: &str,
+    api_key: &str,
+    model: Option<&str>,
+) -> Result<reqwest::RequestBuilder, String> {
+    match provider {
+        "gemini" => {
+            let model = model.unwrap_or("gemini-2.5-flash-lite");
+            Ok(client
+                .get(format!(
+                    "https://generativelanguage.googleapis.com/v1beta/models/{}",
+                    model
+                ))
+                .header("x-goog-api-key", api_key))
+        }
+        "anthropic" => Ok(client
+            .post(crate::ai::anthropic::ANTHROPIC_API_URL)
+            .header("x-api-key", api_key)
+            .header("anthropic-version", crate::ai::anthropic::ANTHROPIC_VERSION)
+            .header("Content-Type", "application/json")
+            .json(&json!({
+                "model": model.unwrap_or("claude-3-5-haiku-latest"),
+                "max_tokens": 1,
+                "messages": [{"role": "user", "content": "1"}]
+            }))),
+        _ => Err("Unsupported provider".to_string()),
+    }
+}
+
+async fn check_provider_api_key(
+    provider: &str,
+    api_key: &str,
+    model: Option<&str>,
+) -> Result<(), String> {
+    if api_key.trim().is_empty() {
+        return Err("API key is required".to_string());
+    }
+
+    let client = reqwest::Client::new();
+    let response = build_key_check_request(&client, provider, api_key.trim(), model)?
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if status.is_success() {
+        Ok(())
+    } else {
+        let snippet: String = body.chars().take(500).collect();
+        log::error!(
+            "{} key validation failed: status={} body_snippet={}",
+            provider,
+            status,
+            snippet
+        );
+        Err(format!("HTTP {}: {}", status, snippet))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AISettings {
     pub enabled: bool,
@@ -50,7 +129,7 @@ lazy_static::lazy_static! {
 }
 
 // Supported AI providers
-const ALLOWED_PROVIDERS: &[&str] = &["groq", "gemini", "openai"];
+pub(crate) const ALLOWED_PROVIDERS: &[&str] = &["groq", "gemini", "openai", "anthropic", "ollama"];
 
 fn validate_provider_name(provider: &str) -> Result<(), String> {
     // First check format
@@ -279,6 +358,27 @@ pub async fn validate_and_cache_api_key(
             );
             return Err(format!("HTTP {}: {}", status, snippet));
         }
+    } else if provider == "gemini" || provider == "anthropic" {
+        check_provider_api_key(&provider, &provided_key, model.as_deref()).await?;
+    } else if provider == "ollama" {
+        let base = base_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_OLLAMA_BASE_URL.to_string());
+        crate::ai::ollama::list_local_models(&base)
+            .await
+            .map_err(|e| format!("Could not reach Ollama at {}: {}", base, e))?;
+
+        let store = app.store("settings").map_err(|e| e.to_string())?;
+        store.set("ai_ollama_base_url", serde_json::Value::String(base));
+        if let Some(m) = model.clone() {
+            store.set("ai_model", serde_json::Value::String(m));
+        }
+        store
+            .save()
+            .map_err(|e| format!("Failed to save AI settings: {}", e))?;
+
+        // Nothing to cache for a local server
+        return Ok(());
     } else {
         return Err("Unsupported provider".to_string());
     }
@@ -350,6 +450,36 @@ pub async fn test_openai_endpoint(
     }
 }
 
+/// Test a Gemini or Anthropic API key without saving or caching anything.
+#[tauri::command]
+pub async fn test_ai_provider_key(
+    provider: String,
+    api_key: String,
+    model: Option<String>,
+) -> Result<(), String> {
+    validate_provider_name(&provider)?;
+    check_provider_api_key(&provider, &api_key, model.as_deref()).await
+}
+
+/// List models installed in the local Ollama server (configured or given base URL).
+#[tauri::command]
+pub async fn list_ollama_models(
+    app: tauri::AppHandle,
+    base_url: Option<String>,
+) -> Result<Vec<AIModel>, String> {
+    let base_url = match base_url {
+        Some(url) if !url.trim().is_empty() => url,
+        _ => {
+            let store = app.store("settings").map_err(|e| e.to_string())?;
+            get_ollama_base_url(&store)
+        }
+    };
+
+    crate::ai::ollama::list_local_models(&base_url)
+        .await
+        .map_err(|e| format!("Could not reach Ollama at {}: {}", base_url, e))
+}
+
 // Frontend is responsible for removing API keys from Stronghold
 // This command clears the cache
 #[tauri::command]
@@ -403,7 +533,7 @@ pub async fn update_ai_settings(
     }
 
     // Check if API key exists when enabling
-    if enabled {
+    if enabled && provider != "ollama" {
         if provider == "openai" {
             let store = app.store("settings").map_err(|e| e.to_string())?;
             let cache_has_key = {
@@ -514,8 +644,224 @@ pub async fn update_enhancement_options(
     Ok(())
 }
 
+/// A user-defined enhancement style. Provider/model fall back to the global AI settings when unset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedEnhancementPreset {
+    pub name: String,
+    pub prompt: String,
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+fn load_enhancement_presets<R: tauri::Runtime>(
+    store: &tauri_plugin_store::Store<R>,
+) -> Vec<NamedEnhancementPreset> {
+    store
+        .get("enhancement_presets")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn load_active_preset_name<R: tauri::Runtime>(
+    store: &tauri_plugin_store::Store<R>,
+) -> Option<String> {
+    store
+        .get("active_enhancement_preset")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+}
+
+fn persist_enhancement_presets<R: tauri::Runtime>(
+    store: &tauri_plugin_store::Store<R>,
+    presets: &[NamedEnhancementPreset],
+    active: Option<&str>,
+) -> Result<(), String> {
+    store.set("enhancement_presets", json!(presets));
+    store.set("active_enhancement_preset", json!(active));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save enhancement presets: {}", e))
+}
+
+/// Insert a preset or replace the one with the same name
+pub(crate) fn upsert_enhancement_preset(
+    presets: &mut Vec<NamedEnhancementPreset>,
+    mut preset: NamedEnhancementPreset,
+) -> Result<(), String> {
+    preset.name = preset.name.trim().to_string();
+    if preset.name.is_empty() {
+        return Err("Preset name cannot be empty".to_string());
+    }
+    if preset.prompt.trim().is_empty() {
+        return Err("Preset prompt cannot be empty".to_string());
+    }
+    if let Some(provider) = preset.provider.as_deref() {
+        validate_provider_name(provider)?;
+    }
+
+    match presets.iter_mut().find(|p| p.name == preset.name) {
+        Some(existing) => *existing = preset,
+        None => presets.push(preset),
+    }
+    Ok(())
+}
+
+/// The active preset, if the pointer still refers to an existing one
+pub(crate) fn resolve_active_preset<'a>(
+    presets: &'a [NamedEnhancementPreset],
+    active: Option<&str>,
+) -> Option<&'a NamedEnhancementPreset> {
+    active.and_then(|name| presets.iter().find(|p| p.name == name))
+}
+
+/// Next preset in the cycle; wraps through "no preset" (the built-in options)
+pub(crate) fn next_enhancement_preset(
+    presets: &[NamedEnhancementPreset],
+    active: Option<&str>,
+) -> Option<String> {
+    let index = active.and_then(|name| presets.iter().position(|p| p.name == name));
+    match index {
+        None => presets.first(),
+        Some(i) => presets.get(i + 1),
+    }
+    .map(|p| p.name.clone())
+}
+
+// Keep clipboard context from dominating the prompt
+const MAX_CLIPBOARD_VARIABLE_CHARS: usize = 2000;
+
+/// Resolve only the variables a prompt actually references, so the clipboard
+/// and frontmost app are never read for prompts that don't use them.
+fn resolve_prompt_variables(template: &str) -> PromptVariables {
+    let now = chrono::Local::now();
+    let uses = |name| template_uses_variable(template, name);
+
+    PromptVariables {
+        date: uses("date").then(|| now.format("%Y-%m-%d").to_string()),
+        time: uses("time").then(|| now.format("%H:%M").to_string()),
+        active_app: uses("active_app")
+            .then(crate::utils::active_app::frontmost_app_name)
+            .flatten(),
+        clipboard: uses("clipboard")
+            .then(|| {
+                arboard::Clipboard::new()
+                    .and_then(|mut clipboard| clipboard.get_text())
+                    .ok()
+                    .map(|text| text.chars().take(MAX_CLIPBOARD_VARIABLE_CHARS).collect())
+            })
+            .flatten(),
+    }
+}
+
+#[tauri::command]
+pub async fn list_enhancement_presets(
+    app: tauri::AppHandle,
+) -> Result<Vec<NamedEnhancementPreset>, String> {
+    let store = app.store("settings").map_err(|e| e.to_string())?;
+    Ok(load_enhancement_presets(&store))
+}
+
+#[tauri::command]
+pub async fn get_active_enhancement_preset(
+    app: tauri::AppHandle,
+) -> Result<Option<String>, String> {
+    let store = app.store("settings").map_err(|e| e.to_string())?;
+    let presets = load_enhancement_presets(&store);
+    let active = load_active_preset_name(&store);
+    Ok(resolve_active_preset(&presets, active.as_deref()).map(|p| p.name.clone()))
+}
+
+#[tauri::command]
+pub async fn save_enhancement_preset(
+    app: tauri::AppHandle,
+    preset: NamedEnhancementPreset,
+) -> Result<(), String> {
+    let store = app.store("settings").map_err(|e| e.to_string())?;
+    let mut presets = load_enhancement_presets(&store);
+    let active = load_active_preset_name(&store);
+
+    upsert_enhancement_preset(&mut presets, preset)?;
+    persist_enhancement_presets(&store, &presets, active.as_deref())?;
+
+    let _ = app.emit("enhancement-presets-changed", ());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_enhancement_preset(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    let store = app.store("settings").map_err(|e| e.to_string())?;
+    let mut presets = load_enhancement_presets(&store);
+    let mut active = load_active_preset_name(&store);
+
+    let before = presets.len();
+    presets.retain(|p| p.name != name);
+    if presets.len() == before {
+        return Err(format!("Preset not found: {}", name));
+    }
+    if active.as_deref() == Some(name.as_str()) {
+        active = None;
+    }
+
+    persist_enhancement_presets(&store, &presets, active.as_deref())?;
+
+    let _ = app.emit("enhancement-presets-changed", ());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_active_enhancement_preset(
+    app: tauri::AppHandle,
+    name: Option<String>,
+) -> Result<(), String> {
+    let store = app.store("settings").map_err(|e| e.to_string())?;
+    let presets = load_enhancement_presets(&store);
+
+    if let Some(ref name) = name {
+        if resolve_active_preset(&presets, Some(name)).is_none() {
+            return Err(format!("Preset not found: {}", name));
+        }
+    }
+
+    persist_enhancement_presets(&store, &presets, name.as_deref())?;
+
+    let _ = app.emit("enhancement-presets-changed", ());
+    Ok(())
+}
+
+/// Advance to the next preset and show its name in the pill
+#[tauri::command]
+pub async fn cycle_enhancement_preset(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let store = app.store("settings").map_err(|e| e.to_string())?;
+    let presets = load_enhancement_presets(&store);
+    let active = load_active_preset_name(&store);
+
+    let next = next_enhancement_preset(&presets, active.as_deref());
+    persist_enhancement_presets(&store, &presets, next.as_deref())?;
+
+    log::info!("Active enhancement preset: {:?}", next);
+    pill_toast(
+        &app,
+        &format!("Preset: {}", next.as_deref().unwrap_or("Default")),
+        1500,
+    );
+    let _ = app.emit("enhancement-presets-changed", ());
+
+    Ok(next)
+}
+
 #[tauri::command]
 pub async fn enhance_transcription(text: String, app: tauri::AppHandle) -> Result<String, String> {
+    enhance_transcription_streaming(text, app, &|_| true).await
+}
+
+/// Enhance text, emitting `enhancing-chunk` events as output streams in.
+/// `on_chunk` sees every delta and can stop the stream by returning `false`.
+pub(crate) async fn enhance_transcription_streaming(
+    text: String,
+    app: tauri::AppHandle,
+    on_chunk: ChunkCallback<'_>,
+) -> Result<String, String> {
     // Quick validation
     if text.trim().is_empty() {
         log::debug!("Skipping enhancement for empty text");
@@ -544,6 +890,28 @@ pub async fn enhance_transcription(text: String, app: tauri::AppHandle) -> Resul
         .and_then(|v| v.as_str().map(|s| s.to_string()))
         .unwrap_or_else(|| "".to_string()); // Empty by default
 
+    // The active preset may override provider/model and supplies its own instructions
+    let active_preset = resolve_active_preset(
+        &load_enhancement_presets(&store),
+        load_active_preset_name(&store).as_deref(),
+    )
+    .cloned();
+    let (provider, model) = match active_preset {
+        Some(ref preset) => (
+            preset
+                .provider
+                .clone()
+                .filter(|p| !p.is_empty())
+                .unwrap_or(provider),
+            preset
+                .model
+                .clone()
+                .filter(|m| !m.is_empty())
+                .unwrap_or(model),
+        ),
+        None => (provider, model),
+    };
+
     // Don't enhance if no model selected
     if model.is_empty() {
         log::warn!(
@@ -585,7 +953,14 @@ pub async fn enhance_transcription(text: String, app: tauri::AppHandle) -> Resul
         opts.insert("no_auth".into(), serde_json::Value::Bool(cached.is_none()));
 
         (cached.unwrap_or_default(), opts)
-    } else if provider == "groq" || provider == "gemini" {
+    } else if provider == "ollama" {
+        let mut opts = std::collections::HashMap::new();
+        opts.insert(
+            "base_url".into(),
+            serde_json::Value::String(get_ollama_base_url(&store)),
+        );
+        (String::new(), opts)
+    } else if provider == "groq" || provider == "gemini" || provider == "anthropic" {
         // Require API key from in-memory cache
         let cache = API_KEY_CACHE
             .lock()
@@ -608,7 +983,15 @@ pub async fn enhance_transcription(text: String, app: tauri::AppHandle) -> Resul
     drop(store); // Release lock before async operation
 
     // Load enhancement options
-    let enhancement_options = get_enhancement_options(app.clone()).await.ok();
+    let mut enhancement_options = get_enhancement_options(app.clone()).await.ok();
+    if let (Some(options), Some(preset)) = (enhancement_options.as_mut(), active_preset) {
+        // Reading the clipboard and frontmost app shells out on some platforms
+        let template = preset.prompt.clone();
+        let variables = tokio::task::spawn_blocking(move || resolve_prompt_variables(&template))
+            .await
+            .map_err(|e| format!("Failed to resolve prompt variables: {}", e))?;
+        options.custom_prompt = Some(render_prompt_template(&preset.prompt, &variables));
+    }
 
     log::info!(
         "Enhancing text with {} model {} (length: {}, options: {:?})",
@@ -618,6 +1001,8 @@ pub async fn enhance_transcription(text: String, app: tauri::AppHandle) -> Resul
         enhancement_options
     );
 
+    let is_local_provider = provider == "ollama";
+
     // Create provider config
     let config = AIProviderConfig {
         provider,
@@ -637,7 +1022,12 @@ pub async fn enhance_transcription(text: String, app: tauri::AppHandle) -> Resul
         options: enhancement_options,
     };
 
-    match provider.enhance_text(request).await {
+    let forward = |delta: &str| {
+        let _ = app.emit("enhancing-chunk", json!({ "delta": delta }));
+        on_chunk(delta)
+    };
+
+    match provider.enhance_text_streaming(request, &forward).await {
         Ok(response) => {
             log::info!(
                 "Text enhanced successfully (original: {}, enhanced: {})",
@@ -646,6 +1036,16 @@ pub async fn enhance_transcription(text: String, app: tauri::AppHandle) -> Resul
             );
             Ok(response.enhanced_text)
         }
+        Err(crate::ai::AIError::NetworkError(e)) if is_local_provider => {
+            // Ollama not running: keep the original text rather than failing the flow
+            log::warn!("Ollama unreachable, using original text: {}", e);
+            pill_toast(&app, "Ollama not running - original text used", 1500);
+            Ok(text)
+        }
+        Err(crate::ai::AIError::Cancelled) => {
+            log::info!("AI formatting cancelled");
+            Err("Enhancement cancelled".to_string())
+        }
         Err(e) => {
             log::error!("AI formatting failed: {}", e);
             // Emit formatting error via pill toast
@@ -714,10 +1114,122 @@ mod tests {
         assert!(validate_provider_name("groq").is_ok());
         assert!(validate_provider_name("gemini").is_ok());
         assert!(validate_provider_name("openai").is_ok());
+        assert!(validate_provider_name("anthropic").is_ok());
+        assert!(validate_provider_name("ollama").is_ok());
         assert!(validate_provider_name("test-provider").is_err());
         assert!(validate_provider_name("test_provider").is_err());
         assert!(validate_provider_name("test provider").is_err());
         assert!(validate_provider_name("test@provider").is_err());
         assert!(validate_provider_name("").is_err());
     }
+
+    #[test]
+    fn test_key_check_request_shape() {
+        let client = reqwest::Client::new();
+
+        let gemini = build_key_check_request(&client, "gemini", "test_key_12345", None)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(gemini.method(), reqwest::Method::GET);
+        assert!(gemini
+            .url()
+            .as_str()
+            .ends_with("/v1beta/models/gemini-2.5-flash-lite"));
+        assert_eq!(gemini.headers()["x-goog-api-key"], "test_key_12345");
+
+        let anthropic = build_key_check_request(
+            &client,
+            "anthropic",
+            "test_key_12345",
+            Some("claude-sonnet-4-0"),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        assert_eq!(anthropic.method(), reqwest::Method::POST);
+        assert_eq!(anthropic.headers()["x-api-key"], "test_key_12345");
+        assert_eq!(anthropic.headers()["anthropic-version"], "2023-06-01");
+        let body: serde_json::Value =
+            serde_json::from_slice(anthropic.body().unwrap().as_bytes().unwrap()).unwrap();
+        assert_eq!(body["model"], "claude-sonnet-4-0");
+        assert_eq!(body["max_tokens"], 1);
+
+        assert!(build_key_check_request(&client, "groq", "test_key_12345", None).is_err());
+    }
+
+    fn preset(name: &str) -> NamedEnhancementPreset {
+        NamedEnhancementPreset {
+            name: name.to_string(),
+            prompt: format!("{} style", name),
+            provider: None,
+            model: None,
+        }
+    }
+
+    #[test]
+    fn test_enhancement_preset_crud() {
+        let mut presets = Vec::new();
+
+        upsert_enhancement_preset(&mut presets, preset("Email")).unwrap();
+        upsert_enhancement_preset(&mut presets, preset(" Code comment ")).unwrap();
+        assert_eq!(presets.len(), 2);
+        assert_eq!(presets[1].name, "Code comment");
+
+        // Same name replaces in place
+        let mut updated = preset("Email");
+        updated.prompt = "Formal email".to_string();
+        updated.provider = Some("anthropic".to_string());
+        upsert_enhancement_preset(&mut presets, updated).unwrap();
+        assert_eq!(presets.len(), 2);
+        assert_eq!(presets[0].prompt, "Formal email");
+
+        assert!(upsert_enhancement_preset(&mut presets, preset("  ")).is_err());
+        let mut blank_prompt = preset("Notes");
+        blank_prompt.prompt = " ".to_string();
+        assert!(upsert_enhancement_preset(&mut presets, blank_prompt).is_err());
+        let mut bad_provider = preset("Notes");
+        bad_provider.provider = Some("unknown".to_string());
+        assert!(upsert_enhancement_preset(&mut presets, bad_provider).is_err());
+        assert_eq!(presets.len(), 2);
+    }
+
+    #[test]
+    fn test_active_preset_pointer_round_trip() {
+        let presets = vec![preset("Email"), preset("Notes")];
+
+        // Stored form survives a serde round trip
+        let stored = json!(presets);
+        let loaded: Vec<NamedEnhancementPreset> = serde_json::from_value(stored).unwrap();
+        assert_eq!(loaded, presets);
+
+        assert_eq!(
+            resolve_active_preset(&loaded, Some("Notes")).map(|p| p.prompt.as_str()),
+            Some("Notes style")
+        );
+        // Dangling pointer (preset deleted) resolves to nothing
+        assert!(resolve_active_preset(&loaded, Some("Gone")).is_none());
+        assert!(resolve_active_preset(&loaded, None).is_none());
+    }
+
+    #[test]
+    fn test_cycle_enhancement_presets() {
+        let presets = vec![preset("Email"), preset("Notes")];
+
+        assert_eq!(
+            next_enhancement_preset(&presets, None).as_deref(),
+            Some("Email")
+        );
+        assert_eq!(
+            next_enhancement_preset(&presets, Some("Email")).as_deref(),
+            Some("Notes")
+        );
+        // Wraps back to the built-in options after the last preset
+        assert_eq!(next_enhancement_preset(&presets, Some("Notes")), None);
+        assert_eq!(
+            next_enhancement_preset(&presets, Some("Gone")).as_deref(),
+            Some("Email")
+        );
+        assert_eq!(next_enhancement_preset(&[], None), None);
+    }
 }
@@ -1,24 +1,41 @@
-use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
 use tauri::{AppHandle, Emitter, Manager, State};
 
+use crate::audio::checkpoint;
+use crate::audio::mic_test::{MicTestState, MIC_TEST_MAX_DURATION};
+use crate::audio::player::{resolve_recording_path, PlaybackState};
 use crate::audio::recorder::AudioRecorder;
+use crate::audio::sounds::{play_recording_sound, RecordingSound};
+use crate::commands::clipboard::{clipboard_audio_source, read_clipboard_contents, ClipboardAudio};
 use crate::commands::license::check_license_status_internal;
 use crate::commands::settings::get_settings;
 use crate::license::LicenseState;
 use crate::parakeet::messages::ParakeetResponse;
 use crate::parakeet::ParakeetManager;
+use crate::recognition::{
+    apply_voice_commands, collapse_repeated_phrases, normalize_spoken_numbers,
+};
+use crate::utils::attachments;
+use crate::utils::history_crypto;
+use crate::utils::history_store;
 use crate::utils::logger::*;
+use crate::utils::redaction;
 #[cfg(debug_assertions)]
 use crate::utils::system_monitor;
+use crate::utils::text_replace::{apply_replacement_rules, ReplacementRule};
 use crate::whisper::cache::TranscriberCache;
-use crate::whisper::languages::validate_language;
+use crate::whisper::chunking::{ChunkPlan, DEFAULT_CHUNK_THRESHOLD_SECS};
+use crate::whisper::languages::{get_language_name, resolve_engine_language, validate_language};
 use crate::whisper::manager::WhisperManager;
-use crate::{emit_to_window, update_recording_state, AppState, RecordingMode, RecordingState};
+use crate::whisper::pool::{TranscriptionPool, UploadSlot, DEFAULT_CONCURRENT_UPLOADS};
+use crate::whisper::transcriber::{partial_to_keep, DetectedLanguage, PartialTranscript};
+use crate::{emit_to_window, update_recording_state, AppState, RecordingState};
 use cpal::traits::{DeviceTrait, HostTrait};
 use serde_json;
+use std::collections::HashMap;
 use std::panic::{RefUnwindSafe, UnwindSafe};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tauri::async_runtime::{Mutex as AsyncMutex, RwLock as AsyncRwLock};
 use tauri_plugin_global_shortcut::GlobalShortcutExt;
@@ -35,11 +52,13 @@ pub struct PillToastPayload {
     pub duration_ms: u64,
 }
 
-/// Show a toast message on the pill's toast window (above the pill)
+/// Show a toast message on the pill's toast window (next to the pill)
 /// This is the single unified API for pill feedback messages.
 /// Uses atomic counter to prevent race conditions with overlapping toasts.
+/// `duration_ms` is scaled by the `toast_duration_scale` setting and reduced motion.
 pub fn pill_toast(app: &AppHandle, message: &str, duration_ms: u64) {
     let id = TOAST_ID_COUNTER.fetch_add(1, AtomicOrdering::SeqCst).wrapping_add(1);
+    let duration_ms = crate::utils::toast_layout::scaled_toast_duration(app, duration_ms);
 
     // Show toast window
     if let Some(toast_window) = app.get_webview_window("toast") {
@@ -91,30 +110,30 @@ pub async fn should_hide_pill(app: &AppHandle) -> bool {
     !show_pill_indicator // Hide only if show_pill_indicator is false
 }
 
-/// Play a system sound to confirm recording start (macOS only)
-#[cfg(target_os = "macos")]
-fn play_recording_start_sound() {
-    std::thread::spawn(|| {
-        let _ = std::process::Command::new("afplay")
-            .arg("/System/Library/Sounds/Tink.aiff")
-            .spawn();
-    });
-}
-
-/// Play a system sound to confirm recording start (Windows)
-#[cfg(target_os = "windows")]
-fn play_recording_start_sound() {
-    std::thread::spawn(|| {
-        // Use PowerShell to play a system sound on Windows
-        let _ = std::process::Command::new("powershell")
-            .args(["-c", "[console]::beep(800, 100)"])
-            .spawn();
-    });
-}
-
-#[cfg(not(any(target_os = "macos", target_os = "windows")))]
-fn play_recording_start_sound() {
-    // No-op on other platforms
+/// Play the recording start or end sound, if it is enabled in settings
+fn play_recording_sound_if_enabled(app: &AppHandle, sound: RecordingSound) {
+    let Ok(store) = app.store("settings") else {
+        return;
+    };
+    let (enabled_key, enabled_default, path_key) = match sound {
+        RecordingSound::Start => ("play_sound_on_recording", true, "start_sound_path"),
+        RecordingSound::End => ("play_sound_on_recording_end", false, "end_sound_path"),
+    };
+    let enabled = store
+        .get(enabled_key)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(enabled_default);
+    if !enabled {
+        return;
+    }
+    let custom_path = store
+        .get(path_key)
+        .and_then(|v| v.as_str().map(|s| s.to_string()));
+    let mute_in_calls = store
+        .get("mute_sounds_in_calls")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    play_recording_sound(sound, custom_path, mute_in_calls);
 }
 
 /// Cached recording configuration to avoid repeated store access during transcription flow
@@ -130,6 +149,30 @@ pub struct RecordingConfig {
     pub language: String,
     pub translate_to_english: bool,
     pub show_recording_status: bool,
+    pub keep_raw_audio: bool,
+    pub normalize_fallback_to_raw: bool,
+    pub ai_stream_insert: bool,
+    pub replacement_rules: Vec<ReplacementRule>,
+    pub voice_commands_enabled: bool,
+    pub voice_command_map: HashMap<String, String>,
+    pub normalize_numbers: bool,
+    pub deduplicate_repeats: bool,
+    pub soniox_streaming: bool,
+    pub low_confidence_threshold: f32,
+    pub warn_language_mismatch: bool,
+    pub min_recording_seconds: f32,
+    pub trim_silence_on_save: bool,
+    pub whisper_language: Option<String>,
+    pub parakeet_language: Option<String>,
+    pub soniox_language: Option<String>,
+    pub keep_partial_on_cancel: bool,
+    pub chunking: Option<ChunkPlan>,
+    pub fallback_blocklist: Vec<String>,
+    pub lock_target_window: bool,
+    pub show_result_summary: bool,
+    pub on_empty_transcription: EmptyTranscriptionAction,
+    pub tray_recording_timer: bool,
+    pub attach_screenshot: bool,
     // Internal cache metadata
     loaded_at: Instant,
 }
@@ -179,10 +222,111 @@ impl RecordingConfig {
                 .get("show_recording_status")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(true),
+            keep_raw_audio: store
+                .get("keep_raw_audio")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            normalize_fallback_to_raw: store
+                .get("normalize_fallback_to_raw")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true),
+            ai_stream_insert: store
+                .get("ai_stream_insert")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            replacement_rules: store
+                .get("replacement_rules")
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default(),
+            voice_commands_enabled: store
+                .get("voice_commands_enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            voice_command_map: store
+                .get("voice_command_map")
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default(),
+            normalize_numbers: store
+                .get("normalize_numbers")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            deduplicate_repeats: store
+                .get("deduplicate_repeats")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true),
+            soniox_streaming: store
+                .get("soniox_streaming")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            low_confidence_threshold: store
+                .get("low_confidence_threshold")
+                .and_then(|v| v.as_f64())
+                .map_or(0.5, |n| n as f32),
+            warn_language_mismatch: store
+                .get("warn_language_mismatch")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            min_recording_seconds: store
+                .get("min_recording_seconds")
+                .and_then(|v| v.as_f64())
+                .map_or(0.5, |n| n as f32),
+            trim_silence_on_save: store
+                .get("trim_silence_on_save")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            whisper_language: store
+                .get("whisper_language")
+                .and_then(|v| v.as_str().map(|s| s.to_string())),
+            parakeet_language: store
+                .get("parakeet_language")
+                .and_then(|v| v.as_str().map(|s| s.to_string())),
+            soniox_language: store
+                .get("soniox_language")
+                .and_then(|v| v.as_str().map(|s| s.to_string())),
+            keep_partial_on_cancel: store
+                .get("keep_partial_on_cancel")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            chunking: chunk_plan(app),
+            fallback_blocklist: store
+                .get("fallback_blocklist")
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default(),
+            lock_target_window: store
+                .get("lock_target_window")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            show_result_summary: store
+                .get("show_result_summary")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            on_empty_transcription: store
+                .get("on_empty_transcription")
+                .and_then(|v| EmptyTranscriptionAction::parse(v.as_str()?).ok())
+                .unwrap_or(EmptyTranscriptionAction::Toast),
+            tray_recording_timer: store
+                .get("tray_recording_timer")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            attach_screenshot: store
+                .get("attach_screenshot")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
             loaded_at: Instant::now(),
         })
     }
 
+    /// The language `engine` transcribes with, its override taking precedence
+    pub fn language_for_engine(&self, engine: &str) -> Option<String> {
+        let engine_override = match engine {
+            "whisper" => self.whisper_language.as_deref(),
+            "parakeet" => self.parakeet_language.as_deref(),
+            "soniox" => self.soniox_language.as_deref(),
+            _ => None,
+        };
+        resolve_engine_language(&self.language, engine_override)
+    }
+
     /// Check if this cache entry is still fresh
     pub fn is_fresh(&self) -> bool {
         self.loaded_at.elapsed() < Self::MAX_CACHE_AGE
@@ -240,6 +384,7 @@ async fn abort_due_to_missing_model(
 
     // Show pill toast for no models error
     pill_toast(app, user_message, 2000);
+    emit_recording_error(app, RecordingError::ModelMissing, user_message);
 
     // Also emit domain event for main window
     let _ = emit_to_window(
@@ -404,13 +549,382 @@ pub async fn get_recording_config(app: &AppHandle) -> Result<RecordingConfig, St
 // Global audio recorder state
 pub struct RecorderState(pub Mutex<AudioRecorder>);
 
+/// Phases reported through the consolidated `recording-progress` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingProgress {
+    Stopping,
+    Normalizing,
+    Transcribing,
+    Enhancing,
+    Inserting,
+    Completed,
+    NoSpeech,
+    Cancelled,
+    Failed,
+}
+
+impl RecordingProgress {
+    pub fn phase(&self) -> &'static str {
+        match self {
+            RecordingProgress::Stopping => "stopping",
+            RecordingProgress::Normalizing => "normalizing",
+            RecordingProgress::Transcribing => "transcribing",
+            RecordingProgress::Enhancing => "enhancing",
+            RecordingProgress::Inserting => "inserting",
+            RecordingProgress::Completed => "completed",
+            RecordingProgress::NoSpeech => "no_speech",
+            RecordingProgress::Cancelled => "cancelled",
+            RecordingProgress::Failed => "failed",
+        }
+    }
+
+    /// Rough position in the pipeline; terminal phases report 100
+    pub fn percent(&self) -> u8 {
+        match self {
+            RecordingProgress::Stopping => 5,
+            RecordingProgress::Normalizing => 15,
+            RecordingProgress::Transcribing => 30,
+            RecordingProgress::Enhancing => 70,
+            RecordingProgress::Inserting => 90,
+            RecordingProgress::Completed
+            | RecordingProgress::NoSpeech
+            | RecordingProgress::Cancelled
+            | RecordingProgress::Failed => 100,
+        }
+    }
+}
+
+/// Emit the consolidated progress event (the granular events are still emitted as before)
+fn emit_recording_progress(app: &AppHandle, progress: RecordingProgress) {
+    if let Err(e) = crate::emit_to_all(
+        app,
+        "recording-progress",
+        serde_json::json!({
+            "phase": progress.phase(),
+            "percent": progress.percent(),
+        }),
+    ) {
+        log::debug!("Failed to emit recording-progress: {}", e);
+    }
+}
+
+/// Failure categories reported through the `recording-error` event, next to the
+/// pill toast. The payload is `{ code, message, recoverable }`; codes are stable:
+///
+/// - `mic_permission_denied`: the OS refused microphone access
+/// - `no_input_device`: no microphone is connected, or the selected one is gone
+/// - `device_busy`: another app is holding the microphone
+/// - `recorder_failed`: the recorder couldn't start for another reason
+/// - `model_missing`: no usable model is installed (or the Soniox token is missing)
+/// - `license_required`: the trial expired and there is no license
+/// - `normalization_failed`: ffmpeg couldn't convert the capture for the engine
+/// - `ffmpeg_missing`: the bundled ffmpeg is missing or won't start; reinstall
+/// - `model_load_failed`: the selected model couldn't be loaded
+/// - `remote_unreachable`: the cloud engine couldn't be reached
+/// - `transcription_failed`: the engine ran but returned an error
+///
+/// `recoverable` is true when simply trying again may work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingError {
+    MicPermissionDenied,
+    NoInputDevice,
+    DeviceBusy,
+    RecorderFailed,
+    ModelMissing,
+    LicenseRequired,
+    NormalizationFailed,
+    FfmpegMissing,
+    ModelLoadFailed,
+    RemoteUnreachable,
+    TranscriptionFailed,
+}
+
+impl RecordingError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            RecordingError::MicPermissionDenied => "mic_permission_denied",
+            RecordingError::NoInputDevice => "no_input_device",
+            RecordingError::DeviceBusy => "device_busy",
+            RecordingError::RecorderFailed => "recorder_failed",
+            RecordingError::ModelMissing => "model_missing",
+            RecordingError::LicenseRequired => "license_required",
+            RecordingError::NormalizationFailed => "normalization_failed",
+            RecordingError::FfmpegMissing => "ffmpeg_missing",
+            RecordingError::ModelLoadFailed => "model_load_failed",
+            RecordingError::RemoteUnreachable => "remote_unreachable",
+            RecordingError::TranscriptionFailed => "transcription_failed",
+        }
+    }
+
+    pub fn recoverable(&self) -> bool {
+        matches!(
+            self,
+            RecordingError::DeviceBusy
+                | RecordingError::RecorderFailed
+                | RecordingError::NormalizationFailed
+                | RecordingError::RemoteUnreachable
+                | RecordingError::TranscriptionFailed
+        )
+    }
+
+    /// Classify an error from `AudioRecorder::start_recording`
+    pub fn from_recorder_error(error: &str) -> Self {
+        if error.contains("permission") || error.contains("access") {
+            RecordingError::MicPermissionDenied
+        } else if error.contains("device") || error.contains("not found") {
+            RecordingError::NoInputDevice
+        } else if error.contains("in use") || error.contains("busy") {
+            RecordingError::DeviceBusy
+        } else {
+            RecordingError::RecorderFailed
+        }
+    }
+
+    /// Classify an error from `ffmpeg::normalize_streaming`
+    pub fn from_normalization_error(error: &str) -> Self {
+        if crate::ffmpeg::is_ffmpeg_missing(error) {
+            RecordingError::FfmpegMissing
+        } else {
+            RecordingError::NormalizationFailed
+        }
+    }
+
+    /// Classify a failed transcription; only cloud engines can be unreachable
+    pub fn from_transcription_error(error: &str, cloud_engine: bool) -> Self {
+        let error = error.to_lowercase();
+        let network = [
+            "error sending request",
+            "connect",
+            "timed out",
+            "timeout",
+            "dns",
+            "unreachable",
+            "network",
+        ];
+        if cloud_engine && network.iter().any(|marker| error.contains(marker)) {
+            RecordingError::RemoteUnreachable
+        } else {
+            RecordingError::TranscriptionFailed
+        }
+    }
+
+    pub fn payload(&self, message: &str) -> serde_json::Value {
+        serde_json::json!({
+            "code": self.code(),
+            "message": message,
+            "recoverable": self.recoverable(),
+        })
+    }
+}
+
+/// Emit the machine-readable `recording-error` event; the toast stays the human-facing signal
+fn emit_recording_error(app: &AppHandle, error: RecordingError, message: &str) {
+    log::debug!("recording-error {}: {}", error.code(), message);
+    if let Err(e) = crate::emit_to_all(app, "recording-error", error.payload(message)) {
+        log::debug!("Failed to emit recording-error: {}", e);
+    }
+}
+
+/// Timing of one transcription, emitted as `transcription-metrics` and kept on the history entry
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct TranscriptionMetrics {
+    /// Time spent loading (or fetching the cached) model; 0 for cloud engines
+    pub model_load_ms: u64,
+    pub transcribe_ms: u64,
+    pub audio_duration_ms: Option<u64>,
+    /// `transcribe_ms / audio_duration_ms`; below 1.0 is faster than real time
+    pub realtime_factor: Option<f64>,
+}
+
+impl TranscriptionMetrics {
+    pub fn new(model_load_ms: u64, transcribe_ms: u64, audio_duration_ms: Option<u64>) -> Self {
+        let realtime_factor = audio_duration_ms
+            .filter(|&ms| ms > 0)
+            .map(|ms| ((transcribe_ms as f64 / ms as f64) * 1000.0).round() / 1000.0);
+        Self {
+            model_load_ms,
+            transcribe_ms,
+            audio_duration_ms,
+            realtime_factor,
+        }
+    }
+}
+
+/// Re-insert the last transcription exactly as the engine produced it, skipping AI enhancement
+#[tauri::command]
+pub async fn insert_last_raw_transcription(app: AppHandle) -> Result<(), String> {
+    let raw_text = app
+        .state::<AppState>()
+        .last_raw_transcription
+        .lock()
+        .map_err(|e| format!("Failed to read last transcription: {}", e))?
+        .clone()
+        .ok_or_else(|| "No transcription available to re-insert".to_string())?;
+
+    log::info!("Re-inserting raw transcription ({} chars)", raw_text.len());
+    crate::commands::text::insert_text(app, raw_text).await
+}
+
+/// Split off the leading run of complete words (up to and including the last whitespace).
+/// Leading whitespace is dropped while nothing has been typed yet, matching the trimmed final text.
+pub(crate) fn take_complete_words(pending: &mut String, at_start: bool) -> Option<String> {
+    if at_start {
+        let trimmed = pending.len() - pending.trim_start().len();
+        pending.drain(..trimmed);
+    }
+
+    let (idx, ws) = pending.char_indices().rfind(|(_, c)| c.is_whitespace())?;
+    Some(pending.drain(..idx + ws.len_utf8()).collect())
+}
+
+/// Part of `final_text` that wasn't typed while streaming. If the final text no
+/// longer starts with what was typed (post-processing changed its start), the
+/// rest after the longest common prefix is returned so nothing is silently lost.
+pub(crate) fn untyped_tail<'a>(final_text: &'a str, typed: &str) -> &'a str {
+    // Leading whitespace is never typed, see take_complete_words
+    let final_text = final_text.trim_start();
+    if let Some(tail) = final_text.strip_prefix(typed) {
+        return tail;
+    }
+
+    let common = final_text
+        .char_indices()
+        .zip(typed.chars())
+        .take_while(|((_, a), b)| a == b)
+        .last()
+        .map_or(0, |((i, c), _)| i + c.len_utf8());
+    log::warn!(
+        "Final text diverges from the streamed text after {} of {} bytes; inserting the rest",
+        common,
+        typed.len()
+    );
+    &final_text[common..]
+}
+
+/// Type streamed enhancement output as it arrives; returns everything that was inserted
+async fn insert_streamed_chunks(
+    app: AppHandle,
+    mut rx: tokio::sync::mpsc::UnboundedReceiver<String>,
+) -> String {
+    let mut pending = String::new();
+    let mut inserted = String::new();
+
+    while let Some(delta) = rx.recv().await {
+        pending.push_str(&delta);
+
+        if let Some(words) = take_complete_words(&mut pending, inserted.is_empty()) {
+            if let Err(e) =
+                crate::commands::text::insert_text_verbatim(app.clone(), words.clone()).await
+            {
+                log::warn!("Failed to insert streamed text: {}", e);
+            }
+            inserted.push_str(&words);
+        }
+    }
+
+    inserted
+}
+
+/// Whether the engine output contains no usable speech
+fn is_blank_transcription(text: &str) -> bool {
+    text.trim().is_empty() || text == "[BLANK_AUDIO]"
+}
+
+/// What happens when a recording transcribes to nothing (`on_empty_transcription`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyTranscriptionAction {
+    /// Tell the user in a pill toast
+    Toast,
+    /// Go back to idle without feedback
+    Silent,
+    /// Keep the audio and a placeholder history entry so it can be re-run
+    KeepRecording,
+}
+
+impl EmptyTranscriptionAction {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "toast" => Ok(Self::Toast),
+            "silent" => Ok(Self::Silent),
+            "keep_recording" => Ok(Self::KeepRecording),
+            other => Err(format!(
+                "Invalid on_empty_transcription '{}': expected toast, silent or keep_recording",
+                other
+            )),
+        }
+    }
+
+    /// Pill toast shown for an empty result, if any
+    pub fn toast_message(self) -> Option<&'static str> {
+        match self {
+            Self::Toast => Some("No speech detected - try speaking closer to the microphone"),
+            Self::Silent => None,
+            Self::KeepRecording => Some("No speech detected - recording kept in history"),
+        }
+    }
+
+    pub fn keeps_recording(self) -> bool {
+        self == Self::KeepRecording
+    }
+}
+
+/// Error stored on the placeholder entry of a recording kept with no speech
+pub(crate) const NO_SPEECH_ERROR: &str = "No speech detected";
+
+/// Metadata of a failed history entry: the entry's own metadata plus the failure
+/// and the recording to re-transcribe it from
+pub(crate) fn failed_entry_metadata(
+    metadata: &serde_json::Map<String, serde_json::Value>,
+    error: &str,
+    recording_file: &Path,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut failed_metadata = metadata.clone();
+    failed_metadata.insert("status".to_string(), serde_json::json!("failed"));
+    failed_metadata.insert("error".to_string(), serde_json::json!(error));
+    failed_metadata.insert(
+        "recording_file".to_string(),
+        serde_json::json!(recording_file.to_string_lossy()),
+    );
+    failed_metadata
+}
+
+/// Save a failed entry with empty text, to be re-transcribed from its recording
+async fn save_failed_entry(
+    app: &AppHandle,
+    model: String,
+    metadata: serde_json::Map<String, serde_json::Value>,
+) {
+    match save_transcription_with_metadata(app.clone(), String::new(), model, metadata).await {
+        Ok(_) => {
+            let _ = emit_to_window(app, "main", "history-updated", ());
+        }
+        Err(e) => log::error!("Failed to save failed transcription entry: {}", e),
+    }
+}
+
 /// Select the best fallback model based on available models
 /// Prioritizes models by size (smaller to larger for better performance)
-fn select_best_fallback_model(
+/// Models in `blocklist` are skipped unless nothing else is downloaded
+pub(crate) fn select_best_fallback_model(
     available_models: &[String],
     requested: &str,
     model_priority: &[String],
+    blocklist: &[String],
 ) -> String {
+    let allowed: Vec<String> = available_models
+        .iter()
+        .filter(|model| !blocklist.contains(model))
+        .cloned()
+        .collect();
+    let available_models = if allowed.is_empty() && !available_models.is_empty() {
+        log::warn!(
+            "Every downloaded model is on the fallback blocklist; choosing from all of them"
+        );
+        available_models
+    } else {
+        &allowed
+    };
+
     // First try to find a model similar to the requested one
     if !requested.is_empty() {
         // If requested "large-v3", try other large variants first
@@ -446,6 +960,11 @@ async fn validate_recording_requirements(app: &AppHandle) -> Result<(), String>
 
     if !availability.any_available() {
         log::error!("No speech recognition engines are ready");
+        emit_recording_error(
+            app,
+            RecordingError::ModelMissing,
+            "No speech recognition engine is ready",
+        );
         // Emit error event with guidance
         let _ = emit_to_window(
             app,
@@ -517,6 +1036,11 @@ async fn validate_recording_requirements(app: &AppHandle) -> Result<(), String>
 
     if matches!(status.status, LicenseState::Expired | LicenseState::None) {
         log::error!("Invalid license: {:?}", status.status);
+        emit_recording_error(
+            app,
+            RecordingError::LicenseRequired,
+            "License required to record",
+        );
 
         // Show and focus the main window
         if let Some(window) = app.get_webview_window("main") {
@@ -568,6 +1092,13 @@ pub async fn start_recording(
         );
     }
 
+    // A real recording takes the microphone from the settings mic test
+    if let Ok(mut mic_test) = app.state::<MicTestState>().0.lock() {
+        if mic_test.stop() {
+            log::info!("Stopped mic test to start recording");
+        }
+    }
+
     // Validate all requirements upfront
     let validation_start = Instant::now();
     match validate_recording_requirements(&app).await {
@@ -607,15 +1138,7 @@ pub async fn start_recording(
     }
 
     // Play sound on recording start if enabled
-    if let Ok(store) = app.store("settings") {
-        let play_sound = store
-            .get("play_sound_on_recording")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(true); // Default to true
-        if play_sound {
-            play_recording_start_sound();
-        }
-    }
+    play_recording_sound_if_enabled(&app, RecordingSound::Start);
 
     // Load recording config once to avoid repeated store access
     let config = get_recording_config(&app).await.map_err(|e| {
@@ -735,6 +1258,15 @@ pub async fn start_recording(
 
         log_file_operation("RECORDING_START", audio_path_str, false, None, None);
 
+        // Soniox realtime: captured audio also goes to a websocket session
+        let soniox_audio = if config.current_engine == "soniox" && config.soniox_streaming {
+            let (tap_tx, tap_rx) = tokio::sync::mpsc::unbounded_channel();
+            recorder.set_audio_tap(tap_tx);
+            Some(tap_rx)
+        } else {
+            None
+        };
+
         // Start recording and get audio level receiver
         let audio_level_rx = match recorder
             .start_recording(audio_path_str, selected_microphone.clone())
@@ -776,6 +1308,11 @@ pub async fn start_recording(
 
                     // Emit user-friendly error via pill toast
                     pill_toast(&app, "Microphone access failed", 1500);
+                    emit_recording_error(
+                        &app,
+                        RecordingError::RecorderFailed,
+                        "Recording failed to start after initialization",
+                    );
 
                     return Err("Failed to start recording".to_string());
                 } else {
@@ -825,6 +1362,7 @@ pub async fn start_recording(
                 };
 
                 pill_toast(&app, user_message, 1500);
+                emit_recording_error(&app, RecordingError::from_recorder_error(&e), &e);
 
                 return Err(e);
             }
@@ -833,6 +1371,19 @@ pub async fn start_recording(
         // Release the recorder lock after successful start
         drop(recorder);
 
+        if let Some(soniox_audio) = soniox_audio {
+            let session = tauri::async_runtime::spawn(crate::soniox::realtime::run_session(
+                app.clone(),
+                soniox_audio,
+                config.language_for_engine("soniox"),
+            ));
+            if let Ok(mut guard) = app_state.soniox_stream.lock() {
+                if let Some(stale) = guard.replace(session) {
+                    stale.abort();
+                }
+            }
+        }
+
         // Start audio level monitoring
         if let Some(audio_level_rx) = audio_level_rx {
             let app_for_levels = app.clone();
@@ -863,8 +1414,68 @@ pub async fn start_recording(
     // Clear cancellation flag for new recording
     app_state.clear_cancellation();
 
+    // Lets the next launch offer this recording if the app goes down before it stops
+    if let Ok(data_dir) = app.path().app_data_dir() {
+        let checkpoint = checkpoint::RecordingCheckpoint {
+            path: audio_path.clone(),
+            started_at: timestamp,
+            microphone: selected_microphone.clone(),
+        };
+        if let Err(e) = checkpoint::write_checkpoint(&data_dir, &checkpoint) {
+            log::warn!("{}", e);
+        }
+    }
+    // Picture of what was on screen when dictation began (attach_screenshot)
+    if config.attach_screenshot {
+        let output = attachments::attachment_path(&recordings_dir, timestamp);
+        let capture = tauri::async_runtime::spawn(async move {
+            match attachments::capture_screenshot(&output).await {
+                Ok(()) => Some(output),
+                Err(e) => {
+                    log::warn!("Failed to capture screenshot attachment: {}", e);
+                    let _ = std::fs::remove_file(&output);
+                    None
+                }
+            }
+        });
+        if let Ok(mut guard) = app_state.pending_attachment.lock() {
+            if let Some(stale) = guard.replace(capture) {
+                discard_pending_attachment(stale);
+            }
+        }
+    }
+    // A skip requested for an earlier recording that never reached enhancement
+    app_state.clear_skip_enhancement();
+
+    // Raw text from a previous recording must not be re-inserted after this one starts
+    if let Ok(mut raw_guard) = app_state.last_raw_transcription.lock() {
+        *raw_guard = None;
+    }
+
+    // Remember where this dictation should end up, before the user can switch away
+    let dictation_target = if config.lock_target_window {
+        tokio::task::spawn_blocking(|| {
+            crate::utils::target_window::target_to_capture(
+                crate::utils::target_window::frontmost_window(),
+                std::process::id(),
+            )
+        })
+        .await
+        .ok()
+        .flatten()
+    } else {
+        None
+    };
+    log::debug!("Dictation target window: {:?}", dictation_target);
+    if let Ok(mut target_guard) = app_state.dictation_target.lock() {
+        *target_guard = dictation_target;
+    }
+
     // Update state to recording
     update_recording_state(&app, RecordingState::Recording, None);
+    if config.tray_recording_timer {
+        crate::menu::start_tray_recording_timer(&app);
+    }
 
     // If a toggle-stop was requested while starting, honor it immediately after entering Recording
     if app_state
@@ -971,6 +1582,8 @@ pub async fn stop_recording(
     // Update state to stopping
     log_state_transition("RECORDING", "recording", "stopping", true, None);
     update_recording_state(&app, RecordingState::Stopping, None);
+    emit_recording_progress(&app, RecordingProgress::Stopping);
+    crate::menu::stop_tray_recording_timer(&app);
     // DO NOT request cancellation here - we want transcription to complete!
     // Cancellation should only happen in cancel_recording command
 
@@ -996,6 +1609,7 @@ pub async fn stop_recording(
             .stop_recording()
             .map_err(|e| format!("Failed to stop recording: {}", e))?;
         log::info!("{}", stop_message);
+        clear_recording_checkpoint(&app);
 
         // Monitor system resources after recording stop
         #[cfg(debug_assertions)]
@@ -1010,6 +1624,8 @@ pub async fn stop_recording(
         }
     } // MutexGuard dropped here BEFORE any await
 
+    play_recording_sound_if_enabled(&app, RecordingSound::End);
+
     // Unregister ESC key
     match "Escape".parse::<tauri_plugin_global_shortcut::Shortcut>() {
         Ok(escape_shortcut) => {
@@ -1065,6 +1681,7 @@ pub async fn stop_recording(
 
         // Transition to idle
         update_recording_state(&app, RecordingState::Idle, None);
+        emit_recording_progress(&app, RecordingProgress::Cancelled);
 
         return Ok("".to_string());
     }
@@ -1091,6 +1708,7 @@ pub async fn stop_recording(
             log::warn!("No audio file found - no recording was made");
             // Make sure to transition back to Idle state
             update_recording_state(&app, RecordingState::Idle, None);
+            emit_recording_progress(&app, RecordingProgress::NoSpeech);
             return Ok("".to_string());
         }
     };
@@ -1105,6 +1723,7 @@ pub async fn stop_recording(
             }
             // Frontend will hide pill after showing feedback
             update_recording_state(&app, RecordingState::Idle, None);
+            emit_recording_progress(&app, RecordingProgress::NoSpeech);
             return Ok("".to_string());
         }
     }
@@ -1225,6 +1844,7 @@ pub async fn stop_recording(
                         &downloaded_models,
                         &configured_model,
                         &models_by_size,
+                        &config.fallback_blocklist,
                     );
 
                     log_model_operation(
@@ -1256,8 +1876,12 @@ pub async fn stop_recording(
                 }
             } else {
                 let models_by_size = whisper_manager.read().await.get_models_by_size();
-                let best_model =
-                    select_best_fallback_model(&downloaded_models, "", &models_by_size);
+                let best_model = select_best_fallback_model(
+                    &downloaded_models,
+                    "",
+                    &models_by_size,
+                    &config.fallback_blocklist,
+                );
 
                 log_model_operation(
                     "AUTO_SELECTION",
@@ -1287,6 +1911,13 @@ pub async fn stop_recording(
         }
     };
 
+    // Pre-normalization capture kept for debugging when keep_raw_audio is on
+    let mut raw_audio_path: Option<PathBuf> = None;
+    // Set when normalization failed and the raw capture is transcribed directly
+    let mut used_raw_fallback = false;
+    // Length of the normalized recording, stored on the history entry for analytics
+    let mut recording_duration_secs: Option<f32> = None;
+
     // For Whisper/Parakeet: normalize and duration gate; for Soniox: skip both
     let audio_path = match &engine_selection {
         ActiveEngineSelection::Soniox { .. } => {
@@ -1294,52 +1925,62 @@ pub async fn stop_recording(
             audio_path
         }
         _ => {
+            emit_recording_progress(&app, RecordingProgress::Normalizing);
+
             // Normalize captured audio to Whisper contract (WAV PCM s16, mono, 16k) via ffmpeg sidecar
             let parent_dir = audio_path
                 .parent()
                 .map(|p| p.to_path_buf())
                 .unwrap_or_else(|| std::path::Path::new(".").to_path_buf());
 
-            let normalized_path = {
+            // A capture already in that shape is transcribed as is, skipping ffmpeg
+            let skipped_normalization = crate::ffmpeg::can_skip_normalization(&app, &audio_path);
+            let normalized_path = if skipped_normalization {
+                log::info!("[RECORD] Capture is already 16 kHz mono s16, skipping normalization");
+                audio_path.clone()
+            } else {
                 let ts = chrono::Local::now().format("%Y%m%d_%H%M%S");
                 let out_path = parent_dir.join(format!("normalized_{}.wav", ts));
-                if let Err(e) =
-                    crate::ffmpeg::normalize_streaming(&app, &audio_path, &out_path).await
-                {
-                    log::error!("Audio normalization (ffmpeg) failed: {}", e);
-                    update_recording_state(
-                        &app,
-                        RecordingState::Error,
-                        Some("Audio normalization failed".to_string()),
-                    );
-                    let _ = std::fs::remove_file(&audio_path);
-                    return Err("Audio normalization failed".to_string());
+                match crate::ffmpeg::normalize_streaming(&app, &audio_path, &out_path).await {
+                    Ok(_) => out_path,
+                    Err(e) if config.normalize_fallback_to_raw => {
+                        // Many engines tolerate non-16k input; try the raw capture before giving up
+                        log::warn!(
+                            "Audio normalization (ffmpeg) failed, falling back to raw capture: {}",
+                            e
+                        );
+                        let _ = std::fs::remove_file(&out_path);
+                        used_raw_fallback = true;
+                        audio_path.clone()
+                    }
+                    Err(e) => {
+                        log::error!("Audio normalization (ffmpeg) failed: {}", e);
+                        let error = RecordingError::from_normalization_error(&e);
+                        let message = match error {
+                            RecordingError::FfmpegMissing => e.clone(),
+                            _ => "Audio normalization failed".to_string(),
+                        };
+                        update_recording_state(&app, RecordingState::Error, Some(message.clone()));
+                        emit_recording_progress(&app, RecordingProgress::Failed);
+                        emit_recording_error(&app, error, &e);
+                        let _ = std::fs::remove_file(&audio_path);
+                        return Err(message);
+                    }
                 }
-                out_path
             };
 
-            // Remove raw capture after successful normalization
-            if let Err(e) = std::fs::remove_file(&audio_path) {
+            // Remove raw capture after successful normalization, unless kept for debugging
+            if used_raw_fallback || skipped_normalization {
+                // The raw capture is the transcription input; the task owns its cleanup
+            } else if config.keep_raw_audio {
+                log::info!("[RECORD] keep_raw_audio enabled — keeping {:?}", audio_path);
+                raw_audio_path = Some(audio_path.clone());
+            } else if let Err(e) = std::fs::remove_file(&audio_path) {
                 log::debug!("Failed to remove raw audio: {}", e);
             }
 
-            // Determine min duration based on recording mode (PTT vs Toggle) once
-            let (min_duration_s_f32, min_duration_label) = {
-                let app_state = app.state::<AppState>();
-                let mode = app_state
-                    .recording_mode
-                    .lock()
-                    .ok()
-                    .map(|g| *g)
-                    .unwrap_or(RecordingMode::Toggle);
-                match mode {
-                    RecordingMode::PushToTalk => (0.5f32, "0.5".to_string()),
-                    RecordingMode::Toggle => (0.5f32, "0.5".to_string()),
-                }
-            };
-
-            // Duration gate (mode-specific) using normalized file
-            let too_short = (|| -> Result<bool, String> {
+            // Duration gate using normalized file
+            let duration = (|| -> Result<f32, String> {
                 let reader = hound::WavReader::open(&normalized_path)
                     .map_err(|e| format!("Failed to open normalized wav: {}", e))?;
                 let spec = reader.spec();
@@ -1356,22 +1997,33 @@ pub async fn stop_recording(
                         ("duration_s", &format!("{:.2}", duration).as_str()),
                     ],
                 );
-                Ok(duration < min_duration_s_f32)
+                Ok(duration)
             })();
+            recording_duration_secs = duration.as_ref().ok().copied();
 
-            if let Ok(true) = too_short {
+            let too_short = duration
+                .as_ref()
+                .is_ok_and(|&duration| recording_too_short(duration, config.min_recording_seconds));
+            if too_short {
                 // Emit friendly feedback and stop here
                 let _ = emit_to_window(
                     &app,
                     "pill",
                     "recording-too-short",
-                    format!("Recording shorter than {} seconds", min_duration_label),
+                    format!(
+                        "Recording shorter than {} seconds",
+                        config.min_recording_seconds
+                    ),
                 );
                 if let Err(e) = std::fs::remove_file(&normalized_path) {
                     log::debug!("Failed to remove short normalized audio: {}", e);
                 }
+                if let Some(raw_path) = raw_audio_path.take() {
+                    let _ = std::fs::remove_file(raw_path);
+                }
                 // Frontend will hide pill after showing feedback
                 update_recording_state(&app, RecordingState::Idle, None);
+                emit_recording_progress(&app, RecordingProgress::NoSpeech);
                 return Ok("".to_string());
             }
 
@@ -1395,12 +2047,10 @@ pub async fn stop_recording(
         config.ai_enabled
     );
 
-    let language = if config.language.is_empty() {
-        None
-    } else {
-        Some(config.language.clone())
-    };
+    let language = config.language_for_engine(engine_selection.engine_name());
     let translate_to_english = config.translate_to_english;
+    let keep_partial_on_cancel = config.keep_partial_on_cancel;
+    let chunking = config.chunking;
 
     let engine_label = engine_selection.engine_name().to_string();
     let selected_model_name = engine_selection.model_name().to_string();
@@ -1417,9 +2067,11 @@ pub async fn stop_recording(
     );
 
     let audio_path_clone = audio_path.clone();
+    let raw_audio_path_for_task = raw_audio_path;
     let engine_selection_for_task = engine_selection;
     let language_for_task = language.clone();
     let selected_model_name_for_task = selected_model_name.clone();
+    let engine_label_for_task = engine_label.clone();
 
     // Spawn and track the transcription task
     let app_for_task = app.clone();
@@ -1428,6 +2080,7 @@ pub async fn stop_recording(
 
         // Update state to transcribing
         update_recording_state(&app_for_task, RecordingState::Transcribing, None);
+        emit_recording_progress(&app_for_task, RecordingProgress::Transcribing);
         // Also emit legacy event to pill window
         let _ = emit_to_window(&app_for_task, "pill", "transcription-started", ());
         // Give UI a moment to render the loader before heavy CPU work
@@ -1446,9 +2099,23 @@ pub async fn stop_recording(
             }
 
             update_recording_state(&app_for_task, RecordingState::Idle, None);
+            emit_recording_progress(&app_for_task, RecordingProgress::Cancelled);
             return;
         }
 
+        // Language reported by the engine (Parakeet), if any
+        let mut detected_language: Option<String> = None;
+
+        // Model load time is split out of the engine time for transcription-metrics
+        let engine_start = Instant::now();
+        let mut model_load_ms: u64 = 0;
+        // Only Whisper reports token probabilities and its own language guess
+        let mut transcription_confidence: Option<f32> = None;
+        let mut whisper_language: Option<DetectedLanguage> = None;
+
+        // Uploads queued behind this dictation wait until it's transcribed
+        let interactive_transcription = app_for_task.state::<TranscriptionPool>().interactive();
+
         let transcription_result: Result<String, String> = match &engine_selection_for_task {
             ActiveEngineSelection::Whisper { model_path, .. } => {
                 let transcriber = {
@@ -1462,66 +2129,62 @@ pub async fn stop_recording(
                                 RecordingState::Error,
                                 Some(e.clone()),
                             );
+                            emit_recording_progress(&app_for_task, RecordingProgress::Failed);
                             if should_hide_pill(&app_for_task).await {
                                 let _ = crate::commands::window::hide_pill_widget(app_for_task.clone())
                                     .await;
                             }
                             pill_toast(&app_for_task, &e, 1500);
+                            emit_recording_error(
+                                &app_for_task,
+                                RecordingError::ModelLoadFailed,
+                                &e,
+                            );
                             return;
                         }
                     }
                 };
-
-                const MAX_RETRIES: u32 = 3;
-                const RETRY_DELAY_MS: u64 = 500;
-
-                let mut result = Err("No attempt made".to_string());
-
-                for attempt in 1..=MAX_RETRIES {
-                    if app_state.is_cancellation_requested() {
-                        log::info!("Transcription cancelled at attempt {}", attempt);
-                        result = Err("Transcription cancelled".to_string());
-                        break;
-                    }
-
-                    result = transcriber.transcribe_with_cancellation(
-                        &audio_path_clone,
-                        language_for_task.as_deref(),
-                        translate_to_english,
-                        || app_state.is_cancellation_requested(),
-                    );
-
-                    match &result {
-                        Ok(_) => {
-                            if attempt > 1 {
-                                log::info!("Transcription succeeded on attempt {}", attempt);
-                            }
-                            break;
-                        }
-                        Err(e) => {
-                            if attempt < MAX_RETRIES {
-                                log::warn!(
-                                    "Transcription attempt {} failed: {}. Retrying in {}ms...",
-                                    attempt,
-                                    e,
-                                    RETRY_DELAY_MS
-                                );
-                                tokio::time::sleep(std::time::Duration::from_millis(
-                                    RETRY_DELAY_MS,
-                                ))
-                                .await;
-                            } else {
-                                log::error!(
-                                    "Transcription failed after {} attempts: {}",
-                                    MAX_RETRIES,
-                                    e
+                model_load_ms = engine_start.elapsed().as_millis() as u64;
+
+                let retry_policy = RetryPolicy::from_store(&app_for_task);
+                retry_with_backoff(
+                    retry_policy,
+                    "Transcription",
+                    |attempt| {
+                        let result = if app_state.is_cancellation_requested() {
+                            log::info!("Transcription cancelled at attempt {}", attempt);
+                            Err("Transcription cancelled".to_string())
+                        } else {
+                            let partial = PartialTranscript::default();
+                            let result = transcriber
+                                .transcribe_with_partial(
+                                    &audio_path_clone,
+                                    language_for_task.as_deref(),
+                                    translate_to_english,
+                                    app_state.should_cancel_recording.clone(),
+                                    &partial,
+                                    chunking,
+                                )
+                                .map(|output| {
+                                    transcription_confidence = output.confidence;
+                                    whisper_language = output.detected_language;
+                                    output.text
+                                });
+                            // Emit now; cancel_recording aborts this task at its next await
+                            if result.is_err() && app_state.is_cancellation_requested() {
+                                offer_partial_transcript(
+                                    &app_for_task,
+                                    partial_to_keep(keep_partial_on_cancel, &partial),
                                 );
                             }
-                        }
-                    }
-                }
-
-                result
+                            result
+                        };
+                        std::future::ready(result)
+                    },
+                    // A cancelled run stays cancelled
+                    |_| !app_state.is_cancellation_requested(),
+                )
+                .await
             }
             ActiveEngineSelection::Parakeet { model_name } => {
                 let parakeet_manager = app_for_task.state::<ParakeetManager>();
@@ -1532,9 +2195,12 @@ pub async fn stop_recording(
                         RecordingState::Error,
                         Some(message.clone()),
                     );
+                    emit_recording_progress(&app_for_task, RecordingProgress::Failed);
                     pill_toast(&app_for_task, &message, 1500);
+                    emit_recording_error(&app_for_task, RecordingError::ModelLoadFailed, &message);
                     return;
                 }
+                model_load_ms = engine_start.elapsed().as_millis() as u64;
 
                 match parakeet_manager
                     .transcribe(
@@ -1546,7 +2212,10 @@ pub async fn stop_recording(
                     )
                     .await
                 {
-                    Ok(ParakeetResponse::Transcription { text, .. }) => Ok(text),
+                    Ok(ParakeetResponse::Transcription { text, language, .. }) => {
+                        detected_language = language;
+                        Ok(text)
+                    }
                     Ok(other) => {
                         let message = format!("Unexpected Parakeet response: {:?}", other);
                         Err(message)
@@ -1555,24 +2224,192 @@ pub async fn stop_recording(
                 }
             }
             ActiveEngineSelection::Soniox { .. } => {
-                match soniox_transcribe_async(
-                    &app_for_task,
-                    &audio_path_clone,
-                    language_for_task.as_deref(),
-                )
-                .await
-                {
-                    Ok(text) => Ok(text),
-                    Err(e) => Err(e),
+                // A realtime session already has the transcript once it finalizes
+                let soniox_stream = app_for_task
+                    .state::<AppState>()
+                    .soniox_stream
+                    .lock()
+                    .ok()
+                    .and_then(|mut guard| guard.take());
+                let streamed = match soniox_stream {
+                    Some(session) => match session.await {
+                        Ok(result) => Some(result),
+                        Err(e) => Some(Err(format!("Soniox stream task failed: {}", e))),
+                    },
+                    None => None,
+                };
+
+                match streamed {
+                    Some(Ok(text)) => Ok(text),
+                    streamed => {
+                        if let Some(Err(e)) = streamed {
+                            log::warn!("Soniox stream failed, uploading instead: {}", e);
+                        }
+                        soniox_transcribe_async(
+                            &app_for_task,
+                            &audio_path_clone,
+                            language_for_task.as_deref(),
+                        )
+                        .await
+                    }
                 }
             }
         };
+        drop(interactive_transcription);
+
+        let metrics = TranscriptionMetrics::new(
+            model_load_ms,
+            (engine_start.elapsed().as_millis() as u64).saturating_sub(model_load_ms),
+            recording_duration_secs.map(|secs| (secs as f64 * 1000.0).round() as u64),
+        );
+        if transcription_result.is_ok() {
+            log::info!(
+                "[METRICS] model_load={}ms transcribe={}ms audio={:?}ms rtf={:?}",
+                metrics.model_load_ms,
+                metrics.transcribe_ms,
+                metrics.audio_duration_ms,
+                metrics.realtime_factor
+            );
+            if let Err(e) = crate::emit_to_all(&app_for_task, "transcription-metrics", &metrics) {
+                log::debug!("Failed to emit transcription-metrics: {}", e);
+            }
+        }
+
+        // Audio is only kept (keep_raw_audio) when it ends up in history
+        let keep_audio_files = config.keep_raw_audio
+            && !app_state.is_cancellation_requested()
+            && matches!(&transcription_result, Ok(text) if !is_blank_transcription(text));
+
+        // No speech with on_empty_transcription = keep_recording: keep it for a re-run
+        let preserve_empty_audio = config.on_empty_transcription.keeps_recording()
+            && !app_state.is_cancellation_requested()
+            && matches!(&transcription_result, Ok(text) if is_blank_transcription(text));
+
+        // Raw fallback also failed: keep the capture and record a failed entry for re-transcription
+        let preserve_failed_audio = used_raw_fallback
+            && !app_state.is_cancellation_requested()
+            && matches!(&transcription_result, Err(e) if !e.contains("cancelled"));
+
+        let mut history_metadata = serde_json::Map::new();
+        history_metadata.insert(
+            "engine".to_string(),
+            serde_json::json!(engine_label_for_task),
+        );
+
+        // The screenshot is kept only if this recording ends up in history
+        let saves_entry = preserve_failed_audio
+            || preserve_empty_audio
+            || (!app_state.is_cancellation_requested()
+                && matches!(&transcription_result, Ok(text) if !is_blank_transcription(text)));
+        if let Some(attachment) = take_pending_attachment(&app_for_task).await {
+            if saves_entry {
+                history_metadata.insert(
+                    attachments::ATTACHMENT_FIELD.to_string(),
+                    serde_json::json!(attachment.to_string_lossy()),
+                );
+            } else {
+                let _ = std::fs::remove_file(&attachment);
+            }
+        }
+        if let Some(duration) = recording_duration_secs {
+            history_metadata.insert(
+                "duration_seconds".to_string(),
+                serde_json::json!((duration as f64 * 100.0).round() / 100.0),
+            );
+        }
+        if transcription_result.is_ok() {
+            history_metadata.insert("metrics".to_string(), serde_json::json!(metrics));
+            if let Some(confidence) = transcription_confidence {
+                let rounded = (confidence as f64 * 1000.0).round() / 1000.0;
+                history_metadata.insert("confidence".to_string(), serde_json::json!(rounded));
+            }
+            if let Some(warning) =
+                low_confidence_warning(transcription_confidence, config.low_confidence_threshold)
+            {
+                log::warn!("Low confidence: {:?}", transcription_confidence);
+                pill_toast(&app_for_task, warning, 2500);
+            }
+            if let Some(detected) = &whisper_language {
+                insert_detected_language(&mut history_metadata, detected);
+            }
+            let mismatch =
+                language_mismatch_warning(whisper_language.as_ref(), language_for_task.as_deref());
+            if let (true, Some(warning)) = (config.warn_language_mismatch, mismatch) {
+                log::warn!("[LANGUAGE] {}", warning);
+                pill_toast(&app_for_task, &warning, 2500);
+            }
+        }
+        let auto_detect = language_for_task.as_deref().is_none_or(|l| l == "auto");
+        if let Some(entry_language) =
+            resolve_entry_language(language_for_task.as_deref(), detected_language.as_deref())
+        {
+            log::info!("[LANGUAGE] History entry language: {}", entry_language);
+            history_metadata.insert("language".to_string(), serde_json::json!(entry_language));
+            if auto_detect && detected_language.is_some() {
+                history_metadata.insert("language_detected".to_string(), serde_json::json!(true));
+            }
+        }
+
+        if preserve_failed_audio {
+            let error = transcription_result
+                .as_ref()
+                .err()
+                .cloned()
+                .unwrap_or_default();
+            log::warn!(
+                "Transcription of raw capture failed, preserving {:?}: {}",
+                audio_path_clone,
+                error
+            );
+
+            let failed_metadata =
+                failed_entry_metadata(&history_metadata, &error, &audio_path_clone);
+            save_failed_entry(
+                &app_for_task,
+                selected_model_name_for_task.clone(),
+                failed_metadata,
+            )
+            .await;
+        } else if preserve_empty_audio {
+            log::info!("No speech detected, keeping {:?}", audio_path_clone);
+            let placeholder =
+                failed_entry_metadata(&history_metadata, NO_SPEECH_ERROR, &audio_path_clone);
+            save_failed_entry(
+                &app_for_task,
+                selected_model_name_for_task.clone(),
+                placeholder,
+            )
+            .await;
+        } else if keep_audio_files {
+            if config.trim_silence_on_save {
+                if let Some(trimmed) = trim_saved_recording(&app_for_task, &audio_path_clone).await
+                {
+                    history_metadata.insert(
+                        "trimmed_duration_seconds".to_string(),
+                        serde_json::json!((trimmed as f64 * 100.0).round() / 100.0),
+                    );
+                }
+            }
+            history_metadata.insert(
+                "recording_file".to_string(),
+                serde_json::json!(audio_path_clone.to_string_lossy()),
+            );
+            if let Some(raw_path) = &raw_audio_path_for_task {
+                history_metadata.insert(
+                    "raw_recording_file".to_string(),
+                    serde_json::json!(raw_path.to_string_lossy()),
+                );
+            }
+        } else {
+            // Clean up temp files
+            if let Err(e) = std::fs::remove_file(&audio_path_clone) {
+                log::warn!("Failed to remove temporary audio file: {}", e);
+            }
+            if let Some(raw_path) = &raw_audio_path_for_task {
+                let _ = std::fs::remove_file(raw_path);
+            }
+        }
 
-        // Clean up temp file regardless of outcome
-        if let Err(e) = std::fs::remove_file(&audio_path_clone) {
-            log::warn!("Failed to remove temporary audio file: {}", e);
-        }
-
         match transcription_result {
             Ok(text) => {
                 // Final cancellation check before processing result
@@ -1589,21 +2426,21 @@ pub async fn stop_recording(
                     }
 
                     update_recording_state(&app_for_task, RecordingState::Idle, None);
+                    emit_recording_progress(&app_for_task, RecordingProgress::Cancelled);
                     return;
                 }
 
                 log::debug!("Transcription successful, {} chars", text.len());
 
                 // Check if transcription is empty or just noise
-                if text.is_empty() || text.trim().is_empty() || text == "[BLANK_AUDIO]" {
+                if is_blank_transcription(&text) {
                     log::info!("Whisper returned empty transcription - no speech detected");
+                    emit_recording_progress(&app_for_task, RecordingProgress::NoSpeech);
 
                     // Emit graceful feedback to user via pill toast
-                    pill_toast(
-                        &app_for_task,
-                        "No speech detected - try speaking closer to the microphone",
-                        1500,
-                    );
+                    if let Some(message) = config.on_empty_transcription.toast_message() {
+                        pill_toast(&app_for_task, message, 1500);
+                    }
 
                     // Wait for feedback to show before hiding pill
                     let app_for_hide = app_for_task.clone();
@@ -1626,12 +2463,34 @@ pub async fn stop_recording(
                     return;
                 }
 
-                // Check if AI enhancement is enabled from cached config
-                let ai_enabled = config.ai_enabled;
+                // Spoken commands and dictionary corrections run before AI enhancement
+                let text = post_process_transcription(
+                    &text,
+                    &config,
+                    resolve_entry_language(
+                        language_for_task.as_deref(),
+                        detected_language.as_deref(),
+                    )
+                    .as_deref(),
+                );
+
+                // Keep the un-enhanced text around for insert_last_raw_transcription
+                if let Ok(mut raw_guard) = app_state.last_raw_transcription.lock() {
+                    *raw_guard = Some(text.clone());
+                }
+
+                // Check if AI enhancement is enabled from cached config; the skip
+                // modifier held while stopping turns it off for this transcription only
+                let skip_enhancement = app_state.take_skip_enhancement();
+                if skip_enhancement && config.ai_enabled {
+                    log::info!("Skipping AI enhancement for this transcription");
+                }
+                let ai_enabled = config.ai_enabled && !skip_enhancement;
 
                 // If AI is enabled, emit enhancing event NOW while pill is still visible
                 if ai_enabled {
                     let _ = app_for_task.emit("enhancing-started", ());
+                    emit_recording_progress(&app_for_task, RecordingProgress::Enhancing);
                 }
 
                 // Backend handles the complete flow
@@ -1639,18 +2498,129 @@ pub async fn stop_recording(
                 let text_for_process = text.clone();
                 let model_for_process = selected_model_name_for_task.clone();
                 let ai_enabled_for_task = ai_enabled; // Capture from cached config
+                let stream_insert_for_task = config.ai_stream_insert;
+                let show_result_summary = config.show_result_summary;
 
                 tokio::spawn(async move {
+                    // Resolved up front so streaming never types into a clipboard-only app
+                    let output_mode =
+                        crate::commands::text::current_output_mode(&app_for_process).await;
+                    let stream_insert_for_task = stream_insert_for_task && output_mode.inserts();
+
+                    // Text already typed while enhancement output was streaming in
+                    let mut streamed_prefix = String::new();
+
                     // 1. Process the transcription and enhancement
                     let final_text = {
                         // Use the captured AI enabled status from cached config
                         if ai_enabled_for_task {
-                            match crate::commands::ai::enhance_transcription(
+                            let (chunk_tx, inserter) = if stream_insert_for_task {
+                                let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+                                let inserter = tokio::spawn(insert_streamed_chunks(
+                                    app_for_process.clone(),
+                                    rx,
+                                ));
+                                (Some(tx), Some(inserter))
+                            } else {
+                                (None, None)
+                            };
+
+                            let app_for_chunks = app_for_process.clone();
+                            let on_chunk = move |delta: &str| {
+                                // Stop streaming as soon as the user cancels
+                                if app_for_chunks
+                                    .state::<AppState>()
+                                    .is_cancellation_requested()
+                                {
+                                    return false;
+                                }
+                                if let Some(tx) = &chunk_tx {
+                                    let _ = tx.send(delta.to_string());
+                                }
+                                true
+                            };
+
+                            let enhancement = crate::commands::ai::enhance_transcription_streaming(
                                 text_for_process.clone(),
                                 app_for_process.clone(),
+                                &on_chunk,
                             )
-                            .await
-                            {
+                            .await;
+
+                            // Closing the channel lets the inserter finish its pending words
+                            drop(on_chunk);
+                            if let Some(inserter) = inserter {
+                                streamed_prefix = inserter.await.unwrap_or_default();
+                            }
+
+                            match enhancement {
+                                Err(e) if e.contains("cancelled") => {
+                                    let _ = app_for_process.emit("enhancing-failed", ());
+                                    if streamed_prefix.is_empty() {
+                                        log::info!("Enhancement cancelled, discarding result");
+                                    } else {
+                                        // Words typed before the cancel stay in the document,
+                                        // so history keeps them too
+                                        log::info!(
+                                            "Enhancement cancelled after streaming {} bytes, saving them",
+                                            streamed_prefix.len()
+                                        );
+                                        history_metadata.insert(
+                                            "raw_text".to_string(),
+                                            serde_json::json!(text_for_process),
+                                        );
+                                        match save_transcription_with_metadata(
+                                            app_for_process.clone(),
+                                            streamed_prefix.clone(),
+                                            model_for_process.clone(),
+                                            history_metadata,
+                                        )
+                                        .await
+                                        {
+                                            Ok(_) => {
+                                                let _ = emit_to_window(
+                                                    &app_for_process,
+                                                    "main",
+                                                    "history-updated",
+                                                    (),
+                                                );
+                                            }
+                                            Err(e) => log::error!(
+                                                "Failed to save partial transcription to history: {}",
+                                                e
+                                            ),
+                                        }
+                                        pill_toast(
+                                            &app_for_process,
+                                            "Cancelled - partial text saved in history",
+                                            1500,
+                                        );
+                                    }
+
+                                    if should_hide_pill(&app_for_process).await {
+                                        if let Err(e) = crate::commands::window::hide_pill_widget(
+                                            app_for_process.clone(),
+                                        )
+                                        .await
+                                        {
+                                            log::error!(
+                                                "Failed to hide pill window on cancellation: {}",
+                                                e
+                                            );
+                                        }
+                                    }
+
+                                    update_recording_state(
+                                        &app_for_process,
+                                        RecordingState::Idle,
+                                        None,
+                                    );
+                                    emit_recording_progress(
+                                        &app_for_process,
+                                        RecordingProgress::Cancelled,
+                                    );
+                                    return;
+                                }
                                 Ok(enhanced) => {
                                     // Emit enhancing completed event (global)
                                     let _ = app_for_process.emit("enhancing-completed", ());
@@ -1704,7 +2674,12 @@ pub async fn stop_recording(
                                         );
                                     }
 
-                                    text_for_process.clone() // Fall back to original text
+                                    if streamed_prefix.is_empty() {
+                                        text_for_process.clone() // Fall back to original text
+                                    } else {
+                                        // Part of the output is already typed; keep that
+                                        streamed_prefix.clone()
+                                    }
                                 }
                             }
                         } else {
@@ -1731,18 +2706,55 @@ pub async fn stop_recording(
                     tokio::time::sleep(std::time::Duration::from_millis(50)).await;
 
                     // Now handle text insertion with stable UI
-                    match crate::commands::text::insert_text(
-                        app_for_process.clone(),
-                        final_text.clone(),
-                    )
-                    .await
-                    {
-                        Ok(_) => log::debug!("Text inserted at cursor successfully"),
+                    emit_recording_progress(&app_for_process, RecordingProgress::Inserting);
+                    let text_to_insert = if streamed_prefix.is_empty() {
+                        final_text.clone()
+                    } else {
+                        // Only the tail that wasn't typed during streaming
+                        untyped_tail(&final_text, &streamed_prefix).to_string()
+                    };
+                    let insert_result = if !output_mode.inserts() || text_to_insert.is_empty() {
+                        Ok(())
+                    } else if streamed_prefix.is_empty() {
+                        crate::commands::text::insert_text(app_for_process.clone(), text_to_insert)
+                            .await
+                    } else {
+                        // Per-app rules only apply to complete text, not a streamed tail
+                        crate::commands::text::insert_text_verbatim(
+                            app_for_process.clone(),
+                            text_to_insert,
+                        )
+                        .await
+                    };
+                    let insert_result = match insert_result {
+                        // Copy after inserting so paste_preserve_clipboard can't undo it
+                        Ok(_) if output_mode.copies() => {
+                            crate::commands::text::copy_text_to_clipboard(final_text.clone()).await
+                        }
+                        result => result,
+                    };
+                    match insert_result {
+                        Ok(_) if !output_mode.inserts() => {
+                            log::debug!("Text copied to clipboard (clipboard-only output)");
+                            pill_toast(&app_for_process, "Copied to clipboard", 1000);
+                        }
+                        Ok(_) => {
+                            log::debug!("Text inserted at cursor successfully");
+                            if show_result_summary {
+                                pill_toast(
+                                    &app_for_process,
+                                    &result_summary(&final_text, recording_duration_secs),
+                                    1500,
+                                );
+                            }
+                        }
                         Err(e) => {
                             log::error!("Failed to insert text: {}", e);
 
                             // Check if it's an accessibility permission issue
-                            if e.contains("accessibility") || e.contains("permission") {
+                            if !output_mode.inserts() {
+                                pill_toast(&app_for_process, "Copy to clipboard failed", 1500);
+                            } else if e.contains("accessibility") || e.contains("permission") {
                                 // Show pill toast for accessibility permission error
                                 pill_toast(
                                     &app_for_process,
@@ -1761,14 +2773,20 @@ pub async fn stop_recording(
                     }
 
                     // 5. Save transcription to history (async, non-blocking)
+                    if ai_enabled_for_task {
+                        // Pre-enhancement text so history can show what the AI changed
+                        history_metadata
+                            .insert("raw_text".to_string(), serde_json::json!(text_for_process));
+                    }
                     let app_for_history = app_for_process.clone();
                     let history_text = final_text.clone();
                     let history_model = model_for_process.clone();
                     tokio::spawn(async move {
-                        match save_transcription(
+                        match save_transcription_with_metadata(
                             app_for_history.clone(),
                             history_text,
                             history_model,
+                            history_metadata,
                         )
                         .await
                         {
@@ -1784,6 +2802,7 @@ pub async fn stop_recording(
 
                     // 6. Transition to idle state
                     update_recording_state(&app_for_process, RecordingState::Idle, None);
+                    emit_recording_progress(&app_for_process, RecordingProgress::Completed);
                 });
             }
             Err(e) => {
@@ -1799,13 +2818,17 @@ pub async fn stop_recording(
                         }
                     }
                     update_recording_state(&app_for_task, RecordingState::Idle, None);
+                    emit_recording_progress(&app_for_task, RecordingProgress::Cancelled);
                 } else if e.contains("too short") {
                     // Handle "too short" errors with specific user feedback
                     log::info!("Recording was too short: {}", e);
+                    emit_recording_progress(&app_for_task, RecordingProgress::NoSpeech);
 
-                    // Clean up the audio file
-                    if let Err(cleanup_err) = std::fs::remove_file(&audio_path_clone) {
-                        log::warn!("Failed to remove short audio file: {}", cleanup_err);
+                    // Clean up the audio file, unless the failed entry saved above points at it
+                    if !preserve_failed_audio {
+                        if let Err(cleanup_err) = std::fs::remove_file(&audio_path_clone) {
+                            log::warn!("Failed to remove short audio file: {}", cleanup_err);
+                        }
                     }
 
                     // Emit specific feedback via pill toast
@@ -1830,9 +2853,27 @@ pub async fn stop_recording(
                 } else {
                     // For other errors, show error state briefly
                     update_recording_state(&app_for_task, RecordingState::Error, Some(e.clone()));
+                    emit_recording_progress(&app_for_task, RecordingProgress::Failed);
+                    let cloud_engine = matches!(
+                        engine_selection_for_task,
+                        ActiveEngineSelection::Soniox { .. }
+                    );
+                    emit_recording_error(
+                        &app_for_task,
+                        RecordingError::from_transcription_error(&e, cloud_engine),
+                        &e,
+                    );
 
                     // Emit error via pill toast
-                    pill_toast(&app_for_task, &e, 1500);
+                    if preserve_failed_audio {
+                        pill_toast(
+                            &app_for_task,
+                            "Transcription failed - audio saved in history",
+                            1500,
+                        );
+                    } else {
+                        pill_toast(&app_for_task, &e, 1500);
+                    }
 
                     // Transition back to Idle after a delay
                     // This ensures we don't get stuck in Error state
@@ -1922,95 +2963,235 @@ pub async fn get_current_audio_device(app: AppHandle) -> Result<String, String>
         .ok_or_else(|| "No default input device found".to_string())
 }
 
+/// Start the settings mic test: capture from `device_name` (the selected
+/// microphone when omitted) and stream `audio-level` events to the main window.
+/// Nothing is transcribed or kept; the capture stops itself after
+/// [`MIC_TEST_MAX_DURATION`] if `stop_mic_test` is never called.
+#[tauri::command]
+pub async fn start_mic_test(app: AppHandle, device_name: Option<String>) -> Result<(), String> {
+    let device = match device_name {
+        Some(name) => Some(name),
+        None => get_settings(app.clone())
+            .await
+            .ok()
+            .and_then(|settings| settings.selected_microphone),
+    };
+    let capture_path = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| e.to_string())?
+        .join("mic_test.wav");
+    if let Some(parent) = capture_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+    }
+
+    let mic_test_state = app.state::<MicTestState>();
+    let (levels, session) = {
+        let mut mic_test = mic_test_state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to acquire mic test lock: {}", e))?;
+        mic_test.check_can_start(crate::get_recording_state(&app))?;
+        let levels = mic_test.start(capture_path, device)?;
+        (levels, mic_test.session())
+    };
+    log::info!("Mic test started");
+
+    let app_for_levels = app.clone();
+    std::thread::spawn(move || {
+        let mut last_emit = std::time::Instant::now();
+        let emit_interval = std::time::Duration::from_millis(100); // Throttle to 10fps
+
+        // Ends when the recorder is dropped by stop_mic_test
+        while let Ok(level) = levels.recv() {
+            if last_emit.elapsed() >= emit_interval {
+                let _ = emit_to_window(&app_for_levels, "main", "audio-level", level);
+                last_emit = std::time::Instant::now();
+            }
+        }
+    });
+
+    let app_for_timeout = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(MIC_TEST_MAX_DURATION).await;
+        let state = app_for_timeout.state::<MicTestState>();
+        if let Ok(mut mic_test) = state.0.lock() {
+            if mic_test.session() == session && mic_test.stop() {
+                log::info!("Mic test stopped after reaching its time limit");
+            }
+        };
+    });
+
+    Ok(())
+}
+
+/// Stop the settings mic test. Does nothing if no test is running.
+#[tauri::command]
+pub async fn stop_mic_test(app: AppHandle) -> Result<(), String> {
+    let state = app.state::<MicTestState>();
+    let mut mic_test = state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to acquire mic test lock: {}", e))?;
+    if mic_test.stop() {
+        log::info!("Mic test stopped");
+    }
+    Ok(())
+}
+
+/// Play a saved recording (a file name in the recordings folder) on the
+/// default output device. Returns once playback starts; `playback-finished`
+/// follows with `completed: false` if it was stopped or replaced.
+#[tauri::command]
+pub async fn play_recording(app: AppHandle, filename: String) -> Result<(), String> {
+    let recordings_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("recordings");
+    let path = resolve_recording_path(&recordings_dir, &filename)?;
+
+    let app_for_finish = app.clone();
+    let filename_for_finish = filename.clone();
+    app.state::<PlaybackState>().play(&path, move |completed| {
+        let _ = emit_to_window(
+            &app_for_finish,
+            "main",
+            "playback-finished",
+            serde_json::json!({ "filename": filename_for_finish, "completed": completed }),
+        );
+    })?;
+    log::info!("Playing recording {}", filename);
+    Ok(())
+}
+
+/// Stop the recording started by `play_recording`, if any
+#[tauri::command]
+pub async fn stop_playback(app: AppHandle) -> Result<(), String> {
+    app.state::<PlaybackState>().stop();
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn cleanup_old_transcriptions(app: AppHandle, days: Option<u32>) -> Result<(), String> {
     if let Some(days) = days {
-        let store = app.store("transcriptions").map_err(|e| e.to_string())?;
-
         let cutoff_date = chrono::Utc::now() - chrono::Duration::days(days as i64);
+        prune_history_before(&app, cutoff_date).await?;
+    }
+
+    Ok(())
+}
 
-        // Get all keys
-        let keys: Vec<String> = store.keys().into_iter().map(|k| k.to_string()).collect();
+/// History keys (RFC3339 timestamps) older than `cutoff`. Keys that don't parse are kept.
+pub(crate) fn expired_history_keys(
+    keys: Vec<String>,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> Vec<String> {
+    keys.into_iter()
+        .filter(|key| chrono::DateTime::parse_from_rfc3339(key).is_ok_and(|date| date < cutoff))
+        .collect()
+}
 
-        // Remove old entries
-        for key in keys {
-            if let Ok(date) = chrono::DateTime::parse_from_rfc3339(&key) {
-                if date < cutoff_date {
-                    store.delete(&key);
-                }
-            }
+/// Delete history entries saved before `cutoff`. Returns how many were removed.
+pub(crate) async fn prune_history_before(
+    app: &AppHandle,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> Result<usize, String> {
+    let keys: Vec<String> = history_store::read_raw_entries(app)?
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    let expired = expired_history_keys(keys, cutoff);
+    if !expired.is_empty() {
+        // Like deleting an entry, drop the audio and screenshot kept for it
+        for entry in history_store::delete_raw_entries(app, &expired)? {
+            remove_entry_files(&entry);
         }
+    }
 
-        store.save().map_err(|e| e.to_string())?;
+    if !expired.is_empty() {
+        let _ = emit_to_window(app, "main", "history-updated", ());
+        // Pruned entries may have been listed in the tray's recent submenu
+        if let Err(e) = crate::commands::settings::update_tray_menu(app.clone()).await {
+            log::warn!("Failed to update tray menu after cleanup: {}", e);
+        }
     }
 
-    Ok(())
+    Ok(expired.len())
 }
 
 #[tauri::command]
 pub async fn save_transcription(app: AppHandle, text: String, model: String) -> Result<(), String> {
-    // De-dup guard: skip saving if the most recent entry matches the same text & model within a short window
-    if let Ok(store) = app.store("transcriptions") {
-        // Find most recent entry
-        let mut latest: Option<(String, serde_json::Value)> = None;
-        for key in store.keys() {
-            if let Some(value) = store.get(&key) {
-                match &latest {
-                    Some((ts, _)) => {
-                        if key > *ts {
-                            latest = Some((key.to_string(), value));
-                        }
-                    }
-                    None => latest = Some((key.to_string(), value)),
-                }
-            }
-        }
+    save_transcription_with_metadata(app, text, model, serde_json::Map::new()).await
+}
 
-        if let Some((ts, v)) = latest {
-            let same_text = v
-                .get("text")
-                .and_then(|x| x.as_str())
-                .map(|s| s == text)
-                .unwrap_or(false);
-            let same_model = v
-                .get("model")
-                .and_then(|x| x.as_str())
-                .map(|s| s == model)
-                .unwrap_or(false);
-            let within_window = chrono::DateTime::parse_from_rfc3339(&ts)
-                .ok()
-                .and_then(|t| {
-                    t.with_timezone(&chrono::Utc)
-                        .signed_duration_since(chrono::Utc::now())
-                        .num_seconds()
-                        .checked_abs()
-                })
-                .map(|secs| secs <= 2)
-                .unwrap_or(false);
-            if same_text && same_model && within_window {
-                log::info!("Skipping duplicate transcription save (same text/model within 2s)");
-                return Ok(());
-            }
+/// Regexes from the `redaction_patterns` setting, applied to text saved to history
+fn history_redaction_patterns(app: &AppHandle) -> Vec<String> {
+    app.store("settings")
+        .ok()
+        .and_then(|store| store.get("redaction_patterns"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Save a history entry with extra fields (e.g. recording file paths) merged in.
+pub(crate) async fn save_transcription_with_metadata(
+    app: AppHandle,
+    text: String,
+    model: String,
+    mut metadata: serde_json::Map<String, serde_json::Value>,
+) -> Result<(), String> {
+    // Redact as history is written, so only the stored copy is affected and
+    // each transcription passes through exactly once
+    let redaction_patterns = history_redaction_patterns(&app);
+    let text = redaction::redact_history_entry(&text, &mut metadata, &redaction_patterns);
+
+    // De-dup guard: skip saving if the most recent entry matches the same text & model within a short window
+    if let Some((ts, v)) = history_store::latest_raw_entry(&app) {
+        let v = history_crypto::decrypt_history_entry(v);
+        let same_text = v
+            .get("text")
+            .and_then(|x| x.as_str())
+            .map(|s| s == text)
+            .unwrap_or(false);
+        let same_model = v
+            .get("model")
+            .and_then(|x| x.as_str())
+            .map(|s| s == model)
+            .unwrap_or(false);
+        let within_window = chrono::DateTime::parse_from_rfc3339(&ts)
+            .ok()
+            .and_then(|t| {
+                t.with_timezone(&chrono::Utc)
+                    .signed_duration_since(chrono::Utc::now())
+                    .num_seconds()
+                    .checked_abs()
+            })
+            .map(|secs| secs <= 2)
+            .unwrap_or(false);
+        if same_text && same_model && within_window {
+            log::info!("Skipping duplicate transcription save (same text/model within 2s)");
+            return Ok(());
         }
     }
 
-    // Save transcription to store with current timestamp
-    let store = app
-        .store("transcriptions")
-        .map_err(|e| format!("Failed to get transcriptions store: {}", e))?;
-
+    // Save transcription to this month's store with current timestamp
     let timestamp = chrono::Utc::now().to_rfc3339();
-    let transcription_data = serde_json::json!({
+    let mut transcription_data = serde_json::json!({
         "text": text.clone(),
         "model": model,
         "timestamp": timestamp.clone()
     });
+    if let Some(entry) = transcription_data.as_object_mut() {
+        entry.extend(metadata);
+    }
 
-    store.set(&timestamp, transcription_data.clone());
-
-    store
-        .save()
-        .map_err(|e| format!("Failed to save transcription: {}", e))?;
+    history_store::set_raw_entry(
+        &app,
+        &timestamp,
+        history_crypto::entry_for_store(&app, transcription_data.clone())?,
+    )?;
 
     // Emit the new transcription data to frontend for append-only update
     let _ = emit_to_window(&app, "main", "transcription-added", transcription_data);
@@ -2031,27 +3212,710 @@ pub async fn save_transcription(app: AppHandle, text: String, model: String) ->
 pub async fn get_transcription_history(
     app: AppHandle,
     limit: Option<usize>,
+    offset: Option<usize>,
+    language: Option<String>,
 ) -> Result<Vec<serde_json::Value>, String> {
-    let store = app.store("transcriptions").map_err(|e| e.to_string())?;
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(50);
+    let language = language
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+
+    // Only the shards up to the requested page are read and decrypted
+    let entries =
+        history_crypto::read_newest_history_entries(&app, offset.saturating_add(limit), |value| {
+            language
+                .as_deref()
+                .is_none_or(|language| entry_has_language(value, language))
+        })?;
+
+    Ok(paginate_history_entries(entries, offset, limit))
+}
+
+/// Text of an entry worth copying: failed placeholders, undecryptable entries
+/// and empty text have none
+fn copyable_entry_text(value: &serde_json::Value) -> Option<String> {
+    if value.get("status").and_then(|s| s.as_str()) == Some("failed") {
+        return None;
+    }
+    value
+        .get("text")
+        .and_then(|t| t.as_str())
+        .filter(|text| !text.trim().is_empty())
+        .map(str::to_string)
+}
+
+/// Text of the newest entry that has any, given entries newest first as from
+/// `read_history_entries`. Failed placeholders and undecryptable entries are skipped.
+pub(crate) fn latest_entry_text(entries: &[(String, serde_json::Value)]) -> Option<String> {
+    entries
+        .iter()
+        .find_map(|(_, value)| copyable_entry_text(value))
+}
+
+/// Copy the newest transcription to the clipboard, for the copy-last hotkey
+pub async fn copy_last_transcription(app: AppHandle) -> Result<(), String> {
+    // Stops at the newest copyable entry instead of reading every shard
+    let entries = history_crypto::read_newest_history_entries(&app, 1, |value| {
+        copyable_entry_text(value).is_some()
+    })?;
+    let Some(text) = latest_entry_text(&entries) else {
+        pill_toast(&app, "No transcription to copy", 1500);
+        return Err("No transcription in history".to_string());
+    };
+
+    crate::commands::text::copy_text_to_clipboard(text).await?;
+    pill_toast(&app, "Copied last transcription", 1500);
+    Ok(())
+}
+
+/// Language to record on a history entry: the engine-detected one when available,
+/// otherwise the configured language unless it is auto-detect.
+pub(crate) fn resolve_entry_language(
+    configured: Option<&str>,
+    detected: Option<&str>,
+) -> Option<String> {
+    detected
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .or_else(|| {
+            configured
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && *l != "auto")
+        })
+        .map(|l| l.to_lowercase())
+}
+
+/// Whether an entry was recorded in `language`; entries without a language never match.
+pub(crate) fn entry_has_language(value: &serde_json::Value, language: &str) -> bool {
+    value
+        .get("language")
+        .and_then(|l| l.as_str())
+        .is_some_and(|l| l.eq_ignore_ascii_case(language))
+}
 
-    let mut entries: Vec<(String, serde_json::Value)> = Vec::new();
+/// Sort history entries newest-first and return the requested page.
+///
+/// Keys are unique RFC3339 timestamps, so ordering by key gives the same
+/// order on every call and consecutive pages never overlap or skip entries.
+pub(crate) fn paginate_history_entries(
+    mut entries: Vec<(String, serde_json::Value)>,
+    offset: usize,
+    limit: usize,
+) -> Vec<serde_json::Value> {
+    // Sort by timestamp (newest first)
+    history_store::sort_newest_first(&mut entries);
+
+    entries
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|(_, v)| v)
+        .collect()
+}
+
+/// Which text of a history entry to copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryTextVariant {
+    /// The text as it was inserted
+    Plain,
+    /// The AI-enhanced text; only entries that went through enhancement have one
+    Enhanced,
+    /// The transcription before AI enhancement
+    Raw,
+}
 
-    // Collect all entries with their timestamps
-    for key in store.keys() {
-        if let Some(value) = store.get(&key) {
-            entries.push((key.to_string(), value));
+impl HistoryTextVariant {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "plain" => Ok(Self::Plain),
+            "enhanced" => Ok(Self::Enhanced),
+            "raw" => Ok(Self::Raw),
+            other => Err(format!(
+                "Invalid text variant '{}': expected plain, enhanced or raw",
+                other
+            )),
         }
     }
+}
 
-    // Sort by timestamp (newest first)
-    entries.sort_by(|a, b| b.0.cmp(&a.0));
+/// The `variant` text of the history entry stored at `timestamp`, if there is one.
+/// Entries without `raw_text` were never enhanced, so their text is also the raw one.
+pub(crate) fn history_entry_text(
+    entry: Option<&serde_json::Value>,
+    timestamp: &str,
+    variant: HistoryTextVariant,
+) -> Result<String, String> {
+    let entry = entry.ok_or_else(|| format!("Transcription not found: {}", timestamp))?;
+    let text = entry
+        .get("text")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| format!("Transcription {} has no text", timestamp))?;
+    let raw_text = entry.get("raw_text").and_then(|v| v.as_str());
 
-    // Apply limit if specified
-    let limit = limit.unwrap_or(50);
-    entries.truncate(limit);
+    match (variant, raw_text) {
+        (HistoryTextVariant::Plain, _) | (HistoryTextVariant::Enhanced, Some(_)) => {
+            Ok(text.to_string())
+        }
+        (HistoryTextVariant::Enhanced, None) => {
+            Err("This transcription wasn't enhanced with AI".to_string())
+        }
+        (HistoryTextVariant::Raw, raw_text) => Ok(raw_text.unwrap_or(text).to_string()),
+    }
+}
+
+/// The full stored history entry (text, raw_text, model, tags, ...) at `timestamp`
+#[tauri::command]
+pub async fn get_transcription_entry(
+    app: AppHandle,
+    timestamp: String,
+) -> Result<serde_json::Value, String> {
+    history_crypto::read_history_entry(&app, &timestamp)
+        .ok_or_else(|| format!("Transcription not found: {}", timestamp))
+}
+
+/// Copy one text variant ("plain", "enhanced" or "raw") of a history entry
+#[tauri::command]
+pub async fn copy_transcription(
+    app: AppHandle,
+    timestamp: String,
+    variant: String,
+) -> Result<(), String> {
+    let variant = HistoryTextVariant::parse(&variant)?;
+    let entry = history_crypto::read_history_entry(&app, &timestamp);
+    let text = history_entry_text(entry.as_ref(), &timestamp, variant)?;
+    crate::commands::text::copy_text_to_clipboard(text).await?;
+    log::info!("Copied {:?} text of transcription {}", variant, timestamp);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_transcription_count(app: AppHandle) -> Result<usize, String> {
+    history_store::entry_count(&app)
+}
 
-    // Return just the values
-    Ok(entries.into_iter().map(|(_, v)| v).collect())
+#[derive(Debug, Default, serde::Serialize)]
+pub struct TranscriptionStats {
+    pub total_entries: usize,
+    pub total_words: usize,
+    pub total_chars: usize,
+    pub by_model: HashMap<String, usize>,
+    /// Entry count per local calendar day (YYYY-MM-DD)
+    pub by_day: std::collections::BTreeMap<String, usize>,
+    pub avg_words_per_entry: f64,
+}
+
+#[tauri::command]
+pub async fn get_transcription_stats(app: AppHandle) -> Result<TranscriptionStats, String> {
+    let entries = history_crypto::read_history_entries(&app)?;
+
+    Ok(compute_transcription_stats(&entries))
+}
+
+/// Aggregate usage numbers over history entries, skipping failed transcriptions.
+pub(crate) fn compute_transcription_stats(
+    entries: &[(String, serde_json::Value)],
+) -> TranscriptionStats {
+    let mut stats = TranscriptionStats::default();
+
+    for (key, value) in entries {
+        if value.get("status").and_then(|s| s.as_str()) == Some("failed") {
+            continue;
+        }
+
+        let text = value.get("text").and_then(|t| t.as_str()).unwrap_or("");
+        let model = value
+            .get("model")
+            .and_then(|m| m.as_str())
+            .unwrap_or("unknown");
+
+        stats.total_entries += 1;
+        stats.total_words += text.split_whitespace().count();
+        stats.total_chars += text.chars().count();
+        *stats.by_model.entry(model.to_string()).or_insert(0) += 1;
+
+        if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(key) {
+            let day = ts
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string();
+            *stats.by_day.entry(day).or_insert(0) += 1;
+        }
+    }
+
+    if stats.total_entries > 0 {
+        stats.avg_words_per_entry = stats.total_words as f64 / stats.total_entries as f64;
+    }
+
+    stats
+}
+
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize)]
+pub struct DailyUsage {
+    pub dictations: usize,
+    pub words: usize,
+}
+
+/// Local-only usage analytics computed from the transcription history
+#[derive(Debug, Default, serde::Serialize)]
+pub struct UsageAnalytics {
+    /// Days covered by the range; `None` for all history
+    pub range_days: Option<u32>,
+    pub total_dictations: usize,
+    pub total_words: usize,
+    /// Dictations and words per local calendar day (YYYY-MM-DD)
+    pub per_day: std::collections::BTreeMap<String, DailyUsage>,
+    /// Average over entries that recorded a duration; older entries have none
+    pub avg_recording_seconds: Option<f64>,
+    pub by_engine: HashMap<String, usize>,
+    pub by_model: HashMap<String, usize>,
+    /// Dictation count per local hour of day (index 0-23)
+    pub by_hour: Vec<usize>,
+    /// Busiest hours first, at most three, only hours with any usage
+    pub peak_hours: Vec<u32>,
+}
+
+/// Parse an analytics range such as "7d", "30d" or "all" into a number of days
+pub(crate) fn parse_analytics_range(range: Option<&str>) -> Result<Option<u32>, String> {
+    let range = match range.map(str::trim) {
+        None | Some("") | Some("all") => return Ok(None),
+        Some(range) => range,
+    };
+
+    range
+        .strip_suffix('d')
+        .and_then(|days| days.parse::<u32>().ok())
+        .filter(|days| *days > 0)
+        .map(Some)
+        .ok_or_else(|| format!("Invalid analytics range: {}", range))
+}
+
+#[tauri::command]
+pub async fn get_analytics(
+    app: AppHandle,
+    range: Option<String>,
+) -> Result<UsageAnalytics, String> {
+    let range_days = parse_analytics_range(range.as_deref())?;
+    let entries = read_entries_in_range(&app, range_days)?;
+
+    let mut analytics = compute_usage_analytics(&entries);
+    analytics.range_days = range_days;
+    Ok(analytics)
+}
+
+/// Decrypted history entries from the last `range_days` days, or all of them
+fn read_entries_in_range(
+    app: &AppHandle,
+    range_days: Option<u32>,
+) -> Result<Vec<(String, serde_json::Value)>, String> {
+    let cutoff = range_days.map(|days| chrono::Utc::now() - chrono::Duration::days(days as i64));
+
+    // Months before the cutoff are skipped without opening their stores
+    let raw_entries = match cutoff {
+        Some(cutoff) => history_store::read_raw_entries_since(app, cutoff)?,
+        None => history_store::read_raw_entries(app)?,
+    };
+    Ok(raw_entries
+        .into_iter()
+        .map(|(key, value)| (key, history_crypto::decrypt_history_entry(value)))
+        .collect())
+}
+
+/// Aggregate analytics over history entries, skipping failed transcriptions.
+pub(crate) fn compute_usage_analytics(entries: &[(String, serde_json::Value)]) -> UsageAnalytics {
+    let mut analytics = UsageAnalytics {
+        by_hour: vec![0; 24],
+        ..Default::default()
+    };
+    let mut total_duration = 0.0;
+    let mut timed_entries = 0usize;
+
+    for (key, value) in entries {
+        if value.get("status").and_then(|s| s.as_str()) == Some("failed") {
+            continue;
+        }
+
+        let words = value
+            .get("text")
+            .and_then(|t| t.as_str())
+            .unwrap_or("")
+            .split_whitespace()
+            .count();
+        let field = |name: &str| {
+            value
+                .get(name)
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string()
+        };
+
+        analytics.total_dictations += 1;
+        analytics.total_words += words;
+        *analytics.by_engine.entry(field("engine")).or_insert(0) += 1;
+        *analytics.by_model.entry(field("model")).or_insert(0) += 1;
+
+        if let Some(duration) = value.get("duration_seconds").and_then(|d| d.as_f64()) {
+            total_duration += duration;
+            timed_entries += 1;
+        }
+
+        if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(key) {
+            let local = ts.with_timezone(&chrono::Local);
+            let day = analytics
+                .per_day
+                .entry(local.format("%Y-%m-%d").to_string())
+                .or_default();
+            day.dictations += 1;
+            day.words += words;
+            analytics.by_hour[chrono::Timelike::hour(&local) as usize] += 1;
+        }
+    }
+
+    if timed_entries > 0 {
+        analytics.avg_recording_seconds = Some(total_duration / timed_entries as f64);
+    }
+
+    analytics.peak_hours = peak_hours(&analytics.by_hour);
+
+    analytics
+}
+
+/// The three busiest hours of day in `by_hour`, busiest first, skipping unused hours
+fn peak_hours(by_hour: &[usize]) -> Vec<u32> {
+    let mut hours: Vec<(u32, usize)> = (0u32..)
+        .zip(by_hour.iter().copied())
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    // Most used first; earlier hour wins ties so the order is stable
+    hours.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    hours.into_iter().take(3).map(|(hour, _)| hour).collect()
+}
+
+/// Local-only productivity numbers for the dashboard, from history alone
+#[derive(Debug, Default, serde::Serialize)]
+pub struct ProductivityReport {
+    /// Days covered by the range; `None` for all history
+    pub range_days: Option<u32>,
+    pub total_dictations: usize,
+    pub total_words: usize,
+    /// Words per local calendar day (YYYY-MM-DD), only days with dictations
+    pub words_by_day: std::collections::BTreeMap<String, usize>,
+    /// Average words over the days in `words_by_day`
+    pub words_per_day: f64,
+    /// Average recording length, over entries that recorded one
+    pub avg_session_seconds: Option<f64>,
+    pub most_used_model: Option<String>,
+    /// Entries without a language don't count
+    pub most_used_language: Option<String>,
+    /// Dictation count per local hour of day (index 0-23)
+    pub by_hour: Vec<usize>,
+    /// Busiest hours first, at most three
+    pub peak_hours: Vec<u32>,
+}
+
+#[tauri::command]
+pub async fn get_productivity_report(
+    app: AppHandle,
+    range: Option<String>,
+) -> Result<ProductivityReport, String> {
+    let range_days = parse_analytics_range(range.as_deref())?;
+    let entries = read_entries_in_range(&app, range_days)?;
+
+    let mut report = compute_productivity_report(&entries);
+    report.range_days = range_days;
+    Ok(report)
+}
+
+/// The most frequent key; alphabetical order breaks ties
+fn most_used(counts: HashMap<String, usize>) -> Option<String> {
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(key, _)| key)
+}
+
+/// Aggregate a productivity report over history entries, skipping failed transcriptions
+pub(crate) fn compute_productivity_report(
+    entries: &[(String, serde_json::Value)],
+) -> ProductivityReport {
+    let mut report = ProductivityReport {
+        by_hour: vec![0; 24],
+        ..Default::default()
+    };
+    let mut models: HashMap<String, usize> = HashMap::new();
+    let mut languages: HashMap<String, usize> = HashMap::new();
+    let mut total_duration = 0.0;
+    let mut timed_entries = 0usize;
+
+    for (key, value) in entries {
+        if value.get("status").and_then(|s| s.as_str()) == Some("failed") {
+            continue;
+        }
+
+        let words = value
+            .get("text")
+            .and_then(|t| t.as_str())
+            .unwrap_or("")
+            .split_whitespace()
+            .count();
+        let field = |name: &str| {
+            value
+                .get(name)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+
+        report.total_dictations += 1;
+        report.total_words += words;
+        if let Some(model) = field("model") {
+            *models.entry(model).or_insert(0) += 1;
+        }
+        if let Some(language) = field("language") {
+            *languages.entry(language).or_insert(0) += 1;
+        }
+        if let Some(duration) = value.get("duration_seconds").and_then(|d| d.as_f64()) {
+            total_duration += duration;
+            timed_entries += 1;
+        }
+
+        if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(key) {
+            let local = ts.with_timezone(&chrono::Local);
+            *report
+                .words_by_day
+                .entry(local.format("%Y-%m-%d").to_string())
+                .or_insert(0) += words;
+            report.by_hour[chrono::Timelike::hour(&local) as usize] += 1;
+        }
+    }
+
+    if !report.words_by_day.is_empty() {
+        report.words_per_day =
+            report.words_by_day.values().sum::<usize>() as f64 / report.words_by_day.len() as f64;
+    }
+    if timed_entries > 0 {
+        report.avg_session_seconds = Some(total_duration / timed_entries as f64);
+    }
+    report.most_used_model = most_used(models);
+    report.most_used_language = most_used(languages);
+    report.peak_hours = peak_hours(&report.by_hour);
+
+    report
+}
+
+/// Whether a recording of `duration` seconds is discarded as too short.
+/// A minimum of 0 keeps every recording.
+pub(crate) fn recording_too_short(duration: f32, min_seconds: f32) -> bool {
+    min_seconds > 0.0 && duration < min_seconds
+}
+
+/// Offer the text decoded before a cancel, so the user can keep it
+fn offer_partial_transcript(app: &AppHandle, partial: Option<String>) {
+    let Some(text) = partial else {
+        return;
+    };
+    log::info!(
+        "Transcription cancelled with {} characters decoded",
+        text.len()
+    );
+    if let Err(e) = crate::emit_to_all(
+        app,
+        "cancelled-with-partial",
+        serde_json::json!({ "text": text }),
+    ) {
+        log::warn!("Failed to emit cancelled-with-partial: {}", e);
+    }
+}
+
+/// Longest wait between two transcription attempts, however many have failed
+const MAX_RETRY_DELAY_MS: u64 = 30_000;
+
+/// How often, and how patiently, a failed transcription is retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RetryPolicy {
+    /// Total attempts, the first one included; never less than 1
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubles for each one after that
+    pub base_delay_ms: u64,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, base_delay_ms: u64) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay_ms,
+        }
+    }
+
+    /// The policy from `transcription_max_retries` and `transcription_retry_delay_ms`
+    pub fn from_store(app: &AppHandle) -> Self {
+        let store = app.store("settings").ok();
+        let setting = |key: &str| store.as_ref().and_then(|s| s.get(key)?.as_u64());
+        Self::new(
+            setting("transcription_max_retries").map_or(3, |n| n.min(u32::MAX as u64) as u32),
+            setting("transcription_retry_delay_ms").unwrap_or(500),
+        )
+    }
+
+    /// How long to wait after failed attempt number `attempt` (1-based)
+    pub fn delay_before_retry(&self, attempt: u32) -> std::time::Duration {
+        let factor = 1u64
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        std::time::Duration::from_millis(
+            self.base_delay_ms
+                .saturating_mul(factor)
+                .min(MAX_RETRY_DELAY_MS),
+        )
+    }
+}
+
+/// Run `op` until it succeeds, fails with an error `retryable` rejects, or
+/// `policy` runs out of attempts, backing off exponentially in between.
+/// `op` gets the 1-based attempt number.
+pub(crate) async fn retry_with_backoff<T, F, Fut>(
+    policy: RetryPolicy,
+    label: &str,
+    mut op: F,
+    retryable: impl Fn(&str) -> bool,
+) -> Result<T, String>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<T, String>>,
+{
+    let mut attempt = 1;
+    loop {
+        log::info!("{} attempt {}/{}", label, attempt, policy.max_attempts);
+        match op(attempt).await {
+            Ok(value) => {
+                if attempt > 1 {
+                    log::info!("{} succeeded on attempt {}", label, attempt);
+                }
+                return Ok(value);
+            }
+            Err(e) if attempt < policy.max_attempts && retryable(&e) => {
+                let delay = policy.delay_before_retry(attempt);
+                log::warn!(
+                    "{} attempt {} failed: {}. Retrying in {}ms...",
+                    label,
+                    attempt,
+                    e,
+                    delay.as_millis()
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                log::error!("{} failed after {} attempt(s): {}", label, attempt, e);
+                return Err(e);
+            }
+        }
+    }
+}
+
+/// Whether a cloud transcription error is worth retrying: the request never
+/// got an answer, or the server answered with a 5xx
+pub(crate) fn is_transient_cloud_error(error: &str) -> bool {
+    error.contains("Network error")
+        || error.split("HTTP ").skip(1).any(|rest| {
+            rest.as_bytes()
+                .get(..3)
+                .is_some_and(|code| code[0] == b'5' && code.iter().all(u8::is_ascii_digit))
+        })
+}
+
+/// Toast to show when Whisper's average token confidence falls below `threshold`.
+/// A threshold of 0 turns the warning off.
+pub(crate) fn low_confidence_warning(
+    confidence: Option<f32>,
+    threshold: f32,
+) -> Option<&'static str> {
+    match confidence {
+        Some(confidence) if threshold > 0.0 && confidence < threshold => {
+            Some("Low confidence — double-check text")
+        }
+        _ => None,
+    }
+}
+
+/// Record what Whisper heard on a history entry, separately from its `language`
+pub(crate) fn insert_detected_language(
+    metadata: &mut serde_json::Map<String, serde_json::Value>,
+    detected: &DetectedLanguage,
+) {
+    let probability = (detected.probability as f64 * 1000.0).round() / 1000.0;
+    metadata.insert(
+        "detected_language".to_string(),
+        serde_json::json!(detected.code),
+    );
+    metadata.insert(
+        "detected_language_probability".to_string(),
+        serde_json::json!(probability),
+    );
+}
+
+/// Minimum detection probability before a language mismatch is worth a warning
+const LANGUAGE_MISMATCH_MIN_PROBABILITY: f32 = 0.8;
+
+/// Toast to show when Whisper is confident the speech was in a different language
+/// than the one configured. Auto-detect never mismatches.
+pub(crate) fn language_mismatch_warning(
+    detected: Option<&DetectedLanguage>,
+    configured: Option<&str>,
+) -> Option<String> {
+    let detected = detected?;
+    let configured = configured.filter(|l| *l != "auto")?;
+    if detected.code == configured || detected.probability < LANGUAGE_MISMATCH_MIN_PROBABILITY {
+        return None;
+    }
+    let name = get_language_name(&detected.code).unwrap_or(detected.code.as_str());
+    Some(format!(
+        "Sounded like {} — check the language setting",
+        name
+    ))
+}
+
+/// Wait for a free upload slot, at most `max_concurrent_uploads` running at once
+async fn acquire_upload_slot(app: &AppHandle) -> UploadSlot {
+    let limit = app
+        .store("settings")
+        .ok()
+        .and_then(|store| store.get("max_concurrent_uploads"))
+        .and_then(|v| v.as_u64())
+        .map_or(DEFAULT_CONCURRENT_UPLOADS, |n| n as usize);
+    let pool = app.state::<TranscriptionPool>().inner().clone();
+    if pool.running_uploads() >= limit {
+        log::info!("[UPLOAD] Waiting for a free transcription slot");
+    }
+    pool.acquire_upload(limit).await
+}
+
+/// How Whisper splits long recordings, from `chunk_long_recordings_after_secs`
+fn chunk_plan(app: &AppHandle) -> Option<ChunkPlan> {
+    let threshold_secs = app
+        .store("settings")
+        .ok()
+        .and_then(|store| store.get("chunk_long_recordings_after_secs"))
+        .and_then(|v| v.as_u64())
+        .map_or(DEFAULT_CHUNK_THRESHOLD_SECS, |n| {
+            n.min(u32::MAX as u64) as u32
+        });
+    ChunkPlan::new(threshold_secs)
+}
+
+/// Unique path for the normalized copy of an uploaded file. Batch transcription
+/// normalizes several files within the same second, so a timestamp alone collides.
+fn upload_normalized_path(recordings_dir: &Path) -> PathBuf {
+    unique_upload_path(recordings_dir, "normalized")
+}
+
+/// A WAV path in `recordings_dir` no other upload in flight is using
+fn unique_upload_path(recordings_dir: &Path, prefix: &str) -> PathBuf {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    let ts = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let id = NEXT_ID.fetch_add(1, AtomicOrdering::Relaxed);
+    recordings_dir.join(format!("{}_{}_{}.wav", prefix, ts, id))
 }
 
 #[tauri::command]
@@ -2102,14 +3966,7 @@ pub async fn transcribe_audio_file(
 
     // Get language and translation settings
     let store = app.store("settings").map_err(|e| e.to_string())?;
-    let language = {
-        let lang = store
-            .get("language")
-            .and_then(|v| v.as_str().map(|s| s.to_string()))
-            .unwrap_or_else(|| "en".to_string());
-
-        validate_language(Some(&lang)).to_string()
-    };
+    let language = upload_language(&app, engine_selection.engine_name())?;
 
     let translate_to_english = store
         .get("translate_to_english")
@@ -2117,10 +3974,11 @@ pub async fn transcribe_audio_file(
         .unwrap_or(false);
 
     log::info!(
-        "[LANGUAGE] transcribe_audio_file using language: {}, translate: {}",
+        "[LANGUAGE] transcribe_audio_file using language: {:?}, translate: {}",
         language,
         translate_to_english
     );
+    let _upload_slot = acquire_upload_slot(&app).await;
 
     // For Soniox, skip normalization and send original wav_path
     let text = match engine_selection {
@@ -2128,11 +3986,10 @@ pub async fn transcribe_audio_file(
             // Normalize to Whisper contract
             log::debug!("[UPLOAD] Normalizing to Whisper WAV (16k mono s16)...");
             let normalized_path = {
-                let ts = chrono::Local::now().format("%Y%m%d_%H%M%S");
-                let out_path = recordings_dir.join(format!("normalized_{}.wav", ts));
+                let out_path = upload_normalized_path(&recordings_dir);
                 crate::ffmpeg::normalize_streaming(&app, &wav_path, &out_path)
                     .await
-                    .map_err(|e| format!("Audio normalization (ffmpeg) failed: {}", e))?;
+                    .map_err(upload_normalization_error)?;
                 out_path
             };
             log::info!("[UPLOAD] Normalized WAV at {:?}", normalized_path);
@@ -2142,11 +3999,24 @@ pub async fn transcribe_audio_file(
                 cache.get_or_create(&model_path)?
             };
 
-            let result = transcriber.transcribe_with_translation(
-                &normalized_path,
-                Some(&language),
-                translate_to_english,
-            )?;
+            // Off the async runtime so concurrent uploads don't stall it
+            let result = {
+                let normalized_path = normalized_path.clone();
+                let language = language.clone();
+                let chunking = chunk_plan(&app);
+                tokio::task::spawn_blocking(move || {
+                    transcriber
+                        .transcribe_with_chunking(
+                            &normalized_path,
+                            language.as_deref(),
+                            translate_to_english,
+                            chunking,
+                        )
+                        .map(|output| output.text)
+                })
+                .await
+                .map_err(|e| format!("Transcription task failed: {}", e))??
+            };
             let _ = std::fs::remove_file(&normalized_path);
             result
         }
@@ -2154,53 +4024,497 @@ pub async fn transcribe_audio_file(
             // Normalize to Whisper/Parakeet contract first
             log::debug!("[UPLOAD] Normalizing to Whisper WAV (16k mono s16)...");
             let normalized_path = {
-                let ts = chrono::Local::now().format("%Y%m%d_%H%M%S");
-                let out_path = recordings_dir.join(format!("normalized_{}.wav", ts));
+                let out_path = upload_normalized_path(&recordings_dir);
                 crate::ffmpeg::normalize_streaming(&app, &wav_path, &out_path)
                     .await
-                    .map_err(|e| format!("Audio normalization (ffmpeg) failed: {}", e))?;
+                    .map_err(upload_normalization_error)?;
                 out_path
             };
             log::info!("[UPLOAD] Normalized WAV at {:?}", normalized_path);
             let parakeet_manager = app.state::<ParakeetManager>();
 
-            parakeet_manager
-                .load_model(&app, &model_name)
-                .await
-                .map_err(|e| format!("Failed to load Parakeet model: {}", e))?;
+            parakeet_manager
+                .load_model(&app, &model_name)
+                .await
+                .map_err(|e| format!("Failed to load Parakeet model: {}", e))?;
+
+            match parakeet_manager
+                .transcribe(
+                    &app,
+                    &model_name,
+                    normalized_path.clone(),
+                    language.clone(),
+                    translate_to_english,
+                )
+                .await
+            {
+                Ok(ParakeetResponse::Transcription { text, .. }) => {
+                    let _ = std::fs::remove_file(&normalized_path);
+                    text
+                }
+                Ok(other) => {
+                    return Err(format!("Unexpected Parakeet response: {:?}", other));
+                }
+                Err(err) => {
+                    return Err(format!("Parakeet transcription failed: {}", err));
+                }
+            }
+        }
+        ActiveEngineSelection::Soniox { .. } => {
+            soniox_transcribe_async(&app, &wav_path, language.as_deref()).await?
+        }
+    };
+
+    log::info!(
+        "[UPLOAD] Completed transcription, {} characters",
+        text.len()
+    );
+    Ok(post_process_with_config(&app, text, language.as_deref()).await)
+}
+
+/// Download the audio at `url` (e.g. a meeting recording link) and transcribe it
+/// like an uploaded file. The download is removed afterwards, whatever the outcome.
+#[tauri::command]
+pub async fn transcribe_url(
+    app: AppHandle,
+    url: String,
+    model_name: String,
+    model_engine: Option<String>,
+) -> Result<String, String> {
+    // Fail before downloading anything when transcription can't run
+    validate_recording_requirements(&app).await?;
+
+    let recordings_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("recordings");
+    std::fs::create_dir_all(&recordings_dir)
+        .map_err(|e| format!("Failed to create recordings directory: {}", e))?;
+
+    let download = crate::utils::remote_audio::download_audio(
+        &url,
+        &recordings_dir,
+        crate::utils::remote_audio::MAX_REMOTE_AUDIO_BYTES,
+    )
+    .await?;
+    transcribe_audio_file(
+        app,
+        download.path().to_string_lossy().to_string(),
+        model_name,
+        model_engine,
+    )
+    .await
+}
+
+/// Transcribe audio copied to the clipboard (a file, its path as text, or raw
+/// audio data) with the current model, then insert and/or copy the text per
+/// `output_mode`
+#[tauri::command]
+pub async fn transcribe_clipboard_audio(app: AppHandle) -> Result<String, String> {
+    let contents = tokio::task::spawn_blocking(read_clipboard_contents)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))??;
+    let source = clipboard_audio_source(contents)?;
+
+    // Raw data goes through a temp file so it takes the same path as an upload
+    let (audio_path, temp_path) = match source {
+        ClipboardAudio::File(path) => {
+            if !path.is_file() {
+                return Err(format!("Audio file not found: {}", path.display()));
+            }
+            (path, None)
+        }
+        ClipboardAudio::Bytes { data, extension } => {
+            let recordings_dir = app
+                .path()
+                .app_data_dir()
+                .map_err(|e| e.to_string())?
+                .join("recordings");
+            std::fs::create_dir_all(&recordings_dir)
+                .map_err(|e| format!("Failed to create recordings directory: {}", e))?;
+            let path = recordings_dir.join(format!("clipboard_audio.{}", extension));
+            std::fs::write(&path, data)
+                .map_err(|e| format!("Failed to write clipboard audio: {}", e))?;
+            (path.clone(), Some(path))
+        }
+    };
+    log::info!("[CLIPBOARD] Transcribing clipboard audio {:?}", audio_path);
+
+    let settings = get_settings(app.clone()).await?;
+    let result = transcribe_audio_file(
+        app.clone(),
+        audio_path.to_string_lossy().to_string(),
+        settings.current_model,
+        Some(settings.current_model_engine),
+    )
+    .await;
+    if let Some(path) = temp_path {
+        let _ = std::fs::remove_file(path);
+    }
+    let text = result?;
+    if text.is_empty() {
+        return Ok(text);
+    }
+
+    let output_mode = crate::commands::text::current_output_mode(&app).await;
+    if output_mode.inserts() {
+        crate::commands::text::insert_text(app.clone(), text.clone()).await?;
+    }
+    if output_mode.copies() {
+        crate::commands::text::copy_text_to_clipboard(text.clone()).await?;
+    }
+    Ok(text)
+}
+
+/// Files transcribed at once by `transcribe_batch`; more only adds ffmpeg processes
+/// competing for the same CPU
+const BATCH_CONCURRENCY: usize = 2;
+
+/// Outcome for one file of `transcribe_batch`
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct BatchTranscriptionResult {
+    pub path: String,
+    pub text: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Run `transcribe` over `paths` with at most `concurrency` files in flight, calling
+/// `on_start(index, path)` as each file begins. Failures are recorded per file and
+/// never stop the batch; results keep the input order.
+pub(crate) async fn run_batch<F, Fut>(
+    paths: Vec<String>,
+    concurrency: usize,
+    on_start: impl Fn(usize, &str),
+    transcribe: F,
+) -> Vec<BatchTranscriptionResult>
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = Result<String, String>>,
+{
+    use futures_util::stream::{self, StreamExt};
+
+    stream::iter(paths.into_iter().enumerate())
+        .map(|(index, path)| {
+            on_start(index, &path);
+            let result = transcribe(path.clone());
+            async move {
+                let (text, error) = match result.await {
+                    Ok(text) => (Some(text), None),
+                    Err(e) => {
+                        log::warn!("[BATCH] Failed to transcribe {}: {}", path, e);
+                        (None, Some(e))
+                    }
+                };
+                BatchTranscriptionResult { path, text, error }
+            }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// Transcribe several audio files with one model, e.g. a folder of voice memos.
+/// Emits `batch-progress` as each file starts.
+#[tauri::command]
+pub async fn transcribe_batch(
+    app: AppHandle,
+    paths: Vec<String>,
+    model_name: String,
+    model_engine: Option<String>,
+) -> Result<Vec<BatchTranscriptionResult>, String> {
+    let total = paths.len();
+    log::info!("[BATCH] Transcribing {} files with {}", total, model_name);
+
+    // Whisper models stay in TranscriberCache, so only the first file pays for loading
+    let results = run_batch(
+        paths,
+        BATCH_CONCURRENCY,
+        |index, path| {
+            if let Err(e) = app.emit(
+                "batch-progress",
+                serde_json::json!({
+                    "index": index,
+                    "total": total,
+                    "current_file": path,
+                }),
+            ) {
+                log::debug!("Failed to emit batch-progress: {}", e);
+            }
+        },
+        |path| transcribe_audio_file(app.clone(), path, model_name.clone(), model_engine.clone()),
+    )
+    .await;
+
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    log::info!(
+        "[BATCH] Done: {} succeeded, {} failed",
+        total - failed,
+        failed
+    );
+    Ok(results)
+}
+
+/// A failed history entry whose recording can be transcribed again
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FailedEntry {
+    pub timestamp: String,
+    pub recording_file: PathBuf,
+}
+
+/// Failed history entries (`status: "failed"`), oldest first, split into those
+/// whose `recording_file` still exists and the timestamps of those whose doesn't
+pub(crate) fn failed_entries_with_audio(
+    entries: &[(String, serde_json::Value)],
+) -> (Vec<FailedEntry>, Vec<String>) {
+    let mut with_audio = Vec::new();
+    let mut missing_audio = Vec::new();
+    for (timestamp, value) in entries {
+        if value.get("status").and_then(|s| s.as_str()) != Some("failed") {
+            continue;
+        }
+        match value.get("recording_file").and_then(|f| f.as_str()) {
+            Some(file) if Path::new(file).is_file() => with_audio.push(FailedEntry {
+                timestamp: timestamp.clone(),
+                recording_file: PathBuf::from(file),
+            }),
+            _ => missing_audio.push(timestamp.clone()),
+        }
+    }
+    with_audio.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    missing_audio.sort();
+    (with_audio, missing_audio)
+}
+
+/// A failed entry after a successful re-transcription: new text and model, no
+/// failure markers. Timestamp and recording are kept so it stays in place.
+pub(crate) fn mark_entry_retranscribed(
+    mut entry: serde_json::Value,
+    text: &str,
+    model: &str,
+    engine: &str,
+) -> serde_json::Value {
+    if let Some(fields) = entry.as_object_mut() {
+        fields.remove("status");
+        fields.remove("error");
+        fields.insert("text".to_string(), serde_json::json!(text));
+        fields.insert("model".to_string(), serde_json::json!(model));
+        fields.insert("engine".to_string(), serde_json::json!(engine));
+    }
+    entry
+}
+
+/// Overwrite the history entry saved at `timestamp`, redacting and encrypting
+/// it like a new entry
+pub(crate) fn update_history_entry(
+    app: &AppHandle,
+    timestamp: &str,
+    entry: serde_json::Value,
+) -> Result<(), String> {
+    if !history_store::has_entry(app, timestamp) {
+        return Err(format!("History entry {} not found", timestamp));
+    }
+
+    let mut fields = match entry {
+        serde_json::Value::Object(fields) => fields,
+        _ => return Err("History entry must be an object".to_string()),
+    };
+    let text = fields
+        .remove("text")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_default();
+    let patterns = history_redaction_patterns(app);
+    let text = redaction::redact_history_entry(&text, &mut fields, &patterns);
+    fields.insert("text".to_string(), serde_json::json!(text));
+
+    history_store::set_raw_entry(
+        app,
+        timestamp,
+        history_crypto::entry_for_store(app, serde_json::Value::Object(fields))?,
+    )
+}
+
+/// Text to re-enhance for a history entry: the pre-enhancement `raw_text` when
+/// the entry has one, otherwise its stored text
+pub(crate) fn reenhance_source(entry: &serde_json::Value) -> Result<String, String> {
+    if entry.get("status").and_then(|s| s.as_str()) == Some("failed") {
+        return Err("This entry has no transcription to enhance".to_string());
+    }
+    if entry.get("decryption_failed").and_then(|v| v.as_bool()) == Some(true) {
+        return Err("This entry couldn't be decrypted".to_string());
+    }
+
+    let field = |name: &str| {
+        entry
+            .get(name)
+            .and_then(|v| v.as_str())
+            .filter(|text| !text.trim().is_empty())
+    };
+    field("raw_text")
+        .or_else(|| field("text"))
+        .map(str::to_string)
+        .ok_or_else(|| "This entry has no text to enhance".to_string())
+}
+
+/// The entry with `enhanced` as its text. The text it was enhanced from is kept
+/// as `raw_text`, so the original is never lost.
+pub(crate) fn apply_reenhanced_text(
+    mut entry: serde_json::Value,
+    source: &str,
+    enhanced: &str,
+) -> serde_json::Value {
+    if let Some(fields) = entry.as_object_mut() {
+        fields.insert("raw_text".to_string(), serde_json::json!(source));
+        fields.insert("text".to_string(), serde_json::json!(enhanced));
+    }
+    entry
+}
+
+/// Path of the screenshot attached to the history entry saved at `timestamp`
+/// (attach_screenshot), or None when it has none or the file is gone
+#[tauri::command]
+pub async fn get_transcription_attachment(
+    app: AppHandle,
+    timestamp: String,
+) -> Result<Option<String>, String> {
+    let entry = history_crypto::read_history_entry(&app, &timestamp)
+        .ok_or_else(|| format!("Transcription not found: {}", timestamp))?;
+    let recordings_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("recordings");
+    Ok(attachments::resolve_attachment(&entry, &recordings_dir)
+        .map(|path| path.to_string_lossy().to_string()))
+}
+
+/// Run AI enhancement again on a history entry, e.g. after changing the prompt,
+/// and update it in place. Emits `transcription-updated`.
+#[tauri::command]
+pub async fn reenhance_transcription(app: AppHandle, timestamp: String) -> Result<String, String> {
+    let ai_enabled = app
+        .store("settings")
+        .ok()
+        .and_then(|store| store.get("ai_enabled"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if !ai_enabled {
+        return Err("AI enhancement is disabled".to_string());
+    }
+
+    let entry = history_crypto::read_history_entry(&app, &timestamp)
+        .ok_or_else(|| format!("Transcription not found: {}", timestamp))?;
+    let source = reenhance_source(&entry)?;
+    let enhanced = crate::commands::ai::enhance_transcription(source.clone(), app.clone()).await?;
+
+    update_history_entry(
+        &app,
+        &timestamp,
+        apply_reenhanced_text(entry, &source, &enhanced),
+    )?;
+    log::info!("Re-enhanced transcription {}", timestamp);
+
+    let _ = emit_to_window(
+        &app,
+        "main",
+        "transcription-updated",
+        serde_json::json!({ "timestamp": timestamp, "text": enhanced }),
+    );
+    Ok(enhanced)
+}
+
+/// Outcome of `retranscribe_all_failed`
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct RetranscribeSummary {
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Failed entries whose recording no longer exists
+    pub skipped: usize,
+}
+
+/// Transcribe every failed history entry again from its kept recording and
+/// update the entries in place. Emits `retranscribe-progress` as each starts.
+#[tauri::command]
+pub async fn retranscribe_all_failed(
+    app: AppHandle,
+    model_name: String,
+    model_engine: Option<String>,
+) -> Result<RetranscribeSummary, String> {
+    let engine = resolve_engine_for_model(&app, &model_name, model_engine.as_deref())
+        .await?
+        .engine_name();
+    let entries = history_crypto::read_history_entries(&app)?;
+    let (failed, missing_audio) = failed_entries_with_audio(&entries);
+    for timestamp in &missing_audio {
+        log::info!(
+            "[RETRANSCRIBE] Skipping {}: recording no longer exists",
+            timestamp
+        );
+    }
+
+    let total = failed.len();
+    log::info!(
+        "[RETRANSCRIBE] Retrying {} failed entries with {}",
+        total,
+        model_name
+    );
+    let mut summary = RetranscribeSummary {
+        skipped: missing_audio.len(),
+        ..Default::default()
+    };
 
-            match parakeet_manager
-                .transcribe(
-                    &app,
-                    &model_name,
-                    normalized_path.clone(),
-                    Some(language.clone()),
-                    translate_to_english,
-                )
-                .await
-            {
-                Ok(ParakeetResponse::Transcription { text, .. }) => {
-                    let _ = std::fs::remove_file(&normalized_path);
-                    text
-                }
-                Ok(other) => {
-                    return Err(format!("Unexpected Parakeet response: {:?}", other));
-                }
-                Err(err) => {
-                    return Err(format!("Parakeet transcription failed: {}", err));
+    for (index, entry) in failed.into_iter().enumerate() {
+        if let Err(e) = app.emit(
+            "retranscribe-progress",
+            serde_json::json!({
+                "index": index,
+                "total": total,
+                "timestamp": entry.timestamp,
+            }),
+        ) {
+            log::debug!("Failed to emit retranscribe-progress: {}", e);
+        }
+
+        let result = transcribe_audio_file(
+            app.clone(),
+            entry.recording_file.to_string_lossy().to_string(),
+            model_name.clone(),
+            model_engine.clone(),
+        )
+        .await;
+
+        let Some(stored) = history_crypto::read_history_entry(&app, &entry.timestamp) else {
+            // Deleted while we were transcribing
+            continue;
+        };
+        let updated = match result {
+            Ok(text) => {
+                summary.succeeded += 1;
+                mark_entry_retranscribed(stored, &text, &model_name, engine)
+            }
+            Err(e) => {
+                log::warn!("[RETRANSCRIBE] {} failed again: {}", entry.timestamp, e);
+                summary.failed += 1;
+                let mut stored = stored;
+                if let Some(fields) = stored.as_object_mut() {
+                    fields.insert("error".to_string(), serde_json::json!(e));
                 }
+                stored
             }
+        };
+        if let Err(e) = update_history_entry(&app, &entry.timestamp, updated) {
+            log::error!("[RETRANSCRIBE] Failed to update {}: {}", entry.timestamp, e);
         }
-        ActiveEngineSelection::Soniox { .. } => {
-            soniox_transcribe_async(&app, &wav_path, Some(&language)).await?
-        }
-    };
+    }
 
+    let _ = emit_to_window(&app, "main", "history-updated", ());
     log::info!(
-        "[UPLOAD] Completed transcription, {} characters",
-        text.len()
+        "[RETRANSCRIBE] Done: {} succeeded, {} failed, {} skipped",
+        summary.succeeded,
+        summary.failed,
+        summary.skipped
     );
-    Ok(text)
+    Ok(summary)
 }
 
 #[tauri::command]
@@ -2230,7 +4544,7 @@ pub async fn transcribe_audio(
     std::fs::create_dir_all(&recordings_dir)
         .map_err(|e| format!("Failed to create recordings directory: {}", e))?;
 
-    let temp_path = recordings_dir.join("temp_audio.wav");
+    let temp_path = unique_upload_path(&recordings_dir, "temp_audio");
 
     std::fs::write(&temp_path, audio_data).map_err(|e| e.to_string())?;
 
@@ -2239,14 +4553,7 @@ pub async fn transcribe_audio(
 
     // Get language and translation settings
     let store = app.store("settings").map_err(|e| e.to_string())?;
-    let language = {
-        let lang = store
-            .get("language")
-            .and_then(|v| v.as_str().map(|s| s.to_string()))
-            .unwrap_or_else(|| "en".to_string());
-
-        validate_language(Some(&lang)).to_string()
-    };
+    let language = upload_language(&app, engine_selection.engine_name())?;
 
     let translate_to_english = store
         .get("translate_to_english")
@@ -2254,10 +4561,11 @@ pub async fn transcribe_audio(
         .unwrap_or(false);
 
     log::info!(
-        "[LANGUAGE] transcribe_audio using language: {}, translate: {}",
+        "[LANGUAGE] transcribe_audio using language: {:?}, translate: {}",
         language,
         translate_to_english
     );
+    let _upload_slot = acquire_upload_slot(&app).await;
 
     let text = match engine_selection {
         ActiveEngineSelection::Whisper { model_path, .. } => {
@@ -2267,11 +4575,22 @@ pub async fn transcribe_audio(
                 cache.get_or_create(&model_path)?
             };
 
-            transcriber.transcribe_with_translation(
-                &temp_path,
-                Some(language.as_str()),
-                translate_to_english,
-            )?
+            // Off the async runtime so concurrent uploads don't stall it
+            let temp_path = temp_path.clone();
+            let language = language.clone();
+            let chunking = chunk_plan(&app);
+            tokio::task::spawn_blocking(move || {
+                transcriber
+                    .transcribe_with_chunking(
+                        &temp_path,
+                        language.as_deref(),
+                        translate_to_english,
+                        chunking,
+                    )
+                    .map(|output| output.text)
+            })
+            .await
+            .map_err(|e| format!("Transcription task failed: {}", e))??
         }
         ActiveEngineSelection::Parakeet { model_name } => {
             let parakeet_manager = app.state::<ParakeetManager>();
@@ -2286,7 +4605,7 @@ pub async fn transcribe_audio(
                     &app,
                     &model_name,
                     temp_path.clone(),
-                    Some(language.clone()),
+                    language.clone(),
                     translate_to_english,
                 )
                 .await
@@ -2297,7 +4616,7 @@ pub async fn transcribe_audio(
             }
         }
         ActiveEngineSelection::Soniox { .. } => {
-            soniox_transcribe_async(&app, &temp_path, Some(&language)).await?
+            soniox_transcribe_async(&app, &temp_path, language.as_deref()).await?
         }
     };
 
@@ -2306,27 +4625,247 @@ pub async fn transcribe_audio(
         log::warn!("Failed to remove test audio file: {}", e);
     }
 
-    Ok(text)
+    Ok(post_process_with_config(&app, text, language.as_deref()).await)
+}
+
+/// Post-processing shared by recordings and uploads: collapsing looped phrases,
+/// spoken commands, number normalization, then the replacement dictionary
+fn post_process_transcription(
+    text: &str,
+    config: &RecordingConfig,
+    language: Option<&str>,
+) -> String {
+    // Translated output is always English
+    let language = if config.translate_to_english {
+        Some("en")
+    } else {
+        language
+    };
+
+    // Before voice commands, which would turn a looped "period" into a row of dots
+    let text = if config.deduplicate_repeats {
+        collapse_repeated_phrases(text)
+    } else {
+        text.to_string()
+    };
+
+    let mut text = if config.voice_commands_enabled {
+        apply_voice_commands(&text, language, &config.voice_command_map)
+    } else {
+        text
+    };
+
+    // Number words are English-only; unknown languages are assumed English like voice commands
+    let is_english = language.is_none_or(|l| l == "en" || l.starts_with("en-"));
+    if config.normalize_numbers && is_english {
+        text = normalize_spoken_numbers(&text);
+    }
+
+    apply_replacement_rules(&text, &config.replacement_rules)
+}
+
+/// Post-process an upload transcription using the cached recording config
+async fn post_process_with_config(app: &AppHandle, text: String, language: Option<&str>) -> String {
+    match get_recording_config(app).await {
+        Ok(config) => post_process_transcription(
+            &text,
+            &config,
+            resolve_entry_language(language, None).as_deref(),
+        ),
+        Err(e) => {
+            log::warn!(
+                "Skipping transcript post-processing, config unavailable: {}",
+                e
+            );
+            text
+        }
+    }
 }
 
 // Soniox async transcription via v1 Files + Transcriptions flow
+const SONIOX_API_BASE: &str = "https://api.soniox.com/v1";
+
+/// An uploaded file and its transcription job on Soniox. Dropping it before
+/// [`SonioxJob::finish`] (the recording was cancelled and its task aborted, which drops
+/// the in-flight request) deletes both server-side so Soniox stops working on them.
+pub(crate) struct SonioxJob {
+    client: reqwest::Client,
+    base: String,
+    key: String,
+    file_id: String,
+    transcription_id: Option<String>,
+    finished: bool,
+}
+
+impl SonioxJob {
+    pub(crate) fn new(client: reqwest::Client, base: &str, key: &str, file_id: String) -> Self {
+        Self {
+            client,
+            base: base.to_string(),
+            key: key.to_string(),
+            file_id,
+            transcription_id: None,
+            finished: false,
+        }
+    }
+
+    pub(crate) fn set_transcription_id(&mut self, transcription_id: String) {
+        self.transcription_id = Some(transcription_id);
+    }
+
+    /// The transcript was fetched; nothing is left to cancel
+    pub(crate) fn finish(mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for SonioxJob {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        let client = self.client.clone();
+        let base = self.base.clone();
+        let key = self.key.clone();
+        let file_id = self.file_id.clone();
+        let transcription_id = self.transcription_id.clone();
+        tauri::async_runtime::spawn(async move {
+            delete_soniox_job(&client, &base, &key, transcription_id.as_deref(), &file_id).await;
+        });
+    }
+}
+
+/// Best-effort removal of an abandoned Soniox job and its upload
+pub(crate) async fn delete_soniox_job(
+    client: &reqwest::Client,
+    base: &str,
+    key: &str,
+    transcription_id: Option<&str>,
+    file_id: &str,
+) {
+    let mut urls = Vec::new();
+    if let Some(id) = transcription_id {
+        urls.push(format!("{}/transcriptions/{}", base, id));
+    }
+    urls.push(format!("{}/files/{}", base, file_id));
+
+    for url in urls {
+        match client.delete(&url).bearer_auth(key).send().await {
+            Ok(resp) if resp.status().is_success() => {
+                log::info!("Deleted cancelled Soniox job resource {}", url)
+            }
+            Ok(resp) => log::warn!("Deleting {} returned HTTP {}", url, resp.status()),
+            Err(e) => log::warn!("Failed to delete {}: {}", url, e),
+        }
+    }
+}
+
+/// The language an upload on `engine` uses: the engine's override when set,
+/// otherwise the global `language`
+fn upload_language(app: &AppHandle, engine: &str) -> Result<Option<String>, String> {
+    let store = app.store("settings").map_err(|e| e.to_string())?;
+    let global = store
+        .get("language")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| "en".to_string());
+    let engine_override = store
+        .get(format!("{}_language", engine))
+        .and_then(|v| v.as_str().map(|s| s.to_string()));
+    Ok(resolve_engine_language(
+        validate_language(Some(&global)),
+        engine_override.as_deref(),
+    ))
+}
+
 async fn soniox_transcribe_async(
     app: &AppHandle,
     wav_path: &Path,
     language: Option<&str>,
 ) -> Result<String, String> {
+    let json = soniox_fetch_transcript(app, wav_path, language, false).await?;
+    soniox_transcript_text(&json)
+}
+
+/// A missing ffmpeg is reported as is, since reinstalling is the fix; other
+/// failures say normalization is what broke
+fn upload_normalization_error(e: String) -> String {
+    if crate::ffmpeg::is_ffmpeg_missing(&e) {
+        e
+    } else {
+        format!("Audio normalization (ffmpeg) failed: {}", e)
+    }
+}
+
+/// A loudness-normalized copy of `wav_path` to upload in its place, when
+/// `loudness_normalize_cloud` is on. The caller deletes it after reading.
+async fn loudness_normalized_upload(app: &AppHandle, wav_path: &Path) -> Option<PathBuf> {
+    if !crate::ffmpeg::loudness_for_cloud_enabled(app) {
+        return None;
+    }
+    let file_name = wav_path.file_name()?.to_string_lossy();
+    let out_path = wav_path.with_file_name(format!("loudnorm_{}", file_name));
+    match crate::ffmpeg::normalize_streaming(app, wav_path, &out_path).await {
+        Ok(()) => Some(out_path),
+        Err(e) => {
+            log::warn!(
+                "Loudness normalization for upload failed, sending as recorded: {}",
+                e
+            );
+            let _ = std::fs::remove_file(&out_path);
+            None
+        }
+    }
+}
+
+/// Upload, transcribe and fetch the raw Soniox transcript (`text` plus timed `tokens`).
+/// With `speakers`, tokens carry a `speaker` label from Soniox's diarization.
+/// Network errors and 5xx responses are retried when `retry_cloud_transcription` is on.
+async fn soniox_fetch_transcript(
+    app: &AppHandle,
+    wav_path: &Path,
+    language: Option<&str>,
+    speakers: bool,
+) -> Result<serde_json::Value, String> {
+    let retry_enabled = app
+        .store("settings")
+        .ok()
+        .and_then(|store| store.get("retry_cloud_transcription"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if !retry_enabled {
+        return soniox_fetch_transcript_once(app, wav_path, language, speakers).await;
+    }
+    retry_with_backoff(
+        RetryPolicy::from_store(app),
+        "Soniox transcription",
+        |_| soniox_fetch_transcript_once(app, wav_path, language, speakers),
+        is_transient_cloud_error,
+    )
+    .await
+}
+
+async fn soniox_fetch_transcript_once(
+    app: &AppHandle,
+    wav_path: &Path,
+    language: Option<&str>,
+    speakers: bool,
+) -> Result<serde_json::Value, String> {
     use reqwest::multipart::{Form, Part};
     use tokio::fs;
 
     let key = crate::secure_store::secure_get(app, "stt_api_key_soniox")?
         .ok_or_else(|| "Soniox API key not set".to_string())?;
 
-    let wav_bytes = fs::read(wav_path)
-        .await
-        .map_err(|e| format!("Failed to read audio file: {}", e))?;
+    // Soniox gets the audio as recorded unless loudness normalization is enabled for it
+    let normalized_upload = loudness_normalized_upload(app, wav_path).await;
+    let wav_bytes = fs::read(normalized_upload.as_deref().unwrap_or(wav_path)).await;
+    if let Some(path) = &normalized_upload {
+        let _ = std::fs::remove_file(path);
+    }
+    let wav_bytes = wav_bytes.map_err(|e| format!("Failed to read audio file: {}", e))?;
 
     let client = reqwest::Client::new();
-    let base = "https://api.soniox.com/v1";
+    let base = SONIOX_API_BASE;
 
     // 1) Upload file -> file_id
     let filename = wav_path
@@ -2359,6 +4898,8 @@ async fn soniox_transcribe_async(
         .and_then(|v| v.as_str())
         .ok_or("Missing file_id")?
         .to_string();
+    // From here on, an abort or error cleans up what was created on Soniox
+    let mut job = SonioxJob::new(client.clone(), base, &key, file_id.clone());
 
     // 2) Create transcription -> transcription_id
     let mut payload = serde_json::json!({
@@ -2368,6 +4909,9 @@ async fn soniox_transcribe_async(
     if let Some(lang) = language {
         payload["language_hints"] = serde_json::json!([lang]);
     }
+    if speakers {
+        payload["enable_speaker_diarization"] = serde_json::json!(true);
+    }
 
     let create_url = format!("{}/transcriptions", base);
     let create_resp = client
@@ -2393,6 +4937,7 @@ async fn soniox_transcribe_async(
         .and_then(|v| v.as_str())
         .ok_or("Missing transcription id")?
         .to_string();
+    job.set_transcription_id(transcription_id.clone());
 
     // 3) Poll status
     let status_url = format!("{}/transcriptions/{}", base, transcription_id);
@@ -2448,8 +4993,12 @@ async fn soniox_transcribe_async(
             code, snippet
         ));
     }
-    let json: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+    let transcript: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+    job.finish();
+    Ok(transcript)
+}
 
+fn soniox_transcript_text(json: &serde_json::Value) -> Result<String, String> {
     // Prefer direct text if present, else join tokens
     if let Some(text) = json.get("text").and_then(|v| v.as_str()) {
         return Ok(text.to_string());
@@ -2474,6 +5023,110 @@ async fn soniox_transcribe_async(
     Err("Soniox transcript format not recognized".to_string())
 }
 
+/// A run of speech attributed to one speaker
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SpeakerSegment {
+    pub speaker_id: String,
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Group the tokens of a diarized Soniox transcript into per-speaker segments.
+/// Token texts carry their own leading spaces, so they are joined as-is.
+pub(crate) fn parse_soniox_speaker_segments(
+    json: &serde_json::Value,
+) -> Result<Vec<SpeakerSegment>, String> {
+    let tokens = json
+        .get("tokens")
+        .and_then(|v| v.as_array())
+        .ok_or("Soniox transcript has no tokens")?;
+
+    let mut segments: Vec<SpeakerSegment> = Vec::new();
+    for token in tokens {
+        let Some(text) = token.get("text").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let speaker_id = match token.get("speaker") {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(serde_json::Value::Number(n)) => n.to_string(),
+            _ => "unknown".to_string(),
+        };
+        let start_ms = token.get("start_ms").and_then(|v| v.as_u64()).unwrap_or(0);
+        let end_ms = token
+            .get("end_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(start_ms);
+
+        match segments.last_mut() {
+            Some(segment) if segment.speaker_id == speaker_id => {
+                segment.text.push_str(text);
+                segment.end_ms = segment.end_ms.max(end_ms);
+            }
+            _ => segments.push(SpeakerSegment {
+                speaker_id,
+                text: text.to_string(),
+                start_ms,
+                end_ms,
+            }),
+        }
+    }
+
+    for segment in &mut segments {
+        segment.text = segment.text.trim().to_string();
+    }
+    segments.retain(|segment| !segment.text.is_empty());
+    Ok(segments)
+}
+
+/// Transcribe a file with speaker labels and save it to history with its segments.
+/// Only Soniox provides diarization; other engines return an error.
+#[tauri::command]
+pub async fn transcribe_with_speakers(
+    app: AppHandle,
+    file_path: String,
+    model_name: String,
+    model_engine: Option<String>,
+) -> Result<Vec<SpeakerSegment>, String> {
+    validate_recording_requirements(&app).await?;
+
+    let audio_path = Path::new(&file_path);
+    if !audio_path.exists() {
+        return Err(format!("Audio file not found: {}", file_path));
+    }
+
+    let engine_selection =
+        resolve_engine_for_model(&app, &model_name, model_engine.as_deref()).await?;
+    if !matches!(engine_selection, ActiveEngineSelection::Soniox { .. }) {
+        return Err(format!(
+            "Speaker labels are not supported for the {} engine. Switch to Soniox to transcribe with speakers.",
+            engine_selection.engine_name()
+        ));
+    }
+
+    let language = upload_language(&app, engine_selection.engine_name())?;
+
+    let json = soniox_fetch_transcript(&app, audio_path, language.as_deref(), true).await?;
+    let segments = parse_soniox_speaker_segments(&json)?;
+    log::info!(
+        "[SPEAKERS] {} segments from {} speakers",
+        segments.len(),
+        segments
+            .iter()
+            .map(|segment| segment.speaker_id.as_str())
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    );
+
+    let text =
+        post_process_with_config(&app, soniox_transcript_text(&json)?, language.as_deref()).await;
+    let mut metadata = serde_json::Map::new();
+    metadata.insert("speakers".to_string(), serde_json::json!(segments));
+    save_transcription_with_metadata(app, text, model_name, metadata).await?;
+
+    Ok(segments)
+}
+
 #[tauri::command]
 pub async fn cancel_recording(app: AppHandle) -> Result<(), String> {
     log::info!("=== CANCEL RECORDING CALLED ===");
@@ -2482,6 +5135,7 @@ pub async fn cancel_recording(app: AppHandle) -> Result<(), String> {
     let app_state = app.state::<AppState>();
     app_state.request_cancellation();
     log::info!("Cancellation requested in app state");
+    crate::menu::stop_tray_recording_timer(&app);
 
     // Get current state
     let current_state = app_state.get_current_state();
@@ -2494,6 +5148,17 @@ pub async fn cancel_recording(app: AppHandle) -> Result<(), String> {
             task.abort();
         }
     }
+    if let Ok(mut stream_guard) = app_state.soniox_stream.lock() {
+        if let Some(session) = stream_guard.take() {
+            log::info!("Closing Soniox stream");
+            session.abort();
+        }
+    }
+    if let Ok(mut attachment_guard) = app_state.pending_attachment.lock() {
+        if let Some(capture) = attachment_guard.take() {
+            discard_pending_attachment(capture);
+        }
+    }
 
     // Stop recording if active
     let recorder_state = app.state::<RecorderState>();
@@ -2517,6 +5182,7 @@ pub async fn cancel_recording(app: AppHandle) -> Result<(), String> {
                 .map_err(|e| format!("Failed to acquire recorder lock: {}", e))?;
             let _ = recorder.stop_recording()?;
         }
+        clear_recording_checkpoint(&app);
 
         // Clean up audio file if it exists
         if let Ok(path_guard) = app_state.current_recording_path.lock() {
@@ -2593,19 +5259,72 @@ pub async fn cancel_recording(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Set the cancellation flag of every download registered in `active_downloads`.
+/// Returns how many downloads were signalled.
+pub(crate) fn cancel_active_downloads(
+    active_downloads: &Mutex<HashMap<String, Arc<AtomicBool>>>,
+) -> usize {
+    match active_downloads.lock() {
+        Ok(downloads) => {
+            for (model_name, cancel_flag) in downloads.iter() {
+                cancel_flag.store(true, AtomicOrdering::Relaxed);
+                log::info!("abort_all: cancelling download for model {}", model_name);
+            }
+            downloads.len()
+        }
+        Err(e) => {
+            log::error!("abort_all: failed to lock active downloads: {}", e);
+            0
+        }
+    }
+}
+
+/// Cancel every in-flight operation: recording, transcription (including AI
+/// enhancement, which runs inside the transcription task) and model downloads.
+/// Unlike `reset_app_data` this leaves settings, history and models untouched.
 #[tauri::command]
-pub async fn delete_transcription_entry(app: AppHandle, timestamp: String) -> Result<(), String> {
-    let store = app
-        .store("transcriptions")
-        .map_err(|e| format!("Failed to get transcriptions store: {}", e))?;
+pub async fn abort_all(
+    app: AppHandle,
+    active_downloads: State<'_, Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>>,
+) -> Result<(), String> {
+    log::info!("=== ABORT ALL CALLED ===");
+
+    // Recording and transcription/enhancement task
+    if let Err(e) = cancel_recording(app.clone()).await {
+        log::warn!("abort_all: cancel_recording failed: {}", e);
+    }
+
+    // Pending model downloads
+    cancel_active_downloads(&active_downloads);
+
+    // Clear leftover hotkey flags so the next recording starts clean
+    let app_state = app.state::<AppState>();
+    app_state
+        .pending_stop_after_start
+        .store(false, AtomicOrdering::SeqCst);
+    app_state.ptt_key_held.store(false, AtomicOrdering::SeqCst);
+    if let Ok(mut detector) = app_state.double_tap.lock() {
+        detector.reset();
+    }
 
-    // Delete the entry
-    store.delete(&timestamp);
+    // cancel_recording may have bailed out early; make sure we end up Idle
+    if app_state.get_current_state() != RecordingState::Idle {
+        update_recording_state(&app, RecordingState::Error, Some("Aborted".to_string()));
+        update_recording_state(&app, RecordingState::Idle, None);
+    }
+
+    let _ = app.emit("operations-aborted", ());
+
+    log::info!("=== ABORT ALL COMPLETED ===");
+    Ok(())
+}
 
-    // Save the store
-    store
-        .save()
-        .map_err(|e| format!("Failed to save store after deletion: {}", e))?;
+#[tauri::command]
+pub async fn delete_transcription_entry(app: AppHandle, timestamp: String) -> Result<(), String> {
+    // Delete the entry from its month's store along with any audio kept for it
+    for entry in history_store::delete_raw_entries(&app, std::slice::from_ref(&timestamp))? {
+        remove_entry_files(&entry);
+    }
 
     // Emit event to update UI
     let _ = emit_to_window(&app, "main", "history-updated", ());
@@ -2623,23 +5342,13 @@ pub async fn delete_transcription_entry(app: AppHandle, timestamp: String) -> Re
 pub async fn clear_all_transcriptions(app: AppHandle) -> Result<(), String> {
     log::info!("[Clear All] Clearing all transcriptions");
 
-    let store = app
-        .store("transcriptions")
-        .map_err(|e| format!("Failed to get transcriptions store: {}", e))?;
-
-    // Get all keys and delete them
-    let keys: Vec<String> = store.keys().into_iter().map(|k| k.to_string()).collect();
-    let count = keys.len();
-
-    for key in keys {
-        store.delete(&key);
+    // Empty every month's store, removing any audio kept for the entries
+    let removed = history_store::clear_raw_entries(&app)?;
+    let count = removed.len();
+    for entry in &removed {
+        remove_entry_files(entry);
     }
 
-    // Save the store
-    store
-        .save()
-        .map_err(|e| format!("Failed to save store after clearing: {}", e))?;
-
     // Emit event to update UI
     let _ = emit_to_window(&app, "main", "history-updated", ());
 
@@ -2652,6 +5361,154 @@ pub async fn clear_all_transcriptions(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Shortest clip silence trimming may leave; anything less means the
+/// threshold ate the speech, so the untrimmed recording is kept
+const MIN_TRIMMED_RECORDING_SECS: f32 = 0.1;
+
+/// Length in seconds of a WAV file
+fn clear_recording_checkpoint(app: &AppHandle) {
+    if let Ok(data_dir) = app.path().app_data_dir() {
+        checkpoint::clear_checkpoint(&data_dir);
+    }
+}
+
+/// Offer a recording the last session never got to stop (crash, force quit) for
+/// transcription via `recovered-recording`, instead of leaving it unreferenced
+pub async fn recover_orphaned_recording(app: &AppHandle) {
+    let Ok(data_dir) = app.path().app_data_dir() else {
+        return;
+    };
+    let orphan =
+        tokio::task::spawn_blocking(move || checkpoint::take_orphaned_recording(&data_dir))
+            .await
+            .unwrap_or(checkpoint::OrphanedRecording::None);
+
+    match orphan {
+        checkpoint::OrphanedRecording::None => {}
+        checkpoint::OrphanedRecording::Missing => {
+            log::info!("Interrupted recording left no audio behind");
+        }
+        checkpoint::OrphanedRecording::TooShort(path) => {
+            log::info!("Removing interrupted recording too short to transcribe");
+            if let Err(e) = std::fs::remove_file(&path) {
+                log::warn!("Failed to remove interrupted recording: {}", e);
+            }
+        }
+        checkpoint::OrphanedRecording::Recoverable {
+            checkpoint,
+            duration_secs,
+        } => {
+            log::info!(
+                "Found interrupted recording {:?} ({:.1}s), offering to transcribe it",
+                checkpoint.path,
+                duration_secs
+            );
+            let recovered = checkpoint::RecoveredRecording {
+                path: checkpoint.path.to_string_lossy().into_owned(),
+                started_at: checkpoint.started_at,
+                duration_secs,
+            };
+            if let Err(e) = crate::emit_to_all(app, "recovered-recording", recovered) {
+                log::warn!("{}", e);
+            }
+        }
+    }
+}
+
+/// Pill toast confirming what was captured, e.g. "42 words · 8s"
+pub(crate) fn result_summary(text: &str, duration_secs: Option<f32>) -> String {
+    let words = text.split_whitespace().count();
+    let words = format!("{} {}", words, if words == 1 { "word" } else { "words" });
+    let Some(duration_secs) = duration_secs else {
+        return words;
+    };
+
+    let secs = (duration_secs.round() as u64).max(1);
+    if secs < 60 {
+        format!("{} · {}s", words, secs)
+    } else {
+        format!("{} · {}m {:02}s", words, secs / 60, secs % 60)
+    }
+}
+
+fn wav_duration_secs(path: &Path) -> Result<f32, String> {
+    let reader = hound::WavReader::open(path).map_err(|e| format!("Failed to open wav: {}", e))?;
+    let spec = reader.spec();
+    let frames = reader.duration() / spec.channels.max(1) as u32;
+    Ok(frames as f32 / spec.sample_rate.max(1) as f32)
+}
+
+/// Refuse a trimmed recording that is empty or too short to hold speech
+pub(crate) fn check_trimmed_duration(duration_secs: f32) -> Result<(), String> {
+    if duration_secs < MIN_TRIMMED_RECORDING_SECS {
+        return Err(format!(
+            "Trimming left {:.2}s of audio, keeping the original",
+            duration_secs
+        ));
+    }
+    Ok(())
+}
+
+/// Trim leading and trailing silence from a recording kept in history,
+/// replacing the file. Returns the trimmed length, or `None` if the original was kept.
+async fn trim_saved_recording(app: &AppHandle, path: &Path) -> Option<f32> {
+    let trimmed_path = path.with_extension("trimmed.wav");
+    let result = async {
+        crate::ffmpeg::trim_silence(app, path, &trimmed_path).await?;
+        let duration = wav_duration_secs(&trimmed_path)?;
+        check_trimmed_duration(duration)?;
+        std::fs::rename(&trimmed_path, path)
+            .map_err(|e| format!("Failed to replace recording: {}", e))?;
+        Ok::<f32, String>(duration)
+    }
+    .await;
+
+    match result {
+        Ok(duration) => {
+            log::info!("Trimmed silence from {:?}, {:.2}s left", path, duration);
+            Some(duration)
+        }
+        Err(e) => {
+            log::warn!("Not trimming {:?}: {}", path, e);
+            let _ = std::fs::remove_file(&trimmed_path);
+            None
+        }
+    }
+}
+
+/// Remove files kept for a history entry: audio (keep_raw_audio) and its
+/// screenshot attachment
+pub(crate) fn remove_entry_files(entry: &serde_json::Value) {
+    for field in ["recording_file", "raw_recording_file"] {
+        if let Some(path) = entry.get(field).and_then(|v| v.as_str()) {
+            if let Err(e) = std::fs::remove_file(path) {
+                log::debug!("Failed to remove {} {}: {}", field, path, e);
+            }
+        }
+    }
+    attachments::remove_attachment(entry);
+}
+
+/// Wait for the screenshot taken when the recording started (attach_screenshot)
+async fn take_pending_attachment(app: &AppHandle) -> Option<PathBuf> {
+    let capture = app
+        .state::<AppState>()
+        .pending_attachment
+        .lock()
+        .ok()?
+        .take()?;
+    capture.await.ok().flatten()
+}
+
+/// Delete the screenshot of a recording that won't reach history, once it's written
+fn discard_pending_attachment(capture: tauri::async_runtime::JoinHandle<Option<PathBuf>>) {
+    tauri::async_runtime::spawn(async move {
+        if let Ok(Some(path)) = capture.await {
+            let _ = std::fs::remove_file(path);
+        }
+    });
+}
+
 #[derive(serde::Serialize)]
 pub struct RecordingStateResponse {
     state: String,
@@ -2,7 +2,7 @@ use arboard;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use clipboard_rs::{common::RustImage, Clipboard, ClipboardContext};
 use image;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
 #[tauri::command]
@@ -128,3 +128,149 @@ pub async fn save_image_to_file(image_data_url: String, file_path: String) -> Re
     log::info!("Successfully saved image to: {}", path.display());
     Ok(())
 }
+
+/// File types the audio upload accepts, also used to spot copied audio files
+pub const AUDIO_FILE_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "flac", "ogg", "mp4", "webm"];
+
+/// Clipboard formats apps use when copying raw audio rather than a file
+const AUDIO_CLIPBOARD_FORMATS: &[&str] = &[
+    "audio/wav",
+    "audio/x-wav",
+    "audio/mpeg",
+    "audio/ogg",
+    "audio/mp4",
+    "com.microsoft.waveform-audio",
+    "public.mp3",
+    "com.apple.m4a-audio",
+];
+
+/// What the clipboard holds, as far as finding audio goes
+#[derive(Debug, Default)]
+pub struct ClipboardContents {
+    /// Copied files, as paths or `file://` URIs
+    pub files: Vec<String>,
+    pub text: Option<String>,
+    /// Data copied in one of the raw audio formats
+    pub audio_bytes: Option<Vec<u8>>,
+}
+
+/// Audio found on the clipboard
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipboardAudio {
+    /// A copied audio file, or its path copied as text
+    File(PathBuf),
+    /// Raw audio data and the file extension for its format
+    Bytes {
+        data: Vec<u8>,
+        extension: &'static str,
+    },
+}
+
+pub fn is_audio_file_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            AUDIO_FILE_EXTENSIONS
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(ext))
+        })
+}
+
+/// A path from a clipboard entry: surrounding quotes and a `file://` prefix
+/// are dropped and percent-escapes decoded
+fn clipboard_entry_path(entry: &str) -> PathBuf {
+    let entry = entry.trim().trim_matches(|c| c == '"' || c == '\'');
+    let Some(uri_path) = entry.strip_prefix("file://") else {
+        return PathBuf::from(entry);
+    };
+
+    let bytes = uri_path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    PathBuf::from(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+/// The file extension for raw audio data, recognized by its header
+pub fn sniff_audio_extension(data: &[u8]) -> Option<&'static str> {
+    match data {
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => Some("wav"),
+        [b'f', b'L', b'a', b'C', ..] => Some("flac"),
+        [b'O', b'g', b'g', b'S', ..] => Some("ogg"),
+        [b'I', b'D', b'3', ..] => Some("mp3"),
+        [0xFF, second, ..] if second & 0xE0 == 0xE0 => Some("mp3"),
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => Some("m4a"),
+        [0x1A, 0x45, 0xDF, 0xA3, ..] => Some("webm"),
+        _ => None,
+    }
+}
+
+/// Decide what to transcribe from the clipboard. Copied files win over text,
+/// and text (a path) over raw data; anything that isn't audio is an error.
+pub fn clipboard_audio_source(contents: ClipboardContents) -> Result<ClipboardAudio, String> {
+    if !contents.files.is_empty() {
+        let paths: Vec<PathBuf> = contents
+            .files
+            .iter()
+            .map(|entry| clipboard_entry_path(entry))
+            .collect();
+        return match paths.iter().find(|path| is_audio_file_path(path)) {
+            Some(path) => Ok(ClipboardAudio::File(path.clone())),
+            None => Err(format!(
+                "The copied file is not an audio file: {}",
+                paths[0].display()
+            )),
+        };
+    }
+
+    if let Some(text) = contents.text.as_deref().filter(|t| !t.trim().is_empty()) {
+        let path = clipboard_entry_path(text);
+        if !text.trim().contains('\n') && is_audio_file_path(&path) {
+            return Ok(ClipboardAudio::File(path));
+        }
+    }
+
+    if let Some(data) = contents.audio_bytes {
+        if let Some(extension) = sniff_audio_extension(&data) {
+            return Ok(ClipboardAudio::Bytes { data, extension });
+        }
+    }
+
+    Err(format!(
+        "The clipboard doesn't contain audio. Copy an audio file ({}) and try again.",
+        AUDIO_FILE_EXTENSIONS.join(", ")
+    ))
+}
+
+/// Read files, text and raw audio from the system clipboard. Blocking.
+pub fn read_clipboard_contents() -> Result<ClipboardContents, String> {
+    let ctx = ClipboardContext::new()
+        .map_err(|e| format!("Failed to create clipboard context: {:?}", e))?;
+
+    let audio_bytes = AUDIO_CLIPBOARD_FORMATS
+        .iter()
+        .filter_map(|format| ctx.get_buffer(format).ok())
+        .find(|data| !data.is_empty());
+
+    Ok(ClipboardContents {
+        files: ctx.get_files().unwrap_or_default(),
+        text: ctx.get_text().ok(),
+        audio_bytes,
+    })
+}
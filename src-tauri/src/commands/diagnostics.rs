@@ -0,0 +1,228 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::commands::settings_transfer::is_secret_key;
+use crate::recognition::RecognitionAvailabilitySnapshot;
+use crate::utils::redaction::REDACTED;
+
+/// How many of the latest error lines from the log go into a report
+const MAX_RECENT_ERRORS: usize = 50;
+
+/// Values following a credential-looking name, e.g. `api_key=...` or `"license_key": "..."`
+static CREDENTIAL_VALUE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?i)((?:api[_-]?key|license[_-]?key|token|secret|password|authorization)["']?\s*[:=]\s*["']?)(?:bearer\s+)?[^\s"',}]+"#,
+    )
+    .expect("valid credential regex")
+});
+
+/// Provider key formats that can show up without a name in front of them
+static BARE_API_KEY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:sk|gsk|xai|AIza)[-_A-Za-z0-9]{16,}").expect("valid api key regex")
+});
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppDiagnostics {
+    pub version: String,
+    pub debug_build: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemDiagnostics {
+    pub os: String,
+    pub os_version: Option<String>,
+    pub arch: String,
+}
+
+/// `None` when the check itself failed
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionDiagnostics {
+    pub microphone: Option<bool>,
+    pub accessibility: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskDiagnostics {
+    /// Free space on the disk holding the app data (models, recordings)
+    pub app_data_free_bytes: Option<u64>,
+}
+
+/// Everything a support request needs, with API keys and the license stripped
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsReport {
+    pub generated_at: String,
+    pub app: AppDiagnostics,
+    pub system: SystemDiagnostics,
+    pub models: RecognitionAvailabilitySnapshot,
+    pub audio_devices: Vec<String>,
+    pub permissions: PermissionDiagnostics,
+    pub settings: Map<String, Value>,
+    pub recent_errors: Vec<String>,
+    pub disk: DiskDiagnostics,
+}
+
+/// Raw material for a [`DiagnosticsReport`], gathered from the running app
+pub struct DiagnosticsSources {
+    pub models: RecognitionAvailabilitySnapshot,
+    pub audio_devices: Vec<String>,
+    pub permissions: PermissionDiagnostics,
+    pub settings: Map<String, Value>,
+    /// Contents of the current log file
+    pub log_text: String,
+    pub app_data_free_bytes: Option<u64>,
+}
+
+/// Mask anything in `text` that looks like an API key, token or license key
+pub fn scrub_secrets(text: &str) -> String {
+    let text = CREDENTIAL_VALUE.replace_all(text, |caps: &regex::Captures| {
+        format!("{}{}", &caps[1], REDACTED)
+    });
+    BARE_API_KEY.replace_all(&text, REDACTED).into_owned()
+}
+
+/// The last `max` error lines of a log, scrubbed of secrets
+pub fn recent_error_lines(log_text: &str, max: usize) -> Vec<String> {
+    let errors: Vec<&str> = log_text
+        .lines()
+        .filter(|line| line.contains("[ERROR]"))
+        .collect();
+    errors[errors.len().saturating_sub(max)..]
+        .iter()
+        .map(|line| scrub_secrets(line))
+        .collect()
+}
+
+/// Settings with secret-looking keys dropped and string values scrubbed
+pub fn sanitize_settings(settings: Map<String, Value>) -> Map<String, Value> {
+    settings
+        .into_iter()
+        .filter(|(key, _)| !is_secret_key(key))
+        .map(|(key, value)| match value {
+            Value::String(s) => (key, Value::String(scrub_secrets(&s))),
+            value => (key, value),
+        })
+        .collect()
+}
+
+pub fn build_diagnostics_report(
+    sources: DiagnosticsSources,
+    generated_at: &str,
+) -> DiagnosticsReport {
+    DiagnosticsReport {
+        generated_at: generated_at.to_string(),
+        app: AppDiagnostics {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            debug_build: cfg!(debug_assertions),
+        },
+        system: SystemDiagnostics {
+            os: std::env::consts::OS.to_string(),
+            os_version: sysinfo::System::long_os_version(),
+            arch: std::env::consts::ARCH.to_string(),
+        },
+        models: sources.models,
+        audio_devices: sources.audio_devices,
+        permissions: sources.permissions,
+        settings: sanitize_settings(sources.settings),
+        recent_errors: recent_error_lines(&sources.log_text, MAX_RECENT_ERRORS),
+        disk: DiskDiagnostics {
+            app_data_free_bytes: sources.app_data_free_bytes,
+        },
+    }
+}
+
+/// The most recently written `voicetypr-*.log` in `log_dir`
+fn latest_log_file(log_dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(log_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with("voicetypr") && name.ends_with(".log")
+        })
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Gather a report from the running app
+pub async fn collect_diagnostics(app: &AppHandle) -> DiagnosticsReport {
+    let settings = match app.store("settings") {
+        Ok(store) => store.entries().into_iter().collect(),
+        Err(_) => Map::new(),
+    };
+    let log_text = app
+        .path()
+        .app_log_dir()
+        .ok()
+        .and_then(|dir| latest_log_file(&dir))
+        .and_then(|path| std::fs::read(path).ok())
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default();
+    let app_data_free_bytes = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .and_then(|dir| crate::utils::system_monitor::available_space_at(&dir));
+
+    let sources = DiagnosticsSources {
+        models: crate::recognition_availability_snapshot(app).await,
+        audio_devices: crate::commands::audio::get_audio_devices(app.clone())
+            .await
+            .unwrap_or_default(),
+        permissions: PermissionDiagnostics {
+            microphone: crate::commands::permissions::check_microphone_permission()
+                .await
+                .ok(),
+            accessibility: crate::commands::permissions::check_accessibility_permission()
+                .await
+                .ok(),
+        },
+        settings,
+        log_text,
+        app_data_free_bytes,
+    };
+    build_diagnostics_report(sources, &chrono::Utc::now().to_rfc3339())
+}
+
+/// Write a diagnostics report next to the logs and return its path.
+/// With `open_folder`, the logs folder is opened afterwards.
+#[tauri::command]
+pub async fn generate_diagnostics(
+    app: AppHandle,
+    open_folder: Option<bool>,
+) -> Result<String, String> {
+    let report = collect_diagnostics(&app).await;
+    let json = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Failed to serialize diagnostics: {}", e))?;
+
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to get log directory: {}", e))?;
+    std::fs::create_dir_all(&log_dir)
+        .map_err(|e| format!("Failed to create log directory: {}", e))?;
+    let path = log_dir.join(format!(
+        "diagnostics-{}.json",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write diagnostics: {}", e))?;
+    log::info!("Wrote diagnostics report to {:?}", path);
+
+    if open_folder.unwrap_or(false) {
+        crate::commands::logs::open_logs_folder(app).await?;
+    }
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Copy a diagnostics report to the clipboard (tray "Copy Diagnostics")
+pub async fn copy_diagnostics_to_clipboard(app: &AppHandle) -> Result<(), String> {
+    let report = collect_diagnostics(app).await;
+    let json = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Failed to serialize diagnostics: {}", e))?;
+    crate::commands::text::copy_text_to_clipboard(json).await
+}
@@ -1,6 +1,20 @@
 use crate::secure_store;
 use tauri::AppHandle;
 
+/// Secure store key of the Soniox API key, which decides whether Soniox is ready
+const SONIOX_KEY: &str = "stt_api_key_soniox";
+
+/// Let the UI know recognition availability may have changed with `key`
+fn notify_availability_if_needed(app: &AppHandle, key: &str) {
+    if key != SONIOX_KEY {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        crate::emit_recognition_availability(&app).await;
+    });
+}
+
 /// Validate key names to prevent edge cases and security issues
 fn validate_key(key: &str) -> Result<(), String> {
     // Check if key is empty
@@ -44,6 +58,7 @@ pub fn keyring_set(app: AppHandle, key: String, value: String) -> Result<(), Str
     // Save to secure store
     secure_store::secure_set(&app, &key, &value)?;
     log::info!("Saved to secure store: {}", key);
+    notify_availability_if_needed(&app, &key);
     Ok(())
 }
 
@@ -64,6 +79,7 @@ pub fn keyring_delete(app: AppHandle, key: String) -> Result<(), String> {
     // Delete from secure store
     secure_store::secure_delete(&app, &key)?;
     log::info!("Deleted from secure store: {}", key);
+    notify_availability_if_needed(&app, &key);
     Ok(())
 }
 
@@ -1,4 +1,7 @@
-use crate::license::{api_client::LicenseApiClient, device, keychain, LicenseState, LicenseStatus};
+use crate::license::{
+    api_client::LicenseApiClient, device, keychain, LicenseState, LicenseStatus,
+    LicenseStatusDetail,
+};
 use crate::simple_cache::{self as scache, SetItemOptions};
 use crate::AppState;
 use chrono::{DateTime, Duration, Utc};
@@ -76,27 +79,34 @@ fn format_duration(duration: &Duration) -> String {
 }
 
 // Helper function to convert hours to days with ceiling
-fn hours_to_days(hours: i64) -> i32 {
+pub(crate) fn hours_to_days(hours: i64) -> i32 {
     (hours as f64 / 24.0).ceil() as i32
 }
 
-// Check if we're within the grace period for offline access
-fn is_within_grace_period(app: &AppHandle) -> Option<i64> {
-    // Use store-backed cache helper
-    // let cache = app.cache();
-
-    if let Ok(Some(timestamp_json)) = scache::get(app, LAST_VALIDATION_KEY) {
-        if let Ok(last_validation) = serde_json::from_value::<DateTime<Utc>>(timestamp_json) {
-            let elapsed = Utc::now().signed_duration_since(last_validation);
-            let days_elapsed = elapsed.num_days();
-
-            if days_elapsed < OFFLINE_GRACE_PERIOD_DAYS {
-                return Some(OFFLINE_GRACE_PERIOD_DAYS - days_elapsed);
-            }
-        }
+// Days of offline access left `grace_days` after the last online validation
+pub(crate) fn grace_days_remaining(
+    last_validation: DateTime<Utc>,
+    now: DateTime<Utc>,
+    grace_days: i64,
+) -> Option<i64> {
+    let days_elapsed = now.signed_duration_since(last_validation).num_days();
+    if days_elapsed < grace_days {
+        Some(grace_days - days_elapsed)
+    } else {
+        None
     }
+}
 
-    None
+// Timestamp of the last successful online validation stored under `key`
+fn last_validation(app: &AppHandle, key: &str) -> Option<DateTime<Utc>> {
+    let timestamp_json = scache::get(app, key).ok().flatten()?;
+    serde_json::from_value::<DateTime<Utc>>(timestamp_json).ok()
+}
+
+// Check if we're within the grace period for offline access
+fn is_within_grace_period(app: &AppHandle) -> Option<i64> {
+    let validated = last_validation(app, LAST_VALIDATION_KEY)?;
+    grace_days_remaining(validated, Utc::now(), OFFLINE_GRACE_PERIOD_DAYS)
 }
 
 // Check if grace period timestamp exists (regardless of whether it's valid)
@@ -110,20 +120,42 @@ fn has_grace_period_timestamp(app: &AppHandle) -> bool {
 
 // Check if we're within the trial grace period
 fn is_within_trial_grace_period(app: &AppHandle) -> Option<i64> {
-    // let cache = app.cache();
-
-    if let Ok(Some(timestamp_json)) = scache::get(app, LAST_TRIAL_VALIDATION_KEY) {
-        if let Ok(last_validation) = serde_json::from_value::<DateTime<Utc>>(timestamp_json) {
-            let elapsed = Utc::now().signed_duration_since(last_validation);
-            let days_elapsed = elapsed.num_days();
+    let validated = last_validation(app, LAST_TRIAL_VALIDATION_KEY)?;
+    grace_days_remaining(validated, Utc::now(), TRIAL_OFFLINE_GRACE_PERIOD_DAYS)
+}
 
-            if days_elapsed < TRIAL_OFFLINE_GRACE_PERIOD_DAYS {
-                return Some(TRIAL_OFFLINE_GRACE_PERIOD_DAYS - days_elapsed);
-            }
-        }
+// Grace-period detail for `status`: licensed users get the long offline grace
+// period, trials the short one, expired or missing licenses none
+pub(crate) fn license_status_detail(
+    status: &LicenseStatus,
+    license_validated: Option<DateTime<Utc>>,
+    trial_validated: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> LicenseStatusDetail {
+    let (last_validated, grace_days) = match status.status {
+        LicenseState::Licensed => (license_validated, OFFLINE_GRACE_PERIOD_DAYS),
+        LicenseState::Trial => (trial_validated, TRIAL_OFFLINE_GRACE_PERIOD_DAYS),
+        LicenseState::Expired | LicenseState::None => (None, 0),
+    };
+    LicenseStatusDetail {
+        state: status.status.clone(),
+        trial_days_left: status.trial_days_left,
+        offline_grace_days_remaining: last_validated
+            .and_then(|validated| grace_days_remaining(validated, now, grace_days)),
+        last_validated: last_validated.map(|validated| validated.to_rfc3339()),
     }
+}
 
-    None
+fn emit_license_status_detail(app: &AppHandle, status: &LicenseStatus) {
+    let detail = license_status_detail(
+        status,
+        last_validation(app, LAST_VALIDATION_KEY),
+        last_validation(app, LAST_TRIAL_VALIDATION_KEY),
+        Utc::now(),
+    );
+    if let Err(e) = crate::emit_to_all(app, "license-status-detail", &detail) {
+        log::debug!("Failed to emit license-status-detail: {}", e);
+    }
 }
 
 // Conservative license deletion check - only delete when absolutely certain
@@ -149,8 +181,17 @@ pub async fn check_license_status(app: AppHandle) -> Result<LicenseStatus, Strin
     check_license_status_impl(app).await
 }
 
-/// Internal implementation of license status check
+/// Internal implementation of license status check. Also emits
+/// `license-status-detail` so the UI can warn before the grace period runs out.
 async fn check_license_status_impl(app: AppHandle) -> Result<LicenseStatus, String> {
+    let result = resolve_license_status(app.clone()).await;
+    if let Ok(status) = &result {
+        emit_license_status_detail(&app, status);
+    }
+    result
+}
+
+async fn resolve_license_status(app: AppHandle) -> Result<LicenseStatus, String> {
     // let cache = app.cache();
 
     // Try to get cached status (cache is cleared on app start)
@@ -0,0 +1,29 @@
+use tauri::{AppHandle, Manager};
+
+use crate::local_api::LocalApiState;
+
+/// What settings shows for the local API
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LocalApiInfo {
+    /// "http://127.0.0.1:<port>" while the server runs
+    pub url: Option<String>,
+    pub token: String,
+}
+
+#[tauri::command]
+pub async fn get_local_api_info(app: AppHandle) -> Result<LocalApiInfo, String> {
+    Ok(LocalApiInfo {
+        url: app
+            .state::<LocalApiState>()
+            .address()
+            .map(|addr| format!("http://{}", addr)),
+        token: crate::local_api::get_or_create_token(&app)?,
+    })
+}
+
+/// Issue a new token, locking out clients that have the old one
+#[tauri::command]
+pub async fn regenerate_local_api_token(app: AppHandle) -> Result<LocalApiInfo, String> {
+    crate::local_api::regenerate_token(&app).await?;
+    get_local_api_info(app).await
+}
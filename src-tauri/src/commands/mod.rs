@@ -3,14 +3,19 @@ pub mod audio;
 pub mod clipboard;
 pub mod debug;
 pub mod device;
+pub mod diagnostics;
 pub mod key_normalizer;
 pub mod keyring;
 pub mod license;
+pub mod local_api;
 pub mod logs;
 pub mod model;
 pub mod permissions;
+pub mod profiles;
 pub mod reset;
+pub mod self_test;
 pub mod settings;
+pub mod settings_transfer;
 pub mod stt;
 pub mod text;
 pub mod utils;
@@ -2,20 +2,22 @@ use crate::commands::license::check_license_status_internal;
 use crate::emit_to_all;
 use crate::license::LicenseState;
 use crate::parakeet::{ParakeetManager, ParakeetModelStatus};
+use crate::recognition::{best_model_for_language, model_candidates, ModelRecommendation};
 use crate::secure_store;
 use crate::utils::onboarding_logger;
-#[cfg(debug_assertions)]
 use crate::utils::system_monitor;
-use crate::whisper::manager::{ModelInfo, WhisperManager};
+use crate::whisper::manager::{ModelInfo, WhisperManager, MAX_DOWNLOAD_CONNECTIONS};
+use crate::whisper::transcriber::AccelerationCapabilities;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex as StdMutex};
 use std::time::Instant;
 use tauri::async_runtime::RwLock;
 use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_store::StoreExt;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum ModelEngine {
+pub(crate) enum ModelEngine {
     Whisper,
     Parakeet,
 }
@@ -29,6 +31,47 @@ impl ModelEngine {
     }
 }
 
+/// Free space required beyond the model itself, so the disk isn't filled to the last byte
+const DOWNLOAD_HEADROOM_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Space shortfall reported with an `insufficient_space` download error
+#[derive(Clone, Debug, PartialEq)]
+pub struct InsufficientSpace {
+    pub required_bytes: u64,
+    pub available_bytes: u64,
+}
+
+/// Decide whether a download of `model_size` bytes fits. Parallel downloads briefly
+/// hold the parts and the joined file at once, doubling the need. Unknown free space
+/// doesn't block the download.
+pub(crate) fn check_download_space(
+    model_size: u64,
+    connections: usize,
+    available_bytes: Option<u64>,
+) -> Result<(), InsufficientSpace> {
+    let copies = if connections > 1 { 2 } else { 1 };
+    let required_bytes = model_size
+        .saturating_mul(copies)
+        .saturating_add(DOWNLOAD_HEADROOM_BYTES);
+
+    match available_bytes {
+        Some(available_bytes) if available_bytes < required_bytes => Err(InsufficientSpace {
+            required_bytes,
+            available_bytes,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Connections to use for a whisper download, from the `download_connections` setting
+fn download_connections(app: &AppHandle) -> usize {
+    app.store("settings")
+        .ok()
+        .and_then(|store| store.get("download_connections"))
+        .and_then(|v| v.as_u64())
+        .map_or(1, |n| n.clamp(1, MAX_DOWNLOAD_CONNECTIONS as u64)) as usize
+}
+
 #[derive(Clone, Copy, Debug)]
 struct DownloadTarget {
     engine: ModelEngine,
@@ -50,6 +93,44 @@ pub async fn download_model(
 
     log::info!("Starting download for model: {}", model_name);
 
+    // Fail up front instead of partway through when the model won't fit
+    let connections = match download_target.engine {
+        ModelEngine::Whisper => download_connections(&app),
+        ModelEngine::Parakeet => 1,
+    };
+    let models_dir = whisper_state.read().await.models_dir().to_path_buf();
+    let available_bytes =
+        tokio::task::spawn_blocking(move || system_monitor::available_space_at(&models_dir))
+            .await
+            .ok()
+            .flatten();
+    if let Err(space) =
+        check_download_space(download_target.size_bytes, connections, available_bytes)
+    {
+        let msg = format!(
+            "Not enough disk space to download '{}': {} MB needed, {} MB available",
+            model_name,
+            space.required_bytes / (1024 * 1024),
+            space.available_bytes / (1024 * 1024)
+        );
+        log::warn!("{}", msg);
+        if let Err(emit_err) = emit_to_all(
+            &app,
+            "download-error",
+            serde_json::json!({
+                "model": model_name,
+                "engine": download_target.engine.as_str(),
+                "error": msg,
+                "code": "insufficient_space",
+                "required_bytes": space.required_bytes,
+                "available_bytes": space.available_bytes
+            }),
+        ) {
+            log::warn!("Failed to emit download-error event: {}", emit_err);
+        }
+        return Err("insufficient_space".to_string());
+    }
+
     // Monitor system resources at download start
     #[cfg(debug_assertions)]
     system_monitor::log_resources_before_operation("MODEL_DOWNLOAD");
@@ -149,6 +230,7 @@ pub async fn download_model(
                 let res = manager
                     .download_model(
                         &model_name,
+                        connections,
                         Some(cancel_flag.clone()),
                         move |downloaded, total| {
                             let _ = progress_tx_clone.send((downloaded, total));
@@ -298,6 +380,7 @@ pub async fn download_model(
             if let Err(e) = crate::commands::settings::update_tray_menu(app.clone()).await {
                 log::warn!("Failed to update tray menu after model download: {}", e);
             }
+            crate::emit_recognition_availability(&app).await;
 
             Ok(())
         }
@@ -428,6 +511,7 @@ pub async fn delete_model(
     if let Err(e) = crate::commands::settings::update_tray_menu(app.clone()).await {
         log::warn!("Failed to update tray menu after model deletion: {}", e);
     }
+    crate::emit_recognition_availability(&app).await;
 
     Ok(())
 }
@@ -651,6 +735,25 @@ pub async fn verify_model(
         ));
     }
 
+    // Size is plausible; the registry checksum catches truncated or tampered files
+    if matches!(model_info.sha256.len(), 40 | 64) {
+        if let Err(e) = WhisperManager::verify_model_checksum(&model_path, &model_info.sha256).await
+        {
+            log::warn!("Model '{}' failed checksum verification: {}", model_name, e);
+
+            // A mismatching file was deleted; refresh so it shows as not downloaded
+            {
+                let mut manager = state.write().await;
+                manager.refresh_downloaded_status();
+            }
+
+            return Err(format!(
+                "Model '{}' failed checksum verification and has been deleted. Please re-download.",
+                model_name
+            ));
+        }
+    }
+
     // File looks good - mark as downloaded
     {
         let mut manager = state.write().await;
@@ -707,3 +810,580 @@ pub async fn preload_model(
 
     Ok(())
 }
+
+/// A downloaded local model to run through [`benchmark_models`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BenchmarkCandidate {
+    pub name: String,
+    pub engine: ModelEngine,
+}
+
+/// Every downloaded Whisper and Parakeet model, Whisper smallest first, then
+/// Parakeet in catalog order
+pub(crate) fn benchmark_candidates(
+    whisper_models: &HashMap<String, ModelInfo>,
+    parakeet_models: &[ParakeetModelStatus],
+) -> Vec<BenchmarkCandidate> {
+    let mut whisper: Vec<&ModelInfo> = whisper_models
+        .values()
+        .filter(|info| info.downloaded)
+        .collect();
+    whisper.sort_by(|a, b| a.size.cmp(&b.size).then_with(|| a.name.cmp(&b.name)));
+
+    let whisper = whisper.into_iter().map(|info| BenchmarkCandidate {
+        name: info.name.clone(),
+        engine: ModelEngine::Whisper,
+    });
+    let parakeet = parakeet_models
+        .iter()
+        .filter(|status| status.downloaded)
+        .map(|status| BenchmarkCandidate {
+            name: status.name.clone(),
+            engine: ModelEngine::Parakeet,
+        });
+    whisper.chain(parakeet).collect()
+}
+
+/// Timings from one model's benchmark run
+#[derive(Debug, Clone)]
+pub(crate) struct BenchmarkRun {
+    pub load: std::time::Duration,
+    pub cold: std::time::Duration,
+    pub warm: std::time::Duration,
+    pub text: String,
+}
+
+/// Transcribe time over clip length; below 1.0 is faster than realtime
+pub(crate) fn realtime_factor(transcribe: std::time::Duration, sample_secs: f64) -> Option<f64> {
+    (sample_secs > 0.0).then(|| transcribe.as_secs_f64() / sample_secs)
+}
+
+/// One model's row in the benchmark results
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ModelBenchmark {
+    pub model: String,
+    pub engine: String,
+    /// Loading the model with nothing cached
+    pub load_ms: u64,
+    /// The first transcription right after loading
+    pub cold_transcribe_ms: u64,
+    /// A second transcription of the same clip with the model warm
+    pub warm_transcribe_ms: u64,
+    /// Warm transcribe time over clip length
+    pub realtime_factor: Option<f64>,
+    pub text: String,
+    /// Set when the model failed to load or transcribe; the timings are then zero
+    pub error: Option<String>,
+}
+
+impl ModelBenchmark {
+    pub(crate) fn from_run(
+        candidate: &BenchmarkCandidate,
+        run: Result<BenchmarkRun, String>,
+        sample_secs: f64,
+    ) -> Self {
+        let mut benchmark = Self {
+            model: candidate.name.clone(),
+            engine: candidate.engine.as_str().to_string(),
+            load_ms: 0,
+            cold_transcribe_ms: 0,
+            warm_transcribe_ms: 0,
+            realtime_factor: None,
+            text: String::new(),
+            error: None,
+        };
+        match run {
+            Ok(run) => {
+                benchmark.load_ms = run.load.as_millis() as u64;
+                benchmark.cold_transcribe_ms = run.cold.as_millis() as u64;
+                benchmark.warm_transcribe_ms = run.warm.as_millis() as u64;
+                benchmark.realtime_factor = realtime_factor(run.warm, sample_secs);
+                benchmark.text = run.text.trim().to_string();
+            }
+            Err(e) => benchmark.error = Some(e),
+        }
+        benchmark
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BenchmarkReport {
+    pub sample_duration_secs: f64,
+    /// One entry per benchmarked model, in the order they ran
+    pub results: Vec<ModelBenchmark>,
+    /// The model with the lowest warm transcribe time, among those that finished
+    pub fastest: Option<String>,
+}
+
+impl BenchmarkReport {
+    pub(crate) fn new(sample_duration_secs: f64, results: Vec<ModelBenchmark>) -> Self {
+        let fastest = results
+            .iter()
+            .filter(|result| result.error.is_none())
+            .min_by_key(|result| result.warm_transcribe_ms)
+            .map(|result| result.model.clone());
+        Self {
+            sample_duration_secs,
+            results,
+            fastest,
+        }
+    }
+}
+
+/// Length of a WAV file in seconds
+fn wav_duration_secs(path: &std::path::Path) -> Result<f64, String> {
+    let reader =
+        hound::WavReader::open(path).map_err(|e| format!("Failed to read sample: {}", e))?;
+    let spec = reader.spec();
+    Ok(reader.duration() as f64 / spec.sample_rate.max(1) as f64)
+}
+
+fn emit_benchmark_progress(
+    app: &AppHandle,
+    candidate: &BenchmarkCandidate,
+    index: usize,
+    total: usize,
+    result: Option<&ModelBenchmark>,
+) {
+    if let Err(e) = emit_to_all(
+        app,
+        "benchmark-progress",
+        serde_json::json!({
+            "model": candidate.name,
+            "engine": candidate.engine.as_str(),
+            "index": index,
+            "total": total,
+            "result": result,
+        }),
+    ) {
+        log::warn!("Failed to emit benchmark progress: {}", e);
+    }
+}
+
+/// Load a Whisper model from an empty cache, then transcribe the sample twice
+async fn benchmark_whisper(
+    app: &AppHandle,
+    state: &State<'_, RwLock<WhisperManager>>,
+    model_name: &str,
+    sample: &std::path::Path,
+    language: Option<String>,
+) -> Result<BenchmarkRun, String> {
+    use crate::whisper::cache::TranscriberCache;
+    use tauri::async_runtime::Mutex as AsyncMutex;
+
+    let model_path = state
+        .read()
+        .await
+        .get_model_path(model_name)
+        .ok_or(format!("Model '{}' not found", model_name))?;
+
+    let (transcriber, load) = {
+        let cache_state = app.state::<AsyncMutex<TranscriberCache>>();
+        let mut cache = cache_state.lock().await;
+        // Every model starts cold so load times compare fairly
+        cache.clear();
+        let started = Instant::now();
+        let transcriber = cache.get_or_create(&model_path)?;
+        (transcriber, started.elapsed())
+    };
+
+    let sample = sample.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let started = Instant::now();
+        transcriber.transcribe_with_translation(&sample, language.as_deref(), false)?;
+        let cold = started.elapsed();
+
+        let started = Instant::now();
+        let text = transcriber.transcribe_with_translation(&sample, language.as_deref(), false)?;
+        Ok(BenchmarkRun {
+            load,
+            cold,
+            warm: started.elapsed(),
+            text,
+        })
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// Load a Parakeet model after unloading the sidecar, then transcribe the sample twice
+async fn benchmark_parakeet(
+    app: &AppHandle,
+    model_name: &str,
+    sample: &std::path::Path,
+    language: Option<String>,
+) -> Result<BenchmarkRun, String> {
+    use crate::parakeet::messages::ParakeetResponse;
+
+    let parakeet_manager = app.state::<ParakeetManager>();
+    if let Err(e) = parakeet_manager.unload_model(app).await {
+        log::debug!("[Benchmark] Parakeet unload before load failed: {}", e);
+    }
+
+    let started = Instant::now();
+    parakeet_manager
+        .load_model(app, model_name)
+        .await
+        .map_err(|e| format!("Failed to load Parakeet model: {}", e))?;
+    let load = started.elapsed();
+
+    let mut timings = Vec::with_capacity(2);
+    let mut text = String::new();
+    for _ in 0..2 {
+        let started = Instant::now();
+        match parakeet_manager
+            .transcribe(
+                app,
+                model_name,
+                sample.to_path_buf(),
+                language.clone(),
+                false,
+            )
+            .await
+        {
+            Ok(ParakeetResponse::Transcription { text: output, .. }) => text = output,
+            Ok(other) => return Err(format!("Unexpected Parakeet response: {:?}", other)),
+            Err(err) => return Err(format!("Parakeet transcription failed: {}", err)),
+        }
+        timings.push(started.elapsed());
+    }
+
+    Ok(BenchmarkRun {
+        load,
+        cold: timings[0],
+        warm: timings[1],
+        text,
+    })
+}
+
+/// Run a short sample clip through every downloaded Whisper and Parakeet model
+/// so the user can compare speed and accuracy. Each model is loaded cold, then
+/// transcribes the clip twice (cold and warm). Emits `benchmark-progress`
+/// before and after each model.
+#[tauri::command]
+pub async fn benchmark_models(
+    app: AppHandle,
+    sample_path: String,
+    state: State<'_, RwLock<WhisperManager>>,
+) -> Result<BenchmarkReport, String> {
+    use crate::whisper::cache::TranscriberCache;
+    use crate::whisper::languages::resolve_engine_language;
+    use tauri::async_runtime::Mutex as AsyncMutex;
+
+    // Benchmarking empties the transcriber cache, which a recording relies on
+    if crate::get_recording_state(&app) != crate::RecordingState::Idle {
+        return Err("Can't benchmark models while recording".to_string());
+    }
+
+    let sample = std::path::PathBuf::from(&sample_path);
+    if !sample.is_file() {
+        return Err(format!("Sample file not found: {}", sample_path));
+    }
+
+    let candidates = {
+        let mut manager = state.write().await;
+        manager.refresh_downloaded_status();
+        let parakeet_models = app.state::<ParakeetManager>().list_models();
+        benchmark_candidates(manager.get_models_status(), &parakeet_models)
+    };
+    if candidates.is_empty() {
+        return Err("No downloaded models to benchmark".to_string());
+    }
+
+    // Normalize once so every model sees the same 16 kHz mono input
+    let normalized = std::env::temp_dir().join(format!(
+        "voicetypr_benchmark_{}.wav",
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ));
+    crate::ffmpeg::normalize_streaming(&app, &sample, &normalized).await?;
+    let sample_secs = match wav_duration_secs(&normalized) {
+        Ok(secs) => secs,
+        Err(e) => {
+            let _ = std::fs::remove_file(&normalized);
+            return Err(e);
+        }
+    };
+
+    let settings = crate::commands::settings::get_settings(app.clone()).await?;
+    let total = candidates.len();
+    let mut results = Vec::with_capacity(total);
+    for (index, candidate) in candidates.iter().enumerate() {
+        log::info!(
+            "[Benchmark] {}/{}: {} ({})",
+            index + 1,
+            total,
+            candidate.name,
+            candidate.engine.as_str()
+        );
+        emit_benchmark_progress(&app, candidate, index, total, None);
+
+        let run = match candidate.engine {
+            ModelEngine::Whisper => {
+                let language = resolve_engine_language(
+                    &settings.language,
+                    settings.whisper_language.as_deref(),
+                );
+                benchmark_whisper(&app, &state, &candidate.name, &normalized, language).await
+            }
+            ModelEngine::Parakeet => {
+                let language = resolve_engine_language(
+                    &settings.language,
+                    settings.parakeet_language.as_deref(),
+                );
+                benchmark_parakeet(&app, &candidate.name, &normalized, language).await
+            }
+        };
+        if let Err(e) = &run {
+            log::warn!("[Benchmark] {} failed: {}", candidate.name, e);
+        }
+
+        let result = ModelBenchmark::from_run(candidate, run, sample_secs);
+        emit_benchmark_progress(&app, candidate, index, total, Some(&result));
+        results.push(result);
+    }
+
+    let _ = std::fs::remove_file(&normalized);
+    // Don't leave the last benchmarked model holding memory; the next
+    // recording loads the model it needs
+    app.state::<AsyncMutex<TranscriberCache>>()
+        .lock()
+        .await
+        .clear();
+
+    Ok(BenchmarkReport::new(sample_secs, results))
+}
+
+/// Import a whisper.cpp ggml model from disk (e.g. a fine-tune) as a selectable
+/// model. The file is copied into the models directory, so the original can go.
+#[tauri::command]
+pub async fn import_whisper_model(
+    app: AppHandle,
+    path: String,
+    display_name: String,
+    state: State<'_, RwLock<WhisperManager>>,
+) -> Result<String, String> {
+    use crate::whisper::custom_models::{custom_model_name, read_ggml_header, CustomModelEntry};
+    use crate::whisper::manager::ModelSize;
+
+    let source = std::path::PathBuf::from(path.trim());
+    if !source.is_file() {
+        return Err(format!("Model file not found: {}", path));
+    }
+    let size = std::fs::metadata(&source)
+        .map_err(|e| format!("Cannot read model file: {}", e))?
+        .len();
+    ModelSize::new(size)?;
+    let header = {
+        let source = source.clone();
+        tokio::task::spawn_blocking(move || read_ggml_header(&source))
+            .await
+            .map_err(|e| format!("Failed to read model file: {}", e))??
+    };
+
+    let entry = CustomModelEntry {
+        name: custom_model_name(&display_name, header.is_multilingual())?,
+        display_name: display_name.trim().to_string(),
+        size,
+        multilingual: header.is_multilingual(),
+    };
+    let target = {
+        let manager = state.read().await;
+        if manager.get_models_status().contains_key(&entry.name) {
+            return Err(format!("A model named '{}' already exists", entry.name));
+        }
+        manager.models_dir().join(format!("{}.bin", entry.name))
+    };
+    log::info!(
+        "Importing {:?} as '{}' (n_vocab {}, ftype {})",
+        source,
+        entry.name,
+        header.n_vocab,
+        header.ftype
+    );
+
+    // Copy under a temporary name so a half-copied file is never picked up
+    let partial = target.with_extension("bin.partial");
+    let copy = {
+        let (source, partial, target) = (source.clone(), partial.clone(), target.clone());
+        tokio::task::spawn_blocking(move || {
+            std::fs::copy(&source, &partial)?;
+            std::fs::rename(&partial, &target)
+        })
+        .await
+        .map_err(|e| format!("Failed to copy model: {}", e))?
+    };
+    if let Err(e) = copy {
+        let _ = std::fs::remove_file(&partial);
+        return Err(format!(
+            "Failed to copy model into the models directory: {}",
+            e
+        ));
+    }
+
+    if let Err(e) = state.write().await.add_custom_model(entry.clone()) {
+        let _ = std::fs::remove_file(&target);
+        return Err(e);
+    }
+
+    if let Err(e) = emit_to_all(
+        &app,
+        "model-downloaded",
+        serde_json::json!({ "model": entry.name, "engine": "whisper" }),
+    ) {
+        log::warn!("Failed to emit model-downloaded event: {}", e);
+    }
+    if let Err(e) = crate::commands::settings::update_tray_menu(app.clone()).await {
+        log::warn!("Failed to update tray menu after model import: {}", e);
+    }
+    crate::emit_recognition_availability(&app).await;
+
+    Ok(entry.name)
+}
+
+/// Which downloaded model best fits `language`, against the current model
+pub async fn model_recommendation_for(
+    app: &AppHandle,
+    language: &str,
+    current_model: &str,
+) -> ModelRecommendation {
+    let candidates = {
+        let whisper_state = app.state::<RwLock<WhisperManager>>();
+        let mut manager = whisper_state.write().await;
+        manager.refresh_downloaded_status();
+        let parakeet_models = app.state::<ParakeetManager>().list_models();
+        model_candidates(&manager.get_models_status(), &parakeet_models)
+    };
+    best_model_for_language(language, current_model, &candidates)
+}
+
+/// Suggest the best downloaded model for `language` (the saved language when
+/// omitted), warning when the current model can't handle it
+#[tauri::command]
+pub async fn recommend_model(
+    app: AppHandle,
+    language: Option<String>,
+) -> Result<ModelRecommendation, String> {
+    let settings = crate::commands::settings::get_settings(app.clone()).await?;
+    let language = language.unwrap_or(settings.language);
+    if language != "auto" && !crate::whisper::languages::is_language_supported(&language) {
+        return Err(format!("Unsupported language: {}", language));
+    }
+    Ok(model_recommendation_for(&app, &language, &settings.current_model).await)
+}
+
+/// Disk usage of the models directory, for the model management screen
+#[derive(Debug, serde::Serialize)]
+pub struct ModelsDirectoryUsage {
+    pub path: String,
+    /// Total size of everything under the models directory (whisper and parakeet)
+    pub models_bytes: u64,
+    /// Free space on the disk holding it, if it could be determined
+    pub available_bytes: Option<u64>,
+}
+
+/// Total size of the files under `dir`, recursing into subdirectories
+fn directory_size(dir: &std::path::Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => directory_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// GPU backends available to Whisper in this build
+#[tauri::command]
+pub async fn get_acceleration_capabilities() -> Result<AccelerationCapabilities, String> {
+    Ok(AccelerationCapabilities::current())
+}
+
+#[tauri::command]
+pub async fn get_models_directory_usage(
+    whisper_state: State<'_, RwLock<WhisperManager>>,
+) -> Result<ModelsDirectoryUsage, String> {
+    let models_dir = whisper_state.read().await.models_dir().to_path_buf();
+
+    tokio::task::spawn_blocking(move || ModelsDirectoryUsage {
+        path: models_dir.to_string_lossy().to_string(),
+        models_bytes: directory_size(&models_dir),
+        available_bytes: system_monitor::available_space_at(&models_dir),
+    })
+    .await
+    .map_err(|e| format!("Failed to measure models directory: {}", e))
+}
+
+/// Move all downloaded models to `path` and use it as the models directory from now on
+#[tauri::command]
+pub async fn set_models_directory(
+    app: AppHandle,
+    path: String,
+    whisper_state: State<'_, RwLock<WhisperManager>>,
+    parakeet_manager: State<'_, ParakeetManager>,
+    active_downloads: State<'_, Arc<StdMutex<HashMap<String, Arc<AtomicBool>>>>>,
+) -> Result<String, String> {
+    use crate::utils::model_relocation;
+    use crate::whisper::cache::TranscriberCache;
+    use tauri::async_runtime::Mutex as AsyncMutex;
+
+    if app.state::<crate::AppState>().get_current_state() != crate::RecordingState::Idle {
+        return Err("Can't move models while recording or transcribing".to_string());
+    }
+    let downloading = active_downloads
+        .lock()
+        .map(|downloads| !downloads.is_empty())
+        .map_err(|_| "Failed to access download tracking".to_string())?;
+    if downloading {
+        return Err("Can't move models while a download is in progress".to_string());
+    }
+
+    // Held for the whole move so no model is looked up at its old path meanwhile
+    let mut whisper_manager = whisper_state.write().await;
+    let current_dir = whisper_manager.models_dir().to_path_buf();
+    let target_dir = std::path::PathBuf::from(path.trim());
+
+    let moved = {
+        let (from, to) = (current_dir.clone(), target_dir.clone());
+        tokio::task::spawn_blocking(move || {
+            model_relocation::validate_models_directory(&from, &to)?;
+            model_relocation::move_models_directory(&from, &to)
+        })
+        .await
+        .map_err(|e| format!("Failed to move models: {}", e))??
+    };
+    log::info!(
+        "Moved {} entries from {:?} to {:?}",
+        moved,
+        current_dir,
+        target_dir
+    );
+
+    let parakeet_dir = target_dir.join("parakeet");
+    if let Err(e) = std::fs::create_dir_all(&parakeet_dir) {
+        log::warn!("Failed to create {:?}: {}", parakeet_dir, e);
+    }
+    whisper_manager.set_models_dir(target_dir.clone());
+    parakeet_manager.set_root_dir(parakeet_dir);
+    drop(whisper_manager);
+
+    // Cached transcribers are keyed by their old paths
+    app.state::<AsyncMutex<TranscriberCache>>()
+        .lock()
+        .await
+        .clear();
+
+    let store = app.store("settings").map_err(|e| e.to_string())?;
+    store.set(
+        model_relocation::MODELS_DIRECTORY_KEY,
+        serde_json::Value::String(target_dir.to_string_lossy().to_string()),
+    );
+    store.save().map_err(|e| e.to_string())?;
+
+    let _ = app.emit("models-directory-changed", target_dir.to_string_lossy());
+    Ok(target_dir.to_string_lossy().to_string())
+}
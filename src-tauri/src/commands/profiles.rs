@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::commands::settings::{apply_settings_values, update_tray_menu};
+
+/// Store holding one entry per profile, keyed by name
+pub const PROFILES_STORE: &str = "profiles";
+
+const MAX_PROFILE_NAME_LENGTH: usize = 50;
+
+/// Settings store keys captured by a profile. Window state, onboarding, history
+/// retention and hotkey lists stay out so switching profiles only changes how
+/// the next recording is made.
+pub const PROFILE_SETTINGS_KEYS: &[&str] = &[
+    "hotkey",
+    "current_model",
+    "current_model_engine",
+    "language",
+    "translate_to_english",
+    "selected_microphone",
+    "recording_mode",
+    "use_different_ptt_key",
+    "ptt_hotkey",
+    "keep_transcription_in_clipboard",
+    "play_sound_on_recording",
+    "insertion_method",
+    "output_mode",
+    "normalize_numbers",
+    "voice_commands_enabled",
+    "soniox_streaming",
+    "ai_enabled",
+    "ai_provider",
+    "ai_model",
+    "active_enhancement_preset",
+];
+
+/// A named snapshot of the profile keys
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingsProfile {
+    pub name: String,
+    pub saved_at: String,
+    pub settings: Map<String, Value>,
+}
+
+/// Trim a profile name and check it is usable as a store key and tray label
+pub fn normalize_profile_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_PROFILE_NAME_LENGTH {
+        return Err(format!(
+            "Profile name must be at most {} characters",
+            MAX_PROFILE_NAME_LENGTH
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Capture the profile keys from the settings store. Keys that were never saved
+/// are left out, so applying the profile leaves them alone.
+pub fn snapshot_profile_settings(read: impl Fn(&str) -> Option<Value>) -> Map<String, Value> {
+    PROFILE_SETTINGS_KEYS
+        .iter()
+        .filter_map(|key| read(key).map(|value| (key.to_string(), value)))
+        .collect()
+}
+
+/// All saved profiles, sorted by name
+pub fn read_profiles<R: tauri::Runtime>(app: &AppHandle<R>) -> Vec<SettingsProfile> {
+    let Ok(store) = app.store(PROFILES_STORE) else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<SettingsProfile> = store
+        .keys()
+        .into_iter()
+        .filter_map(|key| {
+            let value = store.get(&key)?;
+            serde_json::from_value(value)
+                .inspect_err(|e| log::warn!("Skipping malformed profile '{}': {}", key, e))
+                .ok()
+        })
+        .collect();
+    profiles.sort_by_key(|profile| profile.name.to_lowercase());
+    profiles
+}
+
+/// Current values of the profile keys in the settings store
+pub fn current_profile_settings<R: tauri::Runtime>(app: &AppHandle<R>) -> Map<String, Value> {
+    match app.store("settings") {
+        Ok(store) => snapshot_profile_settings(|key| store.get(key)),
+        Err(_) => Map::new(),
+    }
+}
+
+#[tauri::command]
+pub async fn list_profiles(app: AppHandle) -> Result<Vec<SettingsProfile>, String> {
+    Ok(read_profiles(&app))
+}
+
+/// Save the current settings as `name`, replacing a profile with the same name
+#[tauri::command]
+pub async fn save_profile(app: AppHandle, name: String) -> Result<SettingsProfile, String> {
+    let name = normalize_profile_name(&name)?;
+    let profile = SettingsProfile {
+        name: name.clone(),
+        saved_at: chrono::Utc::now().to_rfc3339(),
+        settings: current_profile_settings(&app),
+    };
+
+    let store = app.store(PROFILES_STORE).map_err(|e| e.to_string())?;
+    store.set(&name, json!(profile));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save profile: {}", e))?;
+    log::info!("Saved settings profile '{}'", name);
+
+    if let Err(e) = update_tray_menu(app.clone()).await {
+        log::warn!("Failed to update tray menu after saving profile: {}", e);
+    }
+    Ok(profile)
+}
+
+/// Switch to the profile `name`. Returns the settings keys that changed.
+#[tauri::command]
+pub async fn apply_profile(app: AppHandle, name: String) -> Result<Vec<String>, String> {
+    if app.state::<crate::AppState>().get_current_state() != crate::RecordingState::Idle {
+        return Err("Can't switch profiles while recording or transcribing".to_string());
+    }
+
+    let name = name.trim();
+    let profile = read_profiles(&app)
+        .into_iter()
+        .find(|profile| profile.name == name)
+        .ok_or_else(|| format!("Profile '{}' not found", name))?;
+
+    let changed = apply_settings_values(&app, &profile.settings).await?;
+    if changed.is_empty() {
+        log::info!("Profile '{}' already matches the current settings", name);
+    } else {
+        log::info!("Applied profile '{}', changing {:?}", name, changed);
+    }
+    Ok(changed)
+}
+
+#[tauri::command]
+pub async fn delete_profile(app: AppHandle, name: String) -> Result<(), String> {
+    let name = name.trim();
+    let store = app.store(PROFILES_STORE).map_err(|e| e.to_string())?;
+    if !store.delete(name) {
+        return Err(format!("Profile '{}' not found", name));
+    }
+    store
+        .save()
+        .map_err(|e| format!("Failed to save profiles: {}", e))?;
+    log::info!("Deleted settings profile '{}'", name);
+
+    if let Err(e) = update_tray_menu(app.clone()).await {
+        log::warn!("Failed to update tray menu after deleting profile: {}", e);
+    }
+    Ok(())
+}
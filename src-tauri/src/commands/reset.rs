@@ -31,17 +31,10 @@ pub async fn reset_app_data(app: AppHandle) -> Result<ResetResult, String> {
         }
     }
 
-    // Clear transcriptions store
-    if let Ok(store) = app.store("transcriptions") {
-        store.clear();
-        if let Err(e) = store.save() {
-            errors.push(format!(
-                "Failed to save cleared transcriptions store: {}",
-                e
-            ));
-        } else {
-            cleared_items.push("Transcriptions store".to_string());
-        }
+    // Clear the monthly transcriptions stores
+    match crate::utils::history_store::clear_raw_entries(&app) {
+        Ok(_) => cleared_items.push("Transcriptions store".to_string()),
+        Err(e) => errors.push(format!("Failed to clear transcriptions: {}", e)),
     }
 
     // Delete the actual store files from disk
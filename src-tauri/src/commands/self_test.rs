@@ -0,0 +1,267 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::AppHandle;
+
+use crate::recognition::{recognition_availability_snapshot, RecognitionAvailabilitySnapshot};
+
+/// What the synthesized clip says; the transcript is compared against it
+pub const SELF_TEST_PHRASE: &str = "The quick brown fox jumps over the lazy dog";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestStatus {
+    Passed,
+    Failed,
+    /// Nothing to test with, e.g. no model downloaded yet
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestStep {
+    /// "synthesize", "normalize" or "transcribe"
+    pub name: String,
+    pub ok: bool,
+    pub duration_ms: u64,
+    pub detail: Option<String>,
+}
+
+/// Result of `self_test`, shown in onboarding and attached to support requests
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    pub status: SelfTestStatus,
+    pub passed: bool,
+    pub message: String,
+    pub model: Option<String>,
+    pub engine: Option<String>,
+    pub steps: Vec<SelfTestStep>,
+    pub transcript: Option<String>,
+    /// Share of the phrase's words found in the transcript, 0.0 to 1.0
+    pub word_match: Option<f32>,
+    pub total_ms: u64,
+}
+
+impl SelfTestReport {
+    fn new(status: SelfTestStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            passed: status == SelfTestStatus::Passed,
+            message: message.into(),
+            model: None,
+            engine: None,
+            steps: Vec::new(),
+            transcript: None,
+            word_match: None,
+            total_ms: 0,
+        }
+    }
+
+    /// A skipped report when no engine can transcribe, otherwise `None`
+    pub fn skip_unless_ready(
+        availability: &RecognitionAvailabilitySnapshot,
+        current_model: &str,
+    ) -> Option<Self> {
+        if !availability.any_available() {
+            return Some(Self::new(
+                SelfTestStatus::Skipped,
+                "No speech recognition engine is ready. Download a model first.",
+            ));
+        }
+        if current_model.trim().is_empty() {
+            return Some(Self::new(
+                SelfTestStatus::Skipped,
+                "No model is selected. Pick a model first.",
+            ));
+        }
+        None
+    }
+
+    /// Passed when the pipeline produced any text at all; how much of the phrase
+    /// came back is reported but doesn't fail the test
+    fn finish(steps: Vec<SelfTestStep>, transcript: Option<String>) -> Self {
+        let failed_step = steps.iter().find(|step| !step.ok);
+        let mut report = match (failed_step, transcript.as_deref().map(str::trim)) {
+            (Some(step), _) => Self::new(
+                SelfTestStatus::Failed,
+                format!(
+                    "The {} step failed: {}",
+                    step.name,
+                    step.detail.as_deref().unwrap_or("unknown error")
+                ),
+            ),
+            (None, Some(text)) if !text.is_empty() => {
+                Self::new(SelfTestStatus::Passed, "Transcription pipeline works")
+            }
+            (None, _) => Self::new(
+                SelfTestStatus::Failed,
+                "The engine returned no text for the test clip",
+            ),
+        };
+        report.word_match = transcript.as_deref().map(phrase_word_match);
+        report.transcript = transcript;
+        report.total_ms = steps.iter().map(|step| step.duration_ms).sum();
+        report.steps = steps;
+        report
+    }
+}
+
+/// Share of the words of [`SELF_TEST_PHRASE`] that appear in `transcript`
+pub fn phrase_word_match(transcript: &str) -> f32 {
+    let normalize = |word: &str| {
+        word.trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase()
+    };
+    let heard: Vec<String> = transcript.split_whitespace().map(normalize).collect();
+    let expected: Vec<String> = SELF_TEST_PHRASE.split_whitespace().map(normalize).collect();
+    let found = expected.iter().filter(|word| heard.contains(word)).count();
+    found as f32 / expected.len() as f32
+}
+
+/// Run one step, recording whether it worked and how long it took
+async fn run_step<T>(
+    steps: &mut Vec<SelfTestStep>,
+    name: &str,
+    step: impl std::future::Future<Output = Result<T, String>>,
+) -> Option<T> {
+    let started = Instant::now();
+    let result = step.await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    log::info!(
+        "[SELF_TEST] {} {} in {}ms",
+        name,
+        if result.is_ok() { "ok" } else { "failed" },
+        duration_ms
+    );
+    let (ok, detail, value) = match result {
+        Ok(value) => (true, None, Some(value)),
+        Err(e) => (false, Some(e), None),
+    };
+    steps.push(SelfTestStep {
+        name: name.to_string(),
+        ok,
+        duration_ms,
+        detail,
+    });
+    value
+}
+
+/// Run a short synthesized speech clip through normalization and the current
+/// engine, the same way an uploaded file is transcribed
+#[tauri::command]
+pub async fn self_test(app: AppHandle) -> Result<SelfTestReport, String> {
+    let settings = crate::commands::settings::get_settings(app.clone()).await?;
+    let availability = recognition_availability_snapshot(&app).await;
+    if let Some(skipped) = SelfTestReport::skip_unless_ready(&availability, &settings.current_model)
+    {
+        log::info!("[SELF_TEST] Skipped: {}", skipped.message);
+        return Ok(skipped);
+    }
+
+    let work_dir = std::env::temp_dir().join(format!("voicetypr_self_test_{}", std::process::id()));
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create self-test directory: {}", e))?;
+
+    let mut steps = Vec::new();
+    let mut transcript = None;
+    let synthesized = run_step(&mut steps, "synthesize", async {
+        let dir = work_dir.clone();
+        tokio::task::spawn_blocking(move || synthesize_phrase(&dir))
+            .await
+            .map_err(|e| format!("Task failed: {}", e))?
+    })
+    .await;
+
+    if let Some(clip) = synthesized {
+        let normalized = work_dir.join("normalized.wav");
+        let normalized_ok = run_step(&mut steps, "normalize", async {
+            crate::ffmpeg::normalize_streaming(&app, &clip, &normalized).await
+        })
+        .await;
+
+        if normalized_ok.is_some() {
+            transcript = run_step(
+                &mut steps,
+                "transcribe",
+                crate::commands::audio::transcribe_audio_file(
+                    app.clone(),
+                    normalized.to_string_lossy().into_owned(),
+                    settings.current_model.clone(),
+                    Some(settings.current_model_engine.clone()),
+                ),
+            )
+            .await;
+        }
+    }
+    let _ = std::fs::remove_dir_all(&work_dir);
+
+    let mut report = SelfTestReport::finish(steps, transcript);
+    report.model = Some(settings.current_model);
+    report.engine = Some(settings.current_model_engine);
+    log::info!("[SELF_TEST] {:?}: {}", report.status, report.message);
+    Ok(report)
+}
+
+/// Speak [`SELF_TEST_PHRASE`] into an audio file with the OS text-to-speech
+fn synthesize_phrase(dir: &Path) -> Result<PathBuf, String> {
+    let (program, args, clip) = tts_command(dir)?;
+    let mut command = std::process::Command::new(program);
+    command.args(&args);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("Text-to-speech is not available ({}): {}", program, e))?;
+    if !output.status.success() || !clip.is_file() {
+        return Err(format!(
+            "Text-to-speech failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(clip)
+}
+
+#[cfg(target_os = "macos")]
+fn tts_command(dir: &Path) -> Result<(&'static str, Vec<String>, PathBuf), String> {
+    let clip = dir.join("phrase.aiff");
+    let args = vec![
+        "-o".to_string(),
+        clip.to_string_lossy().into_owned(),
+        SELF_TEST_PHRASE.to_string(),
+    ];
+    Ok(("say", args, clip))
+}
+
+#[cfg(target_os = "windows")]
+fn tts_command(dir: &Path) -> Result<(&'static str, Vec<String>, PathBuf), String> {
+    let clip = dir.join("phrase.wav");
+    let script = format!(
+        "Add-Type -AssemblyName System.Speech; \
+         $s = New-Object System.Speech.Synthesis.SpeechSynthesizer; \
+         $s.SetOutputToWaveFile('{}'); $s.Speak('{}'); $s.Dispose()",
+        clip.to_string_lossy().replace('\'', "''"),
+        SELF_TEST_PHRASE
+    );
+    let args = vec!["-NoProfile".to_string(), "-Command".to_string(), script];
+    Ok(("powershell", args, clip))
+}
+
+#[cfg(target_os = "linux")]
+fn tts_command(dir: &Path) -> Result<(&'static str, Vec<String>, PathBuf), String> {
+    let clip = dir.join("phrase.wav");
+    let args = vec![
+        "-w".to_string(),
+        clip.to_string_lossy().into_owned(),
+        SELF_TEST_PHRASE.to_string(),
+    ];
+    Ok(("espeak-ng", args, clip))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn tts_command(_dir: &Path) -> Result<(&'static str, Vec<String>, PathBuf), String> {
+    Err("Text-to-speech is not supported on this platform".to_string())
+}
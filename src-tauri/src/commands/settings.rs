@@ -1,15 +1,92 @@
 use crate::audio::device_watcher::try_start_device_watcher_if_ready;
 use crate::commands::key_normalizer::{normalize_shortcut_keys, validate_key_combination};
+use crate::commands::text::{AppInsertionRule, InsertionMethod, OutputMode};
 use crate::parakeet::ParakeetManager;
-use crate::whisper::languages::{validate_language, SUPPORTED_LANGUAGES};
-use crate::whisper::manager::WhisperManager;
+use crate::utils::auto_cleanup::AutoCleanupConfig;
+use crate::utils::pill_theme::PillTheme;
+use crate::utils::redaction::{validate_redaction_patterns, DEFAULT_REDACTION_PATTERNS};
+use crate::utils::text_replace::ReplacementRule;
+use crate::utils::toast_layout::{ToastPosition, MAX_DURATION_SCALE, MIN_DURATION_SCALE};
+use crate::whisper::cache::{TranscriberCache, MAX_MODEL_CACHE_CAPACITY};
+use crate::whisper::chunking::DEFAULT_CHUNK_THRESHOLD_SECS;
+use crate::whisper::languages::{
+    validate_language, validate_language_override, SUPPORTED_LANGUAGES,
+};
+use crate::whisper::manager::{WhisperManager, MAX_DOWNLOAD_CONNECTIONS};
+use crate::whisper::pool::{DEFAULT_CONCURRENT_UPLOADS, MAX_CONCURRENT_UPLOADS};
 use crate::AppState;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
 use tauri_plugin_store::StoreExt;
 
+/// A global hotkey that switches the current model, from the `model_hotkeys` setting
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelHotkey {
+    pub hotkey: String,
+    pub model_name: String,
+    /// "whisper", "parakeet" or "soniox"; empty means look the model up
+    pub engine: String,
+    /// Start recording right after switching
+    pub start_recording: bool,
+}
+
+/// Upper bound for `double_tap_window_ms`; longer gaps are no longer a double-tap
+const MAX_DOUBLE_TAP_WINDOW_MS: u32 = 1000;
+
+/// Upper bound for `hotkey_debounce_ms`, so a typo can't swallow every press
+const MAX_HOTKEY_DEBOUNCE_MS: u32 = 1000;
+
+/// Upper bound for `min_recording_seconds`, so a typo can't discard every recording
+const MAX_MIN_RECORDING_SECONDS: f32 = 10.0;
+
+/// Upper bounds for the transcription retry settings
+const MAX_TRANSCRIPTION_ATTEMPTS: u32 = 10;
+const MAX_TRANSCRIPTION_RETRY_DELAY_MS: u64 = 10_000;
+
+const MODEL_ENGINES: [&str; 3] = ["whisper", "parakeet", "soniox"];
+
+/// Parse the stored `model_hotkeys` list entry by entry so one malformed entry doesn't
+/// drop the rest. Entries without a hotkey or model are skipped, unknown engines are
+/// cleared, and only the first entry for a given hotkey is kept.
+pub fn parse_model_hotkeys(value: &serde_json::Value) -> Vec<ModelHotkey> {
+    let Some(entries) = value.as_array() else {
+        return Vec::new();
+    };
+
+    let mut hotkeys: Vec<ModelHotkey> = Vec::new();
+    for entry in entries {
+        let Ok(mut hotkey) = serde_json::from_value::<ModelHotkey>(entry.clone()) else {
+            log::warn!("Skipping malformed model hotkey entry: {}", entry);
+            continue;
+        };
+
+        hotkey.hotkey = hotkey.hotkey.trim().to_string();
+        hotkey.model_name = hotkey.model_name.trim().to_string();
+        hotkey.engine = hotkey.engine.trim().to_lowercase();
+        if hotkey.hotkey.is_empty() || hotkey.model_name.is_empty() {
+            continue;
+        }
+        if !MODEL_ENGINES.contains(&hotkey.engine.as_str()) {
+            hotkey.engine.clear();
+        }
+
+        let normalized = normalize_shortcut_keys(&hotkey.hotkey);
+        if hotkeys
+            .iter()
+            .any(|existing| normalize_shortcut_keys(&existing.hotkey) == normalized)
+        {
+            log::warn!("Duplicate model hotkey '{}' ignored", hotkey.hotkey);
+            continue;
+        }
+        hotkeys.push(hotkey);
+    }
+    hotkeys
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Settings {
     pub hotkey: String,
@@ -33,6 +110,128 @@ pub struct Settings {
     pub play_sound_on_recording: bool,
     // Pill indicator visibility when idle
     pub show_pill_indicator: bool,
+    // Battery-aware model switching
+    pub battery_aware_model: bool,
+    pub battery_threshold_percent: u8,
+    // Debug: keep the pre-normalization capture next to the normalized audio
+    pub keep_raw_audio: bool,
+    // Optional global hotkey that advances to the next enhancement preset
+    pub preset_cycle_hotkey: Option<String>,
+    // Transcribe the raw capture when ffmpeg normalization fails
+    pub normalize_fallback_to_raw: bool,
+    // Type AI enhancement output as it streams in instead of after it completes
+    pub ai_stream_insert: bool,
+    // Optional global hotkey that re-inserts the last transcription without AI enhancement
+    pub raw_insert_hotkey: Option<String>,
+    // How transcriptions are inserted: "paste", "type" or "paste_preserve_clipboard"
+    pub insertion_method: String,
+    // Per-application formatting applied to inserted text, matched against the frontmost app
+    pub app_insertion_rules: Vec<AppInsertionRule>,
+    // Find-and-replace dictionary applied to every transcription before insertion
+    pub replacement_rules: Vec<ReplacementRule>,
+    // Turn spoken punctuation ("comma", "new line") into characters
+    pub voice_commands_enabled: bool,
+    // Extra spoken commands; an empty value disables a built-in phrase
+    pub voice_command_map: HashMap<String, String>,
+    // Convert spoken English numbers to digits ("twenty four" -> "24")
+    pub normalize_numbers: bool,
+    // Recent transcriptions listed in the tray (0 hides the submenu)
+    pub tray_recent_count: u8,
+    // Characters shown per recent transcription in the tray
+    pub tray_recent_label_length: u32,
+    // Global hotkeys that switch the current model
+    pub model_hotkeys: Vec<ModelHotkey>,
+    // Max gap between push-to-talk taps that latches recording on (0 disables)
+    pub double_tap_window_ms: u32,
+    // Color, opacity and shape of the pill and toast windows
+    pub pill_theme: PillTheme,
+    // Where transcriptions go: "insert" at the cursor, "clipboard_only" or "both"
+    pub output_mode: String,
+    // Models kept loaded in memory (1-3); each whisper model holds 1-3GB of RAM
+    pub model_cache_capacity: u8,
+    // Parallel connections per model download (1-8); some servers throttle or reject many
+    pub download_connections: u8,
+    // Whisper GPU acceleration (Metal/Vulkan); falls back to CPU if it can't initialize
+    pub use_gpu: bool,
+    // Stream audio to Soniox's realtime API while recording instead of uploading afterwards
+    pub soniox_streaming: bool,
+    // Warn when Whisper's average token confidence is below this (0-1, 0 disables)
+    pub low_confidence_threshold: f32,
+    // Toast when Whisper is confident the speech wasn't in the selected language
+    pub warn_language_mismatch: bool,
+    // Daily pruning of old history and recordings
+    pub auto_cleanup: AutoCleanupConfig,
+    // Encrypt history entry text at rest with the secure store key
+    pub encrypt_history: bool,
+    // Regexes whose matches are replaced with [REDACTED] in history (inserted text is untouched)
+    pub redaction_patterns: Vec<String>,
+    // How stereo input becomes mono: "mix", "left" or "right"
+    pub stereo_downmix: String,
+    // EBU R128 loudness normalization before transcription: "off", "fast" (one pass) or "accurate" (two pass)
+    pub loudness_normalize: String,
+    // Integrated loudness target in LUFS
+    pub loudness_target_lufs: f32,
+    // Also normalize loudness of audio sent to Soniox, which is otherwise uploaded as recorded
+    pub loudness_normalize_cloud: bool,
+    // Recordings shorter than this are discarded; 0 keeps every recording
+    pub min_recording_seconds: f32,
+    // Trim leading and trailing silence from recordings kept in history
+    pub trim_silence_on_save: bool,
+    // Transcription attempts before giving up, including the first
+    pub transcription_max_retries: u32,
+    // Delay before the first retry; doubles on each further attempt
+    pub transcription_retry_delay_ms: u64,
+    // Retry Soniox transcriptions that fail with a network error or HTTP 5xx
+    pub retry_cloud_transcription: bool,
+    // Per-engine language overrides; None follows `language`, "auto" sends no hint
+    pub whisper_language: Option<String>,
+    pub parakeet_language: Option<String>,
+    pub soniox_language: Option<String>,
+    // Play a sound when recording stops; independent of play_sound_on_recording
+    pub play_sound_on_recording_end: bool,
+    // Custom WAV files for the start/end sounds; None uses the system sound
+    pub start_sound_path: Option<String>,
+    pub end_sound_path: Option<String>,
+    // Skip recording sounds while a meeting app is frontmost
+    pub mute_sounds_in_calls: bool,
+    // File and byte uploads transcribed at the same time (1-4)
+    pub max_concurrent_uploads: u8,
+    // Offer the text decoded before a cancel via cancelled-with-partial
+    pub keep_partial_on_cancel: bool,
+    // Switch to the recording mode last used with the active microphone
+    pub remember_mode_per_device: bool,
+    // Microphone name -> recording mode last used with it
+    pub device_recording_modes: HashMap<String, String>,
+    // Whisper transcribes recordings longer than this in overlapping 30s chunks (0 = never)
+    pub chunk_long_recordings_after_secs: u32,
+    // Models never picked automatically when the selected one isn't available
+    pub fallback_blocklist: Vec<String>,
+    // Modifier held while stopping a recording to skip AI enhancement once, or "none"
+    pub skip_enhancement_modifier: String,
+    // Send each dictation to the window it was started in, refocusing it if needed
+    pub lock_target_window: bool,
+    // Show a word count and duration toast after each transcription
+    pub show_result_summary: bool,
+    // Multiplier for how long pill toasts stay up
+    pub toast_duration_scale: f32,
+    // Where toasts appear relative to the pill: above, below, left or right
+    pub toast_position: String,
+    // Collapse a phrase Whisper hallucinates over and over into one copy
+    pub deduplicate_repeats: bool,
+    // What happens when no speech is detected: "toast", "silent" or "keep_recording",
+    // which keeps the audio and a placeholder history entry to re-run
+    pub on_empty_transcription: String,
+    // Show the elapsed recording time in the tray tooltip
+    pub tray_recording_timer: bool,
+    // Ignore recording hotkey presses this soon after the previous one (0 disables)
+    pub hotkey_debounce_ms: u32,
+    // Loopback HTTP API for scripts (local_api)
+    pub local_api_enabled: bool,
+    pub local_api_port: u16,
+    // Optional global hotkey that copies the newest history entry to the clipboard
+    pub copy_last_hotkey: Option<String>,
+    // Screenshot the screen when a recording starts and attach it to the history entry
+    pub attach_screenshot: bool,
 }
 
 impl Default for Settings {
@@ -56,6 +255,69 @@ impl Default for Settings {
             keep_transcription_in_clipboard: false, // Default to restoring clipboard after paste
             play_sound_on_recording: true,        // Default to playing sound on recording start
             show_pill_indicator: true,            // Default to showing pill indicator when idle
+            battery_aware_model: false, // Default to keeping the selected model on battery
+            battery_threshold_percent: 20, // Switch below 20% battery
+            keep_raw_audio: false,      // Default to deleting audio after transcription
+            preset_cycle_hotkey: None,  // No preset cycle hotkey by default
+            normalize_fallback_to_raw: true, // Transcribe the raw capture if normalization fails
+            ai_stream_insert: false,    // Default to inserting enhanced text once complete
+            raw_insert_hotkey: None,    // No raw re-insert hotkey by default
+            insertion_method: "paste_preserve_clipboard".to_string(), // Paste, then restore the previous clipboard
+            app_insertion_rules: Vec::new(),                          // No per-app rules by default
+            replacement_rules: Vec::new(),                            // No replacements by default
+            voice_commands_enabled: false, // Default to inserting spoken words as-is
+            voice_command_map: HashMap::new(), // No custom voice commands by default
+            normalize_numbers: false,      // Default to keeping numbers as transcribed
+            tray_recent_count: 5,          // Matches the previous fixed tray list
+            tray_recent_label_length: 40,
+            model_hotkeys: Vec::new(),        // No model hotkeys by default
+            double_tap_window_ms: 0,          // Double-tap latching off by default
+            pill_theme: PillTheme::default(), // Black pill, as before
+            output_mode: "insert".to_string(), // Insert at the cursor
+            model_cache_capacity: 1,          // Only the current model
+            download_connections: 1,          // Single connection for safety
+            use_gpu: true,                    // Use the GPU where this build has a backend
+            soniox_streaming: false,          // Upload after recording
+            low_confidence_threshold: 0.5,    // Only clearly shaky results
+            warn_language_mismatch: false,    // Detection on short clips is noisy
+            auto_cleanup: AutoCleanupConfig::default(), // Off; 30 days when enabled
+            encrypt_history: false,
+            redaction_patterns: Vec::new(), // Opt-in, see get_default_redaction_patterns
+            stereo_downmix: "mix".to_string(),
+            loudness_normalize: "off".to_string(),
+            loudness_target_lufs: -16.0,
+            loudness_normalize_cloud: false,
+            min_recording_seconds: 0.5, // Skips accidental taps
+            trim_silence_on_save: false,
+            transcription_max_retries: 3,
+            transcription_retry_delay_ms: 500,
+            retry_cloud_transcription: false,
+            whisper_language: None,
+            parakeet_language: None,
+            soniox_language: None,
+            play_sound_on_recording_end: false,
+            start_sound_path: None,
+            end_sound_path: None,
+            mute_sounds_in_calls: false,
+            max_concurrent_uploads: DEFAULT_CONCURRENT_UPLOADS as u8,
+            keep_partial_on_cancel: false,
+            remember_mode_per_device: false, // Off: one recording mode for every device
+            device_recording_modes: HashMap::new(), // Filled in as modes are used per device
+            chunk_long_recordings_after_secs: DEFAULT_CHUNK_THRESHOLD_SECS, // Five minutes
+            fallback_blocklist: Vec::new(),  // Any downloaded model can be a fallback
+            skip_enhancement_modifier: "shift".to_string(), // Hold Shift to skip enhancement once
+            lock_target_window: false,       // Insert into whatever has focus
+            show_result_summary: false,      // Off; some find it noisy
+            toast_duration_scale: 1.0,       // Toasts last as long as each call asks
+            toast_position: "above".to_string(), // Above the pill, as before
+            deduplicate_repeats: true,       // Looping hallucinations are never wanted
+            on_empty_transcription: "toast".to_string(), // Toast, as before
+            tray_recording_timer: false,     // Tooltip stays "VoiceTypr"
+            hotkey_debounce_ms: crate::recording::DEFAULT_HOTKEY_DEBOUNCE_MS, // Swallows key bounce
+            local_api_enabled: false,
+            local_api_port: crate::local_api::DEFAULT_LOCAL_API_PORT,
+            copy_last_hotkey: None,   // No copy-last hotkey by default
+            attach_screenshot: false, // No screenshots
         }
     }
 }
@@ -143,6 +405,260 @@ pub async fn get_settings(app: AppHandle) -> Result<Settings, String> {
             .get("show_pill_indicator")
             .and_then(|v| v.as_bool())
             .unwrap_or_else(|| Settings::default().show_pill_indicator),
+        battery_aware_model: store
+            .get("battery_aware_model")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().battery_aware_model),
+        battery_threshold_percent: store
+            .get("battery_threshold_percent")
+            .and_then(|v| v.as_u64().map(crate::utils::power::clamp_battery_threshold))
+            .unwrap_or_else(|| Settings::default().battery_threshold_percent),
+        keep_raw_audio: store
+            .get("keep_raw_audio")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().keep_raw_audio),
+        preset_cycle_hotkey: store
+            .get("preset_cycle_hotkey")
+            .and_then(|v| v.as_str().map(|s| s.to_string())),
+        normalize_fallback_to_raw: store
+            .get("normalize_fallback_to_raw")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().normalize_fallback_to_raw),
+        ai_stream_insert: store
+            .get("ai_stream_insert")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().ai_stream_insert),
+        raw_insert_hotkey: store
+            .get("raw_insert_hotkey")
+            .and_then(|v| v.as_str().map(|s| s.to_string())),
+        insertion_method: InsertionMethod::resolve(
+            store
+                .get("insertion_method")
+                .as_ref()
+                .and_then(|v| v.as_str()),
+            store
+                .get("keep_transcription_in_clipboard")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        )
+        .as_str()
+        .to_string(),
+        app_insertion_rules: store
+            .get("app_insertion_rules")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| Settings::default().app_insertion_rules),
+        replacement_rules: store
+            .get("replacement_rules")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| Settings::default().replacement_rules),
+        voice_commands_enabled: store
+            .get("voice_commands_enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().voice_commands_enabled),
+        voice_command_map: store
+            .get("voice_command_map")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| Settings::default().voice_command_map),
+        normalize_numbers: store
+            .get("normalize_numbers")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().normalize_numbers),
+        tray_recent_count: store
+            .get("tray_recent_count")
+            .and_then(|v| v.as_u64().map(|n| n as u8))
+            .unwrap_or_else(|| Settings::default().tray_recent_count),
+        tray_recent_label_length: store
+            .get("tray_recent_label_length")
+            .and_then(|v| v.as_u64().map(|n| n as u32))
+            .unwrap_or_else(|| Settings::default().tray_recent_label_length),
+        model_hotkeys: store
+            .get("model_hotkeys")
+            .map(|v| parse_model_hotkeys(&v))
+            .unwrap_or_else(|| Settings::default().model_hotkeys),
+        double_tap_window_ms: store
+            .get("double_tap_window_ms")
+            .and_then(|v| v.as_u64().map(|n| n as u32))
+            .unwrap_or_else(|| Settings::default().double_tap_window_ms),
+        pill_theme: store
+            .get("pill_theme")
+            .and_then(|v| serde_json::from_value::<PillTheme>(v).ok())
+            .map(|theme| theme.normalized())
+            .unwrap_or_else(|| Settings::default().pill_theme),
+        output_mode: OutputMode::resolve(
+            store.get("output_mode").as_ref().and_then(|v| v.as_str()),
+        )
+        .as_str()
+        .to_string(),
+        model_cache_capacity: store
+            .get("model_cache_capacity")
+            .and_then(|v| v.as_u64().map(|n| n as u8))
+            .unwrap_or_else(|| Settings::default().model_cache_capacity),
+        download_connections: store
+            .get("download_connections")
+            .and_then(|v| v.as_u64().map(|n| n as u8))
+            .unwrap_or_else(|| Settings::default().download_connections),
+        use_gpu: store
+            .get("use_gpu")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().use_gpu),
+        soniox_streaming: store
+            .get("soniox_streaming")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().soniox_streaming),
+        low_confidence_threshold: store
+            .get("low_confidence_threshold")
+            .and_then(|v| v.as_f64().map(|n| n as f32))
+            .unwrap_or_else(|| Settings::default().low_confidence_threshold),
+        warn_language_mismatch: store
+            .get("warn_language_mismatch")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().warn_language_mismatch),
+        auto_cleanup: store
+            .get("auto_cleanup")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| Settings::default().auto_cleanup),
+        encrypt_history: store
+            .get("encrypt_history")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().encrypt_history),
+        redaction_patterns: store
+            .get("redaction_patterns")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| Settings::default().redaction_patterns),
+        stereo_downmix: store
+            .get("stereo_downmix")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| Settings::default().stereo_downmix),
+        loudness_normalize: store
+            .get("loudness_normalize")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| Settings::default().loudness_normalize),
+        loudness_target_lufs: store
+            .get("loudness_target_lufs")
+            .and_then(|v| v.as_f64().map(|n| n as f32))
+            .unwrap_or_else(|| Settings::default().loudness_target_lufs),
+        loudness_normalize_cloud: store
+            .get("loudness_normalize_cloud")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().loudness_normalize_cloud),
+        min_recording_seconds: store
+            .get("min_recording_seconds")
+            .and_then(|v| v.as_f64().map(|n| n as f32))
+            .unwrap_or_else(|| Settings::default().min_recording_seconds),
+        trim_silence_on_save: store
+            .get("trim_silence_on_save")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().trim_silence_on_save),
+        transcription_max_retries: store
+            .get("transcription_max_retries")
+            .and_then(|v| v.as_u64().map(|n| n as u32))
+            .unwrap_or_else(|| Settings::default().transcription_max_retries),
+        transcription_retry_delay_ms: store
+            .get("transcription_retry_delay_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or_else(|| Settings::default().transcription_retry_delay_ms),
+        retry_cloud_transcription: store
+            .get("retry_cloud_transcription")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().retry_cloud_transcription),
+        whisper_language: store
+            .get("whisper_language")
+            .and_then(|v| v.as_str().map(|s| s.to_string())),
+        parakeet_language: store
+            .get("parakeet_language")
+            .and_then(|v| v.as_str().map(|s| s.to_string())),
+        soniox_language: store
+            .get("soniox_language")
+            .and_then(|v| v.as_str().map(|s| s.to_string())),
+        play_sound_on_recording_end: store
+            .get("play_sound_on_recording_end")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().play_sound_on_recording_end),
+        start_sound_path: store
+            .get("start_sound_path")
+            .and_then(|v| v.as_str().map(|s| s.to_string())),
+        end_sound_path: store
+            .get("end_sound_path")
+            .and_then(|v| v.as_str().map(|s| s.to_string())),
+        mute_sounds_in_calls: store
+            .get("mute_sounds_in_calls")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().mute_sounds_in_calls),
+        max_concurrent_uploads: store
+            .get("max_concurrent_uploads")
+            .and_then(|v| v.as_u64().map(|n| n as u8))
+            .unwrap_or_else(|| Settings::default().max_concurrent_uploads),
+        keep_partial_on_cancel: store
+            .get("keep_partial_on_cancel")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().keep_partial_on_cancel),
+        remember_mode_per_device: store
+            .get("remember_mode_per_device")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().remember_mode_per_device),
+        device_recording_modes: store
+            .get("device_recording_modes")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| Settings::default().device_recording_modes),
+        chunk_long_recordings_after_secs: store
+            .get("chunk_long_recordings_after_secs")
+            .and_then(|v| v.as_u64().map(|n| n as u32))
+            .unwrap_or_else(|| Settings::default().chunk_long_recordings_after_secs),
+        fallback_blocklist: store
+            .get("fallback_blocklist")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| Settings::default().fallback_blocklist),
+        skip_enhancement_modifier: store
+            .get("skip_enhancement_modifier")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| Settings::default().skip_enhancement_modifier),
+        lock_target_window: store
+            .get("lock_target_window")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().lock_target_window),
+        show_result_summary: store
+            .get("show_result_summary")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().show_result_summary),
+        toast_duration_scale: store
+            .get("toast_duration_scale")
+            .and_then(|v| v.as_f64().map(|n| n as f32))
+            .unwrap_or_else(|| Settings::default().toast_duration_scale),
+        toast_position: store
+            .get("toast_position")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| Settings::default().toast_position),
+        deduplicate_repeats: store
+            .get("deduplicate_repeats")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().deduplicate_repeats),
+        on_empty_transcription: store
+            .get("on_empty_transcription")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| Settings::default().on_empty_transcription),
+        tray_recording_timer: store
+            .get("tray_recording_timer")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().tray_recording_timer),
+        hotkey_debounce_ms: store
+            .get("hotkey_debounce_ms")
+            .and_then(|v| v.as_u64().map(|n| n as u32))
+            .unwrap_or_else(|| Settings::default().hotkey_debounce_ms),
+        local_api_enabled: store
+            .get("local_api_enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().local_api_enabled),
+        local_api_port: store
+            .get("local_api_port")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| Settings::default().local_api_port),
+        copy_last_hotkey: store
+            .get("copy_last_hotkey")
+            .and_then(|v| v.as_str().map(|s| s.to_string())),
+        attach_screenshot: store
+            .get("attach_screenshot")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().attach_screenshot),
     };
 
     // Pill position is already loaded from store, no need for duplicate state
@@ -159,6 +675,14 @@ pub async fn save_settings(app: AppHandle, settings: Settings) -> Result<(), Str
         .get("current_model")
         .and_then(|v| v.as_str().map(|s| s.to_string()))
         .unwrap_or_default();
+    let old_engine = store
+        .get("current_model_engine")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| Settings::default().current_model_engine);
+    let old_language = store
+        .get("language")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| Settings::default().language);
     let old_mode = store
         .get("recording_mode")
         .and_then(|v| v.as_str().map(|s| s.to_string()))
@@ -167,6 +691,54 @@ pub async fn save_settings(app: AppHandle, settings: Settings) -> Result<(), Str
         .get("onboarding_completed")
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
+    let old_model_hotkeys = store
+        .get("model_hotkeys")
+        .map(|v| parse_model_hotkeys(&v))
+        .unwrap_or_default();
+    let old_pill_theme = store
+        .get("pill_theme")
+        .and_then(|v| serde_json::from_value::<PillTheme>(v).ok())
+        .map(|theme| theme.normalized())
+        .unwrap_or_default();
+    let old_tray_recent = (
+        store
+            .get("tray_recent_count")
+            .and_then(|v| v.as_u64().map(|n| n as u8))
+            .unwrap_or_else(|| Settings::default().tray_recent_count),
+        store
+            .get("tray_recent_label_length")
+            .and_then(|v| v.as_u64().map(|n| n as u32))
+            .unwrap_or_else(|| Settings::default().tray_recent_label_length),
+    );
+    let old_encrypt_history = crate::utils::history_crypto::history_encryption_enabled(&app);
+    let old_battery_settings = (
+        store
+            .get("battery_aware_model")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| Settings::default().battery_aware_model),
+        store
+            .get("battery_threshold_percent")
+            .and_then(|v| v.as_u64().map(crate::utils::power::clamp_battery_threshold))
+            .unwrap_or_else(|| Settings::default().battery_threshold_percent),
+    );
+
+    // Reject a bad regex before anything is written
+    validate_redaction_patterns(&settings.redaction_patterns)?;
+    crate::ffmpeg::StereoDownmix::parse(&settings.stereo_downmix)?;
+    crate::ffmpeg::LoudnessMode::parse(&settings.loudness_normalize)?;
+    crate::ffmpeg::validate_loudness_target(settings.loudness_target_lufs)?;
+    crate::commands::audio::EmptyTranscriptionAction::parse(&settings.on_empty_transcription)?;
+    crate::local_api::validate_port(settings.local_api_port)?;
+    crate::utils::power::validate_battery_threshold(settings.battery_threshold_percent)?;
+    let skip_enhancement_modifier =
+        crate::recording::SkipModifier::parse(&settings.skip_enhancement_modifier)?;
+    let toast_position = ToastPosition::parse(&settings.toast_position)?;
+    let old_toast_position = crate::utils::toast_layout::current_toast_position(&app);
+    for sound_path in [&settings.start_sound_path, &settings.end_sound_path] {
+        if let Some(path) = sound_path.as_deref().filter(|p| !p.trim().is_empty()) {
+            crate::audio::sounds::validate_sound_file(path)?;
+        }
+    }
 
     store.set("hotkey", json!(settings.hotkey));
     store.set("current_model", json!(settings.current_model));
@@ -207,10 +779,183 @@ pub async fn save_settings(app: AppHandle, settings: Settings) -> Result<(), Str
         "play_sound_on_recording",
         json!(settings.play_sound_on_recording),
     );
+    store.set("show_pill_indicator", json!(settings.show_pill_indicator));
+    store.set("battery_aware_model", json!(settings.battery_aware_model));
     store.set(
-        "show_pill_indicator",
-        json!(settings.show_pill_indicator),
+        "battery_threshold_percent",
+        json!(settings.battery_threshold_percent),
     );
+    store.set("keep_raw_audio", json!(settings.keep_raw_audio));
+    store.set("preset_cycle_hotkey", json!(settings.preset_cycle_hotkey));
+    store.set(
+        "normalize_fallback_to_raw",
+        json!(settings.normalize_fallback_to_raw),
+    );
+    store.set("ai_stream_insert", json!(settings.ai_stream_insert));
+    store.set("raw_insert_hotkey", json!(settings.raw_insert_hotkey));
+    // Normalize unknown values so insert_text and the UI agree on the method
+    store.set(
+        "insertion_method",
+        json!(InsertionMethod::resolve(Some(&settings.insertion_method), false).as_str()),
+    );
+    store.set("app_insertion_rules", json!(settings.app_insertion_rules));
+    store.set("replacement_rules", json!(settings.replacement_rules));
+    store.set(
+        "voice_commands_enabled",
+        json!(settings.voice_commands_enabled),
+    );
+    store.set("voice_command_map", json!(settings.voice_command_map));
+    store.set("normalize_numbers", json!(settings.normalize_numbers));
+    let tray_recent = (
+        settings
+            .tray_recent_count
+            .min(crate::menu::MAX_TRAY_RECENT_COUNT),
+        settings.tray_recent_label_length.clamp(
+            crate::menu::MIN_TRAY_LABEL_LENGTH,
+            crate::menu::MAX_TRAY_LABEL_LENGTH,
+        ),
+    );
+    store.set("tray_recent_count", json!(tray_recent.0));
+    store.set("tray_recent_label_length", json!(tray_recent.1));
+    let model_hotkeys = parse_model_hotkeys(&json!(settings.model_hotkeys));
+    store.set("model_hotkeys", json!(model_hotkeys));
+    store.set(
+        "double_tap_window_ms",
+        json!(settings.double_tap_window_ms.min(MAX_DOUBLE_TAP_WINDOW_MS)),
+    );
+    let pill_theme = settings.pill_theme.normalized();
+    store.set("pill_theme", json!(pill_theme));
+    store.set(
+        "output_mode",
+        json!(OutputMode::resolve(Some(&settings.output_mode)).as_str()),
+    );
+    let model_cache_capacity = settings
+        .model_cache_capacity
+        .clamp(1, MAX_MODEL_CACHE_CAPACITY as u8);
+    store.set("model_cache_capacity", json!(model_cache_capacity));
+    store.set(
+        "download_connections",
+        json!(settings
+            .download_connections
+            .clamp(1, MAX_DOWNLOAD_CONNECTIONS)),
+    );
+    store.set("use_gpu", json!(settings.use_gpu));
+    store.set("soniox_streaming", json!(settings.soniox_streaming));
+    let low_confidence_threshold = if settings.low_confidence_threshold.is_finite() {
+        settings.low_confidence_threshold.clamp(0.0, 1.0)
+    } else {
+        Settings::default().low_confidence_threshold
+    };
+    store.set("low_confidence_threshold", json!(low_confidence_threshold));
+    store.set(
+        "warn_language_mismatch",
+        json!(settings.warn_language_mismatch),
+    );
+    store.set("auto_cleanup", json!(settings.auto_cleanup.normalized()));
+    store.set("encrypt_history", json!(settings.encrypt_history));
+    store.set("redaction_patterns", json!(settings.redaction_patterns));
+    store.set("stereo_downmix", json!(settings.stereo_downmix));
+    store.set("loudness_normalize", json!(settings.loudness_normalize));
+    store.set("loudness_target_lufs", json!(settings.loudness_target_lufs));
+    store.set(
+        "loudness_normalize_cloud",
+        json!(settings.loudness_normalize_cloud),
+    );
+    let min_recording_seconds = if settings.min_recording_seconds.is_finite() {
+        settings
+            .min_recording_seconds
+            .clamp(0.0, MAX_MIN_RECORDING_SECONDS)
+    } else {
+        Settings::default().min_recording_seconds
+    };
+    store.set("min_recording_seconds", json!(min_recording_seconds));
+    store.set("trim_silence_on_save", json!(settings.trim_silence_on_save));
+    store.set(
+        "transcription_max_retries",
+        json!(settings
+            .transcription_max_retries
+            .clamp(1, MAX_TRANSCRIPTION_ATTEMPTS)),
+    );
+    store.set(
+        "transcription_retry_delay_ms",
+        json!(settings
+            .transcription_retry_delay_ms
+            .min(MAX_TRANSCRIPTION_RETRY_DELAY_MS)),
+    );
+    store.set(
+        "retry_cloud_transcription",
+        json!(settings.retry_cloud_transcription),
+    );
+    for (key, code) in [
+        ("whisper_language", &settings.whisper_language),
+        ("parakeet_language", &settings.parakeet_language),
+        ("soniox_language", &settings.soniox_language),
+    ] {
+        store.set(key, json!(validate_language_override(code.as_deref())));
+    }
+    store.set(
+        "play_sound_on_recording_end",
+        json!(settings.play_sound_on_recording_end),
+    );
+    store.set("start_sound_path", json!(settings.start_sound_path));
+    store.set("end_sound_path", json!(settings.end_sound_path));
+    store.set("mute_sounds_in_calls", json!(settings.mute_sounds_in_calls));
+    let max_concurrent_uploads = settings
+        .max_concurrent_uploads
+        .clamp(1, MAX_CONCURRENT_UPLOADS as u8);
+    store.set("max_concurrent_uploads", json!(max_concurrent_uploads));
+    store.set(
+        "keep_partial_on_cancel",
+        json!(settings.keep_partial_on_cancel),
+    );
+    store.set(
+        "remember_mode_per_device",
+        json!(settings.remember_mode_per_device),
+    );
+    // Remember a mode the user switches to for the microphone in use
+    let mut device_recording_modes = settings.device_recording_modes.clone();
+    if settings.remember_mode_per_device && old_mode != settings.recording_mode {
+        let active_device = settings
+            .selected_microphone
+            .clone()
+            .or_else(crate::audio::recorder::AudioRecorder::get_default_device);
+        if let Some(device) = active_device {
+            device_recording_modes.insert(device, settings.recording_mode.clone());
+        }
+    }
+    store.set("device_recording_modes", json!(device_recording_modes));
+    store.set(
+        "chunk_long_recordings_after_secs",
+        json!(settings.chunk_long_recordings_after_secs),
+    );
+    store.set("fallback_blocklist", json!(settings.fallback_blocklist));
+    store.set(
+        "skip_enhancement_modifier",
+        json!(settings.skip_enhancement_modifier),
+    );
+    store.set("lock_target_window", json!(settings.lock_target_window));
+    store.set("show_result_summary", json!(settings.show_result_summary));
+    store.set(
+        "toast_duration_scale",
+        json!(settings
+            .toast_duration_scale
+            .clamp(MIN_DURATION_SCALE, MAX_DURATION_SCALE)),
+    );
+    store.set("toast_position", json!(settings.toast_position));
+    store.set("deduplicate_repeats", json!(settings.deduplicate_repeats));
+    store.set(
+        "on_empty_transcription",
+        json!(settings.on_empty_transcription),
+    );
+    store.set("tray_recording_timer", json!(settings.tray_recording_timer));
+    store.set(
+        "hotkey_debounce_ms",
+        json!(settings.hotkey_debounce_ms.min(MAX_HOTKEY_DEBOUNCE_MS)),
+    );
+    store.set("local_api_enabled", json!(settings.local_api_enabled));
+    store.set("local_api_port", json!(settings.local_api_port));
+    store.set("copy_last_hotkey", json!(settings.copy_last_hotkey));
+    store.set("attach_screenshot", json!(settings.attach_screenshot));
 
     // Save pill position if provided
     if let Some((x, y)) = settings.pill_position {
@@ -230,6 +975,37 @@ pub async fn save_settings(app: AppHandle, settings: Settings) -> Result<(), Str
         *mode_guard = recording_mode;
         log::info!("Recording mode updated to: {:?}", recording_mode);
     }
+    if let Ok(mut detector) = app_state.double_tap.lock() {
+        detector.set_window(std::time::Duration::from_millis(
+            settings.double_tap_window_ms.min(MAX_DOUBLE_TAP_WINDOW_MS) as u64,
+        ));
+    }
+    if let Ok(mut debouncer) = app_state.hotkey_debounce.lock() {
+        debouncer.set_interval(std::time::Duration::from_millis(
+            settings.hotkey_debounce_ms.min(MAX_HOTKEY_DEBOUNCE_MS) as u64,
+        ));
+    }
+    if let Ok(mut modifier_guard) = app_state.skip_enhancement_modifier.lock() {
+        *modifier_guard = skip_enhancement_modifier;
+    }
+    // Start, stop or move the local API; a busy port shouldn't fail the save
+    if let Err(e) = crate::local_api::sync_local_api(&app).await {
+        log::warn!("Local API not started: {}", e);
+    }
+    if toast_position != old_toast_position {
+        if let Some(window_manager) = app_state.get_window_manager() {
+            window_manager.reposition_floating_windows();
+        }
+    }
+
+    // Shrinking the cache unloads the least recently used models right away, and
+    // toggling GPU acceleration makes loaded models reload with the new backend
+    {
+        let cache_state = app.state::<tauri::async_runtime::Mutex<TranscriberCache>>();
+        let mut cache = cache_state.lock().await;
+        cache.set_capacity(model_cache_capacity as usize);
+        cache.set_use_gpu(settings.use_gpu);
+    }
 
     // Handle PTT shortcut registration if needed
     if recording_mode == crate::RecordingMode::PushToTalk && settings.use_different_ptt_key {
@@ -273,35 +1049,49 @@ pub async fn save_settings(app: AppHandle, settings: Settings) -> Result<(), Str
         }
     }
 
+    apply_action_hotkey(
+        &app,
+        &app_state.preset_cycle_shortcut,
+        settings.preset_cycle_hotkey.as_deref(),
+        "Preset cycle",
+    );
+    apply_action_hotkey(
+        &app,
+        &app_state.raw_insert_shortcut,
+        settings.raw_insert_hotkey.as_deref(),
+        "Raw insert",
+    );
+    apply_action_hotkey(
+        &app,
+        &app_state.copy_last_shortcut,
+        settings.copy_last_hotkey.as_deref(),
+        "Copy last",
+    );
+    // Only re-register when the list changed; switching models saves settings too
+    if old_model_hotkeys != model_hotkeys {
+        apply_model_hotkeys(&app, &model_hotkeys);
+    }
+
     // Invalidate recording config cache when settings change
     crate::commands::audio::invalidate_recording_config_cache(&app).await;
 
     // Preload new model and update tray menu if model changed
-    let is_parakeet_engine = settings.current_model_engine == "parakeet";
     let is_cloud_engine = settings.current_model_engine == "soniox";
 
     if !settings.current_model.is_empty() && old_model != settings.current_model {
-        use crate::commands::model::preload_model;
-        use tauri::async_runtime::RwLock as AsyncRwLock;
-
         log::info!(
             "Model changed from '{}' to '{}', preloading new model and updating tray menu",
             old_model,
             settings.current_model
         );
 
-        if !(is_parakeet_engine || is_cloud_engine) {
-            // Preload the new Whisper model
-            let app_clone = app.clone();
-            let model_name = settings.current_model.clone();
-            tokio::spawn(async move {
-                let whisper_state =
-                    app_clone.state::<AsyncRwLock<crate::whisper::manager::WhisperManager>>();
-                match preload_model(app_clone.clone(), model_name.clone(), whisper_state).await {
-                    Ok(_) => log::info!("Successfully preloaded new model: {}", model_name),
-                    Err(e) => log::warn!("Failed to preload new model: {}", e),
-                }
-            });
+        if !is_cloud_engine {
+            // Debounced, so quickly switching through models only loads the last one
+            crate::utils::model_preload::schedule_model_preload(
+                &app,
+                settings.current_model.clone(),
+                settings.current_model_engine.clone(),
+            );
         } else {
             log::info!(
                 "Skipping preload for {} engine selection",
@@ -316,6 +1106,11 @@ pub async fn save_settings(app: AppHandle, settings: Settings) -> Result<(), Str
         }
     }
 
+    // Soniox only counts as available while it's the selected engine
+    if old_engine != settings.current_model_engine {
+        crate::emit_recognition_availability(&app).await;
+    }
+
     // If recording mode changed, refresh tray to update checked state
     if old_mode != settings.recording_mode {
         if let Err(e) = update_tray_menu(app.clone()).await {
@@ -323,6 +1118,67 @@ pub async fn save_settings(app: AppHandle, settings: Settings) -> Result<(), Str
         }
     }
 
+    // Restyle the pill and toast windows without recreating them
+    if old_pill_theme != pill_theme {
+        crate::utils::pill_theme::emit_pill_theme(&app);
+    }
+
+    // Rebuild the recent transcriptions submenu with the new count/length
+    if old_tray_recent != tray_recent {
+        if let Err(e) = update_tray_menu(app.clone()).await {
+            log::warn!("Failed to update tray menu after recent list change: {}", e);
+        }
+    }
+
+    // Encrypt the existing history on enable, and decrypt it again on disable
+    if old_encrypt_history != settings.encrypt_history {
+        match crate::utils::history_crypto::migrate_history_encryption(
+            &app,
+            settings.encrypt_history,
+        ) {
+            Ok(count) => log::info!(
+                "{} {} history entries",
+                if settings.encrypt_history {
+                    "Encrypted"
+                } else {
+                    "Decrypted"
+                },
+                count
+            ),
+            Err(e) => log::error!("Failed to migrate history encryption: {}", e),
+        }
+    }
+
+    // Apply battery-aware model switching right away when its settings change
+    if old_battery_settings
+        != (
+            settings.battery_aware_model,
+            settings.battery_threshold_percent,
+        )
+    {
+        crate::utils::power::reevaluate_power_policy(&app).await;
+    }
+
+    // Tell the user when the new language wants a different model
+    if validated_language != old_language {
+        let recommendation = crate::commands::model::model_recommendation_for(
+            &app,
+            validated_language,
+            &settings.current_model,
+        )
+        .await;
+        if !recommendation.warnings.is_empty() {
+            log::info!(
+                "Model recommendation for {}: {:?}",
+                validated_language,
+                recommendation.warnings
+            );
+        }
+        if let Err(e) = crate::emit_to_all(&app, "model-recommendation", recommendation) {
+            log::warn!("Failed to emit model-recommendation event: {}", e);
+        }
+    }
+
     // If onboarding just completed, try to start device watcher
     if !old_onboarding_completed && settings.onboarding_completed {
         log::info!("Onboarding just completed, checking if device watcher should start");
@@ -332,6 +1188,190 @@ pub async fn save_settings(app: AppHandle, settings: Settings) -> Result<(), Str
     Ok(())
 }
 
+/// Settings store keys kept next to [`Settings`] by the AI commands
+pub const AI_SETTINGS_KEYS: &[&str] = &[
+    "ai_enabled",
+    "ai_provider",
+    "ai_model",
+    "ai_openai_base_url",
+    "ai_ollama_base_url",
+    "ai_openai_no_auth",
+    "enhancement_presets",
+    "active_enhancement_preset",
+];
+
+/// Copy `values` for [`Settings`] fields onto `settings`; other keys are ignored
+pub fn overlay_settings(
+    settings: &Settings,
+    values: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Settings, String> {
+    let mut merged = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    if let Some(fields) = merged.as_object_mut() {
+        for (key, value) in values {
+            if fields.contains_key(key) {
+                fields.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    serde_json::from_value(merged).map_err(|e| format!("Invalid settings: {}", e))
+}
+
+/// Keys whose value in `values` differs from `current`
+pub fn changed_settings_keys(
+    current: &serde_json::Map<String, serde_json::Value>,
+    values: &serde_json::Map<String, serde_json::Value>,
+) -> Vec<String> {
+    values
+        .iter()
+        .filter(|(key, value)| current.get(key.as_str()) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// Write several settings store values at once and re-apply everything derived from
+/// them (hotkeys, model cache, RecordingConfig, tray). Returns the keys that changed.
+pub async fn apply_settings_values(
+    app: &AppHandle,
+    values: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<String>, String> {
+    let store = app.store("settings").map_err(|e| e.to_string())?;
+    let current: serde_json::Map<String, serde_json::Value> = values
+        .keys()
+        .filter_map(|key| store.get(key).map(|value| (key.clone(), value)))
+        .collect();
+    let changed = changed_settings_keys(&current, values);
+    if changed.is_empty() {
+        return Ok(changed);
+    }
+
+    // Validate before touching the store so bad values leave everything unchanged
+    let settings = overlay_settings(&get_settings(app.clone()).await?, values)?;
+
+    // Register the recording hotkey first so a conflict leaves everything unchanged
+    if changed.iter().any(|key| key == "hotkey") {
+        set_global_shortcut(app.clone(), settings.hotkey.clone()).await?;
+    }
+
+    for key in changed
+        .iter()
+        .filter(|key| AI_SETTINGS_KEYS.contains(&key.as_str()))
+    {
+        store.set(key, values[key.as_str()].clone());
+    }
+
+    // save_settings persists the store, re-registers push-to-talk and model hotkeys,
+    // preloads the model and invalidates the RecordingConfig cache
+    save_settings(app.clone(), settings).await?;
+
+    // The language and profile checkmarks change even when the model didn't
+    if let Err(e) = update_tray_menu(app.clone()).await {
+        log::warn!("Failed to update tray menu after applying settings: {}", e);
+    }
+    let _ = app.emit("settings-changed", ());
+    if changed.iter().any(|key| key.contains("enhancement_preset")) {
+        let _ = app.emit("enhancement-presets-changed", ());
+    }
+
+    Ok(changed)
+}
+
+/// (Re)register an optional action hotkey (preset cycle, raw re-insert) in its AppState slot
+pub fn apply_action_hotkey(
+    app: &AppHandle,
+    slot: &std::sync::Mutex<Option<Shortcut>>,
+    hotkey: Option<&str>,
+    label: &str,
+) {
+    let shortcuts = app.global_shortcut();
+
+    // Unregister old shortcut if exists
+    if let Ok(mut guard) = slot.lock() {
+        if let Some(old) = guard.take() {
+            let _ = shortcuts.unregister(old);
+        }
+    }
+
+    let Some(hotkey) = hotkey.filter(|h| !h.trim().is_empty()) else {
+        return;
+    };
+
+    let Ok(shortcut) = normalize_shortcut_keys(hotkey).parse::<Shortcut>() else {
+        log::warn!("Invalid {} hotkey format: {}", label, hotkey);
+        return;
+    };
+
+    match shortcuts.register(shortcut.clone()) {
+        Ok(_) => {
+            if let Ok(mut guard) = slot.lock() {
+                *guard = Some(shortcut);
+            }
+            log::info!("{} shortcut registered: {}", label, hotkey);
+        }
+        Err(e) => {
+            log::error!("Failed to register {} shortcut '{}': {}", label, hotkey, e);
+        }
+    }
+}
+
+/// (Re)register the model hotkeys, replacing whatever was registered before.
+/// Hotkeys that fail to register are reported through `hotkey-registration-failed`.
+pub fn apply_model_hotkeys(app: &AppHandle, hotkeys: &[ModelHotkey]) {
+    let app_state = app.state::<AppState>();
+    let shortcuts = app.global_shortcut();
+
+    let Ok(mut registered) = app_state.model_shortcuts.lock() else {
+        log::error!("Failed to lock model shortcuts");
+        return;
+    };
+    for (old, _) in registered.drain(..) {
+        let _ = shortcuts.unregister(old);
+    }
+
+    for hotkey in hotkeys {
+        let result = normalize_shortcut_keys(&hotkey.hotkey)
+            .parse::<Shortcut>()
+            .map_err(|e| format!("Invalid hotkey format: {}", e))
+            .and_then(|shortcut| {
+                shortcuts
+                    .register(shortcut.clone())
+                    .map(|_| shortcut)
+                    .map_err(|e| e.to_string())
+            });
+
+        match result {
+            Ok(shortcut) => {
+                log::info!(
+                    "Model hotkey registered: {} -> {}",
+                    hotkey.hotkey,
+                    hotkey.model_name
+                );
+                registered.push((shortcut, hotkey.clone()));
+            }
+            Err(e) => {
+                log::error!(
+                    "Failed to register model hotkey '{}' for {}: {}",
+                    hotkey.hotkey,
+                    hotkey.model_name,
+                    e
+                );
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.emit(
+                        "hotkey-registration-failed",
+                        json!({
+                            "hotkey": hotkey.hotkey,
+                            "error": e,
+                            "suggestion": format!(
+                                "Choose a different hotkey for {} or close conflicting applications",
+                                hotkey.model_name
+                            )
+                        }),
+                    );
+                }
+            }
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn set_global_shortcut(app: AppHandle, shortcut: String) -> Result<(), String> {
     log::info!("Updating global shortcut to: {}", shortcut);
@@ -456,6 +1496,119 @@ pub async fn set_global_shortcut(app: AppHandle, shortcut: String) -> Result<(),
     Ok(())
 }
 
+/// Result of `check_hotkey_available`, tagged by `status` for the settings UI
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum HotkeyAvailability {
+    Available {
+        normalized: String,
+    },
+    InvalidFormat {
+        error: String,
+    },
+    /// Already registered by one of VoiceTypr's own hotkeys (`used_for` names which)
+    InUseByVoicetypr {
+        normalized: String,
+        used_for: String,
+    },
+    /// The OS refused the registration, most likely because another app holds it
+    RegistrationFailed {
+        normalized: String,
+        error: String,
+    },
+}
+
+/// Validate and normalize `hotkey`, then compare it against VoiceTypr's own registered
+/// shortcuts. Returns the parsed shortcut when the OS still has to be asked.
+pub fn precheck_hotkey(
+    hotkey: &str,
+    registered: &[(Shortcut, String)],
+) -> Result<(String, Shortcut), HotkeyAvailability> {
+    if hotkey.trim().is_empty() || hotkey.len() > 100 {
+        return Err(HotkeyAvailability::InvalidFormat {
+            error: "Hotkey is empty or too long".to_string(),
+        });
+    }
+
+    let normalized = normalize_shortcut_keys(hotkey.trim());
+    validate_key_combination(&normalized)
+        .map_err(|error| HotkeyAvailability::InvalidFormat { error })?;
+
+    let shortcut =
+        normalized
+            .parse::<Shortcut>()
+            .map_err(|e| HotkeyAvailability::InvalidFormat {
+                error: format!("Invalid shortcut format: {}", e),
+            })?;
+
+    if let Some((_, used_for)) = registered.iter().find(|(own, _)| *own == shortcut) {
+        return Err(HotkeyAvailability::InUseByVoicetypr {
+            normalized,
+            used_for: used_for.clone(),
+        });
+    }
+
+    Ok((normalized, shortcut))
+}
+
+/// VoiceTypr's currently registered shortcuts with a label for each
+fn registered_app_shortcuts(app_state: &AppState) -> Vec<(Shortcut, String)> {
+    let mut registered = Vec::new();
+    for (slot, label) in [
+        (&app_state.recording_shortcut, "recording"),
+        (&app_state.ptt_shortcut, "push-to-talk"),
+        (&app_state.preset_cycle_shortcut, "preset cycle"),
+        (&app_state.raw_insert_shortcut, "raw re-insert"),
+        (&app_state.copy_last_shortcut, "copy last transcription"),
+    ] {
+        if let Some(shortcut) = slot.lock().ok().and_then(|guard| guard.clone()) {
+            registered.push((shortcut, label.to_string()));
+        }
+    }
+    if let Ok(model_shortcuts) = app_state.model_shortcuts.lock() {
+        for (shortcut, hotkey) in model_shortcuts.iter() {
+            registered.push((shortcut.clone(), format!("model {}", hotkey.model_name)));
+        }
+    }
+    registered
+}
+
+/// Check whether `hotkey` can be registered, without keeping it registered
+#[tauri::command]
+pub async fn check_hotkey_available(
+    app: AppHandle,
+    hotkey: String,
+) -> Result<HotkeyAvailability, String> {
+    let app_state = app.state::<AppState>();
+    let registered = registered_app_shortcuts(&app_state);
+
+    let (normalized, shortcut) = match precheck_hotkey(&hotkey, &registered) {
+        Ok(parsed) => parsed,
+        Err(result) => return Ok(result),
+    };
+
+    let shortcuts = app.global_shortcut();
+    match shortcuts.register(shortcut.clone()) {
+        Ok(_) => {
+            if let Err(e) = shortcuts.unregister(shortcut) {
+                log::warn!(
+                    "Failed to release hotkey '{}' after check: {}",
+                    normalized,
+                    e
+                );
+            }
+            Ok(HotkeyAvailability::Available { normalized })
+        }
+        Err(e) => {
+            log::info!("Hotkey '{}' is not available: {}", normalized, e);
+            Ok(HotkeyAvailability::RegistrationFailed {
+                normalized,
+                error: e.to_string(),
+            })
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct LanguageInfo {
     pub code: String,
@@ -478,44 +1631,68 @@ pub async fn get_supported_languages() -> Result<Vec<LanguageInfo>, String> {
     Ok(languages)
 }
 
+/// Suggested card, SSN and email patterns for the `redaction_patterns` setting
 #[tauri::command]
-pub async fn set_model_from_tray(app: AppHandle, model_name: String) -> Result<(), String> {
-    // Get current settings
-    let mut settings = get_settings(app.clone()).await?;
+pub async fn get_default_redaction_patterns() -> Result<Vec<String>, String> {
+    Ok(DEFAULT_REDACTION_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect())
+}
 
-    let engine = if model_name == "soniox" {
-        "soniox".to_string()
-    } else {
-        let whisper_state = app.state::<tauri::async_runtime::RwLock<WhisperManager>>();
-        let whisper_has = {
-            let guard = whisper_state.read().await;
-            guard.get_models_status().contains_key(&model_name)
-        };
+/// Engine that owns `model_name`, looked up in the model registries
+async fn resolve_model_engine(app: &AppHandle, model_name: &str) -> String {
+    if model_name == "soniox" {
+        return "soniox".to_string();
+    }
 
-        if whisper_has {
-            "whisper".to_string()
+    let whisper_state = app.state::<tauri::async_runtime::RwLock<WhisperManager>>();
+    let whisper_has = {
+        let guard = whisper_state.read().await;
+        guard.get_models_status().contains_key(model_name)
+    };
+
+    if whisper_has {
+        "whisper".to_string()
+    } else {
+        let parakeet_manager = app.state::<ParakeetManager>();
+        let is_parakeet = parakeet_manager
+            .list_models()
+            .into_iter()
+            .any(|m| m.name == model_name);
+        if is_parakeet {
+            "parakeet".to_string()
         } else {
-            let parakeet_manager = app.state::<ParakeetManager>();
-            let is_parakeet = parakeet_manager
-                .list_models()
-                .into_iter()
-                .any(|m| m.name == model_name);
-            if is_parakeet {
-                "parakeet".to_string()
-            } else {
-                log::warn!(
-                    "set_model_from_tray: model '{}' not found in registries; defaulting to whisper",
-                    model_name
-                );
-                "whisper".to_string()
-            }
+            log::warn!(
+                "Model '{}' not found in registries; defaulting to whisper",
+                model_name
+            );
+            "whisper".to_string()
         }
-    };
+    }
+}
 
-    // Update the model
-    settings.current_model = model_name.clone();
-    settings.current_model_engine = engine.clone();
+/// Point `settings` at a new model the way the tray does, resetting the language to English
+pub fn apply_model_selection(settings: &mut Settings, model_name: &str, engine: &str) {
+    settings.current_model = model_name.to_string();
+    settings.current_model_engine = engine.to_string();
     settings.language = "en".to_string();
+}
+
+/// Switch the current model, refresh the tray and notify the UI.
+/// `engine` is looked up from the model registries when not given.
+async fn select_model(
+    app: &AppHandle,
+    model_name: &str,
+    engine: Option<&str>,
+) -> Result<String, String> {
+    let mut settings = get_settings(app.clone()).await?;
+
+    let engine = match engine {
+        Some(engine) => engine.to_string(),
+        None => resolve_model_engine(app, model_name).await,
+    };
+    apply_model_selection(&mut settings, model_name, &engine);
 
     // Save settings (this will also preload the model)
     save_settings(app.clone(), settings).await?;
@@ -536,6 +1713,50 @@ pub async fn set_model_from_tray(app: AppHandle, model_name: String) -> Result<(
         return Err(format!("Failed to emit model-changed event: {}", e));
     }
 
+    Ok(engine)
+}
+
+#[tauri::command]
+pub async fn set_model_from_tray(app: AppHandle, model_name: String) -> Result<(), String> {
+    select_model(&app, &model_name, None).await.map(|_| ())
+}
+
+/// Switch to the model bound to a model hotkey, optionally starting a recording
+pub async fn set_model_from_hotkey(app: AppHandle, hotkey: ModelHotkey) -> Result<(), String> {
+    let engine = Some(hotkey.engine.as_str()).filter(|e| !e.is_empty());
+    select_model(&app, &hotkey.model_name, engine).await?;
+    log::info!("Model switched via hotkey to: {}", hotkey.model_name);
+
+    let idle = matches!(
+        crate::get_recording_state(&app),
+        crate::RecordingState::Idle | crate::RecordingState::Error
+    );
+    if hotkey.start_recording && idle {
+        let recorder_state = app.state::<crate::commands::audio::RecorderState>();
+        crate::commands::audio::start_recording(app.clone(), recorder_state).await?;
+    } else {
+        crate::commands::audio::pill_toast(&app, &format!("Model: {}", hotkey.model_name), 1500);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_language_from_tray(app: AppHandle, language: String) -> Result<(), String> {
+    let mut settings = get_settings(app.clone()).await?;
+    settings.language = validate_language(Some(&language)).to_string();
+    let language = settings.language.clone();
+
+    // Saving also invalidates the cached recording config
+    save_settings(app.clone(), settings).await?;
+
+    // Move the check mark and the submenu title to the new language
+    update_tray_menu(app.clone()).await?;
+
+    if let Err(e) = app.emit("language-changed", json!({ "language": language })) {
+        log::warn!("Failed to emit language-changed event: {}", e);
+    }
+
     Ok(())
 }
 
@@ -559,6 +1780,41 @@ pub async fn update_tray_menu(app: AppHandle) -> Result<(), String> {
 }
 
 /// Set the selected microphone device
+/// Switch to the recording mode remembered for `device` when per-device modes
+/// are on. Goes through [`save_settings`], which updates `AppState` and the
+/// push-to-talk shortcut.
+pub(crate) async fn apply_device_recording_mode(
+    app: &AppHandle,
+    device: Option<&str>,
+) -> Result<(), String> {
+    let mut settings = get_settings(app.clone()).await?;
+    let Some(mode) = crate::audio::device_watcher::remembered_recording_mode(
+        settings.remember_mode_per_device,
+        &settings.device_recording_modes,
+        device,
+    ) else {
+        return Ok(());
+    };
+    if mode == settings.recording_mode {
+        return Ok(());
+    }
+    // Switching mid-recording would strand a push-to-talk key release
+    if crate::get_recording_state(app) != crate::RecordingState::Idle {
+        log::info!(
+            "Not switching to {} mode for {:?} while recording",
+            mode,
+            device
+        );
+        return Ok(());
+    }
+
+    log::info!("Switching to {} mode for microphone {:?}", mode, device);
+    settings.recording_mode = mode.to_string();
+    save_settings(app.clone(), settings).await?;
+    let _ = app.emit("settings-changed", ());
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn set_audio_device(app: AppHandle, device_name: Option<String>) -> Result<(), String> {
     log::info!("Setting audio device to: {:?}", device_name);
@@ -602,6 +1858,18 @@ pub async fn set_audio_device(app: AppHandle, device_name: Option<String>) -> Re
     // Save the updated settings
     save_settings(app.clone(), settings).await?;
 
+    // Switch to the mode last used with this microphone
+    let active_device = device_name
+        .clone()
+        .or_else(crate::audio::recorder::AudioRecorder::get_default_device);
+    if let Err(e) = apply_device_recording_mode(&app, active_device.as_deref()).await {
+        log::warn!(
+            "Failed to apply recording mode for {:?}: {}",
+            active_device,
+            e
+        );
+    }
+
     // Update tray menu to reflect the change
     update_tray_menu(app.clone()).await?;
 
@@ -0,0 +1,213 @@
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::commands::ai::ALLOWED_PROVIDERS;
+use crate::commands::settings::{
+    apply_settings_values, overlay_settings, Settings, AI_SETTINGS_KEYS,
+};
+
+/// Bumped when the export layout changes incompatibly
+pub const SETTINGS_EXPORT_VERSION: u64 = 1;
+
+const EXPORT_APP_NAME: &str = "VoiceTypr";
+
+/// Settings that only make sense on the machine they were saved on
+const MACHINE_SPECIFIC_KEYS: &[&str] = &["pill_position", "onboarding_completed"];
+
+/// Whether a store key looks like it holds a credential. Secrets live in the
+/// secure store, but anything matching is kept out of exports regardless.
+pub fn is_secret_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key == "license"
+        || ["api_key", "apikey", "token", "secret", "password"]
+            .iter()
+            .any(|marker| key.contains(marker))
+}
+
+/// Settings store keys that are exported and accepted on import
+pub fn exportable_settings_keys() -> Vec<String> {
+    let mut keys: Vec<String> = serde_json::to_value(Settings::default())
+        .ok()
+        .and_then(|value| {
+            value
+                .as_object()
+                .map(|fields| fields.keys().cloned().collect())
+        })
+        .unwrap_or_default();
+    keys.extend(AI_SETTINGS_KEYS.iter().map(|key| key.to_string()));
+    keys.retain(|key| !MACHINE_SPECIFIC_KEYS.contains(&key.as_str()) && !is_secret_key(key));
+    keys
+}
+
+/// Secure store entries that an export can't carry, e.g. `ai_api_key_openai`
+pub fn secret_store_keys() -> Vec<String> {
+    let mut keys = vec!["stt_api_key_soniox".to_string()];
+    keys.extend(
+        ALLOWED_PROVIDERS
+            .iter()
+            .map(|provider| format!("ai_api_key_{}", provider)),
+    );
+    keys.push("license".to_string());
+    keys
+}
+
+/// Build the export document from the settings store. `secrets_to_reenter` lists
+/// the secure store entries that are set here and have to be entered again after import.
+pub fn build_settings_export(
+    read: impl Fn(&str) -> Option<Value>,
+    secrets_to_reenter: Vec<String>,
+    exported_at: &str,
+) -> Value {
+    let settings: Map<String, Value> = exportable_settings_keys()
+        .into_iter()
+        .filter_map(|key| read(&key).map(|value| (key, value)))
+        .collect();
+
+    json!({
+        "app": EXPORT_APP_NAME,
+        "version": SETTINGS_EXPORT_VERSION,
+        "exported_at": exported_at,
+        "settings": settings,
+        "secrets_to_reenter": secrets_to_reenter,
+    })
+}
+
+/// Settings accepted from an import document
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSettingsImport {
+    pub values: Map<String, Value>,
+    /// Keys in the file that were not imported (unknown, machine-specific or secret)
+    pub skipped: Vec<String>,
+    pub secrets_to_reenter: Vec<String>,
+}
+
+/// Parse and validate an export document. Nothing is applied unless the whole file
+/// is valid, so a truncated or hand-edited file can't leave settings half-imported.
+pub fn parse_settings_import(json: &str) -> Result<ParsedSettingsImport, String> {
+    let document: Value =
+        serde_json::from_str(json).map_err(|e| format!("Not a valid settings file: {}", e))?;
+
+    if document.get("app").and_then(|v| v.as_str()) != Some(EXPORT_APP_NAME) {
+        return Err("Not a VoiceTypr settings export".to_string());
+    }
+    match document.get("version").and_then(|v| v.as_u64()) {
+        Some(SETTINGS_EXPORT_VERSION) => {}
+        Some(version) => {
+            return Err(format!(
+                "Settings export version {} is not supported",
+                version
+            ))
+        }
+        None => return Err("Settings export is missing its version".to_string()),
+    }
+    let settings = document
+        .get("settings")
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| "Settings export has no settings section".to_string())?;
+    if settings.is_empty() {
+        return Err("Settings export contains no settings".to_string());
+    }
+
+    let exportable = exportable_settings_keys();
+    let mut values = Map::new();
+    let mut skipped = Vec::new();
+    for (key, value) in settings {
+        if exportable.contains(key) {
+            values.insert(key.clone(), value.clone());
+        } else {
+            skipped.push(key.clone());
+        }
+    }
+
+    // Type-check every Settings field, then the AI keys kept beside them
+    overlay_settings(&Settings::default(), &values)?;
+    for (key, value) in &values {
+        let valid = match key.as_str() {
+            "ai_enabled" | "ai_openai_no_auth" => value.is_boolean(),
+            "enhancement_presets" => value.is_array(),
+            key if AI_SETTINGS_KEYS.contains(&key) => value.is_string() || value.is_null(),
+            _ => true,
+        };
+        if !valid {
+            return Err(format!("Invalid value for {}: {}", key, value));
+        }
+    }
+
+    let known_secrets = secret_store_keys();
+    let secrets_to_reenter = document
+        .get("secrets_to_reenter")
+        .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|key| known_secrets.contains(key))
+        .collect();
+
+    Ok(ParsedSettingsImport {
+        values,
+        skipped,
+        secrets_to_reenter,
+    })
+}
+
+/// Result of `import_settings`
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsImportReport {
+    pub changed: Vec<String>,
+    pub skipped: Vec<String>,
+    /// API keys and the license have to be entered again on this machine
+    pub secrets_to_reenter: Vec<String>,
+}
+
+/// Export the settings store as a JSON document. API keys and the license are
+/// never included; `secrets_to_reenter` names the ones to set up again.
+#[tauri::command]
+pub async fn export_settings(app: AppHandle) -> Result<String, String> {
+    let store = app.store("settings").map_err(|e| e.to_string())?;
+    let secrets_to_reenter = secret_store_keys()
+        .into_iter()
+        .filter(|key| crate::secure_store::secure_has(&app, key).unwrap_or(false))
+        .collect();
+
+    let export = build_settings_export(
+        |key| store.get(key),
+        secrets_to_reenter,
+        &chrono::Utc::now().to_rfc3339(),
+    );
+    log::info!(
+        "Exported {} settings",
+        export["settings"].as_object().map_or(0, |s| s.len())
+    );
+    serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))
+}
+
+/// Validate an export document and merge it into the current settings
+#[tauri::command]
+pub async fn import_settings(app: AppHandle, json: String) -> Result<SettingsImportReport, String> {
+    if app.state::<crate::AppState>().get_current_state() != crate::RecordingState::Idle {
+        return Err("Can't import settings while recording or transcribing".to_string());
+    }
+
+    let parsed = parse_settings_import(&json)?;
+    if !parsed.skipped.is_empty() {
+        log::warn!("Settings import skipped keys: {:?}", parsed.skipped);
+    }
+
+    let changed = apply_settings_values(&app, &parsed.values).await?;
+    log::info!("Imported settings, changed {:?}", changed);
+
+    // Secrets already set up on this machine don't need entering again
+    let secrets_to_reenter = parsed
+        .secrets_to_reenter
+        .into_iter()
+        .filter(|key| !crate::secure_store::secure_has(&app, key).unwrap_or(false))
+        .collect();
+
+    Ok(SettingsImportReport {
+        changed,
+        skipped: parsed.skipped,
+        secrets_to_reenter,
+    })
+}
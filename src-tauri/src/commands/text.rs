@@ -1,4 +1,5 @@
 use arboard::Clipboard;
+use serde::{Deserialize, Serialize};
 use std::panic::{self, AssertUnwindSafe};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
@@ -18,8 +19,210 @@ use enigo::{
 // Global flag to prevent concurrent text insertions
 static IS_INSERTING: AtomicBool = AtomicBool::new(false);
 
+/// How transcribed text is delivered to the focused application
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertionMethod {
+    /// Paste via the clipboard and leave the transcript on it
+    Paste,
+    /// Synthesize keystrokes; the clipboard is never touched
+    Type,
+    /// Paste via the clipboard, then put the previous clipboard text back
+    PastePreserveClipboard,
+}
+
+impl InsertionMethod {
+    /// Resolve the stored `insertion_method` setting. Stores written before the setting
+    /// existed fall back to the legacy `keep_transcription_in_clipboard` flag.
+    pub fn resolve(value: Option<&str>, keep_transcription_in_clipboard: bool) -> Self {
+        match value {
+            Some("paste") => Self::Paste,
+            Some("type") => Self::Type,
+            Some("paste_preserve_clipboard") => Self::PastePreserveClipboard,
+            Some(other) => {
+                log::warn!(
+                    "Unknown insertion method '{}', using paste_preserve_clipboard",
+                    other
+                );
+                Self::PastePreserveClipboard
+            }
+            None if keep_transcription_in_clipboard => Self::Paste,
+            None => Self::PastePreserveClipboard,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Paste => "paste",
+            Self::Type => "type",
+            Self::PastePreserveClipboard => "paste_preserve_clipboard",
+        }
+    }
+}
+
+/// Where a finished transcription is delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputMode {
+    /// Insert at the cursor using the configured insertion method
+    Insert,
+    /// Only copy to the clipboard; nothing is pasted or typed
+    ClipboardOnly,
+    /// Insert at the cursor and leave the transcript on the clipboard
+    Both,
+}
+
+impl OutputMode {
+    /// Resolve a stored `output_mode` value; missing or unknown values insert as before
+    pub fn resolve(value: Option<&str>) -> Self {
+        match value {
+            Some("clipboard_only") => Self::ClipboardOnly,
+            Some("both") => Self::Both,
+            Some("insert") | None => Self::Insert,
+            Some(other) => {
+                log::warn!("Unknown output mode '{}', using insert", other);
+                Self::Insert
+            }
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Insert => "insert",
+            Self::ClipboardOnly => "clipboard_only",
+            Self::Both => "both",
+        }
+    }
+
+    /// Whether the text is inserted at the cursor
+    pub fn inserts(&self) -> bool {
+        !matches!(self, Self::ClipboardOnly)
+    }
+
+    /// Whether the text ends up on the clipboard
+    pub fn copies(&self) -> bool {
+        !matches!(self, Self::Insert)
+    }
+
+    /// The global mode, unless the matched app rule overrides it
+    pub(crate) fn for_rule(global: Self, rule: Option<&AppInsertionRule>) -> Self {
+        rule.and_then(|rule| rule.output_mode.as_deref())
+            .map(|value| Self::resolve(Some(value)))
+            .unwrap_or(global)
+    }
+}
+
+/// Text-only clipboard access, abstracted so the save/restore logic can be tested
+pub(crate) trait ClipboardAccess {
+    fn read_text(&mut self) -> Result<String, String>;
+    fn write_text(&mut self, text: &str) -> Result<(), String>;
+}
+
+impl ClipboardAccess for Clipboard {
+    fn read_text(&mut self) -> Result<String, String> {
+        self.get_text().map_err(|e| e.to_string())
+    }
+
+    fn write_text(&mut self, text: &str) -> Result<(), String> {
+        self.set_text(text).map_err(|e| e.to_string())
+    }
+}
+
+/// Formatting applied to inserted text when the frontmost app matches `app_match`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppInsertionRule {
+    /// Case-insensitive app name; exact matches beat substring matches, `*` matches any app
+    pub app_match: String,
+    pub trailing_space: bool,
+    /// `Some(true)` uppercases the first letter, `Some(false)` lowercases it
+    pub capitalize_first: Option<bool>,
+    pub strip_newlines: bool,
+    /// Overrides the global `output_mode` for this app
+    pub output_mode: Option<String>,
+}
+
+/// Pick the rule for `app_name`: an exact name match wins, then the longest substring
+/// match, then a `*` wildcard. Ties go to the rule listed first.
+pub(crate) fn match_insertion_rule<'a>(
+    rules: &'a [AppInsertionRule],
+    app_name: Option<&str>,
+) -> Option<&'a AppInsertionRule> {
+    let app_name = app_name.map(|name| name.trim().to_lowercase());
+
+    rules
+        .iter()
+        .enumerate()
+        .filter_map(|(index, rule)| {
+            let pattern = rule.app_match.trim().to_lowercase();
+            let score = if pattern == "*" {
+                0
+            } else {
+                let app_name = app_name.as_deref()?;
+                if pattern.is_empty() {
+                    return None;
+                } else if app_name == pattern {
+                    usize::MAX
+                } else if app_name.contains(&pattern) {
+                    pattern.len()
+                } else {
+                    return None;
+                }
+            };
+            Some((score, index, rule))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+        .map(|(_, _, rule)| rule)
+}
+
+/// Apply a rule's transformations: newline stripping, then first-letter case, then trailing space
+pub(crate) fn apply_insertion_rule(text: &str, rule: &AppInsertionRule) -> String {
+    let mut result = if rule.strip_newlines {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        text.to_string()
+    };
+
+    if let Some(uppercase) = rule.capitalize_first {
+        if let Some(first) = result.chars().next() {
+            let rest = &result[first.len_utf8()..];
+            let first: String = if uppercase {
+                first.to_uppercase().collect()
+            } else {
+                first.to_lowercase().collect()
+            };
+            result = first + rest;
+        }
+    }
+
+    if rule.trailing_space && !result.is_empty() && !result.ends_with(char::is_whitespace) {
+        result.push(' ');
+    }
+
+    result
+}
+
 #[tauri::command]
 pub async fn insert_text(app: tauri::AppHandle, text: String) -> Result<(), String> {
+    insert_text_inner(app, text, true).await
+}
+
+/// Insert text exactly as given, skipping per-app rules. Used for streamed fragments,
+/// where capitalization or a trailing space only make sense for the complete text.
+pub(crate) async fn insert_text_verbatim(
+    app: tauri::AppHandle,
+    text: String,
+) -> Result<(), String> {
+    insert_text_inner(app, text, false).await
+}
+
+async fn insert_text_inner(
+    app: tauri::AppHandle,
+    text: String,
+    apply_app_rules: bool,
+) -> Result<(), String> {
     // Check if already inserting text
     if IS_INSERTING.swap(true, Ordering::SeqCst) {
         log::warn!("Text insertion already in progress, skipping duplicate request");
@@ -42,31 +245,114 @@ pub async fn insert_text(app: tauri::AppHandle, text: String) -> Result<(), Stri
     #[cfg(not(target_os = "macos"))]
     let has_accessibility_permission = true;
 
-    // Move to a blocking task since clipboard operations are synchronous
-    let keep_transcription_in_clipboard = {
+    let (insertion_method, app_rules, lock_target_window) = {
         let store = app
             .store("settings")
             .map_err(|e| format!("Failed to access settings: {}", e))?;
-        store
-            .get("keep_transcription_in_clipboard")
+        let insertion_method = InsertionMethod::resolve(
+            store
+                .get("insertion_method")
+                .as_ref()
+                .and_then(|v| v.as_str()),
+            store
+                .get("keep_transcription_in_clipboard")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        );
+        let app_rules: Vec<AppInsertionRule> = if apply_app_rules {
+            store
+                .get("app_insertion_rules")
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let lock_target_window = store
+            .get("lock_target_window")
             .and_then(|v| v.as_bool())
-            .unwrap_or(false)
+            .unwrap_or(false);
+        (insertion_method, app_rules, lock_target_window)
     };
 
-    tokio::task::spawn_blocking(move || {
-        // Always use clipboard method for reliability and to prevent duplicate insertion
-        // This function handles both copying to clipboard and pasting at cursor
-        insert_via_clipboard(
-            text,
-            has_accessibility_permission,
-            Some(app),
-            keep_transcription_in_clipboard,
-        )
+    if lock_target_window {
+        refocus_dictation_target(&app).await;
+    }
+
+    // Only look up the frontmost app when there are rules to match against
+    let text = if app_rules.is_empty() {
+        text
+    } else {
+        let app_name = tokio::task::spawn_blocking(crate::utils::active_app::frontmost_app_name)
+            .await
+            .ok()
+            .flatten();
+        match match_insertion_rule(&app_rules, app_name.as_deref()) {
+            Some(rule) => {
+                log::debug!(
+                    "Applying insertion rule '{}' for app {:?}",
+                    rule.app_match,
+                    app_name
+                );
+                apply_insertion_rule(&text, rule)
+            }
+            None => text,
+        }
+    };
+
+    // Move to a blocking task since clipboard and keyboard operations are synchronous
+    tokio::task::spawn_blocking(move || match insertion_method {
+        InsertionMethod::Type => type_text(&text, has_accessibility_permission),
+        // The clipboard method handles both copying to clipboard and pasting at cursor
+        InsertionMethod::Paste => {
+            insert_via_clipboard(text, has_accessibility_permission, Some(app), false)
+        }
+        InsertionMethod::PastePreserveClipboard => {
+            insert_via_clipboard(text, has_accessibility_permission, Some(app), true)
+        }
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
+/// Bring the window the dictation started in back to the front when the user
+/// switched away while it was transcribing. If it's gone the text goes to the
+/// current window instead.
+async fn refocus_dictation_target(app: &tauri::AppHandle) {
+    use crate::utils::target_window::{self, InsertTarget};
+    use tauri::Manager;
+
+    let Some(target) = app
+        .state::<crate::AppState>()
+        .dictation_target
+        .lock()
+        .ok()
+        .and_then(|guard| guard.clone())
+    else {
+        return;
+    };
+
+    let refocused = tokio::task::spawn_blocking(move || {
+        let current = target_window::frontmost_window();
+        match target_window::insert_target(true, Some(&target), current.as_ref()) {
+            InsertTarget::Current => Ok(false),
+            InsertTarget::Refocus(target) => target_window::refocus(&target).map(|_| true),
+        }
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))
+    .and_then(|result| result);
+
+    match refocused {
+        Ok(true) => {
+            log::info!("Refocused the dictation target window before inserting");
+            // Give the window manager a moment to hand over keyboard focus
+            tokio::time::sleep(Duration::from_millis(150)).await;
+        }
+        Ok(false) => {}
+        Err(e) => log::warn!("{}; inserting into the current window", e),
+    }
+}
+
 /// Copy plain text to the system clipboard without attempting to paste
 #[tauri::command]
 pub async fn copy_text_to_clipboard(text: String) -> Result<(), String> {
@@ -82,48 +368,47 @@ pub async fn copy_text_to_clipboard(text: String) -> Result<(), String> {
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
+/// Output mode for the transcription being delivered now: the global setting, unless
+/// the insertion rule matching the frontmost app overrides it
+pub(crate) async fn current_output_mode(app: &tauri::AppHandle) -> OutputMode {
+    let Ok(store) = app.store("settings") else {
+        return OutputMode::Insert;
+    };
+    let global = OutputMode::resolve(store.get("output_mode").as_ref().and_then(|v| v.as_str()));
+    let rules: Vec<AppInsertionRule> = store
+        .get("app_insertion_rules")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    // Skip the frontmost app lookup when no rule could override the mode
+    if rules.iter().all(|rule| rule.output_mode.is_none()) {
+        return global;
+    }
+
+    let app_name = tokio::task::spawn_blocking(crate::utils::active_app::frontmost_app_name)
+        .await
+        .ok()
+        .flatten();
+    OutputMode::for_rule(global, match_insertion_rule(&rules, app_name.as_deref()))
+}
+
 fn insert_via_clipboard(
     text: String,
     has_accessibility_permission: bool,
     app_handle: Option<tauri::AppHandle>,
-    keep_transcription_in_clipboard: bool,
+    restore_previous_clipboard: bool,
 ) -> Result<(), String> {
     // This function handles both copying text to clipboard AND pasting it at cursor
     // Initialize clipboard
     let mut clipboard =
         Clipboard::new().map_err(|e| format!("Failed to initialize clipboard: {}", e))?;
 
-    let previous_clipboard_text = if keep_transcription_in_clipboard {
-        None
-    } else {
-        match clipboard.get_text() {
-            Ok(value) => Some(value),
-            Err(err) => {
-                log::debug!(
-                    "Could not capture previous clipboard text (likely non-text content): {}",
-                    err
-                );
-                None
-            }
-        }
-    };
-
-    let insertion_result: Result<(), String> = (|| {
-        // Set transcribed text as clipboard content
-        clipboard
-            .set_text(&text)
-            .map_err(|e| format!("Failed to set clipboard: {}", e))?;
-
+    paste_with_clipboard(&mut clipboard, &text, restore_previous_clipboard, || {
         log::info!("Set clipboard content: {}", text);
 
         // Small delay to ensure clipboard is ready
         thread::sleep(Duration::from_millis(50));
 
-        // Verify clipboard content was set
-        if let Ok(clipboard_check) = clipboard.get_text() {
-            log::info!("Clipboard content verified: {}", clipboard_check);
-        }
-
         // Check if we have accessibility permissions before attempting to paste
         if !has_accessibility_permission {
             log::warn!(
@@ -178,12 +463,44 @@ fn insert_via_clipboard(
         }
 
         Ok(())
-    })();
+    })
+}
 
-    if !keep_transcription_in_clipboard {
+/// Put `text` on the clipboard and run `paste`. When `restore_previous` is set the
+/// previous plain-text contents are captured first and put back after a successful
+/// paste; after a failed paste the transcript stays on the clipboard for manual pasting.
+pub(crate) fn paste_with_clipboard<C: ClipboardAccess>(
+    clipboard: &mut C,
+    text: &str,
+    restore_previous: bool,
+    paste: impl FnOnce() -> Result<(), String>,
+) -> Result<(), String> {
+    let previous_clipboard_text = if restore_previous {
+        match clipboard.read_text() {
+            Ok(value) => Some(value),
+            Err(err) => {
+                log::debug!(
+                    "Could not capture previous clipboard text (likely non-text content): {}",
+                    err
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Set transcribed text as clipboard content
+    clipboard
+        .write_text(text)
+        .map_err(|e| format!("Failed to set clipboard: {}", e))?;
+
+    let insertion_result = paste();
+
+    if restore_previous {
         if insertion_result.is_ok() {
             if let Some(previous_text) = previous_clipboard_text {
-                if let Err(e) = clipboard.set_text(&previous_text) {
+                if let Err(e) = clipboard.write_text(&previous_text) {
                     log::error!("Failed to restore original clipboard text: {}", e);
                 } else {
                     log::debug!("Restored original clipboard text after paste");
@@ -203,6 +520,39 @@ fn insert_via_clipboard(
     insertion_result
 }
 
+/// Type `text` into the focused application with synthesized keystrokes
+fn type_text(text: &str, has_accessibility_permission: bool) -> Result<(), String> {
+    if !has_accessibility_permission {
+        log::warn!("No accessibility permission - cannot simulate typing");
+        return Err(
+            "No accessibility permission - cannot type text. Please grant accessibility permission."
+                .to_string(),
+        );
+    }
+
+    // Same settle delay as the paste path so the pill no longer has focus
+    thread::sleep(Duration::from_millis(50));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut enigo = enigo::Enigo::new(&enigo::Settings::default())
+            .map_err(|e| format!("Failed to initialize Enigo: {:?}", e))?;
+        enigo::Keyboard::text(&mut enigo, text).map_err(|e| format!("Failed to type text: {:?}", e))
+    }));
+
+    match result {
+        Ok(result) => {
+            if result.is_ok() {
+                log::info!("Typed {} characters", text.chars().count());
+            }
+            result
+        }
+        Err(panic_err) => {
+            log::error!("PANIC during typing: {:?}", panic_err);
+            Err("Typing failed unexpectedly".to_string())
+        }
+    }
+}
+
 fn try_paste_with_applescript() -> Result<(), String> {
     // Use AppleScript on macOS
     #[cfg(target_os = "macos")]
@@ -457,3 +807,242 @@ fn paste_linux() -> Result<(), SimulateError> {
     log::debug!("Linux paste simulation completed");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// In-memory clipboard that records every write
+    #[derive(Default)]
+    struct FakeClipboard {
+        contents: Option<String>,
+        writes: Vec<String>,
+        fail_writes: bool,
+    }
+
+    impl ClipboardAccess for FakeClipboard {
+        fn read_text(&mut self) -> Result<String, String> {
+            self.contents
+                .clone()
+                .ok_or_else(|| "clipboard holds non-text content".to_string())
+        }
+
+        fn write_text(&mut self, text: &str) -> Result<(), String> {
+            if self.fail_writes {
+                return Err("clipboard unavailable".to_string());
+            }
+            self.writes.push(text.to_string());
+            self.contents = Some(text.to_string());
+            Ok(())
+        }
+    }
+
+    fn clipboard_with(text: &str) -> FakeClipboard {
+        FakeClipboard {
+            contents: Some(text.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_preserve_restores_previous_text_after_paste() {
+        let mut clipboard = clipboard_with("user data");
+        let mut pasted = false;
+
+        let result = paste_with_clipboard(&mut clipboard, "transcript", true, || {
+            pasted = true;
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert!(pasted);
+        assert_eq!(clipboard.writes, vec!["transcript", "user data"]);
+        assert_eq!(clipboard.contents.as_deref(), Some("user data"));
+    }
+
+    #[test]
+    fn test_paste_without_preserve_leaves_transcript() {
+        let mut clipboard = clipboard_with("user data");
+
+        paste_with_clipboard(&mut clipboard, "transcript", false, || Ok(())).unwrap();
+
+        assert_eq!(clipboard.writes, vec!["transcript"]);
+        assert_eq!(clipboard.contents.as_deref(), Some("transcript"));
+    }
+
+    #[test]
+    fn test_failed_paste_keeps_transcript_for_manual_paste() {
+        let mut clipboard = clipboard_with("user data");
+
+        let result = paste_with_clipboard(&mut clipboard, "transcript", true, || {
+            Err("No accessibility permission".to_string())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(clipboard.contents.as_deref(), Some("transcript"));
+    }
+
+    #[test]
+    fn test_non_text_clipboard_is_left_alone() {
+        let mut clipboard = FakeClipboard::default();
+
+        paste_with_clipboard(&mut clipboard, "transcript", true, || Ok(())).unwrap();
+
+        assert_eq!(clipboard.writes, vec!["transcript"]);
+    }
+
+    #[test]
+    fn test_clipboard_write_failure_skips_paste() {
+        let mut clipboard = FakeClipboard {
+            fail_writes: true,
+            ..clipboard_with("user data")
+        };
+        let mut pasted = false;
+
+        let result = paste_with_clipboard(&mut clipboard, "transcript", true, || {
+            pasted = true;
+            Ok(())
+        });
+
+        assert!(result.is_err());
+        assert!(!pasted);
+        assert_eq!(clipboard.contents.as_deref(), Some("user data"));
+    }
+
+    fn rule(app_match: &str) -> AppInsertionRule {
+        AppInsertionRule {
+            app_match: app_match.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_insertion_rule_precedence() {
+        let rules = vec![rule("*"), rule("term"), rule("Terminal"), rule("iterm")];
+
+        // Exact match beats substring and wildcard
+        let matched = match_insertion_rule(&rules, Some("terminal")).unwrap();
+        assert_eq!(matched.app_match, "Terminal");
+
+        // Longest substring wins
+        let matched = match_insertion_rule(&rules, Some("iTerm2")).unwrap();
+        assert_eq!(matched.app_match, "iterm");
+
+        // Wildcard is the fallback, also when the app can't be detected
+        let matched = match_insertion_rule(&rules, Some("Slack")).unwrap();
+        assert_eq!(matched.app_match, "*");
+        let matched = match_insertion_rule(&rules, None).unwrap();
+        assert_eq!(matched.app_match, "*");
+
+        // No wildcard means global defaults apply
+        assert!(match_insertion_rule(&rules[1..], Some("Slack")).is_none());
+        assert!(match_insertion_rule(&[rule("")], Some("Slack")).is_none());
+
+        // Equal scores go to the first rule
+        let rules = vec![
+            AppInsertionRule {
+                trailing_space: true,
+                ..rule("slack")
+            },
+            rule("SLACK"),
+        ];
+        let matched = match_insertion_rule(&rules, Some("Slack")).unwrap();
+        assert!(matched.trailing_space);
+    }
+
+    #[test]
+    fn test_apply_insertion_rule() {
+        let sample = "Hello world.\nSecond line.\n";
+
+        // Default rule leaves text untouched
+        assert_eq!(apply_insertion_rule(sample, &rule("*")), sample);
+
+        let terminal = AppInsertionRule {
+            strip_newlines: true,
+            capitalize_first: Some(false),
+            ..rule("Terminal")
+        };
+        assert_eq!(
+            apply_insertion_rule(sample, &terminal),
+            "hello world. Second line."
+        );
+
+        let chat = AppInsertionRule {
+            trailing_space: true,
+            capitalize_first: Some(true),
+            ..rule("Slack")
+        };
+        assert_eq!(apply_insertion_rule("élan vital", &chat), "Élan vital ");
+        // Never doubles existing trailing whitespace
+        assert_eq!(apply_insertion_rule("Done ", &chat), "Done ");
+        assert_eq!(apply_insertion_rule("", &chat), "");
+    }
+
+    #[test]
+    fn test_insertion_method_resolution() {
+        assert_eq!(
+            InsertionMethod::resolve(Some("type"), false),
+            InsertionMethod::Type
+        );
+        assert_eq!(
+            InsertionMethod::resolve(Some("paste"), false),
+            InsertionMethod::Paste
+        );
+        // Explicit setting wins over the legacy flag
+        assert_eq!(
+            InsertionMethod::resolve(Some("paste_preserve_clipboard"), true),
+            InsertionMethod::PastePreserveClipboard
+        );
+        // Legacy stores without the setting
+        assert_eq!(InsertionMethod::resolve(None, true), InsertionMethod::Paste);
+        assert_eq!(
+            InsertionMethod::resolve(None, false),
+            InsertionMethod::PastePreserveClipboard
+        );
+        assert_eq!(
+            InsertionMethod::resolve(Some("bogus"), false),
+            InsertionMethod::PastePreserveClipboard
+        );
+    }
+
+    #[test]
+    fn test_output_mode_selection() {
+        for (value, mode, inserts, copies) in [
+            ("insert", OutputMode::Insert, true, false),
+            ("clipboard_only", OutputMode::ClipboardOnly, false, true),
+            ("both", OutputMode::Both, true, true),
+        ] {
+            let resolved = OutputMode::resolve(Some(value));
+            assert_eq!(resolved, mode);
+            assert_eq!(resolved.as_str(), value);
+            assert_eq!(resolved.inserts(), inserts, "{}", value);
+            assert_eq!(resolved.copies(), copies, "{}", value);
+        }
+
+        // Stores without the setting, or with a bad value, keep inserting
+        assert_eq!(OutputMode::resolve(None), OutputMode::Insert);
+        assert_eq!(OutputMode::resolve(Some("bogus")), OutputMode::Insert);
+    }
+
+    #[test]
+    fn test_output_mode_app_override() {
+        let terminal = AppInsertionRule {
+            output_mode: Some("clipboard_only".to_string()),
+            ..rule("Terminal")
+        };
+
+        assert_eq!(
+            OutputMode::for_rule(OutputMode::Insert, Some(&terminal)),
+            OutputMode::ClipboardOnly
+        );
+        // Rules without an output mode, or no matching rule, use the global mode
+        assert_eq!(
+            OutputMode::for_rule(OutputMode::Both, Some(&rule("Slack"))),
+            OutputMode::Both
+        );
+        assert_eq!(
+            OutputMode::for_rule(OutputMode::ClipboardOnly, None),
+            OutputMode::ClipboardOnly
+        );
+    }
+}
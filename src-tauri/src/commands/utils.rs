@@ -1,5 +1,5 @@
+use crate::utils::history_crypto;
 use tauri::AppHandle;
-use tauri_plugin_store::StoreExt;
 
 #[tauri::command]
 pub async fn export_transcriptions(app: AppHandle) -> Result<String, String> {
@@ -7,17 +7,8 @@ pub async fn export_transcriptions(app: AppHandle) -> Result<String, String> {
 
     log::info!("Exporting transcriptions to JSON");
 
-    // Get transcription history from the store
-    let store = app.store("transcriptions").map_err(|e| e.to_string())?;
-
-    let mut entries: Vec<(String, serde_json::Value)> = Vec::new();
-
-    // Collect all entries with their timestamps
-    for key in store.keys() {
-        if let Some(value) = store.get(&key) {
-            entries.push((key.to_string(), value));
-        }
-    }
+    // Get transcription history from the store, decrypted if history encryption is on
+    let mut entries = history_crypto::read_history_entries(&app)?;
 
     // Sort by timestamp (newest first)
     entries.sort_by(|a, b| b.0.cmp(&a.0));
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tauri::AppHandle;
 use tauri::Manager;
 use tokio::process::Command;
@@ -96,6 +97,60 @@ fn resolve_binary(app: &AppHandle, names: &[&str], label: &str) -> Result<PathBu
     ))
 }
 
+/// Reported when the bundled ffmpeg can't be found or started, as opposed to a
+/// conversion that failed
+pub const FFMPEG_MISSING_ERROR: &str =
+    "The ffmpeg component is missing or can't run. Please reinstall VoiceTypr.";
+
+/// Whether `error` came from the ffmpeg pre-flight check rather than a conversion
+pub fn is_ffmpeg_missing(error: &str) -> bool {
+    error == FFMPEG_MISSING_ERROR
+}
+
+/// Check that `bin` starts and answers `-version`
+fn ffmpeg_runs(bin: &Path) -> Result<(), String> {
+    let mut cmd = std::process::Command::new(bin);
+    cmd.arg("-version")
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    #[cfg(target_os = "windows")]
+    {
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    let status = cmd
+        .status()
+        .map_err(|e| format!("Failed to spawn '{}': {}", bin.display(), e))?;
+    if !status.success() {
+        return Err(format!(
+            "'{} -version' exited with status {:?}",
+            bin.display(),
+            status.code()
+        ));
+    }
+    Ok(())
+}
+
+/// The ffmpeg pre-flight for a binary looked up as `resolved`. Whatever went
+/// wrong is logged; callers get [`FFMPEG_MISSING_ERROR`].
+pub fn preflight_ffmpeg(resolved: Result<PathBuf, String>) -> Result<PathBuf, String> {
+    resolved
+        .and_then(|bin| ffmpeg_runs(&bin).map(|_| bin))
+        .map_err(|e| {
+            log::error!("ffmpeg pre-flight failed: {}", e);
+            FFMPEG_MISSING_ERROR.to_string()
+        })
+}
+
+/// Make sure ffmpeg can run before converting anything. Checked once per session.
+fn ensure_ffmpeg(app: &AppHandle) -> Result<(), String> {
+    static FFMPEG: OnceLock<Result<PathBuf, String>> = OnceLock::new();
+    FFMPEG
+        .get_or_init(|| preflight_ffmpeg(resolve_binary(app, FFMPEG_CANDIDATES, "ffmpeg")))
+        .as_ref()
+        .map(|_| ())
+        .map_err(Clone::clone)
+}
+
 async fn run_ffmpeg_command(
     app: &AppHandle,
     candidates: &[&str],
@@ -126,6 +181,32 @@ async fn run_ffmpeg_command(
     Ok(())
 }
 
+/// Run ffmpeg and return what it printed to stderr (where filters like `loudnorm` report)
+async fn run_ffmpeg_capture_stderr(app: &AppHandle, args: &[String]) -> Result<String, String> {
+    let bin = resolve_binary(app, FFMPEG_CANDIDATES, "ffmpeg")?;
+    log::debug!("Running ffmpeg from {} with args {:?}", bin.display(), args);
+    let mut cmd = Command::new(&bin);
+    cmd.args(args);
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = cmd
+        .output()
+        .await
+        .map_err(|e| format!("Failed to spawn '{}': {}", bin.display(), e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    if !output.status.success() {
+        return Err(format!(
+            "ffmpeg exited with status {:?}, stderr: {}",
+            output.status.code(),
+            stderr
+        ));
+    }
+    Ok(stderr)
+}
+
 async fn run_ffprobe_capture(app: &AppHandle, args: &[String]) -> Result<Vec<u8>, String> {
     let bin = resolve_binary(app, FFPROBE_CANDIDATES, "ffprobe")?;
     log::debug!(
@@ -168,9 +249,237 @@ pub async fn probe_json(app: &AppHandle, input: &Path) -> Result<serde_json::Val
     serde_json::from_slice(&out).map_err(|e| format!("Failed to parse ffprobe json: {}", e))
 }
 
-pub async fn to_wav_streaming(app: &AppHandle, input: &Path, output: &Path) -> Result<(), String> {
-    // ffmpeg -y -loglevel error -vn -sn -i input -ac 1 -ar 16000 -sample_fmt s16 output
-    let args: Vec<String> = vec![
+/// Settings store key choosing how stereo recordings become mono
+pub const STEREO_DOWNMIX_KEY: &str = "stereo_downmix";
+
+/// How multi-channel input is reduced to mono during normalization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StereoDownmix {
+    /// Average all channels (ffmpeg's own `-ac 1` downmix)
+    #[default]
+    Mix,
+    /// Keep only the first channel, for interfaces that put the mic on the left
+    Left,
+    /// Keep only the second channel
+    Right,
+}
+
+impl StereoDownmix {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "mix" => Ok(Self::Mix),
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            other => Err(format!(
+                "Invalid stereo downmix '{}': expected mix, left or right",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mix => "mix",
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+
+    /// The `-af` filter selecting one channel, if any. Mono input and `Mix`
+    /// need none; `channels` is `None` when the input couldn't be inspected.
+    pub fn filter(&self, channels: Option<u16>) -> Option<&'static str> {
+        if channels.is_some_and(|c| c < 2) {
+            return None;
+        }
+        match self {
+            Self::Mix => None,
+            Self::Left => Some("pan=mono|c0=c0"),
+            Self::Right => Some("pan=mono|c0=c1"),
+        }
+    }
+}
+
+/// The downmix choice from settings; unknown values fall back to `Mix`
+pub fn stereo_downmix_setting(app: &AppHandle) -> StereoDownmix {
+    use tauri_plugin_store::StoreExt;
+    app.store("settings")
+        .ok()
+        .and_then(|store| store.get(STEREO_DOWNMIX_KEY))
+        .and_then(|v| v.as_str().and_then(|s| StereoDownmix::parse(s).ok()))
+        .unwrap_or_default()
+}
+
+/// Settings store keys for loudness normalization
+pub const LOUDNESS_NORMALIZE_KEY: &str = "loudness_normalize";
+pub const LOUDNESS_TARGET_LUFS_KEY: &str = "loudness_target_lufs";
+pub const LOUDNESS_NORMALIZE_CLOUD_KEY: &str = "loudness_normalize_cloud";
+
+/// Integrated loudness target when the setting is missing
+pub const DEFAULT_LOUDNESS_TARGET_LUFS: f32 = -16.0;
+/// Range `loudnorm` accepts for its integrated loudness target
+const LOUDNESS_TARGET_RANGE: std::ops::RangeInclusive<f32> = -70.0..=-5.0;
+const LOUDNESS_TRUE_PEAK: f32 = -1.5;
+const LOUDNESS_RANGE: f32 = 11.0;
+
+/// EBU R128 loudness normalization applied during `normalize_streaming`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoudnessMode {
+    #[default]
+    Off,
+    /// Single `loudnorm` pass; quick, but adjusts gain dynamically
+    Fast,
+    /// Measure first, then apply one linear gain from the measurement
+    Accurate,
+}
+
+impl LoudnessMode {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "off" => Ok(Self::Off),
+            "fast" => Ok(Self::Fast),
+            "accurate" => Ok(Self::Accurate),
+            other => Err(format!(
+                "Invalid loudness normalization '{}': expected off, fast or accurate",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Fast => "fast",
+            Self::Accurate => "accurate",
+        }
+    }
+}
+
+/// Check a LUFS target is one `loudnorm` accepts
+pub fn validate_loudness_target(lufs: f32) -> Result<(), String> {
+    if !LOUDNESS_TARGET_RANGE.contains(&lufs) {
+        return Err(format!(
+            "Loudness target {} LUFS is out of range ({} to {})",
+            lufs,
+            LOUDNESS_TARGET_RANGE.start(),
+            LOUDNESS_TARGET_RANGE.end()
+        ));
+    }
+    Ok(())
+}
+
+/// What the first `loudnorm` pass measured, as printed in its JSON summary
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct LoudnessMeasurement {
+    pub input_i: String,
+    pub input_tp: String,
+    pub input_lra: String,
+    pub input_thresh: String,
+    pub target_offset: String,
+}
+
+/// The `loudnorm` filter for `target_lufs`. With a measurement from a first pass
+/// it becomes the second, linear pass of two-pass normalization.
+pub fn loudnorm_filter(target_lufs: f32, measured: Option<&LoudnessMeasurement>) -> String {
+    let mut filter = format!(
+        "loudnorm=I={:.1}:TP={:.1}:LRA={:.1}",
+        target_lufs, LOUDNESS_TRUE_PEAK, LOUDNESS_RANGE
+    );
+    if let Some(m) = measured {
+        filter.push_str(&format!(
+            ":measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}:offset={}:linear=true",
+            m.input_i, m.input_tp, m.input_lra, m.input_thresh, m.target_offset
+        ));
+    }
+    filter
+}
+
+/// Arguments for the measuring pass of two-pass normalization. Nothing is
+/// written; `loudnorm` prints its JSON summary to stderr.
+pub fn loudness_analysis_args(
+    input: &Path,
+    pre_filter: Option<&str>,
+    target_lufs: f32,
+) -> Vec<String> {
+    let filter = join_filters(&[
+        pre_filter.map(str::to_string),
+        Some(format!(
+            "{}:print_format=json",
+            loudnorm_filter(target_lufs, None)
+        )),
+    ]);
+    vec![
+        "-hide_banner".into(),
+        "-nostats".into(),
+        "-vn".into(),
+        "-sn".into(),
+        "-i".into(),
+        input.to_string_lossy().to_string(),
+        "-af".into(),
+        filter.unwrap_or_default(),
+        "-f".into(),
+        "null".into(),
+        "-".into(),
+    ]
+}
+
+/// Pull the JSON summary `loudnorm` prints at the end of a measuring pass
+pub fn parse_loudness_measurement(stderr: &str) -> Result<LoudnessMeasurement, String> {
+    let start = stderr
+        .rfind('{')
+        .ok_or_else(|| "No loudnorm summary in ffmpeg output".to_string())?;
+    let end = stderr[start..]
+        .find('}')
+        .map(|i| start + i + 1)
+        .ok_or_else(|| "Truncated loudnorm summary in ffmpeg output".to_string())?;
+    serde_json::from_str(&stderr[start..end])
+        .map_err(|e| format!("Failed to parse loudnorm summary: {}", e))
+}
+
+/// Join audio filters into one `-af` chain, skipping the ones not in use
+fn join_filters(filters: &[Option<String>]) -> Option<String> {
+    let chain: Vec<&str> = filters.iter().flatten().map(String::as_str).collect();
+    if chain.is_empty() {
+        None
+    } else {
+        Some(chain.join(","))
+    }
+}
+
+/// The loudness mode and LUFS target from settings; invalid values turn it off
+pub fn loudness_setting(app: &AppHandle) -> (LoudnessMode, f32) {
+    use tauri_plugin_store::StoreExt;
+    let Ok(store) = app.store("settings") else {
+        return (LoudnessMode::Off, DEFAULT_LOUDNESS_TARGET_LUFS);
+    };
+    let mode = store
+        .get(LOUDNESS_NORMALIZE_KEY)
+        .and_then(|v| v.as_str().and_then(|s| LoudnessMode::parse(s).ok()))
+        .unwrap_or_default();
+    let target = store
+        .get(LOUDNESS_TARGET_LUFS_KEY)
+        .and_then(|v| v.as_f64().map(|n| n as f32))
+        .filter(|lufs| validate_loudness_target(*lufs).is_ok())
+        .unwrap_or(DEFAULT_LOUDNESS_TARGET_LUFS);
+    (mode, target)
+}
+
+/// Whether audio sent to cloud engines, which skip normalization, should be
+/// loudness-normalized anyway
+pub fn loudness_for_cloud_enabled(app: &AppHandle) -> bool {
+    use tauri_plugin_store::StoreExt;
+    let enabled = app
+        .store("settings")
+        .ok()
+        .and_then(|store| store.get(LOUDNESS_NORMALIZE_CLOUD_KEY))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    enabled && loudness_setting(app).0 != LoudnessMode::Off
+}
+
+/// Arguments converting `input` to 16 kHz mono s16 WAV, with an optional audio filter
+pub fn wav_conversion_args(input: &Path, output: &Path, filter: Option<&str>) -> Vec<String> {
+    // ffmpeg -y -loglevel error -vn -sn -i input [-af filter] -ac 1 -ar 16000 -sample_fmt s16 output
+    let mut args: Vec<String> = vec![
         "-y".into(),
         "-loglevel".into(),
         "error".into(),
@@ -179,6 +488,12 @@ pub async fn to_wav_streaming(app: &AppHandle, input: &Path, output: &Path) -> R
         "-sn".into(),
         "-i".into(),
         input.to_string_lossy().to_string(),
+    ];
+    if let Some(filter) = filter {
+        args.push("-af".into());
+        args.push(filter.into());
+    }
+    args.extend([
         "-ac".into(),
         "1".into(),
         "-ar".into(),
@@ -186,7 +501,62 @@ pub async fn to_wav_streaming(app: &AppHandle, input: &Path, output: &Path) -> R
         "-sample_fmt".into(),
         "s16".into(),
         output.to_string_lossy().to_string(),
-    ];
+    ]);
+    args
+}
+
+/// Whether audio in `spec` meets the Whisper input contract: 16 kHz mono s16
+pub fn is_whisper_conformant(spec: &hound::WavSpec) -> bool {
+    spec.channels == 1
+        && spec.sample_rate == 16_000
+        && spec.bits_per_sample == 16
+        && spec.sample_format == hound::SampleFormat::Int
+}
+
+/// Whether `input` is already what normalization produces: 16 kHz mono s16 WAV
+pub fn is_normalized_wav(input: &Path) -> bool {
+    hound::WavReader::open(input).is_ok_and(|reader| is_whisper_conformant(&reader.spec()))
+}
+
+/// Whether a finished capture can be transcribed as is, without even the WAV
+/// rewrite: conformant, and its header's sample count is non-zero and fits in
+/// the file, so a header left unfinalized still goes through ffmpeg
+pub fn is_transcription_ready_wav(input: &Path) -> bool {
+    let Ok(file_len) = std::fs::metadata(input).map(|m| m.len()) else {
+        return false;
+    };
+    hound::WavReader::open(input).is_ok_and(|reader| {
+        let data_len = reader.len() as u64 * 2;
+        is_whisper_conformant(&reader.spec()) && data_len > 0 && data_len < file_len
+    })
+}
+
+/// Whether normalization can be skipped for a capture: it is transcription-ready
+/// and no loudness normalization is asked for
+pub fn can_skip_normalization(app: &AppHandle, input: &Path) -> bool {
+    loudness_setting(app).0 == LoudnessMode::Off && is_transcription_ready_wav(input)
+}
+
+/// Copy a WAV that is already 16 kHz mono s16 without ffmpeg. Rewriting it
+/// rather than copying the file leaves a plain header with the sizes filled in.
+pub fn rewrite_wav(input: &Path, output: &Path) -> Result<(), String> {
+    let mut reader =
+        hound::WavReader::open(input).map_err(|e| format!("Failed to open wav: {}", e))?;
+    let mut writer = hound::WavWriter::create(output, reader.spec())
+        .map_err(|e| format!("Failed to create wav: {}", e))?;
+    for sample in reader.samples::<i16>() {
+        let sample = sample.map_err(|e| format!("Failed to read wav: {}", e))?;
+        writer
+            .write_sample(sample)
+            .map_err(|e| format!("Failed to write wav: {}", e))?;
+    }
+    writer
+        .finalize()
+        .map_err(|e| format!("Failed to finish wav: {}", e))
+}
+
+pub async fn to_wav_streaming(app: &AppHandle, input: &Path, output: &Path) -> Result<(), String> {
+    let args = wav_conversion_args(input, output, None);
     run_ffmpeg_command(app, FFMPEG_CANDIDATES, &args, "ffmpeg").await
 }
 
@@ -195,8 +565,91 @@ pub async fn normalize_streaming(
     input: &Path,
     output: &Path,
 ) -> Result<(), String> {
-    // For now, same as to_wav_streaming. Two-pass loudness can be added later.
-    to_wav_streaming(app, input, output).await
+    let downmix = stereo_downmix_setting(app);
+    let channels = hound::WavReader::open(input)
+        .ok()
+        .map(|reader| reader.spec().channels);
+    let downmix_filter = downmix.filter(channels);
+    let (loudness, target_lufs) = loudness_setting(app);
+    log::info!(
+        "Normalizing {:?} ({:?} channels) with stereo downmix '{}', loudness '{}'",
+        input.file_name().unwrap_or_default(),
+        channels,
+        downmix.as_str(),
+        loudness.as_str()
+    );
+
+    // Nothing for ffmpeg to do when the audio is already in shape
+    if loudness == LoudnessMode::Off && is_normalized_wav(input) {
+        match rewrite_wav(input, output) {
+            Ok(()) => {
+                log::info!("Input is already 16 kHz mono, skipped ffmpeg");
+                return Ok(());
+            }
+            Err(e) => log::warn!("WAV fast path failed, using ffmpeg: {}", e),
+        }
+    }
+    ensure_ffmpeg(app)?;
+
+    let loudness_filter = match loudness {
+        LoudnessMode::Off => None,
+        LoudnessMode::Fast => Some(loudnorm_filter(target_lufs, None)),
+        LoudnessMode::Accurate => {
+            let args = loudness_analysis_args(input, downmix_filter, target_lufs);
+            let measured = run_ffmpeg_capture_stderr(app, &args)
+                .await
+                .and_then(|stderr| parse_loudness_measurement(&stderr));
+            match measured {
+                Ok(measured) => {
+                    log::info!(
+                        "Measured loudness {} LUFS, true peak {} dBTP",
+                        measured.input_i,
+                        measured.input_tp
+                    );
+                    Some(loudnorm_filter(target_lufs, Some(&measured)))
+                }
+                Err(e) => {
+                    log::warn!("Loudness measurement failed, using a single pass: {}", e);
+                    Some(loudnorm_filter(target_lufs, None))
+                }
+            }
+        }
+    };
+
+    let filter = join_filters(&[downmix_filter.map(str::to_string), loudness_filter]);
+    let args = wav_conversion_args(input, output, filter.as_deref());
+    run_ffmpeg_command(app, FFMPEG_CANDIDATES, &args, "ffmpeg").await
+}
+
+/// Level below which audio counts as silence when trimming saved recordings
+const SILENCE_TRIM_THRESHOLD_DB: i32 = -50;
+/// Silence kept before the first and after the last sound, in seconds
+const SILENCE_TRIM_MARGIN_SECS: f32 = 0.3;
+
+/// Arguments trimming leading and trailing silence from `input`, keeping a short
+/// margin. Trailing silence is trimmed by reversing, trimming the start and
+/// reversing back, so pauses between phrases stay intact.
+pub fn trim_silence_args(input: &Path, output: &Path) -> Vec<String> {
+    let trim_start = format!(
+        "silenceremove=start_periods=1:start_threshold={}dB:start_silence={:.2}",
+        SILENCE_TRIM_THRESHOLD_DB, SILENCE_TRIM_MARGIN_SECS
+    );
+    vec![
+        "-y".into(),
+        "-loglevel".into(),
+        "error".into(),
+        "-hide_banner".into(),
+        "-i".into(),
+        input.to_string_lossy().to_string(),
+        "-af".into(),
+        format!("{0},areverse,{0},areverse", trim_start),
+        output.to_string_lossy().to_string(),
+    ]
+}
+
+pub async fn trim_silence(app: &AppHandle, input: &Path, output: &Path) -> Result<(), String> {
+    let args = trim_silence_args(input, output);
+    run_ffmpeg_command(app, FFMPEG_CANDIDATES, &args, "ffmpeg").await
 }
 
 pub async fn segment(
@@ -224,3 +677,244 @@ pub async fn segment(
     ];
     run_ffmpeg_command(app, FFMPEG_CANDIDATES, &args, "ffmpeg").await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter_in(args: &[String]) -> Option<&str> {
+        args.iter()
+            .position(|arg| arg == "-af")
+            .map(|i| args[i + 1].as_str())
+    }
+
+    #[test]
+    fn test_stereo_downmix_filters() {
+        let input = Path::new("/tmp/in.wav");
+        let output = Path::new("/tmp/out.wav");
+        let cases = [
+            ("mix", None),
+            ("left", Some("pan=mono|c0=c0")),
+            ("right", Some("pan=mono|c0=c1")),
+        ];
+        for (setting, expected) in cases {
+            let downmix = StereoDownmix::parse(setting).unwrap();
+            assert_eq!(downmix.as_str(), setting);
+
+            let args = wav_conversion_args(input, output, downmix.filter(Some(2)));
+            assert_eq!(filter_in(&args), expected, "stereo_downmix = {}", setting);
+            // Still converted to 16 kHz mono either way
+            let ac = args.iter().position(|arg| arg == "-ac").unwrap();
+            assert_eq!(args[ac + 1], "1");
+            // Input options come before the filter, the output path last
+            assert!(args.iter().position(|arg| arg == "-i").unwrap() < ac);
+            assert_eq!(args.last().unwrap(), "/tmp/out.wav");
+        }
+    }
+
+    #[test]
+    fn test_mono_input_gets_no_channel_filter() {
+        assert_eq!(StereoDownmix::Right.filter(Some(1)), None);
+        assert_eq!(StereoDownmix::Left.filter(Some(1)), None);
+        // Unknown channel count still honours the choice
+        assert_eq!(StereoDownmix::Left.filter(None), Some("pan=mono|c0=c0"));
+    }
+
+    const LOUDNORM_SUMMARY: &str = r#"[Parsed_loudnorm_0 @ 0x600000e04000]
+{
+	"input_i" : "-27.61",
+	"input_tp" : "-4.47",
+	"input_lra" : "18.06",
+	"input_thresh" : "-39.20",
+	"output_i" : "-16.58",
+	"output_tp" : "-1.50",
+	"output_lra" : "14.78",
+	"output_thresh" : "-27.71",
+	"normalization_type" : "dynamic",
+	"target_offset" : "0.58"
+}
+"#;
+
+    #[test]
+    fn test_fast_loudness_filter() {
+        assert_eq!(
+            loudnorm_filter(-16.0, None),
+            "loudnorm=I=-16.0:TP=-1.5:LRA=11.0"
+        );
+        let chain = join_filters(&[
+            StereoDownmix::Left.filter(Some(2)).map(str::to_string),
+            Some(loudnorm_filter(-23.0, None)),
+        ]);
+        let args = wav_conversion_args(
+            Path::new("/tmp/in.wav"),
+            Path::new("/tmp/out.wav"),
+            chain.as_deref(),
+        );
+        assert_eq!(
+            filter_in(&args),
+            Some("pan=mono|c0=c0,loudnorm=I=-23.0:TP=-1.5:LRA=11.0")
+        );
+    }
+
+    #[test]
+    fn test_accurate_loudness_runs_two_passes() {
+        let input = Path::new("/tmp/in.wav");
+
+        // First pass only measures and prints the JSON summary
+        let analysis = loudness_analysis_args(input, Some("pan=mono|c0=c1"), -16.0);
+        assert_eq!(
+            filter_in(&analysis),
+            Some("pan=mono|c0=c1,loudnorm=I=-16.0:TP=-1.5:LRA=11.0:print_format=json")
+        );
+        assert_eq!(&analysis[analysis.len() - 3..], ["-f", "null", "-"]);
+        assert!(!analysis.iter().any(|arg| arg == "error"));
+
+        // Second pass feeds the measurement back for a linear gain
+        let measured = parse_loudness_measurement(LOUDNORM_SUMMARY).unwrap();
+        assert_eq!(measured.input_i, "-27.61");
+        assert_eq!(measured.target_offset, "0.58");
+        assert_eq!(
+            loudnorm_filter(-16.0, Some(&measured)),
+            "loudnorm=I=-16.0:TP=-1.5:LRA=11.0:measured_I=-27.61:measured_TP=-4.47\
+             :measured_LRA=18.06:measured_thresh=-39.20:offset=0.58:linear=true"
+        );
+
+        assert!(parse_loudness_measurement("ffmpeg version 7.0").is_err());
+    }
+
+    #[test]
+    fn test_loudness_settings_validation() {
+        assert_eq!(LoudnessMode::parse("off").unwrap(), LoudnessMode::Off);
+        assert_eq!(LoudnessMode::parse("fast").unwrap(), LoudnessMode::Fast);
+        assert_eq!(
+            LoudnessMode::parse("accurate").unwrap(),
+            LoudnessMode::Accurate
+        );
+        assert!(LoudnessMode::parse("loud").is_err());
+
+        assert!(validate_loudness_target(-16.0).is_ok());
+        assert!(validate_loudness_target(-70.0).is_ok());
+        assert!(validate_loudness_target(-4.0).is_err());
+        assert!(validate_loudness_target(-80.0).is_err());
+    }
+
+    #[test]
+    fn test_trim_silence_args() {
+        let args = trim_silence_args(Path::new("/tmp/in.wav"), Path::new("/tmp/out.wav"));
+        let trim = "silenceremove=start_periods=1:start_threshold=-50dB:start_silence=0.30";
+        assert_eq!(
+            filter_in(&args),
+            Some(format!("{0},areverse,{0},areverse", trim).as_str())
+        );
+        assert_eq!(
+            args[args.iter().position(|a| a == "-i").unwrap() + 1],
+            "/tmp/in.wav"
+        );
+        assert_eq!(args.last().unwrap(), "/tmp/out.wav");
+        // Middle pauses are kept: only the leading period is removed each pass
+        assert!(!filter_in(&args).unwrap().contains("stop_periods"));
+    }
+
+    fn write_wav(path: &Path, channels: u16, sample_rate: u32, samples: &[i16]) {
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for &sample in samples {
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn test_fast_path_only_for_16k_mono_s16() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let samples: Vec<i16> = (0..1600).map(|i| (i % 200) as i16 - 100).collect();
+
+        let ready = dir.path().join("ready.wav");
+        write_wav(&ready, 1, 16_000, &samples);
+        assert!(is_normalized_wav(&ready));
+
+        let stereo = dir.path().join("stereo.wav");
+        write_wav(&stereo, 2, 16_000, &samples);
+        assert!(!is_normalized_wav(&stereo));
+        let resample = dir.path().join("44k.wav");
+        write_wav(&resample, 1, 44_100, &samples);
+        assert!(!is_normalized_wav(&resample));
+        let not_wav = dir.path().join("clip.m4a");
+        std::fs::write(&not_wav, b"not audio").unwrap();
+        assert!(!is_normalized_wav(&not_wav));
+        assert!(!is_normalized_wav(&dir.path().join("missing.wav")));
+
+        // The rewrite keeps every sample
+        let output = dir.path().join("normalized.wav");
+        rewrite_wav(&ready, &output).unwrap();
+        let mut reader = hound::WavReader::open(&output).unwrap();
+        assert_eq!(reader.spec().sample_rate, 16_000);
+        let copied: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+        assert_eq!(copied, samples);
+    }
+
+    #[test]
+    fn test_transcription_ready_capture_detection() {
+        let spec = |channels, sample_rate, bits_per_sample, sample_format| hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample,
+            sample_format,
+        };
+        use hound::SampleFormat::{Float, Int};
+        assert!(is_whisper_conformant(&spec(1, 16_000, 16, Int)));
+        assert!(!is_whisper_conformant(&spec(2, 16_000, 16, Int)));
+        assert!(!is_whisper_conformant(&spec(1, 48_000, 16, Int)));
+        assert!(!is_whisper_conformant(&spec(1, 16_000, 24, Int)));
+        assert!(!is_whisper_conformant(&spec(1, 16_000, 32, Float)));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let samples: Vec<i16> = (0..1600).map(|i| (i % 200) as i16 - 100).collect();
+        let ready = dir.path().join("ready.wav");
+        write_wav(&ready, 1, 16_000, &samples);
+        assert!(is_transcription_ready_wav(&ready));
+
+        let stereo = dir.path().join("stereo.wav");
+        write_wav(&stereo, 2, 16_000, &samples);
+        assert!(!is_transcription_ready_wav(&stereo));
+
+        // A capture without samples still takes the normal path
+        let empty = dir.path().join("empty.wav");
+        write_wav(&empty, 1, 16_000, &[]);
+        assert!(!is_transcription_ready_wav(&empty));
+
+        // Header promising more samples than the file holds, as from a cut-off write
+        let truncated = dir.path().join("truncated.wav");
+        let bytes = std::fs::read(&ready).unwrap();
+        std::fs::write(&truncated, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(!is_transcription_ready_wav(&truncated));
+
+        assert!(!is_transcription_ready_wav(&dir.path().join("missing.wav")));
+    }
+
+    #[test]
+    fn test_missing_sidecar_has_its_own_error() {
+        let not_found = preflight_ffmpeg(Err("ffmpeg binary not found. Searched: ".into()));
+        assert_eq!(not_found, Err(FFMPEG_MISSING_ERROR.to_string()));
+
+        // Found but not runnable, e.g. deleted by antivirus after the lookup
+        let dir = tempfile::TempDir::new().unwrap();
+        let unrunnable = preflight_ffmpeg(Ok(dir.path().join("ffmpeg")));
+        assert_eq!(unrunnable, Err(FFMPEG_MISSING_ERROR.to_string()));
+
+        assert!(is_ffmpeg_missing(FFMPEG_MISSING_ERROR));
+        assert!(!is_ffmpeg_missing("ffmpeg exited with status Some(1)"));
+    }
+
+    #[test]
+    fn test_invalid_stereo_downmix_rejected() {
+        assert!(StereoDownmix::parse("center").is_err());
+        assert!(StereoDownmix::parse("").is_err());
+        assert_eq!(StereoDownmix::default(), StereoDownmix::Mix);
+    }
+}
@@ -19,12 +19,14 @@ mod audio;
 mod commands;
 mod ffmpeg;
 mod license;
+mod local_api;
 mod menu;
 mod parakeet;
 mod recognition;
 mod recording;
 mod secure_store;
 mod simple_cache;
+mod soniox;
 mod state;
 mod state_machine;
 mod utils;
@@ -50,29 +52,39 @@ pub fn hide_dock_icon(app: &tauri::AppHandle) {
 use audio::recorder::AudioRecorder;
 use commands::{
     ai::{
-        cache_ai_api_key, clear_ai_api_key_cache, disable_ai_enhancement, enhance_transcription,
-        get_ai_settings, get_ai_settings_for_provider, get_enhancement_options, get_openai_config,
-        set_openai_config, test_openai_endpoint, update_ai_settings, update_enhancement_options,
+        cache_ai_api_key, clear_ai_api_key_cache, cycle_enhancement_preset,
+        delete_enhancement_preset, disable_ai_enhancement, enhance_transcription,
+        get_active_enhancement_preset, get_ai_settings, get_ai_settings_for_provider,
+        get_enhancement_options, get_openai_config, list_enhancement_presets, list_ollama_models,
+        save_enhancement_preset, set_active_enhancement_preset, set_openai_config,
+        test_ai_provider_key, test_openai_endpoint, update_ai_settings, update_enhancement_options,
         validate_and_cache_api_key,
     },
     audio::*,
     clipboard::{copy_image_to_clipboard, save_image_to_file},
     debug::{debug_transcription_flow, test_transcription_event},
     device::get_device_id,
+    diagnostics::generate_diagnostics,
     keyring::{keyring_delete, keyring_get, keyring_has, keyring_set},
     license::*,
+    local_api::{get_local_api_info, regenerate_local_api_token},
     logs::{clear_old_logs, get_log_directory, open_logs_folder},
     model::{
-        cancel_download, delete_model, download_model, get_model_status, list_downloaded_models,
-        preload_model, verify_model,
+        benchmark_models, cancel_download, delete_model, download_model,
+        get_acceleration_capabilities, get_model_status, get_models_directory_usage,
+        import_whisper_model, list_downloaded_models, preload_model, recommend_model,
+        set_models_directory, verify_model,
     },
     permissions::{
         check_accessibility_permission, check_microphone_permission,
         request_accessibility_permission, request_microphone_permission,
         test_automation_permission,
     },
+    profiles::{apply_profile, delete_profile, list_profiles, save_profile},
     reset::reset_app_data,
+    self_test::self_test,
     settings::*,
+    settings_transfer::{export_settings, import_settings},
     stt::{clear_soniox_key_cache, validate_and_cache_soniox_key},
     text::*,
     utils::export_transcriptions,
@@ -89,7 +101,8 @@ pub use state::{
     update_recording_state, AppState, QueuedPillEvent, RecordingMode, RecordingState,
 };
 pub use recognition::{
-    auto_select_model_if_needed, recognition_availability_snapshot, RecognitionAvailabilitySnapshot,
+    auto_select_model_if_needed, emit_recognition_availability, recognition_availability_snapshot,
+    RecognitionAvailabilitySnapshot,
 };
 
 // Setup logging with daily rotation
@@ -296,6 +309,14 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 }
             });
 
+            // History used to live in one store; move it into monthly ones before anything reads it
+            if let Err(e) = utils::history_store::migrate_legacy_store(app.handle()) {
+                log::error!("Failed to migrate transcription history: {}", e);
+            }
+
+            // Prune old history and recordings daily when auto_cleanup is enabled
+            utils::auto_cleanup::spawn_auto_cleanup(app.handle().clone());
+
             // Set activation policy on macOS to prevent focus stealing
             #[cfg(target_os = "macos")]
             {
@@ -317,7 +338,16 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             }
 
             // Initialize whisper manager
-            let models_dir = app.path().app_data_dir()?.join("models");
+            // A directory picked with set_models_directory wins over the default
+            let models_dir = match app
+                .store("settings")
+                .ok()
+                .and_then(|store| store.get(utils::model_relocation::MODELS_DIRECTORY_KEY))
+                .and_then(|v| v.as_str().map(PathBuf::from))
+            {
+                Some(dir) => dir,
+                None => app.path().app_data_dir()?.join("models"),
+            };
             log::info!("🗂️  Models directory: {:?}", models_dir);
 
             log_start("WHISPER_MANAGER_INIT");
@@ -359,9 +389,31 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             app.manage(Arc::new(Mutex::new(HashMap::<String, Arc<AtomicBool>>::new())));
 
             // Initialize transcriber cache for keeping models in memory
-            // Cache size is 1: only the current model (1-3GB RAM)
-            // When user switches models, old one is unloaded immediately
-            app.manage(AsyncMutex::new(TranscriberCache::new()));
+            // Defaults to 1: only the current model (1-3GB RAM), so the old one is
+            // unloaded on a model switch. model_cache_capacity keeps up to 3 warm.
+            let model_cache_capacity = app
+                .store("settings")
+                .ok()
+                .and_then(|store| store.get("model_cache_capacity"))
+                .and_then(|v| v.as_u64())
+                .map_or(1, |n| (n as usize).min(whisper::cache::MAX_MODEL_CACHE_CAPACITY));
+            let use_gpu = app
+                .store("settings")
+                .ok()
+                .and_then(|store| store.get("use_gpu"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let mut transcriber_cache = TranscriberCache::with_capacity(model_cache_capacity);
+            transcriber_cache.set_use_gpu(use_gpu);
+            let fallback_handle = app.app_handle().clone();
+            transcriber_cache.set_on_load(move |transcriber| {
+                if let Some(reason) = transcriber.gpu_fallback() {
+                    log::warn!("Whisper GPU acceleration unavailable, using CPU: {}", reason);
+                    let _ = fallback_handle.emit("gpu-fallback", serde_json::json!({ "reason": reason }));
+                }
+            });
+            app.manage(AsyncMutex::new(transcriber_cache));
+            app.manage(whisper::pool::TranscriptionPool::new());
 
             // Initialize unified application state
             app.manage(AppState::new());
@@ -397,6 +449,11 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
 
             // Initialize recorder state (kept separate for backwards compatibility)
             app.manage(RecorderState(Mutex::new(AudioRecorder::new())));
+            app.manage(audio::mic_test::MicTestState(Mutex::new(
+                audio::mic_test::MicTest::default(),
+            )));
+            app.manage(audio::player::PlaybackState::default());
+            app.manage(local_api::LocalApiState::default());
 
             // Create device watcher in deferred state - will be started after mic permission granted
             // This prevents early mic permission prompts from CPAL's input_devices() enumeration
@@ -414,6 +471,11 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             display_watcher.start();
             app.manage(display_watcher);
 
+            // Poll the power source for battery-aware model switching
+            let power_watcher = utils::power::PowerWatcher::new(app.app_handle().clone());
+            power_watcher.start();
+            app.manage(power_watcher);
+
             // Create tray icon
             use tauri::tray::{TrayIconBuilder, TrayIconEvent};
 
@@ -436,7 +498,7 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
 
             let _tray = TrayIconBuilder::with_id("main")
                 .icon(tray_icon)
-                .tooltip("VoiceTypr")
+                .tooltip(menu::TRAY_TOOLTIP)
                 .menu(&menu)
                 .on_menu_event(move |app, event| {
                     log::info!("Tray menu event: {:?}", event.id);
@@ -453,6 +515,17 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                         app.exit(0);
                     } else if event_id == "check_updates" {
                         let _ = app.emit("tray-check-updates", ());
+                    } else if event_id == menu::COPY_DIAGNOSTICS_ID {
+                        let app_handle = app.app_handle().clone();
+                        tauri::async_runtime::spawn(async move {
+                            match crate::commands::diagnostics::copy_diagnostics_to_clipboard(&app_handle).await {
+                                Ok(_) => log::info!("Copied diagnostics to clipboard"),
+                                Err(e) => {
+                                    log::error!("Failed to copy diagnostics: {}", e);
+                                    let _ = app_handle.emit("tray-action-error", &format!("Failed to copy diagnostics: {}", e));
+                                }
+                            }
+                        });
                     } else if event_id.starts_with("model_") {
                         // Handle model selection
                         let model_name = match event_id.strip_prefix("model_") {
@@ -476,6 +549,19 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                                 }
                             }
                         });
+                    } else if let Some(language) = menu::parse_language_menu_id(&event_id) {
+                        let app_handle = app.app_handle().clone();
+                        tauri::async_runtime::spawn(async move {
+                            match crate::commands::settings::set_language_from_tray(app_handle.clone(), language.to_string()).await {
+                                Ok(_) => {
+                                    log::info!("Language changed from tray to: {}", language);
+                                }
+                                Err(e) => {
+                                    log::error!("Failed to set language from tray: {}", e);
+                                    let _ = app_handle.emit("tray-action-error", &format!("Failed to change language: {}", e));
+                                }
+                            }
+                        });
                     } else if event_id == "microphone_default" {
                         // Handle default microphone selection
                         let app_handle = app.app_handle().clone();
@@ -514,27 +600,47 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                             }
                         });
                     }
+                    // Clear the recent transcriptions list (clears history and refreshes the tray)
+                    else if event_id == menu::CLEAR_RECENT_ID {
+                        let app_handle = app.app_handle().clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = crate::commands::audio::clear_all_transcriptions(app_handle.clone()).await {
+                                log::error!("Failed to clear recent transcriptions: {}", e);
+                                let _ = app_handle.emit("tray-action-error", &format!("Failed to clear history: {}", e));
+                            }
+                        });
+                    }
                     // Recent transcriptions copy handler
-                    else if let Some(ts) = event_id.strip_prefix("recent_copy_") {
+                    else if let Some((ts, variant)) = menu::parse_recent_copy_id(event_id) {
                         let ts_owned = ts.to_string();
                         let app_handle = app.app_handle().clone();
                         tauri::async_runtime::spawn(async move {
-                            // Read text by timestamp and copy
-                            match app_handle.store("transcriptions") {
-                                Ok(store) => {
-                                    if let Some(val) = store.get(&ts_owned) {
-                                        if let Some(text) = val.get("text").and_then(|v| v.as_str()) {
-                                            if let Err(e) = crate::commands::text::copy_text_to_clipboard(text.to_string()).await {
-                                                log::error!("Failed to copy recent transcription: {}", e);
-                                                let _ = app_handle.emit("tray-action-error", &format!("Failed to copy: {}", e));
-                                            } else {
-                                                log::info!("Copied recent transcription to clipboard");
-                                            }
-                                        }
-                                    }
+                            // Read the chosen text by timestamp and copy
+                            let entry = utils::history_crypto::read_history_entry(&app_handle, &ts_owned);
+                            let copied = match crate::commands::audio::history_entry_text(entry.as_ref(), &ts_owned, variant) {
+                                Ok(text) => crate::commands::text::copy_text_to_clipboard(text).await,
+                                Err(e) => Err(e),
+                            };
+                            if let Err(e) = copied {
+                                log::error!("Failed to copy recent transcription: {}", e);
+                                let _ = app_handle.emit("tray-action-error", &format!("Failed to copy: {}", e));
+                            } else {
+                                log::info!("Copied recent transcription ({:?}) to clipboard", variant);
+                            }
+                        });
+                    }
+                    // Settings profile switcher
+                    else if let Some(name) = event_id.strip_prefix(menu::PROFILE_MENU_PREFIX) {
+                        let name = name.to_string();
+                        let app_handle = app.app_handle().clone();
+                        tauri::async_runtime::spawn(async move {
+                            match crate::commands::profiles::apply_profile(app_handle.clone(), name.clone()).await {
+                                Ok(_) => {
+                                    log::info!("Profile applied from tray: {}", name);
                                 }
                                 Err(e) => {
-                                    log::error!("Failed to open transcriptions store: {}", e);
+                                    log::error!("Failed to apply profile from tray: {}", e);
+                                    let _ = app_handle.emit("tray-action-error", &format!("Failed to switch profile: {}", e));
                                 }
                             }
                         });
@@ -652,6 +758,54 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 log::info!("Recording mode set to: {:?}", recording_mode);
             }
 
+            // Push-to-talk double-tap latching window
+            let double_tap_window_ms = app
+                .store("settings")
+                .ok()
+                .and_then(|store| store.get("double_tap_window_ms"))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            if let Ok(mut detector) = app_state.double_tap.lock() {
+                detector.set_window(std::time::Duration::from_millis(double_tap_window_ms));
+            }
+            let hotkey_debounce_ms = app
+                .store("settings")
+                .ok()
+                .and_then(|store| store.get("hotkey_debounce_ms"))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(recording::DEFAULT_HOTKEY_DEBOUNCE_MS as u64);
+            if let Ok(mut debouncer) = app_state.hotkey_debounce.lock() {
+                debouncer.set_interval(std::time::Duration::from_millis(hotkey_debounce_ms));
+            }
+
+            // Loopback HTTP API, when local_api_enabled
+            let local_api_handle = app.app_handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = local_api::sync_local_api(&local_api_handle).await {
+                    log::warn!("Local API not started: {}", e);
+                }
+            });
+
+            // Modifier that skips AI enhancement for one transcription
+            if let Some(value) = app
+                .store("settings")
+                .ok()
+                .and_then(|store| store.get("skip_enhancement_modifier"))
+            {
+                let modifier = value
+                    .as_str()
+                    .map(crate::recording::SkipModifier::parse)
+                    .unwrap_or(Ok(None));
+                match modifier {
+                    Ok(modifier) => {
+                        if let Ok(mut modifier_guard) = app_state.skip_enhancement_modifier.lock() {
+                            *modifier_guard = modifier;
+                        }
+                    }
+                    Err(e) => log::warn!("{}", e),
+                }
+            }
+
             // Normalize the hotkey for Tauri
             let normalized_hotkey = crate::commands::key_normalizer::normalize_shortcut_keys(&hotkey_str);
 
@@ -774,6 +928,41 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
 
+            // Register optional action shortcuts (preset cycle, raw re-insert, model switching) if configured
+            let action_hotkey = |key: &str| {
+                app.store("settings").ok().and_then(|store| {
+                    store
+                        .get(key)
+                        .and_then(|v| v.as_str().map(|s| s.to_string()))
+                })
+            };
+            let app_state = app.state::<AppState>();
+            crate::commands::settings::apply_action_hotkey(
+                app.app_handle(),
+                &app_state.preset_cycle_shortcut,
+                action_hotkey("preset_cycle_hotkey").as_deref(),
+                "Preset cycle",
+            );
+            crate::commands::settings::apply_action_hotkey(
+                app.app_handle(),
+                &app_state.raw_insert_shortcut,
+                action_hotkey("raw_insert_hotkey").as_deref(),
+                "Raw insert",
+            );
+            crate::commands::settings::apply_action_hotkey(
+                app.app_handle(),
+                &app_state.copy_last_shortcut,
+                action_hotkey("copy_last_hotkey").as_deref(),
+                "Copy last",
+            );
+            let model_hotkeys = app
+                .store("settings")
+                .ok()
+                .and_then(|store| store.get("model_hotkeys"))
+                .map(|v| crate::commands::settings::parse_model_hotkeys(&v))
+                .unwrap_or_default();
+            crate::commands::settings::apply_model_hotkeys(app.app_handle(), &model_hotkeys);
+
             // Preload current model if set (graceful degradation)
             // Use Tauri's async runtime which is available after setup
             if let Ok(store) = app.store("settings") {
@@ -820,6 +1009,11 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             {
                 use tauri::{WebviewUrl, WebviewWindowBuilder};
 
+                // Pill/toast appearance, available to their pages before the first render
+                let pill_theme_script = crate::utils::pill_theme::initialization_script(
+                    &crate::utils::pill_theme::current_pill_theme(app.app_handle()),
+                );
+
                 // Calculate center-bottom position for pill/toast
                 let (pos_x, pos_y) = {
                     let (screen_width, screen_height) = if let Ok(Some(monitor)) = app.primary_monitor() {
@@ -859,7 +1053,8 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                         .inner_size(80.0, 40.0)  // Sized for 3-dot pill (active state with padding)
                         .position(pos_x, pos_y)
                         .visible(true)  // Always visible (controlled by show_pill_indicator setting)
-                        .focused(false);  // Don't steal focus
+                        .focused(false)  // Don't steal focus
+                        .initialization_script(&pill_theme_script);
 
                     // Disable context menu only in production builds
                     #[cfg(not(debug_assertions))]
@@ -884,16 +1079,15 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
 
-                // Create toast window for feedback messages (positioned above pill) - all platforms
-                let toast_width = 400.0;
-                let toast_height = 80.0;
-                let pill_width = 80.0;
-                let gap = 8.0; // Gap between pill and toast
+                // Create toast window for feedback messages (next to the pill) - all platforms
+                use crate::utils::toast_layout::{TOAST_HEIGHT, TOAST_WIDTH};
+                let toast_width = TOAST_WIDTH;
+                let toast_height = TOAST_HEIGHT;
 
-                // Center toast above pill
-                let toast_x = pos_x + (pill_width - toast_width) / 2.0;
-                let toast_y = pos_y - toast_height - gap;
-                log::info!("Toast window position: ({}, {}) - above pill at ({}, {})", toast_x, toast_y, pos_x, pos_y);
+                // Placed where the toast_position setting says, above the pill by default
+                let toast_position = crate::utils::toast_layout::current_toast_position(app.app_handle());
+                let (toast_x, toast_y) = toast_position.origin(pos_x, pos_y);
+                log::info!("Toast window position: ({}, {}) - {:?} pill at ({}, {})", toast_x, toast_y, toast_position, pos_x, pos_y);
 
                 let toast_builder = WebviewWindowBuilder::new(app, "toast", WebviewUrl::App("toast".into()))
                     .title("Feedback")
@@ -904,7 +1098,8 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                     .transparent(true)
                     .inner_size(toast_width, toast_height)
                     .position(toast_x, toast_y)
-                    .visible(false); // Starts hidden
+                    .visible(false) // Starts hidden
+                    .initialization_script(&pill_theme_script);
 
                 #[cfg(not(debug_assertions))]
                 let toast_builder = toast_builder.initialization_script("document.addEventListener('contextmenu', e => e.preventDefault());");
@@ -993,24 +1188,46 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             start_recording,
             stop_recording,
             cancel_recording,
+            abort_all,
+            insert_last_raw_transcription,
             get_current_recording_state,
             debug_transcription_flow,
             test_transcription_event,
             save_transcription,
             get_audio_devices,
             get_current_audio_device,
+            start_mic_test,
+            stop_mic_test,
+            play_recording,
+            stop_playback,
             download_model,
             get_model_status,
+            get_models_directory_usage,
+            set_models_directory,
+            get_acceleration_capabilities,
             preload_model,
+            benchmark_models,
+            recommend_model,
+            import_whisper_model,
             verify_model,
             transcribe_audio,
             transcribe_audio_file,
+            transcribe_url,
+            transcribe_clipboard_audio,
+            transcribe_batch,
+            retranscribe_all_failed,
+            reenhance_transcription,
+            get_transcription_attachment,
+            transcribe_with_speakers,
             get_settings,
             save_settings,
             set_audio_device,
             set_global_shortcut,
+            check_hotkey_available,
             get_supported_languages,
+            get_default_redaction_patterns,
             set_model_from_tray,
+            set_language_from_tray,
             update_tray_menu,
             insert_text,
             delete_model,
@@ -1018,6 +1235,12 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             cancel_download,
             cleanup_old_transcriptions,
             get_transcription_history,
+            get_transcription_entry,
+            copy_transcription,
+            get_transcription_count,
+            get_transcription_stats,
+            get_analytics,
+            get_productivity_report,
             delete_transcription_entry,
             clear_all_transcriptions,
             export_transcriptions,
@@ -1031,6 +1254,12 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             check_microphone_permission,
             request_microphone_permission,
             test_automation_permission,
+            save_profile,
+            apply_profile,
+            list_profiles,
+            delete_profile,
+            export_settings,
+            import_settings,
             check_license_status,
             restore_license,
             activate_license,
@@ -1048,20 +1277,32 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             set_openai_config,
             get_openai_config,
             test_openai_endpoint,
+            test_ai_provider_key,
+            list_ollama_models,
             clear_ai_api_key_cache,
             update_ai_settings,
             enhance_transcription,
             disable_ai_enhancement,
             get_enhancement_options,
             update_enhancement_options,
+            list_enhancement_presets,
+            get_active_enhancement_preset,
+            save_enhancement_preset,
+            delete_enhancement_preset,
+            set_active_enhancement_preset,
+            cycle_enhancement_preset,
             keyring_set,
             keyring_get,
             keyring_delete,
             keyring_has,
+            get_local_api_info,
+            regenerate_local_api_token,
             validate_and_cache_soniox_key,
             clear_soniox_key_cache,
             get_log_directory,
             open_logs_folder,
+            generate_diagnostics,
+            self_test,
             get_device_id,
         ])
         .on_window_event(|window, event| {
@@ -1123,7 +1364,7 @@ async fn perform_startup_checks(app: tauri::AppHandle) {
         &[("stage", "comprehensive_validation")],
     );
 
-    let availability = recognition_availability_snapshot(&app).await;
+    let availability = emit_recognition_availability(&app).await;
     log_model_operation(
         "AVAILABILITY_CHECK",
         "all",
@@ -1135,10 +1376,6 @@ async fn perform_startup_checks(app: tauri::AppHandle) {
         None,
     );
 
-    if let Err(err) = app.emit("recognition-availability", availability.clone()) {
-        log::warn!("Failed to emit recognition availability event: {}", err);
-    }
-
     if availability.any_available() {
         if let Err(e) = auto_select_model_if_needed(&app, &availability).await {
             log::warn!("Failed to auto-select default model: {}", e);
@@ -1152,6 +1389,9 @@ async fn perform_startup_checks(app: tauri::AppHandle) {
         log::info!("✅ At least one speech recognition engine is ready");
     }
 
+    // A recording cut off by a crash or force quit is offered instead of lost
+    crate::commands::audio::recover_orphaned_recording(&app).await;
+
     // Validate AI settings if enabled
     if let Ok(store) = app.store("settings") {
         let ai_enabled = store
@@ -9,6 +9,18 @@ pub struct LicenseStatus {
     pub expires_at: Option<String>,
 }
 
+/// Payload of the `license-status-detail` event: enough for the UI to warn
+/// before a trial or the offline grace period runs out. Never carries the key.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct LicenseStatusDetail {
+    pub state: LicenseState,
+    pub trial_days_left: Option<i32>,
+    /// Days left to use the app without reaching the license server
+    pub offline_grace_days_remaining: Option<i64>,
+    /// Last successful online validation (RFC 3339)
+    pub last_validated: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum LicenseState {
@@ -0,0 +1,349 @@
+//! Just enough HTTP/1.1 for the local API: one request per connection, a
+//! Content-Length body and a JSON response.
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+
+/// Longest request line or header accepted
+const MAX_LINE_BYTES: usize = 8 * 1024;
+const MAX_HEADERS: usize = 64;
+
+/// Largest `POST /transcribe` body, in line with the recorder's own size limit
+const MAX_BODY_BYTES: usize = 500 * 1024 * 1024;
+
+/// Largest body for the other endpoints, which ignore it anyway
+const MAX_CONTROL_BODY_BYTES: usize = 16 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Header names lowercased
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    pub fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            409 => "Conflict",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let body = self.body.to_string();
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            body.len(),
+            body
+        )
+        .into_bytes()
+    }
+}
+
+async fn read_line<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<String, Response> {
+    let mut line = Vec::new();
+    let read = (&mut *reader)
+        .take(MAX_LINE_BYTES as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .await
+        .map_err(|e| Response::error(400, format!("Failed to read request: {}", e)))?;
+    if read == 0 {
+        return Err(Response::error(400, "Empty request"));
+    }
+    if line.len() > MAX_LINE_BYTES || !line.ends_with(b"\n") {
+        return Err(Response::error(400, "Request line or header too long"));
+    }
+    let line = String::from_utf8(line).map_err(|_| Response::error(400, "Invalid header"))?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Read the request line and headers, leaving the body unread so the caller
+/// can check the token and route first. Failures come back as the response to send.
+pub async fn read_head<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Request, Response> {
+    let request_line = read_line(reader).await?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(Response::error(400, "Malformed request line"));
+    };
+    // Query strings aren't used by any endpoint
+    let path = target.split('?').next().unwrap_or(target).to_string();
+    let method = method.to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader).await?;
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            return Err(Response::error(400, "Too many headers"));
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(Response::error(400, "Malformed header"));
+        };
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+    }
+
+    Ok(Request {
+        method,
+        path,
+        headers,
+        body: Vec::new(),
+    })
+}
+
+/// Read the Content-Length body of `request`, refusing anything over `max_body`
+pub async fn read_body<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    request: &mut Request,
+    max_body: usize,
+) -> Result<(), Response> {
+    let length = match request.header("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| Response::error(400, "Invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > max_body {
+        return Err(Response::error(413, "Request body too large"));
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|_| Response::error(400, "Request body shorter than Content-Length"))?;
+    request.body = body;
+    Ok(())
+}
+
+/// Endpoints of the local API, mirroring the recording and transcription commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// `POST /start`: start_recording
+    Start,
+    /// `POST /stop`: stop_recording
+    Stop,
+    /// `POST /transcribe`: transcribe_audio with the body as the audio file
+    Transcribe,
+    /// `GET /state`: the current recording state
+    State,
+}
+
+impl Route {
+    /// Largest body this endpoint accepts; only `/transcribe` takes audio
+    pub fn max_body(self) -> usize {
+        match self {
+            Route::Transcribe => MAX_BODY_BYTES,
+            _ => MAX_CONTROL_BODY_BYTES,
+        }
+    }
+}
+
+pub fn route(method: &str, path: &str) -> Result<Route, Response> {
+    let (route, expected) = match path.trim_end_matches('/') {
+        "/start" => (Route::Start, "POST"),
+        "/stop" => (Route::Stop, "POST"),
+        "/transcribe" => (Route::Transcribe, "POST"),
+        "/state" => (Route::State, "GET"),
+        _ => return Err(Response::error(404, format!("Unknown endpoint {}", path))),
+    };
+    if method != expected {
+        return Err(Response::error(
+            405,
+            format!("{} only accepts {}", path, expected),
+        ));
+    }
+    Ok(route)
+}
+
+/// Token from `Authorization: Bearer <token>` or `X-VoiceTypr-Token: <token>`
+fn request_token(request: &Request) -> Option<&str> {
+    request
+        .header("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| request.header("x-voicetypr-token"))
+        .map(str::trim)
+}
+
+/// Whether the request carries `token`. Compared in constant time.
+pub fn is_authorized(request: &Request, token: &str) -> bool {
+    let Some(given) = request_token(request) else {
+        return false;
+    };
+    if token.is_empty() || given.len() != token.len() {
+        return false;
+    }
+    given
+        .bytes()
+        .zip(token.bytes())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, headers: &[(&str, &str)]) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_ascii_lowercase(), v.to_string()))
+                .collect(),
+            body: Vec::new(),
+        }
+    }
+
+    async fn read_request(raw: &[u8], max_body: usize) -> Result<Request, Response> {
+        let mut reader = tokio::io::BufReader::new(raw);
+        let mut request = read_head(&mut reader).await?;
+        read_body(&mut reader, &mut request, max_body).await?;
+        Ok(request)
+    }
+
+    #[tokio::test]
+    async fn test_read_request() {
+        let raw = b"POST /transcribe?x=1 HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Length: 4\r\nAuthorization: Bearer abc\r\n\r\nRIFFtrailing";
+        let parsed = read_request(raw, 1024).await.unwrap();
+        assert_eq!(parsed.method, "POST");
+        assert_eq!(parsed.path, "/transcribe");
+        assert_eq!(parsed.header("AUTHORIZATION"), Some("Bearer abc"));
+        assert_eq!(parsed.body, b"RIFF");
+
+        let raw = b"POST /transcribe HTTP/1.1\r\nContent-Length: 2048\r\n\r\n";
+        assert_eq!(read_request(raw, 1024).await.unwrap_err().status, 413);
+
+        let raw = b"POST /transcribe HTTP/1.1\r\nContent-Length: 10\r\n\r\nshort";
+        assert_eq!(read_request(raw, 1024).await.unwrap_err().status, 400);
+
+        assert_eq!(
+            read_request(b"garbage\r\n\r\n", 1024)
+                .await
+                .unwrap_err()
+                .status,
+            400
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_head_leaves_body_unread() {
+        let raw = b"POST /start HTTP/1.1\r\nContent-Length: 4\r\n\r\nbody";
+        let mut reader = tokio::io::BufReader::new(&raw[..]);
+        let request = read_head(&mut reader).await.unwrap();
+        assert!(request.body.is_empty());
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, b"body");
+    }
+
+    #[test]
+    fn test_body_limits() {
+        assert_eq!(Route::Transcribe.max_body(), MAX_BODY_BYTES);
+        for route in [Route::Start, Route::Stop, Route::State] {
+            assert_eq!(route.max_body(), MAX_CONTROL_BODY_BYTES);
+        }
+    }
+
+    #[test]
+    fn test_routing() {
+        assert_eq!(route("POST", "/start").unwrap(), Route::Start);
+        assert_eq!(route("POST", "/stop/").unwrap(), Route::Stop);
+        assert_eq!(route("POST", "/transcribe").unwrap(), Route::Transcribe);
+        assert_eq!(route("GET", "/state").unwrap(), Route::State);
+
+        assert_eq!(route("GET", "/start").unwrap_err().status, 405);
+        assert_eq!(route("POST", "/state").unwrap_err().status, 405);
+        assert_eq!(route("GET", "/settings").unwrap_err().status, 404);
+        assert_eq!(route("GET", "/").unwrap_err().status, 404);
+    }
+
+    #[test]
+    fn test_token_auth() {
+        let token = "0123456789abcdef";
+        assert!(is_authorized(
+            &request(
+                "GET",
+                "/state",
+                &[("Authorization", "Bearer 0123456789abcdef")]
+            ),
+            token
+        ));
+        assert!(is_authorized(
+            &request(
+                "GET",
+                "/state",
+                &[("X-VoiceTypr-Token", "0123456789abcdef")]
+            ),
+            token
+        ));
+
+        assert!(!is_authorized(&request("GET", "/state", &[]), token));
+        assert!(!is_authorized(
+            &request(
+                "GET",
+                "/state",
+                &[("Authorization", "Bearer 0123456789abcdeX")]
+            ),
+            token
+        ));
+        assert!(!is_authorized(
+            &request("GET", "/state", &[("Authorization", "0123456789abcdef")]),
+            token
+        ));
+        assert!(!is_authorized(
+            &request("GET", "/state", &[("Authorization", "Bearer 0123")]),
+            token
+        ));
+        // No token configured: nothing gets in
+        assert!(!is_authorized(
+            &request("GET", "/state", &[("Authorization", "Bearer ")]),
+            ""
+        ));
+    }
+
+    #[test]
+    fn test_response_bytes() {
+        let response = Response::error(401, "Missing or wrong token");
+        let bytes = String::from_utf8(response.to_bytes()).unwrap();
+        assert!(bytes.starts_with("HTTP/1.1 401 Unauthorized\r\n"));
+        assert!(bytes.ends_with("\r\n\r\n{\"error\":\"Missing or wrong token\"}"));
+    }
+}
@@ -0,0 +1,376 @@
+//! Opt-in HTTP API on 127.0.0.1 (`local_api_enabled`) so scripts and other local
+//! tools can drive dictation: `POST /start`, `POST /stop`, `POST /transcribe`
+//! with an audio file as the body, and `GET /state`. Every request needs the
+//! token shown in settings, as `Authorization: Bearer <token>`.
+
+mod http;
+
+use std::future::Future;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use rand::Rng;
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::Semaphore;
+
+use http::Response;
+pub use http::Route;
+
+pub const DEFAULT_LOCAL_API_PORT: u16 = 47843;
+
+/// secure_store key of the API token
+const TOKEN_KEY: &str = "local_api_token";
+
+/// Time a client gets to send its whole request, audio upload included
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Connections handled at once; each may buffer an upload of up to `MAX_BODY_BYTES`
+const MAX_CONNECTIONS: usize = 4;
+
+/// Ports below 1024 need elevated rights on macOS and Linux
+pub fn validate_port(port: u16) -> Result<(), String> {
+    if port < 1024 {
+        return Err(format!(
+            "Local API port must be between 1024 and 65535, got {}",
+            port
+        ));
+    }
+    Ok(())
+}
+
+/// Listen on loopback only; the API is never reachable from other machines
+pub async fn bind(port: u16) -> Result<TcpListener, String> {
+    TcpListener::bind(SocketAddrV4::new(Ipv4Addr::LOCALHOST, port))
+        .await
+        .map_err(|e| format!("Failed to bind 127.0.0.1:{}: {}", port, e))
+}
+
+/// Read the head, check the token, route, then read the body within the route's limit
+async fn read_authorized<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    token: &str,
+) -> Result<(Route, Vec<u8>), Response> {
+    let mut request = http::read_head(reader).await?;
+    // Check the token before routing or reading the body, so unauthenticated
+    // callers learn nothing and can't make us buffer an upload
+    if !http::is_authorized(&request, token) {
+        return Err(Response::error(401, "Missing or wrong token"));
+    }
+    let route = http::route(&request.method, &request.path)?;
+    http::read_body(reader, &mut request, route.max_body()).await?;
+    Ok((route, request.body))
+}
+
+/// Answer one request: parse it, check the token, route it and hand it to `dispatch`
+async fn handle_connection<S, F, Fut>(stream: S, token: &str, dispatch: F)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+    F: FnOnce(Route, Vec<u8>) -> Fut,
+    Fut: Future<Output = Response>,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = BufReader::new(reader);
+
+    let request = tokio::time::timeout(REQUEST_TIMEOUT, read_authorized(&mut reader, token))
+        .await
+        .unwrap_or_else(|_| Err(Response::error(400, "Timed out reading request")));
+
+    let response = match request {
+        Ok((route, body)) => dispatch(route, body).await,
+        Err(response) => response,
+    };
+
+    if let Err(e) = writer.write_all(&response.to_bytes()).await {
+        log::debug!("Local API: failed to write response: {}", e);
+    }
+    let _ = writer.shutdown().await;
+}
+
+/// Accept connections until the task is aborted, one task per connection and at
+/// most `MAX_CONNECTIONS` at once. `token` is read per connection, so a new one
+/// applies without a restart.
+async fn serve<F, Fut>(listener: TcpListener, token: Arc<RwLock<String>>, dispatch: F)
+where
+    F: Fn(Route, Vec<u8>) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = Response> + Send,
+{
+    let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+    loop {
+        // Wait for a free slot before accepting; extra clients queue in the backlog
+        let Ok(permit) = connections.clone().acquire_owned().await else {
+            return;
+        };
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                log::warn!("Local API: failed to accept connection: {}", e);
+                continue;
+            }
+        };
+        let token = token.read().map(|token| token.clone()).unwrap_or_default();
+        let dispatch = dispatch.clone();
+        tokio::spawn(async move {
+            handle_connection(stream, &token, dispatch).await;
+            drop(permit);
+        });
+    }
+}
+
+/// Run `route` against the app
+async fn dispatch(app: AppHandle, route: Route, body: Vec<u8>) -> Response {
+    use crate::commands::audio::{
+        get_current_recording_state, start_recording, stop_recording, transcribe_audio,
+        RecorderState,
+    };
+
+    log::info!("Local API: {:?}", route);
+    match route {
+        Route::Start => {
+            let result = start_recording(app.clone(), app.state::<RecorderState>()).await;
+            match result {
+                Ok(()) => Response::ok(json!(get_current_recording_state(app))),
+                Err(e) => Response::error(409, e),
+            }
+        }
+        Route::Stop => {
+            let result = stop_recording(app.clone(), app.state::<RecorderState>()).await;
+            match result {
+                Ok(_) => Response::ok(json!(get_current_recording_state(app))),
+                Err(e) => Response::error(409, e),
+            }
+        }
+        Route::Transcribe => {
+            if body.is_empty() {
+                return Response::error(400, "Request body must be an audio file");
+            }
+            let settings = match crate::commands::settings::get_settings(app.clone()).await {
+                Ok(settings) => settings,
+                Err(e) => return Response::error(500, e),
+            };
+            if settings.current_model.is_empty() {
+                return Response::error(409, "No model selected");
+            }
+            match transcribe_audio(
+                app,
+                body,
+                settings.current_model,
+                Some(settings.current_model_engine),
+            )
+            .await
+            {
+                Ok(text) => Response::ok(json!({ "text": text })),
+                Err(e) => Response::error(500, e),
+            }
+        }
+        Route::State => Response::ok(json!(get_current_recording_state(app))),
+    }
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// The API token, created on first use
+pub fn get_or_create_token(app: &AppHandle) -> Result<String, String> {
+    if let Some(token) = crate::secure_store::secure_get(app, TOKEN_KEY)? {
+        if !token.is_empty() {
+            return Ok(token);
+        }
+    }
+    let token = generate_token();
+    crate::secure_store::secure_set(app, TOKEN_KEY, &token)?;
+    Ok(token)
+}
+
+/// Replace the API token; clients using the old one get 401 from now on
+pub async fn regenerate_token(app: &AppHandle) -> Result<String, String> {
+    let token = generate_token();
+    crate::secure_store::secure_set(app, TOKEN_KEY, &token)?;
+    sync_local_api(app).await?;
+    Ok(token)
+}
+
+struct RunningServer {
+    addr: SocketAddr,
+    token: Arc<RwLock<String>>,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+/// The running local API server, if any
+#[derive(Default)]
+pub struct LocalApiState {
+    server: Mutex<Option<RunningServer>>,
+}
+
+impl LocalApiState {
+    pub fn address(&self) -> Option<SocketAddr> {
+        self.server.lock().ok()?.as_ref().map(|server| server.addr)
+    }
+
+    fn stop(&self) {
+        if let Some(server) = self.server.lock().ok().and_then(|mut guard| guard.take()) {
+            server.task.abort();
+            log::info!("Local API stopped on {}", server.addr);
+        }
+    }
+}
+
+/// Start, restart or stop the server to match the `local_api_enabled` and
+/// `local_api_port` settings and the current token
+pub async fn sync_local_api(app: &AppHandle) -> Result<(), String> {
+    let settings = crate::commands::settings::get_settings(app.clone()).await?;
+    let state = app.state::<LocalApiState>();
+    if !settings.local_api_enabled {
+        state.stop();
+        return Ok(());
+    }
+
+    let token = get_or_create_token(app)?;
+    if let Ok(guard) = state.server.lock() {
+        if let Some(server) = guard.as_ref() {
+            if server.addr.port() == settings.local_api_port {
+                if let Ok(mut current) = server.token.write() {
+                    *current = token;
+                }
+                return Ok(());
+            }
+        }
+    }
+
+    state.stop();
+    let listener = bind(settings.local_api_port).await?;
+    let token = Arc::new(RwLock::new(token));
+    let addr = listener.local_addr().map_err(|e| e.to_string())?;
+    let app_handle = app.clone();
+    let task = tauri::async_runtime::spawn(serve(listener, token.clone(), move |route, body| {
+        dispatch(app_handle.clone(), route, body)
+    }));
+    log::info!("Local API listening on http://{}", addr);
+
+    if let Ok(mut guard) = state.server.lock() {
+        *guard = Some(RunningServer { addr, token, task });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpStream;
+
+    const TOKEN: &str = "test-token";
+
+    async fn start_test_server() -> SocketAddr {
+        let listener = bind(0).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(
+            listener,
+            Arc::new(RwLock::new(TOKEN.to_string())),
+            |route, body: Vec<u8>| async move {
+                Response::ok(json!({ "route": format!("{:?}", route), "bytes": body.len() }))
+            },
+        ));
+        addr
+    }
+
+    async fn send(addr: SocketAddr, raw: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(raw.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_binds_to_loopback_only() {
+        let listener = bind(0).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        assert_eq!(addr.ip(), std::net::IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert!(addr.ip().is_loopback());
+    }
+
+    #[test]
+    fn test_validate_port() {
+        assert!(validate_port(DEFAULT_LOCAL_API_PORT).is_ok());
+        assert!(validate_port(1024).is_ok());
+        assert!(validate_port(65535).is_ok());
+        assert!(validate_port(80).is_err());
+        assert!(validate_port(0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_requests_need_the_token() {
+        let addr = start_test_server().await;
+
+        let response = send(addr, "GET /state HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 401"), "{}", response);
+
+        let response = send(
+            addr,
+            "GET /state HTTP/1.1\r\nAuthorization: Bearer nope\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 401"), "{}", response);
+
+        // Unknown paths are hidden behind auth too
+        let response = send(addr, "GET /secret HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 401"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_before_reading_the_body() {
+        let addr = start_test_server().await;
+
+        // Answered from the headers alone; the announced upload is never sent
+        let response = send(
+            addr,
+            "POST /transcribe HTTP/1.1\r\nContent-Length: 1000000\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 401"), "{}", response);
+
+        let response = send(
+            addr,
+            &format!(
+                "POST /start HTTP/1.1\r\nAuthorization: Bearer {}\r\nContent-Length: 1000000\r\n\r\n",
+                TOKEN
+            ),
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_routes_authorized_requests() {
+        let addr = start_test_server().await;
+        let auth = format!("Authorization: Bearer {}", TOKEN);
+
+        let response = send(addr, &format!("GET /state HTTP/1.1\r\n{}\r\n\r\n", auth)).await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains(r#""route":"State""#), "{}", response);
+
+        let response = send(
+            addr,
+            &format!(
+                "POST /transcribe HTTP/1.1\r\n{}\r\nContent-Length: 4\r\n\r\nRIFF",
+                auth
+            ),
+        )
+        .await;
+        assert!(response.contains(r#""route":"Transcribe""#), "{}", response);
+        assert!(response.contains(r#""bytes":4"#), "{}", response);
+
+        let response = send(addr, &format!("GET /start HTTP/1.1\r\n{}\r\n\r\n", auth)).await;
+        assert!(response.starts_with("HTTP/1.1 405"), "{}", response);
+
+        let response = send(addr, &format!("POST /reset HTTP/1.1\r\n{}\r\n\r\n", auth)).await;
+        assert!(response.starts_with("HTTP/1.1 404"), "{}", response);
+    }
+}
@@ -1,3 +1,12 @@
+mod recording_timer;
 mod tray;
 
-pub use tray::{build_tray_menu, format_tray_model_label, should_mark_model_selected};
+pub use recording_timer::{
+    start_tray_recording_timer, stop_tray_recording_timer, RecordingTimer, TRAY_TOOLTIP,
+};
+
+pub use tray::{
+    build_tray_menu, format_tray_model_label, parse_language_menu_id, parse_recent_copy_id,
+    should_mark_model_selected, CLEAR_RECENT_ID, COPY_DIAGNOSTICS_ID, MAX_TRAY_LABEL_LENGTH,
+    MAX_TRAY_RECENT_COUNT, MIN_TRAY_LABEL_LENGTH, PROFILE_MENU_PREFIX,
+};
@@ -0,0 +1,190 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager};
+
+use crate::{AppState, RecordingState};
+
+/// Tray tooltip while not recording
+pub const TRAY_TOOLTIP: &str = "VoiceTypr";
+
+/// Elapsed time as "MM:SS", or "H:MM:SS" past an hour
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+pub fn recording_tooltip(elapsed: Duration) -> String {
+    format!("{} - Recording {}", TRAY_TOOLTIP, format_elapsed(elapsed))
+}
+
+/// Once-per-second task behind the tray's elapsed recording time. Only one runs
+/// at a time: starting a new one stops whatever an earlier session left behind.
+#[derive(Default)]
+pub struct RecordingTimer {
+    task: Mutex<Option<tokio::task::JoinHandle<()>>>,
+}
+
+impl RecordingTimer {
+    /// Call `tick` with the elapsed time right away and then every second, until
+    /// it returns false or the timer is stopped
+    pub fn start<F>(&self, tick: F)
+    where
+        F: FnMut(Duration) -> bool + Send + 'static,
+    {
+        self.start_every(Duration::from_secs(1), tick);
+    }
+
+    fn start_every<F>(&self, period: Duration, mut tick: F)
+    where
+        F: FnMut(Duration) -> bool + Send + 'static,
+    {
+        let started = Instant::now();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                if !tick(started.elapsed()) {
+                    break;
+                }
+            }
+        });
+
+        if let Ok(mut guard) = self.task.lock() {
+            if let Some(previous) = guard.replace(task) {
+                log::debug!("Stopping recording timer left from the previous session");
+                previous.abort();
+            }
+        }
+    }
+
+    /// Stop the timer; returns whether one was running
+    pub fn stop(&self) -> bool {
+        let Ok(mut guard) = self.task.lock() else {
+            return false;
+        };
+        match guard.take() {
+            Some(task) => {
+                let running = !task.is_finished();
+                task.abort();
+                running
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task
+            .lock()
+            .map(|guard| guard.as_ref().is_some_and(|task| !task.is_finished()))
+            .unwrap_or(false)
+    }
+}
+
+fn set_tray_tooltip(app: &AppHandle, tooltip: &str) {
+    if let Some(tray) = app.tray_by_id("main") {
+        if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+            log::debug!("Failed to set tray tooltip: {}", e);
+        }
+    }
+}
+
+/// Show the elapsed recording time in the tray tooltip until the recording ends
+pub fn start_tray_recording_timer(app: &AppHandle) {
+    let app_handle = app.clone();
+    app.state::<AppState>()
+        .recording_timer
+        .start(move |elapsed| {
+            let state = app_handle.state::<AppState>().get_current_state();
+            if !matches!(state, RecordingState::Starting | RecordingState::Recording) {
+                // The recording ended without stop or cancel, e.g. on a device error
+                set_tray_tooltip(&app_handle, TRAY_TOOLTIP);
+                return false;
+            }
+            set_tray_tooltip(&app_handle, &recording_tooltip(elapsed));
+            true
+        });
+}
+
+/// Stop the tray timer, if running, and restore the tooltip
+pub fn stop_tray_recording_timer(app: &AppHandle) {
+    if app.state::<AppState>().recording_timer.stop() {
+        set_tray_tooltip(app, TRAY_TOOLTIP);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const PERIOD: Duration = Duration::from_millis(10);
+
+    fn counting_tick(counter: &Arc<AtomicUsize>) -> impl FnMut(Duration) -> bool + Send {
+        let counter = counter.clone();
+        move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            true
+        }
+    }
+
+    #[test]
+    fn test_recording_tooltip_format() {
+        assert_eq!(format_elapsed(Duration::from_secs(0)), "00:00");
+        assert_eq!(format_elapsed(Duration::from_millis(83_900)), "01:23");
+        assert_eq!(format_elapsed(Duration::from_secs(3723)), "1:02:03");
+        assert_eq!(
+            recording_tooltip(Duration::from_secs(5)),
+            "VoiceTypr - Recording 00:05"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recording_timer_lifecycle() {
+        let timer = RecordingTimer::default();
+        assert!(!timer.stop());
+
+        let first_ticks = Arc::new(AtomicUsize::new(0));
+        timer.start_every(PERIOD, counting_tick(&first_ticks));
+        tokio::time::sleep(PERIOD * 5).await;
+        assert!(timer.is_running());
+        assert!(first_ticks.load(Ordering::SeqCst) > 0);
+
+        // A new session replaces the old task instead of leaking it
+        let second_ticks = Arc::new(AtomicUsize::new(0));
+        timer.start_every(PERIOD, counting_tick(&second_ticks));
+        tokio::time::sleep(PERIOD).await;
+        let first_total = first_ticks.load(Ordering::SeqCst);
+        tokio::time::sleep(PERIOD * 5).await;
+        assert_eq!(first_ticks.load(Ordering::SeqCst), first_total);
+        assert!(second_ticks.load(Ordering::SeqCst) > 0);
+
+        assert!(timer.stop());
+        assert!(!timer.is_running());
+        tokio::time::sleep(PERIOD).await;
+        let second_total = second_ticks.load(Ordering::SeqCst);
+        tokio::time::sleep(PERIOD * 5).await;
+        assert_eq!(second_ticks.load(Ordering::SeqCst), second_total);
+        assert!(!timer.stop());
+    }
+
+    #[tokio::test]
+    async fn test_recording_timer_ends_itself() {
+        let timer = RecordingTimer::default();
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let counter = ticks.clone();
+        // Like a recording that ended without stop_recording: the tick says so
+        timer.start_every(PERIOD, move |_| counter.fetch_add(1, Ordering::SeqCst) < 2);
+        tokio::time::sleep(PERIOD * 10).await;
+        assert_eq!(ticks.load(Ordering::SeqCst), 3);
+        assert!(!timer.is_running());
+        assert!(!timer.stop());
+    }
+}
@@ -4,6 +4,10 @@ use tauri::Manager;
 use tauri_plugin_store::StoreExt;
 
 use crate::audio;
+use crate::commands::audio::HistoryTextVariant;
+use crate::commands::profiles::{self, SettingsProfile};
+use crate::commands::settings::changed_settings_keys;
+use crate::utils::history_crypto;
 use crate::whisper;
 
 /// Determines if a model should appear as selected in the tray given onboarding status
@@ -29,7 +33,118 @@ pub fn format_tray_model_label(
     }
 }
 
-/// Build the tray menu with all submenus (models, microphones, recent transcriptions, recording mode)
+/// Menu id of the "Clear Recent" item in the recent transcriptions submenu
+pub const CLEAR_RECENT_ID: &str = "recent_clear";
+/// Upper bound for the `tray_recent_count` setting
+pub const MAX_TRAY_RECENT_COUNT: u8 = 15;
+/// Bounds for the `tray_recent_label_length` setting
+pub const MIN_TRAY_LABEL_LENGTH: u32 = 10;
+pub const MAX_TRAY_LABEL_LENGTH: u32 = 200;
+const DEFAULT_TRAY_RECENT_COUNT: usize = 5;
+const DEFAULT_TRAY_LABEL_LENGTH: usize = 40;
+
+/// Menu id of the item that copies a diagnostics report to the clipboard
+pub const COPY_DIAGNOSTICS_ID: &str = "copy_diagnostics";
+
+/// Preview label for a recent transcription: its first line, cut to `max_chars` with an ellipsis
+pub fn recent_entry_label(text: Option<&str>, max_chars: usize) -> String {
+    let Some(text) = text else {
+        return "(unknown)".to_string();
+    };
+
+    let first_line = text.lines().next().unwrap_or("").trim();
+    if first_line.is_empty() {
+        return "(empty)".to_string();
+    }
+
+    let max_chars = max_chars.max(1);
+    if first_line.chars().count() > max_chars {
+        let mut preview: String = first_line.chars().take(max_chars).collect();
+        preview.truncate(preview.trim_end().len());
+        preview.push('\u{2026}');
+        preview
+    } else {
+        first_line.to_string()
+    }
+}
+
+const RECENT_COPY_PREFIX: &str = "recent_copy_";
+const RECENT_COPY_RAW_PREFIX: &str = "recent_copy_raw_";
+
+/// The timestamp and text variant a recent transcription menu id copies
+pub fn parse_recent_copy_id(id: &str) -> Option<(&str, HistoryTextVariant)> {
+    // Timestamps start with a digit, so "raw_" can't be part of one
+    if let Some(ts) = id.strip_prefix(RECENT_COPY_RAW_PREFIX) {
+        Some((ts, HistoryTextVariant::Raw))
+    } else {
+        id.strip_prefix(RECENT_COPY_PREFIX)
+            .map(|ts| (ts, HistoryTextVariant::Plain))
+    }
+}
+
+/// (menu id, label) pairs for the recent transcriptions submenu, newest first.
+/// AI-enhanced entries get a second item copying the text from before enhancement.
+/// A trailing "Clear Recent" item is added whenever any transcription is listed.
+pub fn recent_menu_items(
+    mut entries: Vec<(String, serde_json::Value)>,
+    count: usize,
+    max_chars: usize,
+) -> Vec<(String, String)> {
+    crate::utils::history_store::sort_newest_first(&mut entries);
+    entries.truncate(count);
+
+    let mut items: Vec<(String, String)> = Vec::new();
+    for (ts, entry) in entries {
+        let text = entry.get("text").and_then(|v| v.as_str());
+        items.push((
+            format!("{}{}", RECENT_COPY_PREFIX, ts),
+            recent_entry_label(text, max_chars),
+        ));
+        let raw_text = entry.get("raw_text").and_then(|v| v.as_str());
+        if raw_text.is_some_and(|raw| Some(raw) != text) {
+            items.push((
+                format!("{}{}", RECENT_COPY_RAW_PREFIX, ts),
+                "    \u{21b3} Before AI enhancement".to_string(),
+            ));
+        }
+    }
+
+    if !items.is_empty() {
+        items.push((CLEAR_RECENT_ID.to_string(), "Clear Recent".to_string()));
+    }
+    items
+}
+
+/// Prefix of the profiles submenu ids; the rest of the id is the profile name
+pub const PROFILE_MENU_PREFIX: &str = "profile_";
+
+/// (menu id, label, checked) for the profiles submenu. A profile is checked while
+/// the current settings still match everything it saved.
+pub fn profile_menu_items(
+    profiles: &[SettingsProfile],
+    current: &serde_json::Map<String, serde_json::Value>,
+) -> Vec<(String, String, bool)> {
+    profiles
+        .iter()
+        .map(|profile| {
+            (
+                format!("{}{}", PROFILE_MENU_PREFIX, profile.name),
+                profile.name.clone(),
+                changed_settings_keys(current, &profile.settings).is_empty(),
+            )
+        })
+        .collect()
+}
+
+/// Language code selected by a `language_<code>` tray event, if the code is supported
+pub fn parse_language_menu_id(event_id: &str) -> Option<&'static str> {
+    event_id
+        .strip_prefix("language_")
+        .filter(|code| whisper::languages::is_language_supported(code))
+        .map(|code| whisper::languages::validate_language(Some(code)))
+}
+
+/// Build the tray menu with all submenus (models, microphones, language, recent transcriptions, recording mode)
 pub async fn build_tray_menu<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
 ) -> Result<tauri::menu::Menu<R>, Box<dyn std::error::Error>> {
@@ -57,7 +172,8 @@ pub async fn build_tray_menu<R: tauri::Runtime>(
         let mut models: Vec<(String, String)> = Vec::new();
         let mut whisper_all = std::collections::HashMap::new();
 
-        if let Some(whisper_state) = app.try_state::<AsyncRwLock<whisper::manager::WhisperManager>>()
+        if let Some(whisper_state) =
+            app.try_state::<AsyncRwLock<whisper::manager::WhisperManager>>()
         {
             let manager = whisper_state.read().await;
             whisper_all = manager.get_models_status();
@@ -205,54 +321,95 @@ pub async fn build_tray_menu<R: tauri::Runtime>(
         None
     };
 
-    let mut recent_owned: Vec<tauri::menu::MenuItem<R>> = Vec::new();
+    let current_language = match app.store("settings") {
+        Ok(store) => whisper::languages::validate_language(
+            store
+                .get("language")
+                .and_then(|v| v.as_str().map(|s| s.to_string()))
+                .as_deref(),
+        ),
+        Err(_) => whisper::languages::validate_language(None),
+    };
+
+    let mut languages: Vec<&whisper::languages::Language> =
+        whisper::languages::SUPPORTED_LANGUAGES.values().collect();
+    languages.sort_by_key(|lang| lang.name);
+
+    let mut language_check_items = Vec::new();
+    for lang in &languages {
+        let item = CheckMenuItem::with_id(
+            app,
+            &format!("language_{}", lang.code),
+            lang.name,
+            true,
+            lang.code == current_language,
+            None::<&str>,
+        )?;
+        language_check_items.push(item);
+    }
+    let mut language_items: Vec<&dyn tauri::menu::IsMenuItem<_>> = Vec::new();
+    for item in &language_check_items {
+        language_items.push(item);
+    }
+
+    let current_language_name = whisper::languages::SUPPORTED_LANGUAGES
+        .get(current_language)
+        .map(|lang| lang.name)
+        .unwrap_or(current_language);
+    let language_submenu = Submenu::with_id_and_items(
+        app,
+        "languages",
+        &format!("Language: {}", current_language_name),
+        true,
+        &language_items,
+    )?;
+
+    let current_settings = profiles::current_profile_settings(app);
+    let mut profile_check_items = Vec::new();
+    for (id, label, checked) in profile_menu_items(&profiles::read_profiles(app), &current_settings)
     {
-        if let Ok(store) = app.store("transcriptions") {
-            let mut entries: Vec<(String, serde_json::Value)> = Vec::new();
-            for key in store.keys() {
-                if let Some(value) = store.get(&key) {
-                    entries.push((key.to_string(), value));
-                }
-            }
-            entries.sort_by(|a, b| b.0.cmp(&a.0));
-            entries.truncate(5);
-
-            for (ts, entry) in entries {
-                let mut label = entry
-                    .get("text")
-                    .and_then(|v| v.as_str())
-                    .map(|s| {
-                        let first_line = s.lines().next().unwrap_or("").trim();
-                        let char_count = first_line.chars().count();
-                        let mut preview: String = first_line.chars().take(40).collect();
-                        if char_count > 40 {
-                            preview.push('\u{2026}');
-                        }
-                        if preview.is_empty() {
-                            "(empty)".to_string()
-                        } else {
-                            preview
-                        }
-                    })
-                    .unwrap_or_else(|| "(unknown)".to_string());
-
-                if label.is_empty() {
-                    label = "(empty)".to_string();
-                }
+        let item = CheckMenuItem::with_id(app, &id, label, true, checked, None::<&str>)?;
+        profile_check_items.push(item);
+    }
+    let mut profile_refs: Vec<&dyn tauri::menu::IsMenuItem<_>> = Vec::new();
+    for item in &profile_check_items {
+        profile_refs.push(item);
+    }
 
-                let item = tauri::menu::MenuItem::with_id(
-                    app,
-                    &format!("recent_copy_{}", ts),
-                    label,
-                    true,
-                    None::<&str>,
-                )?;
+    let (recent_count, recent_label_length) = match app.store("settings") {
+        Ok(store) => (
+            store
+                .get("tray_recent_count")
+                .and_then(|v| v.as_u64())
+                .map(|n| n.min(MAX_TRAY_RECENT_COUNT as u64) as usize)
+                .unwrap_or(DEFAULT_TRAY_RECENT_COUNT),
+            store
+                .get("tray_recent_label_length")
+                .and_then(|v| v.as_u64())
+                .map(|n| n as usize)
+                .unwrap_or(DEFAULT_TRAY_LABEL_LENGTH),
+        ),
+        Err(_) => (DEFAULT_TRAY_RECENT_COUNT, DEFAULT_TRAY_LABEL_LENGTH),
+    };
+
+    let recent_separator = PredefinedMenuItem::separator(app)?;
+    let mut recent_owned: Vec<tauri::menu::MenuItem<R>> = Vec::new();
+    if recent_count > 0 {
+        // Only the newest shards are read, enough for the submenu
+        if let Ok(entries) =
+            history_crypto::read_newest_history_entries(app, recent_count, |_| true)
+        {
+            for (id, label) in recent_menu_items(entries, recent_count, recent_label_length) {
+                let item = tauri::menu::MenuItem::with_id(app, &id, label, true, None::<&str>)?;
                 recent_owned.push(item);
             }
         }
     }
     let mut recent_refs: Vec<&dyn tauri::menu::IsMenuItem<_>> = Vec::new();
     for item in &recent_owned {
+        if item.id().as_ref() == CLEAR_RECENT_ID {
+            recent_refs.push(&recent_separator);
+        }
         recent_refs.push(item);
     }
 
@@ -293,6 +450,13 @@ pub async fn build_tray_menu<R: tauri::Runtime>(
         true,
         None::<&str>,
     )?;
+    let copy_diagnostics_i = MenuItem::with_id(
+        app,
+        COPY_DIAGNOSTICS_ID,
+        "Copy Diagnostics",
+        true,
+        None::<&str>,
+    )?;
     let separator2 = PredefinedMenuItem::separator(app)?;
     let quit_i = MenuItem::with_id(app, "quit", "Quit VoiceTypr", true, None::<&str>)?;
 
@@ -306,6 +470,14 @@ pub async fn build_tray_menu<R: tauri::Runtime>(
         menu_builder = menu_builder.item(&microphone_submenu);
     }
 
+    menu_builder = menu_builder.item(&language_submenu);
+
+    if !profile_refs.is_empty() {
+        let profile_submenu =
+            Submenu::with_id_and_items(app, "profiles", "Profiles", true, &profile_refs)?;
+        menu_builder = menu_builder.item(&profile_submenu);
+    }
+
     if !recent_refs.is_empty() {
         let recent_submenu =
             Submenu::with_id_and_items(app, "recent", "Recent Transcriptions", true, &recent_refs)?;
@@ -321,9 +493,132 @@ pub async fn build_tray_menu<R: tauri::Runtime>(
         .item(&separator1)
         .item(&settings_i)
         .item(&check_updates_i)
+        .item(&copy_diagnostics_i)
         .item(&separator2)
         .item(&quit_i)
         .build()?;
 
     Ok(menu)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entries(n: usize) -> Vec<(String, serde_json::Value)> {
+        (0..n)
+            .map(|i| {
+                (
+                    format!("2025-01-01T00:00:{:02}Z", i),
+                    json!({ "text": format!("entry {}", i) }),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recent_menu_items_respects_count() {
+        let items = recent_menu_items(entries(20), 15, 40);
+        // 15 transcriptions plus "Clear Recent"
+        assert_eq!(items.len(), 16);
+        assert_eq!(items[0].0, "recent_copy_2025-01-01T00:00:19Z");
+        assert_eq!(items[15].0, CLEAR_RECENT_ID);
+
+        assert_eq!(recent_menu_items(entries(3), 5, 40).len(), 4);
+        assert!(recent_menu_items(entries(3), 0, 40).is_empty());
+        assert!(recent_menu_items(Vec::new(), 5, 40).is_empty());
+    }
+
+    #[test]
+    fn test_enhanced_entries_offer_raw_copy() {
+        let entries = vec![
+            (
+                "2025-01-01T00:00:01Z".to_string(),
+                json!({ "text": "Meet Dana on Friday.", "raw_text": "meet dana on friday" }),
+            ),
+            (
+                "2025-01-01T00:00:00Z".to_string(),
+                json!({ "text": "plain entry" }),
+            ),
+        ];
+        let ids: Vec<String> = recent_menu_items(entries, 5, 40)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "recent_copy_2025-01-01T00:00:01Z",
+                "recent_copy_raw_2025-01-01T00:00:01Z",
+                "recent_copy_2025-01-01T00:00:00Z",
+                CLEAR_RECENT_ID,
+            ]
+        );
+
+        assert_eq!(
+            parse_recent_copy_id("recent_copy_raw_2025-01-01T00:00:01Z"),
+            Some(("2025-01-01T00:00:01Z", HistoryTextVariant::Raw))
+        );
+        assert_eq!(
+            parse_recent_copy_id("recent_copy_2025-01-01T00:00:00Z"),
+            Some(("2025-01-01T00:00:00Z", HistoryTextVariant::Plain))
+        );
+        assert_eq!(parse_recent_copy_id(CLEAR_RECENT_ID), None);
+    }
+
+    #[test]
+    fn test_profile_menu_items_check_matching_profile() {
+        let profile = |name: &str, model: &str| SettingsProfile {
+            name: name.to_string(),
+            saved_at: "2025-01-01T00:00:00Z".to_string(),
+            settings: json!({ "current_model": model, "ai_enabled": false })
+                .as_object()
+                .cloned()
+                .unwrap(),
+        };
+        let current = json!({ "current_model": "base.en", "ai_enabled": false })
+            .as_object()
+            .cloned()
+            .unwrap();
+
+        let meeting = profile("Meeting", "large-v3");
+        let notes = profile("Notes", "base.en");
+        let items = profile_menu_items(&[meeting, notes], &current);
+        assert_eq!(
+            items,
+            vec![
+                ("profile_Meeting".to_string(), "Meeting".to_string(), false),
+                ("profile_Notes".to_string(), "Notes".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_language_menu_id() {
+        assert_eq!(parse_language_menu_id("language_en"), Some("en"));
+        assert_eq!(parse_language_menu_id("language_de"), Some("de"));
+        // Unsupported codes and other menu ids are ignored
+        assert_eq!(parse_language_menu_id("language_auto"), None);
+        assert_eq!(parse_language_menu_id("language_xx"), None);
+        assert_eq!(parse_language_menu_id("language_"), None);
+        assert_eq!(parse_language_menu_id("model_en"), None);
+    }
+
+    #[test]
+    fn test_recent_entry_label_truncates_with_ellipsis() {
+        assert_eq!(recent_entry_label(Some("short"), 10), "short");
+        assert_eq!(
+            recent_entry_label(Some("hello wonderful world"), 15),
+            "hello wonderful\u{2026}"
+        );
+        // Trailing space at the cut is dropped before the ellipsis
+        assert_eq!(recent_entry_label(Some("hello world"), 6), "hello\u{2026}");
+        assert_eq!(
+            recent_entry_label(Some("first line\nsecond"), 40),
+            "first line"
+        );
+        assert_eq!(recent_entry_label(Some("  "), 40), "(empty)");
+        assert_eq!(recent_entry_label(None, 40), "(unknown)");
+    }
+}
@@ -0,0 +1,300 @@
+//! Progress, cancellation and verification for Parakeet downloads. FluidAudio
+//! downloads the model inside the sidecar and only answers once it's loaded,
+//! so progress is read from the model folder growing on disk while the request
+//! runs, and cancelling stops the sidecar.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use super::models::ParakeetModelDefinition;
+
+/// How often the model folder is measured and the cancel flag checked
+pub const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Where FluidAudio keeps a downloaded model
+/// ("Application Support" has a space, as on every Mac)
+pub fn fluid_audio_model_dir(home: &Path, model_id: &str) -> PathBuf {
+    home.join("Library/Application Support/FluidAudio/Models")
+        .join(format!("{}-coreml", model_id))
+}
+
+/// Total size of the files under `path`, 0 if it doesn't exist
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| dir_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Progress to report for `downloaded` bytes of a model estimated at `estimated`.
+/// Held below 100% until the sidecar confirms the model, since the estimate is
+/// approximate and 100% starts verification in the UI.
+pub fn reported_progress(downloaded: u64, estimated: u64) -> (u64, u64) {
+    (downloaded.min(estimated / 100 * 99), estimated)
+}
+
+/// FluidAudio names the vocabulary per model version ("parakeet_vocab.json",
+/// "parakeet_v3_vocab.json"), so any non-empty `*vocab*.json` stands in for it
+fn has_vocabulary(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries.filter_map(|entry| entry.ok()).any(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            name.contains("vocab") && name.ends_with(".json") && dir_size(&entry.path()) > 0
+        })
+    })
+}
+
+/// Files of `definition` that are missing or empty in `dir`. `.mlmodelc`
+/// bundles are folders and count as present when they hold anything.
+pub fn missing_model_files(dir: &Path, definition: &ParakeetModelDefinition) -> Vec<&'static str> {
+    definition
+        .files
+        .iter()
+        .map(|file| file.filename)
+        .filter(|filename| dir_size(&dir.join(filename)) == 0)
+        .filter(|filename| !(filename.ends_with("vocab.json") && has_vocabulary(dir)))
+        .collect()
+}
+
+/// Whether `dir` holds a complete copy of `definition`. A folder with none of the
+/// listed files but other content comes from a FluidAudio version that lays the
+/// model out differently; like before file-level checks, it counts as downloaded.
+pub fn is_model_complete(dir: &Path, definition: &ParakeetModelDefinition) -> bool {
+    let missing = missing_model_files(dir, definition);
+    if missing.is_empty() {
+        return true;
+    }
+    if missing.len() == definition.files.len() && dir_size(dir) > 0 {
+        log::warn!(
+            "Parakeet model at {:?} has none of the expected files; assuming a different FluidAudio layout",
+            dir
+        );
+        return true;
+    }
+    false
+}
+
+/// Remove `dir` if it holds an incomplete copy of `definition`, so a broken
+/// download is neither listed as downloaded nor loaded. Returns whether it was removed.
+pub fn discard_incomplete(dir: &Path, definition: &ParakeetModelDefinition) -> bool {
+    if !dir.exists() || is_model_complete(dir, definition) {
+        return false;
+    }
+    match std::fs::remove_dir_all(dir) {
+        Ok(()) => {
+            log::info!("Removed incomplete Parakeet model at {:?}", dir);
+            true
+        }
+        Err(e) => {
+            log::warn!(
+                "Failed to remove incomplete Parakeet model {:?}: {}",
+                dir,
+                e
+            );
+            false
+        }
+    }
+}
+
+/// Drive `request` to completion, reporting the size of `model_dir` through
+/// `progress_callback` every `interval`. Setting `cancel_flag` drops the request
+/// and returns "Download cancelled by user", like a cancelled Whisper download.
+pub async fn watch_download<T, F>(
+    request: F,
+    model_dir: Option<&Path>,
+    estimated_size: u64,
+    cancel_flag: Option<&AtomicBool>,
+    progress_callback: &impl Fn(u64, u64),
+    interval: Duration,
+) -> Result<T, String>
+where
+    F: Future<Output = T>,
+{
+    tokio::pin!(request);
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut last_reported = None;
+
+    loop {
+        tokio::select! {
+            result = &mut request => return Ok(result),
+            _ = ticker.tick() => {
+                if cancel_flag.is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+                    return Err("Download cancelled by user".to_string());
+                }
+                let downloaded = model_dir.map_or(0, dir_size);
+                let progress = reported_progress(downloaded, estimated_size);
+                // Only report when something arrived since the last tick
+                if downloaded > 0 && last_reported != Some(progress) {
+                    last_reported = Some(progress);
+                    progress_callback(progress.0, progress.1);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parakeet::models::AVAILABLE_MODELS;
+    use std::sync::{Arc, Mutex};
+
+    fn definition() -> &'static ParakeetModelDefinition {
+        &AVAILABLE_MODELS[0]
+    }
+
+    fn write_complete_model(dir: &Path) {
+        for file in definition().files {
+            let path = dir.join(file.filename);
+            if file.filename.ends_with(".mlmodelc") {
+                std::fs::create_dir_all(&path).unwrap();
+                std::fs::write(path.join("coremldata.bin"), b"weights").unwrap();
+            } else {
+                std::fs::create_dir_all(dir).unwrap();
+                std::fs::write(&path, b"{}").unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_model_dir_and_progress() {
+        assert_eq!(
+            fluid_audio_model_dir(Path::new("/Users/me"), "parakeet-tdt-0.6b-v3"),
+            Path::new("/Users/me/Library/Application Support/FluidAudio/Models")
+                .join("parakeet-tdt-0.6b-v3-coreml")
+        );
+
+        assert_eq!(reported_progress(250, 1000), (250, 1000));
+        // Never 100% before the sidecar confirms, even past the estimate
+        assert_eq!(reported_progress(1000, 1000), (990, 1000));
+        assert_eq!(reported_progress(5000, 1000), (990, 1000));
+    }
+
+    #[test]
+    fn test_incomplete_download_is_detected_and_discarded() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("parakeet-tdt-0.6b-v3-coreml");
+
+        write_complete_model(&dir);
+        assert!(missing_model_files(&dir, definition()).is_empty());
+        assert!(!discard_incomplete(&dir, definition()));
+        assert!(dir.exists());
+
+        // An interrupted download: one bundle empty, the vocabulary missing
+        std::fs::remove_dir_all(dir.join("Decoder.mlmodelc")).unwrap();
+        std::fs::create_dir_all(dir.join("Decoder.mlmodelc")).unwrap();
+        std::fs::remove_file(dir.join("parakeet_vocab.json")).unwrap();
+        assert_eq!(
+            missing_model_files(&dir, definition()),
+            vec!["Decoder.mlmodelc", "parakeet_vocab.json"]
+        );
+        assert!(discard_incomplete(&dir, definition()));
+        assert!(!dir.exists());
+
+        // Nothing downloaded: nothing to discard
+        assert!(!discard_incomplete(&dir, definition()));
+    }
+
+    #[test]
+    fn test_model_file_names_from_other_fluid_audio_versions() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("parakeet-tdt-0.6b-v3-coreml");
+
+        // A version-specific vocabulary name stands in for parakeet_vocab.json
+        write_complete_model(&dir);
+        std::fs::rename(
+            dir.join("parakeet_vocab.json"),
+            dir.join("parakeet_v3_vocab.json"),
+        )
+        .unwrap();
+        assert!(missing_model_files(&dir, definition()).is_empty());
+        assert!(is_model_complete(&dir, definition()));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // None of the listed names at all: a layout we don't know, kept as downloaded
+        std::fs::create_dir_all(dir.join("ParakeetEncoder.mlmodelc")).unwrap();
+        std::fs::write(dir.join("ParakeetEncoder.mlmodelc/weights.bin"), b"w").unwrap();
+        assert!(is_model_complete(&dir, definition()));
+        assert!(!discard_incomplete(&dir, definition()));
+        assert!(dir.exists());
+
+        // An empty folder is not a model
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(!is_model_complete(&dir, definition()));
+    }
+
+    #[tokio::test]
+    async fn test_progress_is_reported_while_the_model_folder_grows() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("model");
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+
+        let writer_dir = dir.clone();
+        let request = async move {
+            for chunk in 1..=3 {
+                tokio::time::sleep(Duration::from_millis(40)).await;
+                std::fs::create_dir_all(&writer_dir).unwrap();
+                std::fs::write(writer_dir.join(format!("part{}", chunk)), vec![0u8; 100]).unwrap();
+            }
+            tokio::time::sleep(Duration::from_millis(40)).await;
+            "loaded"
+        };
+        let result = watch_download(
+            request,
+            Some(&dir),
+            1000,
+            None,
+            &move |downloaded, total| sink.lock().unwrap().push((downloaded, total)),
+            Duration::from_millis(10),
+        )
+        .await;
+
+        assert_eq!(result, Ok("loaded"));
+        let reports = reports.lock().unwrap().clone();
+        assert!(!reports.is_empty());
+        assert!(reports.iter().all(|&(_, total)| total == 1000));
+        assert!(reports.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(reports.last().unwrap().0, 300);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_flag_stops_the_download() {
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let flag = cancel_flag.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            flag.store(true, Ordering::Relaxed);
+        });
+
+        let started = std::time::Instant::now();
+        let result = watch_download(
+            std::future::pending::<()>(),
+            None,
+            1000,
+            Some(&cancel_flag),
+            &|_, _| panic!("nothing was downloaded"),
+            Duration::from_millis(10),
+        )
+        .await;
+
+        let error = result.unwrap_err();
+        // download_model treats errors mentioning "cancelled" as a cancellation
+        assert!(error.contains("cancelled"), "{}", error);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
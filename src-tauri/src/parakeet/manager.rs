@@ -1,12 +1,13 @@
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use log::{info, warn};
 use reqwest::Client;
 use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 
+use super::download;
 use super::error::ParakeetError;
 use super::messages::{ParakeetCommand, ParakeetResponse};
 use super::models::{ParakeetModelDefinition, AVAILABLE_MODELS};
@@ -28,7 +29,7 @@ pub struct ParakeetModelStatus {
 
 pub struct ParakeetManager {
     client: ParakeetClient,
-    root_dir: PathBuf,
+    root_dir: RwLock<PathBuf>,
     http: Client,
 }
 
@@ -38,7 +39,7 @@ impl ParakeetManager {
     pub fn new(root_dir: PathBuf) -> Self {
         Self {
             client: ParakeetClient::new("parakeet-sidecar"),
-            root_dir,
+            root_dir: RwLock::new(root_dir),
             http: Client::new(),
         }
     }
@@ -95,27 +96,32 @@ impl ParakeetManager {
     }
 
     pub fn model_dir(&self, model_name: &str) -> PathBuf {
-        self.root_dir.join(model_name)
+        self.root_dir
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .join(model_name)
+    }
+
+    /// Use a relocated models directory for downloads and lookups
+    pub fn set_root_dir(&self, root_dir: PathBuf) {
+        *self.root_dir.write().unwrap_or_else(|e| e.into_inner()) = root_dir;
+    }
+
+    /// Where FluidAudio keeps `definition`, if the home directory is known
+    fn fluid_audio_model_dir(definition: &ParakeetModelDefinition) -> Option<PathBuf> {
+        dirs::home_dir().map(|home| download::fluid_audio_model_dir(&home, definition.id))
     }
 
     /// Check if a Parakeet model is available.
     /// FluidAudio stores models in ~/Library/Application Support/FluidAudio/Models/
     pub fn is_model_downloaded(&self, definition: &ParakeetModelDefinition) -> bool {
-        if let Some(home) = dirs::home_dir() {
-            // FluidAudio's actual model storage path
-            // IMPORTANT: "Application Support" has a SPACE (macOS standard path)
-            let fluid_audio_models_path = home
-                .join("Library/Application Support/FluidAudio/Models")
-                .join(format!("{}-coreml", definition.id));
-
-            if fluid_audio_models_path.exists() {
-                // Check if directory contains model files
-                if let Ok(entries) = std::fs::read_dir(&fluid_audio_models_path) {
-                    if entries.count() > 0 {
-                        info!("Found FluidAudio model at: {:?}", fluid_audio_models_path);
-                        return true;
-                    }
-                }
+        if let Some(fluid_audio_models_path) = Self::fluid_audio_model_dir(definition) {
+            // Every model file must be there; an interrupted download leaves only some
+            if fluid_audio_models_path.exists()
+                && download::is_model_complete(&fluid_audio_models_path, definition)
+            {
+                info!("Found FluidAudio model at: {:?}", fluid_audio_models_path);
+                return true;
             }
         }
 
@@ -133,11 +139,14 @@ impl ParakeetManager {
         false
     }
 
+    /// Download a model through the sidecar. Progress is reported as the model
+    /// folder fills up, and setting `cancel_flag` stops the sidecar mid-download.
+    /// A download that ends with files missing is removed and reported as failed.
     pub async fn download_model(
         &self,
         app: &AppHandle,
         model_name: &str,
-        _cancel_flag: Option<Arc<AtomicBool>>,
+        cancel_flag: Option<Arc<AtomicBool>>,
         progress_callback: impl Fn(u64, u64) + Send + 'static,
     ) -> Result<(), String> {
         let Some(definition) = self.get_model_definition(model_name) else {
@@ -163,13 +172,48 @@ impl ParakeetManager {
         };
 
         // Send to sidecar and let it handle the download
-        match self.send_command(app, &command).await {
+        let model_dir = Self::fluid_audio_model_dir(definition);
+        let response = download::watch_download(
+            self.send_command(app, &command),
+            model_dir.as_deref(),
+            definition.estimated_size,
+            cancel_flag.as_deref(),
+            &progress_callback,
+            download::PROGRESS_INTERVAL,
+        )
+        .await;
+        let response = match response {
+            Ok(response) => response,
+            Err(cancelled) => {
+                // FluidAudio keeps downloading until the sidecar goes away; the
+                // next command starts a fresh one
+                self.client.shutdown().await;
+                if let Some(dir) = &model_dir {
+                    download::discard_incomplete(dir, definition);
+                }
+                return Err(cancelled);
+            }
+        };
+
+        let result = match response {
             Ok(ParakeetResponse::Status {
                 loaded_model: Some(id),
                 ..
             }) if id == definition.id => {
-                // Download/load completed for the requested version
+                // Download/load completed for the requested version; 100% starts verification
                 progress_callback(definition.estimated_size, definition.estimated_size);
+                // The sidecar just loaded the model, so it is usable whatever the
+                // folder holds; a mismatch means FluidAudio changed its file names
+                if let Some(dir) = model_dir.as_deref().filter(|dir| dir.exists()) {
+                    let missing = download::missing_model_files(dir, definition);
+                    if !missing.is_empty() {
+                        log::warn!(
+                            "Parakeet model loaded but {:?} lacks expected files: {}",
+                            dir,
+                            missing.join(", ")
+                        );
+                    }
+                }
                 Ok(())
             }
             Ok(ParakeetResponse::Status {
@@ -187,7 +231,15 @@ impl ParakeetManager {
             }
             Err(e) => Err(format!("Failed to communicate with sidecar: {}", e)),
             _ => Err("Unexpected response from sidecar".to_string()),
+        };
+
+        // Don't leave a partial model behind to be listed as downloaded
+        if result.is_err() {
+            if let Some(dir) = &model_dir {
+                download::discard_incomplete(dir, definition);
+            }
         }
+        result
     }
 
     pub async fn delete_model(&self, app: &AppHandle, model_name: &str) -> Result<(), String> {
@@ -294,6 +346,23 @@ impl ParakeetManager {
         }
     }
 
+    /// Drop whatever model the sidecar has loaded so the next load starts cold
+    pub async fn unload_model(&self, app: &AppHandle) -> Result<(), ParakeetError> {
+        match self
+            .send_command(app, &ParakeetCommand::UnloadModel {})
+            .await?
+        {
+            ParakeetResponse::Ok { .. } | ParakeetResponse::Status { .. } => Ok(()),
+            ParakeetResponse::Error { code, message, .. } => {
+                Err(ParakeetError::SidecarError { code, message })
+            }
+            other => Err(ParakeetError::SidecarError {
+                code: "unexpected_response".to_string(),
+                message: format!("Unexpected response: {:?}", other),
+            }),
+        }
+    }
+
     pub async fn transcribe(
         &self,
         app: &AppHandle,
@@ -1,5 +1,16 @@
+mod model_recommendation;
 mod model_selection;
+mod numbers;
+mod repetition;
+mod voice_commands;
 
+pub use model_recommendation::{
+    best_model_for_language, model_candidates, ModelCandidate, ModelRecommendation,
+};
 pub use model_selection::{
-    auto_select_model_if_needed, recognition_availability_snapshot, RecognitionAvailabilitySnapshot,
+    auto_select_model_if_needed, emit_recognition_availability, recognition_availability_snapshot,
+    RecognitionAvailabilitySnapshot,
 };
+pub use numbers::normalize_spoken_numbers;
+pub use repetition::collapse_repeated_phrases;
+pub use voice_commands::apply_voice_commands;
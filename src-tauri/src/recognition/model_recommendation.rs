@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::parakeet::ParakeetModelStatus;
+use crate::whisper::languages::get_language_name;
+use crate::whisper::manager::ModelInfo;
+
+/// A model as far as picking one for a language goes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelCandidate {
+    pub name: String,
+    pub engine: String,
+    pub downloaded: bool,
+    /// Languages the model understands; `None` means all Whisper languages
+    pub languages: Option<Vec<String>>,
+    pub speed_score: u8,
+    pub accuracy_score: u8,
+    pub size: u64,
+}
+
+impl ModelCandidate {
+    /// Whether the model can transcribe `language` ("auto" asks it to detect)
+    pub fn supports(&self, language: &str) -> bool {
+        match &self.languages {
+            None => true,
+            // Auto-detection needs more than one language to choose from
+            Some(languages) if language == "auto" => languages.len() > 1,
+            Some(languages) => languages.iter().any(|code| code == language),
+        }
+    }
+
+    fn is_multilingual(&self) -> bool {
+        self.languages
+            .as_ref()
+            .is_none_or(|languages| languages.len() > 1)
+    }
+
+    /// Best fit first: speed and accuracy count equally, so a fast model that
+    /// is nearly as accurate wins over a slow one; ties go to the more accurate,
+    /// then the smaller model
+    fn rank(&self) -> (u16, u8, std::cmp::Reverse<u64>) {
+        (
+            self.speed_score as u16 + self.accuracy_score as u16,
+            self.accuracy_score,
+            std::cmp::Reverse(self.size),
+        )
+    }
+}
+
+/// Whisper and Parakeet models, downloaded or not. `.en` Whisper models only
+/// understand English.
+pub fn model_candidates(
+    whisper_models: &HashMap<String, ModelInfo>,
+    parakeet_models: &[ParakeetModelStatus],
+) -> Vec<ModelCandidate> {
+    let whisper = whisper_models.values().map(|model| ModelCandidate {
+        name: model.name.clone(),
+        engine: "whisper".to_string(),
+        downloaded: model.downloaded,
+        languages: model.name.ends_with(".en").then(|| vec!["en".to_string()]),
+        speed_score: model.speed_score,
+        accuracy_score: model.accuracy_score,
+        size: model.size,
+    });
+    let parakeet = parakeet_models.iter().map(|model| ModelCandidate {
+        name: model.name.clone(),
+        engine: "parakeet".to_string(),
+        downloaded: model.downloaded,
+        languages: crate::parakeet::models::AVAILABLE_MODELS
+            .iter()
+            .find(|definition| definition.id == model.name)
+            .map(|definition| definition.languages.iter().map(|l| l.to_string()).collect()),
+        speed_score: model.speed_score,
+        accuracy_score: model.accuracy_score,
+        size: model.size,
+    });
+    whisper.chain(parakeet).collect()
+}
+
+/// Result of the `recommend_model` command
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelRecommendation {
+    pub language: String,
+    /// Best downloaded model for the language, if any fits
+    pub recommended: Option<String>,
+    pub recommended_engine: Option<String>,
+    /// Whether the selected model can transcribe the language at all
+    pub current_model_suitable: bool,
+    /// Nothing downloaded understands the language; a multilingual model is needed
+    pub needs_multilingual: bool,
+    /// Best model to download when nothing downloaded fits
+    pub suggested_download: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Suggest the model to use for `language` given what's downloaded
+pub fn best_model_for_language(
+    language: &str,
+    current_model: &str,
+    candidates: &[ModelCandidate],
+) -> ModelRecommendation {
+    let language_name = get_language_name(language).unwrap_or(language);
+    let best = |downloaded: bool| {
+        candidates
+            .iter()
+            .filter(|model| model.downloaded == downloaded && model.supports(language))
+            .max_by_key(|model| model.rank())
+    };
+    let recommended = best(true);
+    let current = candidates.iter().find(|model| model.name == current_model);
+    let current_model_suitable = current.is_some_and(|model| model.supports(language));
+
+    let mut warnings = Vec::new();
+    if let Some(current) = current.filter(|_| !current_model_suitable) {
+        if current.is_multilingual() {
+            warnings.push(format!(
+                "{} doesn't support {}",
+                current.name, language_name
+            ));
+        } else {
+            warnings.push(format!(
+                "{} only understands English; {} needs a multilingual model",
+                current.name, language_name
+            ));
+        }
+    }
+
+    let needs_multilingual = recommended.is_none();
+    let suggested_download = if needs_multilingual {
+        best(false).map(|model| model.name.clone())
+    } else {
+        None
+    };
+    if needs_multilingual {
+        warnings.push(match &suggested_download {
+            Some(model) => format!(
+                "No downloaded model supports {}; download {}",
+                language_name, model
+            ),
+            None => format!("No available model supports {}", language_name),
+        });
+    } else if let (Some(recommended), Some(current)) = (recommended, current) {
+        if current_model_suitable
+            && recommended.name != current.name
+            && recommended.accuracy_score >= current.accuracy_score
+            && recommended.speed_score > current.speed_score
+        {
+            warnings.push(format!(
+                "{} is as accurate as {} and faster for {}",
+                recommended.name, current.name, language_name
+            ));
+        }
+    }
+
+    ModelRecommendation {
+        language: language.to_string(),
+        recommended: recommended.map(|model| model.name.clone()),
+        recommended_engine: recommended.map(|model| model.engine.clone()),
+        current_model_suitable,
+        needs_multilingual,
+        suggested_download,
+        warnings,
+    }
+}
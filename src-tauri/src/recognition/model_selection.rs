@@ -20,6 +20,23 @@ impl RecognitionAvailabilitySnapshot {
             || self.parakeet_available
             || (self.soniox_selected && self.soniox_ready)
     }
+
+    /// Snapshot from the engines' state: whether Whisper has a downloaded model,
+    /// the Parakeet catalog, the selected engine and whether a Soniox key is stored
+    pub fn from_state(
+        whisper_available: bool,
+        parakeet_models: &[parakeet::ParakeetModelStatus],
+        current_engine: &str,
+        soniox_key_present: bool,
+    ) -> Self {
+        let soniox_selected = current_engine == "soniox";
+        Self {
+            whisper_available,
+            parakeet_available: parakeet_models.iter().any(|model| model.downloaded),
+            soniox_selected,
+            soniox_ready: soniox_selected && soniox_key_present,
+        }
+    }
 }
 
 /// Get a snapshot of which recognition engines are available
@@ -33,40 +50,40 @@ pub async fn recognition_availability_snapshot(
             false
         };
 
-    let parakeet_available =
-        if let Some(parakeet_manager) = app.try_state::<parakeet::ParakeetManager>() {
-            parakeet_manager
-                .list_models()
-                .into_iter()
-                .any(|model| model.downloaded)
-        } else {
-            false
-        };
+    let parakeet_models = app
+        .try_state::<parakeet::ParakeetManager>()
+        .map(|parakeet_manager| parakeet_manager.list_models())
+        .unwrap_or_default();
 
-    let (soniox_selected, soniox_ready) = match app.store("settings") {
-        Ok(store) => {
-            let engine = store
-                .get("current_model_engine")
-                .and_then(|v| v.as_str().map(|s| s.to_string()))
-                .unwrap_or_else(|| "whisper".to_string());
-
-            if engine == "soniox" {
-                let has_key =
-                    crate::secure_store::secure_has(app, "stt_api_key_soniox").unwrap_or(false);
-                (true, has_key)
-            } else {
-                (false, false)
-            }
-        }
-        Err(_) => (false, false),
-    };
+    let current_engine = app
+        .store("settings")
+        .ok()
+        .and_then(|store| store.get("current_model_engine"))
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| "whisper".to_string());
+
+    let soniox_key_present = current_engine == "soniox"
+        && crate::secure_store::secure_has(app, "stt_api_key_soniox").unwrap_or(false);
 
-    RecognitionAvailabilitySnapshot {
+    RecognitionAvailabilitySnapshot::from_state(
         whisper_available,
-        parakeet_available,
-        soniox_selected,
-        soniox_ready,
+        &parakeet_models,
+        &current_engine,
+        soniox_key_present,
+    )
+}
+
+/// Recompute availability and send it as `recognition-availability`, so the
+/// settings and model screens stay current without polling. Called whenever a
+/// model is downloaded or deleted, the engine changes, or the Soniox key changes.
+pub async fn emit_recognition_availability(
+    app: &tauri::AppHandle,
+) -> RecognitionAvailabilitySnapshot {
+    let availability = recognition_availability_snapshot(app).await;
+    if let Err(err) = app.emit("recognition-availability", availability.clone()) {
+        log::warn!("Failed to emit recognition availability event: {}", err);
     }
+    availability
 }
 
 fn pick_best_parakeet_model(models: Vec<parakeet::ParakeetModelStatus>) -> Option<String> {
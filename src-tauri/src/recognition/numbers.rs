@@ -0,0 +1,644 @@
+//! Spoken English number normalization ("twenty twenty four" -> "2024", "five dollars" -> "$5").
+//!
+//! Conversion is deliberately conservative: bare numbers up to ten and ordinals up to
+//! "tenth" stay as words ("one of them", "first of all"), and anything that doesn't
+//! parse as a complete number is left untouched.
+
+/// Unit and teen words; the index is the value
+const SMALL: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const SMALL_ORDINALS: [&str; 20] = [
+    "zeroth",
+    "first",
+    "second",
+    "third",
+    "fourth",
+    "fifth",
+    "sixth",
+    "seventh",
+    "eighth",
+    "ninth",
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+    "fifteenth",
+    "sixteenth",
+    "seventeenth",
+    "eighteenth",
+    "nineteenth",
+];
+
+const TENS: [(&str, &str, u64); 8] = [
+    ("twenty", "twentieth", 20),
+    ("thirty", "thirtieth", 30),
+    ("forty", "fortieth", 40),
+    ("fifty", "fiftieth", 50),
+    ("sixty", "sixtieth", 60),
+    ("seventy", "seventieth", 70),
+    ("eighty", "eightieth", 80),
+    ("ninety", "ninetieth", 90),
+];
+
+const SCALES: [(&str, &str, u64); 3] = [
+    ("thousand", "thousandth", 1_000),
+    ("million", "millionth", 1_000_000),
+    ("billion", "billionth", 1_000_000_000),
+];
+
+/// Bare integers at or below this stay as words
+const MAX_SPELLED_OUT: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Word {
+    /// 0-9
+    Unit(u64),
+    /// 10-19
+    Teen(u64),
+    Tens(u64),
+    Hundred,
+    Scale(u64),
+    And,
+    Point,
+    Oh,
+    Other,
+}
+
+/// One word of a token; hyphenated tokens ("twenty-four") contribute several pieces
+#[derive(Debug, Clone, Copy)]
+struct Piece {
+    word: Word,
+    ordinal: bool,
+    token: usize,
+    last_in_token: bool,
+}
+
+/// A whitespace-separated token and the byte range of its alphanumeric core
+struct Token<'a> {
+    start: usize,
+    end: usize,
+    core: &'a str,
+    prefix: &'a str,
+    suffix: &'a str,
+}
+
+fn classify(word: &str) -> (Word, bool) {
+    let word = word.to_lowercase();
+    if let Some(v) = SMALL.iter().position(|w| *w == word) {
+        let v = v as u64;
+        return (if v < 10 { Word::Unit(v) } else { Word::Teen(v) }, false);
+    }
+    if let Some(v) = SMALL_ORDINALS.iter().position(|w| *w == word) {
+        let v = v as u64;
+        return (if v < 10 { Word::Unit(v) } else { Word::Teen(v) }, true);
+    }
+    for (cardinal, ordinal, v) in TENS {
+        if word == cardinal {
+            return (Word::Tens(v), false);
+        }
+        if word == ordinal {
+            return (Word::Tens(v), true);
+        }
+    }
+    for (cardinal, ordinal, v) in SCALES {
+        if word == cardinal {
+            return (Word::Scale(v), false);
+        }
+        if word == ordinal {
+            return (Word::Scale(v), true);
+        }
+    }
+    match word.as_str() {
+        "hundred" => (Word::Hundred, false),
+        "hundredth" => (Word::Hundred, true),
+        "and" => (Word::And, false),
+        "point" => (Word::Point, false),
+        "oh" => (Word::Oh, false),
+        _ => (Word::Other, false),
+    }
+}
+
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+
+    for raw in text.split_inclusive(char::is_whitespace) {
+        let trimmed = raw.trim_end();
+        if !trimmed.is_empty() {
+            let core = trimmed.trim_matches(|c: char| !c.is_alphanumeric());
+            let core_start = trimmed.len()
+                - trimmed
+                    .trim_start_matches(|c: char| !c.is_alphanumeric())
+                    .len();
+            tokens.push(Token {
+                start: offset,
+                end: offset + trimmed.len(),
+                core,
+                prefix: &trimmed[..core_start],
+                suffix: &trimmed[core_start + core.len()..],
+            });
+        }
+        offset += raw.len();
+    }
+
+    tokens
+}
+
+fn pieces_for(tokens: &[Token<'_>]) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    for (index, token) in tokens.iter().enumerate() {
+        let parts: Vec<&str> = token.core.split('-').collect();
+        let count = parts.len();
+        for (i, part) in parts.into_iter().enumerate() {
+            let (word, ordinal) = if part.is_empty() || count > 2 {
+                (Word::Other, false)
+            } else {
+                classify(part)
+            };
+            pieces.push(Piece {
+                word,
+                ordinal,
+                token: index,
+                last_in_token: i + 1 == count,
+            });
+        }
+    }
+    pieces
+}
+
+/// A parsed number spanning `pieces[start..end]`
+#[derive(Debug, Clone, PartialEq)]
+struct Parsed {
+    value: u64,
+    decimals: Option<String>,
+    ordinal: bool,
+    end: usize,
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: &'a [Token<'a>],
+    pieces: &'a [Piece],
+}
+
+impl Parser<'_> {
+    fn word(&self, i: usize) -> Word {
+        self.pieces.get(i).map_or(Word::Other, |p| p.word)
+    }
+
+    /// Whether `pieces[i]` may continue a number that started before it: a new token
+    /// must not be separated by punctuation ("five, six") or a line break
+    fn can_continue(&self, i: usize) -> bool {
+        let Some(piece) = self.pieces.get(i) else {
+            return false;
+        };
+        let prev = &self.pieces[i - 1];
+        if prev.token == piece.token {
+            return true;
+        }
+        let (before, after) = (&self.tokens[prev.token], &self.tokens[piece.token]);
+        before.suffix.is_empty()
+            && after.prefix.is_empty()
+            && !self.text[before.end..after.start].contains('\n')
+    }
+
+    fn is_number_word(word: Word) -> bool {
+        matches!(
+            word,
+            Word::Unit(_) | Word::Teen(_) | Word::Tens(_) | Word::Hundred | Word::Scale(_)
+        )
+    }
+
+    /// Year-style pairs: "nineteen eighty four", "twenty twenty", "nineteen oh five"
+    fn parse_year(&self, start: usize) -> Option<Parsed> {
+        let first = self.pieces.get(start)?;
+        let century = match first.word {
+            Word::Teen(v) if v >= 13 => v,
+            Word::Tens(20) => 20,
+            _ => return None,
+        };
+        if first.ordinal || !first.last_in_token || !self.can_continue(start + 1) {
+            return None;
+        }
+
+        let i = start + 1;
+        let (rest, end) = match self.word(i) {
+            Word::Teen(v) => (v, i + 1),
+            Word::Tens(v) => match self.word(i + 1) {
+                Word::Unit(u) if u > 0 && self.can_continue(i + 1) => (v + u, i + 2),
+                _ => (v, i + 1),
+            },
+            Word::Oh if self.can_continue(i + 1) => match self.word(i + 1) {
+                Word::Unit(u) if u > 0 => (u, i + 2),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let last = &self.pieces[end - 1];
+        if last.ordinal || !last.last_in_token {
+            return None;
+        }
+        // "twenty twenty four hundred" is not a year
+        if self.can_continue(end) && Self::is_number_word(self.word(end)) {
+            return None;
+        }
+
+        Some(Parsed {
+            value: century * 100 + rest,
+            decimals: None,
+            ordinal: false,
+            end,
+        })
+    }
+
+    fn parse_integer(&self, start: usize) -> Option<Parsed> {
+        let mut total = 0u64;
+        let mut current = 0u64;
+        let mut last_scale = u64::MAX;
+        let mut prev: Option<Word> = None;
+        let mut best: Option<Parsed> = None;
+        let mut i = start;
+
+        while i < self.pieces.len() {
+            if i > start && !self.can_continue(i) {
+                break;
+            }
+            let piece = self.pieces[i];
+
+            let accepted = match (prev, piece.word) {
+                // Zero only stands alone
+                (None, Word::Unit(0)) => true,
+                (_, Word::Unit(0)) => false,
+                (None | Some(Word::Hundred | Word::Scale(_) | Word::And), Word::Unit(v)) => {
+                    current += v;
+                    true
+                }
+                (Some(Word::Tens(_)), Word::Unit(v)) => {
+                    current += v;
+                    true
+                }
+                (
+                    None | Some(Word::Hundred | Word::Scale(_) | Word::And),
+                    Word::Teen(v) | Word::Tens(v),
+                ) => {
+                    current += v;
+                    true
+                }
+                (Some(Word::Unit(_) | Word::Teen(_) | Word::Tens(_)), Word::Hundred)
+                    if (1..100).contains(&current) =>
+                {
+                    current *= 100;
+                    true
+                }
+                (
+                    Some(Word::Unit(_) | Word::Teen(_) | Word::Tens(_) | Word::Hundred),
+                    Word::Scale(scale),
+                ) if current > 0 && scale < last_scale => {
+                    total += current * scale;
+                    current = 0;
+                    last_scale = scale;
+                    true
+                }
+                // "one hundred and one", "two thousand and five"
+                (Some(Word::Hundred | Word::Scale(_)), Word::And) => {
+                    matches!(
+                        self.word(i + 1),
+                        Word::Unit(1..) | Word::Teen(_) | Word::Tens(_)
+                    ) && self.can_continue(i + 1)
+                }
+                _ => false,
+            };
+
+            if !accepted {
+                break;
+            }
+
+            prev = Some(piece.word);
+            i += 1;
+
+            if piece.word != Word::And && piece.last_in_token {
+                best = Some(Parsed {
+                    value: total + current,
+                    decimals: None,
+                    ordinal: piece.ordinal,
+                    end: i,
+                });
+            }
+            if piece.ordinal || piece.word == Word::Unit(0) {
+                break;
+            }
+        }
+
+        best
+    }
+
+    /// "three point one four": single digit words after "point"
+    fn parse_decimals(&self, parsed: &mut Parsed) {
+        if parsed.ordinal || self.word(parsed.end) != Word::Point || !self.can_continue(parsed.end)
+        {
+            return;
+        }
+
+        let mut digits = String::new();
+        let mut i = parsed.end + 1;
+        while self.can_continue(i) {
+            let piece = self.pieces[i];
+            match piece.word {
+                Word::Unit(d) if !piece.ordinal && piece.last_in_token => {
+                    digits.push_str(&d.to_string());
+                    i += 1;
+                }
+                Word::Oh if piece.last_in_token => {
+                    digits.push('0');
+                    i += 1;
+                }
+                _ => break,
+            }
+        }
+
+        if !digits.is_empty() {
+            parsed.decimals = Some(digits);
+            parsed.end = i;
+        }
+    }
+
+    fn parse(&self, start: usize) -> Option<Parsed> {
+        if let Some(year) = self.parse_year(start) {
+            return Some(year);
+        }
+        let mut parsed = self.parse_integer(start)?;
+        self.parse_decimals(&mut parsed);
+        Some(parsed)
+    }
+
+    /// Lowercased word of the token right after a number ending at `piece_end`, for units
+    /// like "percent"; `None` if it is punctuation-separated or part of a hyphenated word
+    fn next_token_word(&self, piece_end: usize) -> Option<(usize, String)> {
+        let piece = self.pieces.get(piece_end)?;
+        if !self.can_continue(piece_end) || !piece.last_in_token {
+            return None;
+        }
+        Some((piece.token, self.tokens[piece.token].core.to_lowercase()))
+    }
+}
+
+fn format_integer(value: u64) -> String {
+    let digits = value.to_string();
+    // Group thousands only for large amounts so years like 2024 stay intact
+    if value < 10_000 {
+        return digits;
+    }
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+fn ordinal_suffix(value: u64) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+fn format_number(parsed: &Parsed) -> String {
+    let mut out = format_integer(parsed.value);
+    if let Some(decimals) = &parsed.decimals {
+        out.push('.');
+        out.push_str(decimals);
+    }
+    if parsed.ordinal {
+        out.push_str(ordinal_suffix(parsed.value));
+    }
+    out
+}
+
+/// Convert spoken English numbers in `text` to digits, leaving whitespace and
+/// anything that isn't a confidently parsed number untouched
+pub fn normalize_spoken_numbers(text: &str) -> String {
+    let tokens = tokenize(text);
+    let pieces = pieces_for(&tokens);
+    let parser = Parser {
+        text,
+        tokens: &tokens,
+        pieces: &pieces,
+    };
+
+    let mut output = String::with_capacity(text.len());
+    let mut copied_to = 0;
+    let mut i = 0;
+
+    while i < pieces.len() {
+        // Numbers start at a token boundary
+        let starts_token = i == 0 || pieces[i - 1].token != pieces[i].token;
+        let parsed = if starts_token { parser.parse(i) } else { None };
+        let Some(parsed) = parsed else {
+            i += 1;
+            continue;
+        };
+
+        let first_token = pieces[i].token;
+        let mut last_token = pieces[parsed.end - 1].token;
+        let mut end = parsed.end;
+        let number = format_number(&parsed);
+
+        let unit = if parsed.ordinal {
+            None
+        } else {
+            parser.next_token_word(parsed.end)
+        };
+        let replacement = match unit.as_ref().map(|(t, w)| (*t, w.as_str())) {
+            Some((token, "percent")) => {
+                last_token = token;
+                end += 1;
+                Some(format!("{}%", number))
+            }
+            Some((token, "dollars" | "dollar")) => {
+                last_token = token;
+                end += 1;
+                // "five dollars and fifty cents"
+                let cents = (parser.word(end) == Word::And && parser.can_continue(end))
+                    .then(|| parser.parse_integer(end + 1))
+                    .flatten()
+                    .filter(|c| {
+                        parsed.decimals.is_none() && !c.ordinal && (1..100).contains(&c.value)
+                    })
+                    .and_then(|c| {
+                        let (token, word) = parser.next_token_word(c.end)?;
+                        matches!(word.as_str(), "cents" | "cent").then_some((c, token))
+                    });
+                match cents {
+                    Some((c, token)) => {
+                        last_token = token;
+                        end = c.end + 1;
+                        Some(format!("${}.{:02}", number, c.value))
+                    }
+                    None => Some(format!("${}", number)),
+                }
+            }
+            Some((token, "euros" | "euro")) => {
+                last_token = token;
+                end += 1;
+                Some(format!("€{}", number))
+            }
+            _ => {
+                let convert = parsed.decimals.is_some() || parsed.value > MAX_SPELLED_OUT;
+                convert.then_some(number)
+            }
+        };
+
+        match replacement {
+            Some(replacement) => {
+                let first = &tokens[first_token];
+                let last = &tokens[last_token];
+                output.push_str(&text[copied_to..first.start]);
+                output.push_str(first.prefix);
+                output.push_str(&replacement);
+                output.push_str(last.suffix);
+                copied_to = last.end;
+                i = end;
+            }
+            None => i = parsed.end,
+        }
+    }
+
+    output.push_str(&text[copied_to..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phrase_conversions() {
+        let cases = [
+            // Cardinals
+            ("twenty", "20"),
+            ("eleven people", "11 people"),
+            ("twenty four hours", "24 hours"),
+            ("twenty-four hours", "24 hours"),
+            ("ninety nine bottles", "99 bottles"),
+            ("one hundred", "100"),
+            ("one hundred and one", "101"),
+            ("one hundred and one dalmatians", "101 dalmatians"),
+            ("three hundred forty two", "342"),
+            ("two thousand and five", "2005"),
+            ("three thousand four hundred", "3400"),
+            ("twenty five hundred", "2500"),
+            ("one million two hundred thousand", "1,200,000"),
+            ("fifty thousand", "50,000"),
+            // Years
+            ("twenty twenty four", "2024"),
+            ("in nineteen eighty four", "in 1984"),
+            ("nineteen ninety-nine", "1999"),
+            ("twenty twenty", "2020"),
+            ("twenty ten", "2010"),
+            ("nineteen oh five", "1905"),
+            ("nineteen hundred", "1900"),
+            // Ordinals
+            ("the twenty first century", "the 21st century"),
+            ("his eleventh birthday", "his 11th birthday"),
+            ("the twelfth", "the 12th"),
+            ("twenty-third", "23rd"),
+            ("one hundredth", "100th"),
+            ("the forty second street", "the 42nd street"),
+            // Decimals
+            ("three point one four", "3.14"),
+            ("zero point five", "0.5"),
+            ("two point oh", "2.0"),
+            // Currency and percentages
+            ("five dollars", "$5"),
+            ("one dollar", "$1"),
+            ("twenty dollars and fifty cents", "$20.50"),
+            ("five dollars and five cents", "$5.05"),
+            ("three hundred euros", "€300"),
+            ("fifty percent", "50%"),
+            ("five percent", "5%"),
+            ("three point five percent", "3.5%"),
+            // Punctuation and whitespace are preserved
+            ("It costs five dollars.", "It costs $5."),
+            ("(twenty four)", "(24)"),
+            ("twenty\nfour", "20\nfour"),
+            ("Twenty four, please", "24, please"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                normalize_spoken_numbers(input),
+                expected,
+                "input: {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_ambiguous_phrases_are_untouched() {
+        let unchanged = [
+            "one of them",
+            "I have two cats",
+            "first of all",
+            "wait a second",
+            "the third option",
+            "ten minutes",
+            "zero",
+            "a hundred people",
+            "hundreds of people",
+            "five, six",
+            "one one",
+            "twenty-something",
+            "and then",
+            "point taken",
+            "oh well",
+            "we won one",
+            "five cents",
+            "one hundredths",
+            "",
+        ];
+
+        for input in unchanged {
+            assert_eq!(normalize_spoken_numbers(input), input, "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_mixed_sentence() {
+        assert_eq!(
+            normalize_spoken_numbers(
+                "In twenty twenty three we grew forty percent and hired twelve people, one of them remote."
+            ),
+            "In 2023 we grew 40% and hired 12 people, one of them remote."
+        );
+    }
+}
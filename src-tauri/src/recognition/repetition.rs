@@ -0,0 +1,177 @@
+//! Collapse Whisper's looping hallucinations ("thank you thank you thank you thank you").
+//!
+//! Only runs of the same phrase long enough to be implausible as speech are touched:
+//! a single word has to repeat six times in a row, two words four times and longer
+//! phrases three times. "no no no" or "very very good" stay as they are.
+
+/// Longest phrase, in words, checked for looping
+const MAX_PHRASE_WORDS: usize = 12;
+
+/// Back-to-back repetitions of an `n`-word phrase that count as a loop
+fn min_repeats(n: usize) -> usize {
+    match n {
+        1 => 6,
+        2 => 4,
+        _ => 3,
+    }
+}
+
+/// A word as compared for repetition: case and surrounding punctuation ignored
+fn normalize(word: &str) -> String {
+    let trimmed = word.trim_matches(|c: char| !c.is_alphanumeric());
+    if trimmed.is_empty() {
+        word.to_string()
+    } else {
+        trimmed.to_lowercase()
+    }
+}
+
+/// Byte offset where each whitespace-separated word of `text` starts, and the word
+fn words_with_offsets(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(from)) => {
+                words.push((from, &text[from..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(from) = start {
+        words.push((from, &text[from..]));
+    }
+    words
+}
+
+/// Keep one copy of each looping phrase. The kept copy ends with the last
+/// repetition's final word, so a closing period survives. Only the repeated
+/// span is cut out; the rest of the text keeps its line breaks and spacing.
+pub fn collapse_repeated_phrases(text: &str) -> String {
+    let words = words_with_offsets(text);
+    let keys: Vec<String> = words.iter().map(|(_, w)| normalize(w)).collect();
+
+    // Byte ranges to cut: from the first copy's last word up to the last copy's
+    // last word, leaving the first copy's leading words and the final word
+    let mut cuts = Vec::new();
+    let mut removed_words = 0;
+    let mut i = 0;
+    while i < words.len() {
+        let repeat = (1..=MAX_PHRASE_WORDS)
+            .take_while(|n| i + n * min_repeats(*n) <= words.len())
+            .map(|n| (n, repeat_count(&keys, i, n)))
+            .find(|&(n, count)| count >= min_repeats(n));
+
+        match repeat {
+            Some((n, count)) => {
+                let last_word = i + n * count - 1;
+                cuts.push(words[i + n - 1].0..words[last_word].0);
+                removed_words += n * (count - 1);
+                i = last_word + 1;
+            }
+            None => i += 1,
+        }
+    }
+
+    if cuts.is_empty() {
+        return text.to_string();
+    }
+    log::info!(
+        "Collapsed repeated phrases: {} words -> {}",
+        words.len(),
+        words.len() - removed_words
+    );
+
+    let mut output = String::with_capacity(text.len());
+    let mut kept_from = 0;
+    for cut in cuts {
+        output.push_str(&text[kept_from..cut.start]);
+        kept_from = cut.end;
+    }
+    output.push_str(&text[kept_from..]);
+    output
+}
+
+/// How many times the `n` words at `start` occur back to back
+fn repeat_count(keys: &[String], start: usize, n: usize) -> usize {
+    let phrase = &keys[start..start + n];
+    keys[start..]
+        .chunks_exact(n)
+        .take_while(|chunk| *chunk == phrase)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapses_looping_phrases() {
+        assert_eq!(
+            collapse_repeated_phrases("Thank you. Thank you. Thank you. Thank you. Thank you."),
+            "Thank you."
+        );
+        assert_eq!(
+            collapse_repeated_phrases(
+                "So the meeting is at three. I'll see you there. I'll see you there. \
+                 I'll see you there. I'll see you there."
+            ),
+            "So the meeting is at three. I'll see you there."
+        );
+        // Loops mid-transcript keep what comes after
+        assert_eq!(
+            collapse_repeated_phrases("okay okay okay okay okay okay okay okay let's start"),
+            "okay let's start"
+        );
+        assert_eq!(
+            collapse_repeated_phrases(
+                "Please subscribe to my channel please subscribe to my channel \
+                 please subscribe to my channel"
+            ),
+            "Please subscribe to my channel"
+        );
+    }
+
+    #[test]
+    fn test_keeps_ordinary_repetition() {
+        for text in [
+            "no no no, that's not what I meant",
+            "it was very very very good",
+            "I think that that is fine",
+            "ha ha ha ha ha",
+            "thank you thank you thank you so much",
+            "bye bye",
+            "New York, New York",
+        ] {
+            assert_eq!(collapse_repeated_phrases(text), text);
+        }
+    }
+
+    #[test]
+    fn test_untouched_text_keeps_its_spacing() {
+        let text = "First line.\nSecond  line.";
+        assert_eq!(collapse_repeated_phrases(text), text);
+        assert_eq!(collapse_repeated_phrases(""), "");
+    }
+
+    #[test]
+    fn test_collapse_keeps_surrounding_layout() {
+        assert_eq!(
+            collapse_repeated_phrases(
+                "Dear team,\n\nThank you.\nThank you.\nThank you.\nThank you.\n\n\tBest,  Sam"
+            ),
+            "Dear team,\n\nThank you.\n\n\tBest,  Sam"
+        );
+        assert_eq!(
+            collapse_repeated_phrases("Line one.\nokay okay okay okay okay okay\nLine  three."),
+            "Line one.\nokay\nLine  three."
+        );
+        // Spacing inside the kept copy is the first copy's own
+        assert_eq!(
+            collapse_repeated_phrases("see  you soon see you soon see you soon"),
+            "see  you soon"
+        );
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+
+/// How an inserted symbol attaches to the surrounding words
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spacing {
+    /// Attaches to the previous word: "word,"
+    Left,
+    /// Attaches to the next word: "(word"
+    Right,
+    /// No space on either side: "well-known"
+    Join,
+    /// Stands alone between spaces: "a - b"
+    Word,
+    /// Line break; surrounding spaces are dropped
+    Newline,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CommandAction {
+    Insert {
+        text: String,
+        spacing: Spacing,
+        ends_sentence: bool,
+    },
+    /// Uppercase the next word
+    AllCaps,
+    /// Insert the next word verbatim, even if it is a command
+    Literal,
+}
+
+#[derive(Debug, Clone)]
+struct VoiceCommand {
+    words: Vec<String>,
+    action: CommandAction,
+}
+
+fn insert(text: &str, spacing: Spacing, ends_sentence: bool) -> CommandAction {
+    CommandAction::Insert {
+        text: text.to_string(),
+        spacing,
+        ends_sentence,
+    }
+}
+
+/// Built-in spoken commands for `language`. Only English is available so far.
+fn builtin_commands(language: Option<&str>) -> Vec<(&'static str, CommandAction)> {
+    // Unknown language (auto-detect without a result) gets the English map,
+    // since its phrases can only match English speech anyway
+    let is_english = language.is_none_or(|l| l.eq_ignore_ascii_case("en") || l.starts_with("en-"));
+    if !is_english {
+        return Vec::new();
+    }
+
+    vec![
+        ("new paragraph", insert("\n\n", Spacing::Newline, true)),
+        ("new line", insert("\n", Spacing::Newline, true)),
+        ("newline", insert("\n", Spacing::Newline, true)),
+        ("period", insert(".", Spacing::Left, true)),
+        ("full stop", insert(".", Spacing::Left, true)),
+        ("question mark", insert("?", Spacing::Left, true)),
+        ("exclamation mark", insert("!", Spacing::Left, true)),
+        ("exclamation point", insert("!", Spacing::Left, true)),
+        ("comma", insert(",", Spacing::Left, false)),
+        ("colon", insert(":", Spacing::Left, false)),
+        ("semicolon", insert(";", Spacing::Left, false)),
+        ("open paren", insert("(", Spacing::Right, false)),
+        ("open parenthesis", insert("(", Spacing::Right, false)),
+        ("close paren", insert(")", Spacing::Left, false)),
+        ("close parenthesis", insert(")", Spacing::Left, false)),
+        ("open quote", insert("\"", Spacing::Right, false)),
+        ("close quote", insert("\"", Spacing::Left, false)),
+        ("end quote", insert("\"", Spacing::Left, false)),
+        ("hyphen", insert("-", Spacing::Join, false)),
+        ("dash", insert("-", Spacing::Word, false)),
+        ("all caps", CommandAction::AllCaps),
+        ("literal", CommandAction::Literal),
+    ]
+}
+
+/// Spacing for a user-defined replacement: single punctuation marks attach like the
+/// built-ins, anything else (":)", "->", words) stands alone
+fn infer_spacing(text: &str) -> Spacing {
+    let mut chars = text.chars();
+    let single = match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    };
+
+    if text.contains('\n') && text.trim().is_empty() {
+        Spacing::Newline
+    } else if single.is_some_and(|c| ".,;:!?)]}".contains(c)) {
+        Spacing::Left
+    } else if single.is_some_and(|c| "([{".contains(c)) {
+        Spacing::Right
+    } else {
+        Spacing::Word
+    }
+}
+
+/// Lowercase a spoken token and drop the punctuation the engine put around it
+fn normalize_token(token: &str) -> String {
+    token
+        .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+        .to_lowercase()
+}
+
+fn split_phrase(phrase: &str) -> Vec<String> {
+    phrase.split_whitespace().map(normalize_token).collect()
+}
+
+/// Post-processor that turns spoken punctuation ("comma", "new line") into characters
+pub struct VoiceCommandProcessor {
+    /// Longest phrases first so "new paragraph" wins over shorter prefixes
+    commands: Vec<VoiceCommand>,
+}
+
+impl VoiceCommandProcessor {
+    /// Build the command set for `language`. Entries in `custom` add commands or
+    /// override built-ins with the same phrase; an empty value disables a phrase.
+    pub fn new(language: Option<&str>, custom: &HashMap<String, String>) -> Self {
+        let mut commands: Vec<VoiceCommand> = builtin_commands(language)
+            .into_iter()
+            .map(|(phrase, action)| VoiceCommand {
+                words: split_phrase(phrase),
+                action,
+            })
+            .collect();
+
+        for (phrase, text) in custom {
+            let words = split_phrase(phrase);
+            if words.iter().all(|w| w.is_empty()) {
+                continue;
+            }
+            commands.retain(|command| command.words != words);
+            if !text.is_empty() {
+                commands.push(VoiceCommand {
+                    words,
+                    action: insert(text, infer_spacing(text), false),
+                });
+            }
+        }
+
+        commands.sort_by_key(|command| std::cmp::Reverse(command.words.len()));
+        Self { commands }
+    }
+
+    fn match_at(&self, normalized: &[String]) -> Option<&VoiceCommand> {
+        self.commands.iter().find(|command| {
+            normalized.len() >= command.words.len()
+                && command
+                    .words
+                    .iter()
+                    .zip(normalized)
+                    .all(|(word, token)| word == token)
+        })
+    }
+
+    /// Replace spoken commands in `text`
+    pub fn process(&self, text: &str) -> String {
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        let normalized: Vec<String> = tokens.iter().map(|t| normalize_token(t)).collect();
+
+        let mut output = Output::default();
+        let mut all_caps = false;
+        let mut literal = false;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let command = if literal {
+                None
+            } else {
+                self.match_at(&normalized[i..])
+            };
+
+            match command {
+                Some(command) => {
+                    i += command.words.len();
+                    match &command.action {
+                        CommandAction::AllCaps => all_caps = true,
+                        CommandAction::Literal => literal = true,
+                        CommandAction::Insert {
+                            text,
+                            spacing,
+                            ends_sentence,
+                        } => output.push_symbol(text, *spacing, *ends_sentence),
+                    }
+                }
+                None => {
+                    let token = if literal {
+                        // The spoken word itself, without the engine's punctuation
+                        tokens[i].trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                    } else {
+                        tokens[i]
+                    };
+                    let word = if all_caps {
+                        token.to_uppercase()
+                    } else {
+                        token.to_string()
+                    };
+                    output.push_word(&word);
+                    all_caps = false;
+                    literal = false;
+                    i += 1;
+                }
+            }
+        }
+
+        output.text
+    }
+}
+
+/// Builds the result while tracking spacing and sentence starts
+#[derive(Default)]
+struct Output {
+    text: String,
+    /// No space before the next word (after "(", "-" or a line break)
+    glue_next: bool,
+    capitalize_next: bool,
+}
+
+impl Output {
+    fn push_word(&mut self, word: &str) {
+        if !self.text.is_empty() && !self.glue_next {
+            self.text.push(' ');
+        }
+        if self.capitalize_next {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                self.text.extend(first.to_uppercase());
+                self.text.push_str(chars.as_str());
+            }
+        } else {
+            self.text.push_str(word);
+        }
+        self.glue_next = false;
+        self.capitalize_next = false;
+    }
+
+    fn push_symbol(&mut self, symbol: &str, spacing: Spacing, ends_sentence: bool) {
+        match spacing {
+            Spacing::Left => {
+                // The engine often transcribes "hello comma" as "hello, comma,"
+                let trimmed = self
+                    .text
+                    .trim_end_matches(|c: char| c.is_whitespace() || ".,;:!?".contains(c))
+                    .len();
+                self.text.truncate(trimmed);
+                self.text.push_str(symbol);
+                self.glue_next = false;
+            }
+            Spacing::Right | Spacing::Word => {
+                if !self.text.is_empty() && !self.glue_next {
+                    self.text.push(' ');
+                }
+                self.text.push_str(symbol);
+                self.glue_next = spacing == Spacing::Right;
+            }
+            Spacing::Join => {
+                let trimmed = self.text.trim_end().len();
+                self.text.truncate(trimmed);
+                self.text.push_str(symbol);
+                self.glue_next = true;
+            }
+            Spacing::Newline => {
+                let trimmed = self.text.trim_end_matches([' ', '\t']).len();
+                self.text.truncate(trimmed);
+                self.text.push_str(symbol);
+                self.glue_next = true;
+            }
+        }
+        if ends_sentence {
+            self.capitalize_next = true;
+        }
+    }
+}
+
+/// Apply spoken commands to a transcription using the built-in map for `language`
+/// plus the user's `custom` entries
+pub fn apply_voice_commands(
+    text: &str,
+    language: Option<&str>,
+    custom: &HashMap<String, String>,
+) -> String {
+    VoiceCommandProcessor::new(language, custom).process(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english(text: &str) -> String {
+        apply_voice_commands(text, Some("en"), &HashMap::new())
+    }
+
+    #[test]
+    fn test_basic_punctuation() {
+        assert_eq!(
+            english("hello comma how are you question mark"),
+            "hello, how are you?"
+        );
+        assert_eq!(
+            english("first item semicolon second colon third"),
+            "first item; second: third"
+        );
+        assert_eq!(english("see open paren below close paren"), "see (below)");
+        assert_eq!(
+            english("he said open quote hi close quote"),
+            "he said \"hi\""
+        );
+        assert_eq!(english("well hyphen known a dash b"), "well-known a - b");
+    }
+
+    #[test]
+    fn test_period_new_line() {
+        assert_eq!(
+            english("Dear team period new line thanks for waiting period"),
+            "Dear team.\nThanks for waiting."
+        );
+    }
+
+    #[test]
+    fn test_new_paragraph_is_double_newline() {
+        assert_eq!(
+            english("first part new paragraph second part"),
+            "first part\n\nSecond part"
+        );
+    }
+
+    #[test]
+    fn test_engine_punctuation_around_commands_is_dropped() {
+        assert_eq!(
+            english("Hello, comma, how are you? Question mark. New line. Fine."),
+            "Hello, how are you?\nFine."
+        );
+    }
+
+    #[test]
+    fn test_all_caps_and_literal() {
+        assert_eq!(
+            english("this is all caps urgent okay"),
+            "this is URGENT okay"
+        );
+        assert_eq!(
+            english("type the word literal comma here"),
+            "type the word comma here"
+        );
+    }
+
+    #[test]
+    fn test_custom_map_overrides_and_disables() {
+        let mut custom = HashMap::new();
+        custom.insert("smiley face".to_string(), ":)".to_string());
+        custom.insert("dash".to_string(), "—".to_string());
+        custom.insert("comma".to_string(), String::new());
+
+        assert_eq!(
+            apply_voice_commands("great smiley face a dash b comma", Some("en"), &custom),
+            "great :) a — b comma"
+        );
+    }
+
+    #[test]
+    fn test_non_english_has_no_builtin_map() {
+        assert_eq!(
+            apply_voice_commands("hola comma new line", Some("es"), &HashMap::new()),
+            "hola comma new line"
+        );
+        // Unknown language falls back to English
+        assert_eq!(
+            apply_voice_commands("hi comma there", None, &HashMap::new()),
+            "hi, there"
+        );
+    }
+
+    #[test]
+    fn test_text_without_commands_is_unchanged() {
+        let text = "Nothing to see here, just words.";
+        assert_eq!(english(text), text);
+        assert_eq!(english(""), "");
+    }
+}
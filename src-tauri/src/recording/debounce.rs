@@ -0,0 +1,114 @@
+use std::time::{Duration, Instant};
+
+use crate::RecordingMode;
+
+/// Default for `hotkey_debounce_ms`: short enough that deliberate presses never
+/// hit it, long enough to swallow key bounce and accidental double presses
+pub const DEFAULT_HOTKEY_DEBOUNCE_MS: u32 = 150;
+
+/// Drops recording hotkey presses that arrive within `interval` of the last
+/// accepted one, so a bouncy or double-pressed key can't start and immediately
+/// stop a recording. A zero interval accepts every press.
+#[derive(Debug)]
+pub struct HotkeyDebouncer {
+    interval: Duration,
+    last_press: Option<Instant>,
+}
+
+impl Default for HotkeyDebouncer {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(DEFAULT_HOTKEY_DEBOUNCE_MS as u64))
+    }
+}
+
+impl HotkeyDebouncer {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_press: None,
+        }
+    }
+
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Register a press; false when it came too soon after the previous one and
+    /// should be ignored. Ignored presses don't extend the quiet period.
+    pub fn accept(&mut self, now: Instant) -> bool {
+        let too_soon = self
+            .last_press
+            .is_some_and(|last| now.saturating_duration_since(last) < self.interval);
+        if !too_soon {
+            self.last_press = Some(now);
+        }
+        !too_soon
+    }
+}
+
+/// Whether presses in `mode` go through the debouncer. Quick push-to-talk
+/// presses are left alone while double-tap latching is on, since there a fast
+/// second press is intentional.
+pub fn debounce_applies(mode: RecordingMode, double_tap_enabled: bool) -> bool {
+    match mode {
+        RecordingMode::Toggle => true,
+        RecordingMode::PushToTalk => !double_tap_enabled,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_presses_within_interval_are_ignored() {
+        let mut debouncer = HotkeyDebouncer::new(ms(150));
+        let t0 = Instant::now();
+
+        let accepted: Vec<bool> = [0, 40, 149, 150, 200, 320, 1000]
+            .iter()
+            .map(|&offset| debouncer.accept(t0 + ms(offset)))
+            .collect();
+        // 40 and 149 bounce off the first press; 200 is within 150ms of the
+        // accepted press at 150, while 320 is not
+        assert_eq!(accepted, vec![true, false, false, true, false, true, true]);
+    }
+
+    #[test]
+    fn test_ignored_presses_do_not_extend_the_interval() {
+        let mut debouncer = HotkeyDebouncer::new(ms(150));
+        let t0 = Instant::now();
+
+        assert!(debouncer.accept(t0));
+        // Chatter every 50ms: only presses 150ms after the last accepted one count
+        assert!(!debouncer.accept(t0 + ms(50)));
+        assert!(!debouncer.accept(t0 + ms(100)));
+        assert!(debouncer.accept(t0 + ms(150)));
+    }
+
+    #[test]
+    fn test_zero_interval_and_default() {
+        let mut debouncer = HotkeyDebouncer::new(Duration::ZERO);
+        let t0 = Instant::now();
+        assert!(debouncer.accept(t0));
+        assert!(debouncer.accept(t0));
+
+        let mut debouncer = HotkeyDebouncer::default();
+        assert!(debouncer.accept(t0));
+        assert!(!debouncer.accept(t0 + ms(100)));
+        debouncer.set_interval(ms(50));
+        assert!(debouncer.accept(t0 + ms(120)));
+    }
+
+    #[test]
+    fn test_double_tap_presses_are_not_debounced() {
+        assert!(debounce_applies(RecordingMode::Toggle, false));
+        assert!(debounce_applies(RecordingMode::Toggle, true));
+        assert!(debounce_applies(RecordingMode::PushToTalk, false));
+        assert!(!debounce_applies(RecordingMode::PushToTalk, true));
+    }
+}
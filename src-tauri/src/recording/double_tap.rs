@@ -0,0 +1,248 @@
+use std::time::{Duration, Instant};
+
+/// What the push-to-talk handler should do for a key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressAction {
+    /// Regular push-to-talk press: start recording
+    Start,
+    /// Second tap of a double-tap: keep the running recording going hands-free
+    Latch,
+    /// Press while latched: stop the recording
+    StopLatched,
+}
+
+/// What the push-to-talk handler should do for a key release
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseAction {
+    /// Regular push-to-talk release: stop recording
+    Stop,
+    /// Short tap that may become a double-tap: stop after `wait` unless
+    /// `expire_tap(tap)` says a second tap arrived in the meantime
+    DeferStop { tap: u64, wait: Duration },
+    /// Release belonging to a latch/unlatch press
+    Ignore,
+}
+
+/// Timing state machine that turns a quick tap-tap of the push-to-talk key into a
+/// latched (toggle-style) session while a single hold keeps working as push-to-talk.
+///
+/// A tap is a press released within `window`; a second press within `window` of that
+/// release latches the recording started by the first tap. A zero window disables
+/// detection.
+#[derive(Debug, Default)]
+pub struct DoubleTapDetector {
+    window: Duration,
+    last_press: Option<Instant>,
+    /// Release time and id of a tap that may still become a double-tap
+    pending_tap: Option<(Instant, u64)>,
+    next_tap: u64,
+    latched: bool,
+    suppress_release: bool,
+}
+
+impl DoubleTapDetector {
+    #[cfg(test)]
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            ..Self::default()
+        }
+    }
+
+    pub fn set_window(&mut self, window: Duration) {
+        self.window = window;
+        if window.is_zero() {
+            self.reset();
+        }
+    }
+
+    /// Whether double-tap latching is on (non-zero window)
+    pub fn is_enabled(&self) -> bool {
+        !self.window.is_zero()
+    }
+
+    /// Whether the current session is latched on by a double-tap
+    #[cfg(test)]
+    pub fn is_latched(&self) -> bool {
+        self.latched
+    }
+
+    /// Forget any tap or latch, e.g. after the recording was cancelled
+    pub fn reset(&mut self) {
+        self.last_press = None;
+        self.pending_tap = None;
+        self.latched = false;
+        self.suppress_release = false;
+    }
+
+    /// Register a key press. `recording_active` is false once the session ended some
+    /// other way (ESC, pill button, error), which drops a stale latch.
+    pub fn on_press(&mut self, now: Instant, recording_active: bool) -> PressAction {
+        if !recording_active {
+            self.reset();
+        }
+
+        if self.latched {
+            self.reset();
+            self.suppress_release = true;
+            return PressAction::StopLatched;
+        }
+
+        if let Some((released_at, _)) = self.pending_tap.take() {
+            if now.saturating_duration_since(released_at) <= self.window {
+                self.last_press = None;
+                self.latched = true;
+                self.suppress_release = true;
+                return PressAction::Latch;
+            }
+        }
+
+        self.last_press = Some(now);
+        PressAction::Start
+    }
+
+    /// Register a key release
+    pub fn on_release(&mut self, now: Instant) -> ReleaseAction {
+        if self.suppress_release {
+            self.suppress_release = false;
+            return ReleaseAction::Ignore;
+        }
+
+        let is_tap = !self.window.is_zero()
+            && self
+                .last_press
+                .take()
+                .is_some_and(|pressed| now.saturating_duration_since(pressed) <= self.window);
+
+        if is_tap {
+            self.next_tap = self.next_tap.wrapping_add(1);
+            self.pending_tap = Some((now, self.next_tap));
+            ReleaseAction::DeferStop {
+                tap: self.next_tap,
+                wait: self.window,
+            }
+        } else {
+            ReleaseAction::Stop
+        }
+    }
+
+    /// Called once a deferred stop's wait is over; true when tap `tap` never became
+    /// a double-tap and the recording should now stop
+    pub fn expire_tap(&mut self, tap: u64) -> bool {
+        match self.pending_tap {
+            Some((_, pending)) if pending == tap => {
+                self.pending_tap = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(300);
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn tap_id(action: ReleaseAction) -> u64 {
+        match action {
+            ReleaseAction::DeferStop { tap, wait } => {
+                assert_eq!(wait, WINDOW);
+                tap
+            }
+            other => panic!("expected a deferred stop, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hold_stays_push_to_talk() {
+        let mut detector = DoubleTapDetector::new(WINDOW);
+        let t0 = Instant::now();
+
+        assert_eq!(detector.on_press(t0, false), PressAction::Start);
+        assert_eq!(detector.on_release(t0 + ms(2000)), ReleaseAction::Stop);
+        assert!(!detector.is_latched());
+    }
+
+    #[test]
+    fn test_double_tap_latches_until_next_press() {
+        let mut detector = DoubleTapDetector::new(WINDOW);
+        let t0 = Instant::now();
+
+        assert_eq!(detector.on_press(t0, false), PressAction::Start);
+        let tap = tap_id(detector.on_release(t0 + ms(80)));
+        assert_eq!(detector.on_press(t0 + ms(200), true), PressAction::Latch);
+        assert!(detector.is_latched());
+        // The deferred stop from the first tap is cancelled
+        assert!(!detector.expire_tap(tap));
+        assert_eq!(detector.on_release(t0 + ms(280)), ReleaseAction::Ignore);
+
+        // Hands-free until the key is pressed again
+        assert_eq!(
+            detector.on_press(t0 + ms(5000), true),
+            PressAction::StopLatched
+        );
+        assert!(!detector.is_latched());
+        assert_eq!(detector.on_release(t0 + ms(5100)), ReleaseAction::Ignore);
+
+        // Back to plain push-to-talk
+        assert_eq!(detector.on_press(t0 + ms(8000), false), PressAction::Start);
+        assert_eq!(detector.on_release(t0 + ms(9000)), ReleaseAction::Stop);
+    }
+
+    #[test]
+    fn test_single_tap_stops_after_window() {
+        let mut detector = DoubleTapDetector::new(WINDOW);
+        let t0 = Instant::now();
+
+        assert_eq!(detector.on_press(t0, false), PressAction::Start);
+        let tap = tap_id(detector.on_release(t0 + ms(100)));
+        assert!(detector.expire_tap(tap));
+        assert!(!detector.expire_tap(tap));
+
+        // A second press after the window is a fresh push-to-talk press
+        assert_eq!(detector.on_press(t0 + ms(1000), false), PressAction::Start);
+    }
+
+    #[test]
+    fn test_slow_second_press_does_not_latch() {
+        let mut detector = DoubleTapDetector::new(WINDOW);
+        let t0 = Instant::now();
+
+        assert_eq!(detector.on_press(t0, false), PressAction::Start);
+        tap_id(detector.on_release(t0 + ms(100)));
+        assert_eq!(detector.on_press(t0 + ms(500), true), PressAction::Start);
+        assert!(!detector.is_latched());
+    }
+
+    #[test]
+    fn test_stale_latch_is_dropped_when_recording_ended() {
+        let mut detector = DoubleTapDetector::new(WINDOW);
+        let t0 = Instant::now();
+
+        detector.on_press(t0, false);
+        detector.on_release(t0 + ms(50));
+        assert_eq!(detector.on_press(t0 + ms(100), true), PressAction::Latch);
+        detector.on_release(t0 + ms(150));
+
+        // Recording was cancelled with ESC; the next press starts a new session
+        assert_eq!(detector.on_press(t0 + ms(4000), false), PressAction::Start);
+        assert!(!detector.is_latched());
+    }
+
+    #[test]
+    fn test_zero_window_disables_detection() {
+        let mut detector = DoubleTapDetector::new(Duration::ZERO);
+        let t0 = Instant::now();
+
+        assert_eq!(detector.on_press(t0, false), PressAction::Start);
+        assert_eq!(detector.on_release(t0 + ms(20)), ReleaseAction::Stop);
+        assert_eq!(detector.on_press(t0 + ms(40), false), PressAction::Start);
+        assert_eq!(detector.on_release(t0 + ms(60)), ReleaseAction::Stop);
+    }
+}
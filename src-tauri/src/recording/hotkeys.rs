@@ -1,8 +1,11 @@
 use crate::commands::audio::{start_recording, stop_recording, RecorderState};
+use crate::recording::debounce::debounce_applies;
+use crate::recording::double_tap::{PressAction, ReleaseAction};
 use crate::recording::escape_handler::handle_escape_key_press;
 use crate::{get_recording_state, update_recording_state, AppState, RecordingMode, RecordingState};
 use std::sync::atomic::Ordering;
-use tauri::Manager;
+use std::time::Instant;
+use tauri::{Emitter, Manager};
 use tauri_plugin_global_shortcut::{Shortcut, ShortcutState};
 
 /// Handle global shortcut events for recording
@@ -62,28 +65,67 @@ pub fn handle_global_shortcut(
         RecordingMode::PushToTalk => is_recording_shortcut || is_ptt_shortcut,
     };
 
+    if should_handle
+        && event_state == ShortcutState::Pressed
+        && is_bounced_press(&app_state, recording_mode)
+    {
+        log::debug!("Ignoring recording hotkey press within hotkey_debounce_ms of the last one");
+        if recording_mode == RecordingMode::Toggle {
+            crate::commands::audio::pill_toast(app, "Hold on...", 1000);
+        }
+        return;
+    }
+
     if should_handle {
         let current_state = get_recording_state(app);
-        handle_recording_shortcut(app, &app_state, recording_mode, current_state, event_state);
+        handle_recording_shortcut(
+            app,
+            &app_state,
+            shortcut,
+            recording_mode,
+            current_state,
+            event_state,
+        );
     } else if !is_recording_shortcut && !is_ptt_shortcut {
         handle_non_recording_shortcut(app, shortcut, event_state);
     }
 }
 
+/// Whether a recording hotkey press came too soon after the previous one.
+/// Push-to-talk presses with double-tap latching on are never dropped.
+fn is_bounced_press(app_state: &AppState, recording_mode: RecordingMode) -> bool {
+    let double_tap_enabled = app_state
+        .double_tap
+        .lock()
+        .map(|detector| detector.is_enabled())
+        .unwrap_or(false);
+    if !debounce_applies(recording_mode, double_tap_enabled) {
+        return false;
+    }
+    match app_state.hotkey_debounce.lock() {
+        Ok(mut debouncer) => !debouncer.accept(Instant::now()),
+        Err(e) => {
+            log::error!("Failed to lock hotkey_debounce: {}", e);
+            false
+        }
+    }
+}
+
 /// Handle recording-related shortcuts (toggle or PTT)
 fn handle_recording_shortcut(
     app: &tauri::AppHandle,
     app_state: &AppState,
+    shortcut: &Shortcut,
     recording_mode: RecordingMode,
     current_state: RecordingState,
     event_state: ShortcutState,
 ) {
     match recording_mode {
         RecordingMode::Toggle => {
-            handle_toggle_mode(app, app_state, current_state, event_state);
+            handle_toggle_mode(app, app_state, shortcut, current_state, event_state);
         }
         RecordingMode::PushToTalk => {
-            handle_ptt_mode(app, app_state, current_state, event_state);
+            handle_ptt_mode(app, app_state, shortcut, current_state, event_state);
         }
     }
 }
@@ -92,6 +134,7 @@ fn handle_recording_shortcut(
 fn handle_toggle_mode(
     app: &tauri::AppHandle,
     app_state: &AppState,
+    shortcut: &Shortcut,
     current_state: RecordingState,
     event_state: ShortcutState,
 ) {
@@ -99,35 +142,6 @@ fn handle_toggle_mode(
         return;
     }
 
-    let should_throttle = {
-        let now = std::time::Instant::now();
-        match app_state.last_toggle_press.lock() {
-            Ok(mut last_press) => {
-                if let Some(last) = *last_press {
-                    if now.duration_since(last).as_millis() < 300 {
-                        log::debug!("Toggle: Throttling hotkey press (too fast)");
-                        true
-                    } else {
-                        *last_press = Some(now);
-                        false
-                    }
-                } else {
-                    *last_press = Some(now);
-                    false
-                }
-            }
-            Err(e) => {
-                log::error!("Failed to lock last_toggle_press: {}", e);
-                false
-            }
-        }
-    };
-
-    if should_throttle {
-        crate::commands::audio::pill_toast(app, "Hold on...", 1000);
-        return;
-    }
-
     match current_state {
         RecordingState::Idle | RecordingState::Error => {
             log::info!("Toggle: Starting recording via hotkey");
@@ -151,6 +165,7 @@ fn handle_toggle_mode(
         }
         RecordingState::Recording => {
             log::info!("Toggle: Stopping recording via hotkey");
+            check_skip_enhancement_modifier(app_state, shortcut);
             let app_handle = app.clone();
             tauri::async_runtime::spawn(async move {
                 let recorder_state = app_handle.state::<RecorderState>();
@@ -164,10 +179,13 @@ fn handle_toggle_mode(
     }
 }
 
-/// Handle push-to-talk mode recording (hold to record, release to stop)
+/// Handle push-to-talk mode recording (hold to record, release to stop).
+/// A quick double-tap latches the recording on until the key is pressed again;
+/// toggle mode sessions are latched already and don't go through this.
 fn handle_ptt_mode(
     app: &tauri::AppHandle,
     app_state: &AppState,
+    shortcut: &Shortcut,
     current_state: RecordingState,
     event_state: ShortcutState,
 ) {
@@ -176,43 +194,124 @@ fn handle_ptt_mode(
             log::info!("PTT: Key pressed");
             app_state.ptt_key_held.store(true, Ordering::Relaxed);
 
-            if matches!(current_state, RecordingState::Idle | RecordingState::Error) {
-                log::info!("PTT: Starting recording");
-                let app_handle = app.clone();
-                tauri::async_runtime::spawn(async move {
-                    let recorder_state = app_handle.state::<RecorderState>();
-                    match start_recording(app_handle.clone(), recorder_state).await {
-                        Ok(_) => log::info!("PTT: Recording started successfully"),
-                        Err(e) => {
-                            log::error!("PTT: Error starting recording: {}", e);
-                            update_recording_state(&app_handle, RecordingState::Error, Some(e));
-                        }
+            let recording_active = matches!(
+                current_state,
+                RecordingState::Recording | RecordingState::Starting
+            );
+            let action = match app_state.double_tap.lock() {
+                Ok(mut detector) => detector.on_press(Instant::now(), recording_active),
+                Err(_) => PressAction::Start,
+            };
+
+            match action {
+                PressAction::Latch => {
+                    log::info!("PTT: Double-tap detected, recording latched on");
+                }
+                PressAction::StopLatched => {
+                    log::info!("PTT: Stopping latched recording");
+                    spawn_ptt_stop(app);
+                }
+                PressAction::Start => {
+                    if matches!(current_state, RecordingState::Idle | RecordingState::Error) {
+                        log::info!("PTT: Starting recording");
+                        let app_handle = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            let recorder_state = app_handle.state::<RecorderState>();
+                            match start_recording(app_handle.clone(), recorder_state).await {
+                                Ok(_) => log::info!("PTT: Recording started successfully"),
+                                Err(e) => {
+                                    log::error!("PTT: Error starting recording: {}", e);
+                                    update_recording_state(
+                                        &app_handle,
+                                        RecordingState::Error,
+                                        Some(e),
+                                    );
+                                }
+                            }
+                        });
                     }
-                });
+                }
             }
         }
         ShortcutState::Released => {
             log::info!("PTT: Key released");
             app_state.ptt_key_held.store(false, Ordering::Relaxed);
-
             if matches!(
                 current_state,
                 RecordingState::Recording | RecordingState::Starting
             ) {
-                log::info!("PTT: Stopping recording");
-                let app_handle = app.clone();
-                tauri::async_runtime::spawn(async move {
-                    let recorder_state = app_handle.state::<RecorderState>();
-                    match stop_recording(app_handle.clone(), recorder_state).await {
-                        Ok(_) => log::info!("PTT: Recording stopped successfully"),
-                        Err(e) => log::error!("PTT: Error stopping recording: {}", e),
+                check_skip_enhancement_modifier(app_state, shortcut);
+            }
+
+            let action = match app_state.double_tap.lock() {
+                Ok(mut detector) => detector.on_release(Instant::now()),
+                Err(_) => ReleaseAction::Stop,
+            };
+
+            match action {
+                ReleaseAction::Ignore => {}
+                ReleaseAction::DeferStop { tap, wait } => {
+                    // Short tap: give a second tap the chance to latch before stopping
+                    let app_handle = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        tokio::time::sleep(wait).await;
+                        let app_state = app_handle.state::<AppState>();
+                        let expired = app_state
+                            .double_tap
+                            .lock()
+                            .map(|mut detector| detector.expire_tap(tap))
+                            .unwrap_or(true);
+                        if expired
+                            && matches!(
+                                get_recording_state(&app_handle),
+                                RecordingState::Recording | RecordingState::Starting
+                            )
+                        {
+                            log::info!("PTT: Single tap, stopping recording");
+                            spawn_ptt_stop(&app_handle);
+                        }
+                    });
+                }
+                ReleaseAction::Stop => {
+                    if matches!(
+                        current_state,
+                        RecordingState::Recording | RecordingState::Starting
+                    ) {
+                        log::info!("PTT: Stopping recording");
+                        spawn_ptt_stop(app);
                     }
-                });
+                }
             }
         }
     }
 }
 
+/// Flag the recording being stopped to skip AI enhancement when the configured
+/// modifier is held (and isn't simply part of the shortcut itself)
+fn check_skip_enhancement_modifier(app_state: &AppState, shortcut: &Shortcut) {
+    let modifier = app_state
+        .skip_enhancement_modifier
+        .lock()
+        .ok()
+        .and_then(|guard| *guard);
+    if let Some(modifier) = modifier {
+        if !modifier.is_part_of(shortcut.mods) && modifier.is_held() {
+            app_state.request_skip_enhancement();
+        }
+    }
+}
+
+fn spawn_ptt_stop(app: &tauri::AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let recorder_state = app_handle.state::<RecorderState>();
+        match stop_recording(app_handle.clone(), recorder_state).await {
+            Ok(_) => log::info!("PTT: Recording stopped successfully"),
+            Err(e) => log::error!("PTT: Error stopping recording: {}", e),
+        }
+    });
+}
+
 /// Handle non-recording shortcuts (e.g., ESC key)
 fn handle_non_recording_shortcut(
     app: &tauri::AppHandle,
@@ -235,6 +334,79 @@ fn handle_non_recording_shortcut(
         escape_shortcut
     );
 
+    let Some(app_state) = app.try_state::<AppState>() else {
+        return;
+    };
+
+    if is_slot_shortcut(&app_state.preset_cycle_shortcut, shortcut) {
+        if event_state == ShortcutState::Pressed {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = crate::commands::ai::cycle_enhancement_preset(app_handle).await {
+                    log::error!("Failed to cycle enhancement preset: {}", e);
+                }
+            });
+        }
+        return;
+    }
+
+    if is_slot_shortcut(&app_state.raw_insert_shortcut, shortcut) {
+        if event_state == ShortcutState::Pressed {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) =
+                    crate::commands::audio::insert_last_raw_transcription(app_handle).await
+                {
+                    log::warn!("Failed to insert raw transcription: {}", e);
+                }
+            });
+        }
+        return;
+    }
+
+    if is_slot_shortcut(&app_state.copy_last_shortcut, shortcut) {
+        if event_state == ShortcutState::Pressed {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = crate::commands::audio::copy_last_transcription(app_handle).await {
+                    log::warn!("Failed to copy last transcription: {}", e);
+                }
+            });
+        }
+        return;
+    }
+
+    let model_hotkey = app_state
+        .model_shortcuts
+        .lock()
+        .ok()
+        .and_then(|registered| {
+            registered
+                .iter()
+                .find(|(registered, _)| registered == shortcut)
+                .map(|(_, hotkey)| hotkey.clone())
+        });
+    if let Some(model_hotkey) = model_hotkey {
+        if event_state == ShortcutState::Pressed {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = crate::commands::settings::set_model_from_hotkey(
+                    app_handle.clone(),
+                    model_hotkey,
+                )
+                .await
+                {
+                    log::error!("Failed to switch model via hotkey: {}", e);
+                    let _ = app_handle.emit(
+                        "tray-action-error",
+                        &format!("Failed to change model: {}", e),
+                    );
+                }
+            });
+        }
+        return;
+    }
+
     if shortcut == &escape_shortcut {
         log::info!("ESC key detected in global handler");
 
@@ -246,3 +418,10 @@ fn handle_non_recording_shortcut(
         });
     }
 }
+
+/// Whether `shortcut` is the one currently registered in an optional action slot
+fn is_slot_shortcut(slot: &std::sync::Mutex<Option<Shortcut>>, shortcut: &Shortcut) -> bool {
+    slot.lock()
+        .ok()
+        .is_some_and(|guard| guard.as_ref() == Some(shortcut))
+}
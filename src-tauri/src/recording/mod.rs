@@ -1,5 +1,11 @@
+mod debounce;
+mod double_tap;
 pub mod escape_handler;
 mod hotkeys;
+mod skip_modifier;
 
+pub use debounce::{HotkeyDebouncer, DEFAULT_HOTKEY_DEBOUNCE_MS};
+pub use double_tap::DoubleTapDetector;
 pub use escape_handler::handle_escape_key_press;
 pub use hotkeys::handle_global_shortcut;
+pub use skip_modifier::SkipModifier;
@@ -0,0 +1,114 @@
+use tauri_plugin_global_shortcut::Modifiers;
+
+/// Modifier that, held while a recording is stopped, skips AI enhancement for
+/// that one transcription
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipModifier {
+    Shift,
+    Alt,
+    Control,
+    Meta,
+}
+
+impl SkipModifier {
+    /// Parse the `skip_enhancement_modifier` setting; an empty value or "none" disables it
+    pub fn parse(value: &str) -> Result<Option<Self>, String> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "none" => Ok(None),
+            "shift" => Ok(Some(Self::Shift)),
+            "alt" | "option" => Ok(Some(Self::Alt)),
+            "control" | "ctrl" => Ok(Some(Self::Control)),
+            "meta" | "cmd" | "command" | "super" => Ok(Some(Self::Meta)),
+            other => Err(format!(
+                "Invalid skip enhancement modifier '{}': expected shift, alt, control, meta or none",
+                other
+            )),
+        }
+    }
+
+    fn modifiers(&self) -> Modifiers {
+        match self {
+            Self::Shift => Modifiers::SHIFT,
+            Self::Alt => Modifiers::ALT,
+            Self::Control => Modifiers::CONTROL,
+            Self::Meta => Modifiers::META | Modifiers::SUPER,
+        }
+    }
+
+    /// Whether the modifier is already part of the recording shortcut, in which
+    /// case it is always held and can't mean "skip enhancement"
+    pub fn is_part_of(&self, shortcut_mods: Modifiers) -> bool {
+        shortcut_mods.intersects(self.modifiers())
+    }
+
+    /// Whether the modifier is held down right now
+    pub fn is_held(&self) -> bool {
+        modifier_held(*self)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn modifier_held(modifier: SkipModifier) -> bool {
+    use core_graphics::event::CGEventFlags;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventSourceFlagsState(state_id: i32) -> u64;
+    }
+    // kCGEventSourceStateCombinedSessionState
+    let flags = unsafe { CGEventSourceFlagsState(0) };
+    let mask = match modifier {
+        SkipModifier::Shift => CGEventFlags::CGEventFlagShift,
+        SkipModifier::Alt => CGEventFlags::CGEventFlagAlternate,
+        SkipModifier::Control => CGEventFlags::CGEventFlagControl,
+        SkipModifier::Meta => CGEventFlags::CGEventFlagCommand,
+    };
+    flags & mask.bits() != 0
+}
+
+#[cfg(target_os = "windows")]
+fn modifier_held(modifier: SkipModifier) -> bool {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, VIRTUAL_KEY, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+    };
+
+    let down = |key: VIRTUAL_KEY| unsafe { GetAsyncKeyState(key.0 as i32) } < 0;
+    match modifier {
+        SkipModifier::Shift => down(VK_SHIFT),
+        SkipModifier::Alt => down(VK_MENU),
+        SkipModifier::Control => down(VK_CONTROL),
+        SkipModifier::Meta => down(VK_LWIN) || down(VK_RWIN),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn modifier_held(_modifier: SkipModifier) -> bool {
+    // No global keyboard state to query on other platforms
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_skip_modifier() {
+        assert_eq!(SkipModifier::parse("shift"), Ok(Some(SkipModifier::Shift)));
+        assert_eq!(
+            SkipModifier::parse(" Ctrl "),
+            Ok(Some(SkipModifier::Control))
+        );
+        assert_eq!(SkipModifier::parse("cmd"), Ok(Some(SkipModifier::Meta)));
+        assert_eq!(SkipModifier::parse(""), Ok(None));
+        assert_eq!(SkipModifier::parse("none"), Ok(None));
+        assert!(SkipModifier::parse("hyper").is_err());
+    }
+
+    #[test]
+    fn test_modifier_in_shortcut_is_ignored() {
+        let shortcut_mods = Modifiers::SHIFT | Modifiers::SUPER;
+        assert!(SkipModifier::Shift.is_part_of(shortcut_mods));
+        assert!(SkipModifier::Meta.is_part_of(shortcut_mods));
+        assert!(!SkipModifier::Alt.is_part_of(shortcut_mods));
+    }
+}
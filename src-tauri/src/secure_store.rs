@@ -71,7 +71,7 @@ pub fn check_migration_needed<R: Runtime>(app: &AppHandle<R>) -> bool {
 }
 
 /// Encrypt a string value
-fn encrypt_value(value: &str) -> Result<String, String> {
+pub(crate) fn encrypt_value(value: &str) -> Result<String, String> {
     let key = ENCRYPTION_KEY
         .get()
         .ok_or("Encryption key not initialized")?;
@@ -97,7 +97,7 @@ fn encrypt_value(value: &str) -> Result<String, String> {
 }
 
 /// Decrypt a string value
-fn decrypt_value(encrypted: &str) -> Result<String, String> {
+pub(crate) fn decrypt_value(encrypted: &str) -> Result<String, String> {
     let key = ENCRYPTION_KEY
         .get()
         .ok_or("Encryption key not initialized")?;
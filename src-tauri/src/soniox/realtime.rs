@@ -0,0 +1,388 @@
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tauri::AppHandle;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio_tungstenite::tungstenite::{self, Message};
+
+use crate::audio::recorder::StreamedAudio;
+
+const REALTIME_URL: &str = "wss://stt-rt.soniox.com/transcribe-websocket";
+const REALTIME_MODEL: &str = "stt-rt-v3";
+/// Reconnects allowed while audio is still being captured
+const MAX_RECONNECTS: u32 = 3;
+/// How long to wait for the final tokens once the recording has stopped
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SonioxStreamError {
+    /// Rejected API key; reconnecting won't help
+    Auth(String),
+    /// Error reported by Soniox for this session
+    Server { code: u16, message: String },
+    /// Network failure or unexpected close; worth a reconnect
+    Connection(String),
+    /// Message that doesn't match the realtime protocol
+    Protocol(String),
+}
+
+impl std::fmt::Display for SonioxStreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auth(message) => write!(f, "Soniox rejected the API key: {}", message),
+            Self::Server { code, message } => write!(f, "Soniox error {}: {}", code, message),
+            Self::Connection(message) => write!(f, "Soniox connection failed: {}", message),
+            Self::Protocol(message) => write!(f, "Unexpected Soniox message: {}", message),
+        }
+    }
+}
+
+impl SonioxStreamError {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Auth(_) => "auth",
+            Self::Server { .. } => "server",
+            Self::Connection(_) => "connection",
+            Self::Protocol(_) => "protocol",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RealtimeToken {
+    pub text: String,
+    #[serde(default)]
+    pub is_final: bool,
+}
+
+/// One response from the realtime websocket
+#[derive(Debug, Default, Deserialize)]
+pub struct ServerMessage {
+    #[serde(default)]
+    pub tokens: Vec<RealtimeToken>,
+    #[serde(default)]
+    pub finished: bool,
+}
+
+#[derive(Deserialize)]
+struct ErrorMessage {
+    error_code: u16,
+    #[serde(default)]
+    error_message: String,
+}
+
+/// Parse a text frame from Soniox, turning error responses into `SonioxStreamError`
+pub fn parse_server_message(frame: &str) -> Result<ServerMessage, SonioxStreamError> {
+    if let Ok(error) = serde_json::from_str::<ErrorMessage>(frame) {
+        return Err(match error.error_code {
+            401 | 403 => SonioxStreamError::Auth(error.error_message),
+            code => SonioxStreamError::Server {
+                code,
+                message: error.error_message,
+            },
+        });
+    }
+    serde_json::from_str(frame).map_err(|e| SonioxStreamError::Protocol(e.to_string()))
+}
+
+/// First frame of a session: credentials and audio format as JSON text
+pub fn config_message(
+    api_key: &str,
+    sample_rate: u32,
+    channels: u16,
+    language: Option<&str>,
+) -> String {
+    let mut config = serde_json::json!({
+        "api_key": api_key,
+        "model": REALTIME_MODEL,
+        "audio_format": "pcm_s16le",
+        "sample_rate": sample_rate,
+        "num_channels": channels,
+    });
+    if let Some(lang) = language {
+        config["language_hints"] = serde_json::json!([lang]);
+    }
+    config.to_string()
+}
+
+/// Binary audio frame: raw little-endian 16-bit PCM
+pub fn encode_pcm(samples: &[i16]) -> Vec<u8> {
+    samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+}
+
+/// Final text so far plus the tentative tail Soniox may still revise
+#[derive(Debug, Default)]
+pub struct TranscriptAccumulator {
+    final_text: String,
+    partial: String,
+}
+
+impl TranscriptAccumulator {
+    /// Apply a response; non-final tokens replace the previous tentative tail
+    pub fn apply(&mut self, tokens: &[RealtimeToken]) {
+        self.partial.clear();
+        // Control tokens such as <end> and <fin> carry no speech
+        for token in tokens.iter().filter(|t| !is_control_token(&t.text)) {
+            if token.is_final {
+                self.final_text.push_str(&token.text);
+            } else {
+                self.partial.push_str(&token.text);
+            }
+        }
+    }
+
+    /// Drop the tentative tail, e.g. when its session was lost
+    pub fn discard_partial(&mut self) {
+        self.partial.clear();
+    }
+
+    pub fn text(&self) -> String {
+        format!("{}{}", self.final_text, self.partial)
+            .trim()
+            .to_string()
+    }
+
+    pub fn final_text(&self) -> String {
+        self.final_text.trim().to_string()
+    }
+}
+
+fn is_control_token(text: &str) -> bool {
+    text.starts_with('<') && text.ends_with('>')
+}
+
+/// Stream recorded audio to Soniox until the recording stops, emitting
+/// `partial-transcription` as text arrives. Resolves to the final transcript.
+pub async fn run_session(
+    app: AppHandle,
+    mut audio: UnboundedReceiver<StreamedAudio>,
+    language: Option<String>,
+) -> Result<String, String> {
+    let api_key = crate::secure_store::secure_get(&app, "stt_api_key_soniox")?
+        .ok_or_else(|| "Soniox API key not set".to_string())?;
+
+    let (sample_rate, channels) = match audio.recv().await {
+        Some(StreamedAudio::Format {
+            sample_rate,
+            channels,
+        }) => (sample_rate, channels),
+        Some(StreamedAudio::Samples(_)) => {
+            return Err("Recorder sent audio before its format".to_string())
+        }
+        None => return Err("Recording ended before any audio was captured".to_string()),
+    };
+    let config = config_message(&api_key, sample_rate, channels, language.as_deref());
+
+    let mut transcript = TranscriptAccumulator::default();
+    let mut audio_done = false;
+    let mut reconnects = 0;
+    loop {
+        let result =
+            stream_connection(&app, &config, &mut audio, &mut audio_done, &mut transcript).await;
+        let error = match result {
+            Ok(()) => return Ok(transcript.final_text()),
+            Err(e) => e,
+        };
+
+        // Audio already sent to a lost session can't be replayed, so once the
+        // recording is over the caller falls back to the batch upload
+        let retry = matches!(error, SonioxStreamError::Connection(_))
+            && !audio_done
+            && reconnects < MAX_RECONNECTS;
+        log::warn!(
+            "[SONIOX_STREAM] {} (reconnect: {}, attempt {})",
+            error,
+            retry,
+            reconnects
+        );
+        let _ = crate::emit_to_all(
+            &app,
+            "soniox-stream-error",
+            serde_json::json!({
+                "kind": error.kind(),
+                "error": error.to_string(),
+                "reconnecting": retry,
+            }),
+        );
+        if !retry {
+            return Err(error.to_string());
+        }
+
+        reconnects += 1;
+        transcript.discard_partial();
+        tokio::time::sleep(Duration::from_millis(500 * reconnects as u64)).await;
+    }
+}
+
+async fn stream_connection(
+    app: &AppHandle,
+    config: &str,
+    audio: &mut UnboundedReceiver<StreamedAudio>,
+    audio_done: &mut bool,
+    transcript: &mut TranscriptAccumulator,
+) -> Result<(), SonioxStreamError> {
+    let (mut ws, _) = tokio_tungstenite::connect_async(REALTIME_URL)
+        .await
+        .map_err(|e| match e {
+            tungstenite::Error::Http(response)
+                if matches!(response.status().as_u16(), 401 | 403) =>
+            {
+                SonioxStreamError::Auth(format!("HTTP {}", response.status()))
+            }
+            e => SonioxStreamError::Connection(e.to_string()),
+        })?;
+    ws.send(Message::Text(config.to_string()))
+        .await
+        .map_err(|e| SonioxStreamError::Connection(e.to_string()))?;
+    log::info!("[SONIOX_STREAM] Connected");
+
+    let finalize_deadline = tokio::time::sleep(FINALIZE_TIMEOUT);
+    tokio::pin!(finalize_deadline);
+
+    loop {
+        tokio::select! {
+            chunk = audio.recv(), if !*audio_done => {
+                let frame = match chunk {
+                    Some(StreamedAudio::Samples(samples)) => encode_pcm(&samples),
+                    Some(StreamedAudio::Format { .. }) => continue,
+                    None => {
+                        // Recording stopped: an empty frame asks Soniox to finalize
+                        *audio_done = true;
+                        finalize_deadline
+                            .as_mut()
+                            .reset(tokio::time::Instant::now() + FINALIZE_TIMEOUT);
+                        Vec::new()
+                    }
+                };
+                ws.send(Message::Binary(frame))
+                    .await
+                    .map_err(|e| SonioxStreamError::Connection(e.to_string()))?;
+            }
+            frame = ws.next() => {
+                let text = match frame {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => {
+                        return Err(SonioxStreamError::Connection(
+                            "connection closed before the transcript was finished".to_string(),
+                        ))
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(SonioxStreamError::Connection(e.to_string())),
+                };
+
+                let message = parse_server_message(&text)?;
+                if !message.tokens.is_empty() {
+                    transcript.apply(&message.tokens);
+                    let _ = crate::emit_to_all(
+                        app,
+                        "partial-transcription",
+                        serde_json::json!({
+                            "text": transcript.text(),
+                            "final_text": transcript.final_text(),
+                        }),
+                    );
+                }
+                if message.finished {
+                    let _ = ws.close(None).await;
+                    return Ok(());
+                }
+            }
+            _ = &mut finalize_deadline, if *audio_done => {
+                return Err(SonioxStreamError::Connection(
+                    "timed out waiting for the final transcript".to_string(),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Frames recorded from a realtime session dictating "Hello world. How are you?"
+    const SESSION_FIXTURE: [&str; 5] = [
+        r#"{"tokens":[{"text":"Hel","start_ms":600,"end_ms":720,"confidence":0.62,"is_final":false}],"final_audio_proc_ms":0,"total_audio_proc_ms":840}"#,
+        r#"{"tokens":[{"text":"Hello","start_ms":600,"end_ms":900,"confidence":0.97,"is_final":true},{"text":" world","start_ms":960,"end_ms":1260,"confidence":0.81,"is_final":false}],"final_audio_proc_ms":900,"total_audio_proc_ms":1320}"#,
+        r#"{"tokens":[{"text":" world.","start_ms":960,"end_ms":1300,"confidence":0.98,"is_final":true},{"text":" How","start_ms":1800,"end_ms":1950,"confidence":0.74,"is_final":false}],"final_audio_proc_ms":1300,"total_audio_proc_ms":1980}"#,
+        r#"{"tokens":[{"text":" How","start_ms":1800,"end_ms":1950,"confidence":0.99,"is_final":true},{"text":" are","start_ms":1980,"end_ms":2100,"confidence":0.99,"is_final":true},{"text":" you?","start_ms":2130,"end_ms":2400,"confidence":0.98,"is_final":true},{"text":"<fin>","is_final":true}],"final_audio_proc_ms":2640,"total_audio_proc_ms":2640}"#,
+        r#"{"tokens":[],"final_audio_proc_ms":2640,"total_audio_proc_ms":2640,"finished":true}"#,
+    ];
+
+    #[test]
+    fn test_accumulates_recorded_session() {
+        let mut transcript = TranscriptAccumulator::default();
+        let mut partials = Vec::new();
+        let mut finished = false;
+
+        for frame in SESSION_FIXTURE {
+            let message = parse_server_message(frame).unwrap();
+            assert!(!finished, "frame after finished");
+            transcript.apply(&message.tokens);
+            partials.push(transcript.text());
+            finished = message.finished;
+        }
+
+        assert!(finished);
+        assert_eq!(
+            partials,
+            vec![
+                "Hel",
+                "Hello world",
+                "Hello world. How",
+                "Hello world. How are you?",
+                "Hello world. How are you?",
+            ]
+        );
+        assert_eq!(transcript.final_text(), "Hello world. How are you?");
+    }
+
+    #[test]
+    fn test_lost_session_drops_tentative_text() {
+        let mut transcript = TranscriptAccumulator::default();
+        transcript.apply(&parse_server_message(SESSION_FIXTURE[1]).unwrap().tokens);
+        transcript.discard_partial();
+        assert_eq!(transcript.text(), "Hello");
+    }
+
+    #[test]
+    fn test_error_frames() {
+        let auth = r#"{"error_code":401,"error_message":"Invalid API key."}"#;
+        assert_eq!(
+            parse_server_message(auth).unwrap_err(),
+            SonioxStreamError::Auth("Invalid API key.".to_string())
+        );
+
+        let server = r#"{"error_code":400,"error_message":"Audio format not supported."}"#;
+        assert!(matches!(
+            parse_server_message(server),
+            Err(SonioxStreamError::Server { code: 400, .. })
+        ));
+
+        assert!(matches!(
+            parse_server_message("not json"),
+            Err(SonioxStreamError::Protocol(_))
+        ));
+    }
+
+    #[test]
+    fn test_outgoing_framing() {
+        let config: serde_json::Value =
+            serde_json::from_str(&config_message("key", 48000, 2, Some("de"))).unwrap();
+        assert_eq!(config["api_key"], "key");
+        assert_eq!(config["audio_format"], "pcm_s16le");
+        assert_eq!(config["sample_rate"], 48000);
+        assert_eq!(config["num_channels"], 2);
+        assert_eq!(config["language_hints"], serde_json::json!(["de"]));
+
+        let config: serde_json::Value =
+            serde_json::from_str(&config_message("key", 16000, 1, None)).unwrap();
+        assert!(config.get("language_hints").is_none());
+
+        assert_eq!(
+            encode_pcm(&[1, -2, 0x1234]),
+            vec![1, 0, 0xfe, 0xff, 0x34, 0x12]
+        );
+    }
+}
@@ -1,11 +1,14 @@
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
 
 use tauri::{Emitter, Manager};
 
+use crate::commands::settings::ModelHotkey;
+use crate::menu::RecordingTimer;
+use crate::recording::{DoubleTapDetector, HotkeyDebouncer, SkipModifier};
 use crate::state::unified_state::UnifiedRecordingState;
+use crate::utils::target_window::TargetWindow;
 use crate::window_manager::WindowManager;
 
 /// Recording state enum matching frontend
@@ -45,10 +48,29 @@ pub struct AppState {
     pub recording_shortcut: Arc<Mutex<Option<tauri_plugin_global_shortcut::Shortcut>>>,
     pub current_recording_path: Arc<Mutex<Option<PathBuf>>>,
     pub transcription_task: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    /// Realtime Soniox session for the current recording (soniox_streaming)
+    pub soniox_stream: Arc<Mutex<Option<tauri::async_runtime::JoinHandle<Result<String, String>>>>>,
+    /// Screenshot being taken for the current recording (`attach_screenshot`)
+    pub pending_attachment: Arc<Mutex<Option<tauri::async_runtime::JoinHandle<Option<PathBuf>>>>>,
     pub recording_mode: Arc<Mutex<RecordingMode>>,
     pub ptt_key_held: Arc<AtomicBool>,
+    /// Push-to-talk press/release timing for double-tap latching
+    pub double_tap: Arc<Mutex<DoubleTapDetector>>,
     pub ptt_shortcut: Arc<Mutex<Option<tauri_plugin_global_shortcut::Shortcut>>>,
+    pub preset_cycle_shortcut: Arc<Mutex<Option<tauri_plugin_global_shortcut::Shortcut>>>,
+    pub raw_insert_shortcut: Arc<Mutex<Option<tauri_plugin_global_shortcut::Shortcut>>>,
+    pub copy_last_shortcut: Arc<Mutex<Option<tauri_plugin_global_shortcut::Shortcut>>>,
+    /// Registered model hotkeys and the model each one selects
+    pub model_shortcuts: Arc<Mutex<Vec<(tauri_plugin_global_shortcut::Shortcut, ModelHotkey)>>>,
+    /// Un-enhanced text of the last transcription, for re-inserting it verbatim
+    pub last_raw_transcription: Arc<Mutex<Option<String>>>,
+    /// Window the current dictation was started in (`lock_target_window`)
+    pub dictation_target: Arc<Mutex<Option<TargetWindow>>>,
     pub should_cancel_recording: Arc<AtomicBool>,
+    /// Modifier that skips AI enhancement when held while stopping a recording
+    pub skip_enhancement_modifier: Arc<Mutex<Option<SkipModifier>>>,
+    /// One-shot: the next transcription is inserted without AI enhancement
+    pub skip_enhancement_once: Arc<AtomicBool>,
     pub pending_stop_after_start: Arc<AtomicBool>,
     pub esc_pressed_once: Arc<AtomicBool>,
     pub esc_timeout_handle: Arc<Mutex<Option<tauri::async_runtime::JoinHandle<()>>>>,
@@ -57,7 +79,10 @@ pub struct AppState {
         Arc<tokio::sync::RwLock<Option<crate::commands::audio::RecordingConfig>>>,
     pub license_cache: Arc<tokio::sync::RwLock<Option<crate::commands::license::CachedLicense>>>,
     pub pill_event_queue: Arc<Mutex<Vec<QueuedPillEvent>>>,
-    pub last_toggle_press: Arc<Mutex<Option<Instant>>>,
+    /// Recording hotkey presses too close to the previous one (`hotkey_debounce_ms`)
+    pub hotkey_debounce: Arc<Mutex<HotkeyDebouncer>>,
+    /// Elapsed time in the tray tooltip while recording (`tray_recording_timer`)
+    pub recording_timer: Arc<RecordingTimer>,
 }
 
 impl AppState {
@@ -67,10 +92,21 @@ impl AppState {
             recording_shortcut: Arc::new(Mutex::new(None)),
             current_recording_path: Arc::new(Mutex::new(None)),
             transcription_task: Arc::new(Mutex::new(None)),
+            soniox_stream: Arc::new(Mutex::new(None)),
+            pending_attachment: Arc::new(Mutex::new(None)),
             recording_mode: Arc::new(Mutex::new(RecordingMode::Toggle)),
             ptt_key_held: Arc::new(AtomicBool::new(false)),
+            double_tap: Arc::new(Mutex::new(DoubleTapDetector::default())),
             ptt_shortcut: Arc::new(Mutex::new(None)),
+            preset_cycle_shortcut: Arc::new(Mutex::new(None)),
+            raw_insert_shortcut: Arc::new(Mutex::new(None)),
+            copy_last_shortcut: Arc::new(Mutex::new(None)),
+            model_shortcuts: Arc::new(Mutex::new(Vec::new())),
+            last_raw_transcription: Arc::new(Mutex::new(None)),
+            dictation_target: Arc::new(Mutex::new(None)),
             should_cancel_recording: Arc::new(AtomicBool::new(false)),
+            skip_enhancement_modifier: Arc::new(Mutex::new(Some(SkipModifier::Shift))),
+            skip_enhancement_once: Arc::new(AtomicBool::new(false)),
             pending_stop_after_start: Arc::new(AtomicBool::new(false)),
             esc_pressed_once: Arc::new(AtomicBool::new(false)),
             esc_timeout_handle: Arc::new(Mutex::new(None)),
@@ -78,7 +114,8 @@ impl AppState {
             recording_config_cache: Arc::new(tokio::sync::RwLock::new(None)),
             license_cache: Arc::new(tokio::sync::RwLock::new(None)),
             pill_event_queue: Arc::new(Mutex::new(Vec::new())),
-            last_toggle_press: Arc::new(Mutex::new(None)),
+            hotkey_debounce: Arc::new(Mutex::new(HotkeyDebouncer::default())),
+            recording_timer: Arc::new(RecordingTimer::default()),
         }
     }
 
@@ -121,6 +158,22 @@ impl AppState {
         self.should_cancel_recording.load(Ordering::SeqCst)
     }
 
+    /// Skip AI enhancement for the transcription of the recording being stopped
+    pub fn request_skip_enhancement(&self) {
+        self.skip_enhancement_once.store(true, Ordering::SeqCst);
+        log::info!("AI enhancement will be skipped for this transcription");
+    }
+
+    /// Whether enhancement should be skipped, resetting the flag so it only
+    /// applies once
+    pub fn take_skip_enhancement(&self) -> bool {
+        self.skip_enhancement_once.swap(false, Ordering::SeqCst)
+    }
+
+    pub fn clear_skip_enhancement(&self) {
+        self.skip_enhancement_once.store(false, Ordering::SeqCst);
+    }
+
     pub fn emit_to_window(
         &self,
         window: &str,
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use crate::audio::device_watcher::{AudioDevicesChanged, DeviceChangeDebouncer};
     use crate::{AppState, RecordingState};
     use std::path::PathBuf;
     use std::sync::Arc;
@@ -65,6 +66,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_skip_enhancement_flag_is_one_shot() {
+        let app_state = AppState::new();
+
+        // Nothing requested: enhancement runs as usual
+        assert!(!app_state.take_skip_enhancement());
+
+        // Modifier held while stopping: the next transcription skips it...
+        app_state.request_skip_enhancement();
+        assert!(app_state.take_skip_enhancement());
+        // ...and the one after that doesn't
+        assert!(!app_state.take_skip_enhancement());
+
+        // A new recording drops a skip that was never consumed
+        app_state.request_skip_enhancement();
+        app_state.clear_skip_enhancement();
+        assert!(!app_state.take_skip_enhancement());
+    }
+
     #[test]
     fn test_app_state_recording_state_transitions() {
         let app_state = AppState::new();
@@ -283,4 +303,881 @@ mod tests {
             assert!(task_guard.is_none());
         }
     }
+
+    #[test]
+    fn test_recording_progress_percent_is_monotonic() {
+        use crate::commands::audio::RecordingProgress;
+
+        let pipeline = [
+            RecordingProgress::Stopping,
+            RecordingProgress::Normalizing,
+            RecordingProgress::Transcribing,
+            RecordingProgress::Enhancing,
+            RecordingProgress::Inserting,
+            RecordingProgress::Completed,
+        ];
+        for pair in pipeline.windows(2) {
+            assert!(pair[0].percent() < pair[1].percent());
+        }
+
+        for terminal in [
+            RecordingProgress::NoSpeech,
+            RecordingProgress::Cancelled,
+            RecordingProgress::Failed,
+        ] {
+            assert_eq!(terminal.percent(), 100);
+        }
+        assert_eq!(RecordingProgress::NoSpeech.phase(), "no_speech");
+    }
+
+    #[test]
+    fn test_take_complete_words_for_streamed_insert() {
+        use crate::commands::audio::take_complete_words;
+
+        let mut pending = String::from("  Hello wor");
+        assert_eq!(
+            take_complete_words(&mut pending, true).as_deref(),
+            Some("Hello ")
+        );
+        assert_eq!(pending, "wor");
+
+        // Incomplete word stays buffered
+        assert_eq!(take_complete_words(&mut pending, false), None);
+
+        pending.push_str("ld, caf\u{e9}\nnext");
+        assert_eq!(
+            take_complete_words(&mut pending, false).as_deref(),
+            Some("world, caf\u{e9}\n")
+        );
+        assert_eq!(pending, "next");
+
+        // Whitespace-only output before anything is typed is dropped
+        let mut pending = String::from(" \n ");
+        assert_eq!(take_complete_words(&mut pending, true), None);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_untyped_tail_after_streamed_insert() {
+        use crate::commands::audio::untyped_tail;
+
+        assert_eq!(untyped_tail("Hello world.", "Hello "), "world.");
+        assert_eq!(untyped_tail("  Hello world.", "Hello "), "world.");
+        assert_eq!(untyped_tail("Hello world.", ""), "Hello world.");
+        assert_eq!(untyped_tail("Hello world.", "Hello world."), "");
+
+        // The start changed after streaming: insert from where the texts diverge
+        assert_eq!(untyped_tail("Hello, world.", "Hello world "), ", world.");
+        assert_eq!(untyped_tail("Caf\u{e9} ok", "Cafe "), "\u{e9} ok");
+        assert_eq!(untyped_tail("Goodbye.", "Hello "), "Goodbye.");
+    }
+
+    #[test]
+    fn test_transcription_metrics_realtime_factor() {
+        use crate::commands::audio::TranscriptionMetrics;
+
+        let metrics = TranscriptionMetrics::new(1200, 1500, Some(6000));
+        assert_eq!(metrics.model_load_ms, 1200);
+        assert_eq!(metrics.transcribe_ms, 1500);
+        // Model loading doesn't count towards the realtime factor
+        assert_eq!(metrics.realtime_factor, Some(0.25));
+
+        // Slower than real time
+        assert_eq!(
+            TranscriptionMetrics::new(0, 4500, Some(3000)).realtime_factor,
+            Some(1.5)
+        );
+        // Rounded to three decimals
+        assert_eq!(
+            TranscriptionMetrics::new(0, 1000, Some(3000)).realtime_factor,
+            Some(0.333)
+        );
+
+        // Unknown or empty audio has no factor
+        assert_eq!(
+            TranscriptionMetrics::new(0, 800, None).realtime_factor,
+            None
+        );
+        assert_eq!(
+            TranscriptionMetrics::new(0, 800, Some(0)).realtime_factor,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_continues_past_failed_files() {
+        use crate::commands::audio::run_batch;
+        use std::sync::Mutex;
+
+        let dir = tempfile::tempdir().unwrap();
+        let memo = |name: &str, text: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, text).unwrap();
+            path.to_string_lossy().to_string()
+        };
+        let first = memo("first.m4a", "first memo");
+        let second = memo("second.m4a", "second memo");
+        let missing = dir.path().join("missing.m4a").to_string_lossy().to_string();
+
+        let started = Mutex::new(Vec::new());
+        let results = run_batch(
+            vec![first.clone(), missing.clone(), second.clone()],
+            2,
+            |index, path| started.lock().unwrap().push((index, path.to_string())),
+            |path| async move {
+                std::fs::read_to_string(&path)
+                    .map_err(|_| format!("Audio file not found: {}", path))
+            },
+        )
+        .await;
+
+        // Every file is reported, in input order
+        let paths: Vec<_> = results.iter().map(|r| r.path.clone()).collect();
+        assert_eq!(paths, vec![first.clone(), missing.clone(), second.clone()]);
+        assert_eq!(results[0].text.as_deref(), Some("first memo"));
+        assert!(results[0].error.is_none());
+        assert!(results[1].text.is_none());
+        assert!(results[1].error.as_deref().unwrap().contains("not found"));
+        assert_eq!(results[2].text.as_deref(), Some("second memo"));
+
+        assert_eq!(
+            started.into_inner().unwrap(),
+            vec![(0, first), (1, missing), (2, second)]
+        );
+    }
+
+    #[test]
+    fn test_parse_soniox_speaker_segments() {
+        use crate::commands::audio::{parse_soniox_speaker_segments, SpeakerSegment};
+
+        let transcript = serde_json::json!({
+            "id": "73d4357d-cad2-4338-a60d-ec6f2044f721",
+            "text": "Are we ready? Yes, let's start.",
+            "tokens": [
+                {"text": "Are", "start_ms": 120, "end_ms": 300, "confidence": 0.98, "speaker": "1"},
+                {"text": " we", "start_ms": 300, "end_ms": 420, "confidence": 0.99, "speaker": "1"},
+                {"text": " read", "start_ms": 420, "end_ms": 640, "confidence": 0.97, "speaker": "1"},
+                {"text": "y?", "start_ms": 640, "end_ms": 700, "confidence": 0.96, "speaker": "1"},
+                {"text": " Yes,", "start_ms": 1500, "end_ms": 1800, "confidence": 0.99, "speaker": "2"},
+                {"text": " let's", "start_ms": 1900, "end_ms": 2100, "confidence": 0.98, "speaker": "2"},
+                {"text": " start.", "start_ms": 2100, "end_ms": 2500, "confidence": 0.98, "speaker": "2"},
+                {"text": " Good.", "start_ms": 3000, "end_ms": 3300, "confidence": 0.95, "speaker": "1"}
+            ]
+        });
+
+        let segment = |speaker: &str, text: &str, start_ms, end_ms| SpeakerSegment {
+            speaker_id: speaker.to_string(),
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+        };
+        assert_eq!(
+            parse_soniox_speaker_segments(&transcript).unwrap(),
+            vec![
+                segment("1", "Are we ready?", 120, 700),
+                segment("2", "Yes, let's start.", 1500, 2500),
+                segment("1", "Good.", 3000, 3300),
+            ]
+        );
+
+        // Tokens without a speaker label still come through
+        let unlabeled = serde_json::json!({
+            "tokens": [{"text": "Hello", "start_ms": 0, "end_ms": 400}]
+        });
+        assert_eq!(
+            parse_soniox_speaker_segments(&unlabeled).unwrap(),
+            vec![segment("unknown", "Hello", 0, 400)]
+        );
+
+        assert!(parse_soniox_speaker_segments(&serde_json::json!({"text": "hi"})).is_err());
+    }
+
+    #[test]
+    fn test_result_summary_formatting() {
+        use crate::commands::audio::result_summary;
+
+        assert_eq!(
+            result_summary("The quick brown fox", Some(8.4)),
+            "4 words · 8s"
+        );
+        assert_eq!(result_summary("Hello.", Some(1.2)), "1 word · 1s");
+        assert_eq!(result_summary("", Some(2.0)), "0 words · 2s");
+        // Sub-second recordings still read as a second
+        assert_eq!(result_summary("ok", Some(0.3)), "1 word · 1s");
+        assert_eq!(result_summary("one two", Some(65.0)), "2 words · 1m 05s");
+        // Duration unknown: just the word count
+        assert_eq!(result_summary("one two three", None), "3 words");
+    }
+
+    #[test]
+    fn test_low_confidence_result_warns() {
+        use crate::commands::audio::low_confidence_warning;
+        use crate::whisper::transcriber::{average_confidence, TranscriptionOutput};
+
+        let shaky = TranscriptionOutput {
+            text: "their going too the store".to_string(),
+            confidence: average_confidence(&[0.41, 0.38, 0.55, 0.32, 0.47]),
+            detected_language: None,
+            segments: Vec::new(),
+        };
+        assert_eq!(
+            low_confidence_warning(shaky.confidence, 0.5),
+            Some("Low confidence — double-check text")
+        );
+
+        let clear = TranscriptionOutput {
+            text: "they're going to the store".to_string(),
+            confidence: average_confidence(&[0.97, 0.94, 0.99, 0.91, 0.96]),
+            detected_language: None,
+            segments: Vec::new(),
+        };
+        assert_eq!(low_confidence_warning(clear.confidence, 0.5), None);
+
+        // A zero threshold disables the warning; engines without scores never warn
+        assert_eq!(low_confidence_warning(shaky.confidence, 0.0), None);
+        assert_eq!(low_confidence_warning(None, 0.5), None);
+    }
+
+    /// Accept connections on a local port and report each request line
+    async fn record_requests() -> (String, tokio::sync::mpsc::UnboundedReceiver<String>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = vec![0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let _ = tx.send(request.lines().next().unwrap_or_default().to_string());
+                let _ = socket
+                    .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .await;
+            }
+        });
+        (base, rx)
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_soniox_job_is_deleted_server_side() {
+        use crate::commands::audio::SonioxJob;
+        use std::time::Duration;
+
+        let (base, mut requests) = record_requests().await;
+
+        // A transcription still in flight when cancel_recording aborts its task
+        let task = tokio::spawn(async move {
+            let mut job = SonioxJob::new(reqwest::Client::new(), &base, "key", "file-1".into());
+            job.set_transcription_id("job-1".to_string());
+            std::future::pending::<()>().await;
+            job.finish();
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        task.abort();
+
+        let mut deleted = Vec::new();
+        for _ in 0..2 {
+            let request = tokio::time::timeout(Duration::from_secs(5), requests.recv()).await;
+            deleted.push(request.unwrap().unwrap());
+        }
+        assert_eq!(
+            deleted,
+            vec![
+                "DELETE /transcriptions/job-1 HTTP/1.1",
+                "DELETE /files/file-1 HTTP/1.1"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_finished_soniox_job_is_left_alone() {
+        use crate::commands::audio::SonioxJob;
+        use std::time::Duration;
+
+        let (base, mut requests) = record_requests().await;
+
+        let mut job = SonioxJob::new(reqwest::Client::new(), &base, "key", "file-2".into());
+        job.set_transcription_id("job-2".to_string());
+        job.finish();
+
+        let request = tokio::time::timeout(Duration::from_millis(300), requests.recv()).await;
+        assert!(request.is_err(), "unexpected request {:?}", request);
+    }
+
+    #[test]
+    fn test_recording_too_short_at_boundaries() {
+        use crate::commands::audio::recording_too_short;
+
+        assert!(recording_too_short(0.49, 0.5));
+        assert!(!recording_too_short(0.5, 0.5));
+        assert!(!recording_too_short(0.51, 0.5));
+        assert!(recording_too_short(0.0, 0.5));
+
+        assert!(recording_too_short(1.99, 2.0));
+        assert!(!recording_too_short(2.0, 2.0));
+
+        // 0 disables the gate, even for empty recordings
+        assert!(!recording_too_short(0.0, 0.0));
+        assert!(!recording_too_short(0.1, 0.0));
+    }
+
+    #[test]
+    fn test_recording_path_resolution() {
+        use crate::audio::player::resolve_recording_path;
+
+        let dir = tempfile::tempdir().unwrap();
+        let recording = dir.path().join("recording_1700000000.wav");
+        std::fs::write(&recording, b"RIFF").unwrap();
+
+        assert_eq!(
+            resolve_recording_path(dir.path(), "recording_1700000000.wav").unwrap(),
+            recording
+        );
+
+        let missing = resolve_recording_path(dir.path(), "recording_1.wav").unwrap_err();
+        assert_eq!(missing, "Recording not found: recording_1.wav");
+
+        // Only bare file names inside the recordings folder
+        for name in [
+            "",
+            "../settings.json",
+            "/etc/passwd",
+            "sub/recording.wav",
+            ".",
+        ] {
+            assert!(
+                resolve_recording_path(dir.path(), name).is_err(),
+                "accepted {:?}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_trimmed_recording_guard() {
+        use crate::commands::audio::check_trimmed_duration;
+
+        // silenceremove with a threshold above the speech level leaves nothing
+        assert!(check_trimmed_duration(0.0).is_err());
+        assert!(check_trimmed_duration(0.05).is_err());
+        assert!(check_trimmed_duration(0.1).is_ok());
+        assert!(check_trimmed_duration(3.2).is_ok());
+    }
+
+    #[test]
+    fn test_mic_test_lifecycle_without_device() {
+        use crate::audio::mic_test::MicTest;
+
+        let mut mic_test = MicTest::default();
+        assert!(!mic_test.is_active());
+        assert!(mic_test.check_can_start(RecordingState::Idle).is_ok());
+
+        // A real recording owns the microphone
+        for state in [
+            RecordingState::Starting,
+            RecordingState::Recording,
+            RecordingState::Stopping,
+            RecordingState::Transcribing,
+        ] {
+            assert!(mic_test.check_can_start(state).is_err());
+        }
+
+        // Stopping with nothing running is a no-op
+        assert!(!mic_test.stop());
+        assert_eq!(mic_test.session(), 0);
+    }
+
+    #[test]
+    fn test_mic_test_start_and_stop() {
+        use crate::audio::mic_test::MicTest;
+
+        let capture_path = std::env::temp_dir().join("voicetypr_mic_test_lifecycle.wav");
+        let mut mic_test = MicTest::default();
+
+        // CI machines usually have no input device; the failed start must leave nothing behind
+        let Ok(_levels) = mic_test.start(capture_path.clone(), None) else {
+            assert!(!mic_test.is_active());
+            return;
+        };
+        assert!(mic_test.is_active());
+        assert_eq!(mic_test.session(), 1);
+        assert!(mic_test.check_can_start(RecordingState::Idle).is_err());
+
+        assert!(mic_test.stop());
+        assert!(!mic_test.is_active());
+        assert!(!capture_path.exists());
+        assert!(mic_test.check_can_start(RecordingState::Idle).is_ok());
+    }
+
+    fn device_snapshot(devices: &[&str], default: Option<&str>) -> AudioDevicesChanged {
+        AudioDevicesChanged {
+            devices: devices.iter().map(|d| d.to_string()).collect(),
+            default_device: default.map(|d| d.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_device_change_debounce_collapses_storms() {
+        use std::time::{Duration, Instant};
+
+        let mut debouncer = DeviceChangeDebouncer::new(Duration::from_millis(1000));
+        let t0 = Instant::now();
+        let ms = |n| t0 + Duration::from_millis(n);
+
+        let built_in = device_snapshot(&["Built-in"], Some("Built-in"));
+        assert_eq!(debouncer.observe(built_in.clone(), ms(0)), None);
+        assert_eq!(
+            debouncer.observe(built_in.clone(), ms(1000)),
+            Some(built_in.clone())
+        );
+        assert!(!debouncer.has_pending());
+
+        // Docking adds devices one after another and moves the default
+        let storm = [
+            device_snapshot(&["Built-in", "Dock Mic"], Some("Built-in")),
+            device_snapshot(&["Built-in", "Dock Mic", "Webcam"], Some("Built-in")),
+            device_snapshot(&["Built-in", "Dock Mic", "Webcam"], Some("Dock Mic")),
+        ];
+        for (i, snapshot) in storm.iter().enumerate() {
+            assert_eq!(
+                debouncer.observe(snapshot.clone(), ms(2000 + i as u64 * 250)),
+                None
+            );
+        }
+        assert!(debouncer.has_pending());
+        assert_eq!(debouncer.observe(storm[2].clone(), ms(2750)), None);
+        assert_eq!(
+            debouncer.observe(storm[2].clone(), ms(3500)),
+            Some(storm[2].clone())
+        );
+
+        // Unchanged polls report nothing
+        assert_eq!(debouncer.observe(storm[2].clone(), ms(6000)), None);
+        assert!(!debouncer.has_pending());
+    }
+
+    #[test]
+    fn test_device_change_that_reverts_is_not_reported() {
+        use std::time::{Duration, Instant};
+
+        let mut debouncer = DeviceChangeDebouncer::new(Duration::from_millis(1000));
+        let t0 = Instant::now();
+        let built_in = device_snapshot(&["Built-in"], Some("Built-in"));
+        debouncer.observe(built_in.clone(), t0);
+        debouncer.observe(built_in.clone(), t0 + Duration::from_secs(1));
+
+        // A headset blips in and out
+        let blip = device_snapshot(&["Built-in", "Headset"], Some("Headset"));
+        assert_eq!(
+            debouncer.observe(blip, t0 + Duration::from_millis(1500)),
+            None
+        );
+        assert_eq!(
+            debouncer.observe(built_in, t0 + Duration::from_millis(1750)),
+            None
+        );
+        assert!(!debouncer.has_pending());
+    }
+
+    #[test]
+    fn test_fallback_selection_skips_blocklisted_models() {
+        use crate::commands::audio::select_best_fallback_model;
+
+        let names = |models: &[&str]| models.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+        let downloaded = names(&["large-v3", "small.en", "base.en"]);
+        let by_size = names(&["base.en", "small.en", "large-v3"]);
+
+        // The requested large variant isn't downloaded and the one that is is blocked
+        assert_eq!(
+            select_best_fallback_model(
+                &downloaded,
+                "large-v3-turbo",
+                &by_size,
+                &names(&["large-v3"])
+            ),
+            "base.en"
+        );
+        assert_eq!(
+            select_best_fallback_model(&downloaded, "", &by_size, &names(&["base.en"])),
+            "small.en"
+        );
+        // Without a blocklist the similar model still wins
+        assert_eq!(
+            select_best_fallback_model(&downloaded, "large-v3-turbo", &by_size, &[]),
+            "large-v3"
+        );
+    }
+
+    #[test]
+    fn test_fallback_selection_when_every_model_is_blocklisted() {
+        use crate::commands::audio::select_best_fallback_model;
+
+        let names = |models: &[&str]| models.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+        let downloaded = names(&["large-v3", "medium"]);
+        let by_size = names(&["base.en", "medium", "large-v3"]);
+
+        // Blocking everything falls back to the full list rather than failing
+        assert_eq!(
+            select_best_fallback_model(&downloaded, "", &by_size, &downloaded),
+            "medium"
+        );
+    }
+
+    #[test]
+    fn test_active_input_device_prefers_connected_selection() {
+        use crate::audio::device_watcher::active_input_device;
+
+        let devices = vec!["Built-in".to_string(), "USB Mic".to_string()];
+        assert_eq!(
+            active_input_device(Some("USB Mic"), &devices, Some("Built-in")),
+            Some("USB Mic".to_string())
+        );
+        // A selected mic that was unplugged falls back to the OS default
+        assert_eq!(
+            active_input_device(Some("Dock Mic"), &devices, Some("Built-in")),
+            Some("Built-in".to_string())
+        );
+        assert_eq!(
+            active_input_device(None, &devices, Some("Built-in")),
+            Some("Built-in".to_string())
+        );
+        assert_eq!(active_input_device(None, &[], None), None);
+    }
+
+    #[test]
+    fn test_remembered_recording_mode_per_device() {
+        use crate::audio::device_watcher::remembered_recording_mode;
+        use std::collections::HashMap;
+
+        let modes: HashMap<String, String> = [
+            ("Dock Mic", "push_to_talk"),
+            ("Built-in", "toggle"),
+            ("Old Mic", "hold"),
+        ]
+        .into_iter()
+        .map(|(device, mode)| (device.to_string(), mode.to_string()))
+        .collect();
+
+        assert_eq!(
+            remembered_recording_mode(true, &modes, Some("Dock Mic")),
+            Some("push_to_talk")
+        );
+        assert_eq!(
+            remembered_recording_mode(true, &modes, Some("Built-in")),
+            Some("toggle")
+        );
+        // No memory for this device: the current mode stays
+        assert_eq!(
+            remembered_recording_mode(true, &modes, Some("Headset")),
+            None
+        );
+        assert_eq!(remembered_recording_mode(true, &modes, None), None);
+        // Unknown modes are ignored rather than applied
+        assert_eq!(
+            remembered_recording_mode(true, &modes, Some("Old Mic")),
+            None
+        );
+        // Feature off: the global mode always wins
+        assert_eq!(
+            remembered_recording_mode(false, &modes, Some("Dock Mic")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_device_change_payload_shape() {
+        let payload =
+            serde_json::to_value(device_snapshot(&["Built-in", "USB Mic"], Some("USB Mic")))
+                .unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "devices": ["Built-in", "USB Mic"],
+                "default_device": "USB Mic"
+            })
+        );
+
+        let payload = serde_json::to_value(device_snapshot(&[], None)).unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({ "devices": [], "default_device": null })
+        );
+    }
+
+    #[test]
+    fn test_retry_backoff_delay_sequence() {
+        use crate::commands::audio::RetryPolicy;
+        use std::time::Duration;
+
+        let policy = RetryPolicy::new(5, 500);
+        let delays: Vec<Duration> = (1..=4).map(|n| policy.delay_before_retry(n)).collect();
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(500),
+                Duration::from_millis(1000),
+                Duration::from_millis(2000),
+                Duration::from_millis(4000),
+            ]
+        );
+        // The doubling is capped
+        assert_eq!(policy.delay_before_retry(40), Duration::from_secs(30));
+        // Zero attempts still means one try
+        assert_eq!(RetryPolicy::new(0, 500).max_attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_configured_attempts() {
+        use crate::commands::audio::{retry_with_backoff, RetryPolicy};
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), String> = retry_with_backoff(
+            RetryPolicy::new(3, 0),
+            "Test",
+            |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                std::future::ready(Err("HTTP 503 Service Unavailable".to_string()))
+            },
+            |_| true,
+        )
+        .await;
+        assert_eq!(result, Err("HTTP 503 Service Unavailable".to_string()));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Success on a later attempt stops retrying
+        calls.store(0, Ordering::SeqCst);
+        let result = retry_with_backoff(
+            RetryPolicy::new(5, 0),
+            "Test",
+            |attempt| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                std::future::ready(if attempt == 2 {
+                    Ok(attempt)
+                } else {
+                    Err("Network error (upload)".to_string())
+                })
+            },
+            |_| true,
+        )
+        .await;
+        assert_eq!(result, Ok(2));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Errors the caller marks permanent aren't retried
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<(), String> = retry_with_backoff(
+            RetryPolicy::new(3, 0),
+            "Test",
+            |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                std::future::ready(Err("HTTP 401 Unauthorized".to_string()))
+            },
+            crate::commands::audio::is_transient_cloud_error,
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_transient_cloud_errors() {
+        use crate::commands::audio::is_transient_cloud_error;
+
+        assert!(is_transient_cloud_error(
+            "Network error (status): timed out"
+        ));
+        assert!(is_transient_cloud_error(
+            "Soniox upload failed: HTTP 502 Bad Gateway: upstream"
+        ));
+        assert!(!is_transient_cloud_error(
+            "Soniox upload failed: HTTP 401 Unauthorized: bad key"
+        ));
+        assert!(!is_transient_cloud_error("Soniox job failed: HTTP 5"));
+        assert!(!is_transient_cloud_error("Soniox API key not set"));
+    }
+
+    #[test]
+    fn test_recording_sound_path_resolution() {
+        use crate::audio::sounds::{resolve_sound_source, validate_sound_file, SoundSource};
+
+        let dir = tempfile::tempdir().unwrap();
+        let wav_path = dir.path().join("start.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&wav_path, spec).unwrap();
+        for _ in 0..1600 {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+        let wav = wav_path.to_str().unwrap();
+
+        assert_eq!(resolve_sound_source(None), SoundSource::Builtin);
+        assert_eq!(resolve_sound_source(Some("  ")), SoundSource::Builtin);
+        assert_eq!(
+            resolve_sound_source(Some(wav)),
+            SoundSource::Custom(wav_path.clone())
+        );
+        // A custom sound deleted after it was chosen falls back to the system sound
+        let missing = dir.path().join("gone.wav");
+        assert_eq!(resolve_sound_source(missing.to_str()), SoundSource::Builtin);
+
+        assert!(validate_sound_file(wav).is_ok());
+        assert!(validate_sound_file(missing.to_str().unwrap())
+            .unwrap_err()
+            .contains("not found"));
+        let not_audio = dir.path().join("notes.wav");
+        std::fs::write(&not_audio, "not a wav file").unwrap();
+        assert!(validate_sound_file(not_audio.to_str().unwrap())
+            .unwrap_err()
+            .contains("not a playable WAV"));
+    }
+
+    #[test]
+    fn test_mute_sounds_when_meeting_app_frontmost() {
+        use crate::audio::sounds::should_mute_for_frontmost;
+
+        assert!(should_mute_for_frontmost(true, Some("zoom.us")));
+        assert!(should_mute_for_frontmost(true, Some("Microsoft Teams")));
+        assert!(should_mute_for_frontmost(true, Some("ms-teams.exe")));
+        assert!(should_mute_for_frontmost(true, Some("FaceTime")));
+        assert!(!should_mute_for_frontmost(true, Some("Safari")));
+        assert!(!should_mute_for_frontmost(true, None));
+        // The option is off: never mute
+        assert!(!should_mute_for_frontmost(false, Some("zoom.us")));
+    }
+
+    #[test]
+    fn test_history_entry_text_variants() {
+        use crate::commands::audio::{history_entry_text, HistoryTextVariant};
+        use serde_json::json;
+
+        let enhanced = json!({
+            "text": "Meet Dana at the clinic on Friday.",
+            "raw_text": "meet dana at the clinic on friday",
+            "model": "base.en",
+        });
+        let text = |entry, variant| history_entry_text(Some(entry), "ts", variant);
+        assert_eq!(
+            text(&enhanced, HistoryTextVariant::Plain).unwrap(),
+            "Meet Dana at the clinic on Friday."
+        );
+        assert_eq!(
+            text(&enhanced, HistoryTextVariant::Enhanced).unwrap(),
+            "Meet Dana at the clinic on Friday."
+        );
+        assert_eq!(
+            text(&enhanced, HistoryTextVariant::Raw).unwrap(),
+            "meet dana at the clinic on friday"
+        );
+
+        // Never enhanced: the text is the raw transcription, and there's no enhanced one
+        let plain = json!({ "text": "hello world", "model": "base.en" });
+        assert_eq!(
+            text(&plain, HistoryTextVariant::Raw).unwrap(),
+            "hello world"
+        );
+        assert!(text(&plain, HistoryTextVariant::Enhanced).is_err());
+
+        assert_eq!(
+            HistoryTextVariant::parse("raw").unwrap(),
+            HistoryTextVariant::Raw
+        );
+        assert!(HistoryTextVariant::parse("markdown").is_err());
+    }
+
+    #[test]
+    fn test_history_entry_text_not_found() {
+        use crate::commands::audio::{history_entry_text, HistoryTextVariant};
+
+        assert_eq!(
+            history_entry_text(None, "2025-01-01T00:00:00Z", HistoryTextVariant::Plain),
+            Err("Transcription not found: 2025-01-01T00:00:00Z".to_string())
+        );
+        // A damaged entry without text is an error rather than an empty copy
+        let damaged = serde_json::json!({ "model": "base.en" });
+        assert!(history_entry_text(Some(&damaged), "ts", HistoryTextVariant::Raw).is_err());
+    }
+
+    #[test]
+    fn test_empty_transcription_actions() {
+        use crate::commands::audio::EmptyTranscriptionAction;
+
+        let toast = EmptyTranscriptionAction::parse("toast").unwrap();
+        assert!(toast
+            .toast_message()
+            .unwrap()
+            .contains("No speech detected"));
+        assert!(!toast.keeps_recording());
+
+        let silent = EmptyTranscriptionAction::parse("silent").unwrap();
+        assert_eq!(silent.toast_message(), None);
+        assert!(!silent.keeps_recording());
+
+        let keep = EmptyTranscriptionAction::parse("keep_recording").unwrap();
+        assert!(keep.toast_message().unwrap().contains("kept in history"));
+        assert!(keep.keeps_recording());
+
+        assert!(EmptyTranscriptionAction::parse("discard").is_err());
+    }
+
+    #[test]
+    fn test_kept_empty_recording_can_be_rerun() {
+        use crate::commands::audio::{
+            failed_entries_with_audio, failed_entry_metadata, NO_SPEECH_ERROR,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let recording = dir.path().join("recording.wav");
+        std::fs::write(&recording, b"RIFF").unwrap();
+
+        let mut metadata = serde_json::Map::new();
+        metadata.insert("engine".to_string(), serde_json::json!("whisper"));
+        let placeholder = failed_entry_metadata(&metadata, NO_SPEECH_ERROR, &recording);
+        assert_eq!(placeholder["status"], "failed");
+        assert_eq!(placeholder["error"], NO_SPEECH_ERROR);
+        assert_eq!(placeholder["engine"], "whisper");
+
+        // The placeholder is picked up by re-transcription while its audio is kept
+        let mut entry = serde_json::Value::Object(placeholder);
+        entry["text"] = serde_json::json!("");
+        let entries = vec![("2025-01-01T00:00:00Z".to_string(), entry)];
+        let (with_audio, missing_audio) = failed_entries_with_audio(&entries);
+        assert_eq!(with_audio.len(), 1);
+        assert_eq!(with_audio[0].recording_file, recording);
+        assert!(missing_audio.is_empty());
+    }
+
+    #[test]
+    fn test_abort_all_cancels_registered_downloads() {
+        use crate::commands::audio::cancel_active_downloads;
+        use std::collections::HashMap;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Mutex;
+
+        // Register flags the same way download_model does
+        let active_downloads: Mutex<HashMap<String, Arc<AtomicBool>>> = Mutex::new(HashMap::new());
+        let flags: Vec<_> = ["base.en", "large-v3", "parakeet-tdt-0.6b-v3"]
+            .iter()
+            .map(|name| {
+                let flag = Arc::new(AtomicBool::new(false));
+                active_downloads
+                    .lock()
+                    .unwrap()
+                    .insert(name.to_string(), flag.clone());
+                flag
+            })
+            .collect();
+
+        assert_eq!(cancel_active_downloads(&active_downloads), 3);
+        assert!(flags.iter().all(|flag| flag.load(Ordering::Relaxed)));
+
+        // Nothing registered is a no-op
+        assert_eq!(cancel_active_downloads(&Mutex::new(HashMap::new())), 0);
+    }
 }
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::commands::clipboard::{
+        clipboard_audio_source, sniff_audio_extension, ClipboardAudio, ClipboardContents,
+    };
+    use std::path::PathBuf;
+
+    fn wav_header() -> Vec<u8> {
+        let mut data = b"RIFF".to_vec();
+        data.extend_from_slice(&36u32.to_le_bytes());
+        data.extend_from_slice(b"WAVEfmt ");
+        data
+    }
+
+    #[test]
+    fn test_copied_audio_file_is_used() {
+        let contents = ClipboardContents {
+            files: vec!["/Users/me/Downloads/voice note.m4a".to_string()],
+            text: Some("ignored".to_string()),
+            audio_bytes: None,
+        };
+        assert_eq!(
+            clipboard_audio_source(contents),
+            Ok(ClipboardAudio::File(PathBuf::from(
+                "/Users/me/Downloads/voice note.m4a"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_file_uri_is_decoded() {
+        let contents = ClipboardContents {
+            files: vec![
+                "file:///home/me/notes.txt".to_string(),
+                "file:///home/me/voice%20note.OGG".to_string(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            clipboard_audio_source(contents),
+            Ok(ClipboardAudio::File(PathBuf::from(
+                "/home/me/voice note.OGG"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_copied_non_audio_file_is_rejected() {
+        let contents = ClipboardContents {
+            files: vec!["/home/me/report.pdf".to_string()],
+            audio_bytes: Some(wav_header()),
+            ..Default::default()
+        };
+        let error = clipboard_audio_source(contents).unwrap_err();
+        assert!(error.contains("not an audio file"));
+        assert!(error.contains("report.pdf"));
+    }
+
+    #[test]
+    fn test_audio_path_copied_as_text() {
+        let contents = ClipboardContents {
+            text: Some("  \"/tmp/message.mp3\"\n".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            clipboard_audio_source(contents),
+            Ok(ClipboardAudio::File(PathBuf::from("/tmp/message.mp3")))
+        );
+    }
+
+    #[test]
+    fn test_raw_audio_bytes() {
+        let contents = ClipboardContents {
+            text: Some("just some words".to_string()),
+            audio_bytes: Some(wav_header()),
+            ..Default::default()
+        };
+        assert_eq!(
+            clipboard_audio_source(contents),
+            Ok(ClipboardAudio::Bytes {
+                data: wav_header(),
+                extension: "wav"
+            })
+        );
+    }
+
+    #[test]
+    fn test_non_audio_clipboard_is_an_error() {
+        for contents in [
+            ClipboardContents::default(),
+            ClipboardContents {
+                text: Some("Meeting at 3pm".to_string()),
+                ..Default::default()
+            },
+            ClipboardContents {
+                audio_bytes: Some(b"not audio at all".to_vec()),
+                ..Default::default()
+            },
+        ] {
+            let error = clipboard_audio_source(contents).unwrap_err();
+            assert!(error.contains("doesn't contain audio"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_sniff_audio_extension() {
+        assert_eq!(sniff_audio_extension(&wav_header()), Some("wav"));
+        assert_eq!(sniff_audio_extension(b"fLaC\0\0\0\x22"), Some("flac"));
+        assert_eq!(sniff_audio_extension(b"OggS\0\x02"), Some("ogg"));
+        assert_eq!(sniff_audio_extension(b"ID3\x04\0"), Some("mp3"));
+        assert_eq!(sniff_audio_extension(b"\0\0\0\x20ftypM4A "), Some("m4a"));
+        assert_eq!(sniff_audio_extension(b"%PDF-1.7"), None);
+        assert_eq!(sniff_audio_extension(b""), None);
+    }
+}
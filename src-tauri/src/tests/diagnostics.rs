@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use crate::commands::diagnostics::{
+        build_diagnostics_report, recent_error_lines, scrub_secrets, DiagnosticsSources,
+        PermissionDiagnostics,
+    };
+    use crate::recognition::RecognitionAvailabilitySnapshot;
+    use serde_json::json;
+
+    const OPENAI_KEY: &str = "sk-proj-abcdefghijklmnopqrstuvwx1234";
+    const LICENSE_KEY: &str = "VT-1234-ABCD-5678-EFGH";
+
+    fn sources() -> DiagnosticsSources {
+        let settings = json!({
+            "hotkey": "CommandOrControl+Shift+Space",
+            "current_model": "base.en",
+            "ai_provider": "openai",
+            "ai_api_key_openai": OPENAI_KEY,
+            "license": LICENSE_KEY,
+            "ai_openai_base_url": format!("https://proxy.example.com/v1?api_key={}", OPENAI_KEY),
+        });
+        let log_text = [
+            "[2026-01-01][10:00:00][voicetypr_lib][INFO] Recording started",
+            &format!("[2026-01-01][10:00:01][voicetypr_lib::ai][ERROR] Request failed, api_key={}", OPENAI_KEY),
+            &format!("[2026-01-01][10:00:02][voicetypr_lib::license][ERROR] Activation failed for \"license_key\": \"{}\"", LICENSE_KEY),
+            "[2026-01-01][10:00:03][voicetypr_lib][ERROR] Microphone disconnected",
+        ]
+        .join("\n");
+
+        DiagnosticsSources {
+            models: RecognitionAvailabilitySnapshot {
+                whisper_available: true,
+                parakeet_available: false,
+                soniox_selected: false,
+                soniox_ready: false,
+            },
+            audio_devices: vec!["MacBook Pro Microphone".to_string()],
+            permissions: PermissionDiagnostics {
+                microphone: Some(true),
+                accessibility: None,
+            },
+            settings: settings.as_object().cloned().unwrap(),
+            log_text,
+            app_data_free_bytes: Some(42_000_000_000),
+        }
+    }
+
+    #[test]
+    fn test_report_has_expected_sections() {
+        let report = build_diagnostics_report(sources(), "2026-01-01T10:05:00+00:00");
+        let value = serde_json::to_value(&report).unwrap();
+
+        for section in [
+            "generated_at",
+            "app",
+            "system",
+            "models",
+            "audio_devices",
+            "permissions",
+            "settings",
+            "recent_errors",
+            "disk",
+        ] {
+            assert!(value.get(section).is_some(), "missing section {}", section);
+        }
+        assert_eq!(value["app"]["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(value["models"]["whisper_available"], true);
+        assert_eq!(value["audio_devices"][0], "MacBook Pro Microphone");
+        assert_eq!(value["permissions"]["microphone"], true);
+        assert!(value["permissions"]["accessibility"].is_null());
+        assert_eq!(value["disk"]["app_data_free_bytes"], 42_000_000_000u64);
+        assert_eq!(value["settings"]["current_model"], "base.en");
+        assert_eq!(report.recent_errors.len(), 3);
+        assert!(report.recent_errors[2].contains("Microphone disconnected"));
+    }
+
+    #[test]
+    fn test_report_contains_no_secrets() {
+        let report = build_diagnostics_report(sources(), "2026-01-01T10:05:00+00:00");
+        let json = serde_json::to_string(&report).unwrap();
+
+        assert!(!json.contains(OPENAI_KEY));
+        assert!(!json.contains(LICENSE_KEY));
+        assert!(report.settings.get("ai_api_key_openai").is_none());
+        assert!(report.settings.get("license").is_none());
+        // Non-secret settings survive, with embedded keys masked
+        assert_eq!(report.settings["ai_provider"], "openai");
+        assert_eq!(
+            report.settings["ai_openai_base_url"],
+            "https://proxy.example.com/v1?api_key=[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn test_scrub_secrets() {
+        assert_eq!(
+            scrub_secrets("Authorization: Bearer abc.def.ghi"),
+            "Authorization: [REDACTED]"
+        );
+        assert_eq!(
+            scrub_secrets(&format!("using key {}", OPENAI_KEY)),
+            "using key [REDACTED]"
+        );
+        let plain = "Model base.en loaded in 420ms";
+        assert_eq!(scrub_secrets(plain), plain);
+    }
+
+    #[test]
+    fn test_recent_error_lines_keeps_latest() {
+        let log = (0..10)
+            .map(|i| format!("[x][ERROR] failure {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let errors = recent_error_lines(&log, 3);
+        assert_eq!(
+            errors,
+            vec![
+                "[x][ERROR] failure 7",
+                "[x][ERROR] failure 8",
+                "[x][ERROR] failure 9"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_self_test_skips_when_no_engine_is_ready() {
+        use crate::commands::self_test::{SelfTestReport, SelfTestStatus};
+
+        let nothing_ready =
+            RecognitionAvailabilitySnapshot::from_state(false, &[], "whisper", false);
+        let report = SelfTestReport::skip_unless_ready(&nothing_ready, "base.en")
+            .expect("no engine should skip the self-test");
+        assert_eq!(report.status, SelfTestStatus::Skipped);
+        assert!(!report.passed);
+        assert!(report.steps.is_empty());
+        assert!(report.message.contains("Download a model"));
+
+        // A downloaded model but nothing selected is skipped too
+        let whisper_ready =
+            RecognitionAvailabilitySnapshot::from_state(true, &[], "whisper", false);
+        let report = SelfTestReport::skip_unless_ready(&whisper_ready, "").unwrap();
+        assert_eq!(report.status, SelfTestStatus::Skipped);
+        assert!(SelfTestReport::skip_unless_ready(&whisper_ready, "base.en").is_none());
+    }
+
+    #[test]
+    fn test_self_test_phrase_word_match() {
+        use crate::commands::self_test::phrase_word_match;
+
+        assert_eq!(
+            phrase_word_match("The quick brown fox jumps over the lazy dog."),
+            1.0
+        );
+        assert!(phrase_word_match("the quick brown fox") < 0.6);
+        assert_eq!(phrase_word_match(""), 0.0);
+    }
+}
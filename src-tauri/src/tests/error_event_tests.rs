@@ -600,3 +600,141 @@ mod integration_event_tests {
         log::info!("✅ Event ordering preserved correctly");
     }
 }
+
+#[cfg(test)]
+mod recording_error_event_tests {
+    use super::*;
+    use crate::commands::audio::RecordingError;
+
+    /// Emit the way the recording flow does: classify the failure, then send the payload
+    fn emit_failure(collector: &MockEventCollector, error: RecordingError, message: &str) {
+        collector.emit("recording-error", error.payload(message));
+    }
+
+    fn emitted_code(collector: &MockEventCollector) -> String {
+        let payload = collector
+            .find_event("recording-error")
+            .expect("recording-error emitted");
+        payload["code"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_recorder_failures_map_to_codes() {
+        let cases = [
+            (
+                "Microphone permission denied by the system",
+                "mic_permission_denied",
+            ),
+            ("Failed to access input stream", "mic_permission_denied"),
+            ("No input device available", "no_input_device"),
+            ("Selected microphone not found", "no_input_device"),
+            ("Audio stream busy", "device_busy"),
+            ("Unsupported sample format", "recorder_failed"),
+        ];
+
+        for (recorder_error, expected) in cases {
+            let collector = MockEventCollector::new();
+            emit_failure(
+                &collector,
+                RecordingError::from_recorder_error(recorder_error),
+                recorder_error,
+            );
+            assert_eq!(emitted_code(&collector), expected, "{}", recorder_error);
+        }
+    }
+
+    #[test]
+    fn test_pipeline_failures_emit_codes() {
+        let collector = MockEventCollector::new();
+        emit_failure(
+            &collector,
+            RecordingError::ModelMissing,
+            "No speech recognition engine is ready",
+        );
+        emit_failure(
+            &collector,
+            RecordingError::NormalizationFailed,
+            "ffmpeg exited with status 1",
+        );
+        emit_failure(
+            &collector,
+            RecordingError::ModelLoadFailed,
+            "Failed to load model",
+        );
+
+        let codes: Vec<Value> = collector
+            .get_events()
+            .into_iter()
+            .map(|(_, payload)| payload["code"].clone())
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                json!("model_missing"),
+                json!("normalization_failed"),
+                json!("model_load_failed")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remote_unreachable_only_for_cloud_engines() {
+        let network_error =
+            "Soniox request failed: error sending request for url: connection refused";
+
+        let collector = MockEventCollector::new();
+        emit_failure(
+            &collector,
+            RecordingError::from_transcription_error(network_error, true),
+            network_error,
+        );
+        assert_eq!(emitted_code(&collector), "remote_unreachable");
+
+        let collector = MockEventCollector::new();
+        emit_failure(
+            &collector,
+            RecordingError::from_transcription_error("Timed out waiting for whisper", false),
+            "Timed out waiting for whisper",
+        );
+        assert_eq!(emitted_code(&collector), "transcription_failed");
+
+        let collector = MockEventCollector::new();
+        emit_failure(
+            &collector,
+            RecordingError::from_transcription_error("Soniox returned 401 Unauthorized", true),
+            "Soniox returned 401 Unauthorized",
+        );
+        assert_eq!(emitted_code(&collector), "transcription_failed");
+    }
+
+    #[test]
+    fn test_missing_ffmpeg_is_not_a_normalization_failure() {
+        let missing = RecordingError::from_normalization_error(crate::ffmpeg::FFMPEG_MISSING_ERROR);
+        assert_eq!(missing, RecordingError::FfmpegMissing);
+        assert_eq!(missing.code(), "ffmpeg_missing");
+        // Retrying won't help until ffmpeg is reinstalled
+        assert!(!missing.recoverable());
+
+        assert_eq!(
+            RecordingError::from_normalization_error("ffmpeg exited with status Some(1)"),
+            RecordingError::NormalizationFailed
+        );
+    }
+
+    #[test]
+    fn test_recording_error_payload_shape() {
+        let payload = RecordingError::MicPermissionDenied.payload("Microphone permission denied");
+        assert_eq!(payload["code"], "mic_permission_denied");
+        assert_eq!(payload["message"], "Microphone permission denied");
+        assert_eq!(payload["recoverable"], false);
+
+        assert_eq!(
+            RecordingError::RemoteUnreachable.payload("offline")["recoverable"],
+            true
+        );
+        assert_eq!(
+            RecordingError::LicenseRequired.payload("expired")["recoverable"],
+            false
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use chrono::{Duration, TimeZone, Utc};
+
+use crate::commands::license::{grace_days_remaining, hours_to_days, license_status_detail};
+use crate::license::{LicenseState, LicenseStatus};
+
+fn status(state: LicenseState, trial_days_left: Option<i32>) -> LicenseStatus {
+    LicenseStatus {
+        status: state,
+        trial_days_left,
+        license_type: None,
+        license_key: Some("VT-SECRET-KEY".to_string()),
+        expires_at: None,
+    }
+}
+
+#[test]
+fn test_hours_to_days_rounds_up() {
+    assert_eq!(hours_to_days(0), 0);
+    assert_eq!(hours_to_days(1), 1);
+    assert_eq!(hours_to_days(24), 1);
+    assert_eq!(hours_to_days(25), 2);
+    assert_eq!(hours_to_days(-5), 0);
+}
+
+#[test]
+fn test_grace_days_remaining() {
+    let validated = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+
+    assert_eq!(grace_days_remaining(validated, validated, 90), Some(90));
+    // Partial days don't count against the grace period
+    let later = validated + Duration::days(10) + Duration::hours(23);
+    assert_eq!(grace_days_remaining(validated, later, 90), Some(80));
+    assert_eq!(
+        grace_days_remaining(validated, validated + Duration::days(89), 90),
+        Some(1)
+    );
+    assert_eq!(
+        grace_days_remaining(validated, validated + Duration::days(90), 90),
+        None
+    );
+
+    // The one-day trial grace period ends after a full day offline
+    assert_eq!(
+        grace_days_remaining(validated, validated + Duration::hours(23), 1),
+        Some(1)
+    );
+    assert_eq!(
+        grace_days_remaining(validated, validated + Duration::hours(24), 1),
+        None
+    );
+}
+
+#[test]
+fn test_license_status_detail() {
+    let license_validated = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let trial_validated = Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+    let now = Utc.with_ymd_and_hms(2025, 3, 1, 6, 0, 0).unwrap();
+
+    let licensed = license_status_detail(
+        &status(LicenseState::Licensed, None),
+        Some(license_validated),
+        Some(trial_validated),
+        now,
+    );
+    assert_eq!(licensed.state, LicenseState::Licensed);
+    assert_eq!(licensed.offline_grace_days_remaining, Some(31));
+    assert_eq!(
+        licensed.last_validated.as_deref(),
+        Some("2025-01-01T00:00:00+00:00")
+    );
+
+    let trial = license_status_detail(
+        &status(LicenseState::Trial, Some(5)),
+        None,
+        Some(trial_validated),
+        now,
+    );
+    assert_eq!(trial.trial_days_left, Some(5));
+    assert_eq!(trial.offline_grace_days_remaining, Some(1));
+
+    // Never validated online: no grace to report
+    let unvalidated = license_status_detail(&status(LicenseState::Licensed, None), None, None, now);
+    assert_eq!(unvalidated.offline_grace_days_remaining, None);
+    assert_eq!(unvalidated.last_validated, None);
+
+    let expired = license_status_detail(
+        &status(LicenseState::Expired, Some(0)),
+        Some(license_validated),
+        Some(trial_validated),
+        now,
+    );
+    assert_eq!(expired.offline_grace_days_remaining, None);
+    assert_eq!(expired.last_validated, None);
+
+    // The event payload never includes the license key
+    let payload = serde_json::to_string(&licensed).unwrap();
+    assert!(!payload.contains("VT-SECRET-KEY"));
+    assert!(!payload.contains("license_key"));
+}
@@ -7,6 +7,21 @@ mod model_commands;
 #[cfg(test)]
 mod settings_commands;
 
+#[cfg(test)]
+mod profile_commands;
+
+#[cfg(test)]
+mod settings_transfer;
+
+#[cfg(test)]
+mod license_commands;
+
+#[cfg(test)]
+mod diagnostics;
+
+#[cfg(test)]
+mod clipboard_audio;
+
 #[cfg(test)]
 mod transcription_history;
 
@@ -303,4 +303,513 @@ mod tests {
             assert_eq!(model.sha256.len(), 40);
         }
     }
+
+    #[tokio::test]
+    async fn test_tampered_model_fails_checksum_and_is_removed() {
+        use sha2::{Digest, Sha256};
+
+        let temp_dir = TempDir::new().unwrap();
+        let model_path = temp_dir.path().join("base.bin");
+        let original = b"ggml model weights";
+        let sha256 = hex::encode(Sha256::digest(original));
+        let wrong_sha1 = "0".repeat(40);
+
+        // Intact file passes and is kept
+        std::fs::write(&model_path, original).unwrap();
+        assert!(WhisperManager::verify_model_checksum(&model_path, &sha256)
+            .await
+            .is_ok());
+        assert!(model_path.exists());
+
+        // Uppercase registry checksums still match
+        assert!(
+            WhisperManager::verify_model_checksum(&model_path, &sha256.to_uppercase())
+                .await
+                .is_ok()
+        );
+
+        // A single flipped byte fails and the file is deleted
+        std::fs::write(&model_path, b"ggml model weightz").unwrap();
+        let err = WhisperManager::verify_model_checksum(&model_path, &sha256)
+            .await
+            .unwrap_err();
+        assert!(err.contains("SHA256 checksum verification failed"));
+        assert!(err.contains("File has been deleted."));
+        assert!(!model_path.exists());
+
+        // Legacy SHA1 checksums are checked the same way
+        std::fs::write(&model_path, original).unwrap();
+        let err = WhisperManager::verify_model_checksum(&model_path, &wrong_sha1)
+            .await
+            .unwrap_err();
+        assert!(err.contains("SHA1 checksum verification failed"));
+        assert!(!model_path.exists());
+
+        // A file that can't be read was never deleted, so the error doesn't claim it
+        let err = WhisperManager::verify_model_checksum(&model_path, &sha256)
+            .await
+            .unwrap_err();
+        assert!(err.contains("Failed to open file"));
+        assert!(!err.contains("deleted"));
+    }
+
+    #[test]
+    fn test_chunk_ranges_cover_file_without_gaps() {
+        use crate::whisper::manager::chunk_ranges;
+
+        assert_eq!(chunk_ranges(10, 1), vec![(0, 9)]);
+        // Remainder goes to the first ranges
+        assert_eq!(chunk_ranges(10, 3), vec![(0, 3), (4, 6), (7, 9)]);
+        assert_eq!(chunk_ranges(8, 4), vec![(0, 1), (2, 3), (4, 5), (6, 7)]);
+        // Never more ranges than bytes, and at least one connection
+        assert_eq!(chunk_ranges(2, 4), vec![(0, 0), (1, 1)]);
+        assert_eq!(chunk_ranges(5, 0), vec![(0, 4)]);
+        assert!(chunk_ranges(0, 4).is_empty());
+
+        // Contiguous and complete for a realistic model size
+        let total = 147_951_465;
+        let ranges = chunk_ranges(total, 8);
+        assert_eq!(ranges.len(), 8);
+        assert_eq!(ranges.first().unwrap().0, 0);
+        assert_eq!(ranges.last().unwrap().1, total - 1);
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].1 + 1, pair[1].0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concat_parts_keeps_range_order() {
+        use crate::whisper::manager::concat_parts;
+
+        let temp_dir = TempDir::new().unwrap();
+        let contents = ["ggml", "-model", "-weights"];
+        let parts: Vec<_> = (0..contents.len())
+            .map(|index| temp_dir.path().join(format!("base.bin.part{}", index)))
+            .collect();
+
+        // Parts finish in any order; assembly follows range order
+        for (path, content) in parts.iter().zip(contents).rev() {
+            std::fs::write(path, content).unwrap();
+        }
+
+        let output = temp_dir.path().join("base.bin");
+        concat_parts(&parts, &output).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&output).unwrap(),
+            "ggml-model-weights"
+        );
+
+        // A missing part fails instead of producing a short file
+        std::fs::remove_file(&parts[1]).unwrap();
+        assert!(concat_parts(&parts, &output).await.is_err());
+    }
+
+    #[test]
+    fn test_download_space_check() {
+        use crate::commands::model::{check_download_space, InsufficientSpace};
+
+        const MB: u64 = 1024 * 1024;
+        const GB: u64 = 1024 * MB;
+        let large_model = 3 * GB;
+
+        // Plenty of room
+        assert!(check_download_space(large_model, 1, Some(50 * GB)).is_ok());
+
+        // The model fits, but not with the headroom on top
+        assert_eq!(
+            check_download_space(large_model, 1, Some(large_model + 100 * MB)),
+            Err(InsufficientSpace {
+                required_bytes: large_model + 256 * MB,
+                available_bytes: large_model + 100 * MB,
+            })
+        );
+
+        // Parallel downloads need room for the parts and the joined file
+        assert!(check_download_space(large_model, 1, Some(4 * GB)).is_ok());
+        let err = check_download_space(large_model, 4, Some(4 * GB)).unwrap_err();
+        assert_eq!(err.required_bytes, 2 * large_model + 256 * MB);
+
+        // Unknown free space never blocks a download
+        assert!(check_download_space(large_model, 4, None).is_ok());
+    }
+
+    fn whisper_model(name: &str, size: u64, downloaded: bool) -> ModelInfo {
+        ModelInfo {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            size,
+            url: format!("https://example.com/{}.bin", name),
+            sha256: "abc123".to_string(),
+            downloaded,
+            speed_score: 5,
+            accuracy_score: 5,
+            recommended: false,
+        }
+    }
+
+    fn parakeet_model(name: &str, downloaded: bool) -> crate::parakeet::ParakeetModelStatus {
+        crate::parakeet::ParakeetModelStatus {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            size: 500 * 1024 * 1024,
+            url: String::new(),
+            sha256: String::new(),
+            downloaded,
+            speed_score: 9,
+            accuracy_score: 8,
+            recommended: false,
+            engine: "parakeet".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_benchmark_skips_models_that_are_not_downloaded() {
+        use crate::commands::model::{benchmark_candidates, ModelEngine};
+        use std::collections::HashMap;
+
+        let mut whisper = HashMap::new();
+        for (name, size, downloaded) in [
+            ("large-v3", 3_000, true),
+            ("base.en", 150, true),
+            ("medium", 1_500, false),
+        ] {
+            whisper.insert(name.to_string(), whisper_model(name, size, downloaded));
+        }
+        let parakeet = vec![
+            parakeet_model("parakeet-tdt-0.6b-v3", true),
+            parakeet_model("parakeet-tdt-0.6b-v2", false),
+        ];
+
+        let candidates = benchmark_candidates(&whisper, &parakeet);
+        let names: Vec<(&str, ModelEngine)> = candidates
+            .iter()
+            .map(|c| (c.name.as_str(), c.engine))
+            .collect();
+        // Whisper smallest first, then Parakeet; nothing that isn't on disk
+        assert_eq!(
+            names,
+            vec![
+                ("base.en", ModelEngine::Whisper),
+                ("large-v3", ModelEngine::Whisper),
+                ("parakeet-tdt-0.6b-v3", ModelEngine::Parakeet),
+            ]
+        );
+
+        assert!(benchmark_candidates(&HashMap::new(), &[parakeet_model("p", false)]).is_empty());
+    }
+
+    #[test]
+    fn test_benchmark_report_aggregates_results() {
+        use crate::commands::model::{
+            BenchmarkCandidate, BenchmarkReport, BenchmarkRun, ModelBenchmark, ModelEngine,
+        };
+        use std::time::Duration;
+
+        let candidate = |name: &str, engine| BenchmarkCandidate {
+            name: name.to_string(),
+            engine,
+        };
+        let run = |warm_ms| {
+            Ok(BenchmarkRun {
+                load: Duration::from_millis(800),
+                cold: Duration::from_millis(1_200),
+                warm: Duration::from_millis(warm_ms),
+                text: " hello world ".to_string(),
+            })
+        };
+
+        let results = vec![
+            ModelBenchmark::from_run(&candidate("base.en", ModelEngine::Whisper), run(500), 10.0),
+            ModelBenchmark::from_run(
+                &candidate("parakeet-tdt-0.6b-v3", ModelEngine::Parakeet),
+                run(250),
+                10.0,
+            ),
+            ModelBenchmark::from_run(
+                &candidate("large-v3", ModelEngine::Whisper),
+                Err("Failed to load model".to_string()),
+                10.0,
+            ),
+        ];
+        let report = BenchmarkReport::new(10.0, results);
+
+        assert_eq!(report.results.len(), 3);
+        let base = &report.results[0];
+        assert_eq!(base.engine, "whisper");
+        assert_eq!(base.load_ms, 800);
+        assert_eq!(base.cold_transcribe_ms, 1_200);
+        assert_eq!(base.warm_transcribe_ms, 500);
+        assert_eq!(base.realtime_factor, Some(0.05));
+        assert_eq!(base.text, "hello world");
+        assert!(base.error.is_none());
+
+        // A failed model stays in the results, but can't be the fastest
+        let failed = &report.results[2];
+        assert_eq!(failed.error.as_deref(), Some("Failed to load model"));
+        assert_eq!(failed.warm_transcribe_ms, 0);
+        assert_eq!(failed.realtime_factor, None);
+        assert_eq!(report.fastest.as_deref(), Some("parakeet-tdt-0.6b-v3"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["sample_duration_secs"], 10.0);
+        assert_eq!(json["results"][1]["engine"], "parakeet");
+        assert!(json["results"][2]["error"].is_string());
+    }
+
+    #[test]
+    fn test_deleting_last_model_flips_availability() {
+        use crate::recognition::RecognitionAvailabilitySnapshot;
+
+        let temp_dir = TempDir::new().unwrap();
+        let models_dir = temp_dir.path().join("models");
+        std::fs::create_dir_all(&models_dir).unwrap();
+        let mut manager = WhisperManager::new_for_test(models_dir.clone());
+        std::fs::write(models_dir.join("base.en.bin"), vec![0u8; 1024]).unwrap();
+        manager.refresh_downloaded_status();
+
+        let snapshot = |manager: &WhisperManager| {
+            RecognitionAvailabilitySnapshot::from_state(
+                manager.has_downloaded_models(),
+                &[parakeet_model("parakeet-tdt-0.6b-v3", false)],
+                "whisper",
+                false,
+            )
+        };
+        assert!(snapshot(&manager).any_available());
+
+        manager.delete_model_file("base.en").unwrap();
+        let after_delete = snapshot(&manager);
+        assert!(!after_delete.any_available());
+
+        // What the UI receives with the `recognition-availability` event
+        assert_eq!(
+            serde_json::to_value(&after_delete).unwrap(),
+            serde_json::json!({
+                "whisper_available": false,
+                "parakeet_available": false,
+                "soniox_selected": false,
+                "soniox_ready": false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_soniox_availability_needs_selection_and_key() {
+        use crate::recognition::RecognitionAvailabilitySnapshot;
+
+        let soniox = |engine: &str, has_key| {
+            RecognitionAvailabilitySnapshot::from_state(false, &[], engine, has_key)
+        };
+        assert!(soniox("soniox", true).any_available());
+        assert!(!soniox("soniox", false).any_available());
+        // A stored key doesn't count while another engine is selected
+        let other_engine = soniox("whisper", true);
+        assert!(!other_engine.soniox_ready);
+        assert!(!other_engine.any_available());
+    }
+
+    /// The bundled catalog's scores, with `downloaded` marking what's on disk
+    fn recommendation_candidates(downloaded: &[&str]) -> Vec<crate::recognition::ModelCandidate> {
+        use std::collections::HashMap;
+
+        let mut whisper = HashMap::new();
+        for (name, size, speed, accuracy) in [
+            ("base.en", 148_000_000, 8, 5),
+            ("small.en", 488_000_000, 7, 6),
+            ("large-v3-turbo", 1_620_000_000, 7, 9),
+            ("large-v3", 3_100_000_000, 2, 9),
+        ] {
+            let mut model = whisper_model(name, size, downloaded.contains(&name));
+            model.speed_score = speed;
+            model.accuracy_score = accuracy;
+            whisper.insert(name.to_string(), model);
+        }
+        let parakeet: Vec<_> = [
+            ("parakeet-tdt-0.6b-v3", 9, 9),
+            ("parakeet-tdt-0.6b-v2", 10, 8),
+        ]
+        .into_iter()
+        .map(|(name, speed, accuracy)| {
+            let mut model = parakeet_model(name, downloaded.contains(&name));
+            model.speed_score = speed;
+            model.accuracy_score = accuracy;
+            model
+        })
+        .collect();
+        crate::recognition::model_candidates(&whisper, &parakeet)
+    }
+
+    #[test]
+    fn test_recommendation_steers_non_english_away_from_en_models() {
+        use crate::recognition::best_model_for_language;
+
+        let candidates = recommendation_candidates(&["base.en", "small.en", "large-v3-turbo"]);
+        let german = best_model_for_language("de", "base.en", &candidates);
+        assert_eq!(german.recommended.as_deref(), Some("large-v3-turbo"));
+        assert_eq!(german.recommended_engine.as_deref(), Some("whisper"));
+        assert!(!german.current_model_suitable);
+        assert!(!german.needs_multilingual);
+        assert_eq!(
+            german.warnings,
+            vec!["base.en only understands English; German needs a multilingual model"]
+        );
+
+        // The same models all work for English
+        let english = best_model_for_language("en", "base.en", &candidates);
+        assert!(english.current_model_suitable);
+        assert_eq!(english.recommended.as_deref(), Some("large-v3-turbo"));
+        assert!(english.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_recommendation_flags_missing_multilingual_model() {
+        use crate::recognition::best_model_for_language;
+
+        // Only English models on disk
+        let candidates = recommendation_candidates(&["base.en", "parakeet-tdt-0.6b-v2"]);
+        let french = best_model_for_language("fr", "parakeet-tdt-0.6b-v2", &candidates);
+        assert_eq!(french.recommended, None);
+        assert!(french.needs_multilingual);
+        assert!(!french.current_model_suitable);
+        // Parakeet V3 covers French and ranks above the Whisper models
+        assert_eq!(
+            french.suggested_download.as_deref(),
+            Some("parakeet-tdt-0.6b-v3")
+        );
+
+        // Parakeet V3 doesn't cover Japanese, so Whisper is the way
+        let japanese = best_model_for_language("ja", "base.en", &candidates);
+        assert!(japanese.needs_multilingual);
+        assert_eq!(
+            japanese.suggested_download.as_deref(),
+            Some("large-v3-turbo")
+        );
+        assert_eq!(
+            japanese.warnings.last().map(String::as_str),
+            Some("No downloaded model supports Japanese; download large-v3-turbo")
+        );
+    }
+
+    #[test]
+    fn test_recommendation_prefers_language_specific_fit() {
+        use crate::recognition::best_model_for_language;
+
+        let candidates = recommendation_candidates(&[
+            "base.en",
+            "large-v3",
+            "parakeet-tdt-0.6b-v3",
+            "parakeet-tdt-0.6b-v2",
+        ]);
+        // Parakeet V2 ties V3 overall for English; the more accurate V3 wins
+        let english = best_model_for_language("en", "large-v3", &candidates);
+        assert_eq!(english.recommended.as_deref(), Some("parakeet-tdt-0.6b-v3"));
+        assert_eq!(english.recommended_engine.as_deref(), Some("parakeet"));
+        assert!(english.current_model_suitable);
+
+        // large-v3 handles Spanish too, but is slower for the same accuracy
+        let spanish = best_model_for_language("es", "large-v3", &candidates);
+        assert_eq!(spanish.recommended.as_deref(), Some("parakeet-tdt-0.6b-v3"));
+        assert_eq!(
+            spanish.warnings,
+            vec!["parakeet-tdt-0.6b-v3 is as accurate as large-v3 and faster for Spanish"]
+        );
+
+        // Outside Parakeet V3's languages only large-v3 fits
+        let korean = best_model_for_language("ko", "parakeet-tdt-0.6b-v3", &candidates);
+        assert_eq!(korean.recommended.as_deref(), Some("large-v3"));
+        assert_eq!(
+            korean.warnings,
+            vec!["parakeet-tdt-0.6b-v3 doesn't support Korean"]
+        );
+    }
+
+    /// A ggml header with base.en's hyperparameters, `n_vocab` aside
+    fn ggml_header(n_vocab: i32) -> Vec<u8> {
+        let mut bytes = 0x6767_6d6c_u32.to_le_bytes().to_vec();
+        for value in [n_vocab, 1500, 512, 8, 6, 448, 512, 8, 6, 80, 1] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_ggml_header_validation() {
+        use crate::whisper::custom_models::parse_ggml_header;
+
+        let english = parse_ggml_header(&ggml_header(51_864)).unwrap();
+        assert!(!english.is_multilingual());
+        assert_eq!((english.n_audio_layer, english.n_mels), (6, 80));
+        assert!(parse_ggml_header(&ggml_header(51_866))
+            .unwrap()
+            .is_multilingual());
+
+        let err = |bytes: &[u8]| parse_ggml_header(bytes).unwrap_err();
+        assert!(err(b"GGUF\x03\x00\x00\x00").contains("GGUF"));
+        assert!(err(&ggml_header(51_864)[..20]).contains("too short"));
+        assert!(err(&[0u8; 64]).contains("header is missing"));
+        // A ggml file of some other model, e.g. a tiny LLM vocabulary
+        assert!(err(&ggml_header(32_000)).contains("Not a Whisper model"));
+    }
+
+    #[test]
+    fn test_custom_model_names() {
+        use crate::whisper::custom_models::custom_model_name;
+
+        assert_eq!(
+            custom_model_name("  My Fine-Tune (v2) ", true).unwrap(),
+            "custom-my-fine-tune-v2"
+        );
+        assert_eq!(
+            custom_model_name("Medical", false).unwrap(),
+            "custom-medical.en"
+        );
+        assert!(custom_model_name("../..", true).is_err());
+    }
+
+    #[test]
+    fn test_custom_model_registry() {
+        use crate::whisper::custom_models::CustomModelEntry;
+
+        let temp_dir = TempDir::new().unwrap();
+        let models_dir = temp_dir.path().to_path_buf();
+        let entry = CustomModelEntry {
+            name: "custom-medical.en".to_string(),
+            display_name: "Medical".to_string(),
+            size: 20 * 1024 * 1024,
+            multilingual: false,
+        };
+
+        let mut manager = WhisperManager::new(models_dir.clone());
+        // The file has to be in place first
+        assert!(manager.add_custom_model(entry.clone()).is_err());
+        let file = models_dir.join("custom-medical.en.bin");
+        std::fs::File::create(&file)
+            .unwrap()
+            .set_len(entry.size)
+            .unwrap();
+        manager.add_custom_model(entry.clone()).unwrap();
+        assert_eq!(
+            manager.get_model_path("custom-medical.en"),
+            Some(file.clone())
+        );
+        assert!(manager
+            .get_downloaded_model_names()
+            .contains(&"custom-medical.en".to_string()));
+        assert!(manager.add_custom_model(entry.clone()).is_err());
+
+        // Still there after a restart, and never offered for download
+        let mut manager = WhisperManager::new(models_dir.clone());
+        assert_eq!(manager.get_model_path("custom-medical.en"), Some(file));
+        assert!(manager.get_model_info("custom-medical.en").is_err());
+
+        // Deleting an imported model removes it from the list for good
+        manager.delete_model_file("custom-medical.en").unwrap();
+        assert!(!manager
+            .get_models_status()
+            .contains_key("custom-medical.en"));
+        let manager = WhisperManager::new(models_dir);
+        assert!(!manager
+            .get_models_status()
+            .contains_key("custom-medical.en"));
+    }
 }
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use crate::commands::profiles::{
+        normalize_profile_name, snapshot_profile_settings, PROFILE_SETTINGS_KEYS,
+    };
+    use crate::commands::settings::{changed_settings_keys, overlay_settings, Settings};
+    use serde_json::{json, Map, Value};
+
+    /// Settings store contents as save_settings and update_ai_settings leave them
+    fn store_contents(settings: &Settings, ai_enabled: bool) -> Map<String, Value> {
+        let mut store = serde_json::to_value(settings)
+            .unwrap()
+            .as_object()
+            .cloned()
+            .unwrap();
+        store.insert("ai_enabled".to_string(), json!(ai_enabled));
+        store.insert("ai_provider".to_string(), json!("openai"));
+        store
+    }
+
+    fn meeting_settings() -> Settings {
+        Settings {
+            current_model: "large-v3-turbo".to_string(),
+            current_model_engine: "whisper".to_string(),
+            language: "de".to_string(),
+            recording_mode: "push_to_talk".to_string(),
+            hotkey: "CommandOrControl+Shift+M".to_string(),
+            normalize_numbers: true,
+            ..Settings::default()
+        }
+    }
+
+    #[test]
+    fn test_profile_save_apply_round_trip() {
+        let meeting_store = store_contents(&meeting_settings(), true);
+        let profile = snapshot_profile_settings(|key| meeting_store.get(key).cloned());
+
+        // Only profile keys are captured; window state and the like are left out
+        assert!(profile
+            .keys()
+            .all(|k| PROFILE_SETTINGS_KEYS.contains(&k.as_str())));
+        assert!(!profile.contains_key("pill_position"));
+        assert!(!profile.contains_key("onboarding_completed"));
+        assert_eq!(profile["ai_enabled"], true);
+
+        // Switching from the quick note setup changes exactly what differs
+        let quick_note = Settings {
+            current_model: "base.en".to_string(),
+            ..Settings::default()
+        };
+        let quick_store = store_contents(&quick_note, false);
+        let current = snapshot_profile_settings(|key| quick_store.get(key).cloned());
+        let mut changed = changed_settings_keys(&current, &profile);
+        changed.sort();
+        assert_eq!(
+            changed,
+            vec![
+                "ai_enabled",
+                "current_model",
+                "hotkey",
+                "language",
+                "normalize_numbers",
+                "recording_mode",
+            ]
+        );
+
+        let applied = overlay_settings(&quick_note, &profile).unwrap();
+        assert_eq!(applied.current_model, "large-v3-turbo");
+        assert_eq!(applied.language, "de");
+        assert_eq!(applied.recording_mode, "push_to_talk");
+        assert_eq!(applied.hotkey, "CommandOrControl+Shift+M");
+        assert!(applied.normalize_numbers);
+        // Settings outside the profile keep their current values
+        assert_eq!(applied.theme, quick_note.theme);
+
+        // Saving again right after applying gives back the same profile
+        let applied_store = store_contents(&applied, true);
+        let resaved = snapshot_profile_settings(|key| applied_store.get(key).cloned());
+        assert_eq!(resaved, profile);
+        assert!(changed_settings_keys(&resaved, &profile).is_empty());
+    }
+
+    #[test]
+    fn test_profile_missing_keys_are_left_alone() {
+        // A profile saved before a setting existed doesn't reset it
+        let mut profile = Map::new();
+        profile.insert("current_model".to_string(), json!("base.en"));
+
+        let current = meeting_settings();
+        let applied = overlay_settings(&current, &profile).unwrap();
+        assert_eq!(applied.current_model, "base.en");
+        assert_eq!(applied.language, "de");
+        assert_eq!(applied.hotkey, current.hotkey);
+
+        // Keys outside Settings are ignored by the overlay; a wrong type is an error
+        profile.insert("ai_enabled".to_string(), json!(true));
+        assert!(overlay_settings(&current, &profile).is_ok());
+        profile.insert("language".to_string(), json!(42));
+        assert!(overlay_settings(&current, &profile).is_err());
+    }
+
+    #[test]
+    fn test_normalize_profile_name() {
+        assert_eq!(normalize_profile_name("  Meeting ").unwrap(), "Meeting");
+        assert!(normalize_profile_name("   ").is_err());
+        assert!(normalize_profile_name(&"x".repeat(51)).is_err());
+        assert!(normalize_profile_name(&"é".repeat(50)).is_ok());
+    }
+}
@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests {
-    use crate::commands::settings::{get_supported_languages, Settings};
+    use crate::commands::settings::{
+        apply_model_selection, get_supported_languages, parse_model_hotkeys, ModelHotkey, Settings,
+    };
     use serde_json::json;
+    use std::collections::HashMap;
 
     #[test]
     fn test_settings_default() {
@@ -38,6 +41,69 @@ mod tests {
             keep_transcription_in_clipboard: false,
             play_sound_on_recording: true,
             show_pill_indicator: true,
+            battery_aware_model: false,
+            battery_threshold_percent: 20,
+            keep_raw_audio: false,
+            preset_cycle_hotkey: None,
+            normalize_fallback_to_raw: true,
+            ai_stream_insert: true,
+            raw_insert_hotkey: None,
+            insertion_method: "type".to_string(),
+            app_insertion_rules: Vec::new(),
+            replacement_rules: Vec::new(),
+            voice_commands_enabled: true,
+            voice_command_map: HashMap::new(),
+            normalize_numbers: true,
+            tray_recent_count: 10,
+            tray_recent_label_length: 60,
+            model_hotkeys: Vec::new(),
+            double_tap_window_ms: 300,
+            pill_theme: PillTheme::default(),
+            output_mode: "clipboard_only".to_string(),
+            model_cache_capacity: 2,
+            download_connections: 4,
+            use_gpu: false,
+            soniox_streaming: true,
+            low_confidence_threshold: 0.7,
+            warn_language_mismatch: true,
+            auto_cleanup: crate::utils::auto_cleanup::AutoCleanupConfig::default(),
+            encrypt_history: true,
+            redaction_patterns: vec![r"\d+".to_string()],
+            stereo_downmix: "left".to_string(),
+            loudness_normalize: "accurate".to_string(),
+            loudness_target_lufs: -18.0,
+            loudness_normalize_cloud: true,
+            min_recording_seconds: 0.0,
+            trim_silence_on_save: true,
+            transcription_max_retries: 5,
+            transcription_retry_delay_ms: 250,
+            retry_cloud_transcription: true,
+            whisper_language: Some("en".to_string()),
+            parakeet_language: None,
+            soniox_language: Some("auto".to_string()),
+            play_sound_on_recording_end: true,
+            start_sound_path: Some("/Users/me/Sounds/start.wav".to_string()),
+            end_sound_path: None,
+            mute_sounds_in_calls: true,
+            max_concurrent_uploads: 3,
+            keep_partial_on_cancel: true,
+            remember_mode_per_device: true,
+            device_recording_modes: HashMap::new(),
+            chunk_long_recordings_after_secs: 600,
+            fallback_blocklist: vec!["large-v3".to_string()],
+            skip_enhancement_modifier: "alt".to_string(),
+            lock_target_window: true,
+            show_result_summary: true,
+            toast_duration_scale: 2.0,
+            toast_position: "below".to_string(),
+            deduplicate_repeats: false,
+            on_empty_transcription: "keep_recording".to_string(),
+            tray_recording_timer: true,
+            hotkey_debounce_ms: 300,
+            local_api_enabled: true,
+            local_api_port: 47843,
+            copy_last_hotkey: Some("CommandOrControl+Shift+C".to_string()),
+            attach_screenshot: true,
         };
 
         // Test serialization
@@ -97,6 +163,69 @@ mod tests {
             keep_transcription_in_clipboard: true,
             play_sound_on_recording: false,
             show_pill_indicator: false,
+            battery_aware_model: false,
+            battery_threshold_percent: 20,
+            keep_raw_audio: false,
+            preset_cycle_hotkey: None,
+            normalize_fallback_to_raw: true,
+            ai_stream_insert: true,
+            raw_insert_hotkey: None,
+            insertion_method: "type".to_string(),
+            app_insertion_rules: Vec::new(),
+            replacement_rules: Vec::new(),
+            voice_commands_enabled: true,
+            voice_command_map: HashMap::new(),
+            normalize_numbers: true,
+            tray_recent_count: 10,
+            tray_recent_label_length: 60,
+            model_hotkeys: Vec::new(),
+            double_tap_window_ms: 300,
+            pill_theme: PillTheme::default(),
+            output_mode: "clipboard_only".to_string(),
+            model_cache_capacity: 2,
+            download_connections: 4,
+            use_gpu: false,
+            soniox_streaming: true,
+            low_confidence_threshold: 0.7,
+            warn_language_mismatch: true,
+            auto_cleanup: crate::utils::auto_cleanup::AutoCleanupConfig::default(),
+            encrypt_history: true,
+            redaction_patterns: vec![r"\d+".to_string()],
+            stereo_downmix: "left".to_string(),
+            loudness_normalize: "accurate".to_string(),
+            loudness_target_lufs: -18.0,
+            loudness_normalize_cloud: true,
+            min_recording_seconds: 0.0,
+            trim_silence_on_save: true,
+            transcription_max_retries: 5,
+            transcription_retry_delay_ms: 250,
+            retry_cloud_transcription: true,
+            whisper_language: Some("en".to_string()),
+            parakeet_language: None,
+            soniox_language: Some("auto".to_string()),
+            play_sound_on_recording_end: true,
+            start_sound_path: Some("/Users/me/Sounds/start.wav".to_string()),
+            end_sound_path: None,
+            mute_sounds_in_calls: true,
+            max_concurrent_uploads: 3,
+            keep_partial_on_cancel: true,
+            remember_mode_per_device: true,
+            device_recording_modes: HashMap::new(),
+            chunk_long_recordings_after_secs: 600,
+            fallback_blocklist: vec!["large-v3".to_string()],
+            skip_enhancement_modifier: "alt".to_string(),
+            lock_target_window: true,
+            show_result_summary: true,
+            toast_duration_scale: 2.0,
+            toast_position: "below".to_string(),
+            deduplicate_repeats: false,
+            on_empty_transcription: "keep_recording".to_string(),
+            tray_recording_timer: true,
+            hotkey_debounce_ms: 300,
+            local_api_enabled: true,
+            local_api_port: 47843,
+            copy_last_hotkey: Some("CommandOrControl+Shift+C".to_string()),
+            attach_screenshot: true,
         };
 
         let cloned = settings.clone();
@@ -175,6 +304,122 @@ mod tests {
         assert_eq!(specific_settings.current_model, "base");
     }
 
+    #[test]
+    fn test_parse_model_hotkeys() {
+        let value = json!([
+            { "hotkey": "CommandOrControl+Shift+1", "model_name": "base.en", "engine": "whisper" },
+            { "hotkey": " CommandOrControl+Shift+2 ", "model_name": "large-v3", "start_recording": true },
+            // Skipped: no model, no hotkey, wrong shape, duplicate hotkey
+            { "hotkey": "CommandOrControl+Shift+3", "model_name": "" },
+            { "hotkey": "", "model_name": "tiny" },
+            "CommandOrControl+Shift+4",
+            { "hotkey": "CommandOrControl+Shift+1", "model_name": "tiny" },
+            // Unknown engine is cleared so it gets looked up from the model name
+            { "hotkey": "CommandOrControl+Shift+5", "model_name": "parakeet-tdt-0.6b-v3", "engine": "Nvidia" }
+        ]);
+
+        let hotkeys = parse_model_hotkeys(&value);
+        assert_eq!(
+            hotkeys,
+            vec![
+                ModelHotkey {
+                    hotkey: "CommandOrControl+Shift+1".to_string(),
+                    model_name: "base.en".to_string(),
+                    engine: "whisper".to_string(),
+                    start_recording: false,
+                },
+                ModelHotkey {
+                    hotkey: "CommandOrControl+Shift+2".to_string(),
+                    model_name: "large-v3".to_string(),
+                    engine: String::new(),
+                    start_recording: true,
+                },
+                ModelHotkey {
+                    hotkey: "CommandOrControl+Shift+5".to_string(),
+                    model_name: "parakeet-tdt-0.6b-v3".to_string(),
+                    engine: String::new(),
+                    start_recording: false,
+                },
+            ]
+        );
+
+        assert!(parse_model_hotkeys(&json!(null)).is_empty());
+        assert!(parse_model_hotkeys(&json!({ "hotkey": "A" })).is_empty());
+    }
+
+    #[test]
+    fn test_apply_model_selection() {
+        let mut settings = Settings {
+            current_model: "base.en".to_string(),
+            current_model_engine: "whisper".to_string(),
+            language: "de".to_string(),
+            ..Settings::default()
+        };
+
+        apply_model_selection(&mut settings, "parakeet-tdt-0.6b-v3", "parakeet");
+
+        assert_eq!(settings.current_model, "parakeet-tdt-0.6b-v3");
+        assert_eq!(settings.current_model_engine, "parakeet");
+        // Matches the tray: switching models resets the language
+        assert_eq!(settings.language, "en");
+    }
+
+    #[test]
+    fn test_precheck_hotkey() {
+        use tauri_plugin_global_shortcut::Shortcut;
+
+        let recording: Shortcut = "CommandOrControl+Shift+Space".parse().unwrap();
+        let registered = vec![(recording, "recording".to_string())];
+
+        // Free combos pass through normalized
+        let (normalized, _) = precheck_hotkey("Cmd+Shift+R", &registered).unwrap();
+        assert_eq!(normalized, "CommandOrControl+Shift+R");
+
+        // Spelled differently but the same combo as the recording hotkey
+        assert_eq!(
+            precheck_hotkey("ctrl+shift+space", &registered).unwrap_err(),
+            HotkeyAvailability::InUseByVoicetypr {
+                normalized: "CommandOrControl+Shift+Space".to_string(),
+                used_for: "recording".to_string(),
+            }
+        );
+
+        for invalid in ["", "A", "Space+Shift", "CommandOrControl+InvalidKey"] {
+            assert!(
+                matches!(
+                    precheck_hotkey(invalid, &registered),
+                    Err(HotkeyAvailability::InvalidFormat { .. })
+                ),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_hotkey_availability_serialization() {
+        let value = serde_json::to_value(HotkeyAvailability::RegistrationFailed {
+            normalized: "Alt+A".to_string(),
+            error: "already registered".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "status": "registration_failed",
+                "normalized": "Alt+A",
+                "error": "already registered"
+            })
+        );
+
+        let value = serde_json::to_value(HotkeyAvailability::InUseByVoicetypr {
+            normalized: "Alt+B".to_string(),
+            used_for: "push-to-talk".to_string(),
+        })
+        .unwrap();
+        assert_eq!(value["status"], "in_use_by_voicetypr");
+    }
+
     #[test]
     fn test_settings_to_json_value() {
         let settings = Settings::default();
@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod tests {
+    use crate::commands::settings::Settings;
+    use crate::commands::settings_transfer::{
+        build_settings_export, is_secret_key, parse_settings_import, SETTINGS_EXPORT_VERSION,
+    };
+    use serde_json::{json, Map, Value};
+
+    /// A settings store as it looks after the settings and AI pages were saved
+    fn configured_store() -> Map<String, Value> {
+        let settings = Settings {
+            hotkey: "CommandOrControl+Shift+R".to_string(),
+            current_model: "large-v3-turbo".to_string(),
+            language: "fr".to_string(),
+            recording_mode: "push_to_talk".to_string(),
+            onboarding_completed: true,
+            pill_position: Some((120.0, 40.0)),
+            ..Settings::default()
+        };
+        let mut store = serde_json::to_value(settings)
+            .unwrap()
+            .as_object()
+            .cloned()
+            .unwrap();
+        store.insert("ai_enabled".to_string(), json!(true));
+        store.insert("ai_provider".to_string(), json!("openai"));
+        store.insert("ai_model".to_string(), json!("gpt-4o-mini"));
+        store.insert("models_directory".to_string(), json!("/Volumes/External"));
+        store.insert(
+            "ai_api_key_openai".to_string(),
+            json!("sk-should-never-leak"),
+        );
+        store
+    }
+
+    fn export_json(store: &Map<String, Value>) -> String {
+        let export = build_settings_export(
+            |key| store.get(key).cloned(),
+            vec!["ai_api_key_openai".to_string()],
+            "2025-01-01T00:00:00Z",
+        );
+        serde_json::to_string_pretty(&export).unwrap()
+    }
+
+    #[test]
+    fn test_settings_export_import_round_trip() {
+        let store = configured_store();
+        let json = export_json(&store);
+
+        // Secrets and machine-specific values never reach the file
+        assert!(!json.contains("sk-should-never-leak"));
+        assert!(!json.contains("pill_position"));
+        assert!(!json.contains("onboarding_completed"));
+        assert!(!json.contains("/Volumes/External"));
+
+        let parsed = parse_settings_import(&json).unwrap();
+        assert_eq!(parsed.secrets_to_reenter, vec!["ai_api_key_openai"]);
+        assert!(parsed.skipped.is_empty());
+
+        // Every exported value comes back unchanged
+        for (key, value) in &parsed.values {
+            assert_eq!(store.get(key), Some(value), "{} changed", key);
+        }
+        assert_eq!(parsed.values["hotkey"], "CommandOrControl+Shift+R");
+        assert_eq!(parsed.values["language"], "fr");
+        assert_eq!(parsed.values["ai_enabled"], true);
+        assert_eq!(parsed.values["ai_model"], "gpt-4o-mini");
+        assert!(parsed.values.contains_key("model_hotkeys"));
+    }
+
+    #[test]
+    fn test_settings_import_rejects_malformed_files() {
+        let json = export_json(&configured_store());
+
+        // Truncated file
+        assert!(parse_settings_import(&json[..json.len() / 2]).is_err());
+        assert!(parse_settings_import("").is_err());
+        assert!(parse_settings_import("[]").is_err());
+
+        let mut document: Value = serde_json::from_str(&json).unwrap();
+        let with = |change: &dyn Fn(&mut Value)| {
+            let mut changed = document.clone();
+            change(&mut changed);
+            parse_settings_import(&changed.to_string())
+        };
+
+        assert!(with(&|d| d["app"] = json!("SomethingElse")).is_err());
+        assert!(with(&|d| d["version"] = json!(SETTINGS_EXPORT_VERSION + 1)).is_err());
+        assert!(with(&|d| {
+            d.as_object_mut().unwrap().remove("version");
+        })
+        .is_err());
+        assert!(with(&|d| {
+            d.as_object_mut().unwrap().remove("settings");
+        })
+        .is_err());
+        assert!(with(&|d| d["settings"] = json!({})).is_err());
+
+        // One bad value rejects the whole file
+        assert!(with(&|d| d["settings"]["language"] = json!(7)).is_err());
+        assert!(with(&|d| d["settings"]["model_hotkeys"] = json!("F1")).is_err());
+        assert!(with(&|d| d["settings"]["ai_enabled"] = json!("yes")).is_err());
+
+        // Unknown, secret and machine-specific keys are skipped rather than imported
+        document["settings"]["future_setting"] = json!(1);
+        document["settings"]["stt_api_key_soniox"] = json!("secret");
+        document["settings"]["pill_position"] = json!([0, 0]);
+        let parsed = parse_settings_import(&document.to_string()).unwrap();
+        let mut skipped = parsed.skipped.clone();
+        skipped.sort();
+        assert_eq!(
+            skipped,
+            vec!["future_setting", "pill_position", "stt_api_key_soniox"]
+        );
+        assert!(!parsed.values.contains_key("stt_api_key_soniox"));
+
+        // A partial export only carries the keys it has
+        let partial = json!({
+            "app": "VoiceTypr",
+            "version": SETTINGS_EXPORT_VERSION,
+            "settings": { "language": "de" }
+        });
+        let parsed = parse_settings_import(&partial.to_string()).unwrap();
+        assert_eq!(parsed.values.len(), 1);
+        assert!(parsed.secrets_to_reenter.is_empty());
+    }
+
+    #[test]
+    fn test_is_secret_key() {
+        assert!(is_secret_key("ai_api_key_openai"));
+        assert!(is_secret_key("stt_api_key_soniox"));
+        assert!(is_secret_key("license"));
+        assert!(is_secret_key("refresh_token"));
+        assert!(!is_secret_key("hotkey"));
+        assert!(!is_secret_key("ai_provider"));
+    }
+}
@@ -1,5 +1,14 @@
 #[cfg(test)]
 mod tests {
+    use crate::commands::audio::{
+        apply_reenhanced_text, compute_productivity_report, compute_transcription_stats,
+        compute_usage_analytics, entry_has_language, expired_history_keys,
+        failed_entries_with_audio, insert_detected_language, language_mismatch_warning,
+        latest_entry_text, mark_entry_retranscribed, paginate_history_entries,
+        parse_analytics_range, reenhance_source, remove_entry_files, resolve_entry_language,
+        FailedEntry,
+    };
+    use crate::whisper::transcriber::DetectedLanguage;
     use chrono::{Duration, Utc};
     use serde_json::json;
 
@@ -131,6 +140,286 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_history_pagination_windows() {
+        // 120 entries, one minute apart; "Transcription 0" is the newest
+        let now = Utc::now();
+        let entries: Vec<(String, serde_json::Value)> = (0..120)
+            .map(|i| {
+                let timestamp = (now - Duration::minutes(i)).to_rfc3339();
+                (
+                    timestamp.clone(),
+                    json!({
+                        "text": format!("Transcription {}", i),
+                        "model": "base",
+                        "timestamp": timestamp
+                    }),
+                )
+            })
+            .rev()
+            .collect();
+
+        let first = paginate_history_entries(entries.clone(), 0, 50);
+        let second = paginate_history_entries(entries.clone(), 50, 50);
+        let third = paginate_history_entries(entries, 100, 50);
+
+        assert_eq!(first.len(), 50);
+        assert_eq!(second.len(), 50);
+        assert_eq!(third.len(), 20);
+
+        // The second page picks up exactly where the first one ended
+        assert_eq!(first[49]["text"], "Transcription 49");
+        assert_eq!(second[0]["text"], "Transcription 50");
+        assert_eq!(second[49]["text"], "Transcription 99");
+
+        // Pages joined together cover every entry once, newest first
+        let all: Vec<_> = first.iter().chain(&second).chain(&third).collect();
+        for (i, entry) in all.iter().enumerate() {
+            assert_eq!(entry["text"], format!("Transcription {}", i));
+        }
+    }
+
+    #[test]
+    fn test_history_language_filter() {
+        let entries = vec![
+            (
+                "2025-01-03T10:00:00+00:00".to_string(),
+                json!({"text": "bonjour", "model": "base", "language": "fr"}),
+            ),
+            (
+                "2025-01-02T10:00:00+00:00".to_string(),
+                json!({"text": "hello", "model": "base", "language": "en"}),
+            ),
+            // Entry saved before languages were recorded
+            (
+                "2025-01-01T10:00:00+00:00".to_string(),
+                json!({"text": "legacy", "model": "base"}),
+            ),
+        ];
+
+        let french: Vec<_> = entries
+            .iter()
+            .filter(|(_, value)| entry_has_language(value, "FR"))
+            .collect();
+        assert_eq!(french.len(), 1);
+        assert_eq!(french[0].1["text"], "bonjour");
+
+        assert!(!entries
+            .iter()
+            .any(|(_, value)| entry_has_language(value, "de")));
+    }
+
+    #[test]
+    fn test_resolve_entry_language() {
+        // Detected language wins over the configured one
+        assert_eq!(
+            resolve_entry_language(Some("auto"), Some("DE")).as_deref(),
+            Some("de")
+        );
+        assert_eq!(
+            resolve_entry_language(Some("en"), None).as_deref(),
+            Some("en")
+        );
+        assert_eq!(resolve_entry_language(Some("auto"), None), None);
+        assert_eq!(resolve_entry_language(None, Some(" ")), None);
+    }
+
+    #[test]
+    fn test_transcription_stats_word_counting() {
+        let now = Utc::now();
+        let entries = vec![
+            (
+                now.to_rfc3339(),
+                json!({"text": "  hello    world  ", "model": "base.en"}),
+            ),
+            (
+                (now - Duration::minutes(1)).to_rfc3339(),
+                json!({"text": "你好 мир\tñandú\nfin", "model": "large-v3"}),
+            ),
+            (
+                (now - Duration::minutes(2)).to_rfc3339(),
+                json!({"text": "", "model": "base.en"}),
+            ),
+            (
+                (now - Duration::minutes(3)).to_rfc3339(),
+                json!({"text": "should not count", "model": "base.en", "status": "failed"}),
+            ),
+        ];
+
+        let stats = compute_transcription_stats(&entries);
+
+        assert_eq!(stats.total_entries, 3);
+        assert_eq!(stats.total_words, 2 + 4);
+        assert_eq!(
+            stats.total_chars,
+            "  hello    world  ".chars().count() + "你好 мир\tñandú\nfin".chars().count()
+        );
+        assert_eq!(stats.by_model.get("base.en"), Some(&2));
+        assert_eq!(stats.by_model.get("large-v3"), Some(&1));
+        assert_eq!(stats.by_day.values().sum::<usize>(), 3);
+        assert!((stats.avg_words_per_entry - 2.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_transcription_stats_empty() {
+        let stats = compute_transcription_stats(&[]);
+        assert_eq!(stats.total_entries, 0);
+        assert_eq!(stats.avg_words_per_entry, 0.0);
+        assert!(stats.by_day.is_empty());
+    }
+
+    #[test]
+    fn test_usage_analytics_aggregation() {
+        let now = Utc::now();
+        let entries = vec![
+            (
+                now.to_rfc3339(),
+                json!({"text": "one two three", "model": "base.en", "engine": "whisper", "duration_seconds": 3.0}),
+            ),
+            (
+                (now - Duration::minutes(1)).to_rfc3339(),
+                json!({"text": "four five", "model": "parakeet-tdt-0.6b-v3", "engine": "parakeet", "duration_seconds": 1.0}),
+            ),
+            (
+                (now - Duration::minutes(2)).to_rfc3339(),
+                // Entries saved before durations were recorded
+                json!({"text": "six", "model": "base.en"}),
+            ),
+            (
+                (now - Duration::minutes(3)).to_rfc3339(),
+                json!({"text": "", "model": "base.en", "status": "failed", "duration_seconds": 9.0}),
+            ),
+        ];
+
+        let analytics = compute_usage_analytics(&entries);
+
+        assert_eq!(analytics.total_dictations, 3);
+        assert_eq!(analytics.total_words, 6);
+        assert_eq!(analytics.avg_recording_seconds, Some(2.0));
+        assert_eq!(analytics.by_engine.get("whisper"), Some(&1));
+        assert_eq!(analytics.by_engine.get("parakeet"), Some(&1));
+        assert_eq!(analytics.by_engine.get("unknown"), Some(&1));
+        assert_eq!(analytics.by_model.get("base.en"), Some(&2));
+        assert_eq!(
+            analytics.per_day.values().map(|d| d.words).sum::<usize>(),
+            6
+        );
+        assert_eq!(analytics.by_hour.len(), 24);
+        assert_eq!(analytics.by_hour.iter().sum::<usize>(), 3);
+        assert!(!analytics.peak_hours.is_empty() && analytics.peak_hours.len() <= 2);
+    }
+
+    #[test]
+    fn test_usage_analytics_peak_hours() {
+        let at = |hour: u32| {
+            chrono::Local::now()
+                .date_naive()
+                .and_hms_opt(hour, 15, 0)
+                .unwrap()
+                .and_local_timezone(chrono::Local)
+                .unwrap()
+                .to_rfc3339()
+        };
+        let entry = json!({"text": "hi", "model": "base.en"});
+        let entries: Vec<_> = [9, 14, 14, 14, 9, 20, 7]
+            .into_iter()
+            .map(|hour| (at(hour), entry.clone()))
+            .collect();
+
+        let analytics = compute_usage_analytics(&entries);
+
+        assert_eq!(analytics.by_hour[14], 3);
+        // Busiest first, earlier hour breaks the tie between 7 and 20
+        assert_eq!(analytics.peak_hours, vec![14, 9, 7]);
+        assert_eq!(analytics.avg_recording_seconds, None);
+
+        let empty = compute_usage_analytics(&[]);
+        assert_eq!(empty.total_dictations, 0);
+        assert!(empty.peak_hours.is_empty());
+    }
+
+    #[test]
+    fn test_productivity_report_bucketing() {
+        let at = |days_ago: i64, hour: u32| {
+            (chrono::Local::now().date_naive() - Duration::days(days_ago))
+                .and_hms_opt(hour, 30, 0)
+                .unwrap()
+                .and_local_timezone(chrono::Local)
+                .unwrap()
+        };
+        let day = |days_ago: i64| at(days_ago, 12).format("%Y-%m-%d").to_string();
+        let entries = vec![
+            (
+                at(0, 9).to_rfc3339(),
+                json!({"text": "one two three four", "model": "base.en", "language": "en", "duration_seconds": 4.0}),
+            ),
+            (
+                at(0, 9).to_rfc3339(),
+                json!({"text": "five six", "model": "large-v3", "language": "de", "duration_seconds": 2.0}),
+            ),
+            (
+                at(1, 16).to_rfc3339(),
+                json!({"text": "seven eight nine ten eleven twelve", "model": "base.en", "language": "de"}),
+            ),
+            (
+                at(1, 9).to_rfc3339(),
+                json!({"text": "", "model": "large-v3", "status": "failed", "duration_seconds": 30.0}),
+            ),
+        ];
+
+        let report = compute_productivity_report(&entries);
+
+        assert_eq!(report.total_dictations, 3);
+        assert_eq!(report.total_words, 12);
+        assert_eq!(report.words_by_day.len(), 2);
+        assert_eq!(report.words_by_day.get(&day(0)), Some(&6));
+        assert_eq!(report.words_by_day.get(&day(1)), Some(&6));
+        assert_eq!(report.words_per_day, 6.0);
+        // Only the two entries that recorded a duration count
+        assert_eq!(report.avg_session_seconds, Some(3.0));
+        assert_eq!(report.by_hour[9], 2);
+        assert_eq!(report.by_hour[16], 1);
+        assert_eq!(report.by_hour.iter().sum::<usize>(), 3);
+        assert_eq!(report.peak_hours, vec![9, 16]);
+        assert_eq!(report.most_used_model.as_deref(), Some("base.en"));
+        assert_eq!(report.most_used_language.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn test_productivity_report_empty_and_ties() {
+        let empty = compute_productivity_report(&[]);
+        assert_eq!(empty.total_dictations, 0);
+        assert_eq!(empty.words_per_day, 0.0);
+        assert_eq!(empty.avg_session_seconds, None);
+        assert_eq!(empty.most_used_model, None);
+        assert_eq!(empty.most_used_language, None);
+        assert_eq!(empty.by_hour, vec![0; 24]);
+        assert!(empty.peak_hours.is_empty());
+
+        let now = Utc::now();
+        let entries = vec![
+            (now.to_rfc3339(), json!({"text": "hi", "model": "small"})),
+            (
+                (now - Duration::minutes(1)).to_rfc3339(),
+                json!({"text": "hi", "model": "base"}),
+            ),
+        ];
+        let report = compute_productivity_report(&entries);
+        // Equal counts resolve alphabetically; entries without a language don't count
+        assert_eq!(report.most_used_model.as_deref(), Some("base"));
+        assert_eq!(report.most_used_language, None);
+    }
+
+    #[test]
+    fn test_parse_analytics_range() {
+        assert_eq!(parse_analytics_range(None), Ok(None));
+        assert_eq!(parse_analytics_range(Some("all")), Ok(None));
+        assert_eq!(parse_analytics_range(Some("7d")), Ok(Some(7)));
+        assert_eq!(parse_analytics_range(Some(" 30d ")), Ok(Some(30)));
+        assert!(parse_analytics_range(Some("0d")).is_err());
+        assert!(parse_analytics_range(Some("week")).is_err());
+    }
+
     #[test]
     fn test_transcription_content() {
         // Test various transcription content scenarios
@@ -191,4 +480,415 @@ mod tests {
         assert_eq!(sorted[0], timestamps[0]); // Most recent should be first
         assert_eq!(sorted[sorted.len() - 1], timestamps[timestamps.len() - 1]); // Oldest should be last
     }
+
+    #[test]
+    fn test_detected_language_persisted_and_returned() {
+        let detected = DetectedLanguage {
+            code: "de".to_string(),
+            probability: 0.91234,
+        };
+        let mut metadata = serde_json::Map::new();
+        metadata.insert("language".to_string(), json!("en"));
+        insert_detected_language(&mut metadata, &detected);
+
+        // Same shape save_transcription_with_metadata writes to the store
+        let timestamp = Utc::now().to_rfc3339();
+        let mut entry = json!({
+            "text": "Guten Morgen",
+            "model": "base",
+            "timestamp": &timestamp
+        });
+        entry.as_object_mut().unwrap().extend(metadata);
+
+        // Round-trip through the store's JSON file
+        let persisted: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&entry).unwrap()).unwrap();
+        let returned = paginate_history_entries(vec![(timestamp, persisted)], 0, 50);
+
+        assert_eq!(returned.len(), 1);
+        assert_eq!(returned[0]["language"], "en");
+        assert_eq!(returned[0]["detected_language"], "de");
+        assert_eq!(returned[0]["detected_language_probability"], 0.912);
+    }
+
+    #[test]
+    fn test_language_mismatch_warning() {
+        let german = |probability| DetectedLanguage {
+            code: "de".to_string(),
+            probability,
+        };
+
+        assert_eq!(
+            language_mismatch_warning(Some(&german(0.95)), Some("en")).as_deref(),
+            Some("Sounded like German — check the language setting")
+        );
+        // Unsure guesses, matching languages and auto-detect stay quiet
+        assert_eq!(
+            language_mismatch_warning(Some(&german(0.6)), Some("en")),
+            None
+        );
+        assert_eq!(
+            language_mismatch_warning(Some(&german(0.95)), Some("de")),
+            None
+        );
+        assert_eq!(
+            language_mismatch_warning(Some(&german(0.95)), Some("auto")),
+            None
+        );
+        assert_eq!(language_mismatch_warning(Some(&german(0.95)), None), None);
+        assert_eq!(language_mismatch_warning(None, Some("en")), None);
+    }
+
+    #[test]
+    fn test_expired_history_keys_cutoff() {
+        let now = Utc::now();
+        let key = |days: i64| (now - Duration::days(days)).to_rfc3339();
+        let keys = vec![
+            key(0),
+            key(29),
+            key(31),
+            key(365),
+            "not-a-timestamp".to_string(),
+        ];
+
+        let mut expired = expired_history_keys(keys, now - Duration::days(30));
+        expired.sort();
+        let mut expected = vec![key(31), key(365)];
+        expected.sort();
+        assert_eq!(expired, expected);
+
+        // Nothing is older than a cutoff in the past
+        assert!(expired_history_keys(vec![key(0), key(1)], now - Duration::days(7)).is_empty());
+    }
+
+    #[test]
+    fn test_pruned_entry_files_are_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let recording = dir.path().join("recording_1.wav");
+        let raw = dir.path().join("recording_1_raw.wav");
+        let attachment = dir.path().join("attachment_1.png");
+        for file in [&recording, &raw, &attachment] {
+            std::fs::write(file, b"data").unwrap();
+        }
+        let unrelated = dir.path().join("recording_2.wav");
+        std::fs::write(&unrelated, b"RIFF").unwrap();
+
+        // What prune_history_before does for each entry it deletes
+        remove_entry_files(&json!({
+            "text": "old",
+            "recording_file": recording.to_string_lossy(),
+            "raw_recording_file": raw.to_string_lossy(),
+            "attachment_file": attachment.to_string_lossy(),
+        }));
+
+        assert!(!recording.exists());
+        assert!(!raw.exists());
+        assert!(!attachment.exists());
+        assert!(unrelated.exists());
+
+        // Entries without kept files, or whose files are already gone, are fine
+        remove_entry_files(&json!({ "text": "no audio" }));
+        remove_entry_files(&json!({ "recording_file": recording.to_string_lossy() }));
+    }
+
+    #[test]
+    fn test_failed_entries_with_audio_selection() {
+        let dir = tempfile::tempdir().unwrap();
+        let kept = dir.path().join("recording_1.wav");
+        std::fs::write(&kept, b"RIFF").unwrap();
+        let gone = dir.path().join("recording_2.wav");
+
+        let failed = |file: &std::path::Path| {
+            json!({ "text": "", "model": "base", "status": "failed", "error": "timeout",
+                    "recording_file": file.to_string_lossy() })
+        };
+        let entries = vec![
+            ("2026-01-03T10:00:00+00:00".to_string(), failed(&kept)),
+            ("2026-01-01T10:00:00+00:00".to_string(), failed(&gone)),
+            (
+                "2026-01-02T10:00:00+00:00".to_string(),
+                json!({ "text": "fine", "model": "base", "recording_file": kept.to_string_lossy() }),
+            ),
+            (
+                "2026-01-04T10:00:00+00:00".to_string(),
+                json!({ "text": "", "model": "base", "status": "failed" }),
+            ),
+        ];
+
+        let (with_audio, missing_audio) = failed_entries_with_audio(&entries);
+        assert_eq!(
+            with_audio,
+            vec![FailedEntry {
+                timestamp: "2026-01-03T10:00:00+00:00".to_string(),
+                recording_file: kept.clone(),
+            }]
+        );
+        assert_eq!(
+            missing_audio,
+            vec!["2026-01-01T10:00:00+00:00", "2026-01-04T10:00:00+00:00"]
+        );
+    }
+
+    #[test]
+    fn test_retranscribed_entry_updated_in_place() {
+        let failed = json!({
+            "text": "",
+            "model": "base",
+            "engine": "soniox",
+            "timestamp": "2026-01-03T10:00:00+00:00",
+            "status": "failed",
+            "error": "connection reset",
+            "recording_file": "/tmp/recording_1.wav",
+            "duration_seconds": 4.2
+        });
+
+        let updated = mark_entry_retranscribed(failed, "Call the dentist", "large-v3", "whisper");
+        assert_eq!(
+            updated,
+            json!({
+                "text": "Call the dentist",
+                "model": "large-v3",
+                "engine": "whisper",
+                "timestamp": "2026-01-03T10:00:00+00:00",
+                "recording_file": "/tmp/recording_1.wav",
+                "duration_seconds": 4.2
+            })
+        );
+
+        // Counts towards stats now that it is no longer failed
+        let stats =
+            compute_transcription_stats(&[("2026-01-03T10:00:00+00:00".to_string(), updated)]);
+        assert_eq!(stats.total_entries, 1);
+        assert_eq!(stats.total_words, 3);
+    }
+
+    fn history_entry(timestamp: &str) -> (String, serde_json::Value) {
+        (
+            timestamp.to_string(),
+            json!({ "text": format!("said at {}", timestamp), "timestamp": timestamp }),
+        )
+    }
+
+    #[test]
+    fn test_reenhance_source_selection() {
+        // Enhanced entries are re-polished from the original, not from the last output
+        let enhanced = json!({
+            "text": "Please send the report by Friday.",
+            "raw_text": "um please send the report by friday",
+            "model": "base.en"
+        });
+        assert_eq!(
+            reenhance_source(&enhanced).unwrap(),
+            "um please send the report by friday"
+        );
+
+        let plain = json!({"text": "never enhanced", "model": "base.en"});
+        assert_eq!(reenhance_source(&plain).unwrap(), "never enhanced");
+
+        let blank_raw = json!({"text": "stored text", "raw_text": "  "});
+        assert_eq!(reenhance_source(&blank_raw).unwrap(), "stored text");
+
+        assert!(reenhance_source(&json!({"text": "", "status": "failed"})).is_err());
+        assert!(reenhance_source(&json!({"text": "", "decryption_failed": true})).is_err());
+        assert!(reenhance_source(&json!({"text": "   "})).is_err());
+    }
+
+    #[test]
+    fn test_reenhanced_entry_updated_in_place() {
+        let timestamp = "2024-06-01T10:00:00Z";
+        let entry = json!({
+            "text": "Old polish.",
+            "raw_text": "original words",
+            "model": "base.en",
+            "timestamp": timestamp,
+            "tags": ["work"]
+        });
+        let updated = apply_reenhanced_text(entry, "original words", "New polish.");
+        assert_eq!(updated["text"], "New polish.");
+        assert_eq!(updated["raw_text"], "original words");
+        assert_eq!(updated["model"], "base.en");
+        assert_eq!(updated["timestamp"], timestamp);
+        assert_eq!(updated["tags"], json!(["work"]));
+
+        // Entries without raw_text keep their old text as the original
+        let plain = json!({"text": "never enhanced", "model": "base.en"});
+        let source = reenhance_source(&plain).unwrap();
+        let updated = apply_reenhanced_text(plain, &source, "Never enhanced.");
+        assert_eq!(updated["text"], "Never enhanced.");
+        assert_eq!(updated["raw_text"], "never enhanced");
+    }
+
+    #[test]
+    fn test_history_shard_months() {
+        use crate::utils::history_store::{shard_month, shard_store_name};
+
+        assert_eq!(shard_month("2024-06-15T10:00:00+00:00"), "2024-06");
+        // Shards follow UTC, like the timestamps entries are saved with
+        assert_eq!(shard_month("2024-06-30T23:30:00-02:00"), "2024-07");
+        assert_eq!(shard_month("not a timestamp"), "undated");
+        assert_eq!(shard_store_name("2024-06"), "transcriptions-2024-06");
+    }
+
+    #[test]
+    fn test_history_shards_merge_newest_first() {
+        use crate::utils::history_store::{merge_shards, partition_by_month, HistoryIndex};
+
+        let entries = vec![
+            history_entry("2024-05-02T08:00:00+00:00"),
+            history_entry("2024-06-30T23:59:59+00:00"),
+            history_entry("2024-05-31T12:00:00+00:00"),
+            history_entry("2024-07-01T00:00:00+00:00"),
+            history_entry("2024-06-01T00:00:00+00:00"),
+        ];
+        let mut shards = partition_by_month(entries);
+
+        let mut index = HistoryIndex::default();
+        for (month, shard) in &shards {
+            index.set_count(month, shard.len());
+        }
+        assert_eq!(
+            index.months_newest_first(),
+            vec!["2024-07", "2024-06", "2024-05"]
+        );
+
+        let merged = merge_shards(
+            index
+                .months_newest_first()
+                .iter()
+                .map(|month| shards.remove(month).unwrap())
+                .collect(),
+        );
+        let keys: Vec<&str> = merged.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "2024-07-01T00:00:00+00:00",
+                "2024-06-30T23:59:59+00:00",
+                "2024-06-01T00:00:00+00:00",
+                "2024-05-31T12:00:00+00:00",
+                "2024-05-02T08:00:00+00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_undated_history_sorts_last() {
+        use crate::utils::history_store::{sort_newest_first, HistoryIndex};
+
+        let mut index = HistoryIndex::default();
+        index.set_count("undated", 2);
+        index.set_count("2024-05", 1);
+        index.set_count("2024-07", 3);
+        assert_eq!(
+            index.months_newest_first(),
+            vec!["2024-07", "2024-05", "undated"]
+        );
+
+        let mut entries = vec![
+            history_entry("imported-entry"),
+            history_entry("2024-05-02T08:00:00+00:00"),
+            history_entry("2024-07-01T00:00:00+00:00"),
+        ];
+        sort_newest_first(&mut entries);
+        let keys: Vec<&str> = entries.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "2024-07-01T00:00:00+00:00",
+                "2024-05-02T08:00:00+00:00",
+                "imported-entry",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_deletion_targets_entry_shards() {
+        use crate::utils::history_store::{group_by_month, HistoryIndex};
+
+        let timestamps = vec![
+            "2024-06-10T09:00:00+00:00".to_string(),
+            "2024-05-20T09:00:00+00:00".to_string(),
+            "2024-06-11T09:00:00+00:00".to_string(),
+        ];
+        let by_month = group_by_month(&timestamps);
+        assert_eq!(by_month.len(), 2);
+        assert_eq!(by_month["2024-06"], vec![&timestamps[0], &timestamps[2]]);
+        assert_eq!(by_month["2024-05"], vec![&timestamps[1]]);
+
+        // Emptied shards drop out of the index, others keep their count
+        let mut index = HistoryIndex::default();
+        index.set_count("2024-05", 1);
+        index.set_count("2024-06", 5);
+        index.set_count("2024-05", 0);
+        index.set_count("2024-06", 3);
+        assert_eq!(index.months_newest_first(), vec!["2024-06"]);
+        assert_eq!(index.total(), 3);
+    }
+
+    #[test]
+    fn test_legacy_history_migration_partition() {
+        use crate::utils::history_store::{partition_by_month, HistoryIndex};
+
+        let legacy = vec![
+            history_entry("2023-12-31T23:00:00+00:00"),
+            history_entry("2024-01-01T00:30:00+00:00"),
+            history_entry("2024-01-15T12:00:00+00:00"),
+            history_entry("imported-entry"),
+        ];
+        let shards = partition_by_month(legacy.clone());
+
+        assert_eq!(
+            shards.keys().collect::<Vec<_>>(),
+            vec!["2023-12", "2024-01", "undated"]
+        );
+        assert_eq!(shards["2024-01"].len(), 2);
+        // Every entry lands in exactly one shard, unchanged
+        let mut moved: Vec<(String, serde_json::Value)> =
+            shards.values().flatten().cloned().collect();
+        moved.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut expected = legacy;
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(moved, expected);
+
+        // The index written after migration round-trips through the store
+        let mut index = HistoryIndex::default();
+        for (month, shard) in &shards {
+            index.set_count(month, shard.len());
+        }
+        assert_eq!(index.total(), 4);
+        assert_eq!(HistoryIndex::from_value(Some(index.to_value())), index);
+        assert_eq!(HistoryIndex::from_value(None).total(), 0);
+    }
+
+    #[test]
+    fn test_latest_entry_text() {
+        let entries = vec![
+            (
+                "2024-06-03T10:00:00Z".to_string(),
+                json!({ "text": "", "status": "failed", "error": "No speech detected" }),
+            ),
+            (
+                "2024-06-02T10:00:00Z".to_string(),
+                json!({ "text": "", "decryption_failed": true }),
+            ),
+            (
+                "2024-06-01T10:00:00Z".to_string(),
+                json!({ "text": "Send the report today", "model": "base" }),
+            ),
+            (
+                "2024-05-31T10:00:00Z".to_string(),
+                json!({ "text": "Older dictation", "model": "base" }),
+            ),
+        ];
+        assert_eq!(
+            latest_entry_text(&entries).as_deref(),
+            Some("Send the report today")
+        );
+        assert_eq!(
+            latest_entry_text(&entries[3..]).as_deref(),
+            Some("Older dictation")
+        );
+        assert_eq!(latest_entry_text(&entries[..2]), None);
+        assert_eq!(latest_entry_text(&[]), None);
+    }
 }
@@ -0,0 +1,132 @@
+/// The OS accent color as `#rrggbb`, used by the pill's "follow system" theme.
+/// Returns `None` when it can't be determined (unsupported platform or desktop).
+pub fn system_accent_color() -> Option<String> {
+    platform_accent_color()
+}
+
+#[cfg(target_os = "macos")]
+fn platform_accent_color() -> Option<String> {
+    let output = std::process::Command::new("defaults")
+        .args(["read", "-g", "AppleAccentColor"])
+        .output()
+        .ok()?;
+
+    // The key is absent when the default (blue, or multicolor) accent is selected
+    let value = if output.status.success() {
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    } else {
+        String::new()
+    };
+    parse_macos_accent(&value)
+}
+
+#[cfg(target_os = "windows")]
+fn platform_accent_color() -> Option<String> {
+    let output = std::process::Command::new("reg")
+        .args([
+            "query",
+            r"HKCU\Software\Microsoft\Windows\DWM",
+            "/v",
+            "AccentColor",
+        ])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    parse_windows_accent(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "linux")]
+fn platform_accent_color() -> Option<String> {
+    // GNOME 47+; other desktops have no portable equivalent
+    let output = std::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "accent-color"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    parse_gnome_accent(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn platform_accent_color() -> Option<String> {
+    None
+}
+
+/// Map `defaults read -g AppleAccentColor` output to the color macOS shows
+#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+fn parse_macos_accent(value: &str) -> Option<String> {
+    let color = match value.trim() {
+        "" | "4" => "#007aff", // blue (default)
+        "-1" => "#8c8c8c",     // graphite
+        "0" => "#ff5257",
+        "1" => "#f7821b",
+        "2" => "#ffc600",
+        "3" => "#62ba46",
+        "5" => "#a550a7",
+        "6" => "#f74f9e",
+        _ => return None,
+    };
+    Some(color.to_string())
+}
+
+/// Parse the `AccentColor` DWORD from `reg query`; it is stored as 0xAABBGGRR
+#[cfg_attr(not(any(target_os = "windows", test)), allow(dead_code))]
+fn parse_windows_accent(output: &str) -> Option<String> {
+    let line = output.lines().find(|line| line.contains("AccentColor"))?;
+    let hex = line.split_whitespace().last()?.trim_start_matches("0x");
+    let value = u32::from_str_radix(hex, 16).ok()?;
+
+    let r = value & 0xff;
+    let g = (value >> 8) & 0xff;
+    let b = (value >> 16) & 0xff;
+    Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+}
+
+/// Map GNOME's named accent colors to their hex values
+#[cfg_attr(not(any(target_os = "linux", test)), allow(dead_code))]
+fn parse_gnome_accent(value: &str) -> Option<String> {
+    let color = match value.trim().trim_matches('\'') {
+        "blue" => "#3584e4",
+        "teal" => "#2190a4",
+        "green" => "#3a944a",
+        "yellow" => "#c88800",
+        "orange" => "#ed5b00",
+        "red" => "#e62d42",
+        "pink" => "#d56199",
+        "purple" => "#9141ac",
+        "slate" => "#6f8396",
+        _ => return None,
+    };
+    Some(color.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_macos_accent() {
+        assert_eq!(parse_macos_accent("").as_deref(), Some("#007aff"));
+        assert_eq!(parse_macos_accent("-1\n").as_deref(), Some("#8c8c8c"));
+        assert_eq!(parse_macos_accent("0\n").as_deref(), Some("#ff5257"));
+        assert_eq!(parse_macos_accent("42"), None);
+    }
+
+    #[test]
+    fn test_parse_windows_accent() {
+        let output = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\DWM\r\n    AccentColor    REG_DWORD    0xffd77800\r\n\r\n";
+        assert_eq!(parse_windows_accent(output).as_deref(), Some("#0078d7"));
+        assert_eq!(parse_windows_accent("ERROR: not found"), None);
+    }
+
+    #[test]
+    fn test_parse_gnome_accent() {
+        assert_eq!(parse_gnome_accent("'teal'\n").as_deref(), Some("#2190a4"));
+        assert_eq!(parse_gnome_accent("'chartreuse'"), None);
+    }
+}
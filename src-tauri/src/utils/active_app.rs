@@ -0,0 +1,93 @@
+/// Name of the frontmost application, used for the `{active_app}` prompt variable.
+/// Returns `None` when it can't be determined (unsupported platform, missing permissions).
+/// Blocks on a subprocess on macOS and Linux, so call it via `spawn_blocking` from async code.
+pub fn frontmost_app_name() -> Option<String> {
+    platform_frontmost_app()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+const FRONTMOST_APP_SCRIPT: &str =
+    "tell application \"System Events\" to get name of first application process whose frontmost is true";
+
+#[cfg(target_os = "macos")]
+fn platform_frontmost_app() -> Option<String> {
+    let output = std::process::Command::new("osascript")
+        .args(["-e", FRONTMOST_APP_SCRIPT])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        log::debug!(
+            "Failed to read frontmost app: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(target_os = "windows")]
+fn platform_frontmost_app() -> Option<String> {
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    let pid = unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.0 == 0 {
+            return None;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid as *mut u32));
+        pid
+    };
+
+    if pid == 0 {
+        return None;
+    }
+
+    process_name(pid)
+}
+
+#[cfg(target_os = "linux")]
+fn platform_frontmost_app() -> Option<String> {
+    // Works on X11 when xdotool is installed; Wayland has no portable equivalent
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowpid"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let pid = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()?;
+    process_name(pid)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn platform_frontmost_app() -> Option<String> {
+    None
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn process_name(pid: u32) -> Option<String> {
+    use sysinfo::{Pid, ProcessesToUpdate, System};
+
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+
+    let name = system.process(pid)?.name().to_string_lossy().into_owned();
+    // "Code.exe" -> "Code"
+    Some(
+        std::path::Path::new(&name)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or(name),
+    )
+}
@@ -0,0 +1,182 @@
+//! Screenshots attached to history entries (`attach_screenshot`). One is taken
+//! when a recording starts, saved next to the recording and listed on the entry
+//! as `attachment_file`, so the context a dictation was made in can be looked
+//! up later.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt as _;
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// History entry field holding the attachment path
+pub const ATTACHMENT_FIELD: &str = "attachment_file";
+
+/// Longest a screenshot tool may take before the capture is given up
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where the screenshot of the recording started at `started_at` (Unix
+/// seconds) goes, matching its `recording_<started_at>.wav`
+pub fn attachment_path(recordings_dir: &Path, started_at: u64) -> PathBuf {
+    recordings_dir.join(format!("attachment_{}.png", started_at))
+}
+
+/// The attachment of `entry`, if it has one that still exists. Paths outside
+/// `recordings_dir` (e.g. from an edited or imported history) are ignored.
+pub fn resolve_attachment(entry: &Value, recordings_dir: &Path) -> Option<PathBuf> {
+    let path = Path::new(entry.get(ATTACHMENT_FIELD)?.as_str()?);
+    let path = path.canonicalize().ok()?;
+    let recordings_dir = recordings_dir.canonicalize().ok()?;
+    (path.starts_with(&recordings_dir) && path.is_file()).then_some(path)
+}
+
+/// Delete the attachment of a removed history entry. Returns whether a file was removed.
+pub fn remove_attachment(entry: &Value) -> bool {
+    let Some(path) = entry.get(ATTACHMENT_FIELD).and_then(|v| v.as_str()) else {
+        return false;
+    };
+    match std::fs::remove_file(path) {
+        Ok(()) => true,
+        Err(e) => {
+            log::debug!("Failed to remove attachment {}: {}", path, e);
+            false
+        }
+    }
+}
+
+async fn run_capture(mut command: Command, output: &Path) -> Result<(), String> {
+    let program = command.as_std().get_program().to_string_lossy().to_string();
+    let status = tokio::time::timeout(CAPTURE_TIMEOUT, command.status())
+        .await
+        .map_err(|_| format!("{} timed out", program))?
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if !status.success() {
+        return Err(format!("{} failed with {}", program, status));
+    }
+    match std::fs::metadata(output) {
+        Ok(meta) if meta.len() > 0 => Ok(()),
+        _ => Err(format!("{} did not write a screenshot", program)),
+    }
+}
+
+/// Capture the whole screen as a PNG at `output`
+#[cfg(target_os = "macos")]
+pub async fn capture_screenshot(output: &Path) -> Result<(), String> {
+    let mut command = Command::new("screencapture");
+    // -x: no shutter sound
+    command.args(["-x", "-t", "png"]).arg(output);
+    run_capture(command, output).await
+}
+
+/// Capture the whole screen as a PNG at `output`
+#[cfg(target_os = "windows")]
+pub async fn capture_screenshot(output: &Path) -> Result<(), String> {
+    const SCRIPT: &str = "Add-Type -AssemblyName System.Windows.Forms,System.Drawing; \
+        $b = [System.Windows.Forms.SystemInformation]::VirtualScreen; \
+        $bmp = New-Object System.Drawing.Bitmap $b.Width, $b.Height; \
+        $g = [System.Drawing.Graphics]::FromImage($bmp); \
+        $g.CopyFromScreen($b.Left, $b.Top, 0, 0, $bmp.Size); \
+        $bmp.Save($env:VOICETYPR_SCREENSHOT, [System.Drawing.Imaging.ImageFormat]::Png)";
+
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+        // Passed through the environment so the path needs no quoting
+        .env("VOICETYPR_SCREENSHOT", output)
+        .creation_flags(CREATE_NO_WINDOW);
+    run_capture(command, output).await
+}
+
+/// Capture the whole screen as a PNG at `output` with the first screenshot
+/// tool that works: grim (Wayland), gnome-screenshot, then scrot (X11)
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub async fn capture_screenshot(output: &Path) -> Result<(), String> {
+    let candidates: [(&str, &[&str]); 3] = [
+        ("grim", &[]),
+        ("gnome-screenshot", &["-f"]),
+        ("scrot", &["-o"]),
+    ];
+    let mut errors = Vec::new();
+    for (program, args) in candidates {
+        let mut command = Command::new(program);
+        command.args(args).arg(output);
+        match run_capture(command, output).await {
+            Ok(()) => return Ok(()),
+            Err(e) => errors.push(e),
+        }
+    }
+    Err(format!("No screenshot tool worked: {}", errors.join("; ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry_with(path: &Path) -> Value {
+        json!({ "text": "hello", ATTACHMENT_FIELD: path.to_string_lossy() })
+    }
+
+    #[test]
+    fn test_attachment_path_matches_recording() {
+        let dir = Path::new("/data/recordings");
+        assert_eq!(
+            attachment_path(dir, 1718000000),
+            dir.join("attachment_1718000000.png")
+        );
+    }
+
+    #[test]
+    fn test_resolve_attachment() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let recordings_dir = data_dir.path().join("recordings");
+        std::fs::create_dir_all(&recordings_dir).unwrap();
+
+        let path = attachment_path(&recordings_dir, 42);
+        std::fs::write(&path, b"png").unwrap();
+        assert_eq!(
+            resolve_attachment(&entry_with(&path), &recordings_dir),
+            Some(path.canonicalize().unwrap())
+        );
+
+        // No attachment, a deleted one, or one outside the recordings folder
+        assert_eq!(
+            resolve_attachment(&json!({ "text": "hello" }), &recordings_dir),
+            None
+        );
+        let missing = attachment_path(&recordings_dir, 43);
+        assert_eq!(
+            resolve_attachment(&entry_with(&missing), &recordings_dir),
+            None
+        );
+        let outside = data_dir.path().join("secret.png");
+        std::fs::write(&outside, b"png").unwrap();
+        assert_eq!(
+            resolve_attachment(&entry_with(&outside), &recordings_dir),
+            None
+        );
+        let escaping = recordings_dir.join("..").join("secret.png");
+        assert_eq!(
+            resolve_attachment(&entry_with(&escaping), &recordings_dir),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remove_attachment() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = attachment_path(dir.path(), 42);
+        std::fs::write(&path, b"png").unwrap();
+
+        assert!(remove_attachment(&entry_with(&path)));
+        assert!(!path.exists());
+        // Already gone, or never had one
+        assert!(!remove_attachment(&entry_with(&path)));
+        assert!(!remove_attachment(&json!({ "text": "hello" })));
+    }
+}
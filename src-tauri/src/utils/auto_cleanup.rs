@@ -0,0 +1,237 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+/// Settings store key holding the [`AutoCleanupConfig`]
+pub const AUTO_CLEANUP_KEY: &str = "auto_cleanup";
+
+/// Wait after startup before the first run, so cleanup doesn't compete with model loading
+const FIRST_RUN_DELAY: Duration = Duration::from_secs(60);
+/// How often the task wakes up to see whether a run is due (picks up setting changes)
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const RUN_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What the daily cleanup prunes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoCleanupTargets {
+    /// Entries in the transcriptions store
+    pub history: bool,
+    /// Audio files in the recordings directory
+    pub recordings: bool,
+}
+
+impl Default for AutoCleanupTargets {
+    fn default() -> Self {
+        Self {
+            history: true,
+            recordings: true,
+        }
+    }
+}
+
+/// The `auto_cleanup` setting
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoCleanupConfig {
+    pub enabled: bool,
+    pub retention_days: u32,
+    pub applies_to: AutoCleanupTargets,
+}
+
+impl Default for AutoCleanupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: 30,
+            applies_to: AutoCleanupTargets::default(),
+        }
+    }
+}
+
+impl AutoCleanupConfig {
+    /// A retention of 0 days would wipe everything, so it's raised to one day
+    pub fn normalized(self) -> Self {
+        Self {
+            retention_days: self.retention_days.max(1),
+            ..self
+        }
+    }
+
+    fn max_age(&self) -> Duration {
+        Duration::from_secs(self.retention_days as u64 * 24 * 60 * 60)
+    }
+}
+
+/// Files among `files` (path, last modified) that are older than `cutoff`
+pub fn expired_recordings(files: Vec<(PathBuf, SystemTime)>, cutoff: SystemTime) -> Vec<PathBuf> {
+    files
+        .into_iter()
+        .filter(|(_, modified)| *modified < cutoff)
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// Delete files in `recordings_dir` last modified more than `max_age` ago.
+/// Returns how many were removed.
+pub fn cleanup_old_recordings(recordings_dir: &Path, max_age: Duration) -> Result<usize, String> {
+    let entries = match std::fs::read_dir(recordings_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(format!("Cannot read {}: {}", recordings_dir.display(), e)),
+    };
+
+    let files = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata
+                .is_file()
+                .then_some((entry.path(), metadata.modified().ok()?))
+        })
+        .collect();
+    let cutoff = SystemTime::now()
+        .checked_sub(max_age)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let mut removed = 0;
+    for path in expired_recordings(files, cutoff) {
+        match std::fs::remove_file(&path) {
+            Ok(_) => removed += 1,
+            Err(e) => log::warn!("Failed to remove old recording {:?}: {}", path, e),
+        }
+    }
+    Ok(removed)
+}
+
+fn load_config(app: &AppHandle) -> AutoCleanupConfig {
+    app.store("settings")
+        .ok()
+        .and_then(|store| store.get(AUTO_CLEANUP_KEY))
+        .and_then(|v| serde_json::from_value::<AutoCleanupConfig>(v).ok())
+        .unwrap_or_default()
+        .normalized()
+}
+
+/// Prune history and recordings once according to `config`
+async fn run_cleanup(app: &AppHandle, config: AutoCleanupConfig) {
+    if config.applies_to.history {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(config.retention_days as i64);
+        match crate::commands::audio::prune_history_before(app, cutoff).await {
+            Ok(removed) => log::info!("Auto cleanup removed {} history entries", removed),
+            Err(e) => log::warn!("Auto cleanup of history failed: {}", e),
+        }
+    }
+
+    if config.applies_to.recordings {
+        // The file being recorded right now lives in the same directory
+        if app.state::<crate::AppState>().get_current_state() != crate::RecordingState::Idle {
+            log::info!("Recording in progress, auto cleanup skips recordings this time");
+            return;
+        }
+        let Ok(data_dir) = app.path().app_data_dir() else {
+            return;
+        };
+        match cleanup_old_recordings(&data_dir.join("recordings"), config.max_age()) {
+            Ok(removed) => log::info!("Auto cleanup removed {} recordings", removed),
+            Err(e) => log::warn!("Auto cleanup of recordings failed: {}", e),
+        }
+    }
+}
+
+/// Spawn the background task that applies the `auto_cleanup` setting once a day
+pub fn spawn_auto_cleanup(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_RUN_DELAY).await;
+
+        let mut last_run: Option<std::time::Instant> = None;
+        loop {
+            let config = load_config(&app);
+            let due = last_run.is_none_or(|at| at.elapsed() >= RUN_INTERVAL);
+            if config.enabled && due {
+                log::info!(
+                    "Running auto cleanup (retention {} days, {:?})",
+                    config.retention_days,
+                    config.applies_to
+                );
+                run_cleanup(&app, config).await;
+                last_run = Some(std::time::Instant::now());
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_recordings_cutoff() {
+        let now = SystemTime::now();
+        let days_ago = |days: u64| now - Duration::from_secs(days * 24 * 60 * 60);
+        let files = vec![
+            (PathBuf::from("recording_today.wav"), now),
+            (PathBuf::from("recording_29d.wav"), days_ago(29)),
+            (PathBuf::from("recording_31d.wav"), days_ago(31)),
+            (PathBuf::from("normalized_90d.wav"), days_ago(90)),
+        ];
+
+        let expired = expired_recordings(files, days_ago(30));
+        assert_eq!(
+            expired,
+            vec![
+                PathBuf::from("recording_31d.wav"),
+                PathBuf::from("normalized_90d.wav"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cleanup_old_recordings_removes_only_old_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("recording_old.wav");
+        let fresh = dir.path().join("recording_new.wav");
+        std::fs::write(&old, b"old").unwrap();
+        std::fs::write(&fresh, b"new").unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+
+        let forty_days = Duration::from_secs(40 * 24 * 60 * 60);
+        std::fs::File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(SystemTime::now() - forty_days)
+            .unwrap();
+
+        let removed = cleanup_old_recordings(dir.path(), Duration::from_secs(30 * 24 * 60 * 60));
+        assert_eq!(removed.unwrap(), 1);
+        assert!(!old.exists());
+        assert!(fresh.exists());
+        assert!(dir.path().join("nested").exists());
+
+        // A recordings directory that was never created is not an error
+        assert_eq!(
+            cleanup_old_recordings(&dir.path().join("missing"), forty_days).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_auto_cleanup_config_defaults() {
+        let config: AutoCleanupConfig =
+            serde_json::from_value(serde_json::json!({ "enabled": true })).unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.retention_days, 30);
+        assert!(config.applies_to.history && config.applies_to.recordings);
+
+        let zero = AutoCleanupConfig {
+            retention_days: 0,
+            ..AutoCleanupConfig::default()
+        };
+        assert_eq!(zero.normalized().retention_days, 1);
+    }
+}
@@ -0,0 +1,206 @@
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::secure_store::{decrypt_value, encrypt_value};
+use crate::utils::history_store;
+
+/// Settings store key that turns on encryption of history entries
+pub const ENCRYPT_HISTORY_KEY: &str = "encrypt_history";
+
+/// Entry field holding the encrypted copy of the content fields
+pub const ENCRYPTED_FIELD: &str = "encrypted";
+
+/// Entry fields carrying what was said. Model, timestamp, language, metrics and
+/// audio paths stay readable so counts, cleanup and audio deletion work as before.
+const CONTENT_FIELDS: &[&str] = &["text", "raw_text", "speakers"];
+
+/// Whether `entry` was written with history encryption on
+pub fn is_encrypted_entry(entry: &Value) -> bool {
+    entry.get(ENCRYPTED_FIELD).is_some_and(|v| v.is_string())
+}
+
+/// Move the content fields of `entry` into a single encrypted field.
+/// Entries that are already encrypted are returned unchanged.
+pub fn encrypt_history_entry(mut entry: Value) -> Result<Value, String> {
+    if is_encrypted_entry(&entry) {
+        return Ok(entry);
+    }
+    let Some(fields) = entry.as_object_mut() else {
+        return Ok(entry);
+    };
+
+    let content: Map<String, Value> = CONTENT_FIELDS
+        .iter()
+        .filter_map(|field| fields.remove(*field).map(|v| (field.to_string(), v)))
+        .collect();
+    let plaintext = serde_json::to_string(&content)
+        .map_err(|e| format!("Failed to serialize history entry: {}", e))?;
+    fields.insert(
+        ENCRYPTED_FIELD.to_string(),
+        Value::String(encrypt_value(&plaintext)?),
+    );
+    Ok(entry)
+}
+
+/// Restore the content fields of an entry written by [`encrypt_history_entry`].
+/// Plaintext entries saved before encryption was enabled pass through unchanged.
+/// An entry that can't be decrypted (e.g. a store copied from another machine)
+/// comes back with empty text and `decryption_failed` set.
+pub fn decrypt_history_entry(mut entry: Value) -> Value {
+    let Some(fields) = entry.as_object_mut() else {
+        return entry;
+    };
+    let Some(Value::String(ciphertext)) = fields.remove(ENCRYPTED_FIELD) else {
+        return entry;
+    };
+
+    let content = decrypt_value(&ciphertext).and_then(|plaintext| {
+        serde_json::from_str::<Map<String, Value>>(&plaintext)
+            .map_err(|e| format!("Invalid decrypted history entry: {}", e))
+    });
+    match content {
+        Ok(content) => fields.extend(content),
+        Err(e) => {
+            log::warn!("Failed to decrypt history entry: {}", e);
+            fields.insert("text".to_string(), Value::String(String::new()));
+            fields.insert("decryption_failed".to_string(), Value::Bool(true));
+        }
+    }
+    entry
+}
+
+/// Whether the `encrypt_history` setting is on
+pub fn history_encryption_enabled<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.store("settings")
+        .ok()
+        .and_then(|store| store.get(ENCRYPT_HISTORY_KEY))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// The form of `entry` to pass to `store.set`: encrypted when `encrypt_history` is on
+pub fn entry_for_store<R: Runtime>(app: &AppHandle<R>, entry: Value) -> Result<Value, String> {
+    if history_encryption_enabled(app) {
+        encrypt_history_entry(entry)
+    } else {
+        Ok(entry)
+    }
+}
+
+/// Every history entry keyed by timestamp, decrypted, newest first
+pub fn read_history_entries<R: Runtime>(
+    app: &AppHandle<R>,
+) -> Result<Vec<(String, Value)>, String> {
+    Ok(history_store::read_raw_entries(app)?
+        .into_iter()
+        .map(|(key, value)| (key, decrypt_history_entry(value)))
+        .collect())
+}
+
+/// The newest `count` decrypted history entries accepted by `keep`, newest first.
+/// Shards past them are neither opened nor decrypted.
+pub fn read_newest_history_entries<R: Runtime, F>(
+    app: &AppHandle<R>,
+    count: usize,
+    mut keep: F,
+) -> Result<Vec<(String, Value)>, String>
+where
+    F: FnMut(&Value) -> bool,
+{
+    let mut entries = Vec::new();
+    if count == 0 {
+        return Ok(entries);
+    }
+    history_store::visit_raw_entries(app, |key, value| {
+        let value = decrypt_history_entry(value);
+        if keep(&value) {
+            entries.push((key, value));
+        }
+        entries.len() < count
+    })?;
+    Ok(entries)
+}
+
+/// The history entry saved at `timestamp`, decrypted
+pub fn read_history_entry<R: Runtime>(app: &AppHandle<R>, timestamp: &str) -> Option<Value> {
+    history_store::get_raw_entry(app, timestamp).map(decrypt_history_entry)
+}
+
+/// Rewrite every history entry encrypted (`encrypt` true) or back to plaintext.
+/// Entries already in the target form are left alone. Returns how many changed.
+pub fn migrate_history_encryption<R: Runtime>(
+    app: &AppHandle<R>,
+    encrypt: bool,
+) -> Result<usize, String> {
+    history_store::rewrite_raw_entries(app, |value| {
+        if is_encrypted_entry(&value) == encrypt {
+            Ok(None)
+        } else if encrypt {
+            encrypt_history_entry(value).map(Some)
+        } else {
+            Ok(Some(decrypt_history_entry(value)))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secure_store::initialize_encryption_key;
+    use serde_json::json;
+
+    #[test]
+    fn test_encrypted_entry_round_trip() {
+        initialize_encryption_key().unwrap();
+
+        let entry = json!({
+            "text": "Meet Dana at the clinic on Friday",
+            "raw_text": "meet dana at the clinic on friday",
+            "model": "base.en",
+            "timestamp": "2026-01-02T03:04:05+00:00",
+            "language": "en"
+        });
+
+        let encrypted = encrypt_history_entry(entry.clone()).unwrap();
+        assert!(is_encrypted_entry(&encrypted));
+        assert!(encrypted.get("text").is_none());
+        assert!(encrypted.get("raw_text").is_none());
+        assert_eq!(encrypted["model"], "base.en");
+        assert_eq!(encrypted["language"], "en");
+
+        // What the store writes to disk and reads back
+        let on_disk = serde_json::to_string(&encrypted).unwrap();
+        assert!(!on_disk.contains("clinic"));
+        let reloaded: Value = serde_json::from_str(&on_disk).unwrap();
+
+        assert_eq!(decrypt_history_entry(reloaded), entry);
+
+        // Encrypting twice doesn't double-wrap
+        assert_eq!(encrypt_history_entry(encrypted.clone()).unwrap(), encrypted);
+    }
+
+    #[test]
+    fn test_legacy_plaintext_entry_reads_unchanged() {
+        initialize_encryption_key().unwrap();
+
+        let legacy = json!({
+            "text": "Saved before encryption was enabled",
+            "model": "tiny",
+            "timestamp": "2024-05-06T07:08:09+00:00"
+        });
+        assert!(!is_encrypted_entry(&legacy));
+        assert_eq!(decrypt_history_entry(legacy.clone()), legacy);
+    }
+
+    #[test]
+    fn test_undecryptable_entry_is_flagged() {
+        initialize_encryption_key().unwrap();
+
+        let entry = json!({ "encrypted": "dGVzdA==", "model": "tiny" });
+        let decrypted = decrypt_history_entry(entry);
+        assert_eq!(decrypted["text"], "");
+        assert_eq!(decrypted["decryption_failed"], true);
+        assert_eq!(decrypted["model"], "tiny");
+    }
+}
@@ -0,0 +1,370 @@
+//! Transcription history sharded into one store per month ("transcriptions-2024-06")
+//! so a save only rewrites the current month. A small index store lists the shards
+//! and their entry counts. Entries are keyed by their RFC 3339 timestamp, as in the
+//! single `transcriptions` store used before, which is migrated on startup.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+/// The single history store used before sharding
+pub const LEGACY_HISTORY_STORE: &str = "transcriptions";
+
+/// Shard name -> entry count
+pub const HISTORY_INDEX_STORE: &str = "transcriptions-index";
+const SHARDS_KEY: &str = "shards";
+
+/// Shard for keys that aren't timestamps, which only old or imported stores have
+const UNDATED_SHARD: &str = "undated";
+
+/// The shard ("YYYY-MM", UTC) an entry saved at `timestamp` belongs to
+pub fn shard_month(timestamp: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|date| date.with_timezone(&chrono::Utc).format("%Y-%m").to_string())
+        .unwrap_or_else(|_| UNDATED_SHARD.to_string())
+}
+
+pub fn shard_store_name(month: &str) -> String {
+    format!("{}-{}", LEGACY_HISTORY_STORE, month)
+}
+
+fn is_undated(key: &str) -> bool {
+    chrono::DateTime::parse_from_rfc3339(key).is_err()
+}
+
+/// Sort entries by timestamp key, newest first; keys that aren't timestamps go last
+pub fn sort_newest_first(entries: &mut [(String, Value)]) {
+    entries.sort_by(|a, b| {
+        is_undated(&a.0)
+            .cmp(&is_undated(&b.0))
+            .then_with(|| b.0.cmp(&a.0))
+    });
+}
+
+/// Merge shards listed newest month first into one list, newest entry first
+pub fn merge_shards(shards: Vec<Vec<(String, Value)>>) -> Vec<(String, Value)> {
+    shards
+        .into_iter()
+        .flat_map(|mut shard| {
+            sort_newest_first(&mut shard);
+            shard
+        })
+        .collect()
+}
+
+/// Group entries by the shard they belong to
+pub fn partition_by_month(entries: Vec<(String, Value)>) -> BTreeMap<String, Vec<(String, Value)>> {
+    let mut shards: BTreeMap<String, Vec<(String, Value)>> = BTreeMap::new();
+    for (key, value) in entries {
+        shards
+            .entry(shard_month(&key))
+            .or_default()
+            .push((key, value));
+    }
+    shards
+}
+
+/// Group timestamps by the shard holding them
+pub fn group_by_month(timestamps: &[String]) -> BTreeMap<String, Vec<&String>> {
+    let mut by_month: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    for timestamp in timestamps {
+        by_month
+            .entry(shard_month(timestamp))
+            .or_default()
+            .push(timestamp);
+    }
+    by_month
+}
+
+/// Which shards exist and how many entries each holds
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryIndex {
+    counts: BTreeMap<String, usize>,
+}
+
+impl HistoryIndex {
+    pub fn from_value(value: Option<Value>) -> Self {
+        Self {
+            counts: value
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::json!(self.counts)
+    }
+
+    /// Record that `month`'s shard now holds `count` entries; empty shards are dropped
+    pub fn set_count(&mut self, month: &str, count: usize) {
+        if count == 0 {
+            self.counts.remove(month);
+        } else {
+            self.counts.insert(month.to_string(), count);
+        }
+    }
+
+    /// Shards newest first, so reading them in order yields newest entries first.
+    /// Undated entries have no known age and come last.
+    pub fn months_newest_first(&self) -> Vec<String> {
+        let (undated, mut months): (Vec<String>, Vec<String>) = self
+            .counts
+            .keys()
+            .rev()
+            .cloned()
+            .partition(|month| month == UNDATED_SHARD);
+        months.extend(undated);
+        months
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+fn load_index<R: Runtime>(app: &AppHandle<R>) -> Result<HistoryIndex, String> {
+    let store = app.store(HISTORY_INDEX_STORE).map_err(|e| e.to_string())?;
+    Ok(HistoryIndex::from_value(store.get(SHARDS_KEY)))
+}
+
+fn save_index<R: Runtime>(app: &AppHandle<R>, index: &HistoryIndex) -> Result<(), String> {
+    let store = app.store(HISTORY_INDEX_STORE).map_err(|e| e.to_string())?;
+    store.set(SHARDS_KEY, index.to_value());
+    store
+        .save()
+        .map_err(|e| format!("Failed to save history index: {}", e))
+}
+
+/// Raw (possibly encrypted) entries of one shard
+fn shard_entries<R: Runtime>(
+    app: &AppHandle<R>,
+    month: &str,
+) -> Result<Vec<(String, Value)>, String> {
+    let store = app
+        .store(shard_store_name(month))
+        .map_err(|e| e.to_string())?;
+    Ok(store
+        .keys()
+        .into_iter()
+        .filter_map(|key| {
+            let value = store.get(&key)?;
+            Some((key.to_string(), value))
+        })
+        .collect())
+}
+
+/// Every raw (possibly encrypted) history entry, newest first
+pub fn read_raw_entries<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<(String, Value)>, String> {
+    let shards = load_index(app)?
+        .months_newest_first()
+        .iter()
+        .map(|month| shard_entries(app, month))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(merge_shards(shards))
+}
+
+/// Hand raw entries to `visit` newest first, one shard at a time, until it
+/// returns false. Older shards aren't opened once it does.
+pub fn visit_raw_entries<R: Runtime, F>(app: &AppHandle<R>, mut visit: F) -> Result<(), String>
+where
+    F: FnMut(String, Value) -> bool,
+{
+    for month in load_index(app)?.months_newest_first() {
+        let mut shard = shard_entries(app, &month)?;
+        sort_newest_first(&mut shard);
+        for (key, value) in shard {
+            if !visit(key, value) {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Raw entries saved at or after `since`, skipping older shards without opening them
+pub fn read_raw_entries_since<R: Runtime>(
+    app: &AppHandle<R>,
+    since: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<(String, Value)>, String> {
+    let first_month = since.format("%Y-%m").to_string();
+    let mut entries = Vec::new();
+    for month in load_index(app)?.months_newest_first() {
+        if month.as_str() < first_month.as_str() || month == UNDATED_SHARD {
+            continue;
+        }
+        entries.extend(shard_entries(app, &month)?.into_iter().filter(|(key, _)| {
+            chrono::DateTime::parse_from_rfc3339(key)
+                .is_ok_and(|date| date.with_timezone(&chrono::Utc) >= since)
+        }));
+    }
+    sort_newest_first(&mut entries);
+    Ok(entries)
+}
+
+/// The newest raw entry, read from the newest shard only
+pub fn latest_raw_entry<R: Runtime>(app: &AppHandle<R>) -> Option<(String, Value)> {
+    let month = load_index(app)
+        .ok()?
+        .months_newest_first()
+        .into_iter()
+        .next()?;
+    let mut shard = shard_entries(app, &month).ok()?;
+    sort_newest_first(&mut shard);
+    shard.into_iter().next()
+}
+
+/// The raw entry saved at `timestamp`
+pub fn get_raw_entry<R: Runtime>(app: &AppHandle<R>, timestamp: &str) -> Option<Value> {
+    let store = app.store(shard_store_name(&shard_month(timestamp))).ok()?;
+    store.get(timestamp)
+}
+
+pub fn has_entry<R: Runtime>(app: &AppHandle<R>, timestamp: &str) -> bool {
+    get_raw_entry(app, timestamp).is_some()
+}
+
+/// Write `value` (already encrypted if need be) at `timestamp` into its shard
+pub fn set_raw_entry<R: Runtime>(
+    app: &AppHandle<R>,
+    timestamp: &str,
+    value: Value,
+) -> Result<(), String> {
+    let month = shard_month(timestamp);
+    let store = app
+        .store(shard_store_name(&month))
+        .map_err(|e| e.to_string())?;
+    store.set(timestamp, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save transcription: {}", e))?;
+
+    let mut index = load_index(app)?;
+    index.set_count(&month, store.keys().len());
+    save_index(app, &index)
+}
+
+/// Delete the entries saved at `timestamps`, touching only the shards they live
+/// in. Returns the removed raw entries.
+pub fn delete_raw_entries<R: Runtime>(
+    app: &AppHandle<R>,
+    timestamps: &[String],
+) -> Result<Vec<Value>, String> {
+    let mut index = load_index(app)?;
+    let mut removed = Vec::new();
+    for (month, keys) in group_by_month(timestamps) {
+        let store = app
+            .store(shard_store_name(&month))
+            .map_err(|e| e.to_string())?;
+        for key in keys {
+            if let Some(value) = store.get(key) {
+                removed.push(value);
+            }
+            store.delete(key);
+        }
+        store
+            .save()
+            .map_err(|e| format!("Failed to save history after deletion: {}", e))?;
+        index.set_count(&month, store.keys().len());
+    }
+    save_index(app, &index)?;
+    Ok(removed)
+}
+
+/// Empty every shard. Returns the removed raw entries.
+pub fn clear_raw_entries<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<Value>, String> {
+    let mut removed = Vec::new();
+    for month in load_index(app)?.months_newest_first() {
+        let store = app
+            .store(shard_store_name(&month))
+            .map_err(|e| e.to_string())?;
+        removed.extend(store.keys().into_iter().filter_map(|key| store.get(&key)));
+        store.clear();
+        store
+            .save()
+            .map_err(|e| format!("Failed to save cleared history: {}", e))?;
+    }
+    save_index(app, &HistoryIndex::default())?;
+    Ok(removed)
+}
+
+/// Number of history entries, from the index alone
+pub fn entry_count<R: Runtime>(app: &AppHandle<R>) -> Result<usize, String> {
+    Ok(load_index(app)?.total())
+}
+
+/// Rewrite every raw entry with `transform`, which returns the new value or
+/// `None` to leave it. Returns how many changed.
+pub fn rewrite_raw_entries<R: Runtime, F>(
+    app: &AppHandle<R>,
+    mut transform: F,
+) -> Result<usize, String>
+where
+    F: FnMut(Value) -> Result<Option<Value>, String>,
+{
+    let mut rewritten = 0;
+    for month in load_index(app)?.months_newest_first() {
+        let store = app
+            .store(shard_store_name(&month))
+            .map_err(|e| e.to_string())?;
+        let mut changed = false;
+        for key in store.keys() {
+            let Some(value) = store.get(&key) else {
+                continue;
+            };
+            if let Some(value) = transform(value)? {
+                store.set(&key, value);
+                changed = true;
+                rewritten += 1;
+            }
+        }
+        if changed {
+            store
+                .save()
+                .map_err(|e| format!("Failed to save history: {}", e))?;
+        }
+    }
+    Ok(rewritten)
+}
+
+/// Move the entries of the pre-sharding `transcriptions` store into monthly
+/// shards. Shards are saved before the old store is emptied, so an interrupted
+/// migration simply runs again on the next start. Returns how many moved.
+pub fn migrate_legacy_store<R: Runtime>(app: &AppHandle<R>) -> Result<usize, String> {
+    let legacy = app.store(LEGACY_HISTORY_STORE).map_err(|e| e.to_string())?;
+    let entries: Vec<(String, Value)> = legacy
+        .keys()
+        .into_iter()
+        .filter_map(|key| {
+            let value = legacy.get(&key)?;
+            Some((key.to_string(), value))
+        })
+        .collect();
+    if entries.is_empty() {
+        return Ok(0);
+    }
+
+    let moved = entries.len();
+    let mut index = load_index(app)?;
+    for (month, shard) in partition_by_month(entries) {
+        let store = app
+            .store(shard_store_name(&month))
+            .map_err(|e| e.to_string())?;
+        for (key, value) in shard {
+            store.set(key, value);
+        }
+        store
+            .save()
+            .map_err(|e| format!("Failed to save history shard {}: {}", month, e))?;
+        index.set_count(&month, store.keys().len());
+    }
+    save_index(app, &index)?;
+
+    legacy.clear();
+    legacy
+        .save()
+        .map_err(|e| format!("Failed to empty the old history store: {}", e))?;
+    log::info!("Moved {} history entries into monthly stores", moved);
+    Ok(moved)
+}
@@ -1,7 +1,22 @@
 // Utility modules
+pub mod accent_color;
+pub mod active_app;
+pub mod attachments;
+pub mod auto_cleanup;
 pub mod diagnostics;
 pub mod display_watcher;
+pub mod history_crypto;
+pub mod history_store;
 pub mod logger;
+pub mod model_preload;
+pub mod model_relocation;
 pub mod network_diagnostics;
 pub mod onboarding_logger;
+pub mod pill_theme;
+pub mod power;
+pub mod redaction;
+pub mod remote_audio;
 pub mod system_monitor;
+pub mod target_window;
+pub mod text_replace;
+pub mod toast_layout;
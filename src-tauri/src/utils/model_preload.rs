@@ -0,0 +1,129 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde_json::json;
+use tauri::async_runtime::{Mutex as AsyncMutex, RwLock as AsyncRwLock};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::parakeet::ParakeetManager;
+use crate::whisper::cache::TranscriberCache;
+use crate::whisper::manager::WhisperManager;
+
+/// Quiet period after a model change before loading starts, so clicking through
+/// several models only loads the one the user settled on
+const PRELOAD_DEBOUNCE: Duration = Duration::from_millis(750);
+
+static PRELOAD_DEBOUNCER: PreloadDebouncer = PreloadDebouncer::new();
+
+/// Hands out a ticket per preload request; only the newest ticket may load
+#[derive(Debug, Default)]
+pub struct PreloadDebouncer {
+    latest: AtomicU64,
+}
+
+impl PreloadDebouncer {
+    pub const fn new() -> Self {
+        Self {
+            latest: AtomicU64::new(0),
+        }
+    }
+
+    /// Register a request, superseding any earlier one still waiting
+    pub fn request(&self) -> u64 {
+        self.latest.fetch_add(1, Ordering::SeqCst).wrapping_add(1)
+    }
+
+    /// Whether `ticket` is still the newest request once its wait is over
+    pub fn is_latest(&self, ticket: u64) -> bool {
+        self.latest.load(Ordering::SeqCst) == ticket
+    }
+}
+
+/// Load the newly selected model in the background so the next recording doesn't
+/// wait for it. Emits `model-preload-complete` once the model is warm (or failed).
+pub fn schedule_model_preload(app: &AppHandle, model_name: String, engine: String) {
+    let ticket = PRELOAD_DEBOUNCER.request();
+    let app = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(PRELOAD_DEBOUNCE).await;
+        if !PRELOAD_DEBOUNCER.is_latest(ticket) {
+            log::debug!(
+                "Preload of '{}' superseded by a newer model change",
+                model_name
+            );
+            return;
+        }
+
+        let result = preload(&app, &model_name, &engine).await;
+        match &result {
+            Ok(_) => log::info!("Preloaded {} model '{}'", engine, model_name),
+            Err(e) => log::warn!("Failed to preload {} model '{}': {}", engine, model_name, e),
+        }
+
+        if let Err(e) = app.emit(
+            "model-preload-complete",
+            json!({
+                "model": model_name,
+                "engine": engine,
+                "success": result.is_ok(),
+                "error": result.err(),
+            }),
+        ) {
+            log::warn!("Failed to emit model-preload-complete event: {}", e);
+        }
+    });
+}
+
+async fn preload(app: &AppHandle, model_name: &str, engine: &str) -> Result<(), String> {
+    if engine == "parakeet" {
+        return app
+            .state::<ParakeetManager>()
+            .load_model(app, model_name)
+            .await
+            .map_err(|e| e.to_string());
+    }
+
+    let whisper_state = app.state::<AsyncRwLock<WhisperManager>>();
+    let model_path = whisper_state
+        .read()
+        .await
+        .get_model_path(model_name)
+        .ok_or_else(|| format!("Model '{}' not found", model_name))?;
+
+    let already_cached = app
+        .state::<AsyncMutex<TranscriberCache>>()
+        .lock()
+        .await
+        .contains(&model_path);
+    if already_cached {
+        log::debug!("Model '{}' already cached, skipping preload", model_name);
+        return Ok(());
+    }
+
+    crate::commands::model::preload_model(app.clone(), model_name.to_string(), whisper_state).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rapid_requests_only_load_the_last() {
+        let debouncer = PreloadDebouncer::new();
+
+        let first = debouncer.request();
+        let second = debouncer.request();
+        let third = debouncer.request();
+
+        // Earlier requests wake up to find they were superseded
+        assert!(!debouncer.is_latest(first));
+        assert!(!debouncer.is_latest(second));
+        assert!(debouncer.is_latest(third));
+
+        // A change after the last one settled starts a fresh wait
+        let fourth = debouncer.request();
+        assert!(!debouncer.is_latest(third));
+        assert!(debouncer.is_latest(fourth));
+    }
+}
@@ -0,0 +1,231 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Settings store key holding a user-chosen models directory
+pub const MODELS_DIRECTORY_KEY: &str = "models_directory";
+
+const WRITE_PROBE_FILE: &str = ".voicetypr-write-test";
+
+/// Check that `target` can become the models directory in place of `current`,
+/// creating it if needed. Nothing is moved yet.
+pub fn validate_models_directory(current: &Path, target: &Path) -> Result<(), String> {
+    if !target.is_absolute() {
+        return Err("Models directory must be an absolute path".to_string());
+    }
+    if target.exists() && !target.is_dir() {
+        return Err(format!("{} is not a directory", target.display()));
+    }
+
+    fs::create_dir_all(target).map_err(|e| format!("Cannot create {}: {}", target.display(), e))?;
+
+    let current = current
+        .canonicalize()
+        .unwrap_or_else(|_| current.to_path_buf());
+    let target = target
+        .canonicalize()
+        .map_err(|e| format!("Cannot resolve {}: {}", target.display(), e))?;
+    if target == current {
+        return Err("Models are already stored in this directory".to_string());
+    }
+    if target.starts_with(&current) {
+        return Err("The new location can't be inside the current models directory".to_string());
+    }
+
+    // Creating the directory can succeed on read-only mounts that already have it
+    let probe = target.join(WRITE_PROBE_FILE);
+    fs::write(&probe, b"ok").map_err(|e| format!("{} is not writable: {}", target.display(), e))?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(())
+}
+
+/// Move everything in `from` into `to`. If any entry fails to move, the entries
+/// already moved are put back so the models stay in one place.
+pub fn move_models_directory(from: &Path, to: &Path) -> Result<usize, String> {
+    move_entries(from, to, move_entry)
+}
+
+fn move_entries(
+    from: &Path,
+    to: &Path,
+    mut move_one: impl FnMut(&Path, &Path) -> io::Result<()>,
+) -> Result<usize, String> {
+    let entries = match fs::read_dir(from) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name())
+            .collect::<Vec<_>>(),
+        // Nothing downloaded yet
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(format!("Cannot read {}: {}", from.display(), e)),
+    };
+
+    // Refuse up front rather than overwriting files halfway through
+    if let Some(existing) = entries.iter().find(|name| to.join(name).exists()) {
+        return Err(format!(
+            "{} already exists in {}",
+            existing.to_string_lossy(),
+            to.display()
+        ));
+    }
+
+    let mut moved: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for name in &entries {
+        let source = from.join(name);
+        let destination = to.join(name);
+
+        if let Err(e) = move_one(&source, &destination) {
+            log::error!(
+                "Failed to move {:?} to {:?}: {}; rolling back",
+                source,
+                destination,
+                e
+            );
+            for (source, destination) in moved.iter().rev() {
+                if let Err(e) = move_entry(destination, source) {
+                    log::error!("Rollback of {:?} failed: {}", destination, e);
+                }
+            }
+            return Err(format!("Failed to move {}: {}", name.to_string_lossy(), e));
+        }
+        moved.push((source, destination));
+    }
+
+    Ok(moved.len())
+}
+
+/// Rename, falling back to copy + delete when `to` is on another drive
+fn move_entry(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    let copied = if from.is_dir() {
+        copy_dir(from, to)
+    } else {
+        fs::copy(from, to).map(|_| ())
+    };
+    if let Err(e) = copied {
+        // Don't leave a partial copy behind
+        let _ = if to.is_dir() {
+            fs::remove_dir_all(to)
+        } else {
+            fs::remove_file(to)
+        };
+        return Err(e);
+    }
+
+    if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let destination = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &destination)?;
+        } else {
+            fs::copy(entry.path(), destination)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn populate(dir: &Path) {
+        fs::write(dir.join("base.en.bin"), b"base").unwrap();
+        fs::write(dir.join("small.bin"), b"small").unwrap();
+        fs::create_dir_all(dir.join("parakeet/parakeet-tdt-0.6b-v3")).unwrap();
+        fs::write(dir.join("parakeet/parakeet-tdt-0.6b-v3/model.bin"), b"p").unwrap();
+    }
+
+    #[test]
+    fn test_validate_models_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        let current = temp.path().join("models");
+        fs::create_dir_all(&current).unwrap();
+
+        assert!(validate_models_directory(&current, Path::new("relative/models")).is_err());
+        assert!(validate_models_directory(&current, &current).is_err());
+        assert!(validate_models_directory(&current, &current.join("nested")).is_err());
+
+        let file = temp.path().join("file");
+        fs::write(&file, b"").unwrap();
+        assert!(validate_models_directory(&current, &file).is_err());
+
+        // A missing target is created, and the write probe is cleaned up
+        let target = temp.path().join("external/models");
+        assert!(validate_models_directory(&current, &target).is_ok());
+        assert!(target.is_dir());
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_move_models_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        let (from, to) = (temp.path().join("old"), temp.path().join("new"));
+        fs::create_dir_all(&from).unwrap();
+        fs::create_dir_all(&to).unwrap();
+        populate(&from);
+
+        assert_eq!(move_models_directory(&from, &to).unwrap(), 3);
+        assert_eq!(fs::read(to.join("small.bin")).unwrap(), b"small");
+        assert!(to.join("parakeet/parakeet-tdt-0.6b-v3/model.bin").exists());
+        assert_eq!(fs::read_dir(&from).unwrap().count(), 0);
+
+        // A name clash is refused before anything moves
+        populate(&from);
+        assert!(move_models_directory(&from, &to).is_err());
+        assert!(from.join("small.bin").exists());
+    }
+
+    #[test]
+    fn test_failed_move_rolls_back() {
+        let temp = tempfile::tempdir().unwrap();
+        let (from, to) = (temp.path().join("old"), temp.path().join("new"));
+        fs::create_dir_all(&from).unwrap();
+        fs::create_dir_all(&to).unwrap();
+        populate(&from);
+
+        let mut calls = 0;
+        let result = move_entries(&from, &to, |source, destination| {
+            calls += 1;
+            if calls == 3 {
+                return Err(io::Error::other("disk full"));
+            }
+            move_entry(source, destination)
+        });
+
+        assert!(result.unwrap_err().contains("disk full"));
+        assert_eq!(fs::read_dir(&to).unwrap().count(), 0);
+        assert_eq!(fs::read(from.join("base.en.bin")).unwrap(), b"base");
+        assert_eq!(fs::read(from.join("small.bin")).unwrap(), b"small");
+        assert!(from
+            .join("parakeet/parakeet-tdt-0.6b-v3/model.bin")
+            .exists());
+    }
+
+    #[test]
+    fn test_move_entry_copies_directories() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("parakeet");
+        fs::create_dir_all(source.join("model")).unwrap();
+        fs::write(source.join("model/vocab.json"), b"{}").unwrap();
+
+        let destination = temp.path().join("copy");
+        copy_dir(&source, &destination).unwrap();
+        assert_eq!(
+            fs::read(destination.join("model/vocab.json")).unwrap(),
+            b"{}"
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_store::StoreExt;
+
+use super::accent_color::system_accent_color;
+
+const DEFAULT_COLOR: &str = "#000000";
+const MIN_OPACITY: f32 = 0.2;
+const MODES: [&str; 3] = ["default", "custom", "system"];
+const SHAPES: [&str; 3] = ["pill", "rounded", "square"];
+
+/// Appearance of the pill and toast windows, from the `pill_theme` setting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PillTheme {
+    /// "default" (black), "custom" (uses `color`) or "system" (OS accent color)
+    pub mode: String,
+    /// `#rrggbb`, used in custom mode and as the fallback when no accent is available
+    pub color: String,
+    pub opacity: f32,
+    /// "pill", "rounded" or "square"
+    pub shape: String,
+}
+
+impl Default for PillTheme {
+    fn default() -> Self {
+        Self {
+            mode: "default".to_string(),
+            color: DEFAULT_COLOR.to_string(),
+            opacity: 1.0,
+            shape: "pill".to_string(),
+        }
+    }
+}
+
+/// Theme payload of the `pill-theme` event and `window.__PILL_THEME__`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResolvedPillTheme {
+    pub color: String,
+    pub opacity: f32,
+    pub shape: String,
+}
+
+impl PillTheme {
+    /// Replace unknown modes/shapes and invalid colors with defaults and clamp opacity
+    pub fn normalized(&self) -> Self {
+        let defaults = Self::default();
+        let pick = |value: &str, allowed: &[&str], fallback: String| {
+            let value = value.trim().to_lowercase();
+            if allowed.contains(&value.as_str()) {
+                value
+            } else {
+                fallback
+            }
+        };
+
+        Self {
+            mode: pick(&self.mode, &MODES, defaults.mode),
+            color: parse_hex_color(&self.color).unwrap_or(defaults.color),
+            opacity: if self.opacity.is_finite() {
+                self.opacity.clamp(MIN_OPACITY, 1.0)
+            } else {
+                defaults.opacity
+            },
+            shape: pick(&self.shape, &SHAPES, defaults.shape),
+        }
+    }
+
+    /// Resolve the color to show; `system_accent` is only consulted in system mode
+    pub fn resolve(&self, system_accent: impl FnOnce() -> Option<String>) -> ResolvedPillTheme {
+        let theme = self.normalized();
+        let color = match theme.mode.as_str() {
+            "custom" => theme.color,
+            "system" => system_accent()
+                .and_then(|accent| parse_hex_color(&accent))
+                .unwrap_or(theme.color),
+            _ => DEFAULT_COLOR.to_string(),
+        };
+
+        ResolvedPillTheme {
+            color,
+            opacity: theme.opacity,
+            shape: theme.shape,
+        }
+    }
+}
+
+/// Normalize `#rgb` / `#rrggbb` to lowercase `#rrggbb`
+pub fn parse_hex_color(value: &str) -> Option<String> {
+    let hex = value.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    match hex.len() {
+        6 => Some(format!("#{}", hex.to_lowercase())),
+        3 => Some(
+            hex.chars()
+                .flat_map(|c| [c, c])
+                .fold("#".to_string(), |mut out, c| {
+                    out.push(c.to_ascii_lowercase());
+                    out
+                }),
+        ),
+        _ => None,
+    }
+}
+
+/// Script that exposes the theme to a webview before its page loads, so the pill and
+/// toast render with it from the first frame instead of waiting for `pill-theme`
+pub fn initialization_script(theme: &ResolvedPillTheme) -> String {
+    let json = serde_json::to_string(theme).unwrap_or_else(|_| "null".to_string());
+    format!("window.__PILL_THEME__ = {};", json)
+}
+
+/// Theme saved in the settings store
+pub fn load_pill_theme(app: &AppHandle) -> PillTheme {
+    app.store("settings")
+        .ok()
+        .and_then(|store| store.get("pill_theme"))
+        .and_then(|v| serde_json::from_value::<PillTheme>(v).ok())
+        .unwrap_or_default()
+        .normalized()
+}
+
+/// Saved theme with the system accent looked up if needed
+pub fn current_pill_theme(app: &AppHandle) -> ResolvedPillTheme {
+    load_pill_theme(app).resolve(system_accent_color)
+}
+
+/// Push the current theme to the pill and toast windows so changes apply live
+pub fn emit_pill_theme(app: &AppHandle) {
+    let theme = current_pill_theme(app);
+    for label in ["pill", "toast"] {
+        if let Some(window) = app.get_webview_window(label) {
+            if let Err(e) = window.emit("pill-theme", &theme) {
+                log::warn!("Failed to emit pill-theme to {} window: {}", label, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme(mode: &str, color: &str, opacity: f32, shape: &str) -> PillTheme {
+        PillTheme {
+            mode: mode.to_string(),
+            color: color.to_string(),
+            opacity,
+            shape: shape.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#FF8800").as_deref(), Some("#ff8800"));
+        assert_eq!(parse_hex_color(" #f80 ").as_deref(), Some("#ff8800"));
+        assert_eq!(parse_hex_color("ff8800"), None);
+        assert_eq!(parse_hex_color("#ff88"), None);
+        assert_eq!(parse_hex_color("#gg8800"), None);
+    }
+
+    #[test]
+    fn test_normalized_falls_back_to_defaults() {
+        let normalized = theme("Neon", "red", 3.0, "blob").normalized();
+        assert_eq!(normalized, theme("default", "#000000", 1.0, "pill"));
+
+        let normalized = theme("custom", "#ABC", 0.0, "Rounded").normalized();
+        assert_eq!(normalized, theme("custom", "#aabbcc", 0.2, "rounded"));
+
+        assert_eq!(
+            theme("custom", "#abc", f32::NAN, "pill")
+                .normalized()
+                .opacity,
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_resolve_by_mode() {
+        let accent = || Some("#007AFF".to_string());
+
+        assert_eq!(
+            theme("custom", "#ff0000", 0.8, "square").resolve(accent),
+            ResolvedPillTheme {
+                color: "#ff0000".to_string(),
+                opacity: 0.8,
+                shape: "square".to_string(),
+            }
+        );
+        assert_eq!(
+            theme("system", "#ff0000", 1.0, "pill")
+                .resolve(accent)
+                .color,
+            "#007aff"
+        );
+        // No accent available: fall back to the custom color
+        assert_eq!(
+            theme("system", "#ff0000", 1.0, "pill")
+                .resolve(|| None)
+                .color,
+            "#ff0000"
+        );
+        // Default mode ignores the custom color and never looks up the accent
+        assert_eq!(
+            theme("default", "#ff0000", 1.0, "pill")
+                .resolve(|| panic!("accent looked up"))
+                .color,
+            "#000000"
+        );
+    }
+
+    #[test]
+    fn test_initialization_script() {
+        let script = initialization_script(&ResolvedPillTheme {
+            color: "#112233".to_string(),
+            opacity: 0.5,
+            shape: "rounded".to_string(),
+        });
+        assert_eq!(
+            script,
+            r##"window.__PILL_THEME__ = {"color":"#112233","opacity":0.5,"shape":"rounded"};"##
+        );
+    }
+}
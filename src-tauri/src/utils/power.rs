@@ -0,0 +1,510 @@
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::whisper::manager::WhisperManager;
+
+/// How often the power source is polled for changes
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Settings store key remembering which model to restore once back on AC power
+const BATTERY_RESTORE_KEY: &str = "battery_restore_model";
+
+/// Snapshot of the system power source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerStatus {
+    pub on_battery: bool,
+    pub battery_percent: Option<u8>,
+}
+
+/// Read the current power source. Returns None on desktops without a battery
+/// or when the platform query fails.
+pub fn read_power_status() -> Option<PowerStatus> {
+    #[cfg(target_os = "macos")]
+    {
+        let output = std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .ok()?;
+        parse_pmset_output(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+        let output = std::process::Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                "Get-CimInstance Win32_Battery | Select-Object -First 1 | ForEach-Object { \"$($_.BatteryStatus),$($_.EstimatedChargeRemaining)\" }",
+            ])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .ok()?;
+        parse_windows_battery_output(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        read_linux_power_status()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Parse `pmset -g batt` output, e.g.
+/// "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1234)\t85%; discharging; ..."
+pub fn parse_pmset_output(output: &str) -> Option<PowerStatus> {
+    let on_battery = output.contains("'Battery Power'");
+    let has_battery = output.contains("InternalBattery");
+    if !has_battery {
+        return None;
+    }
+
+    let battery_percent = output
+        .split(|c: char| c.is_whitespace() || c == ';')
+        .find_map(|token| token.strip_suffix('%'))
+        .and_then(|n| n.parse::<u8>().ok());
+
+    Some(PowerStatus {
+        on_battery,
+        battery_percent,
+    })
+}
+
+/// Win32_Battery BatteryStatus values that mean running on the battery:
+/// 1 discharging, 4 low and 5 critical
+const WINDOWS_ON_BATTERY_STATUSES: [u32; 3] = [1, 4, 5];
+
+/// Parse "<BatteryStatus>,<EstimatedChargeRemaining>" from Win32_Battery.
+pub fn parse_windows_battery_output(output: &str) -> Option<PowerStatus> {
+    let line = output.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let (status, percent) = line.split_once(',')?;
+    let status = status.trim().parse::<u32>().ok()?;
+
+    Some(PowerStatus {
+        on_battery: WINDOWS_ON_BATTERY_STATUSES.contains(&status),
+        battery_percent: percent.trim().parse::<u8>().ok(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn read_linux_power_status() -> Option<PowerStatus> {
+    let entries = std::fs::read_dir("/sys/class/power_supply").ok()?;
+    let read = |path: &std::path::Path, file: &str| {
+        std::fs::read_to_string(path.join(file))
+            .ok()
+            .map(|s| s.trim().to_string())
+    };
+
+    let mut on_ac = false;
+    let mut battery: Option<(bool, Option<u8>)> = None;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match read(&path, "type").as_deref() {
+            Some("Mains") => on_ac |= read(&path, "online").as_deref() == Some("1"),
+            Some("Battery") if battery.is_none() => {
+                let discharging = read(&path, "status").as_deref() == Some("Discharging");
+                let percent = read(&path, "capacity").and_then(|c| c.parse::<u8>().ok());
+                battery = Some((discharging, percent));
+            }
+            _ => {}
+        }
+    }
+
+    let (discharging, battery_percent) = battery?;
+    Some(PowerStatus {
+        on_battery: discharging && !on_ac,
+        battery_percent,
+    })
+}
+
+/// Stored `battery_threshold_percent`, clamped to 1..=100 so a hand-edited
+/// store can't wrap around when narrowed to u8
+pub fn clamp_battery_threshold(percent: u64) -> u8 {
+    percent.clamp(1, 100) as u8
+}
+
+/// `battery_threshold_percent` must be a percentage; 0 would never switch
+pub fn validate_battery_threshold(percent: u8) -> Result<(), String> {
+    if !(1..=100).contains(&percent) {
+        return Err(format!(
+            "Battery threshold must be between 1 and 100%, got {}",
+            percent
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the battery model should be active for the given power state
+pub fn should_use_battery_model(status: Option<PowerStatus>, threshold_percent: u8) -> bool {
+    match status {
+        Some(PowerStatus {
+            on_battery: true,
+            battery_percent: Some(percent),
+        }) => percent < threshold_percent,
+        _ => false,
+    }
+}
+
+/// Pick the smallest downloaded model, but only if it is actually smaller than
+/// the current one. `downloaded_by_size` must be ordered smallest first.
+pub fn pick_battery_model(downloaded_by_size: &[String], current_model: &str) -> Option<String> {
+    let smallest = downloaded_by_size.first()?;
+    let current_rank = downloaded_by_size.iter().position(|m| m == current_model);
+
+    match current_rank {
+        Some(0) => None,
+        Some(_) => Some(smallest.clone()),
+        // Current model unknown to the size ranking (e.g. empty/auto) - leave it alone
+        None => None,
+    }
+}
+
+/// Polls the power source and switches models when it changes.
+pub struct PowerWatcher {
+    started: Arc<AtomicBool>,
+    app: AppHandle,
+}
+
+impl PowerWatcher {
+    pub fn new(app: AppHandle) -> Self {
+        Self {
+            started: Arc::new(AtomicBool::new(false)),
+            app,
+        }
+    }
+
+    pub fn start(&self) {
+        if self.started.swap(true, Ordering::SeqCst) {
+            log::debug!("PowerWatcher already running, skipping start");
+            return;
+        }
+
+        log::info!("Starting PowerWatcher for battery-aware model switching");
+
+        let app = self.app.clone();
+        let started = self.started.clone();
+
+        tauri::async_runtime::spawn(async move {
+            let mut last_state: Option<(bool, bool)> = None;
+
+            while started.load(Ordering::Relaxed) {
+                let status = tokio::task::spawn_blocking(read_power_status)
+                    .await
+                    .ok()
+                    .flatten();
+                let threshold = battery_threshold(&app);
+                let state = (
+                    status.map(|s| s.on_battery).unwrap_or(false),
+                    should_use_battery_model(status, threshold),
+                );
+
+                if last_state != Some(state) {
+                    if let Some(previous) = last_state {
+                        if previous.0 != state.0 {
+                            log::info!(
+                                "Power source changed: {}",
+                                if state.0 { "battery" } else { "AC" }
+                            );
+                            let _ = app.emit(
+                                "power-source-changed",
+                                json!({
+                                    "on_battery": state.0,
+                                    "battery_percent": status.and_then(|s| s.battery_percent)
+                                }),
+                            );
+                        }
+                    }
+                    last_state = Some(state);
+                    apply_power_policy(&app, status).await;
+                }
+
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        });
+    }
+}
+
+fn battery_threshold(app: &AppHandle) -> u8 {
+    app.store("settings")
+        .ok()
+        .and_then(|store| store.get("battery_threshold_percent"))
+        .and_then(|v| v.as_u64().map(clamp_battery_threshold))
+        .unwrap_or(20)
+}
+
+/// Re-read the power source and apply the battery model policy now.
+/// Called after battery settings change so the user doesn't wait for the next poll.
+pub async fn reevaluate_power_policy(app: &AppHandle) {
+    let status = tokio::task::spawn_blocking(read_power_status)
+        .await
+        .ok()
+        .flatten();
+    apply_power_policy(app, status).await;
+}
+
+/// Switch to the smallest downloaded model on low battery, and restore the
+/// previous model once the condition clears.
+async fn apply_power_policy(app: &AppHandle, status: Option<PowerStatus>) {
+    let store = match app.store("settings") {
+        Ok(store) => store,
+        Err(e) => {
+            log::warn!("PowerWatcher: failed to open settings store: {}", e);
+            return;
+        }
+    };
+
+    let enabled = store
+        .get("battery_aware_model")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let low_battery = enabled && should_use_battery_model(status, battery_threshold(app));
+    let restore = store.get(BATTERY_RESTORE_KEY).filter(|v| v.is_object());
+
+    let current_model = store
+        .get("current_model")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_default();
+    let current_engine = store
+        .get("current_model_engine")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| "whisper".to_string());
+
+    if low_battery && restore.is_none() {
+        // Only Whisper models have a size ladder to step down
+        if current_engine != "whisper" {
+            log::debug!(
+                "PowerWatcher: engine '{}' has no smaller model, not switching",
+                current_engine
+            );
+            return;
+        }
+
+        let downloaded_by_size = {
+            let whisper_state = app.state::<tauri::async_runtime::RwLock<WhisperManager>>();
+            let manager = whisper_state.read().await;
+            let downloaded = manager.get_downloaded_model_names();
+            manager
+                .get_models_by_size()
+                .into_iter()
+                .filter(|m| downloaded.contains(m))
+                .collect::<Vec<_>>()
+        };
+
+        let Some(battery_model) = pick_battery_model(&downloaded_by_size, &current_model) else {
+            log::debug!("PowerWatcher: no smaller downloaded model available");
+            return;
+        };
+
+        log::info!(
+            "Low battery ({:?}%), switching model '{}' -> '{}'",
+            status.and_then(|s| s.battery_percent),
+            current_model,
+            battery_model
+        );
+
+        store.set("current_model", json!(battery_model));
+        store.set(
+            BATTERY_RESTORE_KEY,
+            json!({
+                "model": current_model,
+                "engine": current_engine,
+                "battery_model": battery_model
+            }),
+        );
+        if let Err(e) = store.save() {
+            log::warn!("PowerWatcher: failed to save settings: {}", e);
+        }
+
+        on_model_switched(app, &battery_model, "whisper").await;
+        let _ = app.emit(
+            "battery-model-switched",
+            json!({
+                "model": battery_model,
+                "previous_model": current_model,
+                "battery_percent": status.and_then(|s| s.battery_percent)
+            }),
+        );
+        crate::commands::audio::pill_toast(
+            app,
+            &format!("Low battery - using {}", battery_model),
+            2500,
+        );
+    } else if !low_battery {
+        let Some(restore) = restore else {
+            return;
+        };
+
+        let battery_model = restore["battery_model"].as_str().unwrap_or_default();
+        let original_model = restore["model"].as_str().unwrap_or_default().to_string();
+        let original_engine = restore["engine"].as_str().unwrap_or("whisper").to_string();
+
+        store.delete(BATTERY_RESTORE_KEY);
+
+        // If the user picked another model meanwhile, respect their choice
+        if current_model == battery_model && !original_model.is_empty() {
+            log::info!(
+                "Battery condition cleared, restoring model '{}'",
+                original_model
+            );
+            store.set("current_model", json!(original_model));
+            store.set("current_model_engine", json!(original_engine));
+            if let Err(e) = store.save() {
+                log::warn!("PowerWatcher: failed to save settings: {}", e);
+            }
+
+            on_model_switched(app, &original_model, &original_engine).await;
+            let _ = app.emit("battery-model-restored", json!({ "model": original_model }));
+            crate::commands::audio::pill_toast(
+                app,
+                &format!("Power restored - using {}", original_model),
+                2500,
+            );
+        } else if let Err(e) = store.save() {
+            log::warn!("PowerWatcher: failed to save settings: {}", e);
+        }
+    }
+}
+
+async fn on_model_switched(app: &AppHandle, model: &str, engine: &str) {
+    crate::commands::audio::invalidate_recording_config_cache(app).await;
+
+    if engine == "whisper" {
+        let app_clone = app.clone();
+        let model_name = model.to_string();
+        tokio::spawn(async move {
+            let whisper_state = app_clone.state::<tauri::async_runtime::RwLock<WhisperManager>>();
+            if let Err(e) =
+                crate::commands::model::preload_model(app_clone.clone(), model_name, whisper_state)
+                    .await
+            {
+                log::warn!("PowerWatcher: failed to preload model: {}", e);
+            }
+        });
+    }
+
+    if let Err(e) = crate::commands::settings::update_tray_menu(app.clone()).await {
+        log::warn!(
+            "Failed to update tray menu after battery model switch: {}",
+            e
+        );
+    }
+
+    let _ = app.emit(
+        "model-changed",
+        json!({
+            "model": model,
+            "engine": engine
+        }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pmset_on_battery() {
+        let output = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=4653155)\t15%; discharging; 1:02 remaining present: true\n";
+        let status = parse_pmset_output(output).unwrap();
+        assert!(status.on_battery);
+        assert_eq!(status.battery_percent, Some(15));
+    }
+
+    #[test]
+    fn test_parse_pmset_on_ac_and_desktop() {
+        let output = "Now drawing from 'AC Power'\n -InternalBattery-0 (id=4653155)\t100%; charged; 0:00 remaining present: true\n";
+        let status = parse_pmset_output(output).unwrap();
+        assert!(!status.on_battery);
+        assert_eq!(status.battery_percent, Some(100));
+
+        assert_eq!(parse_pmset_output("Now drawing from 'AC Power'\n"), None);
+    }
+
+    #[test]
+    fn test_parse_windows_battery_output() {
+        let status = parse_windows_battery_output("1,42\r\n").unwrap();
+        assert!(status.on_battery);
+        assert_eq!(status.battery_percent, Some(42));
+
+        assert!(!parse_windows_battery_output("2,100").unwrap().on_battery);
+        assert_eq!(parse_windows_battery_output(""), None);
+    }
+
+    #[test]
+    fn test_parse_windows_low_and_critical_battery() {
+        let low = parse_windows_battery_output("4,9\r\n").unwrap();
+        assert!(low.on_battery);
+        assert_eq!(low.battery_percent, Some(9));
+
+        let critical = parse_windows_battery_output("5,3").unwrap();
+        assert!(critical.on_battery);
+        assert_eq!(critical.battery_percent, Some(3));
+        assert!(should_use_battery_model(Some(critical), 20));
+
+        // Charging states (6-9) and fully charged (3) are on AC power
+        for status in ["3,100", "6,50", "7,80", "8,20", "9,40"] {
+            assert!(!parse_windows_battery_output(status).unwrap().on_battery);
+        }
+    }
+
+    #[test]
+    fn test_should_use_battery_model() {
+        let low = Some(PowerStatus {
+            on_battery: true,
+            battery_percent: Some(10),
+        });
+        let charging = Some(PowerStatus {
+            on_battery: false,
+            battery_percent: Some(10),
+        });
+
+        assert!(should_use_battery_model(low, 20));
+        assert!(!should_use_battery_model(low, 10));
+        assert!(!should_use_battery_model(charging, 20));
+        assert!(!should_use_battery_model(None, 20));
+    }
+
+    #[test]
+    fn test_pick_battery_model() {
+        let models = vec!["base.en".to_string(), "large-v3-turbo".to_string()];
+
+        assert_eq!(
+            pick_battery_model(&models, "large-v3-turbo"),
+            Some("base.en".to_string())
+        );
+        assert_eq!(pick_battery_model(&models, "base.en"), None);
+        assert_eq!(pick_battery_model(&models, ""), None);
+        assert_eq!(pick_battery_model(&[], "large-v3"), None);
+    }
+
+    #[test]
+    fn test_battery_threshold_clamped_on_read() {
+        assert_eq!(clamp_battery_threshold(20), 20);
+        assert_eq!(clamp_battery_threshold(100), 100);
+        // Out-of-range stored values don't wrap around when narrowed to u8
+        assert_eq!(clamp_battery_threshold(0), 1);
+        assert_eq!(clamp_battery_threshold(300), 100);
+        assert_eq!(clamp_battery_threshold(u64::MAX), 100);
+    }
+
+    #[test]
+    fn test_battery_threshold_validated_on_save() {
+        assert!(validate_battery_threshold(1).is_ok());
+        assert!(validate_battery_threshold(20).is_ok());
+        assert!(validate_battery_threshold(100).is_ok());
+        assert!(validate_battery_threshold(0).is_err());
+        assert!(validate_battery_threshold(101).is_err());
+        assert!(validate_battery_threshold(255).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use regex::Regex;
+use serde_json::{Map, Value};
+
+/// What a match of a redaction pattern is replaced with in history
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Suggested `redaction_patterns`: payment card numbers, US social security
+/// numbers and email addresses. Nothing is redacted until they are added to the setting.
+pub const DEFAULT_REDACTION_PATTERNS: &[&str] = &[
+    // 13-19 digits, optionally grouped with spaces or dashes
+    r"\b(?:\d[ -]?){12,18}\d\b",
+    r"\b\d{3}[- ]\d{2}[- ]\d{4}\b",
+    r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b",
+];
+
+/// Check every pattern compiles, naming the first one that doesn't
+pub fn validate_redaction_patterns(patterns: &[String]) -> Result<(), String> {
+    for pattern in patterns.iter().filter(|p| !p.trim().is_empty()) {
+        Regex::new(pattern)
+            .map_err(|e| format!("Invalid redaction pattern '{}': {}", pattern, e))?;
+    }
+    Ok(())
+}
+
+fn compile_patterns(patterns: &[String]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .filter(|p| !p.trim().is_empty())
+        .filter_map(|pattern| {
+            Regex::new(pattern)
+                .inspect_err(|e| {
+                    log::warn!("Skipping invalid redaction pattern '{}': {}", pattern, e)
+                })
+                .ok()
+        })
+        .collect()
+}
+
+fn redact_with(text: &str, regexes: &[Regex]) -> String {
+    let mut result = text.to_string();
+    for regex in regexes {
+        result = regex.replace_all(&result, REDACTED).into_owned();
+    }
+    result
+}
+
+/// Replace every match of `patterns` in `text` with [`REDACTED`].
+/// Blank and invalid patterns are skipped.
+pub fn redact_text(text: &str, patterns: &[String]) -> String {
+    redact_with(text, &compile_patterns(patterns))
+}
+
+/// Redact a history entry's text along with the copies its metadata keeps
+/// (`raw_text` and the per-speaker segments). Returns the redacted text.
+pub fn redact_history_entry(
+    text: &str,
+    metadata: &mut Map<String, Value>,
+    patterns: &[String],
+) -> String {
+    let regexes = compile_patterns(patterns);
+    if regexes.is_empty() {
+        return text.to_string();
+    }
+
+    if let Some(Value::String(raw_text)) = metadata.get_mut("raw_text") {
+        *raw_text = redact_with(raw_text, &regexes);
+    }
+    if let Some(Value::Array(segments)) = metadata.get_mut("speakers") {
+        for segment in segments {
+            if let Some(Value::String(segment_text)) = segment.get_mut("text") {
+                *segment_text = redact_with(segment_text, &regexes);
+            }
+        }
+    }
+    redact_with(text, &regexes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> Vec<String> {
+        DEFAULT_REDACTION_PATTERNS
+            .iter()
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_patterns_compile() {
+        assert!(validate_redaction_patterns(&defaults()).is_ok());
+        assert!(validate_redaction_patterns(&["(unclosed".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_redacts_card_numbers() {
+        let patterns = defaults();
+        assert_eq!(
+            redact_text("card 4111 1111 1111 1111 expires soon", &patterns),
+            "card [REDACTED] expires soon"
+        );
+        assert_eq!(
+            redact_text("number 4111-1111-1111-1111", &patterns),
+            "number [REDACTED]"
+        );
+        assert_eq!(
+            redact_text("amex 378282246310005", &patterns),
+            "amex [REDACTED]"
+        );
+    }
+
+    #[test]
+    fn test_redacts_social_security_numbers() {
+        let patterns = defaults();
+        assert_eq!(
+            redact_text("my SSN is 123-45-6789.", &patterns),
+            "my SSN is [REDACTED]."
+        );
+        assert_eq!(redact_text("or 123 45 6789", &patterns), "or [REDACTED]");
+    }
+
+    #[test]
+    fn test_redacts_email_addresses() {
+        let patterns = defaults();
+        assert_eq!(
+            redact_text("write to jane.doe+work@example.co.uk today", &patterns),
+            "write to [REDACTED] today"
+        );
+    }
+
+    #[test]
+    fn test_leaves_other_text_intact() {
+        let patterns = defaults();
+        let text = "Call me at 5 pm, room 1234, budget 2,500 dollars, see you @ noon.";
+        assert_eq!(redact_text(text, &patterns), text);
+        assert_eq!(redact_text(text, &[]), text);
+    }
+
+    #[test]
+    fn test_redaction_is_idempotent() {
+        let patterns = defaults();
+        let once = redact_text("ssn 123-45-6789 and a@b.io", &patterns);
+        assert_eq!(redact_text(&once, &patterns), once);
+    }
+
+    #[test]
+    fn test_redacts_entry_metadata() {
+        let mut metadata = serde_json::json!({
+            "raw_text": "email bob@example.com",
+            "speakers": [{ "speaker_id": "1", "text": "ssn 123-45-6789", "start_ms": 0, "end_ms": 900 }],
+            "model": "base.en"
+        })
+        .as_object()
+        .cloned()
+        .unwrap();
+
+        let text = redact_history_entry("Email bob@example.com", &mut metadata, &defaults());
+        assert_eq!(text, "Email [REDACTED]");
+        assert_eq!(metadata["raw_text"], "email [REDACTED]");
+        assert_eq!(metadata["speakers"][0]["text"], "ssn [REDACTED]");
+        assert_eq!(metadata["model"], "base.en");
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_skipped() {
+        let patterns = vec!["(unclosed".to_string(), r"secret\d+".to_string()];
+        assert_eq!(
+            redact_text("code secret42 here", &patterns),
+            "code [REDACTED] here"
+        );
+    }
+}
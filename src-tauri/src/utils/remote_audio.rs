@@ -0,0 +1,358 @@
+//! Download a remote audio file (e.g. a meeting recording link) to a temp file
+//! for transcription, refusing anything too large or clearly not audio.
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use futures_util::{Stream, StreamExt};
+use tokio::io::AsyncWriteExt;
+
+use crate::commands::clipboard::AUDIO_FILE_EXTENSIONS;
+
+/// Largest remote file accepted, matching the recorder's own 500MB limit
+pub const MAX_REMOTE_AUDIO_BYTES: u64 = 500 * 1024 * 1024;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+/// Whole download, body included
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// A downloaded file, removed when dropped so every exit path cleans up
+#[derive(Debug)]
+pub struct TempAudioFile {
+    path: PathBuf,
+}
+
+impl TempAudioFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempAudioFile {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("Failed to remove downloaded audio {:?}: {}", self.path, e);
+            }
+        }
+    }
+}
+
+/// Only plain http(s) links are fetched
+pub fn parse_audio_url(url: &str) -> Result<reqwest::Url, String> {
+    let parsed =
+        reqwest::Url::parse(url.trim()).map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(parsed),
+        scheme => Err(format!(
+            "Unsupported URL scheme '{}': use http or https",
+            scheme
+        )),
+    }
+}
+
+/// Reject responses that announce something other than audio or video, such as
+/// the HTML page of a sharing site. Servers that don't say are given the benefit
+/// of the doubt; ffmpeg rejects the file later if it isn't audio.
+pub fn check_content_type(content_type: Option<&str>) -> Result<(), String> {
+    let Some(content_type) = content_type else {
+        return Ok(());
+    };
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    let accepted = mime.is_empty()
+        || mime.starts_with("audio/")
+        || mime.starts_with("video/")
+        || matches!(
+            mime.as_str(),
+            "application/octet-stream" | "application/ogg" | "binary/octet-stream"
+        );
+    if accepted {
+        Ok(())
+    } else {
+        Err(format!(
+            "The link doesn't point to an audio file (content type {})",
+            mime
+        ))
+    }
+}
+
+/// Reject a download whose announced size is over `max_bytes` before reading it
+pub fn check_declared_size(content_length: Option<u64>, max_bytes: u64) -> Result<(), String> {
+    match content_length {
+        Some(length) if length > max_bytes => Err(size_error(max_bytes)),
+        _ => Ok(()),
+    }
+}
+
+fn size_error(max_bytes: u64) -> String {
+    format!(
+        "Remote audio file is larger than the {}MB limit",
+        max_bytes / (1024 * 1024)
+    )
+}
+
+/// Extension for the temp file: the URL's own when it is a known audio type,
+/// otherwise one derived from the content type. ffmpeg probes the content
+/// anyway, so "audio" is fine when neither says.
+pub fn download_extension(url: &reqwest::Url, content_type: Option<&str>) -> String {
+    let from_url = Path::new(url.path())
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .filter(|ext| AUDIO_FILE_EXTENSIONS.contains(&ext.as_str()));
+    if let Some(ext) = from_url {
+        return ext;
+    }
+
+    let mime = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(|mime| mime.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    match mime.as_str() {
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/mp4" | "audio/x-m4a" | "audio/m4a" => "m4a",
+        "audio/flac" | "audio/x-flac" => "flac",
+        "audio/ogg" | "application/ogg" => "ogg",
+        "audio/webm" | "video/webm" => "webm",
+        "video/mp4" => "mp4",
+        _ => "audio",
+    }
+    .to_string()
+}
+
+/// Write `body` to `path`, giving up once more than `max_bytes` arrive. On any
+/// failure the partial file is removed. Returns the bytes written.
+pub async fn write_capped<S, B, E>(body: S, path: &Path, max_bytes: u64) -> Result<u64, String>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+    E: Display,
+{
+    let result = write_body(body, path, max_bytes).await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(path).await;
+    }
+    result
+}
+
+async fn write_body<S, B, E>(mut body: S, path: &Path, max_bytes: u64) -> Result<u64, String>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+    E: Display,
+{
+    let mut out = tokio::fs::File::create(path)
+        .await
+        .map_err(|e| format!("Failed to create download file: {}", e))?;
+
+    let mut written: u64 = 0;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|e| format!("Download failed: {}", e))?;
+        let chunk = chunk.as_ref();
+        written += chunk.len() as u64;
+        // Servers can lie about or omit Content-Length, so count what arrives
+        if written > max_bytes {
+            return Err(size_error(max_bytes));
+        }
+        out.write_all(chunk)
+            .await
+            .map_err(|e| format!("Failed to write download: {}", e))?;
+    }
+    out.flush()
+        .await
+        .map_err(|e| format!("Failed to write download: {}", e))?;
+    Ok(written)
+}
+
+fn temp_download_path(dir: &Path, extension: &str) -> PathBuf {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    let ts = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    dir.join(format!("url_audio_{}_{}.{}", ts, id, extension))
+}
+
+/// Download `url` into `dir`, checking the content type and announced size
+/// before reading the body and the actual size while reading it
+pub async fn download_audio(
+    url: &str,
+    dir: &Path,
+    max_bytes: u64,
+) -> Result<TempAudioFile, String> {
+    let url = parse_audio_url(url)?;
+    let client = reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    log::info!("Downloading remote audio from {}", url);
+    let response = client
+        .get(url.clone())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to fetch {}: server returned {}",
+            url,
+            response.status()
+        ));
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    check_content_type(content_type.as_deref())?;
+    check_declared_size(response.content_length(), max_bytes)?;
+
+    let path = temp_download_path(dir, &download_extension(&url, content_type.as_deref()));
+    let size = write_capped(response.bytes_stream(), &path, max_bytes).await?;
+    log::info!("Downloaded {} bytes of remote audio to {:?}", size, path);
+    Ok(TempAudioFile { path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn chunks(sizes: &[usize]) -> impl Stream<Item = Result<Vec<u8>, String>> + Unpin {
+        stream::iter(sizes.iter().map(|&n| Ok(vec![0u8; n])).collect::<Vec<_>>())
+    }
+
+    fn dir_is_empty(dir: &Path) -> bool {
+        std::fs::read_dir(dir).unwrap().next().is_none()
+    }
+
+    /// Serve `response` to every connection on a loopback port
+    async fn serve(response: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let _ = socket.read(&mut request).await;
+                let _ = socket.write_all(&response).await;
+                let _ = socket.shutdown().await;
+            }
+        });
+        format!("http://{}/meeting.m4a", addr)
+    }
+
+    #[tokio::test]
+    async fn test_write_capped_enforces_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ok.wav");
+        assert_eq!(write_capped(chunks(&[40, 60]), &path, 100).await, Ok(100));
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 100);
+        std::fs::remove_file(&path).unwrap();
+
+        let path = dir.path().join("too_big.wav");
+        let err = write_capped(chunks(&[40, 60, 1]), &path, 100)
+            .await
+            .unwrap_err();
+        assert!(err.contains("limit"), "{}", err);
+        assert!(dir_is_empty(dir.path()));
+    }
+
+    #[tokio::test]
+    async fn test_failed_download_leaves_no_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.wav");
+        let body = stream::iter(vec![Ok(vec![0u8; 10]), Err("connection reset".to_string())]);
+        let err = write_capped(body, &path, 100).await.unwrap_err();
+        assert!(err.contains("connection reset"), "{}", err);
+        assert!(dir_is_empty(dir.path()));
+
+        // The returned file is removed once the caller is done with it
+        let file = download_audio(
+            &serve(
+                b"HTTP/1.1 200 OK\r\nContent-Type: audio/mp4\r\nContent-Length: 4\r\n\r\nRIFF"
+                    .to_vec(),
+            )
+            .await,
+            dir.path(),
+            100,
+        )
+        .await
+        .unwrap();
+        assert_eq!(file.path().extension().unwrap(), "m4a");
+        assert_eq!(std::fs::read(file.path()).unwrap(), b"RIFF");
+        drop(file);
+        assert!(dir_is_empty(dir.path()));
+    }
+
+    #[tokio::test]
+    async fn test_download_checks_before_reading_body() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let url = serve(
+            b"HTTP/1.1 200 OK\r\nContent-Type: audio/mpeg\r\nContent-Length: 5000\r\n\r\n".to_vec(),
+        )
+        .await;
+        let err = download_audio(&url, dir.path(), 100).await.unwrap_err();
+        assert!(err.contains("limit"), "{}", err);
+
+        let url = serve(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: 6\r\n\r\n<html>"
+                .to_vec(),
+        )
+        .await;
+        let err = download_audio(&url, dir.path(), 100).await.unwrap_err();
+        assert!(err.contains("text/html"), "{}", err);
+
+        // No Content-Length: the cap applies while reading
+        let mut response = b"HTTP/1.1 200 OK\r\nContent-Type: audio/mpeg\r\n\r\n".to_vec();
+        response.extend(vec![0u8; 500]);
+        let err = download_audio(&serve(response).await, dir.path(), 100)
+            .await
+            .unwrap_err();
+        assert!(err.contains("limit"), "{}", err);
+
+        let url = serve(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_vec()).await;
+        assert!(download_audio(&url, dir.path(), 100).await.is_err());
+
+        assert!(dir_is_empty(dir.path()));
+    }
+
+    #[test]
+    fn test_url_and_header_checks() {
+        assert!(parse_audio_url("https://example.com/a.mp3").is_ok());
+        assert!(parse_audio_url("file:///etc/passwd").is_err());
+        assert!(parse_audio_url("not a url").is_err());
+
+        assert!(check_content_type(None).is_ok());
+        assert!(check_content_type(Some("audio/mpeg")).is_ok());
+        assert!(check_content_type(Some("Video/MP4; codecs=avc1")).is_ok());
+        assert!(check_content_type(Some("application/octet-stream")).is_ok());
+        assert!(check_content_type(Some("text/html; charset=utf-8")).is_err());
+        assert!(check_content_type(Some("application/json")).is_err());
+
+        assert!(check_declared_size(None, 100).is_ok());
+        assert!(check_declared_size(Some(100), 100).is_ok());
+        assert!(check_declared_size(Some(101), 100).is_err());
+
+        let url = |s: &str| reqwest::Url::parse(s).unwrap();
+        assert_eq!(
+            download_extension(&url("https://x.io/call.MP3?dl=1"), None),
+            "mp3"
+        );
+        assert_eq!(
+            download_extension(&url("https://x.io/download?id=7"), Some("audio/x-wav")),
+            "wav"
+        );
+        assert_eq!(download_extension(&url("https://x.io/file"), None), "audio");
+    }
+}
@@ -83,6 +83,19 @@ fn get_current_resources() -> SystemResources {
     }
 }
 
+/// Free bytes on the disk holding `path`, or `None` when no mounted disk contains it
+pub fn available_space_at(path: &Path) -> Option<u64> {
+    let disks = Disks::new_with_refreshed_list();
+
+    // The most specific mount point wins ("/" contains every path)
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
 /// Get available disk space in GB for the current working directory
 fn get_available_disk_space() -> f64 {
     // Try to get disk space for the current directory
@@ -0,0 +1,237 @@
+/// The window a dictation started in, so its text can go back there even if the
+/// user switched apps while it was being transcribed (`lock_target_window`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetWindow {
+    /// Platform handle: the app's pid on macOS, the HWND on Windows, the X11
+    /// window id on Linux
+    pub handle: u64,
+    /// Process owning the window
+    pub pid: u32,
+    pub app_name: Option<String>,
+}
+
+/// Where the text of a locked dictation should go
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InsertTarget {
+    /// Insert into whatever has focus now
+    Current,
+    /// Bring the captured window back to the front first
+    Refocus(TargetWindow),
+}
+
+/// The target to remember at recording start. VoiceTypr's own windows are never
+/// locked onto, since pasting into them isn't what a dictation is for.
+pub fn target_to_capture(frontmost: Option<TargetWindow>, own_pid: u32) -> Option<TargetWindow> {
+    frontmost.filter(|window| window.pid != own_pid)
+}
+
+/// Whether inserting needs to refocus the captured window first
+pub fn insert_target(
+    lock_enabled: bool,
+    target: Option<&TargetWindow>,
+    current: Option<&TargetWindow>,
+) -> InsertTarget {
+    match target {
+        Some(target) if lock_enabled && current.map(|c| c.handle) != Some(target.handle) => {
+            InsertTarget::Refocus(target.clone())
+        }
+        _ => InsertTarget::Current,
+    }
+}
+
+/// The window that has focus right now
+pub fn frontmost_window() -> Option<TargetWindow> {
+    platform_frontmost_window()
+}
+
+/// Bring `target` back to the front. Fails when the window or its app is gone.
+pub fn refocus(target: &TargetWindow) -> Result<(), String> {
+    platform_refocus(target)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_frontmost_window() -> Option<TargetWindow> {
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg(
+            "tell application \"System Events\" to get {unix id, name} of first application process whose frontmost is true",
+        )
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // "1234, Safari"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (pid, name) = stdout.trim().split_once(", ")?;
+    let pid: u32 = pid.trim().parse().ok()?;
+    Some(TargetWindow {
+        handle: pid as u64,
+        pid,
+        app_name: Some(name.trim().to_string()).filter(|name| !name.is_empty()),
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn platform_refocus(target: &TargetWindow) -> Result<(), String> {
+    let script = format!(
+        "tell application \"System Events\" to set frontmost of (first application process whose unix id is {}) to true",
+        target.pid
+    );
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Target app {:?} is no longer running",
+            target.app_name.as_deref().unwrap_or("unknown")
+        ))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn platform_frontmost_window() -> Option<TargetWindow> {
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    let (handle, pid) = unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.0 == 0 {
+            return None;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid as *mut u32));
+        (hwnd.0 as u64, pid)
+    };
+    Some(TargetWindow {
+        handle,
+        pid,
+        app_name: super::active_app::frontmost_app_name(),
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn platform_refocus(target: &TargetWindow) -> Result<(), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{IsWindow, SetForegroundWindow};
+
+    let hwnd = HWND(target.handle as isize);
+    unsafe {
+        if !IsWindow(hwnd).as_bool() {
+            return Err("Target window is no longer open".to_string());
+        }
+        if !SetForegroundWindow(hwnd).as_bool() {
+            return Err("Windows refused to bring the target window forward".to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn platform_frontmost_window() -> Option<TargetWindow> {
+    // X11 with xdotool only, as for the frontmost app name
+    let run = |args: &[&str]| -> Option<String> {
+        let output = std::process::Command::new("xdotool")
+            .args(args)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let handle = run(&["getactivewindow"])?.parse().ok()?;
+    let pid = run(&["getactivewindow", "getwindowpid"])?.parse().ok()?;
+    Some(TargetWindow {
+        handle,
+        pid,
+        app_name: super::active_app::frontmost_app_name(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn platform_refocus(target: &TargetWindow) -> Result<(), String> {
+    let status = std::process::Command::new("xdotool")
+        .args(["windowactivate", "--sync", &target.handle.to_string()])
+        .status()
+        .map_err(|e| format!("Failed to run xdotool: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err("Target window is no longer open".to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn platform_frontmost_window() -> Option<TargetWindow> {
+    None
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn platform_refocus(_target: &TargetWindow) -> Result<(), String> {
+    Err("Refocusing windows is not supported on this platform".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(handle: u64, pid: u32, name: &str) -> TargetWindow {
+        TargetWindow {
+            handle,
+            pid,
+            app_name: Some(name.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_own_windows_are_not_captured() {
+        let notes = window(10, 200, "Notes");
+        assert_eq!(target_to_capture(Some(notes.clone()), 100), Some(notes));
+        assert_eq!(
+            target_to_capture(Some(window(11, 100, "VoiceTypr")), 100),
+            None
+        );
+        assert_eq!(target_to_capture(None, 100), None);
+    }
+
+    #[test]
+    fn test_refocus_only_when_focus_moved() {
+        let notes = window(10, 200, "Notes");
+        let mail = window(20, 300, "Mail");
+
+        // Switched to Mail while transcribing: go back to Notes
+        assert_eq!(
+            insert_target(true, Some(&notes), Some(&mail)),
+            InsertTarget::Refocus(notes.clone())
+        );
+        // Still in Notes
+        assert_eq!(
+            insert_target(true, Some(&notes), Some(&notes)),
+            InsertTarget::Current
+        );
+        // Focus unknown: try the target anyway
+        assert_eq!(
+            insert_target(true, Some(&notes), None),
+            InsertTarget::Refocus(notes.clone())
+        );
+    }
+
+    #[test]
+    fn test_no_refocus_without_lock_or_target() {
+        let notes = window(10, 200, "Notes");
+        let mail = window(20, 300, "Mail");
+        assert_eq!(
+            insert_target(false, Some(&notes), Some(&mail)),
+            InsertTarget::Current
+        );
+        assert_eq!(
+            insert_target(true, None, Some(&mail)),
+            InsertTarget::Current
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use once_cell::sync::Lazy;
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// A find-and-replace rule from the `replacement_rules` setting
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplacementRule {
+    pub pattern: String,
+    pub replacement: String,
+    /// Treat `pattern` as a regex; `replacement` may then use `$1`-style groups
+    pub is_regex: bool,
+    pub case_sensitive: bool,
+}
+
+struct CompiledRule {
+    regex: Regex,
+    replacement: String,
+    is_regex: bool,
+}
+
+/// Rules compiled to regexes, in the order they are applied
+pub struct CompiledReplacements {
+    rules: Vec<CompiledRule>,
+}
+
+impl CompiledReplacements {
+    /// Compile `rules`, skipping blank patterns and invalid regexes with a warning
+    pub fn new(rules: &[ReplacementRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter(|rule| !rule.pattern.trim().is_empty())
+            .filter_map(|rule| {
+                let source = if rule.is_regex {
+                    rule.pattern.clone()
+                } else {
+                    literal_pattern(rule.pattern.trim())
+                };
+
+                match RegexBuilder::new(&source)
+                    .case_insensitive(!rule.case_sensitive)
+                    .build()
+                {
+                    Ok(regex) => Some(CompiledRule {
+                        regex,
+                        replacement: rule.replacement.clone(),
+                        is_regex: rule.is_regex,
+                    }),
+                    Err(e) => {
+                        log::warn!(
+                            "Skipping invalid replacement pattern '{}': {}",
+                            rule.pattern,
+                            e
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Apply every rule in order; each rule sees the output of the previous one
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        for rule in &self.rules {
+            result = if rule.is_regex {
+                rule.regex
+                    .replace_all(&result, rule.replacement.as_str())
+                    .into_owned()
+            } else {
+                rule.regex
+                    .replace_all(&result, NoExpand(&rule.replacement))
+                    .into_owned()
+            };
+        }
+        result
+    }
+}
+
+/// Escape a literal pattern, anchoring it on word boundaries where it starts or ends
+/// with a word character so "gonna" doesn't match inside longer words
+fn literal_pattern(pattern: &str) -> String {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let start = if is_word(pattern.chars().next()) {
+        r"\b"
+    } else {
+        ""
+    };
+    let end = if is_word(pattern.chars().last()) {
+        r"\b"
+    } else {
+        ""
+    };
+    format!("{}{}{}", start, regex::escape(pattern), end)
+}
+
+// Last compiled rule set; settings rarely change so one entry is enough
+type CachedReplacements = (Vec<ReplacementRule>, Arc<CompiledReplacements>);
+static COMPILED_CACHE: Lazy<Mutex<Option<CachedReplacements>>> = Lazy::new(|| Mutex::new(None));
+
+fn compiled_for(rules: &[ReplacementRule]) -> Arc<CompiledReplacements> {
+    let mut cache = match COMPILED_CACHE.lock() {
+        Ok(cache) => cache,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    if let Some((cached_rules, compiled)) = cache.as_ref() {
+        if cached_rules.as_slice() == rules {
+            return compiled.clone();
+        }
+    }
+
+    let compiled = Arc::new(CompiledReplacements::new(rules));
+    *cache = Some((rules.to_vec(), compiled.clone()));
+    compiled
+}
+
+/// Apply the replacement dictionary to a transcription, reusing compiled regexes
+pub fn apply_replacement_rules(text: &str, rules: &[ReplacementRule]) -> String {
+    if rules.is_empty() {
+        return text.to_string();
+    }
+    compiled_for(rules).apply(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(pattern: &str, replacement: &str) -> ReplacementRule {
+        ReplacementRule {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn regex(pattern: &str, replacement: &str) -> ReplacementRule {
+        ReplacementRule {
+            is_regex: true,
+            ..literal(pattern, replacement)
+        }
+    }
+
+    #[test]
+    fn test_literal_replacement() {
+        let rules = vec![
+            literal("gonna", "going to"),
+            literal("acme corp", "ACME Corp."),
+        ];
+
+        assert_eq!(
+            apply_replacement_rules("Gonna call Acme Corp about it", &rules),
+            "going to call ACME Corp. about it"
+        );
+        // Whole words only
+        assert_eq!(apply_replacement_rules("Gonnastuff", &rules), "Gonnastuff");
+        // Regex metacharacters in literals are matched verbatim, "$1" is not expanded
+        let rules = vec![literal("c++", "C$1 plus plus")];
+        assert_eq!(
+            apply_replacement_rules("I like c++ and cpp", &rules),
+            "I like C$1 plus plus and cpp"
+        );
+    }
+
+    #[test]
+    fn test_case_sensitive_literal() {
+        let rules = vec![ReplacementRule {
+            case_sensitive: true,
+            ..literal("Go", "Golang")
+        }];
+        assert_eq!(
+            apply_replacement_rules("Go is fun, go on", &rules),
+            "Golang is fun, go on"
+        );
+    }
+
+    #[test]
+    fn test_regex_replacement() {
+        let rules = vec![regex(r"(\d+) percent", "$1%")];
+        assert_eq!(
+            apply_replacement_rules("Up 20 percent, then 5 Percent", &rules),
+            "Up 20%, then 5%"
+        );
+    }
+
+    #[test]
+    fn test_invalid_regex_is_skipped() {
+        let rules = vec![regex("(unclosed", "x"), literal("teh", "the")];
+        assert_eq!(
+            apply_replacement_rules("teh (unclosed", &rules),
+            "the (unclosed"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_rules_apply_in_order() {
+        // The first rule's output feeds the second rule
+        let rules = vec![literal("gonna", "going to"), literal("going to", "will")];
+        assert_eq!(apply_replacement_rules("gonna go", &rules), "will go");
+
+        // Reversed order: the second rule never sees "going to"
+        let rules = vec![literal("going to", "will"), literal("gonna", "going to")];
+        assert_eq!(apply_replacement_rules("gonna go", &rules), "going to go");
+
+        // A broad rule listed first wins over a narrower one
+        let rules = vec![
+            regex(r"new york( city)?", "NYC"),
+            literal("new york city", "New York City"),
+        ];
+        assert_eq!(
+            apply_replacement_rules("I love New York City", &rules),
+            "I love NYC"
+        );
+    }
+
+    #[test]
+    fn test_changed_rules_are_recompiled() {
+        let rules = vec![literal("colour", "color")];
+        assert_eq!(apply_replacement_rules("colour", &rules), "color");
+
+        let rules = vec![literal("colour", "hue")];
+        assert_eq!(apply_replacement_rules("colour", &rules), "hue");
+
+        assert_eq!(apply_replacement_rules("unchanged", &[]), "unchanged");
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::OnceLock;
+
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+pub const TOAST_WIDTH: f64 = 400.0;
+pub const TOAST_HEIGHT: f64 = 80.0;
+const PILL_WIDTH: f64 = 80.0;
+const PILL_HEIGHT: f64 = 40.0;
+/// Space between the pill and the toast
+const GAP: f64 = 8.0;
+
+pub const MIN_DURATION_SCALE: f32 = 0.5;
+pub const MAX_DURATION_SCALE: f32 = 4.0;
+/// Toasts stay up at least this much longer when the OS asks for reduced motion
+const REDUCED_MOTION_SCALE: f32 = 1.5;
+
+/// Where the toast window sits relative to the pill (`toast_position` setting)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastPosition {
+    #[default]
+    Above,
+    Below,
+    Left,
+    Right,
+}
+
+impl ToastPosition {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "above" => Ok(Self::Above),
+            "below" => Ok(Self::Below),
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            other => Err(format!(
+                "Invalid toast position '{}': expected above, below, left or right",
+                other
+            )),
+        }
+    }
+
+    /// Top-left corner of the toast window for a pill at (`pill_x`, `pill_y`)
+    pub fn origin(&self, pill_x: f64, pill_y: f64) -> (f64, f64) {
+        let centered_x = pill_x + (PILL_WIDTH - TOAST_WIDTH) / 2.0;
+        let centered_y = pill_y + (PILL_HEIGHT - TOAST_HEIGHT) / 2.0;
+        match self {
+            Self::Above => (centered_x, pill_y - TOAST_HEIGHT - GAP),
+            Self::Below => (centered_x, pill_y + PILL_HEIGHT + GAP),
+            Self::Left => (pill_x - TOAST_WIDTH - GAP, centered_y),
+            Self::Right => (pill_x + PILL_WIDTH + GAP, centered_y),
+        }
+    }
+}
+
+/// How long a toast asked to show for `duration_ms` actually stays up
+pub fn effective_duration_ms(duration_ms: u64, scale: f32, reduced_motion: bool) -> u64 {
+    let mut scale = if scale.is_finite() {
+        scale.clamp(MIN_DURATION_SCALE, MAX_DURATION_SCALE)
+    } else {
+        1.0
+    };
+    if reduced_motion {
+        scale = scale.max(REDUCED_MOTION_SCALE);
+    }
+    (duration_ms as f64 * scale as f64).round() as u64
+}
+
+/// The saved `toast_position`, above the pill when unset or invalid
+pub fn current_toast_position(app: &AppHandle) -> ToastPosition {
+    app.store("settings")
+        .ok()
+        .and_then(|store| store.get("toast_position"))
+        .and_then(|v| v.as_str().and_then(|s| ToastPosition::parse(s).ok()))
+        .unwrap_or_default()
+}
+
+/// `duration_ms` scaled by the `toast_duration_scale` setting and the OS
+/// reduced-motion preference
+pub fn scaled_toast_duration(app: &AppHandle, duration_ms: u64) -> u64 {
+    let scale = app
+        .store("settings")
+        .ok()
+        .and_then(|store| store.get("toast_duration_scale"))
+        .and_then(|v| v.as_f64())
+        .unwrap_or(1.0) as f32;
+    effective_duration_ms(duration_ms, scale, prefers_reduced_motion())
+}
+
+/// Whether the OS asks apps to cut down on motion. Read once per session, since
+/// looking it up means running a system tool.
+pub fn prefers_reduced_motion() -> bool {
+    static REDUCED_MOTION: OnceLock<bool> = OnceLock::new();
+    *REDUCED_MOTION.get_or_init(platform_reduced_motion)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_reduced_motion() -> bool {
+    std::process::Command::new("defaults")
+        .args(["read", "com.apple.universalaccess", "reduceMotion"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "1")
+}
+
+#[cfg(target_os = "windows")]
+fn platform_reduced_motion() -> bool {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    // "Show animations in Windows" off sets MinAnimate to 0
+    std::process::Command::new("reg")
+        .args([
+            "query",
+            r"HKCU\Control Panel\Desktop\WindowMetrics",
+            "/v",
+            "MinAnimate",
+        ])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .is_ok_and(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .last()
+                == Some("0")
+        })
+}
+
+#[cfg(target_os = "linux")]
+fn platform_reduced_motion() -> bool {
+    std::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "enable-animations"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "false")
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn platform_reduced_motion() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_duration() {
+        assert_eq!(effective_duration_ms(1500, 1.0, false), 1500);
+        assert_eq!(effective_duration_ms(1500, 2.0, false), 3000);
+        // Out-of-range scales are clamped
+        assert_eq!(effective_duration_ms(1000, 0.1, false), 500);
+        assert_eq!(effective_duration_ms(1000, 10.0, false), 4000);
+        assert_eq!(effective_duration_ms(1000, f32::NAN, false), 1000);
+    }
+
+    #[test]
+    fn test_reduced_motion_extends_short_toasts() {
+        assert_eq!(effective_duration_ms(1000, 1.0, true), 1500);
+        assert_eq!(effective_duration_ms(1000, 0.5, true), 1500);
+        // A scale already past the reduced-motion minimum is kept
+        assert_eq!(effective_duration_ms(1000, 3.0, true), 3000);
+    }
+
+    #[test]
+    fn test_toast_origin_around_pill() {
+        let (pill_x, pill_y) = (680.0, 850.0);
+        assert_eq!(ToastPosition::Above.origin(pill_x, pill_y), (520.0, 762.0));
+        assert_eq!(ToastPosition::Below.origin(pill_x, pill_y), (520.0, 898.0));
+        assert_eq!(ToastPosition::Left.origin(pill_x, pill_y), (272.0, 830.0));
+        assert_eq!(ToastPosition::Right.origin(pill_x, pill_y), (768.0, 830.0));
+        assert!(ToastPosition::parse("middle").is_err());
+    }
+}
@@ -5,31 +5,44 @@ use std::sync::Arc;
 use super::transcriber::Transcriber;
 use crate::utils::logger::*;
 
-/// Maximum number of models to keep in cache
+/// Default number of models to keep in cache
 /// Only cache the current model to minimize RAM usage (1-3GB per model)
 const MAX_CACHE_SIZE: usize = 1;
 
+/// Upper bound for the `model_cache_capacity` setting
+pub const MAX_MODEL_CACHE_CAPACITY: usize = 3;
+
 /// Simple LRU cache that keeps loaded `Transcriber` models with size limits.
 ///
 /// Loading a GGML model from disk can take hundreds of milliseconds and a lot
 /// of RAM (1-3GB per model). By keeping a limited number of models in memory
 /// we balance performance with memory usage.
-pub struct TranscriberCache {
+pub type TranscriberCache = ModelCache<Transcriber>;
+
+/// LRU bookkeeping behind [`TranscriberCache`], generic over the cached model so
+/// eviction can be tested without loading real models.
+pub struct ModelCache<T> {
     /// Keyed by absolute path to the `.bin` model file.
-    map: HashMap<String, Arc<Transcriber>>,
+    map: HashMap<String, Arc<T>>,
     /// Track access order for LRU eviction
     lru_order: VecDeque<String>,
     /// Maximum number of models to cache
     max_size: usize,
+    /// Whether models load with GPU acceleration (the `use_gpu` setting)
+    use_gpu: bool,
+    /// Called with every freshly loaded model, e.g. to report a GPU fallback
+    on_load: Option<LoadHook<T>>,
 }
 
-impl Default for TranscriberCache {
+type LoadHook<T> = Box<dyn Fn(&T) + Send + Sync>;
+
+impl<T> Default for ModelCache<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl TranscriberCache {
+impl<T> ModelCache<T> {
     /// Create an empty cache with default size limit.
     pub fn new() -> Self {
         Self::with_capacity(MAX_CACHE_SIZE)
@@ -41,23 +54,44 @@ impl TranscriberCache {
             map: HashMap::new(),
             lru_order: VecDeque::new(),
             max_size: max_size.max(1), // At least 1
+            use_gpu: true,
+            on_load: None,
         }
     }
 
-    /// Retrieve a cached transcriber, or load and cache it if it isn't present yet.
-    pub fn get_or_create(&mut self, model_path: &Path) -> Result<Arc<Transcriber>, String> {
-        log::info!(
-            "[TRANSCRIPTION_DEBUG] get_or_create called with path: {:?}",
-            model_path
-        );
+    /// Switch GPU acceleration on or off. Loaded models keep the backend they were
+    /// created with, so they are dropped and reload on next use.
+    pub fn set_use_gpu(&mut self, use_gpu: bool) {
+        if self.use_gpu != use_gpu {
+            self.use_gpu = use_gpu;
+            self.clear();
+        }
+    }
 
-        // Check if the model file exists
-        if !model_path.exists() {
-            let error = format!("Model file does not exist: {:?}", model_path);
-            log::error!("[TRANSCRIPTION_DEBUG] {}", error);
-            return Err(error);
+    /// Register a callback run with each model after it's loaded
+    pub fn set_on_load(&mut self, on_load: impl Fn(&T) + Send + Sync + 'static) {
+        self.on_load = Some(Box::new(on_load));
+    }
+
+    /// Change the capacity, evicting least recently used models when it shrinks
+    pub fn set_capacity(&mut self, max_size: usize) {
+        self.max_size = max_size.max(1);
+        while self.map.len() > self.max_size {
+            self.evict_lru();
         }
+    }
+
+    /// Whether the model at `model_path` is already loaded
+    pub fn contains(&self, model_path: &Path) -> bool {
+        self.map.contains_key(model_path.to_string_lossy().as_ref())
+    }
 
+    /// Retrieve a cached model, or load it with `load` and cache it if it isn't present yet.
+    fn get_or_load(
+        &mut self,
+        model_path: &Path,
+        load: impl FnOnce(&Path) -> Result<T, String>,
+    ) -> Result<Arc<T>, String> {
         // We store the path as a string key – this is fine because the path is
         // produced by the app itself and therefore always valid Unicode.
         let key = model_path.to_string_lossy().to_string();
@@ -87,13 +121,16 @@ impl TranscriberCache {
         );
         let start = std::time::Instant::now();
 
-        let transcriber = match Transcriber::new(model_path) {
+        let transcriber = match load(model_path) {
             Ok(t) => {
                 let elapsed = start.elapsed();
                 log::info!(
                     "[TRANSCRIPTION_DEBUG] Model loaded successfully in {:?}",
                     elapsed
                 );
+                if let Some(on_load) = &self.on_load {
+                    on_load(&t);
+                }
                 Arc::new(t)
             }
             Err(e) => {
@@ -172,8 +209,7 @@ impl TranscriberCache {
         }
     }
 
-    /// Manually clear the cache (e.g. to free RAM or after a model upgrade).
-    #[cfg(test)]
+    /// Manually clear the cache (e.g. to free RAM or after the models were moved).
     pub fn clear(&mut self) {
         self.map.clear();
         self.lru_order.clear();
@@ -191,3 +227,116 @@ impl TranscriberCache {
         self.max_size
     }
 }
+
+impl TranscriberCache {
+    /// Retrieve a cached transcriber, or load and cache it if it isn't present yet.
+    pub fn get_or_create(&mut self, model_path: &Path) -> Result<Arc<Transcriber>, String> {
+        log::info!(
+            "[TRANSCRIPTION_DEBUG] get_or_create called with path: {:?}",
+            model_path
+        );
+
+        // Check if the model file exists
+        if !model_path.exists() {
+            let error = format!("Model file does not exist: {:?}", model_path);
+            log::error!("[TRANSCRIPTION_DEBUG] {}", error);
+            return Err(error);
+        }
+
+        let use_gpu = self.use_gpu;
+        self.get_or_load(model_path, |path| Transcriber::new(path, use_gpu))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Load a fake model, recording which paths were actually loaded
+    fn load_into(loads: &mut Vec<String>) -> impl FnOnce(&Path) -> Result<String, String> + '_ {
+        |path| {
+            let key = path.to_string_lossy().to_string();
+            loads.push(key.clone());
+            Ok(key)
+        }
+    }
+
+    #[test]
+    fn test_lru_eviction_at_capacity() {
+        let capacity = 2;
+        let mut cache = ModelCache::<String>::with_capacity(capacity);
+        let mut loads = Vec::new();
+        let (a, b, c) = (
+            Path::new("/models/a.bin"),
+            Path::new("/models/b.bin"),
+            Path::new("/models/c.bin"),
+        );
+
+        cache.get_or_load(a, load_into(&mut loads)).unwrap();
+        cache.get_or_load(b, load_into(&mut loads)).unwrap();
+        // Touch `a` so `b` becomes the least recently used
+        cache.get_or_load(a, load_into(&mut loads)).unwrap();
+        assert_eq!(loads.len(), 2);
+
+        // Loading capacity + 1 distinct models evicts `b`
+        cache.get_or_load(c, load_into(&mut loads)).unwrap();
+        assert_eq!(cache.size(), capacity);
+        cache.get_or_load(a, load_into(&mut loads)).unwrap();
+        assert_eq!(
+            loads,
+            vec!["/models/a.bin", "/models/b.bin", "/models/c.bin"]
+        );
+
+        cache.get_or_load(b, load_into(&mut loads)).unwrap();
+        assert_eq!(loads.last().map(String::as_str), Some("/models/b.bin"));
+        assert_eq!(loads.len(), 4);
+    }
+
+    #[test]
+    fn test_toggling_gpu_reloads_models() {
+        let mut cache = ModelCache::<String>::with_capacity(2);
+        let mut loads = Vec::new();
+        let loaded = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = loaded.clone();
+        cache.set_on_load(move |_| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        });
+
+        let a = Path::new("/models/a.bin");
+        cache.get_or_load(a, load_into(&mut loads)).unwrap();
+        cache.set_use_gpu(true);
+        cache.get_or_load(a, load_into(&mut loads)).unwrap();
+        assert_eq!(loads.len(), 1);
+
+        // Falling back to CPU drops the GPU-backed model
+        cache.set_use_gpu(false);
+        assert_eq!(cache.size(), 0);
+        cache.get_or_load(a, load_into(&mut loads)).unwrap();
+        assert_eq!(loads.len(), 2);
+        assert_eq!(loaded.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_shrinking_capacity_evicts() {
+        let mut cache = ModelCache::<String>::with_capacity(3);
+        let mut loads = Vec::new();
+        for name in ["/models/a.bin", "/models/b.bin", "/models/c.bin"] {
+            cache
+                .get_or_load(Path::new(name), load_into(&mut loads))
+                .unwrap();
+        }
+
+        cache.set_capacity(1);
+        assert_eq!(cache.size(), 1);
+        assert_eq!(cache.capacity(), 1);
+
+        // Only the most recently used model survives
+        cache
+            .get_or_load(Path::new("/models/c.bin"), load_into(&mut loads))
+            .unwrap();
+        assert_eq!(loads.len(), 3);
+
+        cache.set_capacity(0);
+        assert_eq!(cache.capacity(), 1);
+    }
+}
@@ -0,0 +1,254 @@
+use std::ops::Range;
+
+/// Default for the `chunk_long_recordings_after_secs` setting
+pub const DEFAULT_CHUNK_THRESHOLD_SECS: u32 = 300;
+
+/// Length of each window; matches the 30 s Whisper decodes at a time
+pub const CHUNK_SECS: u32 = 30;
+
+/// How much consecutive windows share, so a word cut at a boundary is heard whole
+pub const CHUNK_OVERLAP_SECS: u32 = 2;
+
+/// Longest run of words looked at when matching one window's end to the next one's start
+const MAX_OVERLAP_WORDS: usize = 16;
+
+/// A decoded segment, timed from the start of the whole recording
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct TimedSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// How a long recording is split into overlapping windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkPlan {
+    /// Recordings up to this long are transcribed in one pass
+    pub threshold_secs: u32,
+    pub chunk_secs: u32,
+    pub overlap_secs: u32,
+}
+
+impl ChunkPlan {
+    /// Chunking above `threshold_secs`; `None` when the threshold is 0 (off)
+    pub fn new(threshold_secs: u32) -> Option<Self> {
+        (threshold_secs > 0).then_some(Self {
+            threshold_secs,
+            chunk_secs: CHUNK_SECS,
+            overlap_secs: CHUNK_OVERLAP_SECS,
+        })
+    }
+
+    /// Sample ranges to transcribe in order. A recording at or below the
+    /// threshold is a single range; otherwise each window starts `overlap_secs`
+    /// before the previous one ended, and the last one ends with the recording.
+    pub fn ranges(&self, total_samples: usize, sample_rate: u32) -> Vec<Range<usize>> {
+        let rate = sample_rate as usize;
+        let chunk = if total_samples <= self.threshold_secs as usize * rate {
+            // One window covering everything
+            total_samples.max(1)
+        } else {
+            self.chunk_secs.max(1) as usize * rate
+        };
+        // Each window has to move forward, whatever the overlap is set to
+        let overlap = (self.overlap_secs as usize * rate).min(chunk / 2);
+
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        loop {
+            let end = (start + chunk).min(total_samples);
+            ranges.push(start..end);
+            if end == total_samples {
+                return ranges;
+            }
+            start = end - overlap;
+        }
+    }
+}
+
+/// Lowercased word without surrounding punctuation, for comparing the same
+/// word decoded in two windows
+fn comparable(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// How many words at the start of `next` repeat the end of `previous`: the
+/// longest run (up to [`MAX_OVERLAP_WORDS`]) where they match word for word
+pub fn overlap_word_count(previous: &[&str], next: &[&str]) -> usize {
+    let longest = previous.len().min(next.len()).min(MAX_OVERLAP_WORDS);
+    (1..=longest)
+        .rev()
+        .find(|&count| {
+            previous[previous.len() - count..]
+                .iter()
+                .zip(&next[..count])
+                .all(|(a, b)| comparable(a) == comparable(b))
+        })
+        .unwrap_or(0)
+}
+
+/// Append the next window's segments, dropping the words it repeats from the
+/// end of what's already stitched
+pub fn stitch_segments(stitched: &mut Vec<TimedSegment>, next: Vec<TimedSegment>) {
+    let tail: Vec<&str> = stitched
+        .iter()
+        .flat_map(|segment| segment.text.split_whitespace())
+        .collect();
+    let tail = &tail[tail.len().saturating_sub(MAX_OVERLAP_WORDS)..];
+    let head: Vec<&str> = next
+        .iter()
+        .flat_map(|segment| segment.text.split_whitespace())
+        .take(MAX_OVERLAP_WORDS)
+        .collect();
+    let mut to_drop = overlap_word_count(tail, &head);
+
+    let mut kept = Vec::with_capacity(next.len());
+    for mut segment in next {
+        if to_drop > 0 {
+            let words: Vec<&str> = segment.text.split_whitespace().collect();
+            if words.len() <= to_drop {
+                to_drop -= words.len();
+                continue;
+            }
+            segment.text = format!(" {}", words[to_drop..].join(" "));
+            to_drop = 0;
+        }
+        if !segment.text.trim().is_empty() {
+            kept.push(segment);
+        }
+    }
+    stitched.extend(kept);
+}
+
+/// The transcript text of stitched segments
+pub fn segments_text(segments: &[TimedSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| segment.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16_000;
+
+    fn segment(start_ms: u64, end_ms: u64, text: &str) -> TimedSegment {
+        TimedSegment {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_short_recordings_are_not_chunked() {
+        let plan = ChunkPlan::new(300).unwrap();
+        let five_minutes = 300 * RATE as usize;
+        let ranges = plan.ranges(five_minutes, RATE);
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0], 0..five_minutes);
+        assert_eq!(plan.ranges(0, RATE).len(), 1);
+        assert_eq!(ChunkPlan::new(0), None);
+    }
+
+    #[test]
+    fn test_chunk_boundaries_overlap() {
+        let plan = ChunkPlan::new(60).unwrap();
+        let secs = |s: usize| s * RATE as usize;
+
+        // 90 s: windows start every 28 s and the last one ends with the recording
+        let ranges = plan.ranges(secs(90), RATE);
+        assert_eq!(
+            ranges,
+            vec![
+                0..secs(30),
+                secs(28)..secs(58),
+                secs(56)..secs(86),
+                secs(84)..secs(90),
+            ]
+        );
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].end - pair[1].start, secs(2));
+        }
+
+        // Landing exactly on a window end doesn't leave an empty tail
+        let exact = plan.ranges(secs(86), RATE);
+        assert_eq!(exact.last(), Some(&(secs(56)..secs(86))));
+    }
+
+    #[test]
+    fn test_overlap_larger_than_chunk_still_advances() {
+        let plan = ChunkPlan {
+            threshold_secs: 1,
+            chunk_secs: 4,
+            overlap_secs: 10,
+        };
+        let ranges = plan.ranges(10 * RATE as usize, RATE);
+        assert!(ranges.windows(2).all(|pair| pair[1].start > pair[0].start));
+        assert_eq!(ranges.last().unwrap().end, 10 * RATE as usize);
+    }
+
+    #[test]
+    fn test_overlap_word_count() {
+        let previous = ["the", "quick", "brown", "fox"];
+        assert_eq!(
+            overlap_word_count(&previous, &["brown", "Fox,", "jumps"]),
+            2
+        );
+        assert_eq!(overlap_word_count(&previous, &["jumps", "over"]), 0);
+        assert_eq!(overlap_word_count(&previous, &[]), 0);
+        // The longest match wins over a shorter one
+        assert_eq!(
+            overlap_word_count(&["a", "b", "a", "b"], &["a", "b", "a", "b", "c"]),
+            4
+        );
+    }
+
+    #[test]
+    fn test_stitching_drops_repeated_words() {
+        let mut stitched = vec![
+            segment(0, 14_000, " The lecture today covers"),
+            segment(14_000, 30_000, " the history of Rome."),
+        ];
+        // The second window starts 2 s early and hears "of Rome." again
+        stitch_segments(
+            &mut stitched,
+            vec![
+                segment(28_000, 29_500, " of rome"),
+                segment(29_500, 40_000, " Its founding was legendary."),
+            ],
+        );
+
+        assert_eq!(
+            segments_text(&stitched),
+            "The lecture today covers the history of Rome. Its founding was legendary."
+        );
+        // Timestamps of the kept segments are untouched
+        assert_eq!(stitched.last().unwrap().start_ms, 29_500);
+        assert_eq!(stitched.len(), 3);
+    }
+
+    #[test]
+    fn test_stitching_trims_a_partly_repeated_segment() {
+        let mut stitched = vec![segment(0, 30_000, " and then we went home")];
+        stitch_segments(
+            &mut stitched,
+            vec![segment(28_000, 33_000, " went home and slept")],
+        );
+        assert_eq!(stitched[1].start_ms, 28_000);
+        assert_eq!(segments_text(&stitched), "and then we went home and slept");
+    }
+
+    #[test]
+    fn test_stitching_without_overlap_keeps_everything() {
+        let mut stitched = Vec::new();
+        stitch_segments(&mut stitched, vec![segment(0, 1_000, " Hello")]);
+        stitch_segments(&mut stitched, vec![segment(28_000, 30_000, " world")]);
+        assert_eq!(segments_text(&stitched), "Hello world");
+    }
+}
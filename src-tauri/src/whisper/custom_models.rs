@@ -0,0 +1,164 @@
+use std::io::Read;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::manager::ModelInfo;
+
+/// Registry of imported models, kept next to them in the models directory so it
+/// moves along when the directory is relocated
+pub const CUSTOM_MODELS_FILE: &str = "custom_models.json";
+
+/// Every imported model's name starts with this, so it can't clash with the catalog
+pub const CUSTOM_MODEL_PREFIX: &str = "custom-";
+
+/// "ggml" as whisper.cpp reads it: a little-endian u32 at the start of the file
+const GGML_MAGIC: u32 = 0x6767_6d6c;
+/// Magic and the eleven i32 hyperparameters that follow it
+const GGML_HEADER_LEN: usize = 48;
+/// Vocabulary size of the English-only checkpoints; multilingual ones are larger
+const ENGLISH_VOCAB: i32 = 51_864;
+
+/// The hyperparameters at the start of a whisper.cpp ggml model
+#[derive(Debug, Clone, PartialEq)]
+pub struct GgmlHeader {
+    pub n_vocab: i32,
+    pub n_audio_layer: i32,
+    pub n_text_layer: i32,
+    pub n_mels: i32,
+    pub ftype: i32,
+}
+
+impl GgmlHeader {
+    pub fn is_multilingual(&self) -> bool {
+        self.n_vocab > ENGLISH_VOCAB
+    }
+}
+
+/// Check that `bytes` start like a whisper.cpp model whisper could load
+pub fn parse_ggml_header(bytes: &[u8]) -> Result<GgmlHeader, String> {
+    if bytes.starts_with(b"GGUF") {
+        return Err(
+            "This is a GGUF file. Whisper needs a ggml model (ggml-*.bin from whisper.cpp)"
+                .to_string(),
+        );
+    }
+    if bytes.len() < GGML_HEADER_LEN {
+        return Err("File is too short to be a Whisper model".to_string());
+    }
+
+    let field = |index: usize| {
+        let start = index * 4;
+        i32::from_le_bytes([
+            bytes[start],
+            bytes[start + 1],
+            bytes[start + 2],
+            bytes[start + 3],
+        ])
+    };
+    if field(0) as u32 != GGML_MAGIC {
+        return Err("Not a ggml Whisper model: the file header is missing".to_string());
+    }
+
+    // n_vocab, n_audio_ctx, n_audio_state, n_audio_head, n_audio_layer, n_text_ctx,
+    // n_text_state, n_text_head, n_text_layer, n_mels, ftype
+    let header = GgmlHeader {
+        n_vocab: field(1),
+        n_audio_layer: field(5),
+        n_text_layer: field(9),
+        n_mels: field(10),
+        ftype: field(11),
+    };
+    if header.n_vocab < ENGLISH_VOCAB || !(1..=32).contains(&header.n_audio_layer) {
+        return Err("Not a Whisper model: its vocabulary or layer count don't match".to_string());
+    }
+    if !(1..=32).contains(&header.n_text_layer) || !matches!(header.n_mels, 80 | 128) {
+        return Err("Not a Whisper model: its decoder or audio features don't match".to_string());
+    }
+    Ok(header)
+}
+
+/// Read and check the header of the model file at `path`
+pub fn read_ggml_header(path: &Path) -> Result<GgmlHeader, String> {
+    let mut file =
+        std::fs::File::open(path).map_err(|e| format!("Cannot open model file: {}", e))?;
+    let mut bytes = Vec::with_capacity(GGML_HEADER_LEN);
+    file.by_ref()
+        .take(GGML_HEADER_LEN as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Cannot read model file: {}", e))?;
+    parse_ggml_header(&bytes)
+}
+
+/// An imported model as stored in [`CUSTOM_MODELS_FILE`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomModelEntry {
+    pub name: String,
+    pub display_name: String,
+    pub size: u64,
+    pub multilingual: bool,
+}
+
+impl CustomModelEntry {
+    pub fn to_model_info(&self) -> ModelInfo {
+        ModelInfo {
+            name: self.name.clone(),
+            display_name: self.display_name.clone(),
+            size: self.size,
+            // Nothing to download or checksum: the file came from the user
+            url: String::new(),
+            sha256: String::new(),
+            downloaded: false,
+            speed_score: 5,
+            accuracy_score: 5,
+            recommended: false,
+        }
+    }
+}
+
+/// Registry name for a model imported as `display_name`, e.g. "custom-my-finetune".
+/// English-only models get the catalog's ".en" suffix.
+pub fn custom_model_name(display_name: &str, multilingual: bool) -> Result<String, String> {
+    let mut slug = String::new();
+    for c in display_name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        return Err("Give the model a name with at least one letter or digit".to_string());
+    }
+
+    let suffix = if multilingual { "" } else { ".en" };
+    Ok(format!("{}{}{}", CUSTOM_MODEL_PREFIX, slug, suffix))
+}
+
+pub fn is_custom_model_name(name: &str) -> bool {
+    name.starts_with(CUSTOM_MODEL_PREFIX)
+}
+
+pub fn load_custom_models(models_dir: &Path) -> Vec<CustomModelEntry> {
+    let Ok(json) = std::fs::read(models_dir.join(CUSTOM_MODELS_FILE)) else {
+        return Vec::new();
+    };
+    match serde_json::from_slice::<Vec<CustomModelEntry>>(&json) {
+        Ok(entries) => entries
+            .into_iter()
+            .filter(|entry| is_custom_model_name(&entry.name))
+            .collect(),
+        Err(e) => {
+            log::warn!("Ignoring unreadable {}: {}", CUSTOM_MODELS_FILE, e);
+            Vec::new()
+        }
+    }
+}
+
+pub fn save_custom_models(models_dir: &Path, entries: &[CustomModelEntry]) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(entries)
+        .map_err(|e| format!("Failed to serialize custom models: {}", e))?;
+    std::fs::write(models_dir.join(CUSTOM_MODELS_FILE), json)
+        .map_err(|e| format!("Failed to save custom models: {}", e))
+}
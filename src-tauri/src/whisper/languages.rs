@@ -428,7 +428,6 @@ pub fn is_language_supported(code: &str) -> bool {
 }
 
 /// Get the language name for a given code
-#[cfg(test)]
 pub fn get_language_name(code: &str) -> Option<&'static str> {
     SUPPORTED_LANGUAGES.get(code).map(|lang| lang.name)
 }
@@ -456,6 +455,30 @@ pub fn validate_language(code: Option<&str>) -> &'static str {
     }
 }
 
+/// Normalize a per-engine language override before it is saved.
+/// Empty clears the override, "auto" is kept (no language hint for that
+/// engine) and any other code goes through [`validate_language`].
+pub fn validate_language_override(code: Option<&str>) -> Option<String> {
+    match code.map(str::trim) {
+        None | Some("") => None,
+        Some("auto") => Some("auto".to_string()),
+        Some(code) => Some(validate_language(Some(code)).to_string()),
+    }
+}
+
+/// The language an engine transcribes with: its override when one is set,
+/// otherwise the global `language`. `None` means no hint ("auto" or empty).
+pub fn resolve_engine_language(global: &str, engine_override: Option<&str>) -> Option<String> {
+    let language = match engine_override {
+        Some(code) if !code.is_empty() => code,
+        _ => global,
+    };
+    match language {
+        "" | "auto" => None,
+        code => Some(code.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -483,4 +506,39 @@ mod tests {
         assert_eq!(get_language_name("zh"), Some("Chinese"));
         assert_eq!(get_language_name("invalid"), None);
     }
+
+    #[test]
+    fn test_validate_language_override() {
+        assert_eq!(validate_language_override(None), None);
+        assert_eq!(validate_language_override(Some("")), None);
+        assert_eq!(
+            validate_language_override(Some("auto")),
+            Some("auto".to_string())
+        );
+        assert_eq!(
+            validate_language_override(Some("de")),
+            Some("de".to_string())
+        );
+        assert_eq!(
+            validate_language_override(Some("invalid")),
+            Some("en".to_string())
+        );
+    }
+
+    #[test]
+    fn test_engine_language_precedence() {
+        // An override beats the global language
+        assert_eq!(
+            resolve_engine_language("en", Some("de")),
+            Some("de".to_string())
+        );
+        assert_eq!(resolve_engine_language("en", Some("auto")), None);
+        // Unset or empty overrides fall back to the global
+        assert_eq!(resolve_engine_language("fr", None), Some("fr".to_string()));
+        assert_eq!(
+            resolve_engine_language("fr", Some("")),
+            Some("fr".to_string())
+        );
+        assert_eq!(resolve_engine_language("", None), None);
+    }
 }
@@ -1,15 +1,25 @@
+use super::custom_models::{
+    is_custom_model_name, load_custom_models, save_custom_models, CustomModelEntry,
+};
 use futures_util::StreamExt;
 use reqwest;
 use sha1::Sha1;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::fs;
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
 
+/// Upper bound for the `download_connections` setting
+pub const MAX_DOWNLOAD_CONNECTIONS: u8 = 8;
+
+/// Start of the error a range request gets when the server sends the whole file
+/// instead; the download then falls back to a single connection
+const RANGE_IGNORED_ERROR: &str = "Server ignored range request";
+
 // Type-safe size validation
 #[derive(Debug, Clone, Copy)]
 #[allow(dead_code)] // Field accessed through as_bytes() in tests
@@ -166,10 +176,43 @@ impl WhisperManager {
         // Removed: large-v3-turbo-q8_0 to simplify model list
 
         let mut manager = Self { models_dir, models };
+        manager.register_custom_models();
         manager.check_downloaded_models();
         manager
     }
 
+    /// Add the models imported into the current models directory, dropping any
+    /// registered from a previous one
+    fn register_custom_models(&mut self) {
+        self.models.retain(|name, _| !is_custom_model_name(name));
+        for entry in load_custom_models(&self.models_dir) {
+            self.models
+                .entry(entry.name.clone())
+                .or_insert_with(|| entry.to_model_info());
+        }
+    }
+
+    /// Register a model file already copied to `<models_dir>/<name>.bin` and record
+    /// it in the custom models registry so it survives a restart
+    pub fn add_custom_model(&mut self, entry: CustomModelEntry) -> Result<(), String> {
+        if !is_custom_model_name(&entry.name) || self.models.contains_key(&entry.name) {
+            return Err(format!("A model named '{}' already exists", entry.name));
+        }
+        let path = self.models_dir.join(format!("{}.bin", entry.name));
+        if !path.is_file() {
+            return Err(format!("Model file not found: {}", path.display()));
+        }
+
+        let mut entries = load_custom_models(&self.models_dir);
+        entries.push(entry.clone());
+        save_custom_models(&self.models_dir, &entries)?;
+
+        let mut info = entry.to_model_info();
+        info.downloaded = true;
+        self.models.insert(entry.name, info);
+        Ok(())
+    }
+
     fn check_downloaded_models(&mut self) {
         log::info!(
             "[check_downloaded_models] Checking models directory: {:?}",
@@ -234,6 +277,7 @@ impl WhisperManager {
     pub async fn download_model(
         &self,
         model_name: &str,
+        connections: usize,
         cancel_flag: Option<Arc<AtomicBool>>,
         progress_callback: impl Fn(u64, u64),
     ) -> Result<(), String> {
@@ -245,12 +289,25 @@ impl WhisperManager {
             &model_info,
             &output_path,
             &self.models_dir,
+            connections,
             cancel_flag,
             progress_callback,
         )
         .await
     }
 
+    /// Directory downloaded models are stored in
+    pub fn models_dir(&self) -> &Path {
+        &self.models_dir
+    }
+
+    /// Point the manager at a relocated models directory and rescan it
+    pub fn set_models_dir(&mut self, models_dir: PathBuf) {
+        self.models_dir = models_dir;
+        self.register_custom_models();
+        self.check_downloaded_models();
+    }
+
     /// Get model info needed for download (doesn't hold lock during download)
     pub fn get_model_info(&self, model_name: &str) -> Result<(ModelInfo, PathBuf), String> {
         // Use centralized validation
@@ -263,6 +320,13 @@ impl WhisperManager {
             model_name
         ))?;
 
+        if model.url.is_empty() {
+            return Err(format!(
+                "Model '{}' was imported from a file and can't be downloaded",
+                model_name
+            ));
+        }
+
         // Validate model size before downloading
         let _ = model.validated_size()?;
 
@@ -271,11 +335,13 @@ impl WhisperManager {
         Ok((model.clone(), output_path))
     }
 
-    /// Download a model file (should be called without holding the manager lock)
+    /// Download a model file (should be called without holding the manager lock).
+    /// `connections` > 1 fetches byte ranges in parallel when the server allows it.
     pub async fn download_model_file(
         model_info: &ModelInfo,
         output_path: &PathBuf,
         models_dir: &PathBuf,
+        connections: usize,
         cancel_flag: Option<Arc<AtomicBool>>,
         progress_callback: impl Fn(u64, u64),
     ) -> Result<(), String> {
@@ -329,14 +395,115 @@ impl WhisperManager {
 
         // Download the model
         let client = reqwest::Client::new();
-        let response = client
-            .get(&model_info.url)
-            .send()
+
+        // Split across several connections only when the server supports byte ranges
+        let range_size = if connections > 1 {
+            let size = Self::range_download_size(&client, &model_info.url).await;
+            if size.is_none() {
+                log::info!("Server doesn't support range requests, using a single connection");
+            }
+            size
+        } else {
+            None
+        };
+
+        let parallel = if let Some(total_size) = range_size {
+            Self::validate_download_size(model_info, total_size)?;
+            log::info!(
+                "[download_model] Downloading {} bytes over {} connections",
+                total_size,
+                connections
+            );
+            match Self::download_parts(
+                &client,
+                &model_info.url,
+                output_path,
+                total_size,
+                connections,
+                cancel_flag.as_ref(),
+                &progress_callback,
+            )
             .await
-            .map_err(|e| e.to_string())?;
+            {
+                Ok(()) => Some((total_size, total_size)),
+                // Advertised ranges but answered 200 with the whole file
+                Err(e) if e.starts_with(RANGE_IGNORED_ERROR) => {
+                    log::warn!("{}; falling back to a single connection", e);
+                    None
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            None
+        };
+
+        let (downloaded, total_size) = match parallel {
+            Some(sizes) => sizes,
+            None => {
+                Self::download_single_stream(
+                    &client,
+                    model_info,
+                    output_path,
+                    cancel_flag.as_ref(),
+                    &progress_callback,
+                )
+                .await?
+            }
+        };
 
-        let total_size = response.content_length().unwrap_or(model_info.size);
+        // Also sync the parent directory to ensure directory entry is visible
+        if let Some(parent) = output_path.parent() {
+            if let Ok(dir) = std::fs::File::open(parent) {
+                let _ = dir.sync_all();
+            }
+        }
+
+        // Ensure final 100% progress is sent
+        if downloaded < total_size {
+            progress_callback(total_size, total_size);
+        }
+
+        // Verify checksum if available
+        if !model_info.sha256.is_empty() {
+            log::info!("Verifying model checksum...");
+            match model_info.sha256.len() {
+                40 | 64 => {
+                    Self::verify_model_checksum(&output_path, &model_info.sha256).await?;
+                }
+                _ => {
+                    log::warn!(
+                        "Invalid checksum length for {}. Skipping verification.",
+                        model_info.name
+                    );
+                    log::warn!(
+                        "Expected SHA1 (40 chars) or SHA256 (64 chars), got {} chars.",
+                        model_info.sha256.len()
+                    );
+                }
+            }
+        } else {
+            log::warn!(
+                "No checksum available for {}. Skipping verification.",
+                model_info.name
+            );
+            log::warn!("File integrity cannot be guaranteed without checksum verification.");
+        }
+
+        // Log what files are in the directory after download
+        log::info!("[download_model] Download complete. Listing models directory:");
+        if let Ok(entries) = std::fs::read_dir(models_dir) {
+            for entry in entries.flatten() {
+                if let Some(name) = entry.file_name().to_str() {
+                    log::info!("[download_model]   Found file: {}", name);
+                }
+            }
+        }
 
+        Ok(())
+    }
+
+    /// Check the size the server reports against the registry and our limits
+    fn validate_download_size(model_info: &ModelInfo, total_size: u64) -> Result<(), String> {
         // Validate reported size matches expected size (allow 10% variance for compression)
         let size_variance =
             (total_size as f64 - model_info.size as f64).abs() / model_info.size as f64;
@@ -351,6 +518,25 @@ impl WhisperManager {
 
         // Validate the total size is within our limits
         let _ = ModelSize::new(total_size)?;
+        Ok(())
+    }
+
+    /// Download over one connection; returns (downloaded, total) bytes
+    async fn download_single_stream(
+        client: &reqwest::Client,
+        model_info: &ModelInfo,
+        output_path: &Path,
+        cancel_flag: Option<&Arc<AtomicBool>>,
+        progress_callback: &impl Fn(u64, u64),
+    ) -> Result<(u64, u64), String> {
+        let response = client
+            .get(&model_info.url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let total_size = response.content_length().unwrap_or(model_info.size);
+        Self::validate_download_size(model_info, total_size)?;
 
         let mut file = fs::File::create(&output_path)
             .await
@@ -363,7 +549,7 @@ impl WhisperManager {
 
         while let Some(chunk) = stream.next().await {
             // Check for cancellation
-            if let Some(ref flag) = cancel_flag {
+            if let Some(flag) = cancel_flag {
                 if flag.load(Ordering::Relaxed) {
                     log::info!("Download cancelled by user for model: {}", model_info.name);
                     // Clean up partial download
@@ -407,120 +593,193 @@ impl WhisperManager {
             .map_err(|e| format!("Failed to sync file to disk: {}", e))?;
         drop(file);
 
-        // Also sync the parent directory to ensure directory entry is visible
-        if let Some(parent) = output_path.parent() {
-            if let Ok(dir) = std::fs::File::open(parent) {
-                let _ = dir.sync_all();
-            }
-        }
+        Ok((downloaded, total_size))
+    }
 
-        // Ensure final 100% progress is sent
-        if downloaded < total_size {
-            progress_callback(total_size, total_size);
+    /// Size of the file at `url` when the server accepts byte range requests
+    async fn range_download_size(client: &reqwest::Client, url: &str) -> Option<u64> {
+        let response = client
+            .head(url)
+            .send()
+            .await
+            .ok()?
+            .error_for_status()
+            .ok()?;
+        let headers = response.headers();
+
+        let accepts_ranges = headers
+            .get(reqwest::header::ACCEPT_RANGES)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.eq_ignore_ascii_case("bytes"));
+        if !accepts_ranges {
+            return None;
         }
 
-        // Verify checksum if available
-        if !model_info.sha256.is_empty() {
-            log::info!("Verifying model checksum...");
-            match model_info.sha256.len() {
-                40 => {
-                    // SHA1 checksum (legacy from whisper.cpp)
-                    Self::verify_sha1_checksum(&output_path, &model_info.sha256).await?;
-                }
-                64 => {
-                    // SHA256 checksum (preferred)
-                    Self::verify_sha256_checksum(&output_path, &model_info.sha256).await?;
-                }
-                _ => {
-                    log::warn!(
-                        "Invalid checksum length for {}. Skipping verification.",
-                        model_info.name
-                    );
-                    log::warn!(
-                        "Expected SHA1 (40 chars) or SHA256 (64 chars), got {} chars.",
-                        model_info.sha256.len()
-                    );
-                }
-            }
-        } else {
-            log::warn!(
-                "No checksum available for {}. Skipping verification.",
-                model_info.name
-            );
-            log::warn!("File integrity cannot be guaranteed without checksum verification.");
-        }
+        // Read the header directly; reqwest reports 0 for the empty body of a HEAD response
+        headers
+            .get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|&len| len > 0)
+    }
 
-        // Log what files are in the directory after download
-        log::info!("[download_model] Download complete. Listing models directory:");
-        if let Ok(entries) = std::fs::read_dir(models_dir) {
-            for entry in entries.flatten() {
-                if let Some(name) = entry.file_name().to_str() {
-                    log::info!("[download_model]   Found file: {}", name);
-                }
+    /// Fetch the byte ranges concurrently into part files, then join them into
+    /// `output_path`. Any failure or cancellation aborts the remaining parts.
+    async fn download_parts(
+        client: &reqwest::Client,
+        url: &str,
+        output_path: &Path,
+        total_size: u64,
+        connections: usize,
+        cancel_flag: Option<&Arc<AtomicBool>>,
+        progress_callback: &impl Fn(u64, u64),
+    ) -> Result<(), String> {
+        let ranges = chunk_ranges(total_size, connections);
+        let part_paths: Vec<PathBuf> = (0..ranges.len())
+            .map(|index| output_path.with_extension(format!("bin.part{}", index)))
+            .collect();
+
+        // Aggregate progress across parts, still reported every 1%
+        let downloaded = AtomicU64::new(0);
+        let last_reported = AtomicU64::new(0);
+        let update_threshold = total_size / 100;
+        let report = |len: u64| {
+            let now = downloaded.fetch_add(len, Ordering::Relaxed) + len;
+            if now - last_reported.load(Ordering::Relaxed) >= update_threshold || now == total_size
+            {
+                last_reported.store(now, Ordering::Relaxed);
+                progress_callback(now, total_size);
             }
-        }
+        };
 
-        Ok(())
+        let result = futures_util::future::try_join_all(ranges.iter().zip(&part_paths).map(
+            |(&range, part_path)| {
+                Self::download_range(client, url, range, part_path, cancel_flag, &report)
+            },
+        ))
+        .await;
+        let result = match result {
+            Ok(_) => concat_parts(&part_paths, output_path).await,
+            Err(e) => Err(e),
+        };
+
+        for part_path in &part_paths {
+            let _ = fs::remove_file(part_path).await;
+        }
+        if result.is_err() {
+            let _ = fs::remove_file(output_path).await;
+        }
+        result
     }
 
-    /// Verify the SHA256 checksum of a downloaded file
-    async fn verify_sha256_checksum(
-        file_path: &PathBuf,
-        expected_checksum: &str,
+    /// Download the inclusive byte range `(start, end)` into `part_path`
+    async fn download_range(
+        client: &reqwest::Client,
+        url: &str,
+        (start, end): (u64, u64),
+        part_path: &Path,
+        cancel_flag: Option<&Arc<AtomicBool>>,
+        report: &impl Fn(u64),
     ) -> Result<(), String> {
-        // Open the file
-        let mut file = fs::File::open(file_path)
+        let response = client
+            .get(url)
+            .header(reqwest::header::RANGE, format!("bytes={}-{}", start, end))
+            .send()
             .await
-            .map_err(|e| format!("Failed to open file for checksum verification: {}", e))?;
+            .map_err(|e| e.to_string())?;
+        if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return Err(format!(
+                "{} for bytes {}-{} (status {})",
+                RANGE_IGNORED_ERROR,
+                start,
+                end,
+                response.status()
+            ));
+        }
 
-        // Read file in chunks and calculate hash
-        let mut hasher = Sha256::new();
-        let mut buffer = vec![0; 8192]; // 8KB buffer
+        let expected = end - start + 1;
+        let mut file = fs::File::create(part_path)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut written: u64 = 0;
+        let mut stream = response.bytes_stream();
 
-        loop {
-            let bytes_read = file
-                .read(&mut buffer)
-                .await
-                .map_err(|e| format!("Failed to read file for checksum: {}", e))?;
+        while let Some(chunk) = stream.next().await {
+            if cancel_flag.is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+                log::info!(
+                    "Download cancelled by user, aborting range {}-{}",
+                    start,
+                    end
+                );
+                return Err("Download cancelled by user".to_string());
+            }
 
-            if bytes_read == 0 {
-                break;
+            let chunk = chunk.map_err(|e| e.to_string())?;
+            written += chunk.len() as u64;
+            if written > expected {
+                return Err(format!(
+                    "Download exceeded expected size for bytes {}-{}: got {} bytes",
+                    start, end, written
+                ));
             }
 
-            hasher.update(&buffer[..bytes_read]);
+            file.write_all(&chunk).await.map_err(|e| e.to_string())?;
+            report(chunk.len() as u64);
         }
 
-        // Get the final hash
-        let result = hasher.finalize();
-        let calculated_checksum = format!("{:x}", result);
+        if written != expected {
+            return Err(format!(
+                "Incomplete download for bytes {}-{}: got {} of {} bytes",
+                start, end, written, expected
+            ));
+        }
+        file.flush().await.map_err(|e| e.to_string())
+    }
+
+    /// Verify a model file against its registry checksum: SHA256 (64 hex chars), or
+    /// SHA1 (40 chars) as published by whisper.cpp. A mismatching file is deleted so
+    /// it can't be loaded and the model can be downloaded again.
+    pub async fn verify_model_checksum(
+        file_path: &Path,
+        expected_checksum: &str,
+    ) -> Result<(), String> {
+        let (algorithm, calculated_checksum) = match expected_checksum.len() {
+            40 => ("SHA1", Self::file_digest::<Sha1>(file_path).await?),
+            64 => ("SHA256", Self::file_digest::<Sha256>(file_path).await?),
+            len => {
+                return Err(format!(
+                    "Invalid checksum length: expected SHA1 (40 chars) or SHA256 (64 chars), got {} chars",
+                    len
+                ))
+            }
+        };
 
         // Compare checksums
-        if calculated_checksum != expected_checksum {
+        if !calculated_checksum.eq_ignore_ascii_case(expected_checksum) {
             // Delete the corrupted file
-            let _ = fs::remove_file(file_path).await;
+            let removal = match fs::remove_file(file_path).await {
+                Ok(()) => "File has been deleted.".to_string(),
+                Err(e) => format!("Failed to delete the file: {}", e),
+            };
             return Err(format!(
-                "Checksum verification failed!\nExpected: {}\nCalculated: {}\nFile has been deleted.",
-                expected_checksum,
-                calculated_checksum
+                "{} checksum verification failed!\nExpected: {}\nCalculated: {}\n{}",
+                algorithm, expected_checksum, calculated_checksum, removal
             ));
         }
 
-        log::info!("SHA256 checksum verified successfully!");
+        log::info!("{} checksum verified successfully!", algorithm);
         Ok(())
     }
 
-    /// Verify the SHA1 checksum of a downloaded file (legacy support for whisper.cpp models)
-    async fn verify_sha1_checksum(
-        file_path: &PathBuf,
-        expected_checksum: &str,
-    ) -> Result<(), String> {
+    /// Hex digest of a file, read in chunks so multi-GB models aren't loaded into memory
+    async fn file_digest<D: Digest>(file_path: &Path) -> Result<String, String> {
         // Open the file
         let mut file = fs::File::open(file_path)
             .await
             .map_err(|e| format!("Failed to open file for checksum verification: {}", e))?;
 
         // Read file in chunks and calculate hash
-        let mut hasher = Sha1::new();
+        let mut hasher = D::new();
         let mut buffer = vec![0; 8192]; // 8KB buffer
 
         loop {
@@ -536,23 +795,7 @@ impl WhisperManager {
             hasher.update(&buffer[..bytes_read]);
         }
 
-        // Get the final hash
-        let result = hasher.finalize();
-        let calculated_checksum = format!("{:x}", result);
-
-        // Compare checksums
-        if calculated_checksum != expected_checksum {
-            // Delete the corrupted file
-            let _ = fs::remove_file(file_path).await;
-            return Err(format!(
-                "SHA1 checksum verification failed!\nExpected: {}\nCalculated: {}\nFile has been deleted.",
-                expected_checksum,
-                calculated_checksum
-            ));
-        }
-
-        log::info!("SHA1 checksum verified successfully!");
-        Ok(())
+        Ok(hex::encode(hasher.finalize()))
     }
 
     pub fn get_model_path(&self, model_name: &str) -> Option<PathBuf> {
@@ -640,6 +883,14 @@ impl WhisperManager {
         }
         std::fs::remove_file(&path).map_err(|e| e.to_string())?;
 
+        // An imported model has nothing to download again, so it leaves the list
+        if is_custom_model_name(model_name) {
+            let mut entries = load_custom_models(&self.models_dir);
+            entries.retain(|entry| entry.name != model_name);
+            save_custom_models(&self.models_dir, &entries)?;
+            self.models.remove(model_name);
+        }
+
         // update internal flags
         if let Some(info) = self.models.get_mut(model_name) {
             info.downloaded = false;
@@ -773,3 +1024,47 @@ impl WhisperManager {
         manager
     }
 }
+
+/// Split `total` bytes into at most `parts` contiguous, inclusive byte ranges for
+/// HTTP Range requests. Earlier ranges take the remainder, so sizes differ by at most 1.
+pub(crate) fn chunk_ranges(total: u64, parts: usize) -> Vec<(u64, u64)> {
+    if total == 0 {
+        return Vec::new();
+    }
+
+    let parts = (parts.max(1) as u64).min(total);
+    let base = total / parts;
+    let remainder = total % parts;
+    let mut start = 0;
+
+    (0..parts)
+        .map(|index| {
+            let len = base + u64::from(index < remainder);
+            let range = (start, start + len - 1);
+            start += len;
+            range
+        })
+        .collect()
+}
+
+/// Join downloaded parts into `output_path` in the order given
+pub(crate) async fn concat_parts(part_paths: &[PathBuf], output_path: &Path) -> Result<(), String> {
+    let mut output = fs::File::create(output_path)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for part_path in part_paths {
+        let mut part = fs::File::open(part_path)
+            .await
+            .map_err(|e| format!("Failed to open download part {:?}: {}", part_path, e))?;
+        tokio::io::copy(&mut part, &mut output)
+            .await
+            .map_err(|e| format!("Failed to assemble download: {}", e))?;
+    }
+
+    output.flush().await.map_err(|e| e.to_string())?;
+    output
+        .sync_all()
+        .await
+        .map_err(|e| format!("Failed to sync file to disk: {}", e))
+}
@@ -1,4 +1,7 @@
 pub mod cache;
+pub mod chunking;
+pub mod custom_models;
 pub mod languages;
 pub mod manager;
+pub mod pool;
 pub mod transcriber;
@@ -0,0 +1,173 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::Notify;
+
+/// Default for the `max_concurrent_uploads` setting
+pub const DEFAULT_CONCURRENT_UPLOADS: usize = 2;
+
+/// Upper bound for the `max_concurrent_uploads` setting; every slot is a full
+/// whisper run competing for the same CPU cores
+pub const MAX_CONCURRENT_UPLOADS: usize = 4;
+
+#[derive(Default)]
+struct PoolState {
+    /// Upload transcriptions currently holding a slot
+    running: usize,
+    /// Recording transcriptions in flight; uploads don't start while any are
+    interactive: usize,
+}
+
+#[derive(Default)]
+struct PoolInner {
+    state: Mutex<PoolState>,
+    changed: Notify,
+}
+
+impl PoolInner {
+    fn update(&self, change: impl FnOnce(&mut PoolState)) {
+        if let Ok(mut state) = self.state.lock() {
+            change(&mut state);
+        }
+        self.changed.notify_waiters();
+    }
+}
+
+/// Admission for transcriptions outside the recording flow (file and byte uploads).
+///
+/// A loaded [`Transcriber`](super::transcriber::Transcriber) creates a fresh
+/// whisper state for every run, so concurrent runs can share one cached model
+/// without locking; the pool only bounds how many uploads run at once. The
+/// recording flow never waits for a slot, and while it is transcribing no new
+/// upload starts, so a dictation isn't stuck behind a queue of files.
+#[derive(Clone, Default)]
+pub struct TranscriptionPool(Arc<PoolInner>);
+
+/// A running upload; dropping it frees the slot
+pub struct UploadSlot(Arc<PoolInner>);
+
+/// Marks a recording transcription in flight; dropping it lets uploads start again
+pub struct InteractiveTranscription(Arc<PoolInner>);
+
+impl TranscriptionPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait for an upload slot, with at most `limit` uploads running at once
+    pub async fn acquire_upload(&self, limit: usize) -> UploadSlot {
+        let limit = limit.clamp(1, MAX_CONCURRENT_UPLOADS);
+        loop {
+            // Registered before checking so a slot freed in between isn't missed
+            let notified = self.0.changed.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if let Ok(mut state) = self.0.state.lock() {
+                if state.interactive == 0 && state.running < limit {
+                    state.running += 1;
+                    return UploadSlot(self.0.clone());
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// Give a recording transcription priority over uploads until the guard drops
+    pub fn interactive(&self) -> InteractiveTranscription {
+        self.0.update(|state| state.interactive += 1);
+        InteractiveTranscription(self.0.clone())
+    }
+
+    /// Uploads currently running
+    pub fn running_uploads(&self) -> usize {
+        self.0.state.lock().map(|state| state.running).unwrap_or(0)
+    }
+}
+
+impl Drop for UploadSlot {
+    fn drop(&mut self) {
+        self.0
+            .update(|state| state.running = state.running.saturating_sub(1));
+    }
+}
+
+impl Drop for InteractiveTranscription {
+    fn drop(&mut self) {
+        self.0
+            .update(|state| state.interactive = state.interactive.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::sync::Barrier;
+    use tokio::time::timeout;
+
+    #[tokio::test]
+    async fn test_two_uploads_run_concurrently() {
+        let pool = TranscriptionPool::new();
+        // Each upload waits at the barrier while holding its slot, so both
+        // only complete if they run at the same time
+        let barrier = Arc::new(Barrier::new(2));
+
+        let uploads: Vec<_> = (0..2)
+            .map(|i| {
+                let pool = pool.clone();
+                let barrier = barrier.clone();
+                tokio::spawn(async move {
+                    let _slot = pool.acquire_upload(2).await;
+                    barrier.wait().await;
+                    format!("upload {} done", i)
+                })
+            })
+            .collect();
+
+        for (i, upload) in uploads.into_iter().enumerate() {
+            let result = timeout(Duration::from_secs(5), upload)
+                .await
+                .expect("uploads should not deadlock")
+                .unwrap();
+            assert_eq!(result, format!("upload {} done", i));
+        }
+        assert_eq!(pool.running_uploads(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_upload_limit_queues_extra_uploads() {
+        let pool = TranscriptionPool::new();
+        let first = pool.acquire_upload(1).await;
+
+        let queued = pool.clone();
+        let second = tokio::spawn(async move { queued.acquire_upload(1).await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!second.is_finished());
+
+        drop(first);
+        let _second = timeout(Duration::from_secs(5), second)
+            .await
+            .expect("queued upload should start once a slot frees")
+            .unwrap();
+        assert_eq!(pool.running_uploads(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recording_transcription_holds_back_new_uploads() {
+        let pool = TranscriptionPool::new();
+        let interactive = pool.interactive();
+
+        let queued = pool.clone();
+        let upload = tokio::spawn(async move { queued.acquire_upload(2).await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!upload.is_finished());
+        assert_eq!(pool.running_uploads(), 0);
+
+        drop(interactive);
+        let _slot = timeout(Duration::from_secs(5), upload)
+            .await
+            .expect("upload should start after the recording transcription")
+            .unwrap();
+        assert_eq!(pool.running_uploads(), 1);
+    }
+}
@@ -1,20 +1,130 @@
+use serde::Serialize;
+use std::ops::Range;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use whisper_rs::{
-    convert_integer_to_float_audio, convert_stereo_to_mono_audio, FullParams, SamplingStrategy,
-    WhisperContext, WhisperContextParameters,
+    convert_integer_to_float_audio, convert_stereo_to_mono_audio, get_lang_str, FullParams,
+    SamplingStrategy, SegmentCallbackData, WhisperContext, WhisperContextParameters,
 };
 
+use super::chunking::{segments_text, stitch_segments, ChunkPlan, TimedSegment};
 use crate::utils::logger::*;
 #[cfg(debug_assertions)]
 use crate::utils::system_monitor;
 
+/// GPU backends whisper-rs is built with for this platform (see Cargo.toml)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AccelerationCapabilities {
+    pub metal: bool,
+    pub vulkan: bool,
+    pub cuda: bool,
+    pub coreml: bool,
+}
+
+impl AccelerationCapabilities {
+    pub fn current() -> Self {
+        Self {
+            metal: cfg!(target_os = "macos"),
+            vulkan: cfg!(target_os = "windows"),
+            cuda: false,
+            coreml: false,
+        }
+    }
+
+    /// Whether any backend that `use_gpu` can switch on is compiled in
+    pub fn gpu_available(&self) -> bool {
+        self.metal || self.vulkan || self.cuda
+    }
+}
+
+/// Whether to try a GPU context before the CPU one
+pub fn should_attempt_gpu(use_gpu: bool, capabilities: &AccelerationCapabilities) -> bool {
+    use_gpu && capabilities.gpu_available()
+}
+
+/// Text of a transcription plus how sure Whisper was about it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptionOutput {
+    pub text: String,
+    /// Mean probability of the text tokens (0-1); `None` when nothing was decoded
+    pub confidence: Option<f32>,
+    /// What language Whisper thinks was spoken, regardless of the language setting
+    pub detected_language: Option<DetectedLanguage>,
+    /// Decoded segments, timed from the start of the recording
+    pub segments: Vec<TimedSegment>,
+}
+
+/// Whisper's language detection result
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DetectedLanguage {
+    /// ISO 639-1 code, e.g. "de"
+    pub code: String,
+    /// Probability Whisper assigned to `code` (0-1)
+    pub probability: f32,
+}
+
+/// Mean of the token probabilities, or `None` when there are none
+pub fn average_confidence(probabilities: &[f32]) -> Option<f32> {
+    if probabilities.is_empty() {
+        return None;
+    }
+    Some(probabilities.iter().sum::<f32>() / probabilities.len() as f32)
+}
+
+/// Index and probability of the most likely language in Whisper's per-language probabilities
+pub fn most_likely_language(probabilities: &[f32]) -> Option<(usize, f32)> {
+    probabilities
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, p)| p.is_finite())
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Text Whisper has decoded so far in a run, shared with the caller so it
+/// survives the run being cancelled part-way
+#[derive(Debug, Clone, Default)]
+pub struct PartialTranscript(Arc<Mutex<String>>);
+
+impl PartialTranscript {
+    /// Append one decoded segment
+    pub fn push_segment(&self, segment: &str) {
+        let segment = segment.trim();
+        if segment.is_empty() {
+            return;
+        }
+        if let Ok(mut text) = self.0.lock() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(segment);
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.0.lock().map(|text| text.clone()).unwrap_or_default()
+    }
+}
+
+/// The text to offer after a cancelled run: only when `keep_partial` is on and
+/// something was decoded before the cancel
+pub fn partial_to_keep(keep_partial: bool, partial: &PartialTranscript) -> Option<String> {
+    if !keep_partial {
+        return None;
+    }
+    Some(partial.text()).filter(|text| !text.is_empty())
+}
+
 pub struct Transcriber {
     context: WhisperContext,
+    /// Why GPU initialization failed, when the model fell back to CPU
+    gpu_fallback: Option<String>,
 }
 
 impl Transcriber {
-    pub fn new(model_path: &Path) -> Result<Self, String> {
+    pub fn new(model_path: &Path, use_gpu: bool) -> Result<Self, String> {
         let init_start = Instant::now();
         let model_path_str = model_path
             .to_str()
@@ -47,10 +157,20 @@ impl Transcriber {
         let mut ctx_params = WhisperContextParameters::default();
         #[allow(unused_assignments)] // gpu_used is assigned in multiple conditional blocks
         let mut gpu_used = false;
+        #[allow(unused_mut)] // Only assigned on platforms with a GPU backend
+        let mut gpu_fallback: Option<String> = None;
+
+        let attempt_gpu = should_attempt_gpu(use_gpu, &AccelerationCapabilities::current());
+        if !attempt_gpu {
+            ctx_params.use_gpu(false);
+            if !use_gpu {
+                log::info!("🎮 GPU acceleration disabled in settings, using CPU");
+            }
+        }
 
         // macOS: Try Metal first, fallback to CPU if it fails
         #[cfg(target_os = "macos")]
-        {
+        if attempt_gpu {
             ctx_params.use_gpu(true);
             let metal_start = Instant::now();
 
@@ -94,9 +214,13 @@ impl Transcriber {
                         &[("backend", "Metal"), ("model_path", model_path_str)],
                     );
 
-                    return Ok(Self { context: ctx });
+                    return Ok(Self {
+                        context: ctx,
+                        gpu_fallback: None,
+                    });
                 }
                 Err(gpu_err) => {
+                    gpu_fallback = Some(gpu_err.to_string());
                     log_with_context(
                         log::Level::Info,
                         "🎮 METAL_FALLBACK",
@@ -119,7 +243,7 @@ impl Transcriber {
 
         // Windows: Try Vulkan GPU first, fallback to CPU if it fails (just like macOS!)
         #[cfg(target_os = "windows")]
-        {
+        if attempt_gpu {
             ctx_params.use_gpu(true);
             let vulkan_start = Instant::now();
 
@@ -171,9 +295,13 @@ impl Transcriber {
                         &[("backend", "Vulkan"), ("model_path", model_path_str)],
                     );
 
-                    return Ok(Self { context: ctx });
+                    return Ok(Self {
+                        context: ctx,
+                        gpu_fallback: None,
+                    });
                 }
                 Err(gpu_err) => {
+                    gpu_fallback = Some(gpu_err.to_string());
                     log_with_context(
                         log::Level::Info,
                         "🎮 VULKAN_FALLBACK",
@@ -267,7 +395,15 @@ impl Transcriber {
             ],
         );
 
-        Ok(Self { context: ctx })
+        Ok(Self {
+            context: ctx,
+            gpu_fallback,
+        })
+    }
+
+    /// Reason GPU initialization failed, if this model is running on CPU as a fallback
+    pub fn gpu_fallback(&self) -> Option<&str> {
+        self.gpu_fallback.as_deref()
     }
 
     pub fn transcribe_with_translation(
@@ -277,6 +413,7 @@ impl Transcriber {
         translate: bool,
     ) -> Result<String, String> {
         self.transcribe_with_cancellation(audio_path, language, translate, || false)
+            .map(|output| output.text)
     }
 
     pub fn transcribe_with_cancellation<F>(
@@ -285,7 +422,60 @@ impl Transcriber {
         language: Option<&str>,
         translate: bool,
         should_cancel: F,
-    ) -> Result<String, String>
+    ) -> Result<TranscriptionOutput, String>
+    where
+        F: Fn() -> bool,
+    {
+        self.transcribe_inner(audio_path, language, translate, should_cancel, None, None)
+    }
+
+    /// Transcribe a file, splitting it into overlapping windows when `chunking`
+    /// is set and the recording is longer than its threshold
+    pub fn transcribe_with_chunking(
+        &self,
+        audio_path: &Path,
+        language: Option<&str>,
+        translate: bool,
+        chunking: Option<ChunkPlan>,
+    ) -> Result<TranscriptionOutput, String> {
+        self.transcribe_inner(audio_path, language, translate, || false, None, chunking)
+    }
+
+    /// Like [`Self::transcribe_with_cancellation`], but setting `cancel` also stops
+    /// Whisper mid-inference, and each segment is appended to `partial` as soon as
+    /// it's decoded so the text so far outlives a cancelled run
+    pub fn transcribe_with_partial(
+        &self,
+        audio_path: &Path,
+        language: Option<&str>,
+        translate: bool,
+        cancel: Arc<AtomicBool>,
+        partial: &PartialTranscript,
+        chunking: Option<ChunkPlan>,
+    ) -> Result<TranscriptionOutput, String> {
+        let should_cancel = {
+            let cancel = cancel.clone();
+            move || cancel.load(Ordering::SeqCst)
+        };
+        self.transcribe_inner(
+            audio_path,
+            language,
+            translate,
+            should_cancel,
+            Some((cancel, partial.clone())),
+            chunking,
+        )
+    }
+
+    fn transcribe_inner<F>(
+        &self,
+        audio_path: &Path,
+        language: Option<&str>,
+        translate: bool,
+        should_cancel: F,
+        live: Option<(Arc<AtomicBool>, PartialTranscript)>,
+        chunking: Option<ChunkPlan>,
+    ) -> Result<TranscriptionOutput, String>
     where
         F: Fn() -> bool,
     {
@@ -475,12 +665,6 @@ impl Transcriber {
             resampled_audio.len() as f32 / 16_000_f32
         );
 
-        // Create transcription parameters - use BeamSearch for better accuracy
-        let mut params = FullParams::new(SamplingStrategy::BeamSearch {
-            beam_size: 5,
-            patience: -1.0,
-        });
-
         // Set language - use centralized validation
         log::info!("[LANGUAGE] Received language: {:?}", language);
 
@@ -501,16 +685,12 @@ impl Transcriber {
 
         if let Some(lang) = final_lang {
             log::info!("[LANGUAGE] Final language set to: {}", lang);
-            params.set_language(Some(lang));
         }
 
-        // Set translate mode
         if translate {
             log::info!("[LANGUAGE] Translation mode enabled - will translate to English");
-            params.set_translate(true);
         } else {
             log::info!("[LANGUAGE] Transcription mode - will transcribe in original language");
-            params.set_translate(false);
         }
 
         // Use most cores but leave one free to keep UI responsive
@@ -518,42 +698,62 @@ impl Transcriber {
             .map(|n| n.get())
             .unwrap_or(4);
         let threads = std::cmp::max(1, hw.saturating_sub(1)) as i32; // e.g., 8 cores -> 7 threads
-        params.set_n_threads(threads);
         log::info!("[PERFORMANCE] Using {} threads for transcription", threads);
 
-        params.set_no_context(false); // Enable context for better word recognition
-        params.set_print_special(false);
-        params.set_print_progress(false);
-        params.set_print_realtime(false);
-        params.set_print_timestamps(false);
-
-        // Suppress blank outputs to avoid empty transcriptions
-        params.set_suppress_blank(true);
-
-        // Don't suppress non-speech tokens - they help with timing and context
-        params.set_suppress_nst(true);
-
-        // Adjust speech detection threshold
-        params.set_no_speech_thold(0.6); // Default value, as higher values can cause issues
-
-        // Quality thresholds - use more lenient values to avoid rejecting valid speech
-        // Default entropy threshold is 2.4, we'll keep it default to avoid over-filtering
-        params.set_entropy_thold(2.4); // Default value - prevents filtering out valid but uncertain speech
-
-        // Use default log probability threshold to avoid being too strict
-        params.set_logprob_thold(-1.0); // Default value - balanced probability requirements
-
-        // Set initial prompt to help with context
-        params.set_initial_prompt(""); // Empty prompt to avoid biasing the model
-
-        // Temperature settings - slight randomness helps avoid repetitive loops
-        params.set_temperature(0.2); // Small amount of randomness instead of deterministic
-        params.set_temperature_inc(0.2); // Increase by 0.2 on fallback (default)
-        params.set_max_initial_ts(1.0); // Limit initial timestamp search
-
-        // Limit segment length to prevent runaway hallucinations
-        params.set_max_len(0); // 0 means no limit
-        params.set_length_penalty(-1.0); // Default penalty
+        // Fresh parameters for every Whisper pass; long recordings run one per chunk
+        let full_params = || {
+            // Use BeamSearch for better accuracy
+            let mut params = FullParams::new(SamplingStrategy::BeamSearch {
+                beam_size: 5,
+                patience: -1.0,
+            });
+            params.set_language(final_lang);
+            params.set_translate(translate);
+            params.set_n_threads(threads);
+
+            params.set_no_context(false); // Enable context for better word recognition
+            params.set_print_special(false);
+            params.set_print_progress(false);
+            params.set_print_realtime(false);
+            params.set_print_timestamps(false);
+
+            // Suppress blank outputs to avoid empty transcriptions
+            params.set_suppress_blank(true);
+
+            // Don't suppress non-speech tokens - they help with timing and context
+            params.set_suppress_nst(true);
+
+            // Adjust speech detection threshold
+            params.set_no_speech_thold(0.6); // Default value, as higher values can cause issues
+
+            // Quality thresholds - use more lenient values to avoid rejecting valid speech
+            // Default entropy threshold is 2.4, we'll keep it default to avoid over-filtering
+            params.set_entropy_thold(2.4); // Default value - prevents filtering out valid but uncertain speech
+
+            // Use default log probability threshold to avoid being too strict
+            params.set_logprob_thold(-1.0); // Default value - balanced probability requirements
+
+            // Set initial prompt to help with context
+            params.set_initial_prompt(""); // Empty prompt to avoid biasing the model
+
+            // Temperature settings - slight randomness helps avoid repetitive loops
+            params.set_temperature(0.2); // Small amount of randomness instead of deterministic
+            params.set_temperature_inc(0.2); // Increase by 0.2 on fallback (default)
+            params.set_max_initial_ts(1.0); // Limit initial timestamp search
+
+            // Limit segment length to prevent runaway hallucinations
+            params.set_max_len(0); // 0 means no limit
+            params.set_length_penalty(-1.0); // Default penalty
+
+            // Stop inference as soon as a cancel comes in, keeping segments as they're decoded
+            if let Some((cancel, partial)) = live.clone() {
+                params.set_abort_callback_safe(move || cancel.load(Ordering::SeqCst));
+                params.set_segment_callback_safe(move |segment: SegmentCallbackData| {
+                    partial.push_segment(&segment.text)
+                });
+            }
+            params
+        };
 
         // Run transcription
         log::info!("[TRANSCRIPTION_DEBUG] Creating Whisper state...");
@@ -576,96 +776,172 @@ impl Transcriber {
 
         log_audio_metrics("WHISPER_INPUT", 0.0, 0.0, duration_seconds, None);
 
-        let inference_start = Instant::now();
-        log_start("WHISPER_INFERENCE");
-        log_with_context(
-            log::Level::Debug,
-            "Starting Whisper inference",
-            &[
-                ("samples", &samples_count.to_string().as_str()),
-                (
-                    "duration_seconds",
-                    &format!("{:.2}", duration_seconds).as_str(),
-                ),
-                ("language", language.unwrap_or("auto")),
-                ("translate", &translate.to_string().as_str()),
-            ],
-        );
+        // Long recordings go through Whisper in overlapping windows, stitched back together
+        let ranges: Vec<Range<usize>> = match chunking {
+            Some(plan) => plan.ranges(samples_count, 16_000),
+            None => std::iter::once(0..samples_count).collect(),
+        };
+        if ranges.len() > 1 {
+            log::info!(
+                "[CHUNKING] {:.0}s recording split into {} chunks",
+                duration_seconds,
+                ranges.len()
+            );
+        }
 
-        match state.full(params, &resampled_audio) {
-            Ok(_) => {
-                let inference_time = inference_start.elapsed();
-                let inference_ms = inference_time.as_millis();
-
-                log_performance(
-                    "WHISPER_INFERENCE",
-                    inference_ms as u64,
-                    Some(&format!(
-                        "audio_duration={:.2}s, samples={}",
-                        duration_seconds, samples_count
-                    )),
-                );
+        let mut segments: Vec<TimedSegment> = Vec::new();
+        let mut token_probabilities = Vec::new();
+        let mut extraction_time_ms = 0;
+        let token_eot = self.context.token_eot();
+        for (chunk_index, range) in ranges.iter().enumerate() {
+            let chunk_audio = &resampled_audio[range.clone()];
+            let chunk_seconds = chunk_audio.len() as f32 / 16_000_f32;
 
-                log::info!(
-                    "✅ Whisper inference completed in {:.2}s",
-                    inference_time.as_secs_f32()
-                );
-            }
-            Err(e) => {
-                let error = format!("Whisper inference failed: {}", e);
-                log_failed("WHISPER_INFERENCE", &error);
-                log_with_context(
-                    log::Level::Debug,
-                    "Inference failed",
-                    &[
-                        ("samples", &samples_count.to_string().as_str()),
-                        (
-                            "duration_seconds",
-                            &format!("{:.2}", duration_seconds).as_str(),
-                        ),
-                        (
-                            "inference_time_ms",
-                            &inference_start.elapsed().as_millis().to_string().as_str(),
-                        ),
-                    ],
-                );
-                return Err(error);
-            }
-        }
+            let inference_start = Instant::now();
+            log_start("WHISPER_INFERENCE");
+            log_with_context(
+                log::Level::Debug,
+                "Starting Whisper inference",
+                &[
+                    ("samples", &chunk_audio.len().to_string().as_str()),
+                    (
+                        "duration_seconds",
+                        &format!("{:.2}", chunk_seconds).as_str(),
+                    ),
+                    (
+                        "chunk",
+                        &format!("{}/{}", chunk_index + 1, ranges.len()).as_str(),
+                    ),
+                    ("language", language.unwrap_or("auto")),
+                    ("translate", &translate.to_string().as_str()),
+                ],
+            );
 
-        // Get text
-        let text_extraction_start = Instant::now();
-        log::info!("[TRANSCRIPTION_DEBUG] Getting segments from Whisper output...");
-        let num_segments = state.full_n_segments().map_err(|e| {
-            let error = format!("Failed to get segments: {}", e);
-            log::error!("[TRANSCRIPTION_DEBUG] {}", error);
-            error
-        })?;
+            match state.full(full_params(), chunk_audio) {
+                // An aborted run may report success with only part of the audio decoded
+                _ if should_cancel() => {
+                    log::info!("[TRANSCRIPTION_DEBUG] Transcription cancelled during inference");
+                    return Err("Transcription cancelled".to_string());
+                }
+                Ok(_) => {
+                    let inference_time = inference_start.elapsed();
+                    let inference_ms = inference_time.as_millis();
 
-        log::info!(
-            "[TRANSCRIPTION_DEBUG] Transcription complete: {} segments",
-            num_segments
-        );
+                    log_performance(
+                        "WHISPER_INFERENCE",
+                        inference_ms as u64,
+                        Some(&format!(
+                            "audio_duration={:.2}s, samples={}",
+                            chunk_seconds,
+                            chunk_audio.len()
+                        )),
+                    );
 
-        let mut text = String::new();
-        for i in 0..num_segments {
-            let segment = state.full_get_segment_text(i).map_err(|e| {
-                let error = format!("Failed to get segment {}: {}", i, e);
+                    log::info!(
+                        "✅ Whisper inference completed in {:.2}s",
+                        inference_time.as_secs_f32()
+                    );
+                }
+                Err(e) => {
+                    let error = format!("Whisper inference failed: {}", e);
+                    log_failed("WHISPER_INFERENCE", &error);
+                    log_with_context(
+                        log::Level::Debug,
+                        "Inference failed",
+                        &[
+                            ("samples", &chunk_audio.len().to_string().as_str()),
+                            (
+                                "duration_seconds",
+                                &format!("{:.2}", chunk_seconds).as_str(),
+                            ),
+                            (
+                                "inference_time_ms",
+                                &inference_start.elapsed().as_millis().to_string().as_str(),
+                            ),
+                        ],
+                    );
+                    return Err(error);
+                }
+            }
+
+            // Get text
+            let text_extraction_start = Instant::now();
+            log::info!("[TRANSCRIPTION_DEBUG] Getting segments from Whisper output...");
+            let num_segments = state.full_n_segments().map_err(|e| {
+                let error = format!("Failed to get segments: {}", e);
                 log::error!("[TRANSCRIPTION_DEBUG] {}", error);
                 error
             })?;
-            log::info!("[TRANSCRIPTION_DEBUG] Segment {}: '{}'", i, segment);
-            text.push_str(&segment);
-            text.push(' ');
+
+            log::info!(
+                "[TRANSCRIPTION_DEBUG] Transcription complete: {} segments",
+                num_segments
+            );
+
+            // Whisper times segments in centiseconds from the start of the chunk
+            let chunk_offset_ms = (range.start / 16) as u64;
+            let segment_ms = |centiseconds: Result<i64, _>| {
+                chunk_offset_ms + centiseconds.unwrap_or(0).max(0) as u64 * 10
+            };
+            let mut chunk_segments = Vec::with_capacity(num_segments.max(0) as usize);
+            for i in 0..num_segments {
+                let segment = state.full_get_segment_text(i).map_err(|e| {
+                    let error = format!("Failed to get segment {}: {}", i, e);
+                    log::error!("[TRANSCRIPTION_DEBUG] {}", error);
+                    error
+                })?;
+                log::info!("[TRANSCRIPTION_DEBUG] Segment {}: '{}'", i, segment);
+
+                // Special tokens (timestamps, end of text) sort after EOT and say nothing about the words
+                let n_tokens = state.full_n_tokens(i).unwrap_or(0);
+                for j in 0..n_tokens {
+                    if let (Ok(id), Ok(p)) =
+                        (state.full_get_token_id(i, j), state.full_get_token_p(i, j))
+                    {
+                        if id < token_eot {
+                            token_probabilities.push(p);
+                        }
+                    }
+                }
+
+                chunk_segments.push(TimedSegment {
+                    start_ms: segment_ms(state.full_get_segment_t0(i)),
+                    end_ms: segment_ms(state.full_get_segment_t1(i)),
+                    text: segment,
+                });
+            }
+            stitch_segments(&mut segments, chunk_segments);
+            extraction_time_ms += text_extraction_start.elapsed().as_millis() as u64;
         }
 
-        let result = text.trim().to_string();
+        let num_segments = segments.len();
+        let result = segments_text(&segments);
+        let confidence = average_confidence(&token_probabilities);
+        log::info!(
+            "[TRANSCRIPTION_DEBUG] Average token confidence: {:?}",
+            confidence
+        );
+
+        // The language setting forces decoding, so ask Whisper separately what it heard.
+        // Best effort: a failed detection just leaves the field empty.
+        let detected_language = state
+            .pcm_to_mel(&resampled_audio[ranges[0].clone()], threads as usize)
+            .and_then(|_| state.lang_detect(0, threads as usize))
+            .ok()
+            .and_then(|probabilities| {
+                let (id, probability) = most_likely_language(&probabilities)?;
+                let code = get_lang_str(id as i32)?;
+                Some(DetectedLanguage {
+                    code: code.to_string(),
+                    probability,
+                })
+            });
+        log::info!("[LANGUAGE] Detected language: {:?}", detected_language);
 
         // Log text extraction performance
-        let extraction_time = text_extraction_start.elapsed().as_millis() as u64;
         log_performance(
             "TEXT_EXTRACTION",
-            extraction_time,
+            extraction_time_ms,
             Some(&format!(
                 "segments={}, chars={}",
                 num_segments,
@@ -731,7 +1007,12 @@ impl Transcriber {
             );
         }
 
-        Ok(result)
+        Ok(TranscriptionOutput {
+            text: result,
+            confidence,
+            detected_language,
+            segments,
+        })
     }
 }
 
@@ -816,6 +1097,58 @@ mod tests {
         assert_eq!(result, mono_audio);
     }
 
+    #[test]
+    fn test_average_confidence() {
+        assert_eq!(average_confidence(&[]), None);
+        let confidence = average_confidence(&[0.9, 0.5, 0.7]).unwrap();
+        assert!((confidence - 0.7).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_most_likely_language() {
+        assert_eq!(most_likely_language(&[]), None);
+        assert_eq!(
+            most_likely_language(&[0.1, f32::NAN, 0.7, 0.2]),
+            Some((2, 0.7))
+        );
+    }
+
+    #[test]
+    fn test_acceleration_capabilities() {
+        let capabilities = AccelerationCapabilities::current();
+        assert_eq!(capabilities.metal, cfg!(target_os = "macos"));
+        assert_eq!(capabilities.vulkan, cfg!(target_os = "windows"));
+
+        let json = serde_json::to_value(capabilities).unwrap();
+        for key in ["metal", "vulkan", "cuda", "coreml"] {
+            assert!(json[key].is_boolean(), "missing {}", key);
+        }
+    }
+
+    #[test]
+    fn test_gpu_attempt_decision() {
+        let metal = AccelerationCapabilities {
+            metal: true,
+            vulkan: false,
+            cuda: false,
+            coreml: false,
+        };
+        let cpu_only = AccelerationCapabilities {
+            metal: false,
+            ..metal
+        };
+        // CoreML only speeds up the encoder; it isn't a GPU context
+        let coreml_only = AccelerationCapabilities {
+            coreml: true,
+            ..cpu_only
+        };
+
+        assert!(should_attempt_gpu(true, &metal));
+        assert!(!should_attempt_gpu(false, &metal));
+        assert!(!should_attempt_gpu(true, &cpu_only));
+        assert!(!should_attempt_gpu(true, &coreml_only));
+    }
+
     #[test]
     fn test_convert_invalid_channels() {
         // Test that zero channels returns an error
@@ -823,4 +1156,23 @@ mod tests {
         let result = convert_multichannel_to_mono(&audio, 0);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_partial_transcript_survives_cancel() {
+        let partial = PartialTranscript::default();
+        // What the segment callback sees before ESC stops inference
+        let from_callback = partial.clone();
+        from_callback.push_segment(" Hello there,");
+        from_callback.push_segment("  ");
+        from_callback.push_segment(" this is the first part");
+
+        assert_eq!(partial.text(), "Hello there, this is the first part");
+        assert_eq!(
+            partial_to_keep(true, &partial),
+            Some("Hello there, this is the first part".to_string())
+        );
+        // Discarding stays the default
+        assert_eq!(partial_to_keep(false, &partial), None);
+        assert_eq!(partial_to_keep(true, &PartialTranscript::default()), None);
+    }
 }
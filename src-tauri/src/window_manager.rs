@@ -213,7 +213,10 @@ impl WindowManager {
         .inner_size(80.0, 40.0)
         .position(position_x, position_y)
         .visible(true) // Start visible
-        .focused(false); // Don't steal focus
+        .focused(false) // Don't steal focus
+        .initialization_script(&crate::utils::pill_theme::initialization_script(
+            &crate::utils::pill_theme::current_pill_theme(&self.app_handle),
+        ));
 
         // Disable context menu only in production builds
         #[cfg(not(debug_assertions))]
@@ -633,14 +636,11 @@ impl WindowManager {
             }
         }
 
-        // Reposition toast window (above pill)
+        // Reposition toast window (where toast_position puts it around the pill)
         if let Some(toast) = self.app_handle.get_webview_window("toast") {
-            let toast_width = 400.0;
-            let toast_height = 80.0;
-            let pill_width = 80.0;
-            let gap = 8.0;
-            let toast_x = pill_x + (pill_width - toast_width) / 2.0;
-            let toast_y = pill_y - toast_height - gap;
+            let (toast_x, toast_y) =
+                crate::utils::toast_layout::current_toast_position(&self.app_handle)
+                    .origin(pill_x, pill_y);
 
             if let Err(e) = toast.set_position(LogicalPosition::new(toast_x, toast_y)) {
                 log::warn!("Failed to reposition toast window: {}", e);
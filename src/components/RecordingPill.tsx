@@ -1,5 +1,6 @@
 import { AudioDots } from "@/components/AudioDots";
 import { useSetting } from "@/contexts/SettingsContext";
+import { usePillTheme } from "@/hooks/usePillTheme";
 import { useRecording } from "@/hooks/useRecording";
 import { listen } from "@tauri-apps/api/event";
 import { useEffect, useState } from "react";
@@ -7,10 +8,13 @@ import { motion } from "framer-motion";
 
 type PillState = "idle" | "listening" | "transcribing" | "formatting";
 
+const SHAPE_RADIUS = { pill: 9999, rounded: 10, square: 3 } as const;
+
 export function RecordingPill() {
   const recording = useRecording();
   const [audioLevel, setAudioLevel] = useState(0);
   const [isFormatting, setIsFormatting] = useState(false);
+  const theme = usePillTheme();
 
   // Setting: show pill indicator when idle (default: true)
   const showPillIndicator = useSetting("show_pill_indicator") ?? true;
@@ -92,6 +96,15 @@ export function RecordingPill() {
       {/* Solid black pill - grows when active */}
       <motion.div
         className="flex items-center justify-center rounded-full select-none bg-black shadow-lg"
+        style={
+          theme
+            ? {
+                backgroundColor: theme.color,
+                opacity: theme.opacity,
+                borderRadius: SHAPE_RADIUS[theme.shape],
+              }
+            : undefined
+        }
         animate={{
           // ~1.4x growth from idle to active
           paddingLeft: isActive ? 14 : 10,
@@ -292,27 +292,39 @@ export function GeneralSettings() {
                 />
               </div>
 
-              <div className="flex items-center justify-between">
-                <div className="space-y-0.5">
-                  <Label
-                    htmlFor="clipboard-retain"
-                    className="text-sm font-medium"
-                  >
-                    Keep Transcript in Clipboard
-                  </Label>
-                  <p className="text-xs text-muted-foreground">
-                    Leave transcribed text available for manual pastes
-                  </p>
-                </div>
-                <Switch
-                  id="clipboard-retain"
-                  checked={settings.keep_transcription_in_clipboard ?? false}
-                  onCheckedChange={async (checked) =>
-                    await updateSettings({
-                      keep_transcription_in_clipboard: checked,
-                    })
+              <div className="space-y-2">
+                <Label className="text-sm font-medium">Insertion Method</Label>
+                <ToggleGroup
+                  type="single"
+                  value={
+                    settings.insertion_method || "paste_preserve_clipboard"
                   }
-                />
+                  onValueChange={async (value) => {
+                    if (value) {
+                      await updateSettings({
+                        insertion_method: value as
+                          | "paste"
+                          | "type"
+                          | "paste_preserve_clipboard",
+                      });
+                    }
+                  }}
+                  variant="outline"
+                  className="w-full"
+                >
+                  <ToggleGroupItem value="paste_preserve_clipboard">
+                    Paste
+                  </ToggleGroupItem>
+                  <ToggleGroupItem value="paste">Paste &amp; Keep</ToggleGroupItem>
+                  <ToggleGroupItem value="type">Type</ToggleGroupItem>
+                </ToggleGroup>
+                <p className="text-xs text-muted-foreground">
+                  {settings.insertion_method === "type"
+                    ? "Type the text as keystrokes without touching the clipboard"
+                    : settings.insertion_method === "paste"
+                      ? "Paste and leave the transcript in the clipboard"
+                      : "Paste and restore your previous clipboard contents"}
+                </p>
               </div>
 
               <div className="flex items-center justify-between">
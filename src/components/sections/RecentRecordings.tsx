@@ -271,10 +271,27 @@ export function RecentRecordings({ history, hotkey = "Cmd+Shift+Space", onHistor
                               {item.text}
                             </p>
                             {item.model && (
-                              <div className="mt-2">
+                              <div className="mt-2 flex items-center gap-2">
                                 <span className="text-xs text-muted-foreground">
                                   {MODEL_DISPLAY_NAMES[item.model] || item.model}
                                 </span>
+                                {item.detected_language && (
+                                  <span
+                                    className={cn(
+                                      "text-xs uppercase",
+                                      item.language && item.language !== item.detected_language
+                                        ? "text-amber-600"
+                                        : "text-muted-foreground"
+                                    )}
+                                    title={`Detected ${item.detected_language}${
+                                      item.detected_language_probability !== undefined
+                                        ? ` (${Math.round(item.detected_language_probability * 100)}%)`
+                                        : ""
+                                    }`}
+                                  >
+                                    {item.detected_language}
+                                  </span>
+                                )}
                               </div>
                             )}
                           </div>
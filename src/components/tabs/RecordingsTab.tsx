@@ -20,7 +20,10 @@ export function RecordingsTab() {
         id: item.timestamp || Date.now().toString(),
         text: item.text,
         timestamp: new Date(item.timestamp),
-        model: item.model
+        model: item.model,
+        language: item.language,
+        detected_language: item.detected_language,
+        detected_language_probability: item.detected_language_probability
       }));
       setHistory(formattedHistory);
     } catch (error) {
@@ -36,13 +39,16 @@ export function RecordingsTab() {
         await loadHistory();
 
         // Listen for new transcriptions (append-only for efficiency)
-        registerEvent<{text: string; model: string; timestamp: string}>("transcription-added", (data) => {
+        registerEvent<{text: string; model: string; timestamp: string; language?: string; detected_language?: string; detected_language_probability?: number}>("transcription-added", (data) => {
           console.log("[RecordingsTab] New transcription added:", data.timestamp);
           const newItem: TranscriptionHistory = {
             id: data.timestamp,
             text: data.text,
             timestamp: new Date(data.timestamp),
-            model: data.model
+            model: data.model,
+            language: data.language,
+            detected_language: data.detected_language,
+            detected_language_probability: data.detected_language_probability
           };
           // Prepend new item to history (newest first)
           setHistory(prev => [newItem, ...prev]);
@@ -0,0 +1,29 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import type { ResolvedPillTheme } from '@/types';
+
+declare global {
+  interface Window {
+    // Injected by the backend when the pill/toast window is created
+    __PILL_THEME__?: ResolvedPillTheme;
+  }
+}
+
+export function usePillTheme(): ResolvedPillTheme | null {
+  const [theme, setTheme] = useState<ResolvedPillTheme | null>(
+    () => window.__PILL_THEME__ ?? null,
+  );
+
+  // Live updates when the pill_theme setting changes
+  useEffect(() => {
+    const unlisten = listen<ResolvedPillTheme>('pill-theme', (event) => {
+      setTheme(event.payload);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return theme;
+}
@@ -146,8 +146,9 @@ export class EventCoordinator {
       // Transcription events
       "transcription-complete": "pill",  // Pill window handles paste/clipboard/save
       "history-updated": "main",         // Main window reloads history
-      "audio-level": "pill",
+      "audio-level": "all",             // Pill while recording, main during the settings mic test
       "recording-state-changed": "all",
+      "cancelled-with-partial": "all",  // Text decoded before ESC, offered when keep_partial_on_cancel is on
       
       // Model events should go to all windows (for onboarding support)
       "download-progress": "all",
@@ -155,6 +156,12 @@ export class EventCoordinator {
       "model-verifying": "all",
       "download-cancelled": "all",
       "download-error": "all",
+      "benchmark-progress": "main",
+      "recognition-availability": "main", // Re-sent when models, engine or Soniox key change
+      "recovered-recording": "main",
+      "model-recommendation": "main", // Sent when the language changes
+      "license-status-detail": "main", // Trial and offline grace days, after each license check
+      "transcription-updated": "main", // A history entry was re-enhanced in place
       
       // Recording/transcription errors now use pill_toast() → FeedbackToast directly,
       // not as routed events. Only domain-specific main window errors are listed here.
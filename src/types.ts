@@ -60,6 +60,210 @@ export interface AppSettings {
   play_sound_on_recording?: boolean;
   // Pill indicator visibility
   show_pill_indicator?: boolean;
+  // Battery-aware model switching
+  battery_aware_model?: boolean;
+  battery_threshold_percent?: number;
+  // Debug: keep the pre-normalization capture next to the normalized audio
+  keep_raw_audio?: boolean;
+  // Optional global hotkey that advances to the next enhancement preset
+  preset_cycle_hotkey?: string;
+  // Transcribe the raw capture when ffmpeg normalization fails
+  normalize_fallback_to_raw?: boolean;
+  // Type AI enhancement output as it streams in instead of after it completes
+  ai_stream_insert?: boolean;
+  // Optional global hotkey that re-inserts the last transcription without AI enhancement
+  raw_insert_hotkey?: string;
+  // How transcriptions reach the focused app; paste_preserve_clipboard restores the clipboard
+  insertion_method?: 'paste' | 'type' | 'paste_preserve_clipboard';
+  // Per-application formatting applied to inserted text, matched against the frontmost app
+  app_insertion_rules?: AppInsertionRule[];
+  // Find-and-replace dictionary applied to every transcription before insertion
+  replacement_rules?: ReplacementRule[];
+  // Turn spoken punctuation ("comma", "new line") into characters
+  voice_commands_enabled?: boolean;
+  // Extra spoken commands; an empty value disables a built-in phrase
+  voice_command_map?: Record<string, string>;
+  // Convert spoken English numbers to digits ("twenty four" -> "24")
+  normalize_numbers?: boolean;
+  // Recent transcriptions listed in the tray (0 hides the submenu)
+  tray_recent_count?: number;
+  // Characters shown per recent transcription in the tray
+  tray_recent_label_length?: number;
+  // Global hotkeys that switch the current model
+  model_hotkeys?: ModelHotkey[];
+  // Max gap between push-to-talk taps that latches recording on (0 disables)
+  double_tap_window_ms?: number;
+  // Color, opacity and shape of the pill and toast windows
+  pill_theme?: PillTheme;
+  // Where transcriptions go: "insert" at the cursor, "clipboard_only" or "both"
+  output_mode?: OutputMode;
+  // Models kept loaded in memory (1-3); each whisper model holds 1-3GB of RAM
+  model_cache_capacity?: number;
+  // Parallel connections per model download (1-8); some servers throttle or reject many
+  download_connections?: number;
+  // Whisper GPU acceleration (Metal/Vulkan); falls back to CPU if it can't initialize
+  use_gpu?: boolean;
+  // Stream audio to Soniox's realtime API while recording instead of uploading afterwards
+  soniox_streaming?: boolean;
+  // Warn when Whisper's average token confidence is below this (0-1, 0 disables)
+  low_confidence_threshold?: number;
+  // Toast when Whisper is confident the speech wasn't in the selected language
+  warn_language_mismatch?: boolean;
+  // Daily pruning of old history and recordings
+  auto_cleanup?: AutoCleanupConfig;
+  // Encrypt history entry text at rest with the secure store key
+  encrypt_history?: boolean;
+  // Regexes whose matches are replaced with [REDACTED] in history (inserted text is untouched)
+  redaction_patterns?: string[];
+  // How stereo input becomes mono: "mix", "left" or "right"
+  stereo_downmix?: 'mix' | 'left' | 'right';
+  // EBU R128 loudness normalization before transcription: "off", "fast" (one pass) or "accurate" (two pass)
+  loudness_normalize?: 'off' | 'fast' | 'accurate';
+  // Integrated loudness target in LUFS
+  loudness_target_lufs?: number;
+  // Also normalize loudness of audio sent to Soniox, which is otherwise uploaded as recorded
+  loudness_normalize_cloud?: boolean;
+  // Recordings shorter than this are discarded; 0 keeps every recording
+  min_recording_seconds?: number;
+  // Trim leading and trailing silence from recordings kept in history
+  trim_silence_on_save?: boolean;
+  // Transcription attempts before giving up, including the first
+  transcription_max_retries?: number;
+  // Delay before the first retry; doubles on each further attempt
+  transcription_retry_delay_ms?: number;
+  // Retry Soniox transcriptions that fail with a network error or HTTP 5xx
+  retry_cloud_transcription?: boolean;
+  // Per-engine language overrides; null follows `language`, "auto" sends no hint
+  whisper_language?: string | null;
+  parakeet_language?: string | null;
+  soniox_language?: string | null;
+  // Play a sound when recording stops; independent of play_sound_on_recording
+  play_sound_on_recording_end?: boolean;
+  // Custom WAV files for the start/end sounds; null uses the system sound
+  start_sound_path?: string | null;
+  end_sound_path?: string | null;
+  // Skip recording sounds while a meeting app is frontmost
+  mute_sounds_in_calls?: boolean;
+  // File and byte uploads transcribed at the same time (1-4)
+  max_concurrent_uploads?: number;
+  // Offer the text decoded before a cancel via cancelled-with-partial
+  keep_partial_on_cancel?: boolean;
+  // Switch to the recording mode last used with the active microphone
+  remember_mode_per_device?: boolean;
+  // Microphone name -> recording mode last used with it
+  device_recording_modes?: Record<string, string>;
+  // Whisper transcribes recordings longer than this in overlapping 30s chunks (0 = never)
+  chunk_long_recordings_after_secs?: number;
+  // Models never picked automatically when the selected one isn't available
+  fallback_blocklist?: string[];
+  // Modifier held while stopping a recording to skip AI enhancement once, or "none"
+  skip_enhancement_modifier?: string;
+  // Send each dictation to the window it was started in, refocusing it if needed
+  lock_target_window?: boolean;
+  // Show a word count and duration toast after each transcription
+  show_result_summary?: boolean;
+  // Multiplier for how long pill toasts stay up
+  toast_duration_scale?: number;
+  // Where toasts appear relative to the pill: above, below, left or right
+  toast_position?: 'above' | 'below' | 'left' | 'right';
+  // Collapse a phrase Whisper hallucinates over and over into one copy
+  deduplicate_repeats?: boolean;
+  // What happens when no speech is detected; "keep_recording" keeps the audio and a placeholder history entry to re-run
+  on_empty_transcription?: 'toast' | 'silent' | 'keep_recording';
+  // Show the elapsed recording time in the tray tooltip
+  tray_recording_timer?: boolean;
+  // Ignore recording hotkey presses this soon after the previous one (0 disables)
+  hotkey_debounce_ms?: number;
+  // Loopback HTTP API for scripts (local_api)
+  local_api_enabled?: boolean;
+  local_api_port?: number;
+  // Optional global hotkey that copies the newest history entry to the clipboard
+  copy_last_hotkey?: string;
+  // Screenshot the screen when a recording starts and attach it to the history entry
+  attach_screenshot?: boolean;
+}
+
+export type OutputMode = 'insert' | 'clipboard_only' | 'both';
+
+export type PillShape = 'pill' | 'rounded' | 'square';
+
+export interface AutoCleanupConfig {
+  enabled: boolean;
+  // Entries and recordings older than this are removed (minimum 1)
+  retention_days: number;
+  applies_to: { history: boolean; recordings: boolean };
+}
+
+export interface PillTheme {
+  // 'system' follows the OS accent color, falling back to `color`
+  mode: 'default' | 'custom' | 'system';
+  // #rrggbb
+  color: string;
+  opacity: number;
+  shape: PillShape;
+}
+
+// Payload of the `pill-theme` event and `window.__PILL_THEME__`
+export interface ResolvedPillTheme {
+  color: string;
+  opacity: number;
+  shape: PillShape;
+}
+
+export interface ModelHotkey {
+  hotkey: string;
+  model_name: string;
+  // Empty means look the engine up from the model name
+  engine?: SpeechModelEngine | '';
+  start_recording?: boolean;
+}
+
+// Result of the check_hotkey_available command
+export type HotkeyAvailability =
+  | { status: 'available'; normalized: string }
+  | { status: 'invalid_format'; error: string }
+  | { status: 'in_use_by_voicetypr'; normalized: string; used_for: string }
+  | { status: 'registration_failed'; normalized: string; error: string };
+
+export interface ReplacementRule {
+  pattern: string;
+  replacement: string;
+  // Regex patterns may use $1-style groups in the replacement
+  is_regex?: boolean;
+  case_sensitive?: boolean;
+}
+
+export interface AppInsertionRule {
+  // Case-insensitive app name (exact or substring); "*" matches any app
+  app_match: string;
+  trailing_space?: boolean;
+  // true uppercases the first letter, false lowercases it, unset leaves it alone
+  capitalize_first?: boolean | null;
+  strip_newlines?: boolean;
+  // Overrides output_mode for this app; unset uses the global mode
+  output_mode?: OutputMode | null;
+}
+
+// Named snapshot of settings from save_profile; apply_profile switches to it
+export interface SettingsProfile {
+  name: string;
+  saved_at: string;
+  // Settings store keys (model, language, recording mode, AI...) and their values
+  settings: Record<string, unknown>;
+}
+
+// Result of import_settings; secrets_to_reenter lists secure store keys (e.g. ai_api_key_openai)
+export interface SettingsImportReport {
+  changed: string[];
+  skipped: string[];
+  secrets_to_reenter: string[];
+}
+
+export interface SpeakerSegment {
+  speaker_id: string;
+  text: string;
+  start_ms: number;
+  end_ms: number;
 }
 
 export interface TranscriptionHistory {
@@ -67,6 +271,60 @@ export interface TranscriptionHistory {
   text: string;
   timestamp: Date;
   model: string;
+  language?: string;
+  language_detected?: boolean;
+  raw_text?: string;
+  metrics?: TranscriptionMetrics;
+  // Whisper's average token confidence (0-1)
+  confidence?: number;
+  // What Whisper heard, even when the language is fixed
+  detected_language?: string;
+  detected_language_probability?: number;
+  speakers?: SpeakerSegment[];
+}
+
+// Payload of the transcription-metrics event
+export interface TranscriptionMetrics {
+  model_load_ms: number;
+  transcribe_ms: number;
+  audio_duration_ms: number | null;
+  // transcribe_ms / audio_duration_ms; below 1 is faster than real time
+  realtime_factor: number | null;
+}
+
+// Payload of the recording-error event; codes are documented on RecordingError in audio.rs
+export type RecordingErrorCode =
+  | 'mic_permission_denied'
+  | 'no_input_device'
+  | 'device_busy'
+  | 'recorder_failed'
+  | 'model_missing'
+  | 'license_required'
+  | 'normalization_failed'
+  | 'ffmpeg_missing'
+  | 'model_load_failed'
+  | 'remote_unreachable'
+  | 'transcription_failed';
+
+export interface RecordingErrorEvent {
+  code: RecordingErrorCode;
+  message: string;
+  // True when simply trying again may work
+  recoverable: boolean;
+}
+
+// Result of `retranscribe_all_failed`
+export interface RetranscribeSummary {
+  succeeded: number;
+  failed: number;
+  // Failed entries whose recording no longer exists
+  skipped: number;
+}
+
+// Payload of the `audio-devices-changed` event
+export interface AudioDevicesChangedEvent {
+  devices: string[];
+  default_device: string | null;
 }
 
 export interface LicenseStatus {
@@ -76,3 +334,51 @@ export interface LicenseStatus {
   license_key?: string;
   expires_at?: string;
 }
+
+// Payload of the `license-status-detail` event
+export interface LicenseStatusDetail {
+  state: LicenseStatus['status'];
+  trial_days_left: number | null;
+  offline_grace_days_remaining: number | null;
+  last_validated: string | null;
+}
+
+// From get_local_api_info; url is null while the local API is off
+export interface LocalApiInfo {
+  url: string | null;
+  token: string;
+}
+
+// From get_productivity_report; computed locally from history
+export interface ProductivityReport {
+  range_days: number | null;
+  total_dictations: number;
+  total_words: number;
+  words_by_day: Record<string, number>;
+  words_per_day: number;
+  avg_session_seconds: number | null;
+  most_used_model: string | null;
+  most_used_language: string | null;
+  by_hour: number[];
+  peak_hours: number[];
+}
+
+// One file's outcome from transcribe_batch
+export interface BatchTranscriptionResult {
+  path: string;
+  text: string | null;
+  error: string | null;
+}
+
+export interface AccelerationCapabilities {
+  metal: boolean;
+  vulkan: boolean;
+  cuda: boolean;
+  coreml: boolean;
+}
+
+export interface ModelsDirectoryUsage {
+  path: string;
+  models_bytes: number;
+  available_bytes: number | null;
+}
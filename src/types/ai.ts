@@ -5,6 +5,14 @@ export type EnhancementPreset = 'Default' | 'Prompts' | 'Email' | 'Commit';
 export interface EnhancementOptions {
   preset: EnhancementPreset;
   custom_vocabulary: string[];
+  custom_prompt?: string;
+}
+
+export interface NamedEnhancementPreset {
+  name: string;
+  prompt: string;
+  provider?: string | null;
+  model?: string | null;
 }
 
 export interface AISettings {